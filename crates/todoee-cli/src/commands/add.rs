@@ -1,17 +1,38 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, Utc};
 use std::fs;
+use std::io::{self, BufRead, IsTerminal, Read};
 use todoee_core::{
-    AiClient, Category, Config, EntityType, LocalDb, Operation, OperationType, Priority, Todo,
+    AiClient, Category, Config, DateInput, EntityType, LocalDb, MAX_REMIND_BEFORE_MINUTES,
+    Operation, OperationType, Priority, Todo, TodoeeError, dates, is_icon, short_id,
 };
 use uuid::Uuid;
 
+use super::config::prompt;
+use super::{ids, output};
+
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     description: Vec<String>,
     use_ai: bool,
     category: Option<String>,
     priority: Option<i32>,
     reminder: Option<String>,
+    remind_before: Option<String>,
+    no_nag: bool,
+    icon: Option<String>,
+    yes: bool,
+    dry_run: bool,
+    stdin: bool,
 ) -> Result<()> {
+    if stdin {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .context("Failed to read tasks from stdin")?;
+        return run_lines(&text, category, priority, dry_run).await;
+    }
+
     // Join description parts into a single string
     let description = description.join(" ");
 
@@ -30,6 +51,17 @@ pub async fn run(
         );
     }
 
+    if let Some(ref icon) = icon
+        && !is_icon(icon)
+    {
+        anyhow::bail!("Invalid icon '{}': use a single emoji or symbol", icon);
+    }
+
+    let remind_before = match remind_before {
+        Some(input) => parse_remind_before(&input)?,
+        None => None,
+    };
+
     // Load config and open local database
     let config = Config::load().context("Failed to load configuration")?;
     let db_path = config.local_db_path()?;
@@ -45,47 +77,105 @@ pub async fn run(
     let db = LocalDb::new(&db_path).await?;
     db.run_migrations().await?;
 
+    let date_input = config.display.date_input();
+
     // Create todo based on whether AI is enabled and a model is configured
+    let mut ai_parsed = false;
+    let mut retry_ai = false;
     let mut todo = if !use_ai || config.ai.model.is_none() {
-        // Manual mode: create task directly from input
-        Todo::new(description.clone(), None)
+        // Manual mode: parse dates locally, no network needed
+        parse_offline(&description, &date_input)
     } else {
         // AI mode: parse natural language with graceful fallback
-        match parse_with_ai(&config, &description).await {
-            Ok(todo) => todo,
+        match parse_with_ai(&config, &db, &description).await {
+            Ok(todo) => {
+                ai_parsed = true;
+                todo
+            }
+            Err(TodoeeError::Network(reason)) => {
+                retry_ai = config.ai.retry_offline;
+                if retry_ai {
+                    eprintln!(
+                        "\u{26A0}  Offline - saved locally, will retry AI parsing when back online"
+                    );
+                } else {
+                    eprintln!(
+                        "\u{26A0}  Offline - saved locally without AI parsing ({})",
+                        reason
+                    );
+                    eprintln!(
+                        "   Run 'todoee config set ai.retry_offline true' to have the daemon retry."
+                    );
+                }
+                parse_offline(&description, &date_input)
+            }
             Err(e) => {
                 eprintln!("AI parsing failed: {}", e);
                 eprintln!("Creating task with original text instead.");
-                Todo::new(description.clone(), None)
+                parse_offline(&description, &date_input)
             }
         }
     };
 
-    // Override category if manually specified (create if doesn't exist)
-    if let Some(cat_name) = category {
-        let cat_id = get_or_create_category(&db, &cat_name, None).await?;
-        todo.category_id = Some(cat_id);
+    // An explicit --icon wins; otherwise pick up an emoji typed in the title
+    match icon {
+        Some(icon) => todo.icon = Some(icon),
+        None => {
+            todo.detect_icon();
+        }
     }
 
-    // Override priority if manually specified (1=Low, 2=Medium, 3=High)
+    // Override priority if manually specified (1=Low, 2=Medium, 3=High, 4=Urgent)
     if let Some(p) = priority {
-        todo.priority = match p {
-            1 => Priority::Low,
-            3 => Priority::High,
-            _ => Priority::Medium,
-        };
+        todo.priority = Priority::from_i32(p);
     }
 
     // Set reminder if specified
     if let Some(reminder_str) = &reminder {
-        todo.reminder_at = parse_reminder_time(reminder_str);
+        todo.reminder_at = dates::parse_reminder(reminder_str, &date_input);
         if todo.reminder_at.is_none() {
-            println!("\u{26A0}  Could not parse reminder time: {}", reminder_str);
+            eprintln!("\u{26A0}  Could not parse reminder time: {}", reminder_str);
         }
     }
+    if remind_before.is_some() {
+        todo.remind_before_minutes = remind_before;
+    }
+    todo.no_nag = no_nag;
+
+    if dry_run {
+        if output::is_json() {
+            return output::print_json(&todo);
+        }
+        println!("Dry run - nothing saved:");
+        println!("  Title: {}", todo.display_title());
+        if let Some(ref cat_name) = category {
+            println!("  Category: {}", cat_name);
+        }
+        print_fields(&todo);
+        return Ok(());
+    }
+
+    // Let the user check what the model made of the input before saving.
+    // Skipped with --yes or when stdin is not interactive (scripts, pipes).
+    if ai_parsed && !yes && io::stdin().is_terminal() {
+        let stdin = io::stdin();
+        if !review(&mut stdin.lock(), &mut todo, &date_input)? {
+            println!("\u{2717} Discarded: {}", todo.title);
+            return Ok(());
+        }
+    }
+
+    // Override category if manually specified (create if doesn't exist)
+    if let Some(cat_name) = category {
+        let cat_id = get_or_create_category(&db, &cat_name, None).await?;
+        todo.category_id = Some(cat_id);
+    }
 
     // Save todo to database
     db.create_todo(&todo).await?;
+    if retry_ai {
+        db.queue_ai_parse(todo.id, &description).await?;
+    }
 
     // Record operation for undo support
     let op = Operation::new(
@@ -97,8 +187,17 @@ pub async fn run(
     );
     db.record_operation(&op).await?;
 
+    if output::is_json() {
+        return output::print_json(&todo);
+    }
+
+    output::detail!("Full ID: {}", todo.id);
+    if output::is_quiet() {
+        return Ok(());
+    }
+
     // Print confirmation with checkmark emoji
-    println!("\u{2713} Created: {}", todo.title);
+    println!("\u{2713} Created: {}", todo.display_title());
 
     // Print category if any
     if let Some(cat_id) = todo.category_id
@@ -107,107 +206,412 @@ pub async fn run(
         println!("  Category: {}", cat_name);
     }
 
-    // Print due date if any
+    print_fields(&todo);
+
+    let id_len = ids::id_length(&db, &config).await?;
+    println!("  ID: {}", short_id(&todo.id, id_len));
+
+    Ok(())
+}
+
+/// Add one todo per line of `text`, as a single undoable batch
+pub async fn run_lines(
+    text: &str,
+    category: Option<String>,
+    priority: Option<i32>,
+    dry_run: bool,
+) -> Result<()> {
+    let lines = parse_lines(text);
+    if lines.is_empty() {
+        anyhow::bail!("No tasks given (expected one task per line)");
+    }
+
+    let config = Config::load().context("Failed to load configuration")?;
+    let date_input = config.display.date_input();
+
+    if dry_run {
+        if output::is_json() {
+            let todos: Vec<Todo> = lines
+                .iter()
+                .map(|line| build_line_todo(line, priority, &date_input))
+                .collect();
+            return output::print_json(&todos);
+        }
+        println!("Dry run - nothing saved:");
+        for line in &lines {
+            println!(
+                "  {}",
+                build_line_todo(line, priority, &date_input).display_title()
+            );
+        }
+        return Ok(());
+    }
+
+    let db_path = config.local_db_path()?;
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    let todos = add_lines(&db, &lines, category.as_deref(), priority, &date_input).await?;
+
+    if output::is_json() {
+        return output::print_json(&todos);
+    }
+
+    output::say!("\u{2713} Created {} todos", todos.len());
+    let id_len = ids::id_length(&db, &config).await?;
+    for todo in &todos {
+        output::say!("  {}  {}", short_id(&todo.id, id_len), todo.display_title());
+        output::detail!("Full ID: {}", todo.id);
+    }
+    output::say!("Run 'todoee undo' to remove them all.");
+    Ok(())
+}
+
+/// Tasks in a block of text, one per line. Blank lines and `#` comments are
+/// skipped, and list markers ("- ", "* ", "- [ ] ") are removed.
+pub fn parse_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let line = line
+                .strip_prefix(['-', '*'])
+                .filter(|rest| rest.is_empty() || rest.starts_with(' '))
+                .unwrap_or(line)
+                .trim_start();
+            line.strip_prefix("[ ]").unwrap_or(line).trim().to_string()
+        })
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Create one todo per line in a single transaction.
+///
+/// Dates are parsed offline from each line. The batch is recorded as one
+/// operation, so a single `undo` removes every todo it created.
+pub async fn add_lines(
+    db: &LocalDb,
+    lines: &[String],
+    category: Option<&str>,
+    priority: Option<i32>,
+    date_input: &DateInput,
+) -> Result<Vec<Todo>> {
+    let category_id = match category {
+        Some(name) => Some(get_or_create_category(db, name, None).await?),
+        None => None,
+    };
+
+    let todos: Vec<Todo> = lines
+        .iter()
+        .map(|line| {
+            let mut todo = build_line_todo(line, priority, date_input);
+            todo.category_id = category_id;
+            todo
+        })
+        .collect();
+
+    db.create_todos(&todos, &Operation::batch_create(&todos))
+        .await?;
+    Ok(todos)
+}
+
+fn build_line_todo(line: &str, priority: Option<i32>, date_input: &DateInput) -> Todo {
+    let mut todo = parse_offline(line, date_input);
+    todo.detect_icon();
+    if let Some(p) = priority {
+        todo.priority = Priority::from_i32(p);
+    }
+    todo
+}
+
+/// Print due date, reminder and priority
+fn print_fields(todo: &Todo) {
     if let Some(due) = todo.due_date {
-        println!("  Due: {}", due.format("%Y-%m-%d %H:%M"));
+        println!("  Due: {}", dates::format_due(&due, &Local));
     }
 
-    // Print reminder if any
     if let Some(reminder) = todo.reminder_at {
         println!("  Reminder: {}", reminder.format("%Y-%m-%d %H:%M"));
     }
 
-    // Print priority
+    if let Some(minutes) = todo.remind_before_minutes {
+        println!(
+            "  Remind before: {}",
+            dates::format_duration(Duration::minutes(minutes))
+        );
+    }
+
+    if todo.no_nag {
+        println!("  Repeat notifications: off");
+    }
+
     let priority_str = match todo.priority {
         Priority::Low => "Low",
         Priority::Medium => "Medium",
         Priority::High => "High",
+        Priority::Urgent => "Urgent",
     };
     println!("  Priority: {}", priority_str);
+}
+
+/// Show the parsed task and let the user save, tweak or discard it.
+///
+/// Returns `false` if the task was discarded.
+fn review(input: &mut impl BufRead, todo: &mut Todo, date_input: &DateInput) -> Result<bool> {
+    loop {
+        println!("AI parsed your task as:");
+        println!("  Title: {}", todo.title);
+        if let Some(ref desc) = todo.description {
+            println!("  Description: {}", desc);
+        }
+        print_fields(todo);
+
+        let answer = prompt(input, "Save? [Y]es / [e]dit / [n]o", "")?;
+        match answer.as_deref().map(str::to_lowercase).as_deref() {
+            None | Some("y") | Some("yes") => return Ok(true),
+            Some("n") | Some("no") => return Ok(false),
+            Some("e") | Some("edit") => edit_fields(input, todo, date_input)?,
+            Some(other) => println!("  \u{2717} Unknown answer '{}'", other),
+        }
+        println!();
+    }
+}
 
-    // Print short ID (first 8 chars of UUID)
-    println!("  ID: {}", &todo.id.to_string()[..8]);
+/// Prompt for title, due date and priority; empty answers keep the current value
+fn edit_fields(input: &mut impl BufRead, todo: &mut Todo, date_input: &DateInput) -> Result<()> {
+    if let Some(title) = prompt(input, "Title", &todo.title)? {
+        todo.title = title;
+    }
+
+    let current_due = todo
+        .due_date
+        .map(|d| dates::format_due(&d, &Local))
+        .unwrap_or_default();
+    loop {
+        let Some(answer) = prompt(
+            input,
+            "Due (YYYY-MM-DD [HH:MM], friday 3pm, +3d, none)",
+            &current_due,
+        )?
+        else {
+            break;
+        };
+        match parse_due(&answer, date_input) {
+            Some(due) => {
+                todo.due_date = due;
+                break;
+            }
+            None => println!("  \u{2717} Could not parse due date '{}'", answer),
+        }
+    }
+
+    let current_priority = match todo.priority {
+        Priority::Low => "1",
+        Priority::Medium => "2",
+        Priority::High => "3",
+        Priority::Urgent => "4",
+    };
+    loop {
+        let Some(answer) = prompt(input, "Priority (1-4)", current_priority)? else {
+            break;
+        };
+        match answer.to_lowercase().as_str() {
+            "1" | "low" => todo.priority = Priority::Low,
+            "2" | "medium" => todo.priority = Priority::Medium,
+            "3" | "high" => todo.priority = Priority::High,
+            "4" | "urgent" => todo.priority = Priority::Urgent,
+            _ => {
+                println!("  \u{2717} Priority must be 1, 2, 3 or 4");
+                continue;
+            }
+        }
+        break;
+    }
 
     Ok(())
 }
 
+/// Parse a due date answer. `Some(None)` clears the due date, `None` means invalid.
+pub fn parse_due(input: &str, date_input: &DateInput) -> Option<Option<DateTime<Utc>>> {
+    match input.trim() {
+        "none" | "-" => Some(None),
+        s => dates::parse_due(s, date_input).map(Some),
+    }
+}
+
+/// Parse a --remind-before value into minutes. "none" gives `None`.
+pub fn parse_remind_before(input: &str) -> Result<Option<i64>> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    match dates::parse_duration(input).map(|d| d.num_minutes()) {
+        Some(minutes) if minutes <= MAX_REMIND_BEFORE_MINUTES => Ok(Some(minutes)),
+        _ => anyhow::bail!(
+            "Invalid value for --remind-before: expected a duration up to 7d such as 30m, 2h or 1d (or none), got '{}'",
+            input
+        ),
+    }
+}
+
+/// Create a todo without AI, taking the due date from a trailing date phrase
+/// ("pay rent friday" is due Friday)
+pub fn parse_offline(description: &str, date_input: &DateInput) -> Todo {
+    match dates::split_due(description, &Local::now(), date_input) {
+        Some((title, parsed)) => {
+            let mut todo = Todo::new(title, None);
+            todo.due_date = Some(parsed.due_date(&Local));
+            todo
+        }
+        None => Todo::new(description.to_string(), None),
+    }
+}
+
 /// Parse natural language input using AI and convert to Todo
-async fn parse_with_ai(config: &Config, description: &str) -> Result<Todo> {
+async fn parse_with_ai(
+    config: &Config,
+    db: &LocalDb,
+    description: &str,
+) -> todoee_core::Result<Todo> {
     let client = AiClient::new(config)?;
-    let parsed = client.parse_task(description).await?;
-
-    let mut todo = Todo::new(parsed.title, None);
-    todo.description = parsed.description;
-    todo.due_date = parsed.due_date;
-    todo.reminder_at = parsed.reminder_at;
-
-    // Convert AI priority to internal Priority enum
-    if let Some(p) = parsed.priority {
-        todo.priority = match p {
-            1 => Priority::High, // AI uses 1=highest
-            2 => Priority::High,
-            3 => Priority::Medium,
-            _ => Priority::Low,
-        };
-    }
+    let parsed = client.parse_task_cached(db, description).await?;
 
-    // Store AI metadata for debugging/learning
-    todo.ai_metadata = Some(serde_json::json!({
-        "original_input": description,
-        "parsed_category": parsed.category,
-    }));
+    // Keep the original text as the human-entered title so AI changes can be reverted
+    let mut todo = Todo::new(description.to_string(), None);
+    todo.apply_ai_parse(&parsed, description);
 
     Ok(todo)
 }
 
 /// Look up or create a category by name
-async fn get_or_create_category(db: &LocalDb, name: &str, user_id: Option<Uuid>) -> Result<Uuid> {
+pub async fn get_or_create_category(
+    db: &LocalDb,
+    name: &str,
+    user_id: Option<Uuid>,
+) -> Result<Uuid> {
     // Check if category already exists
     if let Some(existing) = db.get_category_by_name(name).await? {
         return Ok(existing.id);
     }
 
-    // Create new category with provided user_id or generate one
-    let category = Category::new(user_id.unwrap_or_else(Uuid::new_v4), name.to_string());
+    let category = new_category(name, user_id);
     db.create_category(&category).await?;
 
     Ok(category.id)
 }
 
+/// A category not yet stored, owned by `user_id` or else a generated ID
+pub fn new_category(name: &str, user_id: Option<Uuid>) -> Category {
+    Category::new(user_id.unwrap_or_else(Uuid::new_v4), name.to_string())
+}
+
 /// Look up category name by ID
 async fn find_category_name(db: &LocalDb, id: Uuid) -> Result<Option<String>> {
     let categories = db.list_categories().await?;
     Ok(categories.into_iter().find(|c| c.id == id).map(|c| c.name))
 }
 
-/// Parse reminder time from natural language string
-fn parse_reminder_time(s: &str) -> Option<chrono::DateTime<chrono::Utc>> {
-    let now = chrono::Utc::now();
-    let s = s.to_lowercase();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
 
-    // Handle "in X minutes/hours"
-    if let Some(rest) = s.strip_prefix("in ") {
-        if let Some(minutes) = rest
-            .strip_suffix(" minutes")
-            .or_else(|| rest.strip_suffix(" minute"))
-            && let Ok(m) = minutes.trim().parse::<i64>()
-        {
-            return Some(now + chrono::Duration::minutes(m));
-        }
-        if let Some(hours) = rest
-            .strip_suffix(" hours")
-            .or_else(|| rest.strip_suffix(" hour"))
-            && let Ok(h) = hours.trim().parse::<i64>()
-        {
-            return Some(now + chrono::Duration::hours(h));
-        }
+    fn parsed_todo() -> Todo {
+        let mut todo = Todo::new("Call mom".to_string(), None);
+        todo.priority = Priority::Medium;
+        todo
+    }
+
+    #[test]
+    fn test_parse_remind_before() {
+        assert_eq!(parse_remind_before("2h").unwrap(), Some(120));
+        assert_eq!(parse_remind_before("0").unwrap(), Some(0));
+        assert_eq!(parse_remind_before(" None ").unwrap(), None);
+        assert!(parse_remind_before("8d").is_err());
+        assert!(parse_remind_before("soon").is_err());
+    }
+
+    #[test]
+    fn test_review_accepts_on_enter() {
+        let mut todo = parsed_todo();
+        let mut input = Cursor::new("\n");
+        assert!(review(&mut input, &mut todo, &DateInput::default()).unwrap());
+        assert_eq!(todo.title, "Call mom");
+    }
+
+    #[test]
+    fn test_review_discard() {
+        let mut todo = parsed_todo();
+        let mut input = Cursor::new("n\n");
+        assert!(!review(&mut input, &mut todo, &DateInput::default()).unwrap());
     }
 
-    // Handle "tomorrow"
-    if s.contains("tomorrow") {
-        let tomorrow = now + chrono::Duration::days(1);
-        return Some(tomorrow.date_naive().and_hms_opt(9, 0, 0)?.and_utc());
+    #[test]
+    fn test_review_edit_then_save() {
+        let mut todo = parsed_todo();
+        // edit -> new title, bad due date then a valid one, keep priority -> save
+        let mut input = Cursor::new("e\nCall mum\nsoonish\n2030-05-01\n\ny\n");
+        assert!(review(&mut input, &mut todo, &DateInput::default()).unwrap());
+
+        assert_eq!(todo.title, "Call mum");
+        assert_eq!(
+            todo.due_date.unwrap().format("%Y-%m-%d").to_string(),
+            "2030-05-01"
+        );
+        assert_eq!(todo.priority, Priority::Medium);
+    }
+
+    #[test]
+    fn test_review_edit_priority_and_clear_due() {
+        let mut todo = parsed_todo();
+        todo.due_date = Some(Utc::now());
+        let mut input = Cursor::new("e\n\nnone\n5\nhigh\n\n");
+        assert!(review(&mut input, &mut todo, &DateInput::default()).unwrap());
+
+        assert!(todo.due_date.is_none());
+        assert_eq!(todo.priority, Priority::High);
     }
 
-    None
+    #[test]
+    fn test_review_end_of_input_cancels() {
+        let mut todo = parsed_todo();
+        let mut input = Cursor::new("");
+        assert!(review(&mut input, &mut todo, &DateInput::default()).is_err());
+    }
+
+    #[test]
+    fn test_parse_due() {
+        assert_eq!(parse_due("none", &DateInput::default()), Some(None));
+        assert!(parse_due("+3d", &DateInput::default()).unwrap().is_some());
+        assert_eq!(
+            parse_due("2030-01-02 08:15", &DateInput::default())
+                .unwrap()
+                .unwrap()
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+            "2030-01-02 08:15"
+        );
+        assert!(
+            parse_due("next friday 3pm", &DateInput::default())
+                .unwrap()
+                .is_some()
+        );
+        assert!(parse_due("someday", &DateInput::default()).is_none());
+    }
+
+    #[test]
+    fn test_parse_lines_skips_blanks_and_comments() {
+        let text = "# groceries\nBuy milk\n\n  - Buy eggs  \n* [ ] Call bakery\n- \n";
+        assert_eq!(
+            parse_lines(text),
+            vec!["Buy milk", "Buy eggs", "Call bakery"]
+        );
+    }
 }
@@ -0,0 +1,146 @@
+//! Agenda command showing today's summary, then events and due todos day by day.
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Utc};
+use todoee_core::{Config, DailySummary, Event, LocalDb, Priority, Todo, short_id};
+
+use super::event::format_time_range;
+use super::{ids, output};
+
+/// Event occurrences and due todos for a single day
+type DayAgenda = (Vec<(Event, DateTime<Utc>)>, Vec<Todo>);
+
+/// Show today's summary, and unless `summary_only`, events and due todos
+/// for the next N days, starting today.
+pub async fn run(days: i64, summary_only: bool) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+    let id_len = ids::id_length(&db, &config).await?;
+
+    let pending = db.list_todos(true).await?;
+    if summary_only {
+        let summary = DailySummary::new(&pending, &config.ranking, Utc::now(), &Local);
+        if output::is_json() {
+            return output::print_json(&summary);
+        }
+        for line in summary.lines() {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    let days = days.max(1);
+    let today = Local::now().date_naive();
+    let from = Local
+        .from_local_datetime(&today.and_hms_opt(0, 0, 0).expect("midnight is valid"))
+        .earliest()
+        .context("Could not determine start of today")?
+        .with_timezone(&Utc);
+    let to = from + Duration::days(days);
+
+    let mut agenda: BTreeMap<NaiveDate, DayAgenda> = BTreeMap::new();
+
+    for (event, start) in db.list_event_occurrences(from, to).await? {
+        let day = start.with_timezone(&Local).date_naive().max(today);
+        agenda.entry(day).or_default().0.push((event, start));
+    }
+
+    let summary = DailySummary::new(&pending, &config.ranking, Utc::now(), &Local);
+    for todo in pending {
+        if let Some(due) = todo.due_date
+            && due >= from
+            && due < to
+        {
+            let day = due.with_timezone(&Local).date_naive();
+            agenda.entry(day).or_default().1.push(todo);
+        }
+    }
+
+    if output::is_json() {
+        let days: Vec<_> = agenda
+            .iter()
+            .map(|(day, (events, todos))| {
+                let events: Vec<_> = events
+                    .iter()
+                    .map(|(event, start)| serde_json::json!({ "start": start, "event": event }))
+                    .collect();
+                serde_json::json!({
+                    "date": day,
+                    "week": day.iso_week().week(),
+                    "events": events,
+                    "todos": todos,
+                })
+            })
+            .collect();
+        return output::print_json(&days);
+    }
+
+    for line in summary.lines() {
+        println!("\x1b[1m{}\x1b[0m", line);
+    }
+    println!();
+
+    if agenda.is_empty() {
+        println!("Nothing scheduled in the next {} day(s).", days);
+        return Ok(());
+    }
+
+    let mut current_week = None;
+    for (day, (events, mut todos)) in agenda {
+        // ISO week numbers, shown once per week
+        let week = day.iso_week().week();
+        if current_week != Some(week) {
+            println!("\x1b[90m-- Week {} --\x1b[0m", week);
+            current_week = Some(week);
+        }
+
+        let label = if day == today {
+            " (today)"
+        } else if day == today + Duration::days(1) {
+            " (tomorrow)"
+        } else {
+            ""
+        };
+        println!("== {}{} ==", day.format("%a, %b %d"), label);
+
+        for (event, start) in &events {
+            println!(
+                "  \x1b[36m{}\x1b[0m {}",
+                format_time_range(event, *start),
+                event.title
+            );
+        }
+
+        todos.sort_by_key(|t| std::cmp::Reverse(t.priority));
+        for todo in &todos {
+            let pri = match todo.priority {
+                Priority::Urgent => "\x1b[1;97;41m!!!\x1b[0m",
+                Priority::High => "\x1b[31m!!!\x1b[0m",
+                Priority::Medium => "\x1b[33m!! \x1b[0m",
+                Priority::Low => "\x1b[90m!  \x1b[0m",
+            };
+            println!(
+                "  [ ] {} {} \x1b[90m[{}]\x1b[0m",
+                pri,
+                todo.title,
+                short_id(&todo.id, id_len)
+            );
+        }
+        println!();
+    }
+
+    Ok(())
+}
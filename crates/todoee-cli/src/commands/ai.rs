@@ -0,0 +1,112 @@
+//! AI-related commands.
+
+use std::fs;
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use todoee_core::{Config, EntityType, IdMatch, LocalDb, Operation, OperationType, short_id};
+
+use super::ids;
+
+#[derive(Subcommand, Clone)]
+pub enum AiCommand {
+    /// Undo the fields AI set on a todo, restoring what you typed
+    ///
+    /// Fields you have edited since the AI set them are kept.
+    ///
+    /// Example: todoee ai revert abc1
+    Revert {
+        /// Todo ID (short prefix or full UUID)
+        id: String,
+    },
+}
+
+pub async fn run(command: AiCommand) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    match command {
+        AiCommand::Revert { id } => revert(&db, &config, &id).await,
+    }
+}
+
+async fn revert(db: &LocalDb, config: &Config, id: &str) -> Result<()> {
+    match ids::resolve(db, config, id).await? {
+        IdMatch::NotFound => {
+            eprintln!("No todo found matching '{}'", id);
+            eprintln!("Hint: Use 'todoee list --all' to see all todos including completed ones.");
+            anyhow::bail!("Todo not found");
+        }
+        IdMatch::Unique(mut todo) => {
+            if todo.ai_metadata.is_none() {
+                anyhow::bail!("'{}' was not created with AI", todo.title);
+            }
+
+            let prev_state = serde_json::to_value(&todo)?;
+            let (reverted, kept) = todo.revert_ai_fields();
+
+            if reverted.is_empty() {
+                println!("Nothing to revert for '{}'", todo.title);
+                if !kept.is_empty() {
+                    println!("  Edited since AI set them: {}", field_names(&kept));
+                }
+                return Ok(());
+            }
+
+            db.update_todo(&todo).await?;
+
+            let op = Operation::new(
+                OperationType::Update,
+                EntityType::Todo,
+                todo.id,
+                Some(prev_state),
+                Some(serde_json::to_value(&todo)?),
+            );
+            db.record_operation(&op).await?;
+
+            println!("\u{21B6} Reverted AI changes: {}", todo.title);
+            println!("  Restored: {}", field_names(&reverted));
+            if !kept.is_empty() {
+                println!("  Kept (edited since): {}", field_names(&kept));
+            }
+            let id_len = ids::id_length(db, config).await?;
+            println!("  ID: {}", short_id(&todo.id, id_len));
+        }
+        IdMatch::Ambiguous(matches) => {
+            eprintln!("Multiple todos match '{}'. Please be more specific:", id);
+            eprintln!();
+            let id_len = ids::match_length(&matches);
+            for todo in &matches {
+                let status = if todo.is_completed { "[x]" } else { "[ ]" };
+                eprintln!(
+                    "  {} {} [{}]",
+                    status,
+                    todo.title,
+                    short_id(&todo.id, id_len)
+                );
+            }
+            anyhow::bail!("Ambiguous ID - provide more characters");
+        }
+    }
+
+    Ok(())
+}
+
+/// Human-readable, comma separated field names ("due date, priority")
+pub fn field_names(fields: &[String]) -> String {
+    fields
+        .iter()
+        .map(|f| f.replace('_', " ").replace(" at", ""))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,174 @@
+//! User-defined command aliases from the `[aliases]` config table.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use todoee_core::Config;
+use todoee_core::config::split_command;
+
+use super::output;
+
+/// Expand an alias in the command position of `args` (program name first).
+///
+/// Like git, built-in commands always win over an alias of the same name,
+/// and an alias may expand to another alias. Words after the alias are kept.
+pub fn expand(
+    mut args: Vec<String>,
+    aliases: &BTreeMap<String, String>,
+    builtins: &[String],
+) -> Result<Vec<String>> {
+    let Some(pos) = command_position(&args) else {
+        return Ok(args);
+    };
+
+    let mut chain: Vec<String> = Vec::new();
+    while !builtins.contains(&args[pos])
+        && let Some(expansion) = aliases.get(&args[pos])
+    {
+        if chain.contains(&args[pos]) {
+            chain.push(args[pos].clone());
+            anyhow::bail!("Alias loop: {}", chain.join(" -> "));
+        }
+        chain.push(args[pos].clone());
+
+        let words =
+            split_command(expansion).with_context(|| format!("Invalid alias '{}'", args[pos]))?;
+        if words.is_empty() {
+            anyhow::bail!("Alias '{}' is empty", args[pos]);
+        }
+        args.splice(pos..=pos, words);
+    }
+
+    Ok(args)
+}
+
+/// Global flags followed by a separate value
+const VALUE_FLAGS: &[&str] = &["--profile"];
+
+/// Index of the command in `args` (program name first). Global flags such as
+/// `-i` or `--profile work` may come before it.
+pub fn command_position(args: &[String]) -> Option<usize> {
+    let mut pos = 1;
+    while let Some(arg) = args.get(pos) {
+        if VALUE_FLAGS.contains(&arg.as_str()) {
+            pos += 2;
+        } else if arg.starts_with('-') {
+            pos += 1;
+        } else {
+            return Some(pos);
+        }
+    }
+    None
+}
+
+/// The value of `--profile`, read before clap so aliases come from the
+/// profile's config
+pub fn profile_flag(args: &[String]) -> Option<String> {
+    let mut iter = args.iter().skip(1).take_while(|arg| *arg != "--");
+    while let Some(arg) = iter.next() {
+        if arg == "--profile" {
+            return iter.next().cloned();
+        }
+        if let Some(value) = arg.strip_prefix("--profile=") {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+/// List configured aliases, flagging any shadowed by a built-in command.
+pub fn list(builtins: &[String]) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+
+    if output::is_json() {
+        let aliases: Vec<_> = config
+            .aliases
+            .iter()
+            .map(|(name, expansion)| {
+                serde_json::json!({
+                    "name": name,
+                    "expansion": expansion,
+                    "shadowed": builtins.contains(name),
+                })
+            })
+            .collect();
+        return output::print_json(&aliases);
+    }
+
+    if config.aliases.is_empty() {
+        println!("No aliases. Define one with:");
+        println!("  todoee config set aliases.today \"list --today\"");
+        return Ok(());
+    }
+
+    let width = config.aliases.keys().map(|k| k.len()).max().unwrap_or(0);
+    for (name, expansion) in &config.aliases {
+        let shadowed = if builtins.contains(name) {
+            "  \x1b[33m(ignored: built-in command of the same name)\x1b[0m"
+        } else {
+            ""
+        };
+        println!("{:<width$}  = {}{}", name, expansion, shadowed);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    fn aliases(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_expand_alias() {
+        let builtins = words("list add");
+        let aliases = aliases(&[
+            ("today", "list --today"),
+            ("t", "today"),
+            ("list", "add shadowed"),
+            ("wip", "list -c \"work in progress\""),
+        ]);
+
+        let expanded = expand(words("todoee t --all"), &aliases, &builtins).unwrap();
+        assert_eq!(expanded, words("todoee list --today --all"));
+
+        let expanded = expand(words("todoee -i today"), &aliases, &builtins).unwrap();
+        assert_eq!(expanded, words("todoee -i list --today"));
+
+        let expanded = expand(words("todoee --profile work today"), &aliases, &builtins).unwrap();
+        assert_eq!(expanded, words("todoee --profile work list --today"));
+        assert_eq!(
+            profile_flag(&words("todoee t --profile=work")).as_deref(),
+            Some("work")
+        );
+        assert_eq!(profile_flag(&words("todoee add -- --profile x")), None);
+
+        let expanded = expand(words("todoee wip"), &aliases, &builtins).unwrap();
+        assert_eq!(expanded[3], "work in progress");
+
+        // Built-ins win, and arguments are never expanded
+        let expanded = expand(words("todoee list today"), &aliases, &builtins).unwrap();
+        assert_eq!(expanded, words("todoee list today"));
+        assert_eq!(
+            expand(words("todoee"), &aliases, &builtins).unwrap(),
+            words("todoee")
+        );
+    }
+
+    #[test]
+    fn test_expand_alias_loop() {
+        let aliases = aliases(&[("a", "b"), ("b", "a --x")]);
+        let err = expand(words("todoee a"), &aliases, &[]).unwrap_err();
+        assert_eq!(err.to_string(), "Alias loop: a -> b -> a");
+    }
+}
@@ -0,0 +1,109 @@
+//! Attach command: link files and URLs to a todo, and open them.
+
+use std::fs;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+use todoee_core::{Attachment, Config, LocalDb, is_url, notes};
+
+use super::{ids, output};
+
+pub async fn run(id: String, target: String) -> Result<()> {
+    let target = resolve_target(&target)?;
+
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    let todo = ids::resolve_todo(&db, &config, &id).await?;
+
+    let attachment = Attachment::new(todo.id, target);
+    db.add_attachment(&attachment).await?;
+
+    if output::is_json() {
+        return output::print_json(&attachment);
+    }
+    println!("\u{2713} Attached to {}: {}", todo.title, attachment.target);
+    Ok(())
+}
+
+/// URLs are kept as typed; file paths must exist and are stored absolute,
+/// so they still open from another working directory
+fn resolve_target(target: &str) -> Result<String> {
+    let target = target.trim();
+    if target.is_empty() {
+        anyhow::bail!("Attachment cannot be empty");
+    }
+    if is_url(target) {
+        return Ok(target.to_string());
+    }
+    let path = notes::expand_home(target);
+    let absolute = fs::canonicalize(&path).with_context(|| {
+        format!(
+            "No such file: {} (URLs need a scheme such as https://)",
+            target
+        )
+    })?;
+    Ok(absolute.display().to_string())
+}
+
+/// Open a URL or file with the platform's default application
+pub fn open(target: &str) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(windows)]
+    let mut command = {
+        let mut command = Command::new("cmd");
+        // The empty argument is the window title `start` expects first
+        command.args(["/C", "start", ""]);
+        command
+    };
+    #[cfg(not(any(target_os = "macos", windows)))]
+    let mut command = Command::new("xdg-open");
+
+    // Quiet, so the opener's messages don't land in the TUI
+    command
+        .arg(target)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to open {}", target))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_target() {
+        assert_eq!(
+            resolve_target(" https://example.com/spec ").unwrap(),
+            "https://example.com/spec"
+        );
+
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("spec.pdf");
+        fs::write(&file, b"%PDF").unwrap();
+        let resolved = resolve_target(&file.display().to_string()).unwrap();
+        assert_eq!(
+            resolved,
+            fs::canonicalize(&file).unwrap().display().to_string()
+        );
+
+        let missing = temp_dir.path().join("missing.pdf");
+        assert!(resolve_target(&missing.display().to_string()).is_err());
+        assert!(resolve_target("  ").is_err());
+    }
+}
@@ -2,7 +2,12 @@
 
 use anyhow::Result;
 use clap::Subcommand;
-use todoee_core::{Config, EntityType, LocalDb, Operation, OperationType, Priority};
+use todoee_core::{
+    Config, EntityType, IdMatch, LocalDb, Operation, OperationType, Todo, validation,
+};
+use uuid::Uuid;
+
+use super::ids;
 
 #[derive(Subcommand, Clone)]
 pub enum BatchCommand {
@@ -18,7 +23,7 @@ pub enum BatchCommand {
     },
     /// Set priority for multiple todos
     Priority {
-        /// Priority level (1=low, 2=medium, 3=high)
+        /// Priority level (1=low, 2=medium, 3=high, 4=urgent)
         level: u8,
         /// Todo IDs (or prefixes)
         ids: Vec<String>,
@@ -31,20 +36,21 @@ pub async fn run(cmd: BatchCommand) -> Result<()> {
     let db = LocalDb::new(&db_path).await?;
     db.run_migrations().await?;
 
+    // Every change in one batch is undone together
+    let group_id = Uuid::new_v4();
+
     match cmd {
         BatchCommand::Done { ids } => {
             if ids.is_empty() {
                 anyhow::bail!("No IDs provided. Usage: todoee batch done <id1> <id2> ...");
             }
-            // For done, we only need incomplete todos (exclude_completed = true)
-            let todos = db.list_todos(true).await?;
             let mut count = 0;
             for id in &ids {
-                let id_lower = id.to_lowercase();
-                if let Some(todo) = todos
-                    .iter()
-                    .find(|t| t.id.to_string().to_lowercase().starts_with(&id_lower))
-                {
+                if let Some(todo) = find(&db, &config, id).await? {
+                    if todo.is_completed {
+                        println!("Already done: {}", todo.title);
+                        continue;
+                    }
                     let mut updated = todo.clone();
                     let prev = serde_json::to_value(&updated)?;
                     updated.mark_complete();
@@ -57,12 +63,11 @@ pub async fn run(cmd: BatchCommand) -> Result<()> {
                         todo.id,
                         Some(prev),
                         Some(new_state),
-                    );
+                    )
+                    .in_group(group_id);
                     db.record_operation(&op).await?;
                     count += 1;
                     println!("\u{2713} {}", todo.title);
-                } else {
-                    println!("Not found: {}", id);
                 }
             }
             println!("\nMarked {} todo(s) as done", count);
@@ -71,28 +76,21 @@ pub async fn run(cmd: BatchCommand) -> Result<()> {
             if ids.is_empty() {
                 anyhow::bail!("No IDs provided. Usage: todoee batch delete <id1> <id2> ...");
             }
-            // Include all todos for delete (exclude_completed = false)
-            let todos = db.list_todos(false).await?;
             let mut count = 0;
             for id in &ids {
-                let id_lower = id.to_lowercase();
-                if let Some(todo) = todos
-                    .iter()
-                    .find(|t| t.id.to_string().to_lowercase().starts_with(&id_lower))
-                {
+                if let Some(todo) = find(&db, &config, id).await? {
                     let op = Operation::new(
                         OperationType::Delete,
                         EntityType::Todo,
                         todo.id,
-                        Some(serde_json::to_value(todo)?),
+                        Some(serde_json::to_value(&todo)?),
                         None,
-                    );
+                    )
+                    .in_group(group_id);
                     db.record_operation(&op).await?;
                     db.delete_todo(todo.id).await?;
                     count += 1;
                     println!("\u{2717} {}", todo.title);
-                } else {
-                    println!("Not found: {}", id);
                 }
             }
             println!("\nDeleted {} todo(s)", count);
@@ -103,24 +101,11 @@ pub async fn run(cmd: BatchCommand) -> Result<()> {
                     "No IDs provided. Usage: todoee batch priority <level> <id1> <id2> ..."
                 );
             }
-            let priority = match level {
-                1 => Priority::Low,
-                2 => Priority::Medium,
-                3 => Priority::High,
-                _ => {
-                    anyhow::bail!("Priority must be 1, 2, or 3");
-                }
-            };
+            let priority = validation::priority(level.into())?;
 
-            // Include all todos for priority changes (exclude_completed = false)
-            let todos = db.list_todos(false).await?;
             let mut count = 0;
             for id in &ids {
-                let id_lower = id.to_lowercase();
-                if let Some(todo) = todos
-                    .iter()
-                    .find(|t| t.id.to_string().to_lowercase().starts_with(&id_lower))
-                {
+                if let Some(todo) = find(&db, &config, id).await? {
                     let mut updated = todo.clone();
                     let prev = serde_json::to_value(&updated)?;
                     updated.priority = priority;
@@ -132,12 +117,11 @@ pub async fn run(cmd: BatchCommand) -> Result<()> {
                         todo.id,
                         Some(prev),
                         Some(serde_json::to_value(&updated)?),
-                    );
+                    )
+                    .in_group(group_id);
                     db.record_operation(&op).await?;
                     count += 1;
                     println!("~ {} (now {:?})", todo.title, priority);
-                } else {
-                    println!("Not found: {}", id);
                 }
             }
             println!("\nUpdated priority for {} todo(s)", count);
@@ -146,3 +130,22 @@ pub async fn run(cmd: BatchCommand) -> Result<()> {
 
     Ok(())
 }
+
+/// The todo `id` refers to, or `None` after saying why it is skipped
+async fn find(db: &LocalDb, config: &Config, id: &str) -> Result<Option<Todo>> {
+    Ok(match ids::resolve(db, config, id).await? {
+        IdMatch::Unique(todo) => Some(*todo),
+        IdMatch::NotFound => {
+            println!("Not found: {}", id);
+            None
+        }
+        IdMatch::Ambiguous(matches) => {
+            println!(
+                "Ambiguous: {} matches {} todos, provide more characters",
+                id,
+                matches.len()
+            );
+            None
+        }
+    })
+}
@@ -0,0 +1,293 @@
+//! Category commands: listing and renaming.
+
+use std::fs;
+use std::io;
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use todoee_core::config::split_command;
+use todoee_core::{Config, LocalDb};
+
+use super::config::confirm;
+use super::history::format_command;
+use super::output;
+
+#[derive(Subcommand, Clone)]
+pub enum CategoryCommand {
+    /// List categories with their todo counts
+    List,
+    /// Rename a category, updating aliases and contexts that filter on the old name
+    Rename {
+        /// Current category name
+        old: String,
+        /// New category name
+        new: String,
+        /// Show what would change without renaming
+        #[arg(long)]
+        preview: bool,
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+pub async fn run(cmd: CategoryCommand) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    match cmd {
+        CategoryCommand::List => list(&db).await,
+        CategoryCommand::Rename {
+            old,
+            new,
+            preview,
+            yes,
+        } => rename(&db, config, &old, &new, preview, yes).await,
+    }
+}
+
+async fn list(db: &LocalDb) -> Result<()> {
+    let categories = db.list_categories().await?;
+    let mut rows = Vec::with_capacity(categories.len());
+    for category in categories {
+        let todos = db.list_todos_by_category(category.id).await?;
+        let pending = todos.iter().filter(|t| !t.is_completed).count();
+        rows.push((category, pending, todos.len()));
+    }
+
+    if output::is_json() {
+        let rows: Vec<_> = rows
+            .iter()
+            .map(|(category, pending, total)| {
+                serde_json::json!({ "category": category, "pending": pending, "total": total })
+            })
+            .collect();
+        return output::print_json(&rows);
+    }
+
+    if rows.is_empty() {
+        println!("No categories. Use 'todoee add -c <name>' to create one!");
+        return Ok(());
+    }
+
+    let width = rows
+        .iter()
+        .map(|(c, _, _)| c.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for (category, pending, total) in &rows {
+        println!(
+            "{:<width$}  {} pending \x1b[90m({} total)\x1b[0m",
+            category.name, pending, total
+        );
+    }
+
+    Ok(())
+}
+
+async fn rename(
+    db: &LocalDb,
+    mut config: Config,
+    old: &str,
+    new: &str,
+    preview: bool,
+    yes: bool,
+) -> Result<()> {
+    let new = new.trim();
+    if new.is_empty() {
+        anyhow::bail!("Category name cannot be empty");
+    }
+
+    let category = db
+        .get_category_by_name(old)
+        .await?
+        .with_context(|| format!("Category '{}' not found", old))?;
+    if category.name == new {
+        anyhow::bail!("Category is already named '{}'", new);
+    }
+    if db.get_category_by_name(new).await?.is_some() {
+        anyhow::bail!("Category '{}' already exists", new);
+    }
+
+    let todos = db.list_todos_by_category(category.id).await?.len();
+    let aliases: Vec<(String, String)> = config
+        .aliases
+        .iter()
+        .filter_map(|(name, expansion)| {
+            rename_in_alias(expansion, old, new).map(|renamed| (name.clone(), renamed))
+        })
+        .collect();
+    let contexts: Vec<(String, String)> = config
+        .contexts
+        .filters
+        .iter()
+        .filter_map(|(name, filter)| {
+            rename_in_filter(filter, old, new).map(|renamed| (name.clone(), renamed))
+        })
+        .collect();
+
+    if output::is_json() {
+        if !preview && !yes {
+            anyhow::bail!("Pass --yes (or --preview) to rename with --json");
+        }
+    } else {
+        println!("Rename category '{}' to '{}':", old, new);
+        println!("  {} todo(s) will show the new name", todos);
+        for (name, renamed) in &aliases {
+            println!(
+                "  alias {}: {} \u{2192} {}",
+                name, config.aliases[name], renamed
+            );
+        }
+        for (name, renamed) in &contexts {
+            println!(
+                "  context {}: {} \u{2192} {}",
+                name, config.contexts.filters[name], renamed
+            );
+        }
+        if aliases.is_empty() && contexts.is_empty() {
+            println!("  No aliases or contexts refer to '{}'", old);
+        }
+    }
+
+    let applied =
+        !preview && (yes || confirm(&mut io::stdin().lock(), "Rename the category?", false)?);
+    if applied {
+        db.rename_category(category.id, new).await?;
+        if !aliases.is_empty() || !contexts.is_empty() {
+            config.aliases.extend(aliases.iter().cloned());
+            config.contexts.filters.extend(contexts.iter().cloned());
+            config.save().context("Failed to save configuration")?;
+        }
+    }
+
+    if output::is_json() {
+        let aliases: Vec<_> = aliases
+            .iter()
+            .map(|(name, expansion)| serde_json::json!({ "name": name, "expansion": expansion }))
+            .collect();
+        let contexts: Vec<_> = contexts
+            .iter()
+            .map(|(name, filter)| serde_json::json!({ "name": name, "filter": filter }))
+            .collect();
+        return output::print_json(&serde_json::json!({
+            "old": old,
+            "new": new,
+            "todos": todos,
+            "aliases": aliases,
+            "contexts": contexts,
+            "applied": applied,
+        }));
+    }
+
+    if applied {
+        println!("\u{2713} Renamed category: {} \u{2192} {}", old, new);
+    } else if !preview {
+        println!("Cancelled");
+    }
+
+    Ok(())
+}
+
+/// Rewrite `-c old`, `--category old` and `--category=old` in an alias
+/// expansion. Returns `None` when the alias doesn't filter on `old`.
+fn rename_in_alias(expansion: &str, old: &str, new: &str) -> Option<String> {
+    let mut words = split_command(expansion).ok()?;
+    let mut changed = false;
+
+    for i in 0..words.len() {
+        let follows_flag = i > 0 && matches!(words[i - 1].as_str(), "-c" | "--category");
+        if follows_flag && words[i] == old {
+            words[i] = new.to_string();
+            changed = true;
+        } else if words[i] == format!("--category={}", old) {
+            words[i] = format!("--category={}", new);
+            changed = true;
+        }
+    }
+
+    changed.then(|| format_command(&words))
+}
+
+/// Rewrite `category:old` terms (also `cat:` and `tag:`) in a context's
+/// filter. Returns `None` when the filter doesn't mention `old`.
+fn rename_in_filter(filter: &str, old: &str, new: &str) -> Option<String> {
+    let mut words = split_command(filter).ok()?;
+    let mut changed = false;
+
+    for word in &mut words {
+        let inner = word.trim_start_matches('(').trim_end_matches(')');
+        let Some(at) = inner.find([':', '=']) else {
+            continue;
+        };
+        let (key, value) = (&inner[..at], &inner[at + 1..]);
+        let is_category = matches!(key.to_lowercase().as_str(), "category" | "cat" | "tag");
+        // Filters match category names ignoring case
+        if is_category && value.to_lowercase() == old.to_lowercase() {
+            let open = word.len() - word.trim_start_matches('(').len();
+            let close = word.len() - word.trim_end_matches(')').len();
+            *word = format!(
+                "{}{}{}{}",
+                "(".repeat(open),
+                &inner[..=at],
+                new,
+                ")".repeat(close)
+            );
+            changed = true;
+        }
+    }
+
+    changed.then(|| format_command(&words))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_in_alias() {
+        assert_eq!(
+            rename_in_alias("list -c work", "work", "job").as_deref(),
+            Some("list -c job")
+        );
+        assert_eq!(
+            rename_in_alias("list --category=work --all", "work", "day job").as_deref(),
+            Some("list \"--category=day job\" --all")
+        );
+        assert_eq!(
+            rename_in_alias("add \"work\" --category 'big work'", "big work", "x").as_deref(),
+            Some("add work --category x")
+        );
+        // Titles and other flags that happen to match are left alone
+        assert_eq!(rename_in_alias("add work -p 3", "work", "job"), None);
+        assert_eq!(rename_in_alias("list -c home", "work", "job"), None);
+    }
+
+    #[test]
+    fn test_rename_in_filter() {
+        assert_eq!(
+            rename_in_filter("category:work and priority:high", "work", "job").as_deref(),
+            Some("category:job and priority:high")
+        );
+        assert_eq!(
+            rename_in_filter("(tag:Work or cat:home) and not work", "work", "day job").as_deref(),
+            Some("\"(tag:day job\" or cat:home) and not work")
+        );
+        assert_eq!(
+            rename_in_filter("category:\"deep work\"", "deep work", "focus").as_deref(),
+            Some("category:focus")
+        );
+        // Titles and projects that happen to match are left alone
+        assert_eq!(rename_in_filter("work project:work", "work", "job"), None);
+    }
+}
@@ -1,53 +1,396 @@
 use anyhow::{Context, Result};
+use clap::Subcommand;
 use std::env;
-use todoee_core::Config;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use todoee_core::auth::Auth;
+use todoee_core::config::{AI_PROVIDERS, CONFIG_KEYS, DATE_INPUT_ORDERS, DENSITIES, THEMES};
+use todoee_core::{AiClient, Config, SyncService, net};
 
-pub async fn run(init: bool) -> Result<()> {
-    if init {
-        run_init().await
-    } else {
-        show_config().await
+#[derive(Subcommand, Clone)]
+pub enum ConfigCommand {
+    /// Print the value of a setting
+    Get {
+        /// Setting key (e.g. ai.model)
+        key: String,
+    },
+    /// Change a setting and save config.toml
+    Set {
+        /// Setting key (e.g. ai.model)
+        key: String,
+        /// New value (empty string clears optional settings)
+        value: String,
+    },
+    /// Write settings and aliases to a TOML file (no todos, no secrets)
+    Export {
+        /// Output file (default: stdout)
+        path: Option<PathBuf>,
+    },
+    /// Apply settings from a file written by `config export`
+    Import {
+        /// Settings file
+        path: PathBuf,
+    },
+}
+
+pub async fn run(init: bool, command: Option<ConfigCommand>) -> Result<()> {
+    match command {
+        Some(ConfigCommand::Get { key }) => get(&key),
+        Some(ConfigCommand::Set { key, value }) => set(&key, &value),
+        Some(ConfigCommand::Export { path }) => export(path.as_deref()),
+        Some(ConfigCommand::Import { path }) => import(&path),
+        None if init => run_init().await,
+        None => show_config().await,
     }
 }
 
-/// Initialize configuration with default settings
+/// Print a single configuration value (for scripting)
+fn get(key: &str) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    println!("{}", config.get_value(key)?);
+    Ok(())
+}
+
+/// Update a single configuration value and save
+fn set(key: &str, value: &str) -> Result<()> {
+    let mut config = Config::load().context("Failed to load configuration")?;
+    config.set_value(key, value)?;
+    config.save().context("Failed to save configuration")?;
+    if let Some(name) = key.strip_prefix("aliases.")
+        && !config.aliases.contains_key(name)
+    {
+        println!("\u{2713} Removed alias: {}", name);
+        return Ok(());
+    }
+    if let Some(name) = key
+        .strip_prefix("webhooks.")
+        .and_then(|rest| rest.strip_suffix(".url"))
+        && !config.webhooks.contains_key(name)
+    {
+        println!("\u{2713} Removed webhook: {}", name);
+        return Ok(());
+    }
+    println!("\u{2713} {} = {}", key, config.get_value(key)?);
+    Ok(())
+}
+
+/// Write the settings to `path`, or stdout
+fn export(path: Option<&Path>) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let content = config.export_settings()?;
+    match path {
+        Some(path) => {
+            fs::write(path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("\u{2713} Settings exported to {}", path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// Apply settings from a file and save
+fn import(path: &Path) -> Result<()> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut config = Config::load().context("Failed to load configuration")?;
+    let count = config
+        .import_settings(&content)
+        .with_context(|| format!("Nothing imported from {}", path.display()))?;
+    config.save().context("Failed to save configuration")?;
+    println!(
+        "\u{2713} Imported {} setting{} from {}",
+        count,
+        if count == 1 { "" } else { "s" },
+        path.display()
+    );
+    Ok(())
+}
+
+/// Interactive configuration wizard
 async fn run_init() -> Result<()> {
     let config_path = Config::config_path()?;
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
 
-    // Check if config already exists
-    if config_path.exists() {
+    // Existing settings become the wizard defaults
+    let mut config = if config_path.exists() {
         println!("Configuration file already exists at:");
         println!("  {}", config_path.display());
-        println!();
-        println!("To recreate, delete the file first and run 'todoee config --init' again.");
-        return Ok(());
+        if !confirm(&mut input, "Update it with the setup wizard?", false)? {
+            return Ok(());
+        }
+        Config::load().context("Failed to load configuration")?
+    } else {
+        Config::default()
+    };
+
+    println!();
+    println!("todoee setup");
+    println!("============");
+    println!("Press Enter to keep the value shown in brackets.");
+    println!();
+
+    // AI
+    println!("[AI]");
+    prompt_value(
+        &mut input,
+        &mut config,
+        "ai.provider",
+        &format!("Provider ({})", AI_PROVIDERS.join("/")),
+    )?;
+    prompt_value(
+        &mut input,
+        &mut config,
+        "ai.model",
+        "Model (e.g. openai/gpt-4o-mini, '-' to disable)",
+    )?;
+    if config.ai.needs_api_key() {
+        prompt_value(
+            &mut input,
+            &mut config,
+            "ai.api_key_env",
+            "API key environment variable",
+        )?;
+    }
+    prompt_value(
+        &mut input,
+        &mut config,
+        "ai.base_url",
+        "Custom endpoint URL (optional, '-' for the default)",
+    )?;
+    if config.ai.model.is_some() {
+        check_ai(&config).await;
     }
+    println!();
+
+    // Notifications
+    println!("[Notifications]");
+    prompt_value(
+        &mut input,
+        &mut config,
+        "notifications.enabled",
+        "Enable reminders (yes/no)",
+    )?;
+    if config.notifications.enabled {
+        prompt_value(
+            &mut input,
+            &mut config,
+            "notifications.sound",
+            "Play sound (yes/no)",
+        )?;
+        prompt_value(
+            &mut input,
+            &mut config,
+            "notifications.advance_minutes",
+            "Minutes of advance notice for events",
+        )?;
+    }
+    println!();
+
+    // Display
+    println!("[Display]");
+    prompt_value(
+        &mut input,
+        &mut config,
+        "display.theme",
+        &format!("Theme ({})", THEMES.join("/")),
+    )?;
+    prompt_value(
+        &mut input,
+        &mut config,
+        "display.density",
+        &format!("Density ({})", DENSITIES.join("/")),
+    )?;
+    prompt_value(
+        &mut input,
+        &mut config,
+        "display.date_input_order",
+        &format!(
+            "Typed date order, e.g. 05/03 ({})",
+            DATE_INPUT_ORDERS.join("/")
+        ),
+    )?;
+    println!();
+
+    // Database
+    println!("[Database]");
+    prompt_value(
+        &mut input,
+        &mut config,
+        "database.url_env",
+        "Cloud database URL environment variable",
+    )?;
+    if config.get_database_url().is_some() {
+        println!("  \u{2713} {} is set", config.database.url_env);
+    } else {
+        println!(
+            "  \u{2717} {} is not set (local-only until it is)",
+            config.database.url_env
+        );
+    }
+    println!();
 
-    // Create default config and save
-    let config = Config::default();
     config.save().context("Failed to save configuration")?;
 
-    println!("\u{2713} Configuration file created at:");
+    println!("\u{2713} Configuration saved to:");
     println!("  {}", config_path.display());
     println!();
+
+    if config.get_database_url().is_some() && confirm(&mut input, "Set up cloud sync now?", true)? {
+        provision_sync(&mut input, &config).await?;
+        println!();
+    }
     println!("Next steps:");
     println!();
-    println!("1. Set up AI parsing (optional):");
-    println!("   export OPENROUTER_API_KEY=your_api_key");
-    println!("   Then edit {} and set ai.model", config_path.display());
-    println!();
-    println!("2. Set up cloud sync (optional):");
-    println!("   export NEON_DATABASE_URL=your_connection_string");
-    println!();
-    println!("3. Start using todoee:");
-    println!("   todoee add \"Buy groceries\"");
-    println!("   todoee list");
+    if config.ai.needs_api_key() && config.get_ai_api_key().is_err() {
+        println!("  export {}=your_api_key", config.ai.api_key_env);
+    }
+    if config.get_database_url().is_none() {
+        println!(
+            "  export {}=your_connection_string",
+            config.database.url_env
+        );
+    }
+    println!("  todoee add \"Buy groceries\"");
+    println!("  todoee list");
     println!();
     println!("Run 'todoee config' to see current configuration status.");
 
     Ok(())
 }
 
+/// Connect to the cloud database, migrate it, link this device to a user
+/// and run a first sync, so problems show up here rather than at the first
+/// `todoee sync`. Remote failures are reported, not returned.
+async fn provision_sync(input: &mut impl BufRead, config: &Config) -> Result<()> {
+    println!("[Sync]");
+    print!("  Connecting and migrating the cloud database... ");
+    io::stdout().flush()?;
+    let service = match SyncService::new(config).await {
+        Ok(service) => service,
+        Err(e) => {
+            println!("\u{2717} {}", e);
+            return Ok(());
+        }
+    };
+    match service.ping().await {
+        Ok(rtt) => println!("\u{2713} {} ms", rtt.as_millis()),
+        Err(e) => {
+            println!("\u{2717} {}", e);
+            return Ok(());
+        }
+    }
+    if let Ok(version) = service.remote_schema_version().await {
+        println!("  \u{2713} Schema at version {}", version);
+    }
+
+    let default_email = Auth::load()
+        .ok()
+        .flatten()
+        .map(|auth| auth.email)
+        .unwrap_or_default();
+    let email = loop {
+        match prompt(input, "Email to sync as", &default_email)? {
+            Some(email) => break email,
+            None if !default_email.is_empty() => break default_email,
+            None => {}
+        }
+    };
+    let user = match service.bootstrap_user(&email).await {
+        Ok(user) => user,
+        Err(e) => {
+            println!("  \u{2717} {}", e);
+            return Ok(());
+        }
+    };
+    Auth::new(&user)
+        .save()
+        .context("Failed to save auth.json")?;
+    println!("  \u{2713} This device syncs as {}", user.email);
+
+    if let Err(e) = super::sync::sync_now(&service).await {
+        println!("  \u{2717} {:#}", e);
+    }
+    Ok(())
+}
+
+/// Prompt for a setting until a valid value (or Enter to keep) is given
+fn prompt_value(
+    input: &mut impl BufRead,
+    config: &mut Config,
+    key: &str,
+    label: &str,
+) -> Result<()> {
+    loop {
+        let current = config.get_value(key)?;
+        let answer = prompt(input, label, &current)?;
+        let Some(answer) = answer else {
+            return Ok(());
+        };
+        let value = if answer == "-" { "" } else { answer.as_str() };
+        match config.set_value(key, value) {
+            Ok(()) => return Ok(()),
+            Err(e) => println!("  \u{2717} {}", e),
+        }
+    }
+}
+
+/// Read one answer; returns None when the user keeps the default
+pub fn prompt(input: &mut impl BufRead, label: &str, default: &str) -> Result<Option<String>> {
+    if default.is_empty() {
+        print!("  {}: ", label);
+    } else {
+        print!("  {} [{}]: ", label, default);
+    }
+    io::stdout().flush()?;
+
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        anyhow::bail!("Cancelled (end of input)");
+    }
+    let line = line.trim();
+    Ok(if line.is_empty() {
+        None
+    } else {
+        Some(line.to_string())
+    })
+}
+
+/// Ask a yes/no question; an empty answer picks `default`
+pub fn confirm(input: &mut impl BufRead, question: &str, default: bool) -> Result<bool> {
+    let hint = if default { "Y/n" } else { "y/N" };
+    let answer = prompt(input, &format!("{} ({})", question, hint), "")?;
+    Ok(match answer.as_deref().map(str::to_lowercase).as_deref() {
+        Some("y") | Some("yes") => true,
+        Some("n") | Some("no") => false,
+        _ => default,
+    })
+}
+
+/// Validate the AI settings with a cheap request; failures are only reported
+async fn check_ai(config: &Config) {
+    if config.ai.needs_api_key() && config.get_ai_api_key().is_err() {
+        println!(
+            "  \u{2717} {} is not set - AI parsing will be unavailable until it is",
+            config.ai.api_key_env
+        );
+        return;
+    }
+
+    print!("  Checking {}... ", config.ai.provider);
+    let _ = io::stdout().flush();
+    let result = match AiClient::new(config) {
+        Ok(client) => client.ping().await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => println!("\u{2713} ok"),
+        Err(e) => println!("\u{2717} {}", e),
+    }
+}
+
 /// Show current configuration with status indicators
 async fn show_config() -> Result<()> {
     let config = Config::load().context("Failed to load configuration")?;
@@ -81,12 +424,21 @@ async fn show_config() -> Result<()> {
             .unwrap_or("(not set - AI parsing disabled)")
     );
 
+    if let Some(url) = &config.ai.base_url {
+        println!("  Endpoint: {}", url);
+    }
+
     let ai_key_set = env::var(&config.ai.api_key_env).is_ok();
-    if ai_key_set {
+    if !config.ai.needs_api_key() {
+        println!("  \u{2713} No API key needed");
+    } else if ai_key_set {
         println!("  \u{2713} {} is set", config.ai.api_key_env);
     } else {
         println!("  \u{2717} {} is not set", config.ai.api_key_env);
     }
+    if config.ai.retry_offline {
+        println!("  Offline parses are retried by todoee-daemon");
+    }
     println!();
 
     // Database Configuration
@@ -103,7 +455,39 @@ async fn show_config() -> Result<()> {
             config.database.url_env
         );
     }
+    if let Some(dir) = &config.database.sync_dir {
+        println!(
+            "  Sync directory: {} (used instead of the URL)",
+            dir.display()
+        );
+    }
     println!("  Local DB: {}", config.database.local_db_name);
+    println!("  Sync batch size: {}", config.database.sync_batch_size);
+    println!("  Connection pool: {}", config.database.pool_size);
+    match config.database.backups {
+        0 => println!("  Backups: off"),
+        n => println!("  Backups: daily, keeping {}", n),
+    }
+    println!();
+
+    // Network Configuration
+    println!("[Network]");
+    match net::proxy_url(&config.network) {
+        Some(proxy) if config.network.proxy.is_some() => println!("  Proxy: {}", proxy),
+        Some(proxy) => println!("  Proxy: {} (from environment)", proxy),
+        None => println!("  Proxy: none"),
+    }
+    if net::proxy_url(&config.network).is_some() {
+        println!("  Direct: {}", net::no_proxy(&config.network));
+    }
+    if let Some(path) = &config.network.ca_cert {
+        let mark = if path.is_file() {
+            "\u{2713}"
+        } else {
+            "\u{2717}"
+        };
+        println!("  {} CA certificate: {}", mark, path.display());
+    }
     println!();
 
     // Notification Configuration
@@ -128,12 +512,157 @@ async fn show_config() -> Result<()> {
         "  Advance notice: {} minutes",
         config.notifications.advance_minutes
     );
+    let advance = |value: &str| {
+        if value.is_empty() {
+            "off".to_string()
+        } else {
+            value.to_string()
+        }
+    };
+    println!(
+        "  Due alerts: high {}, medium {}, low {}",
+        advance(&config.notifications.advance_high),
+        advance(&config.notifications.advance_medium),
+        advance(&config.notifications.advance_low)
+    );
+    println!(
+        "  Reminders from due dates: {}",
+        if config.notifications.derive_reminders {
+            "yes"
+        } else {
+            "no"
+        }
+    );
+    match config.notifications.batch_threshold {
+        0 => println!("  Combine: never"),
+        n => println!("  Combine: {} or more at once", n),
+    }
+    match config.notifications.nag_minutes {
+        0 => println!("  Repeat when overdue: never"),
+        n => println!("  Repeat when overdue: every {} minutes", n),
+    }
+    println!(
+        "  Daily agenda: {}",
+        if config.notifications.daily_summary_time.is_empty() {
+            "off"
+        } else {
+            &config.notifications.daily_summary_time
+        }
+    );
     println!();
 
     // Display Configuration
     println!("[Display]");
     println!("  Theme: {}", config.display.theme);
+    for (role, color) in &config.display.colors {
+        println!("    {}: {}", role, color);
+    }
     println!("  Date format: {}", config.display.date_format);
+    println!("  Density: {}", config.display.density);
+    println!("  Date input order: {}", config.display.date_input_order);
+    println!("  ID length: {}", config.display.id_length);
+    println!("  Animations: {}", config.display.animations);
+    println!("  Reduced motion: {}", config.display.reduced_motion);
+    println!("  Celebrate inbox zero: {}", config.display.celebrate);
+    println!(
+        "  Due urgent / soon within: {} / {} days",
+        config.display.due_urgent_days, config.display.due_soon_days
+    );
+    println!();
+
+    // Update Configuration
+    println!("[Updates]");
+    let yes_no = |on: bool| if on { "yes" } else { "no" };
+    println!("  Weekly version check: {}", yes_no(config.updates.check));
+    println!("  Self-update: {}", yes_no(config.updates.self_update));
+    println!();
+
+    // Cleanup Configuration
+    println!("[Cleanup]");
+    if config.gc.auto {
+        println!(
+            "  Automatic: {}, removing data older than {} days",
+            config.gc.interval, config.gc.days
+        );
+    } else {
+        println!("  Automatic: off (run 'todoee gc' by hand)");
+    }
+    let excluded = config.gc.excluded_categories();
+    if !excluded.is_empty() {
+        println!("  Keeping completed todos in: {}", excluded.join(", "));
+    }
+    println!();
+
+    // History Configuration
+    println!("[History]");
+    let history = &config.history;
+    if history.max_operations > 0 {
+        println!("  Kept: up to {} operations", history.max_operations);
+    } else {
+        println!("  Kept: all operations");
+    }
+    println!(
+        "  Compacted after {} days{}",
+        history.compact_after_days,
+        if history.auto_compact {
+            ", hourly by todoee-daemon"
+        } else {
+            ""
+        }
+    );
+    println!();
+
+    // Ranking Configuration
+    println!("[Ranking]");
+    let ranking = &config.ranking;
+    println!(
+        "  Priority weights: urgent {}, high {}, medium {}, low {}",
+        ranking.urgent, ranking.high, ranking.medium, ranking.low
+    );
+    println!();
+
+    // Time Blocking Configuration
+    println!("[Timeblock]");
+    let timeblock = &config.timeblock;
+    println!(
+        "  Working day: {}-{}, {} minute blocks",
+        timeblock.day_start, timeblock.day_end, timeblock.block_minutes
+    );
+    println!();
+
+    // GitHub Configuration
+    println!("[GitHub]");
+    let github = &config.github;
+    println!("  Token env: {}", github.token_env);
+    println!("  API URL: {}", github.api_url);
+    println!("  Close issues: {}", github.close_issues);
+    for (label, category) in &github.labels {
+        println!("  Label {} -> {}", label, category);
+    }
+    println!();
+
+    // Webhooks
+    if !config.webhooks.is_empty() {
+        println!("[Webhooks]");
+        for (name, hook) in &config.webhooks {
+            let events: Vec<String> = hook.events.iter().map(|e| e.to_string()).collect();
+            println!("  {} = {} ({})", name, hook.url, events.join(", "));
+        }
+        println!();
+    }
+
+    // Aliases
+    if !config.aliases.is_empty() {
+        println!("[Aliases]");
+        for (name, expansion) in &config.aliases {
+            println!("  {} = {}", name, expansion);
+        }
+        println!();
+    }
+    println!(
+        "Keys for 'todoee config get/set': {}",
+        CONFIG_KEYS.join(", ")
+    );
 
     Ok(())
 }
@@ -0,0 +1,205 @@
+//! `todoee conflicts`: todos changed both here and on another device since
+//! the last sync, kept with both versions until you pick what to keep.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use todoee_core::conflict::{Field, Side};
+use todoee_core::{
+    Config, Conflict, EntityType, IdMatch, LocalDb, Operation, OperationType, SyncStatus, short_id,
+};
+use uuid::Uuid;
+
+use super::config::prompt;
+use super::{ids, output};
+
+#[derive(Subcommand, Clone)]
+pub enum ConflictsCommand {
+    /// List todos in conflict, field by field
+    List,
+    /// Keep one version of a todo in conflict, or pick each field
+    Resolve {
+        /// Todo ID (or prefix)
+        id: String,
+        /// Version to keep; merge asks for each field that differs
+        #[arg(long, value_parser = ["local", "remote", "merge"])]
+        keep: String,
+    },
+}
+
+pub async fn run(cmd: ConflictsCommand) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db = LocalDb::new(&config.local_db_path()?).await?;
+    db.run_migrations().await?;
+
+    match cmd {
+        ConflictsCommand::List => list(&db, &config).await,
+        ConflictsCommand::Resolve { id, keep } => resolve(&db, &config, &id, &keep).await,
+    }
+}
+
+async fn list(db: &LocalDb, config: &Config) -> Result<()> {
+    let conflicts = db.list_conflicts().await?;
+    if output::is_json() {
+        let conflicts: Vec<_> = conflicts
+            .iter()
+            .map(|c| {
+                serde_json::json!({
+                    "local": c.local,
+                    "remote": c.remote,
+                    "detected_at": c.detected_at,
+                    "fields": c.fields().iter().map(|f| f.name()).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        return output::print_json(&conflicts);
+    }
+    if conflicts.is_empty() {
+        output::say!("No sync conflicts");
+        return Ok(());
+    }
+
+    let names = names(db).await?;
+    let id_len = ids::id_length(db, config).await?;
+    println!(
+        "{} todo{} changed here and on another device (local | remote):",
+        conflicts.len(),
+        if conflicts.len() == 1 { "" } else { "s" }
+    );
+    for conflict in &conflicts {
+        println!();
+        println!(
+            "  [{}] {}",
+            short_id(&conflict.local.id, id_len),
+            conflict.local.display_title()
+        );
+        print_fields(conflict, &names);
+    }
+    println!();
+    println!("Resolve with: todoee conflicts resolve <id> --keep local|remote|merge");
+    Ok(())
+}
+
+async fn resolve(db: &LocalDb, config: &Config, id: &str, keep: &str) -> Result<()> {
+    let todo = match ids::resolve(db, config, id).await? {
+        IdMatch::Unique(todo) => todo,
+        IdMatch::NotFound => anyhow::bail!("No todo found matching '{}'", id),
+        IdMatch::Ambiguous(_) => {
+            anyhow::bail!("Multiple todos match '{}'; provide more characters", id)
+        }
+    };
+    let Some(conflict) = db.get_conflict(todo.id).await? else {
+        anyhow::bail!("'{}' has no sync conflict", todo.title);
+    };
+
+    let resolved = match keep {
+        "local" => conflict.resolve(|_| Side::Local),
+        "remote" => conflict.resolve(|_| Side::Remote),
+        _ => {
+            let names = names(db).await?;
+            let stdin = io::stdin();
+            let picks = pick_fields(&mut stdin.lock(), &conflict, &names)?;
+            conflict.resolve(|f| picks[&f])
+        }
+    };
+    let op = Operation::new(
+        OperationType::Update,
+        EntityType::Todo,
+        resolved.id,
+        serde_json::to_value(&conflict.local).ok(),
+        serde_json::to_value(&resolved).ok(),
+    );
+    db.resolve_conflict(&resolved, &op).await?;
+
+    if output::is_json() {
+        return output::print_json(&resolved);
+    }
+    output::say!("\u{2713} Resolved: {}", resolved.display_title());
+    if resolved.sync_status == SyncStatus::Pending {
+        output::say!("  Run `todoee sync` to send it to your other devices");
+    }
+    Ok(())
+}
+
+/// Ask which side to keep for each field that differs
+fn pick_fields(
+    input: &mut impl BufRead,
+    conflict: &Conflict,
+    names: &HashMap<Uuid, String>,
+) -> Result<HashMap<Field, Side>> {
+    let mut picks = HashMap::new();
+    for field in conflict.fields() {
+        println!(
+            "  {}: local {:?}, remote {:?}",
+            field.name(),
+            field.display(&conflict.local, names),
+            field.display(&conflict.remote, names)
+        );
+        let side = loop {
+            match prompt(input, "Keep (l)ocal or (r)emote", "l")?.as_deref() {
+                None | Some("l") | Some("local") => break Side::Local,
+                Some("r") | Some("remote") => break Side::Remote,
+                Some(_) => {}
+            }
+        };
+        picks.insert(field, side);
+    }
+    Ok(picks)
+}
+
+fn print_fields(conflict: &Conflict, names: &HashMap<Uuid, String>) {
+    for field in conflict.fields() {
+        println!(
+            "      {:<14} {}  \x1b[90m|\x1b[0m  {}",
+            field.name(),
+            field.display(&conflict.local, names),
+            field.display(&conflict.remote, names)
+        );
+    }
+}
+
+/// Category and project names by ID, to show them by name
+async fn names(db: &LocalDb) -> Result<HashMap<Uuid, String>> {
+    let mut names: HashMap<Uuid, String> = db
+        .list_categories()
+        .await?
+        .into_iter()
+        .map(|c| (c.id, c.name))
+        .collect();
+    names.extend(
+        db.list_projects(true)
+            .await?
+            .into_iter()
+            .map(|p| (p.id, p.name)),
+    );
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use todoee_core::{Priority, Todo};
+
+    #[test]
+    fn test_merge_asks_for_each_differing_field() {
+        let local = Todo::new("Buy milk".to_string(), None);
+        let mut remote = local.clone();
+        remote.title = "Buy oat milk".to_string();
+        remote.priority = Priority::Urgent;
+        let conflict = Conflict {
+            local,
+            remote,
+            detected_at: chrono::Utc::now(),
+        };
+
+        let mut input = "r\nmaybe\n\n".as_bytes();
+        let picks = pick_fields(&mut input, &conflict, &HashMap::new()).unwrap();
+        assert_eq!(picks[&Field::Title], Side::Remote);
+        assert_eq!(picks[&Field::Priority], Side::Local);
+        let merged = conflict.resolve(|f| picks[&f]);
+        assert_eq!(merged.title, "Buy oat milk");
+        assert_eq!(merged.priority, Priority::Medium);
+    }
+}
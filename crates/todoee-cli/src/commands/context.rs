@@ -0,0 +1,132 @@
+//! Contexts: named filters that narrow list, search, now and the TUI until
+//! cleared, like taskwarrior's.
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use clap::Subcommand;
+use todoee_core::{Config, Filter, FilterNames, LocalDb, Todo};
+
+use super::output;
+
+#[derive(Subcommand, Clone)]
+pub enum ContextCommand {
+    /// Create or replace a context
+    Define {
+        /// Context name
+        name: String,
+        /// Filter, e.g. "category:work or priority:high"
+        filter: String,
+    },
+    /// Apply a context until `todoee context none`
+    Set {
+        /// Context name
+        name: String,
+    },
+    /// Stop filtering by context
+    None,
+    /// List contexts, marking the active one
+    List,
+    /// Remove a context
+    Delete {
+        /// Context name
+        name: String,
+    },
+}
+
+pub fn run(cmd: ContextCommand) -> Result<()> {
+    let mut config = Config::load().context("Failed to load configuration")?;
+
+    match cmd {
+        ContextCommand::Define { name, filter } => {
+            config.contexts.define(&name, &filter)?;
+            config.save()?;
+            output::say!("\u{2713} Defined context '{}': {}", name, filter.trim());
+            if config.contexts.active.as_deref() != Some(name.as_str()) {
+                output::say!("  Use it: todoee context set {}", name);
+            }
+        }
+        ContextCommand::Set { name } => {
+            if !config.contexts.filters.contains_key(&name) {
+                anyhow::bail!(
+                    "No context named '{}'. Define it with: todoee context define {} \"<filter>\"",
+                    name,
+                    name
+                );
+            }
+            config.contexts.active = Some(name.clone());
+            config.save()?;
+            output::say!("\u{2713} Context '{}' is active", name);
+        }
+        ContextCommand::None => {
+            match config.contexts.active.take() {
+                Some(name) => output::say!("\u{2713} Cleared context '{}'", name),
+                None => output::say!("No context is active."),
+            }
+            config.save()?;
+        }
+        ContextCommand::List => list(&config)?,
+        ContextCommand::Delete { name } => {
+            if config.contexts.filters.remove(&name).is_none() {
+                anyhow::bail!("No context named '{}'", name);
+            }
+            if config.contexts.active.as_deref() == Some(name.as_str()) {
+                config.contexts.active = None;
+            }
+            config.save()?;
+            output::say!("\u{2713} Deleted context '{}'", name);
+        }
+    }
+    Ok(())
+}
+
+fn list(config: &Config) -> Result<()> {
+    let contexts = &config.contexts;
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "active": contexts.active,
+            "contexts": contexts.filters,
+        }));
+    }
+
+    if contexts.filters.is_empty() {
+        println!("No contexts. Define one with:");
+        println!("  todoee context define work \"category:work or priority:high\"");
+        return Ok(());
+    }
+    let width = contexts.filters.keys().map(|k| k.len()).max().unwrap_or(0);
+    for (name, filter) in &contexts.filters {
+        if contexts.active.as_deref() == Some(name.as_str()) {
+            println!("\x1b[32m* {:<width$}\x1b[0m  {}", name, filter);
+        } else {
+            println!("  {:<width$}  {}", name, filter);
+        }
+    }
+    Ok(())
+}
+
+/// The context in effect, with what it needs to filter todos
+pub struct ActiveContext {
+    pub name: String,
+    filter: Filter,
+    names: FilterNames,
+}
+
+impl ActiveContext {
+    /// The active context from `config`, or `None` when no context is set
+    pub async fn load(db: &LocalDb, config: &Config) -> Result<Option<Self>> {
+        let Some((name, filter)) = config.contexts.active_filter()? else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            name: name.to_string(),
+            filter,
+            names: db.filter_names().await?,
+        }))
+    }
+
+    /// Keep only the todos in this context
+    pub fn retain(&self, todos: &mut Vec<Todo>) {
+        let now = Local::now();
+        todos.retain(|todo| self.filter.matches(todo, &self.names, &now));
+    }
+}
@@ -1,7 +1,12 @@
 //! Daemon management commands.
 
 use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
 use std::process::Command;
+use todoee_core::config::PROFILE_ENV;
+use todoee_core::{Config, DaemonStatus, dates};
+
+use super::output;
 
 pub async fn run_start() -> Result<()> {
     // Check if daemon is already running
@@ -29,7 +34,12 @@ pub async fn run_start() -> Result<()> {
         return Ok(());
     }
 
-    Command::new(&daemon_path)
+    let mut daemon = Command::new(&daemon_path);
+    // Watch the profile this command runs in, even if given by --profile
+    if let Some(profile) = Config::profile() {
+        daemon.env(PROFILE_ENV, profile);
+    }
+    daemon
         .spawn()
         .map_err(|e| anyhow::anyhow!("Failed to start daemon: {}", e))?;
 
@@ -83,6 +93,14 @@ pub async fn run_stop() -> Result<()> {
 }
 
 pub async fn run_status() -> Result<()> {
+    let status = DaemonStatus::load()?;
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "running": is_daemon_running().ok(),
+            "status": status,
+        }));
+    }
+
     match is_daemon_running() {
         Ok(true) => {
             println!("\u{2713} Daemon is running.");
@@ -95,9 +113,55 @@ pub async fn run_status() -> Result<()> {
             println!("\u{26a0}  Could not determine daemon status: {}", e);
         }
     }
+
+    let Some(status) = status else {
+        return Ok(());
+    };
+    let now = Utc::now();
+    println!();
+    println!("  PID:            {}", status.pid);
+    println!("  Started:        {}", when(status.started_at, now));
+    match status.last_check {
+        Some(at) => println!("  Last check:     {}", when(at, now)),
+        None => println!("  Last check:     none yet"),
+    }
+    if status.is_stale(now) {
+        println!(
+            "  \x1b[33mNo check for a while; this status may be from a daemon that has exited.\x1b[0m"
+        );
+    }
+    println!("  Reminders sent: {}", status.reminders_sent);
+    match status.last_sync {
+        Some(at) => println!("  Last sync:      {}", when(at, now)),
+        None => println!("  Last sync:      never"),
+    }
+    if !status.errors.is_empty() {
+        println!();
+        println!("  Recent errors:");
+        for error in status.errors.iter().rev().take(5) {
+            println!(
+                "    \x1b[90m{}\x1b[0m {}",
+                error.at.with_timezone(&Local).format("%m-%d %H:%M"),
+                error.message
+            );
+        }
+    }
     Ok(())
 }
 
+/// Local time and how long ago, e.g. "2026-10-16 09:30 (5m ago)"
+fn when(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let ago = match dates::format_duration(now - at).as_str() {
+        "0" => "just now".to_string(),
+        elapsed => format!("{} ago", elapsed),
+    };
+    format!(
+        "{} ({})",
+        at.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+        ago
+    )
+}
+
 fn is_daemon_running() -> Result<bool> {
     #[cfg(unix)]
     {
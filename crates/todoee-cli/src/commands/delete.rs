@@ -1,6 +1,8 @@
 use anyhow::{Context, Result};
 use std::fs;
-use todoee_core::{Config, EntityType, LocalDb, Operation, OperationType, Todo};
+use todoee_core::{Config, EntityType, IdMatch, LocalDb, Operation, OperationType, short_id};
+
+use super::{ids, output};
 
 pub async fn run(id: String) -> Result<()> {
     // Load config and open local database
@@ -18,20 +20,15 @@ pub async fn run(id: String) -> Result<()> {
     let db = LocalDb::new(&db_path).await?;
     db.run_migrations().await?;
 
-    // Find todos matching the partial ID
-    let matches = find_todos_by_partial_id(&db, &id).await?;
-
-    match matches.len() {
-        0 => {
+    // Find the todo matching the list number or partial ID
+    match ids::resolve(&db, &config, &id).await? {
+        IdMatch::NotFound => {
             // No match found
             eprintln!("No todo found matching '{}'", id);
             eprintln!("Hint: Use 'todoee list --all' to see all todos including completed ones.");
             anyhow::bail!("Todo not found");
         }
-        1 => {
-            // Single match found
-            let todo = matches.into_iter().next().unwrap();
-
+        IdMatch::Unique(todo) => {
             // Record operation BEFORE deleting for undo support
             let op = Operation::new(
                 OperationType::Delete,
@@ -45,15 +42,19 @@ pub async fn run(id: String) -> Result<()> {
             // Delete the todo
             db.delete_todo(todo.id).await?;
 
-            println!("\u{2717} Deleted: {}", todo.title);
+            if output::is_json() {
+                return output::print_json(&todo);
+            }
+            output::say!("\u{2717} Deleted: {}", todo.title);
+            output::detail!("Full ID: {}", todo.id);
         }
-        _ => {
-            // Multiple matches - ask for more specific ID
+        IdMatch::Ambiguous(matches) => {
             eprintln!("Multiple todos match '{}'. Please be more specific:", id);
             eprintln!();
+            let id_len = ids::match_length(&matches);
             for todo in &matches {
                 let status = if todo.is_completed { "[x]" } else { "[ ]" };
-                let short_id = &todo.id.to_string()[..8];
+                let short_id = short_id(&todo.id, id_len);
                 eprintln!("  {} {} [{}]", status, todo.title, short_id);
             }
             anyhow::bail!("Ambiguous ID - provide more characters");
@@ -62,21 +63,3 @@ pub async fn run(id: String) -> Result<()> {
 
     Ok(())
 }
-
-/// Find todos whose ID starts with the given prefix
-async fn find_todos_by_partial_id(db: &LocalDb, prefix: &str) -> Result<Vec<Todo>> {
-    let prefix_lower = prefix.to_lowercase();
-    let all_todos = db.list_todos(false).await?;
-
-    let matches: Vec<Todo> = all_todos
-        .into_iter()
-        .filter(|todo| {
-            todo.id
-                .to_string()
-                .to_lowercase()
-                .starts_with(&prefix_lower)
-        })
-        .collect();
-
-    Ok(matches)
-}
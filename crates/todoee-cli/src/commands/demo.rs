@@ -0,0 +1,266 @@
+//! Demo mode (`--demo`): every command and the TUI run against a stand-in
+//! copy of the database whose titles, descriptions and names are replaced by
+//! made-up ones, so screenshots, recordings and bug reports don't show real
+//! tasks. The copy is rebuilt on every run and changes to it are thrown away.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use todoee_core::{Config, LocalDb};
+
+/// File name of the stand-in database, next to the real one
+const DEMO_DB_NAME: &str = "demo.db";
+
+pub(crate) const CATEGORY_NAMES: &[&str] = &[
+    "Work",
+    "Home",
+    "Errands",
+    "Health",
+    "Finance",
+    "Reading",
+    "Garden",
+    "Side project",
+    "Family",
+    "Travel",
+    "Learning",
+    "Car",
+];
+
+const PROJECT_NAMES: &[&str] = &[
+    "Launch",
+    "Website refresh",
+    "Kitchen remodel",
+    "Q3 planning",
+    "Move house",
+    "Onboarding",
+    "Conference talk",
+    "Garden shed",
+];
+
+pub(crate) const VERBS: &[&str] = &[
+    "Call", "Email", "Review", "Buy", "Fix", "Plan", "Write", "Book", "Pay", "Clean", "Update",
+    "Renew", "Schedule", "Prepare", "Sort out",
+];
+
+pub(crate) const OBJECTS: &[&str] = &[
+    "dentist appointment",
+    "quarterly report",
+    "groceries",
+    "bike tyre",
+    "team offsite",
+    "blog post",
+    "flights to Lisbon",
+    "electricity bill",
+    "garage",
+    "passport",
+    "release notes",
+    "insurance",
+    "birthday present",
+    "pull request",
+    "tax return",
+    "gym membership",
+    "kitchen shelf",
+    "slides for Monday",
+];
+
+pub(crate) const DESCRIPTIONS: &[&str] = &[
+    "Check the shared drive first",
+    "Ask Sam for the latest numbers",
+    "Keep the receipt",
+    "Before the end of the month",
+    "See the email thread from last week",
+];
+
+/// Build the stand-in database and switch this process over to it
+pub async fn start() -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let real_path = config.local_db_path()?;
+    let demo_path = Config::config_dir()?.join(DEMO_DB_NAME);
+    if demo_path == real_path {
+        anyhow::bail!(
+            "database.local_db_name must not be '{}' to use --demo",
+            DEMO_DB_NAME
+        );
+    }
+
+    // Start from scratch so nothing from an earlier demo run survives
+    for suffix in ["", "-wal", "-shm"] {
+        let path = demo_path.with_file_name(format!("{}{}", DEMO_DB_NAME, suffix));
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                return Err(e).with_context(|| {
+                    format!("Failed to remove old demo database: {}", path.display())
+                });
+            }
+            _ => {}
+        }
+    }
+    if let Some(parent) = demo_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let demo = LocalDb::new(&demo_path).await?;
+    demo.run_migrations().await?;
+    if real_path.exists() {
+        copy_anonymized(&real_path, &demo).await?;
+    }
+
+    Config::use_demo_db(demo_path);
+    Ok(())
+}
+
+/// Copy todos, categories, projects and events from the database at `real`
+/// into `demo` with made-up text. IDs, dates, priorities and completion are
+/// kept, so lists look and sort the same. History, stash and linked notes
+/// are left behind.
+async fn copy_anonymized(real: &Path, demo: &LocalDb) -> Result<()> {
+    let real = LocalDb::new(real).await?;
+    real.run_migrations().await?;
+
+    let mut used = HashSet::new();
+    for mut category in real.list_categories().await? {
+        category.name = unique_name(&category.name, CATEGORY_NAMES, &mut used);
+        demo.create_category(&category).await?;
+    }
+
+    let mut used = HashSet::new();
+    for mut project in real.list_projects(true).await? {
+        project.name = unique_name(&project.name, PROJECT_NAMES, &mut used);
+        demo.create_project(&project).await?;
+    }
+
+    for mut todo in real.list_todos(false).await? {
+        todo.title = title(&todo.title);
+        todo.description = todo.description.as_deref().map(description);
+        todo.ai_metadata = None;
+        demo.create_todo(&todo).await?;
+    }
+
+    for mut event in real.list_events().await? {
+        event.title = title(&event.title);
+        event.description = event.description.as_deref().map(description);
+        demo.create_event(&event).await?;
+    }
+
+    Ok(())
+}
+
+/// A made-up task name for `original`; the same title always gets the same name
+pub fn title(original: &str) -> String {
+    let hash = fnv1a(original);
+    format!(
+        "{} {}",
+        VERBS[(hash % VERBS.len() as u64) as usize],
+        OBJECTS[((hash >> 16) % OBJECTS.len() as u64) as usize]
+    )
+}
+
+fn description(original: &str) -> String {
+    let hash = fnv1a(original);
+    DESCRIPTIONS[(hash % DESCRIPTIONS.len() as u64) as usize].to_string()
+}
+
+/// A name from `names` picked by hashing `original`, numbered if already taken
+fn unique_name(original: &str, names: &[&str], used: &mut HashSet<String>) -> String {
+    let base = names[(fnv1a(original) % names.len() as u64) as usize];
+    let mut name = base.to_string();
+    let mut n = 2;
+    while !used.insert(name.clone()) {
+        name = format!("{} {}", base, n);
+        n += 1;
+    }
+    name
+}
+
+/// FNV-1a, which unlike the std hasher gives the same value on every run
+/// and Rust version
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use todoee_core::{Category, Project, Todo};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_title_is_deterministic() {
+        assert_eq!(
+            title("Call mum about Sunday"),
+            title("Call mum about Sunday")
+        );
+        assert_ne!(title("Call mum about Sunday"), "Call mum about Sunday");
+        assert_eq!(fnv1a(""), 0xcbf2_9ce4_8422_2325);
+
+        let mut used = HashSet::new();
+        let first = unique_name("Acme Corp", CATEGORY_NAMES, &mut used);
+        let second = unique_name("Acme Corp", CATEGORY_NAMES, &mut used);
+        assert_eq!(second, format!("{} 2", first));
+    }
+
+    #[tokio::test]
+    async fn test_copy_hides_text_and_keeps_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let real_path = temp_dir.path().join("real.db");
+        let real = LocalDb::new(&real_path).await.unwrap();
+        real.run_migrations().await.unwrap();
+
+        let category = Category::new(Uuid::nil(), "Acme Corp".to_string());
+        let project = Project::new(Uuid::nil(), "Secret merger".to_string());
+        let mut todo = Todo::new("Email Jane about the merger".to_string(), None);
+        todo.category_id = Some(category.id);
+        todo.project_id = Some(project.id);
+        todo.description = Some("Account 1234".to_string());
+        let mut done = Todo::new("Pay Dr Smith".to_string(), None);
+        done.mark_complete();
+        real.create_category(&category).await.unwrap();
+        real.create_project(&project).await.unwrap();
+        real.create_todo(&todo).await.unwrap();
+        real.create_todo(&done).await.unwrap();
+
+        let demo = LocalDb::new(&temp_dir.path().join("demo.db"))
+            .await
+            .unwrap();
+        demo.run_migrations().await.unwrap();
+        copy_anonymized(&real_path, &demo).await.unwrap();
+
+        let copied = demo.get_todo(todo.id).await.unwrap().unwrap();
+        assert_eq!(copied.title, title(&todo.title));
+        assert_eq!(
+            copied.description.as_deref(),
+            Some(description("Account 1234").as_str())
+        );
+        assert_eq!(copied.category_id, Some(category.id));
+        assert_eq!(copied.project_id, Some(project.id));
+        assert!(demo.get_todo(done.id).await.unwrap().unwrap().is_completed);
+
+        let names: Vec<_> = demo
+            .list_categories()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert!(!names.contains(&"Acme Corp".to_string()));
+        assert!(
+            demo.get_project_by_name("Secret merger")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // The real database is untouched
+        let original = real.get_todo(todo.id).await.unwrap().unwrap();
+        assert_eq!(original.title, "Email Jane about the merger");
+    }
+}
@@ -0,0 +1,268 @@
+//! Developer tools, built with `--features dev-tools`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use clap::Subcommand;
+use todoee_core::{Category, Config, LocalDb, Operation, Priority, Todo, dates};
+use uuid::Uuid;
+
+use super::demo::{CATEGORY_NAMES, DESCRIPTIONS, OBJECTS, VERBS};
+use super::output;
+
+#[derive(Subcommand, Clone)]
+pub enum DevCommand {
+    /// Fill a database with synthetic todos for benchmarks and screenshots
+    Seed {
+        /// Number of todos to create
+        #[arg(long, default_value = "1000")]
+        todos: usize,
+
+        /// Number of categories to spread them over
+        #[arg(long, default_value = "8")]
+        categories: usize,
+
+        /// How many days of history to spread creation dates over
+        #[arg(long, default_value = "90")]
+        days: i64,
+
+        /// Random seed; the same seed gives the same data
+        #[arg(long, default_value = "1")]
+        seed: u64,
+
+        /// Database file to fill (default: the configured database)
+        #[arg(long, value_name = "PATH")]
+        db: Option<PathBuf>,
+    },
+}
+
+/// Small deterministic generator (SplitMix64), so a seed always gives the
+/// same database without pulling in a random number crate
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n.max(1)
+    }
+
+    /// True with the given percentage chance
+    fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len() as u64) as usize]
+    }
+}
+
+pub async fn run(command: DevCommand) -> Result<()> {
+    match command {
+        DevCommand::Seed {
+            todos,
+            categories,
+            days,
+            seed,
+            db,
+        } => run_seed(todos, categories, days, seed, db).await,
+    }
+}
+
+async fn run_seed(
+    todos: usize,
+    categories: usize,
+    days: i64,
+    seed: u64,
+    db_path: Option<PathBuf>,
+) -> Result<()> {
+    if days < 1 {
+        anyhow::bail!("Invalid value for --days: expected at least 1");
+    }
+    let db_path = match db_path {
+        Some(path) => path,
+        None => Config::load()
+            .context("Failed to load configuration")?
+            .local_db_path()?,
+    };
+    let db = open_db(&db_path).await?;
+
+    // Categories already seeded earlier are reused, so seeding can be repeated
+    let mut seeded = Vec::new();
+    for name in category_names(categories) {
+        let category = match db.get_category_by_name(&name).await? {
+            Some(category) => category,
+            None => {
+                let category = Category::new(Uuid::nil(), name);
+                db.create_category(&category).await?;
+                category
+            }
+        };
+        seeded.push(category);
+    }
+    let categories = seeded;
+
+    let todos = generate(todos, &categories, days, seed, Utc::now());
+    db.create_todos(&todos, &Operation::batch_create(&todos))
+        .await?;
+
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "db": db_path,
+            "todos": todos.len(),
+            "categories": categories.len(),
+        }));
+    }
+    println!(
+        "\u{2713} Seeded {} todos in {} categories into {}",
+        todos.len(),
+        categories.len(),
+        db_path.display()
+    );
+    Ok(())
+}
+
+async fn open_db(db_path: &Path) -> Result<LocalDb> {
+    if let Some(parent) = db_path.parent()
+        && !parent.as_os_str().is_empty()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let db = LocalDb::new(db_path).await?;
+    db.run_migrations().await?;
+    Ok(db)
+}
+
+/// `count` category names, numbered once the built-in names run out
+fn category_names(count: usize) -> Vec<String> {
+    (0..count)
+        .map(|i| {
+            let name = CATEGORY_NAMES[i % CATEGORY_NAMES.len()];
+            match i / CATEGORY_NAMES.len() {
+                0 => name.to_string(),
+                round => format!("{} {}", name, round + 1),
+            }
+        })
+        .collect()
+}
+
+/// Synthetic todos in `categories`, created over the `days` before `now`.
+///
+/// Older todos are more likely to be done, due dates cluster around the
+/// creation date with some overdue, and priorities lean towards medium.
+fn generate(
+    todo_count: usize,
+    categories: &[Category],
+    days: i64,
+    seed: u64,
+    now: DateTime<Utc>,
+) -> Vec<Todo> {
+    let mut rng = Rng(seed);
+    let span = days * 24 * 60;
+    (0..todo_count)
+        .map(|_| {
+            let mut todo = Todo::new(format!("{} {}", rng.pick(VERBS), rng.pick(OBJECTS)), None);
+            let age = Duration::minutes(rng.below(span as u64) as i64);
+            todo.created_at = now - age;
+            todo.updated_at = todo.created_at;
+
+            if !categories.is_empty() && rng.chance(80) {
+                todo.category_id = Some(categories[rng.below(categories.len() as u64) as usize].id);
+            }
+            todo.priority = match rng.below(10) {
+                0..=1 => Priority::High,
+                2..=7 => Priority::Medium,
+                _ => Priority::Low,
+            };
+            if rng.chance(20) {
+                todo.description = Some(rng.pick(DESCRIPTIONS).to_string());
+            }
+            if rng.chance(70) {
+                let due = todo.created_at + Duration::hours(rng.below(24 * 21) as i64);
+                let due = if rng.chance(50) {
+                    dates::with_due_time(&due, None, &Utc)
+                } else {
+                    due
+                };
+                todo.due_date = Some(due);
+                // Before the stored due time, which for date-only todos is
+                // noon rather than the hour picked above
+                if rng.chance(15) {
+                    todo.reminder_at = Some(due - Duration::hours(2));
+                }
+            }
+
+            // The older a todo, the more likely it is done
+            let done_chance = 20 + 60 * age.num_minutes() as u64 / span.max(1) as u64;
+            if rng.chance(done_chance) {
+                let completed = todo.created_at
+                    + Duration::minutes(rng.below(age.num_minutes().max(1) as u64) as i64);
+                todo.is_completed = true;
+                todo.completed_at = Some(completed);
+                todo.updated_at = completed;
+            }
+            todo
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_generate_is_reproducible() {
+        let names = category_names(20);
+        assert_eq!(names[0], "Work");
+        assert_eq!(names[12], "Work 2");
+        let categories: Vec<_> = names
+            .into_iter()
+            .map(|name| Category::new(Uuid::nil(), name))
+            .collect();
+
+        let now = Utc::now();
+        let todos = generate(500, &categories, 365, 7, now);
+        assert_eq!(todos.len(), 500);
+        assert!(todos.iter().all(|t| t.created_at <= now));
+        assert!(
+            todos
+                .iter()
+                .all(|t| t.created_at >= now - Duration::days(365))
+        );
+        assert!(todos.iter().any(|t| t.is_completed));
+        assert!(todos.iter().any(|t| !t.is_completed));
+
+        let again = generate(500, &categories, 365, 7, now);
+        let titles = |todos: &[Todo]| todos.iter().map(|t| t.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(&todos), titles(&again));
+    }
+
+    #[tokio::test]
+    async fn test_seed_fills_database() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("seed").join("bench.db");
+        run_seed(200, 5, 30, 1, Some(db_path.clone()))
+            .await
+            .unwrap();
+        // Seeding again adds more todos to the same categories
+        run_seed(200, 5, 30, 1, Some(db_path.clone()))
+            .await
+            .unwrap();
+
+        let db = open_db(&db_path).await.unwrap();
+        assert_eq!(db.list_todos(false).await.unwrap().len(), 400);
+        assert_eq!(db.list_categories().await.unwrap().len(), 5);
+    }
+}
@@ -0,0 +1,196 @@
+//! Device commands for managing which machines may sync.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use clap::Subcommand;
+use todoee_core::{Config, IdMatch, sync::SyncService};
+
+use super::config::confirm;
+use super::output;
+
+#[derive(Subcommand, Clone)]
+pub enum DevicesCommand {
+    /// List devices registered for sync
+    List,
+    /// Revoke a device so it can no longer sync
+    Revoke {
+        /// Device ID (or prefix)
+        id: String,
+    },
+    /// Delete this device's local database
+    Wipe {
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+pub async fn run(cmd: DevicesCommand) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+
+    // Wiping must work on a revoked device, so it never touches the remote
+    if let DevicesCommand::Wipe { yes } = cmd {
+        return wipe(&config, yes);
+    }
+
+    let service = SyncService::new(&config)
+        .await
+        .context("Failed to initialize sync service")?;
+    if !service.is_configured() {
+        println!("Cloud sync is not configured, so there are no devices to manage.");
+        println!("Set {} to enable it.", config.database.url_env);
+        return Ok(());
+    }
+
+    match cmd {
+        DevicesCommand::List => list(&service).await,
+        DevicesCommand::Revoke { id } => revoke(&service, &id).await,
+        DevicesCommand::Wipe { .. } => unreachable!("handled above"),
+    }
+}
+
+async fn list(service: &SyncService) -> Result<()> {
+    let current = service.device_id().await?;
+    let devices = service.list_devices().await?;
+
+    if output::is_json() {
+        let devices: Vec<_> = devices
+            .iter()
+            .map(|device| serde_json::json!({ "current": device.id == current, "device": device }))
+            .collect();
+        return output::print_json(&devices);
+    }
+
+    if devices.is_empty() {
+        println!("No devices registered yet. Devices register on their first sync.");
+        return Ok(());
+    }
+
+    println!("Devices:\n");
+    for device in &devices {
+        let marker = if device.id == current { "*" } else { " " };
+        let status = match device.revoked_at {
+            Some(at) => format!(
+                "revoked {}",
+                at.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+            ),
+            None => format!(
+                "last sync {}",
+                device
+                    .last_seen_at
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+            ),
+        };
+        println!(
+            "{} {}  {:<24} {}",
+            marker,
+            &device.id.to_string()[..8],
+            device.name,
+            status
+        );
+    }
+    println!("\n* this device");
+
+    Ok(())
+}
+
+async fn revoke(service: &SyncService, id: &str) -> Result<()> {
+    let devices = service.list_devices().await?;
+    let device = match IdMatch::by_prefix(devices, id, |d| d.id) {
+        IdMatch::Unique(device) => device,
+        IdMatch::NotFound => {
+            eprintln!("No device found matching '{}'", id);
+            eprintln!("Hint: Use 'todoee devices list' to see all devices.");
+            anyhow::bail!("Device not found");
+        }
+        IdMatch::Ambiguous(matches) => {
+            eprintln!("Multiple devices match '{}'. Please be more specific:", id);
+            eprintln!();
+            for d in &matches {
+                eprintln!("  {} [{}]", d.name, &d.id.to_string()[..8]);
+            }
+            anyhow::bail!("Ambiguous ID - provide more characters");
+        }
+    };
+
+    if !service.revoke_device(device.id).await? {
+        println!("{} is already revoked", device.name);
+        return Ok(());
+    }
+
+    println!(
+        "\u{2713} Revoked {} ({})",
+        device.name,
+        &device.id.to_string()[..8]
+    );
+    if device.id == service.device_id().await? {
+        println!("  This is the current device; it will no longer sync.");
+    }
+    println!("  Anyone with the database URL still has access, so rotate the");
+    println!("  database password if the device was lost.");
+
+    Ok(())
+}
+
+fn wipe(config: &Config, yes: bool) -> Result<()> {
+    let db_path = config.local_db_path()?;
+    if !db_path.exists() {
+        println!("No local database at {}", db_path.display());
+        return Ok(());
+    }
+
+    println!("This permanently deletes all local todos, events and history:");
+    println!("  {}", db_path.display());
+    if !yes && !confirm(&mut io::stdin().lock(), "Delete the local database?", false)? {
+        println!("Cancelled");
+        return Ok(());
+    }
+
+    let removed = wipe_local_db(&db_path)?;
+    println!("\u{2713} Deleted {} file(s)", removed.len());
+    Ok(())
+}
+
+/// Delete a SQLite database and its WAL and shared-memory files.
+/// Returns the files that were removed.
+pub fn wipe_local_db(db_path: &Path) -> Result<Vec<PathBuf>> {
+    let mut removed = Vec::new();
+    for suffix in ["", "-wal", "-shm"] {
+        let mut path = db_path.as_os_str().to_owned();
+        path.push(suffix);
+        let path = PathBuf::from(path);
+        if path.exists() {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to delete {}", path.display()))?;
+            removed.push(path);
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_wipe_local_db_removes_sidecar_files() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("cache.db");
+        fs::write(&db_path, "db").unwrap();
+        fs::write(dir.path().join("cache.db-wal"), "wal").unwrap();
+        fs::write(dir.path().join("other.db"), "keep").unwrap();
+
+        let removed = wipe_local_db(&db_path).unwrap();
+        assert_eq!(removed.len(), 2);
+        assert!(!db_path.exists());
+        assert!(dir.path().join("other.db").exists());
+
+        assert!(wipe_local_db(&db_path).unwrap().is_empty());
+    }
+}
@@ -4,7 +4,9 @@ use std::fs;
 
 use anyhow::{Context, Result};
 use chrono::{Local, TimeZone, Utc};
-use todoee_core::{Config, LocalDb, OperationType};
+use todoee_core::{Config, LocalDb, OperationType, short_id};
+
+use super::{ids, output};
 
 pub async fn run(hours: Option<i64>) -> Result<()> {
     let config = Config::load().context("Failed to load config")?;
@@ -24,6 +26,10 @@ pub async fn run(hours: Option<i64>) -> Result<()> {
     let since = Utc::now() - chrono::Duration::hours(hours);
     let operations = db.list_operations_since(since).await?;
 
+    if output::is_json() {
+        return output::print_json(&operations);
+    }
+
     if operations.is_empty() {
         println!("No changes in the last {} hours.", hours);
         return Ok(());
@@ -36,9 +42,10 @@ pub async fn run(hours: Option<i64>) -> Result<()> {
     let mut deletes = 0;
     let mut completes = 0;
 
+    let id_len = ids::id_length(&db, &config).await?;
     for op in &operations {
         let time = Local.from_utc_datetime(&op.created_at.naive_utc());
-        let short_id = &op.entity_id.to_string()[..8];
+        let short_id = short_id(&op.entity_id, id_len);
 
         let title = op
             .new_state
@@ -58,6 +65,17 @@ pub async fn run(hours: Option<i64>) -> Result<()> {
                 );
                 creates += 1;
             }
+            OperationType::BatchCreate => {
+                for todo in op.batch_todos() {
+                    println!(
+                        "\x1b[32m+ {}\x1b[0m {} {}",
+                        time.format("%H:%M"),
+                        todoee_core::short_id(&todo.id, id_len),
+                        todo.title
+                    );
+                    creates += 1;
+                }
+            }
             OperationType::Delete => {
                 println!(
                     "\x1b[31m- {}\x1b[0m {} {}",
@@ -93,7 +111,8 @@ pub async fn run(hours: Option<i64>) -> Result<()> {
                     title
                 );
             }
-            OperationType::Stash | OperationType::Unstash => {}
+            // Summaries are of compacted history, older than a diff looks back
+            OperationType::Stash | OperationType::Unstash | OperationType::Summary => {}
         }
     }
 
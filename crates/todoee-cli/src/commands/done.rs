@@ -1,6 +1,8 @@
 use anyhow::{Context, Result};
 use std::fs;
-use todoee_core::{Config, EntityType, LocalDb, Operation, OperationType, Todo};
+use todoee_core::{Config, EntityType, IdMatch, LocalDb, Operation, OperationType, short_id};
+
+use super::{github, ids, output};
 
 pub async fn run(id: String) -> Result<()> {
     // Load config and open local database
@@ -18,22 +20,20 @@ pub async fn run(id: String) -> Result<()> {
     let db = LocalDb::new(&db_path).await?;
     db.run_migrations().await?;
 
-    // Find todos matching the partial ID
-    let matches = find_todos_by_partial_id(&db, &id).await?;
-
-    match matches.len() {
-        0 => {
+    // Find the todo matching the list number or partial ID
+    match ids::resolve(&db, &config, &id).await? {
+        IdMatch::NotFound => {
             // No match found
             eprintln!("No todo found matching '{}'", id);
             eprintln!("Hint: Use 'todoee list --all' to see all todos including completed ones.");
             anyhow::bail!("Todo not found");
         }
-        1 => {
-            // Single match found
-            let mut todo = matches.into_iter().next().unwrap();
-
+        IdMatch::Unique(mut todo) => {
             if todo.is_completed {
-                println!("Todo '{}' is already marked as complete.", todo.title);
+                if output::is_json() {
+                    return output::print_json(&todo);
+                }
+                output::say!("Todo '{}' is already marked as complete.", todo.title);
                 return Ok(());
             }
 
@@ -59,16 +59,23 @@ pub async fn run(id: String) -> Result<()> {
             );
             db.record_operation(&op).await?;
 
-            println!("\u{2713} Completed: {}", todo.title);
-            println!("  ID: {}", &todo.id.to_string()[..8]);
+            if output::is_json() {
+                github::close_completed(&config, &todo).await;
+                return output::print_json(&todo);
+            }
+            output::say!("\u{2713} Completed: {}", todo.title);
+            let id_len = ids::id_length(&db, &config).await?;
+            output::say!("  ID: {}", short_id(&todo.id, id_len));
+            output::detail!("Full ID: {}", todo.id);
+            github::close_completed(&config, &todo).await;
         }
-        _ => {
-            // Multiple matches - ask for more specific ID
+        IdMatch::Ambiguous(matches) => {
             eprintln!("Multiple todos match '{}'. Please be more specific:", id);
             eprintln!();
+            let id_len = ids::match_length(&matches);
             for todo in &matches {
                 let status = if todo.is_completed { "[x]" } else { "[ ]" };
-                let short_id = &todo.id.to_string()[..8];
+                let short_id = short_id(&todo.id, id_len);
                 eprintln!("  {} {} [{}]", status, todo.title, short_id);
             }
             anyhow::bail!("Ambiguous ID - provide more characters");
@@ -77,21 +84,3 @@ pub async fn run(id: String) -> Result<()> {
 
     Ok(())
 }
-
-/// Find todos whose ID starts with the given prefix
-async fn find_todos_by_partial_id(db: &LocalDb, prefix: &str) -> Result<Vec<Todo>> {
-    let prefix_lower = prefix.to_lowercase();
-    let all_todos = db.list_todos(false).await?;
-
-    let matches: Vec<Todo> = all_todos
-        .into_iter()
-        .filter(|todo| {
-            todo.id
-                .to_string()
-                .to_lowercase()
-                .starts_with(&prefix_lower)
-        })
-        .collect();
-
-    Ok(matches)
-}
@@ -1,22 +1,63 @@
 use anyhow::{Context, Result};
-use chrono::Utc;
+use chrono::{DateTime, Duration, Local, Utc};
 use std::fs;
 use todoee_core::{
-    Category, Config, EntityType, LocalDb, Operation, OperationType, Priority, SyncStatus, Todo,
+    Category, Config, EntityType, IdMatch, LocalDb, Operation, OperationType, Priority, SyncStatus,
+    dates, short_id, validation,
 };
 use uuid::Uuid;
 
+use super::{ids, output};
+
+use super::add::{parse_due, parse_remind_before};
+use super::remind;
+
+/// Longest description `todoee edit --description` accepts, as for `todoee add`
+const MAX_DESCRIPTION_LEN: usize = 10000;
+
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     id: String,
     title: Option<String>,
     category: Option<String>,
     priority: Option<i32>,
+    description: Option<String>,
+    due: Option<String>,
+    clear_due: bool,
+    reminder: Option<String>,
+    clear_reminder: bool,
+    project: Option<String>,
+    remind_before: Option<String>,
+    no_nag: Option<bool>,
 ) -> Result<()> {
     // Validate that at least one field is being edited
-    if title.is_none() && category.is_none() && priority.is_none() {
-        anyhow::bail!("At least one of --title, --category, or --priority must be provided");
+    if title.is_none()
+        && category.is_none()
+        && priority.is_none()
+        && description.is_none()
+        && due.is_none()
+        && !clear_due
+        && reminder.is_none()
+        && !clear_reminder
+        && project.is_none()
+        && remind_before.is_none()
+        && no_nag.is_none()
+    {
+        anyhow::bail!(
+            "At least one of --title, --category, --priority, --description, --due, --clear-due, --reminder, --clear-reminder, --project, --remind-before, --no-nag or --nag must be provided"
+        );
     }
 
+    // Some(None) clears the description
+    let description = description
+        .map(|input| parse_description(&input))
+        .transpose()?;
+
+    // Some(None) goes back to the per-priority notification setting
+    let remind_before = remind_before
+        .map(|input| parse_remind_before(&input))
+        .transpose()?;
+
     if let Some(ref t) = title
         && t.trim().is_empty()
     {
@@ -25,6 +66,26 @@ pub async fn run(
 
     // Load config and open local database
     let config = Config::load().context("Failed to load configuration")?;
+
+    let date_input = config.display.date_input();
+    let due = match due {
+        Some(input) => Some(parse_due(&input, &date_input).with_context(|| {
+            format!(
+                "Could not understand due date '{}'. Try YYYY-MM-DD, YYYY-MM-DD HH:MM, 'friday 3pm', 'in 2 hours' or 'none'",
+                input
+            )
+        })?),
+        None if clear_due => Some(None),
+        None => None,
+    };
+
+    let reminder = match reminder {
+        Some(input) if input.trim().eq_ignore_ascii_case("none") => Some(None),
+        Some(input) => Some(Some(remind::parse_time(&input, &date_input, Utc::now())?)),
+        None if clear_reminder => Some(None),
+        None => None,
+    };
+
     let db_path = config.local_db_path()?;
 
     // Ensure config directory exists
@@ -38,19 +99,37 @@ pub async fn run(
     let db = LocalDb::new(&db_path).await?;
     db.run_migrations().await?;
 
-    // Find todos matching the partial ID
-    let matches = find_todos_by_partial_id(&db, &id).await?;
+    // Resolve the project up front so a typo fails before anything changes
+    let project = match project {
+        Some(name) if name.eq_ignore_ascii_case("none") => Some(None),
+        Some(name) => {
+            let project = db.get_project_by_name(&name).await?.with_context(|| {
+                format!(
+                    "Project '{}' not found. Create it with: todoee project create \"{}\"",
+                    name, name
+                )
+            })?;
+            if project.is_archived() {
+                anyhow::bail!("Project '{}' is archived", project.name);
+            }
+            Some(Some(project))
+        }
+        None => None,
+    };
 
-    match matches.len() {
-        0 => {
+    // Find the todo matching the list number or partial ID
+    match ids::resolve(&db, &config, &id).await? {
+        IdMatch::NotFound => {
             // No match found
             eprintln!("No todo found matching '{}'", id);
             eprintln!("Hint: Use 'todoee list --all' to see all todos including completed ones.");
             anyhow::bail!("Todo not found");
         }
-        1 => {
-            // Single match found
-            let mut todo = matches.into_iter().next().unwrap();
+        IdMatch::Unique(mut todo) => {
+            if todo.is_completed && reminder.is_some_and(|r| r.is_some()) {
+                anyhow::bail!("'{}' is completed; reopen it to set a reminder", todo.title);
+            }
+
             let mut changes: Vec<String> = Vec::new();
 
             // Save previous state for undo support
@@ -63,6 +142,20 @@ pub async fn run(
                 changes.push(format!("Title: '{}' -> '{}'", old_title, new_title));
             }
 
+            // Update description if provided (None clears it)
+            if let Some(new_description) = description {
+                let describe = |d: &Option<String>| match d {
+                    Some(d) => format!("'{}'", d),
+                    None => "None".to_string(),
+                };
+                changes.push(format!(
+                    "Description: {} -> {}",
+                    describe(&todo.description),
+                    describe(&new_description)
+                ));
+                todo.description = new_description;
+            }
+
             // Update category if provided (create if doesn't exist)
             if let Some(cat_name) = category {
                 let cat_id = get_or_create_category(&db, &cat_name, None).await?;
@@ -82,20 +175,86 @@ pub async fn run(
                     Priority::Low => "Low",
                     Priority::Medium => "Medium",
                     Priority::High => "High",
+                    Priority::Urgent => "Urgent",
                 };
-                todo.priority = match p {
-                    1 => Priority::Low,
-                    3 => Priority::High,
-                    _ => Priority::Medium,
-                };
+                todo.priority = validation::priority(p.into())?;
                 let new_priority = match todo.priority {
                     Priority::Low => "Low",
                     Priority::Medium => "Medium",
                     Priority::High => "High",
+                    Priority::Urgent => "Urgent",
                 };
                 changes.push(format!("Priority: {} -> {}", old_priority, new_priority));
             }
 
+            // Update due date if provided (None clears it)
+            if let Some(new_due) = due {
+                let describe = |d: Option<DateTime<Utc>>| {
+                    d.map(|d| dates::format_due(&d, &Local))
+                        .unwrap_or_else(|| "None".to_string())
+                };
+                changes.push(format!(
+                    "Due: {} -> {}",
+                    describe(todo.due_date),
+                    describe(new_due)
+                ));
+                todo.due_date = new_due;
+            }
+
+            // Update reminder if provided (None clears it)
+            if let Some(new_reminder) = reminder {
+                let describe = |r: Option<DateTime<Utc>>| {
+                    r.map(|r| r.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_else(|| "None".to_string())
+                };
+                changes.push(format!(
+                    "Reminder: {} -> {}",
+                    describe(todo.reminder_at),
+                    describe(new_reminder)
+                ));
+                todo.reminder_at = new_reminder;
+            }
+
+            // Update project if provided (None removes it from its project)
+            if let Some(new_project) = project {
+                let old_project = match todo.project_id {
+                    Some(old_id) => db
+                        .get_project(old_id)
+                        .await?
+                        .map_or_else(|| "Unknown".to_string(), |p| p.name),
+                    None => "None".to_string(),
+                };
+                let new_name = new_project
+                    .as_ref()
+                    .map_or_else(|| "None".to_string(), |p| p.name.clone());
+                todo.project_id = new_project.map(|p| p.id);
+                changes.push(format!("Project: '{}' -> '{}'", old_project, new_name));
+            }
+
+            // Update the notification advance if provided (None clears it)
+            if let Some(new_minutes) = remind_before {
+                let describe = |m: Option<i64>| {
+                    m.map(|m| dates::format_duration(Duration::minutes(m)))
+                        .unwrap_or_else(|| "None".to_string())
+                };
+                changes.push(format!(
+                    "Remind before: {} -> {}",
+                    describe(todo.remind_before_minutes),
+                    describe(new_minutes)
+                ));
+                todo.remind_before_minutes = new_minutes;
+            }
+
+            if let Some(no_nag) = no_nag {
+                let describe = |no_nag: bool| if no_nag { "off" } else { "on" };
+                changes.push(format!(
+                    "Repeat notifications: {} -> {}",
+                    describe(todo.no_nag),
+                    describe(no_nag)
+                ));
+                todo.no_nag = no_nag;
+            }
+
             // Update timestamps and sync status
             todo.updated_at = Utc::now();
             todo.sync_status = SyncStatus::Pending;
@@ -113,22 +272,32 @@ pub async fn run(
             );
             db.record_operation(&op).await?;
 
+            if output::is_json() {
+                return output::print_json(&todo);
+            }
+
+            output::detail!("Full ID: {}", todo.id);
+            if output::is_quiet() {
+                return Ok(());
+            }
+
             // Print confirmation
             println!("\u{270E} Updated: {}", todo.title);
-            println!("  ID: {}", &todo.id.to_string()[..8]);
+            let id_len = ids::id_length(&db, &config).await?;
+            println!("  ID: {}", short_id(&todo.id, id_len));
             println!();
             println!("Changes:");
             for change in &changes {
                 println!("  - {}", change);
             }
         }
-        _ => {
-            // Multiple matches - ask for more specific ID
+        IdMatch::Ambiguous(matches) => {
             eprintln!("Multiple todos match '{}'. Please be more specific:", id);
             eprintln!();
+            let id_len = ids::match_length(&matches);
             for todo in &matches {
                 let status = if todo.is_completed { "[x]" } else { "[ ]" };
-                let short_id = &todo.id.to_string()[..8];
+                let short_id = short_id(&todo.id, id_len);
                 eprintln!("  {} {} [{}]", status, todo.title, short_id);
             }
             anyhow::bail!("Ambiguous ID - provide more characters");
@@ -138,22 +307,17 @@ pub async fn run(
     Ok(())
 }
 
-/// Find todos whose ID starts with the given prefix
-async fn find_todos_by_partial_id(db: &LocalDb, prefix: &str) -> Result<Vec<Todo>> {
-    let prefix_lower = prefix.to_lowercase();
-    let all_todos = db.list_todos(false).await?;
-
-    let matches: Vec<Todo> = all_todos
-        .into_iter()
-        .filter(|todo| {
-            todo.id
-                .to_string()
-                .to_lowercase()
-                .starts_with(&prefix_lower)
-        })
-        .collect();
-
-    Ok(matches)
+/// Parse a --description value. Blank text gives `None`, clearing it.
+fn parse_description(input: &str) -> Result<Option<String>> {
+    let input = input.trim();
+    if input.len() > MAX_DESCRIPTION_LEN {
+        anyhow::bail!(
+            "Description too long ({} chars). Maximum is {} characters.",
+            input.len(),
+            MAX_DESCRIPTION_LEN
+        );
+    }
+    Ok((!input.is_empty()).then(|| input.to_string()))
 }
 
 /// Look up or create a category by name
@@ -175,3 +339,18 @@ async fn find_category_name(db: &LocalDb, id: Uuid) -> Result<Option<String>> {
     let categories = db.list_categories().await?;
     Ok(categories.into_iter().find(|c| c.id == id).map(|c| c.name))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_description() {
+        assert_eq!(
+            parse_description("  Call before noon ").unwrap(),
+            Some("Call before noon".to_string())
+        );
+        assert_eq!(parse_description("   ").unwrap(), None);
+        assert!(parse_description(&"x".repeat(MAX_DESCRIPTION_LEN + 1)).is_err());
+    }
+}
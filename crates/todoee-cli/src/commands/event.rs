@@ -0,0 +1,261 @@
+//! Event commands for managing calendar entries.
+
+use std::fs;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use clap::Subcommand;
+use todoee_core::{
+    Config, Event, IdMatch, LocalDb, RECUR_DAILY, RECUR_MONTHLY, RECUR_WEEKDAYS, RECUR_WEEKLY,
+    RecurrenceRule,
+};
+
+use super::output;
+
+#[derive(Subcommand, Clone)]
+pub enum EventCommand {
+    /// Add a calendar event
+    ///
+    /// Examples:
+    ///   todoee event add "standup" 9:30-9:45 --recur weekdays
+    ///   todoee event add "dentist" 14:00 --date 2025-03-02
+    Add {
+        /// Event title
+        title: String,
+        /// Time or time range (e.g. 9:30 or 9:30-9:45)
+        time: String,
+        /// Date: today, tomorrow, or YYYY-MM-DD (default: today)
+        #[arg(short, long)]
+        date: Option<String>,
+        /// Repeat: daily, weekdays, weekly, monthly, or an RRULE
+        /// (e.g. "FREQ=MONTHLY;BYDAY=-1FR")
+        #[arg(short, long)]
+        recur: Option<String>,
+        /// Optional description
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// List all events
+    List,
+    /// Delete an event by ID
+    Delete {
+        /// Event ID (or prefix)
+        id: String,
+    },
+}
+
+pub async fn run(cmd: EventCommand) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    match cmd {
+        EventCommand::Add {
+            title,
+            time,
+            date,
+            recur,
+            description,
+        } => {
+            add(
+                &db,
+                title,
+                &time,
+                date.as_deref(),
+                recur.as_deref(),
+                description,
+            )
+            .await
+        }
+        EventCommand::List => list(&db).await,
+        EventCommand::Delete { id } => delete(&db, &id).await,
+    }
+}
+
+async fn add(
+    db: &LocalDb,
+    title: String,
+    time: &str,
+    date: Option<&str>,
+    recur: Option<&str>,
+    description: Option<String>,
+) -> Result<()> {
+    let title = title.trim().to_string();
+    if title.is_empty() {
+        anyhow::bail!("Event title cannot be empty");
+    }
+
+    let date = parse_date(date)?;
+    let (start, end) = parse_time_range(time)?;
+    let start_time = to_utc(date, start)?;
+    let mut end_time = to_utc(date, end)?;
+    // A range like 23:30-0:30 ends the next day
+    if end_time <= start_time {
+        end_time += Duration::days(1);
+    }
+
+    let mut event = Event::new(title, start_time, end_time);
+    event.description = description;
+    event.recurrence_rule = recur.map(parse_recurrence).transpose()?;
+
+    db.create_event(&event).await?;
+
+    println!("\u{2713} Created event: {}", event.title);
+    match event.next_occurrence(event.start_time) {
+        Some(first) => println!("  When: {}", format_span(&event, first)),
+        None => println!("  When: never (the rule has no occurrences)"),
+    }
+    if let Some(rule) = &event.recurrence_rule {
+        println!("  Repeats: {}", describe_recurrence(rule));
+    }
+    println!("  ID: {}", &event.id.to_string()[..8]);
+
+    Ok(())
+}
+
+async fn list(db: &LocalDb) -> Result<()> {
+    let events = db.list_events().await?;
+
+    if output::is_json() {
+        return output::print_json(&events);
+    }
+
+    if events.is_empty() {
+        println!("No events. Use 'todoee event add' to create one!");
+        return Ok(());
+    }
+
+    for event in &events {
+        let repeat = event
+            .recurrence_rule
+            .as_deref()
+            .map(|r| format!(" \x1b[36m({})\x1b[0m", describe_recurrence(r)))
+            .unwrap_or_default();
+        println!(
+            "\x1b[90m{}\x1b[0m {} {}{}",
+            &event.id.to_string()[..8],
+            format_span(event, event.start_time),
+            event.title,
+            repeat
+        );
+    }
+
+    Ok(())
+}
+
+async fn delete(db: &LocalDb, id: &str) -> Result<()> {
+    match db.resolve_short_event_id(id).await? {
+        IdMatch::NotFound => {
+            eprintln!("No event found matching '{}'", id);
+            eprintln!("Hint: Use 'todoee event list' to see all events.");
+            anyhow::bail!("Event not found");
+        }
+        IdMatch::Unique(event) => {
+            db.delete_event(event.id).await?;
+            println!("\u{2717} Deleted event: {}", event.title);
+        }
+        IdMatch::Ambiguous(matches) => {
+            eprintln!("Multiple events match '{}'. Please be more specific:", id);
+            eprintln!();
+            for event in &matches {
+                eprintln!("  {} [{}]", event.title, &event.id.to_string()[..8]);
+            }
+            anyhow::bail!("Ambiguous ID - provide more characters");
+        }
+    }
+
+    Ok(())
+}
+
+/// Format an occurrence as "Mon 03-02 09:30-09:45" in local time.
+pub fn format_span(event: &Event, start: DateTime<Utc>) -> String {
+    format!(
+        "{} {}",
+        start.with_timezone(&Local).format("%a %m-%d"),
+        format_time_range(event, start)
+    )
+}
+
+/// Format an occurrence's time of day as "09:30-09:45" in local time.
+pub fn format_time_range(event: &Event, start: DateTime<Utc>) -> String {
+    let local_start = start.with_timezone(&Local);
+    let local_end = (start + event.duration()).with_timezone(&Local);
+    format!(
+        "{}-{}",
+        local_start.format("%H:%M"),
+        local_end.format("%H:%M")
+    )
+}
+
+/// Human-readable name for a stored recurrence rule.
+pub fn describe_recurrence(rule: &str) -> &str {
+    match rule {
+        RECUR_DAILY => "daily",
+        RECUR_WEEKDAYS => "weekdays",
+        RECUR_WEEKLY => "weekly",
+        RECUR_MONTHLY => "monthly",
+        other => other,
+    }
+}
+
+/// Accept a preset name or a raw RRULE such as `FREQ=WEEKLY;INTERVAL=2;BYDAY=TU`.
+fn parse_recurrence(input: &str) -> Result<String> {
+    let rule = match input.to_lowercase().as_str() {
+        "daily" => RECUR_DAILY.to_string(),
+        "weekdays" => RECUR_WEEKDAYS.to_string(),
+        "weekly" => RECUR_WEEKLY.to_string(),
+        "monthly" => RECUR_MONTHLY.to_string(),
+        _ => RecurrenceRule::parse(input)
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "{}\nUse daily, weekdays, weekly, monthly, or an RRULE like FREQ=WEEKLY;BYDAY=TU",
+                    e
+                )
+            })?
+            .to_string(),
+    };
+    Ok(rule)
+}
+
+fn parse_date(input: Option<&str>) -> Result<NaiveDate> {
+    let today = Local::now().date_naive();
+    match input.map(|s| s.trim().to_lowercase()).as_deref() {
+        None | Some("today") => Ok(today),
+        Some("tomorrow") => Ok(today + Duration::days(1)),
+        Some(s) => NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .with_context(|| format!("Invalid date '{}'. Use today, tomorrow, or YYYY-MM-DD", s)),
+    }
+}
+
+/// Parse "9:30" or "9:30-9:45". A single time defaults to a 30 minute event.
+fn parse_time_range(input: &str) -> Result<(NaiveTime, NaiveTime)> {
+    let parse = |s: &str| {
+        NaiveTime::parse_from_str(s.trim(), "%H:%M")
+            .with_context(|| format!("Invalid time '{}'. Use HH:MM", s.trim()))
+    };
+
+    match input.split_once('-') {
+        Some((start, end)) => Ok((parse(start)?, parse(end)?)),
+        None => {
+            let start = parse(input)?;
+            Ok((start, start + Duration::minutes(30)))
+        }
+    }
+}
+
+fn to_utc(date: NaiveDate, time: NaiveTime) -> Result<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .with_context(|| format!("{} {} does not exist in the local timezone", date, time))
+}
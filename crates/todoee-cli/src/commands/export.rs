@@ -3,12 +3,21 @@ use std::path::Path;
 use anyhow::{Context, Result};
 use serde::Serialize;
 use std::fs;
-use todoee_core::{Config, LocalDb};
+use todoee_core::{
+    Attachment, Category, Config, LocalDb, NoteLink, Operation, Project, Todo, TodoNote, ics,
+    markdown, org, short_id, taskwarrior,
+};
+
+use super::ids;
 
 #[derive(Debug, Clone, Copy)]
 pub enum ExportFormat {
     Json,
     Csv,
+    Org,
+    Markdown,
+    Ics,
+    Taskwarrior,
 }
 
 #[derive(Serialize)]
@@ -17,6 +26,61 @@ struct ExportData {
     exported_at: String,
     todos: Vec<todoee_core::Todo>,
     categories: Vec<todoee_core::Category>,
+    /// Progress notes (`todoee note`) on the exported todos
+    notes: Vec<TodoNote>,
+    /// Files and URLs (`todoee attach`) on the exported todos
+    attachments: Vec<Attachment>,
+}
+
+/// One todo with everything attached to it, for handing a task over or
+/// attaching it to a bug report
+#[derive(Serialize)]
+struct TodoExport {
+    version: String,
+    exported_at: String,
+    todo: Todo,
+    category: Option<Category>,
+    project: Option<Project>,
+    /// Markdown checkbox the todo was imported from
+    note: Option<NoteLink>,
+    /// Progress notes, oldest first
+    notes: Vec<TodoNote>,
+    /// Attached files and URLs
+    attachments: Vec<Attachment>,
+    /// Operations on the todo, oldest first (with `--with-history`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    history: Option<Vec<Operation>>,
+}
+
+async fn todo_export(db: &LocalDb, todo: Todo, with_history: bool) -> Result<TodoExport> {
+    let category = match todo.category_id {
+        Some(id) => db.list_categories().await?.into_iter().find(|c| c.id == id),
+        None => None,
+    };
+    let project = match todo.project_id {
+        Some(id) => db.get_project(id).await?,
+        None => None,
+    };
+    let note = db.get_note_link(todo.id).await?;
+    let notes = db.list_todo_notes(todo.id).await?;
+    let attachments = db.list_attachments(todo.id).await?;
+    let history = if with_history {
+        Some(db.list_operations_for(todo.id).await?)
+    } else {
+        None
+    };
+
+    Ok(TodoExport {
+        version: "1.0".to_string(),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        todo,
+        category,
+        project,
+        note,
+        notes,
+        attachments,
+        history,
+    })
 }
 
 /// Export todos to a file in the specified format.
@@ -39,11 +103,32 @@ async fn export_todos_impl(
     let todos = db.list_todos(!include_completed).await?;
     let categories = db.list_categories().await?;
 
+    let (notes, attachments) = if matches!(format, ExportFormat::Json) {
+        let exported: std::collections::HashSet<_> = todos.iter().map(|t| t.id).collect();
+        let notes = db
+            .list_all_todo_notes()
+            .await?
+            .into_iter()
+            .filter(|n| exported.contains(&n.todo_id))
+            .collect();
+        let attachments = db
+            .list_all_attachments()
+            .await?
+            .into_iter()
+            .filter(|a| exported.contains(&a.todo_id))
+            .collect();
+        (notes, attachments)
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
     let data = ExportData {
         version: "1.0".to_string(),
         exported_at: chrono::Utc::now().to_rfc3339(),
         todos: todos.clone(),
-        categories,
+        categories: categories.clone(),
+        notes,
+        attachments,
     };
 
     match format {
@@ -63,6 +148,31 @@ async fn export_todos_impl(
             }
             wtr.flush().context("Failed to flush CSV writer")?;
         }
+        ExportFormat::Org => {
+            let org = org::write(&todos, &categories, &chrono::Local);
+            std::fs::write(output_path, org).with_context(|| {
+                format!("Failed to write export file: {}", output_path.display())
+            })?;
+        }
+        ExportFormat::Markdown => {
+            let markdown = markdown::write(&todos, &categories, &chrono::Local);
+            std::fs::write(output_path, markdown).with_context(|| {
+                format!("Failed to write export file: {}", output_path.display())
+            })?;
+        }
+        ExportFormat::Ics => {
+            let ics = ics::write(&todos, &categories, chrono::Utc::now());
+            std::fs::write(output_path, ics).with_context(|| {
+                format!("Failed to write export file: {}", output_path.display())
+            })?;
+        }
+        ExportFormat::Taskwarrior => {
+            let projects = db.list_projects(true).await?;
+            let json = taskwarrior::write(&todos, &categories, &projects, &chrono::Local);
+            std::fs::write(output_path, json).with_context(|| {
+                format!("Failed to write export file: {}", output_path.display())
+            })?;
+        }
     }
 
     Ok(todos.len())
@@ -86,6 +196,10 @@ pub async fn run(output: Option<String>, format: String, include_completed: bool
 
     let format = match format.to_lowercase().as_str() {
         "csv" => ExportFormat::Csv,
+        "org" => ExportFormat::Org,
+        "md" | "markdown" => ExportFormat::Markdown,
+        "ics" | "ical" => ExportFormat::Ics,
+        "taskwarrior" | "tw" => ExportFormat::Taskwarrior,
         _ => ExportFormat::Json,
     };
 
@@ -94,6 +208,10 @@ pub async fn run(output: Option<String>, format: String, include_completed: bool
         match format {
             ExportFormat::Json => format!("todoee_export_{}.json", timestamp),
             ExportFormat::Csv => format!("todoee_export_{}.csv", timestamp),
+            ExportFormat::Org => format!("todoee_export_{}.org", timestamp),
+            ExportFormat::Markdown => format!("todoee_export_{}.md", timestamp),
+            ExportFormat::Ics => format!("todoee_export_{}.ics", timestamp),
+            ExportFormat::Taskwarrior => format!("todoee_export_{}.taskwarrior.json", timestamp),
         }
     });
 
@@ -103,6 +221,45 @@ pub async fn run(output: Option<String>, format: String, include_completed: bool
     Ok(())
 }
 
+/// Export the todo matching `id` as a JSON document, optionally with its
+/// operation history.
+pub async fn run_todo(id: &str, output: Option<String>, with_history: bool) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    let todo = ids::resolve_todo(&db, &config, id).await?;
+
+    let output_path =
+        output.unwrap_or_else(|| format!("todoee_todo_{}.json", short_id(&todo.id, 8)));
+    let title = todo.title.clone();
+    let data = todo_export(&db, todo, with_history).await?;
+    let json =
+        serde_json::to_string_pretty(&data).context("Failed to serialize export data to JSON")?;
+    fs::write(&output_path, json)
+        .with_context(|| format!("Failed to write export file: {}", output_path))?;
+
+    match &data.history {
+        Some(history) => println!(
+            "\u{2713} Exported \"{}\" with {} operations to {}",
+            title,
+            history.len(),
+            output_path
+        ),
+        None => println!("\u{2713} Exported \"{}\" to {}", title, output_path),
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,4 +308,104 @@ mod tests {
         let content = std::fs::read_to_string(&output_path).unwrap();
         assert!(content.contains("Test task"));
     }
+
+    #[tokio::test]
+    async fn test_export_org_writes_headings() {
+        let temp_dir = TempDir::new().unwrap();
+        let output_path = temp_dir.path().join("export.org");
+
+        let db_path = temp_dir.path().join("test.db");
+        let db = LocalDb::new(&db_path).await.unwrap();
+        db.run_migrations().await.unwrap();
+
+        let mut done = Todo::new("Finished task".to_string(), None);
+        done.mark_complete();
+        db.create_todo(&Todo::new("Test task".to_string(), None))
+            .await
+            .unwrap();
+        db.create_todo(&done).await.unwrap();
+
+        let count = export_todos(&db, &output_path, ExportFormat::Org)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let content = std::fs::read_to_string(&output_path).unwrap();
+        assert!(content.contains("* TODO Test task\n"));
+        assert!(content.contains("* DONE Finished task\n"));
+    }
+
+    #[tokio::test]
+    async fn test_export_ics_writes_vtodos() {
+        let temp_dir = TempDir::new().unwrap();
+        let output_path = temp_dir.path().join("export.ics");
+
+        let db_path = temp_dir.path().join("test.db");
+        let db = LocalDb::new(&db_path).await.unwrap();
+        db.run_migrations().await.unwrap();
+
+        let mut done = Todo::new("Finished task".to_string(), None);
+        done.mark_complete();
+        db.create_todo(&Todo::new("Test task".to_string(), None))
+            .await
+            .unwrap();
+        db.create_todo(&done).await.unwrap();
+
+        let count = export_todos(&db, &output_path, ExportFormat::Ics)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let content = std::fs::read_to_string(&output_path).unwrap();
+        assert!(content.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(content.contains("SUMMARY:Test task\r\nPRIORITY:5\r\nSTATUS:NEEDS-ACTION"));
+        assert!(content.contains("SUMMARY:Finished task\r\nPRIORITY:5\r\nSTATUS:COMPLETED"));
+    }
+
+    #[tokio::test]
+    async fn test_todo_export_includes_history() {
+        use todoee_core::{EntityType, OperationType};
+
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDb::new(&temp_dir.path().join("test.db"))
+            .await
+            .unwrap();
+        db.run_migrations().await.unwrap();
+
+        let todo = Todo::new("Handed-off task".to_string(), None);
+        let other = Todo::new("Unrelated".to_string(), None);
+        db.create_todo(&todo).await.unwrap();
+        db.create_todo(&other).await.unwrap();
+        for (t, op) in [
+            (&todo, OperationType::Create),
+            (&other, OperationType::Create),
+            (&todo, OperationType::Update),
+        ] {
+            let state = serde_json::to_value(t).ok();
+            db.record_operation(&Operation::new(op, EntityType::Todo, t.id, None, state))
+                .await
+                .unwrap();
+        }
+
+        db.add_todo_note(todo.id, "Waiting on review")
+            .await
+            .unwrap();
+
+        let data = todo_export(&db, todo.clone(), true).await.unwrap();
+        let json = serde_json::to_value(&data).unwrap();
+        assert_eq!(json["todo"]["title"], "Handed-off task");
+        assert_eq!(json["notes"][0]["body"], "Waiting on review");
+        let history = json["history"].as_array().unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0]["operation_type"], "create");
+        assert_eq!(history[1]["operation_type"], "update");
+
+        let data = todo_export(&db, todo, false).await.unwrap();
+        assert!(
+            serde_json::to_value(&data)
+                .unwrap()
+                .get("history")
+                .is_none()
+        );
+    }
 }
@@ -3,11 +3,17 @@ use std::io::{self, Write};
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
+use chrono::{Local, Utc};
 use crossterm::ExecutableCommand;
 use crossterm::cursor;
 use crossterm::event::{self, Event, KeyCode, KeyEvent};
 use crossterm::terminal::{self, ClearType};
-use todoee_core::{Config, EntityType, LocalDb, Operation, OperationType, Priority, Todo};
+use todoee_core::{
+    Config, EntityType, FocusOutcome, FocusSession, IdMatch, LocalDb, Operation, OperationType,
+    Priority, Todo, short_id,
+};
+
+use super::{ids, output};
 
 /// Result of a focus session.
 enum FocusResult {
@@ -21,7 +27,7 @@ enum FocusResult {
     Completed,
 }
 
-pub async fn run(id: Option<String>, duration_mins: u32) -> Result<()> {
+pub async fn run(id: Option<String>, duration_mins: u32, fullscreen: bool) -> Result<()> {
     let config = Config::load().context("Failed to load configuration")?;
     let db_path = config.local_db_path()?;
 
@@ -35,43 +41,149 @@ pub async fn run(id: Option<String>, duration_mins: u32) -> Result<()> {
     let db = LocalDb::new(&db_path).await?;
     db.run_migrations().await?;
 
-    let todo = select_todo(&db, id.as_deref()).await?;
+    let todo = select_todo(&db, &config, id.as_deref()).await?;
     let duration = Duration::from_secs(u64::from(duration_mins) * 60);
     let start = Instant::now();
+    let mut session = FocusSession::new(todo.id, Utc::now());
+    session.planned_minutes = Some(duration_mins);
 
     // Set up terminal
     let mut stdout = io::stdout();
     terminal::enable_raw_mode()?;
+    if fullscreen {
+        stdout.execute(terminal::EnterAlternateScreen)?;
+    }
     stdout.execute(terminal::Clear(ClearType::All))?;
     stdout.execute(cursor::Hide)?;
 
     // Main loop
-    let result = run_timer(&mut stdout, &todo.title, duration, start);
+    let result = run_timer(
+        &mut stdout,
+        &todo.title,
+        duration,
+        start,
+        &mut session,
+        fullscreen,
+    );
 
     // Restore terminal
     stdout.execute(cursor::Show)?;
+    if fullscreen {
+        stdout.execute(terminal::LeaveAlternateScreen)?;
+    }
     terminal::disable_raw_mode()?;
 
-    handle_result(result?, &db, &todo, start).await
+    let result = result?;
+    session.ended_at = Utc::now();
+    session.outcome = Some(match result {
+        FocusResult::Done => FocusOutcome::Done,
+        FocusResult::Completed => FocusOutcome::Completed,
+        FocusResult::Skip | FocusResult::Quit => FocusOutcome::Stopped,
+    });
+    db.save_focus_session(&session).await?;
+    handle_result(result, &db, &todo, start, &session).await
 }
 
-async fn select_todo(db: &LocalDb, id: Option<&str>) -> Result<Todo> {
-    let todos = db.list_todos(false).await?;
+/// How far back `todoee focus --history` looks
+const HISTORY_DAYS: i64 = 30;
 
-    if let Some(id) = id {
-        let id_lower = id.to_lowercase();
-        let matches: Vec<_> = todos
-            .into_iter()
-            .filter(|t| t.id.to_string().to_lowercase().starts_with(&id_lower))
+/// List recent focus sessions, newest first.
+pub async fn history() -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+    if !db_path.exists() {
+        println!("No focus sessions yet.");
+        return Ok(());
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    let since = Utc::now() - chrono::Duration::days(HISTORY_DAYS);
+    let mut sessions = db.list_focus_sessions(since).await?;
+    sessions.reverse();
+
+    let mut titles = Vec::with_capacity(sessions.len());
+    for session in &sessions {
+        titles.push(db.get_todo(session.todo_id).await?.map(|t| t.title));
+    }
+
+    if output::is_json() {
+        let entries: Vec<_> = sessions
+            .iter()
+            .zip(&titles)
+            .map(|(session, title)| {
+                serde_json::json!({
+                    "id": session.id,
+                    "todo_id": session.todo_id,
+                    "title": title,
+                    "started_at": session.started_at,
+                    "ended_at": session.ended_at,
+                    "minutes": session.duration().num_minutes(),
+                    "planned_minutes": session.planned_minutes,
+                    "outcome": session.outcome,
+                    "interruptions": session.interruptions.len(),
+                })
+            })
             .collect();
+        return output::print_json(&entries);
+    }
+
+    if sessions.is_empty() {
+        println!("No focus sessions in the last {} days.", HISTORY_DAYS);
+        return Ok(());
+    }
+
+    println!(
+        "\x1b[1mFocus sessions (last {} days)\x1b[0m\n",
+        HISTORY_DAYS
+    );
+    for (session, title) in sessions.iter().zip(&titles) {
+        let planned = session
+            .planned_minutes
+            .map_or_else(|| "?".to_string(), |m| m.to_string());
+        let outcome = session
+            .outcome
+            .map_or_else(|| "-".to_string(), |o| o.to_string());
+        let interruptions = match session.interruptions.len() {
+            0 => String::new(),
+            1 => "  1 interruption".to_string(),
+            n => format!("  {} interruptions", n),
+        };
+        println!(
+            "  {}  {:>3}/{:>3} min  {:<9}  {}\x1b[90m{}\x1b[0m",
+            session
+                .started_at
+                .with_timezone(&Local)
+                .format("%a %b %d %H:%M"),
+            session.duration().num_minutes(),
+            planned,
+            outcome,
+            truncate(title.as_deref().unwrap_or("(deleted)"), 40),
+            interruptions
+        );
+    }
 
-        match matches.len() {
-            0 => anyhow::bail!("Todo not found"),
-            1 => Ok(matches.into_iter().next().unwrap()),
-            _ => {
+    let total: i64 = sessions.iter().map(|s| s.duration().num_minutes()).sum();
+    println!(
+        "\n  {} session{}, {} min focused",
+        sessions.len(),
+        if sessions.len() == 1 { "" } else { "s" },
+        total
+    );
+    Ok(())
+}
+
+async fn select_todo(db: &LocalDb, config: &Config, id: Option<&str>) -> Result<Todo> {
+    if let Some(id) = id {
+        match ids::resolve(db, config, id).await? {
+            IdMatch::NotFound => anyhow::bail!("Todo not found"),
+            IdMatch::Unique(todo) => Ok(*todo),
+            IdMatch::Ambiguous(matches) => {
                 eprintln!("Multiple todos match '{}'. Please be more specific:", id);
+                let id_len = ids::match_length(&matches);
                 for todo in &matches {
-                    let short_id = &todo.id.to_string()[..8];
+                    let short_id = short_id(&todo.id, id_len);
                     eprintln!("  {} [{}]", todo.title, short_id);
                 }
                 anyhow::bail!("Ambiguous ID - provide more characters")
@@ -79,7 +191,8 @@ async fn select_todo(db: &LocalDb, id: Option<&str>) -> Result<Todo> {
         }
     } else {
         // Pick the highest priority todo, or first if tied
-        todos
+        db.list_todos(false)
+            .await?
             .into_iter()
             .max_by_key(|t| priority_value(t.priority))
             .ok_or_else(|| anyhow::anyhow!("No todos to focus on"))
@@ -88,6 +201,7 @@ async fn select_todo(db: &LocalDb, id: Option<&str>) -> Result<Todo> {
 
 fn priority_value(priority: Priority) -> u8 {
     match priority {
+        Priority::Urgent => 4,
         Priority::High => 3,
         Priority::Medium => 2,
         Priority::Low => 1,
@@ -99,6 +213,7 @@ async fn handle_result(
     db: &LocalDb,
     todo: &Todo,
     start: Instant,
+    session: &FocusSession,
 ) -> Result<()> {
     match result {
         FocusResult::Done => {
@@ -133,6 +248,11 @@ async fn handle_result(
     let elapsed_mins = elapsed.as_secs() / 60;
     let elapsed_secs = elapsed.as_secs() % 60;
     println!("Focused for {}:{:02}", elapsed_mins, elapsed_secs);
+    match session.interruptions.len() {
+        0 => {}
+        1 => println!("Interrupted once"),
+        n => println!("Interrupted {} times", n),
+    }
 
     Ok(())
 }
@@ -142,22 +262,55 @@ fn run_timer(
     title: &str,
     duration: Duration,
     start: Instant,
+    session: &mut FocusSession,
+    fullscreen: bool,
 ) -> Result<FocusResult> {
+    // Reason being typed after `i`; the timer keeps running meanwhile
+    let mut reason: Option<String> = None;
+    // Terminal size of the last fullscreen frame, to clear after a resize
+    let mut size = None;
+
     loop {
         let elapsed = start.elapsed();
         if elapsed >= duration {
             return Ok(FocusResult::Completed);
         }
 
-        draw_ui(stdout, title, duration, elapsed)?;
+        if fullscreen {
+            let (cols, rows) = terminal::size()?;
+            if size.replace((cols, rows)) != Some((cols, rows)) {
+                stdout.execute(terminal::Clear(ClearType::All))?;
+            }
+            draw_big_clock(stdout, duration - elapsed, cols, rows)?;
+            draw_status(stdout, rows.saturating_sub(1), None, reason.as_deref())?;
+        } else {
+            draw_ui(stdout, title, duration, elapsed)?;
+            draw_status(stdout, 11, Some(session), reason.as_deref())?;
+        }
 
         // Check for input (poll for 100ms)
         if event::poll(Duration::from_millis(100))?
             && let Event::Key(KeyEvent { code, .. }) = event::read()?
         {
+            if let Some(text) = reason.as_mut() {
+                match code {
+                    KeyCode::Enter => {
+                        session.interrupt(text, Utc::now());
+                        reason = None;
+                    }
+                    KeyCode::Esc => reason = None,
+                    KeyCode::Backspace => {
+                        text.pop();
+                    }
+                    KeyCode::Char(c) => text.push(c),
+                    _ => {}
+                }
+                continue;
+            }
             match code {
                 KeyCode::Char('d') => return Ok(FocusResult::Done),
                 KeyCode::Char('s') => return Ok(FocusResult::Skip),
+                KeyCode::Char('i') => reason = Some(String::new()),
                 KeyCode::Char('q') | KeyCode::Esc => return Ok(FocusResult::Quit),
                 _ => {}
             }
@@ -165,6 +318,92 @@ fn run_timer(
     }
 }
 
+/// The line under the timer: the reason prompt after `i`, otherwise the
+/// interruptions logged so far (when `session` is given)
+fn draw_status(
+    stdout: &mut io::Stdout,
+    row: u16,
+    session: Option<&FocusSession>,
+    reason: Option<&str>,
+) -> Result<()> {
+    stdout.execute(cursor::MoveTo(0, row))?;
+    stdout.execute(terminal::Clear(ClearType::CurrentLine))?;
+    let interruptions = session.map_or(0, |s| s.interruptions.len());
+    match (reason, interruptions) {
+        (Some(text), _) => print!(
+            "  Interrupted by (Enter to log, Esc to cancel): {}\u{2588}",
+            text
+        ),
+        (None, 0) => {}
+        (None, n) => print!("  \x1b[90mInterruptions: {}\x1b[0m", n),
+    }
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Height in rows of [`big_text`]
+const BIG_HEIGHT: usize = 5;
+
+/// FIGlet-style block glyphs for the digits 0-9
+const BIG_DIGITS: [[&str; BIG_HEIGHT]; 10] = [
+    ["█████", "█   █", "█   █", "█   █", "█████"],
+    ["  █  ", " ██  ", "  █  ", "  █  ", " ███ "],
+    ["█████", "    █", "█████", "█    ", "█████"],
+    ["█████", "    █", " ████", "    █", "█████"],
+    ["█   █", "█   █", "█████", "    █", "    █"],
+    ["█████", "█    ", "█████", "    █", "█████"],
+    ["█████", "█    ", "█████", "█   █", "█████"],
+    ["█████", "    █", "   █ ", "  █  ", "  █  "],
+    ["█████", "█   █", "█████", "█   █", "█████"],
+    ["█████", "█   █", "█████", "    █", "█████"],
+];
+
+const BIG_COLON: [&str; BIG_HEIGHT] = ["   ", " █ ", "   ", " █ ", "   "];
+
+/// `text` (digits and colons) in big block letters, one string per row
+fn big_text(text: &str) -> [String; BIG_HEIGHT] {
+    let mut rows: [String; BIG_HEIGHT] = Default::default();
+    for (i, c) in text.chars().enumerate() {
+        let glyph = match c.to_digit(10) {
+            Some(d) => &BIG_DIGITS[d as usize],
+            None => &BIG_COLON,
+        };
+        for (row, part) in rows.iter_mut().zip(glyph) {
+            if i > 0 {
+                row.push_str("  ");
+            }
+            row.push_str(part);
+        }
+    }
+    rows
+}
+
+/// The `--fullscreen` screen: the countdown in big digits, centered,
+/// turning yellow in the last five minutes and red in the last one
+fn draw_big_clock(
+    stdout: &mut io::Stdout,
+    remaining: Duration,
+    cols: u16,
+    rows: u16,
+) -> Result<()> {
+    let secs = remaining.as_secs();
+    let color = match secs {
+        0..=60 => "31",
+        61..=300 => "33",
+        _ => "32",
+    };
+    let lines = big_text(&format!("{:02}:{:02}", secs / 60, secs % 60));
+    let width = lines[0].chars().count() as u16;
+    let left = cols.saturating_sub(width) / 2;
+    let top = rows.saturating_sub(BIG_HEIGHT as u16) / 2;
+    for (i, line) in lines.iter().enumerate() {
+        stdout.execute(cursor::MoveTo(left, top + i as u16))?;
+        print!("\x1b[1;{}m{}\x1b[0m", color, line);
+    }
+    stdout.flush()?;
+    Ok(())
+}
+
 fn draw_ui(
     stdout: &mut io::Stdout,
     title: &str,
@@ -213,7 +452,7 @@ fn draw_ui(
         "\x1b[1;36m\u{2502}\x1b[0m                                                  \x1b[1;36m\u{2502}\x1b[0m"
     );
     println!(
-        "\x1b[1;36m\u{2502}\x1b[0m  \x1b[90m[d] done  [s] skip  [q] quit\x1b[0m                  \x1b[1;36m\u{2502}\x1b[0m"
+        "\x1b[1;36m\u{2502}\x1b[0m  \x1b[90m[d] done  [s] skip  [i] interrupted  [q] quit\x1b[0m \x1b[1;36m\u{2502}\x1b[0m"
     );
     println!(
         "\x1b[1;36m\u{256e}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{256f}\x1b[0m"
@@ -224,9 +463,25 @@ fn draw_ui(
 }
 
 fn truncate(s: &str, max: usize) -> String {
-    if s.len() <= max {
+    if s.chars().count() <= max {
         s.to_string()
     } else {
-        format!("{}...", &s[..max - 3])
+        format!("{}...", s.chars().take(max - 3).collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_big_text_rows_line_up() {
+        let rows = big_text("25:00");
+        // Four digits, a colon and four gaps
+        let width = 4 * 5 + 3 + 4 * 2;
+        for row in &rows {
+            assert_eq!(row.chars().count(), width);
+        }
+        assert_eq!(rows[1], "    █  █       █   █   █  █   █");
     }
 }
@@ -1,12 +1,16 @@
 //! Garbage collection command for cleaning up old data.
 
 use std::fs;
+use std::io::{self, IsTerminal};
 
 use anyhow::{Context, Result};
 use chrono::Utc;
+use todoee_core::gc::{self, GcOptions, GcState};
 use todoee_core::{Config, LocalDb};
 
-pub async fn run(days: Option<i64>, dry_run: bool) -> Result<()> {
+use super::output;
+
+pub async fn run(days: Option<i64>, dry_run: bool, compact: bool) -> Result<()> {
     let config = Config::load().context("Failed to load config")?;
     let db_path = config.local_db_path()?;
 
@@ -20,53 +24,109 @@ pub async fn run(days: Option<i64>, dry_run: bool) -> Result<()> {
     let db = LocalDb::new(&db_path).await?;
     db.run_migrations().await?;
 
-    let days = days.unwrap_or(30);
+    let options = GcOptions {
+        days: days.unwrap_or(config.gc.days as i64),
+        compact,
+        exclude_categories: config.gc.excluded_categories(),
+    };
+    let days = options.days;
+    let now = Utc::now();
 
     // Get counts for stats
     let all_todos = db.list_todos(false).await?;
     let pending = all_todos.iter().filter(|t| !t.is_completed).count();
     let completed = all_todos.iter().filter(|t| t.is_completed).count();
+    let deleted = db.count_deleted_todos(0).await?;
 
     println!("Database stats:");
     println!("  Total todos:  {}", all_todos.len());
     println!("  Pending:      {}", pending);
     println!("  Completed:    {}", completed);
+    println!("  Deleted:      {} (restorable with undo)", deleted);
     println!();
 
-    let cutoff = Utc::now() - chrono::Duration::days(days);
-
     if dry_run {
         println!("Dry run - would clean items older than {} days", days);
 
         // Count what would be deleted
-        let old_completed = all_todos
-            .iter()
-            .filter(|t| t.is_completed && t.completed_at.map(|c| c < cutoff).unwrap_or(false))
-            .count();
+        let old_completed = gc::old_completed_todos(&db, &options, now).await?.len();
 
         println!("Would delete:");
         println!("  {} old completed todo(s)", old_completed);
-        println!("  Old operation history");
+        println!(
+            "  {} deleted todo(s) (purged permanently)",
+            db.count_deleted_todos(days).await?
+        );
+        println!(
+            "  {} expired AI cache entries",
+            db.count_expired_ai_cache().await?
+        );
+        if compact {
+            println!("  Intermediate edits in old operation history (compacted)");
+        } else {
+            println!("  Old operation history");
+        }
+        if !options.exclude_categories.is_empty() {
+            println!(
+                "Keeping completed todos in: {}",
+                options.exclude_categories.join(", ")
+            );
+        }
         return Ok(());
     }
 
-    // Delete old operations
-    let deleted_ops = db.clear_old_operations(days).await?;
-
-    // Delete old completed todos
-    let mut deleted_todos = 0;
-    for todo in all_todos.iter().filter(|t| t.is_completed) {
-        if let Some(completed_at) = todo.completed_at
-            && completed_at < cutoff
-        {
-            db.delete_todo(todo.id).await?;
-            deleted_todos += 1;
-        }
+    let summary = gc::collect(&db, &options, now).await?;
+    // A run by hand counts for the automatic schedule too
+    let _ = GcState {
+        last_run: Some(now),
     }
+    .save(&GcState::path(&Config::config_dir()?));
 
     println!("Cleanup complete:");
-    println!("  Deleted {} old operation(s)", deleted_ops);
-    println!("  Deleted {} old completed todo(s)", deleted_todos);
+    if compact {
+        println!(
+            "  Compacted {} old operation(s)",
+            summary.compacted_operations
+        );
+    } else {
+        println!("  Deleted {} old operation(s)", summary.deleted_operations);
+    }
+    println!("  Deleted {} old completed todo(s)", summary.deleted_todos);
+    println!("  Purged {} deleted todo(s)", summary.purged_todos);
+    println!("  Purged {} expired AI cache entries", summary.purged_cache);
 
     Ok(())
 }
+
+/// After a command: clean up when `gc.auto` is on and the interval has
+/// passed, with a one-line summary on stderr. Failures are only mentioned.
+pub async fn auto() {
+    if Config::is_demo() {
+        return;
+    }
+    let Ok(config) = Config::load() else {
+        return;
+    };
+    if !config.gc.auto {
+        return;
+    }
+
+    let result = async {
+        let config_dir = Config::config_dir()?;
+        let db = LocalDb::new(&config.local_db_path()?).await?;
+        gc::auto_collect(&db, &config.gc, &config_dir, Utc::now()).await
+    }
+    .await;
+
+    if output::is_json() || !io::stderr().is_terminal() {
+        return;
+    }
+    match result {
+        Ok(Some(summary)) if !summary.is_empty() && !output::is_quiet() => {
+            eprintln!();
+            eprintln!("\x1b[90mAutomatic cleanup removed {}\x1b[0m", summary);
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("\x1b[33mwarning:\x1b[0m automatic cleanup failed: {:#}", e),
+    }
+}
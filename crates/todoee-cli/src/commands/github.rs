@@ -0,0 +1,183 @@
+//! `todoee github`: import the GitHub issues assigned to you as todos, and
+//! close them again once their todo is done.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use todoee_core::github::{self, GithubClient, REF_PREFIX};
+use todoee_core::{Config, LocalDb, Operation, Todo, net, short_id};
+use uuid::Uuid;
+
+use super::{add, ids, output};
+
+/// Timeout for each GitHub API request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Subcommand, Clone)]
+pub enum GithubCommand {
+    /// Import open issues assigned to you as todos
+    Pull {
+        /// Only issues in this repository, as owner/name
+        #[arg(long)]
+        repo: Option<String>,
+        /// Category for every imported issue, instead of github.labels
+        #[arg(short, long)]
+        category: Option<String>,
+    },
+}
+
+pub async fn run(cmd: GithubCommand) -> Result<()> {
+    match cmd {
+        GithubCommand::Pull { repo, category } => pull(repo.as_deref(), category.as_deref()).await,
+    }
+}
+
+async fn pull(repo: Option<&str>, category: Option<&str>) -> Result<()> {
+    if let Some(repo) = repo
+        && !github::is_repo(repo)
+    {
+        anyhow::bail!("Invalid repository '{}': expected owner/name", repo);
+    }
+    let config = Config::load().context("Failed to load configuration")?;
+    let client = client(&config)?;
+    let db = LocalDb::new(&config.local_db_path()?).await?;
+    db.run_migrations().await?;
+
+    let issues = client
+        .assigned_issues(repo)
+        .await
+        .context("Failed to fetch issues from GitHub")?;
+    let known = db.external_refs(REF_PREFIX).await?;
+
+    let mut categories: HashMap<String, Uuid> = HashMap::new();
+    let mut todos = Vec::new();
+    for issue in issues.iter().filter(|i| !known.contains(&i.external_ref())) {
+        let labels = issue.label_names();
+        let name = category.or_else(|| config.github.category_for(&labels));
+        let category_id = match name {
+            Some(name) => match categories.get(name) {
+                Some(&id) => Some(id),
+                None => {
+                    let id = add::get_or_create_category(&db, name, None).await?;
+                    categories.insert(name.to_string(), id);
+                    Some(id)
+                }
+            },
+            None => None,
+        };
+        todos.push(issue.to_todo(None, category_id));
+    }
+    if !todos.is_empty() {
+        db.create_todos(&todos, &Operation::batch_create(&todos))
+            .await?;
+    }
+
+    // Issues completed here since the last pull that are still open there
+    let mut closed = Vec::new();
+    if config.github.close_issues {
+        let open: HashSet<String> = issues.iter().map(|i| i.external_ref()).collect();
+        for (repo, number) in to_close(&db.list_linked_todos(REF_PREFIX).await?, &open) {
+            match client.close_issue(&repo, number).await {
+                Ok(()) => closed.push(github::external_ref(&repo, number)),
+                Err(e) => eprintln!("Warning: couldn't close {}#{}: {:#}", repo, number, e),
+            }
+        }
+    }
+
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "imported": todos,
+            "closed": closed,
+        }));
+    }
+    let id_len = ids::id_length(&db, &config).await?;
+    for todo in &todos {
+        output::say!(
+            "\u{2713} Imported: {} [{}] \x1b[90m{}\x1b[0m",
+            todo.title,
+            short_id(&todo.id, id_len),
+            todo.external_ref.as_deref().unwrap_or_default()
+        );
+    }
+    for reference in &closed {
+        output::say!(
+            "\u{2713} Closed {}",
+            reference.trim_start_matches(REF_PREFIX)
+        );
+    }
+    let skipped = issues.len() - todos.len();
+    output::say!(
+        "{} issue{} imported, {} already linked",
+        todos.len(),
+        if todos.len() == 1 { "" } else { "s" },
+        skipped
+    );
+    Ok(())
+}
+
+/// Close the issue a just-completed todo was imported from, when
+/// `github.close_issues` is on. Failures are warnings: the next
+/// `todoee github pull` tries again.
+pub async fn close_completed(config: &Config, todo: &Todo) {
+    if !config.github.close_issues {
+        return;
+    }
+    let Some((repo, number)) = todo.external_ref.as_deref().and_then(github::parse_ref) else {
+        return;
+    };
+    let result = match client(config) {
+        Ok(client) => client.close_issue(repo, number).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) if output::is_json() => {}
+        Ok(()) => output::say!("  Closed {}#{} on GitHub", repo, number),
+        Err(e) => eprintln!("Warning: couldn't close {}#{}: {:#}", repo, number, e),
+    }
+}
+
+fn client(config: &Config) -> Result<GithubClient> {
+    let http = net::http_client(&config.network, REQUEST_TIMEOUT)?;
+    GithubClient::new(http, &config.github)
+}
+
+/// Issues of completed todos in `linked` that are still in `open`
+fn to_close(linked: &[Todo], open: &HashSet<String>) -> Vec<(String, u64)> {
+    linked
+        .iter()
+        .filter(|t| t.is_completed)
+        .filter_map(|t| t.external_ref.as_deref())
+        .filter(|reference| open.contains(*reference))
+        .filter_map(github::parse_ref)
+        .map(|(repo, number)| (repo.to_string(), number))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_open_issues_of_completed_todos_close() {
+        let linked_todo = |number: u64, completed: bool| {
+            let mut todo = Todo::new(format!("Issue {}", number), None);
+            todo.external_ref = Some(github::external_ref("acme/web", number));
+            if completed {
+                todo.mark_complete();
+            }
+            todo
+        };
+        let linked = [
+            linked_todo(1, true),
+            linked_todo(2, false),
+            linked_todo(3, true),
+        ];
+        let open: HashSet<String> = [1, 2]
+            .into_iter()
+            .map(|n| github::external_ref("acme/web", n))
+            .collect();
+        assert_eq!(to_close(&linked, &open), [("acme/web".to_string(), 1)]);
+    }
+}
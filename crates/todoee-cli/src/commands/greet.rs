@@ -0,0 +1,307 @@
+//! Short summary for a shell greeting: `todoee greet` in .zshrc or .bashrc.
+//!
+//! Shell startup must stay fast, so the numbers come from `greet-cache.json`
+//! while it is fresh. A stale cache is recomputed within the latency budget;
+//! if that runs over, the cached numbers (or just the greeting) are shown and
+//! a detached `todoee greet --refresh` updates the cache for next time.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use todoee_core::{Config, LocalDb, short_id};
+
+use super::context::ActiveContext;
+use super::{ids, now, output};
+
+/// File in the config directory holding the last summary
+const CACHE_FILE: &str = "greet-cache.json";
+
+/// How long a summary is reused when the database hasn't changed
+const CACHE_MINUTES: i64 = 10;
+
+/// Days of history searched for the streak
+const STREAK_DAYS: i64 = 366;
+
+const QUOTES: &[(&str, &str)] = &[
+    ("Well begun is half done.", "Aristotle"),
+    (
+        "The secret of getting ahead is getting started.",
+        "Mark Twain",
+    ),
+    (
+        "It does not matter how slowly you go as long as you do not stop.",
+        "Confucius",
+    ),
+    (
+        "Action is the foundational key to all success.",
+        "Pablo Picasso",
+    ),
+    (
+        "Simplicity is the ultimate sophistication.",
+        "Leonardo da Vinci",
+    ),
+    (
+        "Great things are done by a series of small things brought together.",
+        "Vincent van Gogh",
+    ),
+    (
+        "Energy and persistence conquer all things.",
+        "Benjamin Franklin",
+    ),
+    ("You may delay, but time will not.", "Benjamin Franklin"),
+];
+
+/// What the greeting shows, as cached
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Summary {
+    pub computed_at: DateTime<Utc>,
+    /// Days in a row with at least one completion, ending today or yesterday
+    pub streak: u32,
+    pub due_today: usize,
+    pub overdue: usize,
+    /// Title and short ID of what `todoee now` would recommend first
+    pub next: Option<(String, String)>,
+}
+
+impl Summary {
+    /// Whether the summary can be shown as is: computed today, recently, and
+    /// not before the database last changed
+    pub fn is_fresh(&self, now: DateTime<Utc>, db_modified: Option<DateTime<Utc>>) -> bool {
+        self.is_today(now)
+            && now - self.computed_at < Duration::minutes(CACHE_MINUTES)
+            && db_modified.is_none_or(|modified| modified <= self.computed_at)
+    }
+
+    /// Whether the summary was computed on the local day of `now`
+    fn is_today(&self, now: DateTime<Utc>) -> bool {
+        self.computed_at.with_timezone(&Local).date_naive()
+            == now.with_timezone(&Local).date_naive()
+            && self.computed_at <= now
+    }
+}
+
+pub async fn run(quote: bool, budget: u64, refresh: bool) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let cache_path = Config::config_dir()?.join(CACHE_FILE);
+    let db_path = config.local_db_path()?;
+
+    if refresh {
+        let summary = compute(&config, &db_path).await?;
+        return save(&cache_path, &summary);
+    }
+
+    let now = Utc::now();
+    let cached = load(&cache_path);
+    let summary = match &cached {
+        Some(summary) if summary.is_fresh(now, modified(&db_path)) => cached,
+        _ => {
+            let budget = std::time::Duration::from_millis(budget);
+            match tokio::time::timeout(budget, compute(&config, &db_path)).await {
+                Ok(summary) => {
+                    let summary = summary?;
+                    // Best effort: the greeting is shown either way
+                    let _ = save(&cache_path, &summary);
+                    Some(summary)
+                }
+                Err(_) => {
+                    refresh_in_background();
+                    cached.filter(|summary| summary.is_today(now))
+                }
+            }
+        }
+    };
+
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "summary": summary,
+            "quote": quote.then(|| quote_of_the_day(Local::now().date_naive())),
+        }));
+    }
+
+    let local = Local::now();
+    let greeting = match local.hour() {
+        5..=11 => "Good morning",
+        12..=17 => "Good afternoon",
+        _ => "Good evening",
+    };
+    println!(
+        "\x1b[1m{}!\x1b[0m \x1b[90m{}\x1b[0m",
+        greeting,
+        local.format("%A, %B %-d")
+    );
+    if let Some(summary) = &summary {
+        let mut parts = Vec::new();
+        if summary.streak > 0 {
+            parts.push(format!("\x1b[33m{}-day streak\x1b[0m", summary.streak));
+        }
+        parts.push(format!("{} due today", summary.due_today));
+        if summary.overdue > 0 {
+            parts.push(format!("\x1b[31m{} overdue\x1b[0m", summary.overdue));
+        }
+        println!("  {}", parts.join(" \u{00b7} "));
+        if let Some((title, id)) = &summary.next {
+            println!(
+                "  \x1b[1;32m\u{2192}\x1b[0m {} \x1b[90m[{}]\x1b[0m",
+                title, id
+            );
+        }
+    }
+    if quote {
+        let (text, author) = quote_of_the_day(local.date_naive());
+        println!("  \x1b[3;90m\"{}\" \u{2014} {}\x1b[0m", text, author);
+    }
+    Ok(())
+}
+
+async fn compute(config: &Config, db_path: &Path) -> Result<Summary> {
+    let computed_at = Utc::now();
+    let today = computed_at.with_timezone(&Local).date_naive();
+    if !db_path.exists() {
+        return Ok(Summary {
+            computed_at,
+            streak: 0,
+            due_today: 0,
+            overdue: 0,
+            next: None,
+        });
+    }
+
+    let db = LocalDb::new(db_path).await?;
+    db.run_migrations().await?;
+    let id_len = ids::id_length(&db, config).await?;
+
+    let completed: BTreeSet<NaiveDate> = db
+        .activity_by_hour(computed_at - Duration::days(STREAK_DAYS))
+        .await?
+        .into_iter()
+        .filter(|bucket| bucket.completed > 0)
+        .map(|bucket| bucket.hour.with_timezone(&Local).date_naive())
+        .collect();
+
+    let mut todos = db.list_todos(true).await?;
+    if let Some(context) = ActiveContext::load(&db, config).await? {
+        context.retain(&mut todos);
+    }
+    let due_today = todos
+        .iter()
+        .filter(|t| {
+            t.due_date
+                .is_some_and(|due| due.with_timezone(&Local).date_naive() == today)
+        })
+        .count();
+    let overdue = todos
+        .iter()
+        .filter(|t| t.due_date.is_some_and(|due| due < computed_at))
+        .count();
+    let next = todos
+        .iter()
+        .map(|t| (t, now::calculate_score(t, config).0))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(t, _)| (t.title.clone(), short_id(&t.id, id_len)));
+
+    Ok(Summary {
+        computed_at,
+        streak: streak(&completed, today),
+        due_today,
+        overdue,
+        next,
+    })
+}
+
+/// Days in a row with a completion, counting back from `today`, or from
+/// yesterday while nothing is done yet today
+pub fn streak(completed: &BTreeSet<NaiveDate>, today: NaiveDate) -> u32 {
+    let mut day = if completed.contains(&today) {
+        today
+    } else {
+        today - Duration::days(1)
+    };
+    let mut count = 0;
+    while completed.contains(&day) {
+        count += 1;
+        day -= Duration::days(1);
+    }
+    count
+}
+
+fn quote_of_the_day(date: NaiveDate) -> (&'static str, &'static str) {
+    QUOTES[date.num_days_from_ce() as usize % QUOTES.len()]
+}
+
+/// When the database last changed, its write-ahead log included
+fn modified(db_path: &Path) -> Option<DateTime<Utc>> {
+    let mut wal = db_path.as_os_str().to_owned();
+    wal.push("-wal");
+    [db_path.to_path_buf(), PathBuf::from(wal)]
+        .iter()
+        .filter_map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .max()
+        .map(|time: SystemTime| DateTime::<Utc>::from(time))
+}
+
+fn load(path: &Path) -> Option<Summary> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+fn save(path: &Path, summary: &Summary) -> Result<()> {
+    fs::write(path, serde_json::to_string(summary)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Update the cache from a detached process, so this shell doesn't wait
+fn refresh_in_background() {
+    let Ok(exe) = std::env::current_exe() else {
+        return;
+    };
+    let mut command = Command::new(exe);
+    command
+        .args(["greet", "--refresh"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    if let Some(profile) = Config::profile() {
+        command.env(todoee_core::config::PROFILE_ENV, profile);
+    }
+    let _ = command.spawn();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streak_and_freshness() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+        let completed: BTreeSet<_> = [day(10), day(12), day(13), day(14)].into();
+        assert_eq!(streak(&completed, day(14)), 3);
+        assert_eq!(streak(&completed, day(15)), 3, "today isn't over yet");
+        assert_eq!(streak(&completed, day(16)), 0);
+
+        let now = Local::now()
+            .date_naive()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap()
+            .with_timezone(&Utc);
+        let summary = Summary {
+            computed_at: now - Duration::minutes(1),
+            streak: 3,
+            due_today: 2,
+            overdue: 0,
+            next: None,
+        };
+        assert!(summary.is_fresh(now, None));
+        assert!(summary.is_fresh(now, Some(now - Duration::minutes(5))));
+        assert!(!summary.is_fresh(now, Some(now)), "database changed since");
+        assert!(!summary.is_fresh(now + Duration::minutes(CACHE_MINUTES), None));
+    }
+}
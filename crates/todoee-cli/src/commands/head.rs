@@ -4,7 +4,9 @@ use std::fs;
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use todoee_core::{Config, LocalDb, Priority, Todo};
+use todoee_core::{Config, LocalDb, Priority, Todo, short_id};
+
+use super::{ids, output};
 
 /// Show the N most recently created todos.
 pub async fn head(count: usize, all: bool) -> Result<()> {
@@ -23,13 +25,17 @@ pub async fn head(count: usize, all: bool) -> Result<()> {
 
     let todos = db.list_todos_head(count, all).await?;
 
+    if output::is_json() {
+        return output::print_json(&todos);
+    }
+
     if todos.is_empty() {
         println!("No todos.");
         return Ok(());
     }
 
     println!("Last {} todos:\n", todos.len());
-    print_todos(&todos);
+    print_todos(&todos, ids::id_length(&db, &config).await?);
     Ok(())
 }
 
@@ -50,17 +56,21 @@ pub async fn tail(count: usize, all: bool) -> Result<()> {
 
     let todos = db.list_todos_tail(count, all).await?;
 
+    if output::is_json() {
+        return output::print_json(&todos);
+    }
+
     if todos.is_empty() {
         println!("No todos.");
         return Ok(());
     }
 
     println!("Oldest {} todos:\n", todos.len());
-    print_todos(&todos);
+    print_todos(&todos, ids::id_length(&db, &config).await?);
     Ok(())
 }
 
-fn print_todos(todos: &[Todo]) {
+fn print_todos(todos: &[Todo], id_len: usize) {
     for todo in todos {
         let check = if todo.is_completed {
             "\x1b[32m[x]\x1b[0m"
@@ -69,12 +79,13 @@ fn print_todos(todos: &[Todo]) {
         };
 
         let pri = match todo.priority {
+            Priority::Urgent => "\x1b[1;97;41m!!!\x1b[0m",
             Priority::High => "\x1b[31m!!!\x1b[0m",
             Priority::Medium => "\x1b[33m!! \x1b[0m",
             Priority::Low => "\x1b[90m!  \x1b[0m",
         };
 
-        let id = &todo.id.to_string()[..8];
+        let id = short_id(&todo.id, id_len);
         let age = format_age(todo.created_at);
 
         println!(
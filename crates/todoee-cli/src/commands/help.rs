@@ -11,6 +11,7 @@ const HELP_TEXT: &str = r#"
 └─────────────────────────────────────────────────────────────────────────────────┘
 
   Launch interactive TUI:     todoee
+  Learn the TUI keys:         todoee tutorial
   Add a task:                 todoee add "Buy groceries"
   Add with AI parsing:        todoee add "Review PR by Friday" --ai
   List tasks:                 todoee list
@@ -24,16 +25,33 @@ const HELP_TEXT: &str = r#"
   add, a        Add a new task (offline by default)
                   todoee add "task description"
                   todoee add "urgent task" -p 3 -c work
+                  todoee add "pay rent friday"                # Due Friday, no AI
+                  todoee add "call mom" -r "tomorrow 3pm"     # Reminder
+                  todoee add "deploy friday 2pm" --remind-before 2h
+                  todoee add "Deploy 🚀"                      # Icon from emoji
+                  todoee add "Water plants" --icon 🌱
                   todoee add "Review PR by Friday" --ai
+                  todoee add "Review PR by Friday" --ai --yes      # Skip review
+                  todoee add "Review PR by Friday" --ai --dry-run  # Parse only
+                  todoee add --stdin -c work < tasks.txt      # One task per line
+                  todoee add --from-branch                    # Named after the git branch
 
   list, ls      List tasks with filters
                   todoee list                    # Pending tasks
                   todoee list --today            # Due today
                   todoee list --all              # Include completed
                   todoee list -c work            # By category
+                  todoee list -P launch          # By project
+                  todoee list --week 42          # Due in ISO week 42
+                  todoee list "pri:high due<+3d" # By query
+                  todoee list --sort urgency     # Most urgent first, with scores
 
   done, d       Mark task as complete
                   todoee done abc1               # Use short ID prefix
+                  todoee done 2                  # Or #2 of the last list
+
+  reopen, undone  Mark a completed task as incomplete again
+                  todoee reopen abc1
 
   delete, rm    Permanently delete a task
                   todoee delete abc1
@@ -41,6 +59,15 @@ const HELP_TEXT: &str = r#"
   edit          Modify a task
                   todoee edit abc1 --title "New title"
                   todoee edit abc1 -p 3 -c urgent
+                  todoee edit abc1 --due "2026-03-01 14:30"
+                  todoee edit abc1 --reminder "tomorrow 9am"   # --clear-reminder removes it
+                  todoee edit abc1 --description "Ask about the invoice"
+                  todoee edit abc1 -P launch     # Move to a project
+                  todoee edit abc1 --remind-before none   # Back to the default
+                  todoee edit abc1 --no-nag      # No repeat notifications when overdue
+
+  ai revert     Restore what you typed in place of AI-set fields
+                  todoee ai revert abc1
 
 ┌─────────────────────────────────────────────────────────────────────────────────┐
 │  GIT-LIKE COMMANDS                                                              │
@@ -56,6 +83,19 @@ const HELP_TEXT: &str = r#"
                   todoee log                     # Last 10 operations
                   todoee log -n 20               # Last 20
                   todoee log --oneline           # Compact format
+                  todoee log --stats             # History size and limits
+
+  history       Show the commands you ran (log shows the changes)
+                  todoee history                 # Last 20 commands
+                  todoee history -n 50
+
+  repeat        Re-run a command from history (like !!)
+                  todoee repeat                  # The last command
+                  todoee repeat 42               # Command 42 from history
+
+  alias         List your command aliases ([aliases] in config)
+                  todoee config set aliases.today "list --today"
+                  todoee today                   # Runs 'list --today'
 
   diff          Show recent changes
                   todoee diff                    # Last 24 hours
@@ -68,6 +108,14 @@ const HELP_TEXT: &str = r#"
                   todoee stash list              # View stash
                   todoee stash clear             # Clear all stashed
 
+  hook          Complete tasks from git commit messages
+                  todoee hook install            # Then commit with "todoee:done abc1"
+                  todoee hook uninstall
+
+  github        Import GitHub issues assigned to you
+                  todoee github pull             # Token from $GITHUB_TOKEN
+                  todoee github pull --repo acme/web
+
 ┌─────────────────────────────────────────────────────────────────────────────────┐
 │  VIEW COMMANDS                                                                  │
 └─────────────────────────────────────────────────────────────────────────────────┘
@@ -84,12 +132,42 @@ const HELP_TEXT: &str = r#"
   overdue       Show all past-due tasks
                   todoee overdue
 
-  search        Fuzzy search tasks
+  search        Fuzzy search tasks, or filter them with a ':' query
                   todoee search "meeting"
+                  todoee search ":pri:high due<=+3d"
 
   show          View detailed task info
                   todoee show abc1
 
+  note          Timestamped progress notes on a task
+                  todoee note add abc1 "spoke with vendor"
+                  todoee note list abc1
+
+  attach        Link a file or URL to a task (o opens it in the TUI)
+                  todoee attach abc1 ~/docs/spec.pdf
+                  todoee attach abc1 https://example.com/pr/42
+
+  agenda        Today's summary, then events and due tasks day by day
+                  todoee agenda                  # Next 7 days
+                  todoee agenda -d 1             # Just today
+                  todoee agenda --summary        # Due today, overdue, what first
+
+  event         Manage calendar events (reminded by the daemon)
+                  todoee event add "standup" 9:30-9:45 --recur weekdays
+                  todoee event list
+                  todoee event delete abc1
+
+  category      List and rename categories
+                  todoee category list           # Todo counts per category
+                  todoee category rename work job --preview
+
+  project       Group todos into projects and track progress
+                  todoee project create launch
+                  todoee edit abc1 -P launch     # Move a todo into it
+                  todoee list -P launch          # Its todos
+                  todoee project list            # Progress bars per project
+                  todoee project archive launch
+
 ┌─────────────────────────────────────────────────────────────────────────────────┐
 │  PRODUCTIVITY                                                                   │
 └─────────────────────────────────────────────────────────────────────────────────┘
@@ -97,19 +175,55 @@ const HELP_TEXT: &str = r#"
   now           Get smart recommendation for what to work on
                   todoee now
 
+  greet         Quick summary for your shell's startup file
+                  todoee greet --quote           # Cached, never slows the shell
+
   focus         Start a Pomodoro focus session
                   todoee focus                   # 25 min, auto-picks task
                   todoee focus abc1              # Focus on specific task
                   todoee focus -d 45             # Custom duration (45 min)
+                  todoee focus --fullscreen      # Big countdown only
+                  todoee focus --history         # Sessions from the last 30 days
 
                 Focus mode controls:
                   Space    Pause/Resume
                   Enter    Complete early
+                  i        Log an interruption (optional reason)
                   q        Quit
 
+  snooze        Push a todo's reminder later
+                  todoee snooze abc1 10m         # Also 1h, 1h30m, 2d or 45
+
+  remind        Set, clear and list reminders
+                  todoee remind set abc1 "tomorrow 9am"
+                  todoee remind clear abc1
+                  todoee remind list             # Upcoming, soonest first
+
+  timeblock     Plan today in time blocks, with a reminder per block
+                  todoee timeblock               # Adjust on a timeline, Enter saves
+                  todoee timeblock --yes         # Save the suggested plan
+
   insights      View productivity analytics
                   todoee insights                # Last 30 days
                   todoee insights --days 7       # Last 7 days
+                  todoee insights --days 365     # A year, counted by the database
+
+  stats         Created/completed todos over time
+                  todoee stats                   # Daily, last 30 days
+                  todoee stats --interval week --since 2026-01-01
+                  todoee stats --export csv -o backlog.csv
+  journal       What you completed on a day, with a reflection
+                  todoee journal yesterday
+                  todoee journal --note "Mornings went well"
+
+  track export  Export focus sessions as time entries
+                  todoee track export -f toggl-csv -o toggl.csv
+                  todoee track export -f beeminder --unit sessions
+                  todoee track export --days 7   # JSON, last 7 days
+
+  matrix        Eisenhower matrix (urgent = due in 2 days, important = high priority)
+                  todoee matrix                  # Show the four quadrants
+                  todoee matrix move abc1 schedule   # Adjusts priority/due date
 
 ┌─────────────────────────────────────────────────────────────────────────────────┐
 │  BATCH OPERATIONS                                                               │
@@ -128,16 +242,55 @@ const HELP_TEXT: &str = r#"
 │  MAINTENANCE                                                                    │
 └─────────────────────────────────────────────────────────────────────────────────┘
 
-  gc            Clean up old completed tasks and history
-                  todoee gc                      # Delete > 30 days old
+  gc            Clean up old completed tasks, deleted tasks, history and AI cache
+                  todoee gc                      # Delete > gc.days (30) old
                   todoee gc --days 7             # Delete > 7 days old
                   todoee gc --dry-run            # Preview only
+                  todoee gc --compact            # Shrink history, keep snapshots
+                  todoee config set gc.auto true # Clean up weekly by itself
 
   sync          Sync with remote server (if configured)
                   todoee sync
 
+  normalize-dates  Move imported due times to one time of day (undoable)
+                  todoee normalize-dates --time 09:00 --timezone Europe/Berlin
+                  todoee normalize-dates --time none --preview
+
+  serve         Serve a JSON API for web and mobile frontends
+                  todoee serve                   # http://127.0.0.1:8080/api
+                  todoee serve --bind 0.0.0.0    # Reachable from other machines
+
+  serve-ics     Serve a read-only calendar feed of your todos
+                  todoee serve-ics               # http://127.0.0.1:8080/todoee.ics
+                  todoee serve-ics --port 9000
+                  todoee export -f ics           # Or export a one-off .ics file
+
+  devices       Manage devices allowed to sync
+                  todoee devices list
+                  todoee devices revoke abc1     # Lost laptop: stop it syncing
+                  todoee devices wipe            # Delete this device's local data
+
+  conflicts     Review todos changed here and on another device
+                  todoee conflicts list          # Both versions, field by field
+                  todoee conflicts resolve abc1 --keep local|remote|merge
+
+  login         Log in so sync only exchanges your todos
+                  todoee login                   # Creates the account the first time
+                  todoee whoami                  # Show who this device syncs as
+                  todoee logout
+
   config        Configure settings
                   todoee config --init           # Interactive setup
+                  todoee config get ai.model     # Print one setting
+                  todoee config set display.theme light   # dark/light/solarized/gruvbox/custom
+                  todoee config set display.colors.primary '#ff8800'
+                  todoee config set webhooks.slack.url https://hooks.slack.com/...
+                  todoee config export settings.toml   # Settings and aliases, no secrets
+                  todoee config import settings.toml
+
+  self-update   Install the latest release (signature checked)
+                  todoee self-update
+                  todoee self-update --check     # Only check for a new version
 
 ┌─────────────────────────────────────────────────────────────────────────────────┐
 │  PRIORITY LEVELS                                                                │
@@ -146,6 +299,7 @@ const HELP_TEXT: &str = r#"
     -p 1    Low priority      (green  ! )
     -p 2    Medium priority   (yellow !!)
     -p 3    High priority     (red   !!!)
+    -p 4    Urgent priority   (red   !!!, reversed)
 
 ┌─────────────────────────────────────────────────────────────────────────────────┐
 │  TASK IDs                                                                       │
@@ -158,6 +312,12 @@ const HELP_TEXT: &str = r#"
 
   Example:      todoee done a1b2
 
+  Lists show the shortest unique prefix. Fix the length with:
+                todoee config set display.id_length 8
+
+  `todoee list` also numbers its rows. Commands that take a todo ID take
+  those numbers until the next list: todoee done 2  (or '#2', quoted)
+
 ┌─────────────────────────────────────────────────────────────────────────────────┐
 │  COMMON WORKFLOWS                                                               │
 └─────────────────────────────────────────────────────────────────────────────────┘
@@ -180,6 +340,23 @@ const HELP_TEXT: &str = r#"
     todoee gc                   # Remove old items
     todoee insights             # Review productivity
 
+  Scripting (JSON on stdout):
+    todoee list --json | jq '.[].title'
+    TODOEE_OUTPUT=json todoee overdue
+    todoee -q done abc1 && echo ok   # -q: only errors; --verbose: more detail
+
+  Focus on one area (applies to list, search, now and the TUI):
+    todoee context define work "category:work or priority:high"
+    todoee context set work     # todoee context none to clear
+
+  Separate work and personal todos:
+    todoee profile create work  # Own config and database
+    todoee profile switch work  # Or: todoee --profile work list
+
+  Screenshots and bug reports (made-up titles, nothing saved):
+    todoee --demo               # TUI with anonymized tasks
+    todoee --demo list
+
 ┌─────────────────────────────────────────────────────────────────────────────────┐
 │  MORE HELP                                                                      │
 └─────────────────────────────────────────────────────────────────────────────────┘
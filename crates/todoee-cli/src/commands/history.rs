@@ -0,0 +1,129 @@
+//! Command history: the CLI invocations themselves, as opposed to the
+//! operation log of changes they made.
+
+use std::fs;
+use std::process;
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use todoee_core::{CommandHistoryEntry, Config, LocalDb};
+
+use super::output;
+
+async fn open_db() -> Result<LocalDb> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+    Ok(db)
+}
+
+/// Remember an invocation for `todoee history` and `todoee repeat`.
+pub async fn record(args: &[String]) -> Result<()> {
+    open_db().await?.record_command(args).await
+}
+
+/// Show the most recent invocations, oldest first like a shell history.
+pub async fn run(limit: i64) -> Result<()> {
+    let db = open_db().await?;
+    let mut history = db.list_command_history(limit).await?;
+    history.reverse();
+
+    if output::is_json() {
+        return output::print_json(&history);
+    }
+
+    if history.is_empty() {
+        println!("No command history yet.");
+        return Ok(());
+    }
+
+    for entry in &history {
+        println!(
+            "\x1b[90m{:>5}\x1b[0m  {}  todoee {}",
+            entry.id,
+            entry
+                .invoked_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M"),
+            format_command(&entry.args)
+        );
+    }
+
+    Ok(())
+}
+
+/// Re-run the last command, or the one numbered `id` in `todoee history`.
+pub async fn repeat(id: Option<i64>) -> Result<()> {
+    let db = open_db().await?;
+    let entry: Option<CommandHistoryEntry> = match id {
+        Some(id) => db.get_command(id).await?,
+        None => db.list_command_history(1).await?.into_iter().next(),
+    };
+    let Some(entry) = entry else {
+        match id {
+            Some(id) => anyhow::bail!(
+                "No command numbered {} (see 'todoee history' for the numbers)",
+                id
+            ),
+            None => anyhow::bail!("No command history yet"),
+        }
+    };
+    drop(db);
+
+    println!(
+        "\x1b[90m\u{21BB} todoee {}\x1b[0m",
+        format_command(&entry.args)
+    );
+
+    let exe = std::env::current_exe().context("Failed to locate the todoee executable")?;
+    let status = process::Command::new(exe)
+        .args(&entry.args)
+        .status()
+        .context("Failed to re-run command")?;
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+
+    Ok(())
+}
+
+/// Join arguments for display, quoting empty ones and ones with spaces or quotes.
+pub fn format_command(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'')
+            {
+                format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+            } else {
+                arg.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_command_quotes_when_needed() {
+        let args: Vec<String> = ["add", "Buy milk", "-p", "3", "", "say \"hi\""]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            format_command(&args),
+            r#"add "Buy milk" -p 3 "" "say \"hi\"""#
+        );
+    }
+}
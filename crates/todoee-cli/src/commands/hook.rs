@@ -0,0 +1,173 @@
+//! `todoee hook`: a git post-commit hook that completes the todos a commit
+//! message refers to, e.g. "todoee:done abc1" or "fixes #abc1".
+
+use std::fs;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use todoee_core::{Config, EntityType, IdMatch, LocalDb, Operation, OperationType, git};
+use uuid::Uuid;
+
+use super::output;
+
+/// First comment line of a hook written by `todoee hook install`
+const MARKER: &str = "# Installed by `todoee hook install`";
+
+/// Hook git runs after each commit
+const HOOK_NAME: &str = "post-commit";
+
+#[derive(Subcommand, Clone)]
+pub enum HookCommand {
+    /// Install the post-commit hook in the current repository
+    Install {
+        /// Replace a post-commit hook that todoee didn't install
+        #[arg(long)]
+        force: bool,
+    },
+    /// Remove the hook installed by `todoee hook install`
+    Uninstall,
+    /// Complete the todos the last commit refers to (run by the hook)
+    #[command(hide = true)]
+    PostCommit,
+}
+
+pub async fn run(cmd: HookCommand) -> Result<()> {
+    let dir = std::env::current_dir().context("Failed to read the current directory")?;
+    match cmd {
+        HookCommand::Install { force } => install(&dir, force),
+        HookCommand::Uninstall => uninstall(&dir),
+        HookCommand::PostCommit => post_commit(&dir).await,
+    }
+}
+
+fn install(dir: &Path, force: bool) -> Result<()> {
+    let hooks = git::hooks_dir(dir)?;
+    let path = hooks.join(HOOK_NAME);
+    if let Ok(existing) = fs::read_to_string(&path)
+        && !existing.contains(MARKER)
+        && !force
+    {
+        anyhow::bail!(
+            "{} already exists. Add `todoee hook post-commit` to it yourself, or replace it with: todoee hook install --force",
+            path.display()
+        );
+    }
+
+    let exe = std::env::current_exe().context("Failed to find the todoee executable")?;
+    let script = format!(
+        "#!/bin/sh\n{}: completes todos named in commit messages\n'{}' hook post-commit || true\n",
+        MARKER,
+        exe.display().to_string().replace('\'', r"'\''")
+    );
+    fs::create_dir_all(&hooks).with_context(|| format!("Failed to create {}", hooks.display()))?;
+    fs::write(&path, script).with_context(|| format!("Failed to write {}", path.display()))?;
+    // Git runs hooks on Windows without an executable bit
+    #[cfg(unix)]
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+        .with_context(|| format!("Failed to make {} executable", path.display()))?;
+
+    output::say!("\u{2713} Installed {}", path.display());
+    output::say!("  Commit with \"todoee:done abc1\" or \"fixes #abc1\" to complete a todo");
+    Ok(())
+}
+
+fn uninstall(dir: &Path) -> Result<()> {
+    let path = git::hooks_dir(dir)?.join(HOOK_NAME);
+    match fs::read_to_string(&path) {
+        Ok(existing) if existing.contains(MARKER) => {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            output::say!("\u{2713} Removed {}", path.display());
+        }
+        Ok(_) => anyhow::bail!(
+            "{} wasn't installed by todoee; leaving it alone",
+            path.display()
+        ),
+        Err(_) => output::say!("No todoee hook installed in this repository"),
+    }
+    Ok(())
+}
+
+/// Complete every todo the last commit refers to, as one undo step. Runs
+/// inside git, so problems are reported without failing.
+async fn post_commit(dir: &Path) -> Result<()> {
+    let references = git::done_references(&git::last_commit_message(dir)?);
+    if references.is_empty() {
+        return Ok(());
+    }
+    let commit = git::last_commit_hash(dir)?;
+
+    let config = Config::load().context("Failed to load configuration")?;
+    let db = LocalDb::new(&config.local_db_path()?).await?;
+    db.run_migrations().await?;
+
+    let group_id = Uuid::new_v4();
+    for id in references {
+        let mut todo = match db.resolve_short_id(&id).await? {
+            IdMatch::Unique(todo) => *todo,
+            IdMatch::Ambiguous(_) => {
+                eprintln!("todoee: '{}' matches more than one todo, skipped", id);
+                continue;
+            }
+            IdMatch::NotFound => {
+                eprintln!("todoee: no todo '{}', skipped", id);
+                continue;
+            }
+        };
+        if todo.is_completed {
+            continue;
+        }
+
+        let prev = serde_json::to_value(&todo)?;
+        todo.mark_complete();
+        db.update_todo(&todo).await?;
+        let op = Operation::new(
+            OperationType::Complete,
+            EntityType::Todo,
+            todo.id,
+            Some(prev),
+            Some(serde_json::to_value(&todo)?),
+        )
+        .in_group(group_id);
+        db.record_operation(&op).await?;
+        output::say!("\u{2713} todoee: completed \"{}\" ({})", todo.title, commit);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_install_keeps_foreign_hooks() {
+        let dir = TempDir::new().unwrap();
+        let status = Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(dir.path())
+            .status();
+        if !status.is_ok_and(|s| s.success()) {
+            return; // git isn't available
+        }
+        let path = git::hooks_dir(dir.path()).unwrap().join(HOOK_NAME);
+
+        install(dir.path(), false).unwrap();
+        let script = fs::read_to_string(&path).unwrap();
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("' hook post-commit || true\n"));
+        install(dir.path(), false).unwrap();
+        uninstall(dir.path()).unwrap();
+        assert!(!path.exists());
+
+        fs::write(&path, "#!/bin/sh\nmake lint\n").unwrap();
+        assert!(install(dir.path(), false).is_err());
+        assert!(uninstall(dir.path()).is_err());
+        install(dir.path(), true).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains(MARKER));
+    }
+}
@@ -0,0 +1,16 @@
+//! Plain HTTP/1.1 pieces shared by `todoee serve` and `todoee serve-ics`.
+
+/// A complete HTTP/1.1 response. HEAD responses keep Content-Length but
+/// leave out the body.
+pub fn response(status: &str, headers: &[(&str, &str)], body: &str, with_body: bool) -> String {
+    let mut out = format!("HTTP/1.1 {}\r\n", status);
+    for (name, value) in headers {
+        out.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    out.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    out.push_str("Connection: close\r\n\r\n");
+    if with_body {
+        out.push_str(body);
+    }
+    out
+}
@@ -0,0 +1,116 @@
+//! Short todo IDs, sized by `[display] id_length`, and the `#N` numbers
+//! `todoee list` prints.
+
+use std::borrow::Borrow;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use todoee_core::{Config, IdMatch, LocalDb, MIN_ID_LENGTH, Todo, short_id, unique_prefix_len};
+use uuid::Uuid;
+
+/// Characters to show of todo IDs: the configured length, or with "auto" the
+/// shortest prefix that tells every todo apart, like git.
+pub async fn id_length(db: &LocalDb, config: &Config) -> Result<usize> {
+    match config.display.fixed_id_length() {
+        Some(len) => Ok(len),
+        None => Ok(unique_prefix_len(&db.todo_ids().await?)),
+    }
+}
+
+/// Length that tells ambiguous matches apart, so the listing after an
+/// "Ambiguous ID" error shows which characters to add.
+pub fn match_length<T: Borrow<Todo>>(matches: &[T]) -> usize {
+    let ids: Vec<_> = matches.iter().map(|t| t.borrow().id).collect();
+    unique_prefix_len(&ids)
+}
+
+/// Remember the todos `todoee list` just printed, in order, so `#N` can
+/// refer to them.
+pub fn save_listing(config: &Config, ids: &[Uuid]) -> Result<()> {
+    let path = listing_path(config)?;
+    let content = serde_json::to_string(ids).context("Failed to serialize list numbers")?;
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Find the todo `input` refers to: a number from the last `todoee list`
+/// ("#2", or "2" when shorter than any ID prefix) or an ID prefix.
+pub async fn resolve(db: &LocalDb, config: &Config, input: &str) -> Result<IdMatch> {
+    let Some(number) = list_number(input) else {
+        return db.resolve_short_id(input).await;
+    };
+
+    let path = listing_path(config)?;
+    if !path.exists() {
+        anyhow::bail!("No list to number from yet; run 'todoee list' first");
+    }
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let listing: Vec<Uuid> = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let todo = match number.checked_sub(1).and_then(|i| listing.get(i)) {
+        Some(id) => db.get_todo(*id).await?,
+        None => None,
+    };
+    Ok(todo.map_or(IdMatch::NotFound, |todo| IdMatch::Unique(Box::new(todo))))
+}
+
+/// The one todo `input` refers to. When there is none, or several, says so
+/// on stderr (listing the candidates) and fails.
+pub async fn resolve_todo(db: &LocalDb, config: &Config, input: &str) -> Result<Todo> {
+    match resolve(db, config, input).await? {
+        IdMatch::Unique(todo) => Ok(*todo),
+        IdMatch::NotFound => {
+            eprintln!("No todo found matching '{}'", input);
+            eprintln!("Hint: Use 'todoee list --all' to see all todos including completed ones.");
+            anyhow::bail!("Todo not found");
+        }
+        IdMatch::Ambiguous(matches) => {
+            eprintln!("Multiple todos match '{}'. Please be more specific:", input);
+            eprintln!();
+            let id_len = match_length(&matches);
+            for todo in &matches {
+                eprintln!("  {} [{}]", todo.title, short_id(&todo.id, id_len));
+            }
+            anyhow::bail!("Ambiguous ID - provide more characters");
+        }
+    }
+}
+
+/// The list number in `input`, if it is one
+fn list_number(input: &str) -> Option<usize> {
+    let input = input.trim();
+    let digits = match input.strip_prefix('#') {
+        Some(digits) => digits,
+        // Bare numbers as long as an ID prefix stay ID prefixes
+        None if input.len() < MIN_ID_LENGTH => input,
+        None => return None,
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// Next to the database, so demo mode keeps its own numbers
+fn listing_path(config: &Config) -> Result<PathBuf> {
+    Ok(config.local_db_path()?.with_extension("list.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_number() {
+        assert_eq!(list_number("#3"), Some(3));
+        assert_eq!(list_number("12"), Some(12));
+        assert_eq!(list_number("#1234"), Some(1234));
+        // Four digits could be the start of an ID
+        assert_eq!(list_number("1234"), None);
+        assert_eq!(list_number("#"), None);
+        assert_eq!(list_number("#a1"), None);
+        assert_eq!(list_number("ab"), None);
+    }
+}
@@ -2,8 +2,15 @@ use std::path::Path;
 
 use anyhow::{Context, Result};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
-use todoee_core::{Category, Config, LocalDb, Todo};
+use todoee_core::{
+    Attachment, Category, Config, DateInput, LocalDb, NoteLink, Project, Todo, markdown, notes,
+    org, taskwarrior, validation,
+};
+use uuid::Uuid;
+
+use super::add::{add_lines, parse_lines, parse_offline};
 
 #[derive(Debug, Clone, Copy)]
 pub enum ImportMode {
@@ -11,6 +18,53 @@ pub enum ImportMode {
     Replace, // Overwrite existing
 }
 
+/// Import file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// A `todoee export` JSON file
+    Json,
+    /// An org-mode file
+    Org,
+    /// A markdown checklist
+    Markdown,
+    /// A Taskwarrior `task export` file
+    Taskwarrior,
+    /// Plain text, one task per line
+    Lines,
+}
+
+impl ImportFormat {
+    /// Parse a `--format` value
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "org" => Ok(Self::Org),
+            "md" | "markdown" => Ok(Self::Markdown),
+            "taskwarrior" | "tw" => Ok(Self::Taskwarrior),
+            "lines" | "txt" => Ok(Self::Lines),
+            _ => anyhow::bail!(
+                "Unknown import format: {} (use json, org, md, taskwarrior or lines)",
+                name
+            ),
+        }
+    }
+
+    /// Infer the format from a file extension, defaulting to JSON
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .as_deref()
+        {
+            Some("org") => Self::Org,
+            Some("md" | "markdown") => Self::Markdown,
+            Some("txt") => Self::Lines,
+            _ => Self::Json,
+        }
+    }
+}
+
 #[derive(Deserialize)]
 struct ImportData {
     #[allow(dead_code)]
@@ -19,28 +73,73 @@ struct ImportData {
     exported_at: String,
     todos: Vec<Todo>,
     categories: Vec<Category>,
+    #[serde(default)]
+    projects: Vec<Project>,
+    #[serde(default)]
+    attachments: Vec<Attachment>,
+}
+
+/// Counts from an import.
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    pub todos: usize,
+    pub categories: usize,
+    pub projects: usize,
+    /// Titles of todos whose ID belonged to a different existing todo.
+    /// They are imported under a new ID.
+    pub reassigned: Vec<String>,
 }
 
 /// Import todos from a file.
 ///
-/// Imports todos and categories from a JSON file. This is the public API
-/// for programmatic import, used by tests and intended for future sync functionality.
+/// Imports todos and categories from a JSON export, or from an org-mode file
+/// when the path ends in `.org`. Plain text files are handled by
+/// [`import_lines`]. This is the public API for programmatic import, used by
+/// tests and intended for future sync functionality.
 ///
-/// Returns a tuple of (imported_todos_count, imported_categories_count).
+/// A todo whose ID matches an existing todo with a different creation time is a
+/// different todo, so it is imported alongside under a new ID.
 #[allow(dead_code)]
 pub async fn import_todos(
     db: &LocalDb,
     input_path: &Path,
     mode: ImportMode,
-) -> Result<(usize, usize)> {
-    // Read and parse JSON
+) -> Result<ImportSummary> {
+    let format = ImportFormat::from_path(input_path);
+    import_file(db, input_path, mode, format).await
+}
+
+async fn import_file(
+    db: &LocalDb,
+    input_path: &Path,
+    mode: ImportMode,
+    format: ImportFormat,
+) -> Result<ImportSummary> {
     let content = fs::read_to_string(input_path)
         .with_context(|| format!("Failed to read import file: {}", input_path.display()))?;
 
-    let data: ImportData = serde_json::from_str(&content).context("Failed to parse import JSON")?;
+    let data: ImportData = match format {
+        ImportFormat::Org => org_import_data(&content, &db.list_categories().await?),
+        ImportFormat::Markdown => markdown_import_data(&content, &db.list_categories().await?),
+        ImportFormat::Taskwarrior => taskwarrior_import_data(
+            &content,
+            &db.list_categories().await?,
+            &db.list_projects(true).await?,
+        )?,
+        ImportFormat::Json => {
+            serde_json::from_str(&content).context("Failed to parse import JSON")?
+        }
+        ImportFormat::Lines => anyhow::bail!("Plain text files are imported with import_lines"),
+    };
+
+    // Check every todo first, so a bad record doesn't leave a partial import
+    let now = chrono::Utc::now();
+    for (i, todo) in data.todos.iter().enumerate() {
+        validation::validate_todo(todo, None, now)
+            .with_context(|| format!("Todo {} ('{}') can't be imported", i + 1, todo.title))?;
+    }
 
-    let mut imported_categories = 0;
-    let mut imported_todos = 0;
+    let mut summary = ImportSummary::default();
 
     // Import categories first (todos may reference them)
     for category in data.categories {
@@ -53,18 +152,40 @@ pub async fn import_todos(
                 // Delete and recreate in replace mode
                 db.delete_category(existing_cat.id).await?;
                 db.create_category(&category).await?;
-                imported_categories += 1;
+                summary.categories += 1;
             }
             (None, _) => {
                 db.create_category(&category).await?;
-                imported_categories += 1;
+                summary.categories += 1;
             }
         }
     }
 
+    // Projects are matched by name and never replaced, as they carry no todo data
+    for project in data.projects {
+        if db.get_project_by_name(&project.name).await?.is_none() {
+            db.create_project(&project).await?;
+            summary.projects += 1;
+        }
+    }
+
+    // Where each imported todo ended up, for its attachments
+    let mut todo_ids: HashMap<Uuid, Uuid> = HashMap::new();
+
     // Import todos with merge/replace logic
-    for todo in data.todos {
+    for mut todo in data.todos {
         let existing = db.get_todo(todo.id).await?;
+        if let Some(ref existing) = existing
+            && !existing.is_same_todo(&todo)
+        {
+            let original = todo.id;
+            todo.id = Uuid::new_v4();
+            todo_ids.insert(original, todo.id);
+            db.create_todo(&todo).await?;
+            summary.todos += 1;
+            summary.reassigned.push(todo.title);
+            continue;
+        }
         match (existing, mode) {
             (Some(_), ImportMode::Merge) => {
                 // Skip existing todo in merge mode
@@ -72,33 +193,312 @@ pub async fn import_todos(
             (Some(_), ImportMode::Replace) => {
                 // Update existing in replace mode
                 db.update_todo(&todo).await?;
-                imported_todos += 1;
+                summary.todos += 1;
             }
             (None, _) => {
-                db.create_todo(&todo).await?;
-                imported_todos += 1;
+                // A todo deleted here earlier is brought back rather than duplicated
+                if db.is_locally_deleted(todo.id).await? {
+                    db.restore_todo(&todo).await?;
+                } else {
+                    db.create_todo(&todo).await?;
+                }
+                summary.todos += 1;
             }
         }
+        todo_ids.insert(todo.id, todo.id);
+    }
+
+    // Attachments are kept by ID, so importing the same file twice adds nothing
+    for mut attachment in data.attachments {
+        let Some(&todo_id) = todo_ids.get(&attachment.todo_id) else {
+            continue;
+        };
+        if todo_id != attachment.todo_id {
+            attachment.id = Uuid::new_v4();
+            attachment.todo_id = todo_id;
+        }
+        db.add_attachment(&attachment).await?;
     }
 
-    Ok((imported_todos, imported_categories))
+    Ok(summary)
 }
 
-pub async fn run(input: String, mode: String) -> Result<()> {
-    // Load config and open local database
-    let config = Config::load().context("Failed to load configuration")?;
-    let db_path = config.local_db_path()?;
+/// Import a plain text file with one task per line.
+///
+/// All tasks are created in one transaction and can be removed with a single
+/// `undo`. Returns the number of todos created.
+pub async fn import_lines(
+    db: &LocalDb,
+    input_path: &Path,
+    category: Option<&str>,
+    priority: Option<i32>,
+) -> Result<usize> {
+    let content = fs::read_to_string(input_path)
+        .with_context(|| format!("Failed to read import file: {}", input_path.display()))?;
+    let lines = parse_lines(&content);
+    if lines.is_empty() {
+        return Ok(0);
+    }
+    // Files are read with the default, locale-independent date input
+    Ok(
+        add_lines(db, &lines, category, priority, &DateInput::default())
+            .await?
+            .len(),
+    )
+}
 
-    // Ensure config directory exists
-    if let Some(parent) = db_path.parent()
-        && !parent.exists()
-    {
-        fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+/// Todos from an org document, with categories for tags not seen before.
+///
+/// The first tag of a heading becomes its category. Existing categories are
+/// matched by their org tag form, so "Side project" matches `:Side_project:`.
+fn org_import_data(content: &str, existing: &[Category]) -> ImportData {
+    let mut known: HashMap<String, Uuid> = existing
+        .iter()
+        .map(|c| (org::tag_name(&c.name), c.id))
+        .collect();
+    let mut categories = Vec::new();
+    let mut todos = Vec::new();
+
+    for entry in org::parse(content, &chrono::Local) {
+        let mut todo = entry.todo;
+        if let Some(tag) = entry.tags.first() {
+            let id = *known.entry(tag.clone()).or_insert_with(|| {
+                let category = Category::new(Uuid::new_v4(), tag.clone());
+                let id = category.id;
+                categories.push(category);
+                id
+            });
+            todo.category_id = Some(id);
+        }
+        todos.push(todo);
     }
 
-    let db = LocalDb::new(&db_path).await?;
-    db.run_migrations().await?;
+    ImportData {
+        version: "org".to_string(),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        todos,
+        categories,
+        projects: Vec::new(),
+        attachments: Vec::new(),
+    }
+}
+
+/// Todos from a markdown checklist, with categories for names not seen before.
+///
+/// Existing categories are matched by name or by their tag form, so a
+/// `#Side_project` tag outside its section still finds "Side project".
+fn markdown_import_data(content: &str, existing: &[Category]) -> ImportData {
+    let mut categories: Vec<Category> = Vec::new();
+    let mut todos = Vec::new();
+
+    for entry in markdown::parse(content, &chrono::Local) {
+        let mut todo = entry.todo;
+        if let Some(name) = entry.category {
+            let matches = |c: &&Category| c.name == name || org::tag_name(&c.name) == name;
+            todo.category_id = Some(match existing.iter().chain(&categories).find(matches) {
+                Some(category) => category.id,
+                None => {
+                    let category = Category::new(Uuid::new_v4(), name);
+                    let id = category.id;
+                    categories.push(category);
+                    id
+                }
+            });
+        }
+        todos.push(todo);
+    }
+
+    ImportData {
+        version: "markdown".to_string(),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        todos,
+        categories,
+        projects: Vec::new(),
+        attachments: Vec::new(),
+    }
+}
+
+/// Todos from a Taskwarrior export, with categories and projects for names
+/// not seen before.
+///
+/// The first tag becomes the category, matched like org tags, and the
+/// project is matched by name.
+fn taskwarrior_import_data(
+    content: &str,
+    existing_categories: &[Category],
+    existing_projects: &[Project],
+) -> Result<ImportData> {
+    let mut categories: Vec<Category> = Vec::new();
+    let mut projects: Vec<Project> = Vec::new();
+    let mut todos = Vec::new();
+
+    for entry in taskwarrior::parse(content, &chrono::Local)? {
+        let mut todo = entry.todo;
+        if let Some(tag) = entry.tags.first() {
+            let matches = |c: &&Category| org::tag_name(&c.name) == *tag;
+            todo.category_id = Some(
+                match existing_categories.iter().chain(&categories).find(matches) {
+                    Some(category) => category.id,
+                    None => {
+                        let category = Category::new(Uuid::new_v4(), tag.clone());
+                        let id = category.id;
+                        categories.push(category);
+                        id
+                    }
+                },
+            );
+        }
+        if let Some(name) = entry.project {
+            let matches = |p: &&Project| p.name == name;
+            todo.project_id = Some(
+                match existing_projects.iter().chain(&projects).find(matches) {
+                    Some(project) => project.id,
+                    None => {
+                        let project = Project::new(Uuid::nil(), name);
+                        let id = project.id;
+                        projects.push(project);
+                        id
+                    }
+                },
+            );
+        }
+        todos.push(todo);
+    }
+
+    Ok(ImportData {
+        version: "taskwarrior".to_string(),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        todos,
+        categories,
+        projects,
+        attachments: Vec::new(),
+    })
+}
+
+/// Counts from a markdown notes import.
+#[derive(Debug, Default)]
+pub struct NotesSummary {
+    pub notes: usize,
+    pub todos: usize,
+    /// Todos completed because their box was ticked in the note (two-way mode)
+    pub completed: usize,
+    /// Checkboxes imported on an earlier run
+    pub already_imported: usize,
+}
+
+/// Import unchecked markdown checkboxes under `dir` as todos.
+///
+/// Each todo is linked back to its file and line, so running the import again
+/// only picks up new items. With `two_way`, completing a todo ticks its box in
+/// the note, and a box ticked in the note completes the todo on the next import.
+pub async fn import_markdown_dir(db: &LocalDb, dir: &Path, two_way: bool) -> Result<NotesSummary> {
+    // Absolute paths keep the links valid wherever todoee is run from
+    let dir = dir
+        .canonicalize()
+        .with_context(|| format!("Notes directory not found: {}", dir.display()))?;
+    let tasks = notes::scan_dir(&dir)?;
+
+    let mut summary = NotesSummary::default();
+    let mut seen_notes = std::collections::HashSet::new();
+
+    for task in tasks {
+        seen_notes.insert(task.path.clone());
+        let path = task.path.to_string_lossy().into_owned();
+
+        if let Some(link) = db.find_note_link(&path, &task.text).await? {
+            summary.already_imported += 1;
+            let two_way = link.two_way || two_way;
+            if link.line != task.line || two_way != link.two_way {
+                db.create_note_link(&NoteLink {
+                    line: task.line,
+                    two_way,
+                    ..link.clone()
+                })
+                .await?;
+            }
+
+            if two_way
+                && task.checked
+                && let Some(mut todo) = db.get_todo(link.todo_id).await?
+                && !todo.is_completed
+            {
+                todo.mark_complete();
+                db.update_todo(&todo).await?;
+                summary.completed += 1;
+            }
+            continue;
+        }
+
+        if task.checked {
+            continue;
+        }
+
+        let mut todo = parse_offline(&task.text, &DateInput::default());
+        todo.detect_icon();
+        todo.description = Some(format!("From {}:{}", path, task.line));
+        db.create_todo(&todo).await?;
+        db.create_note_link(&NoteLink {
+            todo_id: todo.id,
+            path,
+            line: task.line,
+            text: task.text,
+            two_way,
+        })
+        .await?;
+        summary.todos += 1;
+    }
+
+    summary.notes = seen_notes.len();
+    Ok(summary)
+}
+
+pub async fn run_markdown(dir: String, two_way: bool) -> Result<()> {
+    let db = open_db().await?;
+
+    let summary = import_markdown_dir(&db, &notes::expand_home(&dir), two_way).await?;
+
+    println!(
+        "\u{2713} Imported {} todos from {} notes in {}",
+        summary.todos, summary.notes, dir
+    );
+    if summary.completed > 0 {
+        println!("  Completed: {} (ticked in notes)", summary.completed);
+    }
+    if summary.already_imported > 0 {
+        println!("  Already imported: {}", summary.already_imported);
+    }
+    if two_way {
+        println!("  Completing these todos will tick their boxes in the notes.");
+    }
+    Ok(())
+}
+
+pub async fn run(
+    input: String,
+    mode: String,
+    format: Option<String>,
+    category: Option<String>,
+    priority: Option<i32>,
+) -> Result<()> {
+    let input_path = Path::new(&input);
+    let format = match format {
+        Some(name) => ImportFormat::parse(&name)?,
+        None => ImportFormat::from_path(input_path),
+    };
+    if format != ImportFormat::Lines && (category.is_some() || priority.is_some()) {
+        anyhow::bail!("--category and --priority only apply to --format lines");
+    }
+
+    let db = open_db().await?;
+
+    if format == ImportFormat::Lines {
+        let count = import_lines(&db, input_path, category.as_deref(), priority).await?;
+        println!("\u{2713} Imported {} todos from {}", count, input);
+        if count > 0 {
+            println!("Run 'todoee undo' to remove them all.");
+        }
+        return Ok(());
+    }
 
     // Parse mode string to ImportMode
     let import_mode = match mode.to_lowercase().as_str() {
@@ -106,16 +506,45 @@ pub async fn run(input: String, mode: String) -> Result<()> {
         _ => ImportMode::Merge,
     };
 
-    let input_path = Path::new(&input);
-    let (todos_count, categories_count) = import_todos(&db, input_path, import_mode).await?;
+    let summary = import_file(&db, input_path, import_mode, format).await?;
 
     println!(
         "\u{2713} Imported {} todos and {} categories from {}",
-        todos_count, categories_count, input
+        summary.todos, summary.categories, input
     );
+    if summary.projects > 0 {
+        println!("  New projects: {}", summary.projects);
+    }
+    if !summary.reassigned.is_empty() {
+        println!(
+            "\u{26A0} {} todo(s) had an ID already used by a different todo and got a new ID:",
+            summary.reassigned.len()
+        );
+        for title in &summary.reassigned {
+            println!("  {}", title);
+        }
+    }
     Ok(())
 }
 
+/// Load config and open the local database
+async fn open_db() -> Result<LocalDb> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    // Ensure config directory exists
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+    Ok(db)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,6 +586,249 @@ mod tests {
         assert_eq!(todos[0].title, "Imported task");
     }
 
+    #[tokio::test]
+    async fn test_import_org_maps_tags_and_merges_reimport() {
+        let temp_dir = TempDir::new().unwrap();
+        let import_path = temp_dir.path().join("tasks.org");
+        std::fs::write(
+            &import_path,
+            "* TODO [#A] Pay rent :Side_project:\n\
+             * TODO Water plants :garden:\n\
+             * DONE Buy eggs\n\
+             * Notes\n",
+        )
+        .unwrap();
+
+        let db_path = temp_dir.path().join("test.db");
+        let db = LocalDb::new(&db_path).await.unwrap();
+        db.run_migrations().await.unwrap();
+        let existing = Category::new(Uuid::new_v4(), "Side project".to_string());
+        db.create_category(&existing).await.unwrap();
+
+        let summary = import_todos(&db, &import_path, ImportMode::Merge)
+            .await
+            .unwrap();
+        assert_eq!(summary.todos, 3);
+        assert_eq!(summary.categories, 1);
+
+        let todos = db.list_todos(false).await.unwrap();
+        let rent = todos.iter().find(|t| t.title == "Pay rent").unwrap();
+        assert_eq!(rent.category_id, Some(existing.id));
+        assert!(
+            todos
+                .iter()
+                .any(|t| t.title == "Buy eggs" && t.is_completed)
+        );
+        assert!(db.get_category_by_name("garden").await.unwrap().is_some());
+
+        // Exported todos carry their IDs, so importing them again adds nothing
+        let export_path = temp_dir.path().join("export.org");
+        std::fs::write(&export_path, org::write(&todos, &[], &chrono::Local)).unwrap();
+        let summary = import_todos(&db, &export_path, ImportMode::Merge)
+            .await
+            .unwrap();
+        assert_eq!(summary.todos, 0);
+        assert!(summary.reassigned.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_taskwarrior_maps_projects_and_tags() {
+        let temp_dir = TempDir::new().unwrap();
+        let import_path = temp_dir.path().join("tasks.json");
+        std::fs::write(
+            &import_path,
+            r#"[
+  {"uuid":"b6a2c2a4-1b8f-4a5e-9a53-1d2b0f6a3c11","description":"Pay rent","status":"pending",
+   "entry":"20260105T081500Z","priority":"H","project":"Flat","tags":["Side_project"]},
+  {"uuid":"0f3e9c61-52a7-4f0e-8d7b-6c1f2b9a4e20","description":"Paint walls","status":"completed",
+   "entry":"20260102T081500Z","end":"20260103T081500Z","project":"Flat","tags":["diy"]}
+]"#,
+        )
+        .unwrap();
+
+        let db_path = temp_dir.path().join("test.db");
+        let db = LocalDb::new(&db_path).await.unwrap();
+        db.run_migrations().await.unwrap();
+        let existing = Category::new(Uuid::new_v4(), "Side project".to_string());
+        db.create_category(&existing).await.unwrap();
+
+        let summary = import_file(
+            &db,
+            &import_path,
+            ImportMode::Merge,
+            ImportFormat::Taskwarrior,
+        )
+        .await
+        .unwrap();
+        assert_eq!(summary.todos, 2);
+        assert_eq!(summary.categories, 1);
+        assert_eq!(summary.projects, 1);
+
+        let flat = db.get_project_by_name("Flat").await.unwrap().unwrap();
+        let todos = db.list_todos_by_project(flat.id).await.unwrap();
+        assert_eq!(todos.len(), 2);
+        let rent = todos.iter().find(|t| t.title == "Pay rent").unwrap();
+        assert_eq!(rent.category_id, Some(existing.id));
+        assert_eq!(rent.id.to_string(), "b6a2c2a4-1b8f-4a5e-9a53-1d2b0f6a3c11");
+        assert!(db.get_category_by_name("diy").await.unwrap().is_some());
+
+        // Exporting and importing again keeps the UUIDs, so nothing is added
+        use crate::commands::export::{ExportFormat, export_todos};
+        let export_path = temp_dir.path().join("export.json");
+        export_todos(&db, &export_path, ExportFormat::Taskwarrior)
+            .await
+            .unwrap();
+        let summary = import_file(
+            &db,
+            &export_path,
+            ImportMode::Merge,
+            ImportFormat::Taskwarrior,
+        )
+        .await
+        .unwrap();
+        assert_eq!(summary.todos, 0);
+        assert_eq!(summary.projects, 0);
+        assert!(summary.reassigned.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_markdown_export_round_trips() {
+        use crate::commands::export::{ExportFormat, export_todos};
+
+        let temp_dir = TempDir::new().unwrap();
+        let source = LocalDb::new(&temp_dir.path().join("source.db"))
+            .await
+            .unwrap();
+        source.run_migrations().await.unwrap();
+        let category = Category::new(Uuid::new_v4(), "Side project".to_string());
+        source.create_category(&category).await.unwrap();
+        let mut ship = Todo::new("Ship v2".to_string(), None);
+        ship.category_id = Some(category.id);
+        ship.priority = todoee_core::Priority::High;
+        ship.description = Some("Write release notes".to_string());
+        let mut done = Todo::new("Buy eggs".to_string(), None);
+        done.mark_complete();
+        source.create_todo(&ship).await.unwrap();
+        source.create_todo(&done).await.unwrap();
+
+        let export_path = temp_dir.path().join("tasks.md");
+        export_todos(&source, &export_path, ExportFormat::Markdown)
+            .await
+            .unwrap();
+        let content = std::fs::read_to_string(&export_path).unwrap();
+        assert!(content.contains("## Side project\n\n- [ ] Ship v2 #Side_project !p3"));
+
+        let db = LocalDb::new(&temp_dir.path().join("test.db"))
+            .await
+            .unwrap();
+        db.run_migrations().await.unwrap();
+        assert_eq!(
+            ImportFormat::from_path(&export_path),
+            ImportFormat::Markdown
+        );
+        let summary = import_todos(&db, &export_path, ImportMode::Merge)
+            .await
+            .unwrap();
+        assert_eq!(summary.todos, 2);
+        assert_eq!(summary.categories, 1);
+
+        let imported = db.get_todo(ship.id).await.unwrap().unwrap();
+        assert!(imported.is_same_todo(&ship));
+        assert_eq!(imported.priority, ship.priority);
+        assert_eq!(imported.description, ship.description);
+        let category = db.get_category_by_name("Side project").await.unwrap();
+        assert_eq!(imported.category_id, category.map(|c| c.id));
+        assert!(db.get_todo(done.id).await.unwrap().unwrap().is_completed);
+
+        // Importing the same file again adds nothing
+        let summary = import_todos(&db, &export_path, ImportMode::Merge)
+            .await
+            .unwrap();
+        assert_eq!(summary.todos, 0);
+        assert_eq!(summary.categories, 0);
+    }
+
+    #[tokio::test]
+    async fn test_json_export_round_trips_attachments() {
+        use crate::commands::export::{ExportFormat, export_todos};
+
+        let temp_dir = TempDir::new().unwrap();
+        let source = LocalDb::new(&temp_dir.path().join("source.db"))
+            .await
+            .unwrap();
+        source.run_migrations().await.unwrap();
+        let todo = Todo::new("Review spec".to_string(), None);
+        source.create_todo(&todo).await.unwrap();
+        let attachment = Attachment::new(todo.id, "https://example.com/spec".to_string());
+        source.add_attachment(&attachment).await.unwrap();
+
+        let export_path = temp_dir.path().join("export.json");
+        export_todos(&source, &export_path, ExportFormat::Json)
+            .await
+            .unwrap();
+
+        let db = LocalDb::new(&temp_dir.path().join("test.db"))
+            .await
+            .unwrap();
+        db.run_migrations().await.unwrap();
+        // A different todo already holds the ID, so the import is reassigned
+        let mut other = Todo::new("Unrelated".to_string(), None);
+        other.id = todo.id;
+        other.created_at = todo.created_at - chrono::Duration::days(1);
+        db.create_todo(&other).await.unwrap();
+
+        let summary = import_todos(&db, &export_path, ImportMode::Merge)
+            .await
+            .unwrap();
+        assert_eq!(summary.reassigned, vec!["Review spec".to_string()]);
+        assert!(db.list_attachments(other.id).await.unwrap().is_empty());
+        let imported = db
+            .list_todos(false)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|t| t.title == "Review spec")
+            .unwrap();
+        let attachments = db.list_attachments(imported.id).await.unwrap();
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].target, attachment.target);
+    }
+
+    #[tokio::test]
+    async fn test_import_lines_is_one_batch() {
+        let temp_dir = TempDir::new().unwrap();
+        let import_path = temp_dir.path().join("tasks.txt");
+        std::fs::write(
+            &import_path,
+            "Buy milk\n\nCall mom tomorrow\n# later\nFix bike\n",
+        )
+        .unwrap();
+        assert_eq!(ImportFormat::from_path(&import_path), ImportFormat::Lines);
+
+        let db_path = temp_dir.path().join("test.db");
+        let db = LocalDb::new(&db_path).await.unwrap();
+        db.run_migrations().await.unwrap();
+
+        let count = import_lines(&db, &import_path, Some("errands"), Some(3))
+            .await
+            .unwrap();
+        assert_eq!(count, 3);
+
+        let category = db.get_category_by_name("errands").await.unwrap().unwrap();
+        let todos = db.list_todos(false).await.unwrap();
+        assert_eq!(todos.len(), 3);
+        assert!(todos.iter().all(
+            |t| t.category_id == Some(category.id) && t.priority == todoee_core::Priority::High
+        ));
+        let call = todos.iter().find(|t| t.title == "Call mom").unwrap();
+        assert!(call.due_date.is_some());
+
+        // The whole batch is a single undoable operation
+        let op = db.get_last_undoable_operation().await.unwrap().unwrap();
+        assert_eq!(op.operation_type, todoee_core::OperationType::BatchCreate);
+        assert_eq!(op.batch_todos().len(), 3);
+    }
+
     #[tokio::test]
     async fn test_import_merge_skips_existing() {
         let temp_dir = TempDir::new().unwrap();
@@ -167,7 +839,8 @@ mod tests {
         db.run_migrations().await.unwrap();
 
         // Create an existing todo with the same ID
-        let existing_todo = Todo::new("Existing task".to_string(), None);
+        let mut existing_todo = Todo::new("Existing task".to_string(), None);
+        existing_todo.created_at = "2026-01-31T12:00:00Z".parse().unwrap();
         let existing_id = existing_todo.id;
         db.create_todo(&existing_todo).await.unwrap();
 
@@ -195,8 +868,7 @@ mod tests {
 
         let result = import_todos(&db, &import_path, ImportMode::Merge).await;
         assert!(result.is_ok());
-        let (imported_todos, _) = result.unwrap();
-        assert_eq!(imported_todos, 0); // Should skip existing
+        assert_eq!(result.unwrap().todos, 0); // Should skip existing
 
         let todos = db.list_todos(false).await.unwrap();
         assert_eq!(todos.len(), 1);
@@ -213,7 +885,8 @@ mod tests {
         db.run_migrations().await.unwrap();
 
         // Create an existing todo with the same ID
-        let existing_todo = Todo::new("Existing task".to_string(), None);
+        let mut existing_todo = Todo::new("Existing task".to_string(), None);
+        existing_todo.created_at = "2026-01-31T12:00:00Z".parse().unwrap();
         let existing_id = existing_todo.id;
         db.create_todo(&existing_todo).await.unwrap();
 
@@ -241,11 +914,123 @@ mod tests {
 
         let result = import_todos(&db, &import_path, ImportMode::Replace).await;
         assert!(result.is_ok());
-        let (imported_todos, _) = result.unwrap();
-        assert_eq!(imported_todos, 1); // Should replace existing
+        assert_eq!(result.unwrap().todos, 1); // Should replace existing
 
         let todos = db.list_todos(false).await.unwrap();
         assert_eq!(todos.len(), 1);
         assert_eq!(todos[0].title, "Updated task"); // Title changed
     }
+
+    #[tokio::test]
+    async fn test_import_reassigns_colliding_id() {
+        let temp_dir = TempDir::new().unwrap();
+        let import_path = temp_dir.path().join("import.json");
+
+        let db_path = temp_dir.path().join("test.db");
+        let db = LocalDb::new(&db_path).await.unwrap();
+        db.run_migrations().await.unwrap();
+
+        // Existing todo created at a different time than the imported one
+        let existing_todo = Todo::new("Existing task".to_string(), None);
+        let existing_id = existing_todo.id;
+        db.create_todo(&existing_todo).await.unwrap();
+
+        let json_data = format!(
+            r#"{{
+            "version": "1.0",
+            "exported_at": "2026-01-31T12:00:00Z",
+            "todos": [
+                {{
+                    "id": "{}",
+                    "title": "Different task",
+                    "priority": "medium",
+                    "is_completed": false,
+                    "created_at": "2026-01-31T12:00:00Z",
+                    "updated_at": "2026-01-31T12:00:00Z",
+                    "sync_status": "pending"
+                }}
+            ],
+            "categories": []
+        }}"#,
+            existing_id
+        );
+        std::fs::write(&import_path, json_data).unwrap();
+
+        let summary = import_todos(&db, &import_path, ImportMode::Replace)
+            .await
+            .unwrap();
+        assert_eq!(summary.todos, 1);
+        assert_eq!(summary.reassigned, vec!["Different task".to_string()]);
+
+        // Both todos are kept, the existing one untouched
+        let todos = db.list_todos(false).await.unwrap();
+        assert_eq!(todos.len(), 2);
+        let existing = db.get_todo(existing_id).await.unwrap().unwrap();
+        assert_eq!(existing.title, "Existing task");
+    }
+
+    #[tokio::test]
+    async fn test_import_markdown_dir_links_and_is_repeatable() {
+        let temp_dir = TempDir::new().unwrap();
+        let notes_dir = temp_dir.path().join("notes");
+        std::fs::create_dir(&notes_dir).unwrap();
+        let note = notes_dir.join("week.md");
+        std::fs::write(&note, "# Week\n- [ ] Pay rent\n- [x] Already done\n").unwrap();
+
+        let db = LocalDb::new(&temp_dir.path().join("test.db"))
+            .await
+            .unwrap();
+        db.run_migrations().await.unwrap();
+
+        let summary = import_markdown_dir(&db, &notes_dir, false).await.unwrap();
+        assert_eq!((summary.notes, summary.todos), (1, 1));
+
+        let todos = db.list_todos(false).await.unwrap();
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].title, "Pay rent");
+        let source = format!("{}:2", note.canonicalize().unwrap().display());
+        assert!(todos[0].description.as_deref().unwrap().ends_with(&source));
+
+        // New items are picked up, imported ones are not duplicated
+        std::fs::write(&note, "# Week\n\n- [ ] Pay rent\n- [ ] Call mom\n").unwrap();
+        let summary = import_markdown_dir(&db, &notes_dir, false).await.unwrap();
+        assert_eq!((summary.todos, summary.already_imported), (1, 1));
+        assert_eq!(db.list_todos(false).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_import_markdown_dir_two_way() {
+        let temp_dir = TempDir::new().unwrap();
+        let note = temp_dir.path().join("todo.md");
+        std::fs::write(&note, "- [ ] Water plants\n- [ ] Fix bike\n").unwrap();
+
+        let db = LocalDb::new(&temp_dir.path().join("test.db"))
+            .await
+            .unwrap();
+        db.run_migrations().await.unwrap();
+        import_markdown_dir(&db, temp_dir.path(), true)
+            .await
+            .unwrap();
+
+        let todos = db.list_todos(false).await.unwrap();
+        let mut plants = todos
+            .iter()
+            .find(|t| t.title == "Water plants")
+            .unwrap()
+            .clone();
+        plants.mark_complete();
+        db.update_todo(&plants).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&note).unwrap(),
+            "- [x] Water plants\n- [ ] Fix bike\n"
+        );
+
+        // Ticking a box in the note completes the todo on the next import
+        std::fs::write(&note, "- [x] Water plants\n- [x] Fix bike\n").unwrap();
+        let summary = import_markdown_dir(&db, temp_dir.path(), true)
+            .await
+            .unwrap();
+        assert_eq!(summary.completed, 1);
+        assert!(db.list_todos(true).await.unwrap().is_empty());
+    }
 }
@@ -1,11 +1,14 @@
 //! Productivity insights command.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
 
 use anyhow::{Context, Result};
-use chrono::{Datelike, Local, TimeZone, Utc, Weekday};
-use todoee_core::{Config, LocalDb, OperationType};
+use chrono::{Datelike, Duration, Local, NaiveDate, TimeZone, Utc, Weekday};
+use serde::Serialize;
+use todoee_core::{ActivityBucket, Config, FocusSession, LocalDb, dates};
+
+use super::output;
 
 pub async fn run(days: Option<i64>) -> Result<()> {
     let config = Config::load().context("Failed to load config")?;
@@ -22,54 +25,99 @@ pub async fn run(days: Option<i64>) -> Result<()> {
     db.run_migrations().await?;
 
     let days = days.unwrap_or(30);
-    let since = Utc::now() - chrono::Duration::days(days);
-    let operations = db.list_operations_since(since).await?;
-    let todos = db.list_todos(false).await?;
+    let now = Utc::now();
+    let since = now - chrono::Duration::days(days);
+    // Aggregated by the database: one row per active hour, not per operation
+    let activity = db.activity_by_hour(since).await?;
+    let stats = db.todo_stats(since, now).await?;
+    let categories = db.completions_by_category(since).await?;
+    let projects = db.project_progress().await?;
+    let focus = db.focus_stats(since).await?;
+    let focus_by_day = focus_sessions_by_day(&db.list_focus_sessions(since).await?);
+    let focus_by_todo = db.focus_time_by_todo(since).await?;
+    let first_day = since.with_timezone(&Local).date_naive();
+    let reflected: Vec<NaiveDate> = db
+        .list_journal(first_day)
+        .await?
+        .into_iter()
+        .map(|e| e.date)
+        .collect();
+    let journal = reflection_split(&activity, &reflected, first_day, Local::now().date_naive());
+    let average_completion = db.average_completion_time(since).await?;
+    let oldest_open = db.list_todos_tail(1, false).await?.into_iter().next();
 
     // Calculate metrics
-    let total_completed = operations
-        .iter()
-        .filter(|op| op.operation_type == OperationType::Complete)
-        .count();
-
-    let total_created = operations
-        .iter()
-        .filter(|op| op.operation_type == OperationType::Create)
-        .count();
+    let total_completed: u64 = activity.iter().map(|b| b.completed).sum();
+    let total_created: u64 = activity.iter().map(|b| b.created).sum();
 
     // Completion by day of week
-    let mut by_weekday: HashMap<Weekday, usize> = HashMap::new();
-    for op in operations
-        .iter()
-        .filter(|op| op.operation_type == OperationType::Complete)
-    {
-        let local = Local.from_utc_datetime(&op.created_at.naive_utc());
-        *by_weekday.entry(local.weekday()).or_insert(0) += 1;
-    }
+    let by_weekday = completions_by_weekday(&activity);
 
     // Find most productive day
-    let best_day = by_weekday
-        .iter()
+    let best_day = WEEKDAYS
+        .into_iter()
+        .zip(by_weekday)
+        .filter(|(_, count)| *count > 0)
         .max_by_key(|(_, count)| *count)
-        .map(|(day, _)| *day);
+        .map(|(day, _)| day);
 
     // Completion heatmap (last 4 weeks)
-    let mut heatmap: Vec<Vec<usize>> = vec![vec![0; 7]; 4];
-    for op in operations
-        .iter()
-        .filter(|op| op.operation_type == OperationType::Complete)
-    {
-        let days_ago = Utc::now().signed_duration_since(op.created_at).num_days() as usize;
+    let mut heatmap: Vec<Vec<u64>> = vec![vec![0; 7]; 4];
+    for bucket in activity.iter().filter(|b| b.completed > 0) {
+        let days_ago = now.signed_duration_since(bucket.hour).num_days() as usize;
         if days_ago < 28 {
             let week = days_ago / 7;
-            let local = Local.from_utc_datetime(&op.created_at.naive_utc());
+            let local = Local.from_utc_datetime(&bucket.hour.naive_utc());
             let day = local.weekday().num_days_from_monday() as usize;
             if week < 4 && day < 7 {
-                heatmap[week][day] += 1;
+                heatmap[week][day] += bucket.completed;
             }
         }
     }
 
+    let pending = stats.pending;
+    let overdue = stats.overdue;
+    let completion_rate = if total_created > 0 {
+        (total_completed as f64 / total_created as f64 * 100.0) as u32
+    } else {
+        0
+    };
+
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "days": days,
+            "created": total_created,
+            "completed": total_completed,
+            "completion_rate": completion_rate,
+            "most_productive_day": best_day.map(|d| format!("{:?}", d)),
+            "heatmap": heatmap,
+            "by_weekday": WEEKDAYS
+                .iter()
+                .zip(by_weekday)
+                .map(|(day, completed)| serde_json::json!({
+                    "day": format!("{:?}", day),
+                    "completed": completed,
+                }))
+                .collect::<Vec<_>>(),
+            "average_completion_secs": average_completion.map(|d| d.num_seconds()),
+            "oldest_open": oldest_open,
+            "pending": pending,
+            "overdue": overdue,
+            "categories": categories,
+            "projects": projects,
+            "focus": focus,
+            "focus_by_day": focus_by_day
+                .iter()
+                .map(|(day, sessions)| serde_json::json!({
+                    "day": day,
+                    "sessions": sessions,
+                }))
+                .collect::<Vec<_>>(),
+            "focus_by_todo": focus_by_todo,
+            "journal": journal,
+        }));
+    }
+
     // Print report
     println!("\x1b[1m┌─────────────────────────────────────────────────────────┐\x1b[0m");
     println!(
@@ -80,17 +128,21 @@ pub async fn run(days: Option<i64>) -> Result<()> {
 
     println!("  Tasks Created:    {}", total_created);
     println!("  Tasks Completed:  {}", total_completed);
-
-    let completion_rate = if total_created > 0 {
-        (total_completed as f64 / total_created as f64 * 100.0) as u32
-    } else {
-        0
-    };
     println!("  Completion Rate:  {}%", completion_rate);
 
     if let Some(day) = best_day {
         println!("  Most Productive:  {:?}", day);
     }
+    if let Some(average) = average_completion {
+        println!("  Avg. to Complete: {}", dates::format_duration(average));
+    }
+    if let Some(ref todo) = oldest_open {
+        let age = now.signed_duration_since(todo.created_at).num_days();
+        println!(
+            "  Oldest Open:      {} \x1b[90m({}d old)\x1b[0m",
+            todo.title, age
+        );
+    }
 
     println!("\n  \x1b[1mCompletion Heatmap (last 4 weeks):\x1b[0m");
     println!("         Mon Tue Wed Thu Fri Sat Sun");
@@ -115,15 +167,93 @@ pub async fn run(days: Option<i64>) -> Result<()> {
         println!();
     }
 
+    if total_completed > 0 {
+        println!("\n  \x1b[1mCompleted by Weekday:\x1b[0m");
+        let rows: Vec<(String, u64)> = WEEKDAYS
+            .iter()
+            .zip(by_weekday)
+            .map(|(day, count)| (format!("{:?}", day), count))
+            .collect();
+        print_bar_table(&rows);
+    }
+
+    if !categories.is_empty() {
+        println!("\n  \x1b[1mCompleted by Category:\x1b[0m");
+        let rows: Vec<(String, u64)> = categories
+            .iter()
+            .map(|c| {
+                let name = c.category.as_deref().unwrap_or("Uncategorized");
+                (name.to_string(), c.completed)
+            })
+            .collect();
+        print_bar_table(&rows);
+    }
+
+    if !projects.is_empty() {
+        println!("\n  \x1b[1mProjects:\x1b[0m");
+        let width = projects
+            .iter()
+            .map(|p| p.project.name.chars().count())
+            .max()
+            .unwrap_or(0);
+        for p in &projects {
+            println!(
+                "  {:<width$}  \x1b[32m{}\x1b[0m {}/{}",
+                p.project.name,
+                super::project::progress_bar(p.ratio(), 20),
+                p.completed,
+                p.total,
+            );
+        }
+    }
+
+    if focus.sessions > 0 {
+        println!("\n  \x1b[1mFocus:\x1b[0m");
+        println!(
+            "  {} session{}, {:.1} interruptions per session",
+            focus.sessions,
+            if focus.sessions == 1 { "" } else { "s" },
+            focus.interruptions_per_session()
+        );
+        if let Some((reason, count)) = focus.reasons.first() {
+            println!("  Most common interruption: {} ({})", reason, count);
+        }
+
+        println!("\n  Sessions per day:");
+        let rows: Vec<(String, u64)> = focus_by_day
+            .iter()
+            .map(|(day, sessions)| (day.format("%a %b %d").to_string(), *sessions))
+            .collect();
+        print_bar_table(&rows);
+
+        println!("\n  Minutes per todo:");
+        let rows: Vec<(String, u64)> = focus_by_todo
+            .iter()
+            .take(5)
+            .map(|t| {
+                let title = t.title.as_deref().unwrap_or("(deleted)");
+                (truncate(title, 30), t.minutes)
+            })
+            .collect();
+        print_bar_table(&rows);
+    }
+
+    if journal.days_with > 0 {
+        println!("\n  \x1b[1mJournal:\x1b[0m");
+        println!(
+            "  Reflected on {} of {} days",
+            journal.days_with,
+            journal.days_with + journal.days_without
+        );
+        println!(
+            "  Avg. completions: {:.1} on days with a reflection, {:.1} without",
+            journal.average_with, journal.average_without
+        );
+    }
+
     // Suggestions
     println!("\n  \x1b[1mSuggestions:\x1b[0m");
 
-    let pending = todos.iter().filter(|t| !t.is_completed).count();
-    let overdue = todos
-        .iter()
-        .filter(|t| !t.is_completed && t.due_date.is_some_and(|d| d < Utc::now()))
-        .count();
-
     if overdue > 0 {
         println!(
             "  \x1b[33m•\x1b[0m You have {} overdue todos - consider rescheduling",
@@ -148,3 +278,176 @@ pub async fn run(days: Option<i64>) -> Result<()> {
 
     Ok(())
 }
+
+/// Days of the week, Monday first, matching [`completions_by_weekday`].
+pub const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// Completions per local weekday, Monday first.
+pub fn completions_by_weekday(activity: &[ActivityBucket]) -> [u64; 7] {
+    let mut counts = [0; 7];
+    for bucket in activity {
+        let local = Local.from_utc_datetime(&bucket.hour.naive_utc());
+        counts[local.weekday().num_days_from_monday() as usize] += bucket.completed;
+    }
+    counts
+}
+
+/// Focus sessions counted by the local day they started, days without
+/// sessions left out.
+pub fn focus_sessions_by_day(sessions: &[FocusSession]) -> Vec<(NaiveDate, u64)> {
+    let mut counts: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+    for session in sessions {
+        let day = session.started_at.with_timezone(&Local).date_naive();
+        *counts.entry(day).or_default() += 1;
+    }
+    counts.into_iter().collect()
+}
+
+/// Completions on days with a journal reflection against days without.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ReflectionSplit {
+    pub days_with: u64,
+    /// Mean completions per day with a reflection
+    pub average_with: f64,
+    pub days_without: u64,
+    pub average_without: f64,
+}
+
+/// Split the local days `first..=last` by whether they are in `reflected`
+/// and average the completions on each side.
+pub fn reflection_split(
+    activity: &[ActivityBucket],
+    reflected: &[NaiveDate],
+    first: NaiveDate,
+    last: NaiveDate,
+) -> ReflectionSplit {
+    let mut by_day: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+    for bucket in activity {
+        let day = bucket.hour.with_timezone(&Local).date_naive();
+        *by_day.entry(day).or_default() += bucket.completed;
+    }
+    let reflected: HashSet<_> = reflected.iter().collect();
+
+    let (mut with, mut without) = ((0, 0), (0, 0));
+    let mut day = first;
+    while day <= last {
+        let side = if reflected.contains(&day) {
+            &mut with
+        } else {
+            &mut without
+        };
+        side.0 += 1;
+        side.1 += by_day.get(&day).copied().unwrap_or(0);
+        day += Duration::days(1);
+    }
+    let average = |(days, completed): (u64, u64)| {
+        if days == 0 {
+            0.0
+        } else {
+            completed as f64 / days as f64
+        }
+    };
+    ReflectionSplit {
+        days_with: with.0,
+        average_with: average(with),
+        days_without: without.0,
+        average_without: average(without),
+    }
+}
+
+/// Print `label  bar count` rows, bars scaled to the largest count.
+fn print_bar_table(rows: &[(String, u64)]) {
+    let width = rows
+        .iter()
+        .map(|(l, _)| l.chars().count())
+        .max()
+        .unwrap_or(0);
+    let max = rows.iter().map(|(_, c)| *c).max().unwrap_or(0).max(1);
+    for (label, count) in rows {
+        println!(
+            "  {:<width$}  \x1b[32m{}\x1b[0m {}",
+            label,
+            super::project::progress_bar(*count as f64 / max as f64, 20),
+            count
+        );
+    }
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        s.to_string()
+    } else {
+        format!("{}...", s.chars().take(max - 3).collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completions_by_weekday() {
+        let monday = Local.with_ymd_and_hms(2026, 10, 12, 9, 0, 0).unwrap();
+        let bucket = |at: chrono::DateTime<Local>, completed| ActivityBucket {
+            hour: at.with_timezone(&Utc),
+            created: 1,
+            completed,
+        };
+        let activity = [
+            bucket(monday, 2),
+            bucket(monday + chrono::Duration::hours(3), 1),
+            bucket(monday + chrono::Duration::days(6), 4),
+        ];
+        assert_eq!(completions_by_weekday(&activity), [3, 0, 0, 0, 0, 0, 4]);
+    }
+
+    #[test]
+    fn test_focus_sessions_by_day() {
+        let monday = Local.with_ymd_and_hms(2026, 10, 12, 9, 0, 0).unwrap();
+        let session = |at: chrono::DateTime<Local>| {
+            FocusSession::new(uuid::Uuid::new_v4(), at.with_timezone(&Utc))
+        };
+        let sessions = [
+            session(monday),
+            session(monday + chrono::Duration::hours(5)),
+            session(monday + chrono::Duration::days(2)),
+        ];
+        let day = |d| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+        assert_eq!(
+            focus_sessions_by_day(&sessions),
+            [(day(12), 2), (day(14), 1)]
+        );
+    }
+
+    #[test]
+    fn test_reflection_split() {
+        let day = |d| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+        let bucket = |d, completed| ActivityBucket {
+            hour: Local
+                .with_ymd_and_hms(2026, 10, d, 14, 0, 0)
+                .unwrap()
+                .with_timezone(&Utc),
+            created: 0,
+            completed,
+        };
+        let activity = [bucket(12, 4), bucket(13, 1), bucket(14, 6)];
+        let split = reflection_split(&activity, &[day(12), day(14)], day(12), day(15));
+        assert_eq!(
+            split,
+            ReflectionSplit {
+                days_with: 2,
+                average_with: 5.0,
+                days_without: 2,
+                average_without: 0.5,
+            }
+        );
+    }
+}
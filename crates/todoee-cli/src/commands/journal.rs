@@ -0,0 +1,115 @@
+//! Per-day journal: what got done, and a short reflection on it.
+
+use std::fs;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+use todoee_core::{Config, LocalDb, Operation, dates};
+
+use super::output;
+
+pub async fn run(date: Option<String>, note: Option<String>) -> Result<()> {
+    let config = Config::load().context("Failed to load config")?;
+    let today = Local::now().date_naive();
+    let date = match date {
+        // Only here: due-date parsing has no use for past days
+        Some(input) if input.trim().eq_ignore_ascii_case("yesterday") => today - Duration::days(1),
+        Some(input) => {
+            let date_input = config.display.date_input();
+            dates::parse_with(&input, &Local::now(), &date_input)
+                .map(|parsed| parsed.date)
+                .with_context(|| format!("Could not understand date '{}'", input))?
+        }
+        None => today,
+    };
+
+    let db_path = config.local_db_path()?;
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    let entry = match note {
+        Some(note) => {
+            let note = note.trim();
+            if note.is_empty() {
+                anyhow::bail!("Journal note cannot be empty");
+            }
+            Some(db.append_journal(date, note).await?)
+        }
+        None => db.get_journal(date).await?,
+    };
+    let start = local_midnight(date);
+    let completions = db
+        .list_completions(start, local_midnight(date + Duration::days(1)))
+        .await?;
+
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "date": date,
+            "completed": completions
+                .iter()
+                .map(|op| serde_json::json!({
+                    "todo_id": op.entity_id,
+                    "title": title(op),
+                    "completed_at": op.created_at,
+                }))
+                .collect::<Vec<_>>(),
+            "note": entry.as_ref().map(|e| &e.note),
+        }));
+    }
+
+    println!("\x1b[1m{}\x1b[0m\n", date.format("%A, %B %-d, %Y"));
+    if completions.is_empty() {
+        println!("  Nothing completed.");
+    } else {
+        println!("  Completed ({}):", completions.len());
+        for op in &completions {
+            let time = op.created_at.with_timezone(&Local);
+            println!(
+                "    \x1b[90m{}\x1b[0m  \x1b[32m\u{2713}\x1b[0m {}",
+                time.format("%H:%M"),
+                title(op)
+            );
+        }
+    }
+
+    println!();
+    match entry {
+        Some(entry) => {
+            println!("  Reflection:");
+            for line in entry.note.lines() {
+                println!("    {}", line);
+            }
+        }
+        None => println!(
+            "  \x1b[90mNo reflection yet. Add one: todoee journal {} --note \"...\"\x1b[0m",
+            date
+        ),
+    }
+    Ok(())
+}
+
+/// Start of a local day, in UTC
+pub fn local_midnight(date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_hms_opt(0, 0, 0).expect("midnight is a valid time");
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+/// Title of the completed todo as recorded in the operation
+fn title(op: &Operation) -> &str {
+    op.new_state
+        .as_ref()
+        .or(op.previous_state.as_ref())
+        .and_then(|s| s.get("title"))
+        .and_then(|t| t.as_str())
+        .unwrap_or("?")
+}
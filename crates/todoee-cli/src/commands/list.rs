@@ -1,11 +1,93 @@
 use anyhow::{Context, Result};
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Datelike, Local, Utc};
+use std::cmp::Reverse;
 use std::collections::HashMap;
 use std::fs;
-use todoee_core::{Category, Config, LocalDb, Priority, Todo};
+use std::str::FromStr;
+use todoee_core::{
+    Category, Config, DisplayConfig, DueUrgency, Filter, LocalDb, Priority, RankingConfig, Todo,
+    dates, short_id, urgency,
+};
 use uuid::Uuid;
 
-pub async fn run(today: bool, category: Option<String>, all: bool) -> Result<()> {
+use super::context::ActiveContext;
+use super::{ids, output};
+
+/// Order of a `--sort` list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListSort {
+    /// Oldest first
+    Created,
+    /// Soonest first, todos without a due date last
+    Due,
+    /// Most important first
+    Priority,
+    Title,
+    /// Most urgent first
+    Urgency,
+}
+
+impl FromStr for ListSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "created" => Ok(Self::Created),
+            "due" => Ok(Self::Due),
+            "priority" => Ok(Self::Priority),
+            "title" => Ok(Self::Title),
+            "urgency" => Ok(Self::Urgency),
+            _ => Err(format!(
+                "Unknown sort '{}' (expected created, due, priority, title or urgency)",
+                s
+            )),
+        }
+    }
+}
+
+impl ListSort {
+    pub fn sort(self, todos: &mut [Todo], ranking: &RankingConfig, now: DateTime<Utc>) {
+        match self {
+            Self::Created => todos.sort_by_key(|t| t.created_at),
+            Self::Due => todos.sort_by_key(|t| (t.due_date.is_none(), t.due_date)),
+            Self::Priority => todos.sort_by_key(|t| Reverse(t.priority)),
+            Self::Title => todos.sort_by_key(|t| t.title.to_lowercase()),
+            Self::Urgency => todos.sort_by(|a, b| {
+                urgency::urgency(b, ranking, now).total_cmp(&urgency::urgency(a, ranking, now))
+            }),
+        }
+    }
+}
+
+pub async fn run(
+    query: Option<String>,
+    today: bool,
+    category: Option<String>,
+    project: Option<String>,
+    week: Option<u32>,
+    all: bool,
+    sort: Option<ListSort>,
+) -> Result<()> {
+    // Resolve the week first so a bad number fails before touching the database
+    let week_bounds = match week {
+        Some(week) => {
+            let year = Local::now().date_naive().iso_week().year();
+            let bounds = dates::iso_week_bounds(year, week)
+                .with_context(|| format!("Week {} does not exist in {}", week, year))?;
+            Some((week, bounds))
+        }
+        None => None,
+    };
+
+    let filter = match &query {
+        Some(query) => Some(
+            query
+                .parse::<Filter>()
+                .with_context(|| format!("Invalid query \"{}\"", query))?,
+        ),
+        None => None,
+    };
+
     // Load config and open local database
     let config = Config::load().context("Failed to load configuration")?;
     let db_path = config.local_db_path()?;
@@ -22,7 +104,9 @@ pub async fn run(today: bool, category: Option<String>, all: bool) -> Result<()>
     db.run_migrations().await?;
 
     // Get todos based on filters
-    let todos = if today {
+    let todos = if let Some(filter) = &filter {
+        db.list_todos_filtered(filter, !all, &Local::now()).await?
+    } else if today {
         db.list_todos_due_today().await?
     } else if let Some(cat_name) = &category {
         // Find category by name
@@ -31,22 +115,120 @@ pub async fn run(today: bool, category: Option<String>, all: bool) -> Result<()>
             .await?
             .with_context(|| format!("Category '{}' not found", cat_name))?;
         db.list_todos_by_category(cat.id).await?
+    } else if let Some(project_name) = &project {
+        let project = db
+            .get_project_by_name(project_name)
+            .await?
+            .with_context(|| format!("Project '{}' not found", project_name))?;
+        let mut todos = db.list_todos_by_project(project.id).await?;
+        if !all {
+            todos.retain(|t| !t.is_completed);
+        }
+        todos
     } else {
         // List all todos, exclude completed unless --all is set
         db.list_todos(!all).await?
     };
 
+    let mut todos = match week_bounds {
+        Some((_, (monday, sunday))) => todos
+            .into_iter()
+            .filter(|t| {
+                t.due_date.is_some_and(|due| {
+                    let day = due.with_timezone(&Local).date_naive();
+                    day >= monday && day <= sunday
+                })
+            })
+            .collect(),
+        None => todos,
+    };
+    let context = ActiveContext::load(&db, &config).await?;
+    if let Some(context) = &context {
+        context.retain(&mut todos);
+    }
+    let now = Utc::now();
+    if let Some(sort) = sort {
+        sort.sort(&mut todos, &config.ranking, now);
+    }
+
+    if output::is_json() {
+        let ids: Vec<Uuid> = todos.iter().map(|t| t.id).collect();
+        ids::save_listing(&config, &ids)?;
+        if sort == Some(ListSort::Urgency) {
+            let scored: Vec<_> = todos
+                .iter()
+                .map(|t| {
+                    let mut value = serde_json::to_value(t)?;
+                    value["urgency"] = urgency::urgency(t, &config.ranking, now).into();
+                    Ok(value)
+                })
+                .collect::<Result<_>>()?;
+            return output::print_json(&scored);
+        }
+        return output::print_json(&todos);
+    }
+
     // Handle empty results
     if todos.is_empty() {
-        if today {
+        if let Some(query) = &query {
+            println!("No tasks match \"{}\".", query);
+        } else if let Some((week, _)) = week_bounds {
+            println!("No tasks due in week {}.", week);
+        } else if today {
             println!("No tasks due today. Enjoy your free time!");
         } else if let Some(cat_name) = &category {
             println!("No tasks in category '{}'.", cat_name);
+        } else if let Some(project_name) = &project {
+            println!("No tasks in project '{}'.", project_name);
         } else if all {
             println!("No tasks found. Use 'todoee add' to create one!");
         } else {
             println!("No pending tasks. Use 'todoee add' to create one!");
         }
+        if let Some(context) = &context {
+            println!(
+                "\x1b[90mContext '{}' is active; clear it with: todoee context none\x1b[0m",
+                context.name
+            );
+        }
+        return Ok(());
+    }
+
+    let compact = config.display.is_compact();
+    let icon_column = todos.iter().any(|t| t.icon.is_some());
+    let id_len = ids::id_length(&db, &config).await?;
+    let number_width = todos.len().to_string().len();
+
+    if let Some(context) = &context {
+        println!("\x1b[90mContext: {}\x1b[0m", context.name);
+    }
+
+    if let Some((week, (monday, sunday))) = week_bounds {
+        println!(
+            "Week {} \x1b[90m({} \u{2013} {})\x1b[0m",
+            week,
+            monday.format("%b %d"),
+            sunday.format("%b %d")
+        );
+        if !compact {
+            println!();
+        }
+    }
+
+    // A sorted list is one list, in that order
+    if let Some(sort) = sort {
+        for (i, todo) in todos.iter().enumerate() {
+            let number = format!("#{:<number_width$}", i + 1);
+            if sort == ListSort::Urgency {
+                print!(
+                    "  \x1b[36m{:>5.1}\x1b[0m",
+                    urgency::urgency(todo, &config.ranking, now)
+                );
+            }
+            print_todo(todo, &number, &config.display, icon_column, id_len);
+        }
+        let ids: Vec<Uuid> = todos.iter().map(|t| t.id).collect();
+        ids::save_listing(&config, &ids)?;
         return Ok(());
     }
 
@@ -82,8 +264,9 @@ pub async fn run(today: bool, category: Option<String>, all: bool) -> Result<()>
     });
 
     // Print each category group
+    let mut listed: Vec<Uuid> = Vec::with_capacity(todos.len());
     for (idx, cat_id) in sorted_categories.iter().enumerate() {
-        if idx > 0 {
+        if idx > 0 && !compact {
             println!(); // Blank line between categories
         }
 
@@ -100,34 +283,59 @@ pub async fn run(today: bool, category: Option<String>, all: bool) -> Result<()>
         // Print todos in this category
         if let Some(todos_in_cat) = grouped.get(cat_id) {
             for todo in todos_in_cat {
-                print_todo(todo);
+                listed.push(todo.id);
+                let number = format!("#{:<number_width$}", listed.len());
+                print_todo(todo, &number, &config.display, icon_column, id_len);
             }
         }
     }
 
+    // Numbers follow the printed order, categories and all
+    ids::save_listing(&config, &listed)?;
+
     Ok(())
 }
 
-/// Print a single todo item with status, priority, title, ID, and due date
-fn print_todo(todo: &Todo) {
+/// Print a single todo item with its list number, status, priority, title,
+/// ID, and due date. Compact lists leave out the ID. With `icon_column` set,
+/// titles are indented past the icon so they line up whether or not a todo
+/// has one.
+fn print_todo(
+    todo: &Todo,
+    number: &str,
+    display: &DisplayConfig,
+    icon_column: bool,
+    id_len: usize,
+) {
+    let compact = display.is_compact();
     // Status checkbox
     let checkbox = if todo.is_completed { "[x]" } else { "[ ]" };
 
     // Priority markers
     let priority = match todo.priority {
+        Priority::Urgent => "!!!! ",
         Priority::High => "!!! ",
         Priority::Medium => "!! ",
         Priority::Low => "! ",
     };
 
-    // Short ID (first 8 characters of UUID)
-    let short_id = &todo.id.to_string()[..8];
+    let short_id = short_id(&todo.id, id_len);
 
     // Due date formatting
-    let due_info = format_due_date(todo.due_date);
+    let due_info = format_due_date(todo.due_date, display);
 
     // Build the output line
-    let mut line = format!("{} {}{} [{}]", checkbox, priority, todo.title, short_id);
+    let mut line = format!(
+        "\x1b[90m{}\x1b[0m {} {}{}{}",
+        number,
+        checkbox,
+        priority,
+        todo.icon_prefix(icon_column),
+        todo.title
+    );
+    if !compact {
+        line.push_str(&format!(" [{}]", short_id));
+    }
     if !due_info.is_empty() {
         line.push_str(&format!(" {}", due_info));
     }
@@ -135,8 +343,9 @@ fn print_todo(todo: &Todo) {
     println!("  {}", line);
 }
 
-/// Format due date info for display
-fn format_due_date(due_date: Option<DateTime<Utc>>) -> String {
+/// Format due date info for display; days are counted down while the
+/// todo is due soon
+fn format_due_date(due_date: Option<DateTime<Utc>>, display: &DisplayConfig) -> String {
     let Some(due) = due_date else {
         return String::new();
     };
@@ -156,10 +365,13 @@ fn format_due_date(due_date: Option<DateTime<Utc>>) -> String {
             format!("[OVERDUE by {} days]", days_overdue)
         }
     } else if days_diff == 0 {
-        "[TODAY]".to_string()
+        match dates::due_time(&due, &Local) {
+            Some(time) => format!("[TODAY {}]", time.format("%H:%M")),
+            None => "[TODAY]".to_string(),
+        }
     } else if days_diff == 1 {
         "[Tomorrow]".to_string()
-    } else if days_diff <= 7 {
+    } else if display.due_urgency(&due, now) <= DueUrgency::Soon {
         format!("[in {} days]", days_diff)
     } else {
         // Further out: show formatted date
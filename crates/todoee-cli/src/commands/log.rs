@@ -4,9 +4,11 @@ use std::fs;
 
 use anyhow::{Context, Result};
 use chrono::{Local, TimeZone};
-use todoee_core::{Config, LocalDb};
+use todoee_core::{Config, LocalDb, OperationStats, OperationType};
 
-pub async fn run(limit: Option<usize>, oneline: bool) -> Result<()> {
+use super::{ids, output};
+
+pub async fn run(limit: Option<usize>, oneline: bool, stats: bool) -> Result<()> {
     let config = Config::load().context("Failed to load config")?;
     let db_path = config.local_db_path()?;
 
@@ -20,25 +22,39 @@ pub async fn run(limit: Option<usize>, oneline: bool) -> Result<()> {
     let db = LocalDb::new(&db_path).await?;
     db.run_migrations().await?;
 
+    if stats {
+        return print_stats(&db.operation_stats().await?, &config);
+    }
+
     let operations = db.list_operations(limit.unwrap_or(10)).await?;
 
+    if output::is_json() {
+        return output::print_json(&operations);
+    }
+
     if operations.is_empty() {
         println!("No operations recorded yet.");
         return Ok(());
     }
 
+    let id_len = ids::id_length(&db, &config).await?;
     for op in operations {
         let time = Local.from_utc_datetime(&op.created_at.naive_utc());
         let short_id = &op.id.to_string()[..7];
-        let entity_short = &op.entity_id.to_string()[..8];
+        let entity_short = todoee_core::short_id(&op.entity_id, id_len);
 
-        let title = op
-            .new_state
-            .as_ref()
-            .or(op.previous_state.as_ref())
-            .and_then(|s| s.get("title"))
-            .and_then(|t| t.as_str())
-            .unwrap_or("?");
+        let title = match op.operation_type {
+            OperationType::BatchCreate => format!("{} todos", op.batch_todos().len()),
+            OperationType::Summary => format!("{} todos", op.summary_states().len()),
+            _ => op
+                .new_state
+                .as_ref()
+                .or(op.previous_state.as_ref())
+                .and_then(|s| s.get("title"))
+                .and_then(|t| t.as_str())
+                .unwrap_or("?")
+                .to_string(),
+        };
 
         let status = if op.undone { " (undone)" } else { "" };
 
@@ -49,7 +65,7 @@ pub async fn run(limit: Option<usize>, oneline: bool) -> Result<()> {
                 time.format("%m-%d %H:%M"),
                 op.operation_type,
                 entity_short,
-                truncate(title, 40),
+                truncate(&title, 40),
                 status
             );
         } else {
@@ -65,6 +81,55 @@ pub async fn run(limit: Option<usize>, oneline: bool) -> Result<()> {
     Ok(())
 }
 
+/// How much history is kept, and the limits on it
+fn print_stats(stats: &OperationStats, config: &Config) -> Result<()> {
+    if output::is_json() {
+        return output::print_json(stats);
+    }
+
+    let history = &config.history;
+    println!(
+        "Operations: {} ({} undone, {} in groups, {} compacted summaries)",
+        stats.operations, stats.undone, stats.grouped, stats.summaries
+    );
+    println!(
+        "Size:       {} of saved todo states",
+        format_size(stats.bytes)
+    );
+    if let (Some(oldest), Some(newest)) = (stats.oldest, stats.newest) {
+        println!(
+            "Span:       {} to {}",
+            oldest.with_timezone(&Local).format("%Y-%m-%d"),
+            newest.with_timezone(&Local).format("%Y-%m-%d")
+        );
+    }
+    if history.max_operations > 0 {
+        println!("Limit:      {} operations", history.max_operations);
+    } else {
+        println!("Limit:      none");
+    }
+    println!(
+        "Compaction: after {} days, {}",
+        history.compact_after_days,
+        if history.auto_compact {
+            "hourly by todoee-daemon"
+        } else {
+            "with 'todoee gc --compact'"
+        }
+    );
+
+    Ok(())
+}
+
+/// A byte count in B, KB or MB
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
 fn truncate(s: &str, max: usize) -> String {
     if s.len() <= max {
         s.to_string()
@@ -0,0 +1,190 @@
+//! `todoee login`, `logout` and `whoami`: the account this device syncs as.
+//!
+//! Logging in stores a session token in `auth.json`. From then on sync only
+//! uploads and downloads that user's todos, categories and projects, so
+//! several people can share one database.
+
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use anyhow::{Context, Result};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use todoee_core::auth::Auth;
+use todoee_core::{Config, sync::SyncService};
+
+use super::config::prompt;
+use super::output;
+
+pub async fn login(email: Option<String>) -> Result<()> {
+    if Config::is_demo() {
+        anyhow::bail!("Sync is turned off in demo mode");
+    }
+    let config = Config::load().context("Failed to load configuration")?;
+    let service = SyncService::new(&config)
+        .await
+        .context("Failed to initialize sync service")?;
+    if !service.is_configured() {
+        anyhow::bail!(
+            "Cloud sync is not configured. Set {} or database.sync_dir first",
+            config.database.url_env
+        );
+    }
+
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let email = match email {
+        Some(email) => email,
+        None => {
+            let default = Auth::load()?.map(|auth| auth.email).unwrap_or_default();
+            loop {
+                match prompt(&mut input, "Email", &default)? {
+                    Some(email) => break email,
+                    None if !default.is_empty() => break default,
+                    None => {}
+                }
+            }
+        }
+    };
+    let email = email.trim().to_string();
+    if !email.contains('@') {
+        anyhow::bail!("'{}' isn't an email address", email);
+    }
+
+    let new_account = !service.has_account(&email).await?;
+    if new_account {
+        println!(
+            "  No account for {} yet; choose a password to create it.",
+            email
+        );
+    }
+    let password = read_password(&mut input, "Password")?;
+    if new_account && read_password(&mut input, "Confirm password")? != password {
+        anyhow::bail!("Passwords don't match");
+    }
+
+    let login = service
+        .login(&email, &password)
+        .await
+        .context("Login failed")?;
+    Auth::with_session(&login.user, login.session_token.clone())
+        .save()
+        .context("Failed to save auth.json")?;
+
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "user": login.user,
+            "new_account": login.new_account,
+            "claimed": login.claimed,
+        }));
+    }
+    if login.new_account {
+        output::say!("\u{2713} Created account {}", login.user.email);
+    } else {
+        output::say!("\u{2713} Logged in as {}", login.user.email);
+    }
+    if login.claimed > 0 {
+        output::say!(
+            "  {} todo{} synced before accounts existed now belong to you",
+            login.claimed,
+            if login.claimed == 1 { "" } else { "s" }
+        );
+    }
+    output::say!("  Sync now only exchanges your todos. Log in on your other devices too.");
+    Ok(())
+}
+
+pub async fn logout() -> Result<()> {
+    let Some(auth) = Auth::load()? else {
+        output::say!("Not logged in");
+        return Ok(());
+    };
+
+    // Ending the session remotely is best effort: offline, the local token
+    // is still forgotten
+    if auth.session_token.is_some() && !Config::is_demo() {
+        let config = Config::load().context("Failed to load configuration")?;
+        let ended = match SyncService::new(&config).await {
+            Ok(service) if service.is_configured() => service.logout().await,
+            Ok(_) => Ok(()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = ended {
+            eprintln!("Warning: couldn't end the session on the server: {}", e);
+        }
+    }
+    Auth::clear().context("Failed to update auth.json")?;
+
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({ "logged_out": auth.email }));
+    }
+    output::say!("\u{2713} Logged out of {}", auth.email);
+    Ok(())
+}
+
+pub fn whoami() -> Result<()> {
+    let auth = Auth::load()?;
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "email": auth.as_ref().map(|a| &a.email),
+            "user_id": auth.as_ref().map(|a| a.user_id),
+            "logged_in": auth.as_ref().is_some_and(|a| a.session_token.is_some()),
+        }));
+    }
+    match auth {
+        Some(auth) if auth.session_token.is_some() => {
+            println!("{}", auth.email);
+            output::detail!("User ID: {}", auth.user_id);
+        }
+        Some(auth) => {
+            println!("{} (linked, not logged in)", auth.email);
+            println!("  Run `todoee login` so sync only exchanges your todos");
+        }
+        None => println!("Not logged in. Run `todoee login` to sign in."),
+    }
+    Ok(())
+}
+
+/// Ask for a password without echoing it. Piped input is read as a line, so
+/// scripts can log in.
+fn read_password(input: &mut impl BufRead, label: &str) -> Result<String> {
+    print!("  {}: ", label);
+    io::stdout().flush()?;
+    if !io::stdin().is_terminal() {
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            anyhow::bail!("Cancelled (end of input)");
+        }
+        println!();
+        return Ok(line.trim_end_matches(['\r', '\n']).to_string());
+    }
+
+    terminal::enable_raw_mode()?;
+    let password = read_hidden();
+    terminal::disable_raw_mode()?;
+    println!();
+    password
+}
+
+fn read_hidden() -> Result<String> {
+    let mut password = String::new();
+    loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => return Ok(password),
+            KeyCode::Backspace => {
+                password.pop();
+            }
+            KeyCode::Esc => anyhow::bail!("Cancelled"),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                anyhow::bail!("Cancelled")
+            }
+            KeyCode::Char(c) => password.push(c),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Eisenhower matrix command: sort pending todos by urgency and importance.
+
+use std::fs;
+
+use anyhow::{Context, Result};
+use chrono::{Local, Utc};
+use clap::Subcommand;
+use todoee_core::{
+    Config, EntityType, LocalDb, Operation, OperationType, Priority, Quadrant, Todo,
+    URGENT_WITHIN_DAYS, short_id,
+};
+
+use super::{ids, output};
+
+/// Width of one matrix column in characters
+const COLUMN_WIDTH: usize = 40;
+
+#[derive(Subcommand, Clone)]
+pub enum MatrixCommand {
+    /// Move a todo to another quadrant
+    ///
+    /// Adjusts priority and due date so the todo lands in the target quadrant.
+    ///
+    /// Examples:
+    ///   todoee matrix move abc1 schedule
+    ///   todoee matrix move abc1 1
+    Move {
+        /// Todo ID (or prefix)
+        id: String,
+        /// Target quadrant: do, schedule, delegate, eliminate (or 1-4)
+        quadrant: String,
+    },
+}
+
+pub async fn run(command: Option<MatrixCommand>) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    match command {
+        None => show(&db, &config).await,
+        Some(MatrixCommand::Move { id, quadrant }) => move_todo(&db, &config, &id, &quadrant).await,
+    }
+}
+
+async fn show(db: &LocalDb, config: &Config) -> Result<()> {
+    let now = Utc::now();
+    let todos = db.list_todos(true).await?;
+
+    if todos.is_empty() && !output::is_json() {
+        println!("No pending todos. Use 'todoee add' to create one!");
+        return Ok(());
+    }
+
+    let id_len = ids::id_length(db, config).await?;
+    let mut quadrants: [Vec<Todo>; 4] = Default::default();
+    for todo in todos {
+        quadrants[todo.quadrant(now).index()].push(todo);
+    }
+    for todos in &mut quadrants {
+        todos.sort_by_key(|t| {
+            (
+                t.due_date.is_none(),
+                t.due_date,
+                std::cmp::Reverse(t.priority),
+            )
+        });
+    }
+
+    if output::is_json() {
+        let mut matrix = serde_json::Map::new();
+        for quadrant in [
+            Quadrant::DoFirst,
+            Quadrant::Schedule,
+            Quadrant::Delegate,
+            Quadrant::Eliminate,
+        ] {
+            matrix.insert(
+                quadrant.to_string(),
+                serde_json::to_value(&quadrants[quadrant.index()])?,
+            );
+        }
+        return output::print_json(&matrix);
+    }
+
+    println!(
+        "\x1b[90mUrgent = due within {} days, important = high priority\x1b[0m",
+        URGENT_WITHIN_DAYS
+    );
+    println!();
+
+    for pair in Quadrant::ALL.chunks(2) {
+        let (left, right) = (pair[0], pair[1]);
+        println!(
+            "{}{}",
+            pad(&header(left), COLUMN_WIDTH, header_width(left)),
+            header(right)
+        );
+
+        let rows = quadrants[left.index()]
+            .len()
+            .max(quadrants[right.index()].len())
+            .max(1);
+        for row in 0..rows {
+            let cell = |q: Quadrant| match quadrants[q.index()].get(row) {
+                Some(todo) => format_cell(todo, id_len),
+                None if row == 0 => ("  \x1b[90m(empty)\x1b[0m".to_string(), 9),
+                None => (String::new(), 0),
+            };
+            let (left_text, left_width) = cell(left);
+            let (right_text, _) = cell(right);
+            println!(
+                "{}{}",
+                pad(&left_text, COLUMN_WIDTH, left_width),
+                right_text
+            );
+        }
+        println!();
+    }
+
+    println!("Move a todo with 'todoee matrix move <id> <do|schedule|delegate|eliminate>'");
+
+    Ok(())
+}
+
+async fn move_todo(db: &LocalDb, config: &Config, id: &str, quadrant: &str) -> Result<()> {
+    let target: Quadrant = quadrant.parse()?;
+
+    let mut todo = ids::resolve_todo(db, config, id).await?;
+    if todo.is_completed {
+        anyhow::bail!("'{}' is completed; reopen it to move it", todo.title);
+    }
+    let prev_state = serde_json::to_value(&todo)?;
+    let before = todo.clone();
+
+    if !todo.move_to_quadrant(target, Utc::now()) {
+        println!("'{}' is already in {}", todo.title, target.title());
+        return Ok(());
+    }
+
+    db.update_todo(&todo).await?;
+
+    let op = Operation::new(
+        OperationType::Update,
+        EntityType::Todo,
+        todo.id,
+        Some(prev_state),
+        Some(serde_json::to_value(&todo)?),
+    );
+    db.record_operation(&op).await?;
+
+    println!("\u{270E} Moved to {}: {}", target.title(), todo.title);
+    if before.priority != todo.priority {
+        println!(
+            "  Priority: {} -> {}",
+            priority_name(before.priority),
+            priority_name(todo.priority)
+        );
+    }
+    if before.due_date != todo.due_date {
+        println!(
+            "  Due: {} -> {}",
+            format_due(before.due_date),
+            format_due(todo.due_date)
+        );
+    }
+
+    Ok(())
+}
+
+fn header(q: Quadrant) -> String {
+    let color = match q {
+        Quadrant::DoFirst => "31",
+        Quadrant::Schedule => "33",
+        Quadrant::Delegate => "36",
+        Quadrant::Eliminate => "90",
+    };
+    format!("\x1b[1;{}m{}. {}\x1b[0m", color, q.index() + 1, q.title())
+}
+
+/// Visible width of `header(q)`, ignoring escape codes
+fn header_width(q: Quadrant) -> usize {
+    q.title().chars().count() + 3
+}
+
+/// Render a todo as "  abc12345 Title" and return it with its visible width.
+fn format_cell(todo: &Todo, id_len: usize) -> (String, usize) {
+    let max_title = COLUMN_WIDTH - 13;
+    let title: String = if todo.title.chars().count() > max_title {
+        let mut t: String = todo.title.chars().take(max_title - 1).collect();
+        t.push('\u{2026}');
+        t
+    } else {
+        todo.title.clone()
+    };
+    let width = 2 + id_len + 1 + title.chars().count();
+    (
+        format!("  \x1b[90m{}\x1b[0m {}", short_id(&todo.id, id_len), title),
+        width,
+    )
+}
+
+fn pad(text: &str, width: usize, visible: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(visible)))
+}
+
+fn priority_name(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Low => "Low",
+        Priority::Medium => "Medium",
+        Priority::Urgent => "Urgent",
+        Priority::High => "High",
+    }
+}
+
+fn format_due(due: Option<chrono::DateTime<Utc>>) -> String {
+    due.map(|d| d.with_timezone(&Local).format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "none".to_string())
+}
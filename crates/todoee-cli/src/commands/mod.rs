@@ -1,26 +1,62 @@
 pub mod add;
+pub mod agenda;
+pub mod ai;
+pub mod alias;
+pub mod attach;
 pub mod batch;
+pub mod category;
 pub mod config;
+pub mod conflicts;
+pub mod context;
 pub mod daemon;
 pub mod delete;
+pub mod demo;
+#[cfg(feature = "dev-tools")]
+pub mod dev;
+pub mod devices;
 pub mod diff;
 pub mod done;
 pub mod edit;
+pub mod event;
 pub mod export;
 pub mod focus;
 pub mod gc;
+pub mod github;
+pub mod greet;
 pub mod head;
 pub mod help;
+pub mod history;
+pub mod hook;
+pub mod http;
+pub mod ids;
 pub mod import;
 pub mod insights;
+pub mod journal;
 pub mod list;
 pub mod log;
+pub mod login;
+pub mod matrix;
+pub mod normalize;
+pub mod note;
 pub mod now;
+pub mod output;
+pub mod profile;
+pub mod project;
+pub mod recover;
 pub mod redo;
+pub mod remind;
+pub mod reopen;
 pub mod search;
+pub mod self_update;
+pub mod serve;
+pub mod serve_ics;
 pub mod show;
+pub mod snooze;
 pub mod stash;
+pub mod stats;
 pub mod sync;
+pub mod timeblock;
+pub mod track;
 pub mod undo;
 pub mod upcoming;
 
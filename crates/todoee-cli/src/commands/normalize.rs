@@ -0,0 +1,287 @@
+//! Bulk rewrite of due date times, e.g. after importing from another app.
+
+use std::fs;
+use std::io;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use todoee_core::{Config, EntityType, LocalDb, Operation, OperationType, Todo, dates, short_id};
+use uuid::Uuid;
+
+use super::config::confirm;
+use super::{ids, output};
+
+/// A due date that would change
+struct Change {
+    todo: Todo,
+    due: DateTime<Utc>,
+    old: String,
+    new: String,
+}
+
+pub async fn run(
+    time: String,
+    timezone: Option<String>,
+    category: Option<String>,
+    project: Option<String>,
+    include_date_only: bool,
+    preview: bool,
+    yes: bool,
+) -> Result<()> {
+    let time = parse_time(&time)?;
+    let tz = timezone.as_deref().map(parse_timezone).transpose()?;
+
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    let mut todos = if let Some(name) = &category {
+        let category = db
+            .get_category_by_name(name)
+            .await?
+            .with_context(|| format!("Category '{}' not found", name))?;
+        db.list_todos_by_category(category.id).await?
+    } else if let Some(name) = &project {
+        let project = db
+            .get_project_by_name(name)
+            .await?
+            .with_context(|| format!("Project '{}' not found", name))?;
+        db.list_todos_by_project(project.id).await?
+    } else {
+        db.list_todos(true).await?
+    };
+    todos.retain(|t| !t.is_completed);
+
+    let changes = match &tz {
+        Some(tz) => plan(todos, time, tz, include_date_only),
+        None => plan(todos, time, &Local, include_date_only),
+    };
+    let target = format!(
+        "{} {}",
+        time.map(|t| t.format("%H:%M").to_string())
+            .unwrap_or_else(|| "date only".to_string()),
+        timezone.as_deref().unwrap_or("local time")
+    );
+
+    if output::is_json() {
+        if !preview && !yes {
+            anyhow::bail!("Pass --yes (or --preview) to normalize with --json");
+        }
+    } else if changes.is_empty() {
+        println!("All matching due dates are already at {}", target);
+        return Ok(());
+    } else {
+        let id_len = ids::id_length(&db, &config).await?;
+        println!("Normalize {} due date(s) to {}:", changes.len(), target);
+        for change in &changes {
+            println!(
+                "  \x1b[90m{}\x1b[0m {}  {} \u{2192} {}",
+                short_id(&change.todo.id, id_len),
+                change.todo.title,
+                change.old,
+                change.new
+            );
+        }
+    }
+
+    let applied = !preview
+        && !changes.is_empty()
+        && (yes || confirm(&mut io::stdin().lock(), "Rewrite these due dates?", false)?);
+    if applied {
+        apply(&db, &changes).await?;
+    }
+
+    if output::is_json() {
+        let changes: Vec<_> = changes
+            .iter()
+            .map(|c| {
+                serde_json::json!({
+                    "id": c.todo.id,
+                    "title": c.todo.title,
+                    "old": c.todo.due_date,
+                    "new": c.due,
+                })
+            })
+            .collect();
+        return output::print_json(&serde_json::json!({
+            "time": time.map(|t| t.format("%H:%M").to_string()),
+            "timezone": timezone,
+            "changes": changes,
+            "applied": applied,
+        }));
+    }
+
+    if applied {
+        println!("\u{2713} Normalized {} due date(s)", changes.len());
+        println!("Run 'todoee undo' to revert them all.");
+    } else if !preview {
+        println!("Cancelled");
+    }
+
+    Ok(())
+}
+
+/// "09:00", or "none" to drop the time and keep only the date
+fn parse_time(value: &str) -> Result<Option<NaiveTime>> {
+    if value.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .map(Some)
+        .map_err(|_| anyhow::anyhow!("Invalid value for --time: expected HH:MM or none"))
+}
+
+/// An IANA timezone name such as "Europe/Berlin"
+fn parse_timezone(name: &str) -> Result<Tz> {
+    name.parse().map_err(|_| {
+        anyhow::anyhow!(
+            "Unknown timezone '{}' (use an IANA name such as Europe/Berlin)",
+            name
+        )
+    })
+}
+
+/// Due dates of `todos` that change when moved to `time` in `tz`. Date-only
+/// due dates are left alone unless `include_date_only` is set.
+fn plan<Z: TimeZone>(
+    todos: Vec<Todo>,
+    time: Option<NaiveTime>,
+    tz: &Z,
+    include_date_only: bool,
+) -> Vec<Change> {
+    todos
+        .into_iter()
+        .filter_map(|todo| {
+            let old = todo.due_date?;
+            if dates::is_date_only(&old) && !include_date_only {
+                return None;
+            }
+            let due = dates::with_due_time(&old, time, tz);
+            (due != old).then(|| Change {
+                old: dates::format_due(&old, tz),
+                new: dates::format_due(&due, tz),
+                todo,
+                due,
+            })
+        })
+        .collect()
+}
+
+/// Rewrite the due dates as one group, so a single `undo` reverts them all
+async fn apply(db: &LocalDb, changes: &[Change]) -> Result<Uuid> {
+    let group_id = Uuid::new_v4();
+    for change in changes {
+        let mut updated = change.todo.clone();
+        let prev = serde_json::to_value(&updated)?;
+        updated.due_date = Some(change.due);
+        updated.updated_at = Utc::now();
+        db.update_todo(&updated).await?;
+
+        let op = Operation::new(
+            OperationType::Update,
+            EntityType::Todo,
+            updated.id,
+            Some(prev),
+            Some(serde_json::to_value(&updated)?),
+        )
+        .in_group(group_id);
+        db.record_operation(&op).await?;
+    }
+    Ok(group_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn due(s: &str) -> DateTime<Utc> {
+        dates::parse(s, &Utc::now()).unwrap().due_date(&Utc)
+    }
+
+    fn todo(title: &str, due_date: &str) -> Todo {
+        let mut todo = Todo::new(title.to_string(), None);
+        todo.due_date = Some(due(due_date));
+        todo
+    }
+
+    #[test]
+    fn test_plan_moves_times_in_timezone() {
+        let berlin = parse_timezone("Europe/Berlin").unwrap();
+        let todos = vec![
+            todo("late", "2026-03-01 23:30"),
+            todo("summer", "2026-07-01 04:15"),
+            todo("already", "2026-03-05 08:00"),
+            todo("date only", "2026-03-02"),
+        ];
+
+        let changes = plan(todos.clone(), parse_time("09:00").unwrap(), &berlin, false);
+        let moved: Vec<_> = changes
+            .iter()
+            .map(|c| (c.todo.title.as_str(), c.due.to_rfc3339(), c.new.as_str()))
+            .collect();
+        assert_eq!(
+            moved,
+            vec![
+                // 23:30 UTC is already 2 March in Berlin
+                (
+                    "late",
+                    "2026-03-02T08:00:00+00:00".to_string(),
+                    "2026-03-02 09:00"
+                ),
+                (
+                    "summer",
+                    "2026-07-01T07:00:00+00:00".to_string(),
+                    "2026-07-01 09:00"
+                ),
+            ]
+        );
+
+        let changes = plan(todos.clone(), parse_time("09:00").unwrap(), &berlin, true);
+        assert_eq!(changes.len(), 3);
+
+        let changes = plan(todos, parse_time("none").unwrap(), &berlin, false);
+        assert_eq!(changes.len(), 3);
+        assert!(changes.iter().all(|c| dates::is_date_only(&c.due)));
+
+        assert!(parse_time("9am").is_err());
+        assert!(parse_timezone("Mars/Olympus").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_is_one_undo_group() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDb::new(&temp_dir.path().join("test.db"))
+            .await
+            .unwrap();
+        db.run_migrations().await.unwrap();
+        let todos = vec![
+            todo("one", "2026-03-01 23:30"),
+            todo("two", "2026-03-04 13:10"),
+        ];
+        for todo in &todos {
+            db.create_todo(todo).await.unwrap();
+        }
+
+        let changes = plan(todos.clone(), parse_time("09:00").unwrap(), &Utc, false);
+        let group_id = apply(&db, &changes).await.unwrap();
+        let one = db.get_todo(todos[0].id).await.unwrap().unwrap();
+        assert_eq!(one.due_date, Some(due("2026-03-01 09:00")));
+
+        assert_eq!(db.undo_operation_group(group_id).await.unwrap().len(), 2);
+        for todo in &todos {
+            let restored = db.get_todo(todo.id).await.unwrap().unwrap();
+            assert_eq!(restored.due_date, todo.due_date);
+        }
+    }
+}
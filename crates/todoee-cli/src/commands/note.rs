@@ -0,0 +1,100 @@
+//! Note commands: timestamped progress notes on a todo.
+
+use std::fs;
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use clap::Subcommand;
+use todoee_core::{Config, LocalDb, TodoNote};
+
+use super::{ids, output};
+
+#[derive(Subcommand, Clone)]
+pub enum NoteCommand {
+    /// Append a note to a todo
+    Add {
+        /// Todo ID (or prefix)
+        id: String,
+        /// Note text
+        body: String,
+    },
+    /// Show a todo's notes, oldest first
+    List {
+        /// Todo ID (or prefix)
+        id: String,
+    },
+}
+
+pub async fn run(cmd: NoteCommand) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    match cmd {
+        NoteCommand::Add { id, body } => add(&db, &config, &id, &body).await,
+        NoteCommand::List { id } => list(&db, &config, &id).await,
+    }
+}
+
+async fn add(db: &LocalDb, config: &Config, id: &str, body: &str) -> Result<()> {
+    let body = body.trim();
+    if body.is_empty() {
+        anyhow::bail!("Note cannot be empty");
+    }
+
+    let todo = ids::resolve_todo(db, config, id).await?;
+    let note = db.add_todo_note(todo.id, body).await?;
+
+    if output::is_json() {
+        return output::print_json(&note);
+    }
+    println!("\u{2713} Added note to: {}", todo.title);
+    Ok(())
+}
+
+async fn list(db: &LocalDb, config: &Config, id: &str) -> Result<()> {
+    let todo = ids::resolve_todo(db, config, id).await?;
+    let notes = db.list_todo_notes(todo.id).await?;
+
+    if output::is_json() {
+        return output::print_json(&notes);
+    }
+
+    if notes.is_empty() {
+        println!("No notes on \"{}\".", todo.title);
+        println!("  Add one with: todoee note add {} \"...\"", id);
+        return Ok(());
+    }
+
+    println!("Notes on \"{}\":", todo.title);
+    for note in &notes {
+        print_note(note);
+    }
+    Ok(())
+}
+
+/// One note as `  2025-01-15 14:30  text`, continuation lines indented
+fn print_note(note: &TodoNote) {
+    let at = note
+        .created_at
+        .with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M");
+    let mut lines = note.body.lines();
+    println!(
+        "  \x1b[90m{}\x1b[0m  {}",
+        at,
+        lines.next().unwrap_or_default()
+    );
+    for line in lines {
+        println!("  {:16}  {}", "", line);
+    }
+}
@@ -0,0 +1,112 @@
+//! Output mode shared by commands: formatted text for people, or JSON for
+//! scripts when `--json` or `TODOEE_OUTPUT=json` is given.
+//!
+//! `-q/--quiet` and `--verbose` set how much text goes with the result:
+//! messages printed with [`say!`] disappear when quiet, and [`detail!`]
+//! lines only appear when verbose. Errors are always printed.
+
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+static JSON: AtomicBool = AtomicBool::new(false);
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// How much commands print besides errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Nothing; the exit code tells whether it worked
+    Quiet,
+    Normal,
+    /// Also what was looked up and where, on stderr
+    Verbose,
+}
+
+impl Verbosity {
+    /// From the `--quiet` and `--verbose` flags; quiet wins
+    pub fn from_flags(quiet: bool, verbose: bool) -> Self {
+        if quiet {
+            Self::Quiet
+        } else if verbose {
+            Self::Verbose
+        } else {
+            Self::Normal
+        }
+    }
+}
+
+/// Environment variable that selects the output format ("json" or "text").
+pub const OUTPUT_ENV: &str = "TODOEE_OUTPUT";
+
+/// Pick the output format for this run. The `--json` flag wins over the
+/// environment.
+pub fn init(json_flag: bool, verbosity: Verbosity) {
+    let from_env = std::env::var(OUTPUT_ENV).is_ok_and(|v| v.eq_ignore_ascii_case("json"));
+    JSON.store(json_flag || from_env, Ordering::Relaxed);
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// How much to print besides errors.
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        _ => Verbosity::Verbose,
+    }
+}
+
+/// Whether only errors should be printed.
+pub fn is_quiet() -> bool {
+    verbosity() == Verbosity::Quiet
+}
+
+/// `println!` unless `--quiet` is given.
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if !$crate::commands::output::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
+pub(crate) use say;
+
+/// `eprintln!` only when `--verbose` is given.
+macro_rules! detail {
+    ($($arg:tt)*) => {
+        if $crate::commands::output::verbosity() == $crate::commands::output::Verbosity::Verbose {
+            eprintln!($($arg)*);
+        }
+    };
+}
+pub(crate) use detail;
+
+/// Whether commands should print JSON instead of formatted text.
+pub fn is_json() -> bool {
+    JSON.load(Ordering::Relaxed)
+}
+
+/// Print `value` to stdout as pretty JSON.
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<()> {
+    let json = serde_json::to_string_pretty(value).context("Failed to serialize output")?;
+    println!("{}", json);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_selects_json() {
+        init(true, Verbosity::Normal);
+        assert!(is_json());
+    }
+
+    #[test]
+    fn test_quiet_wins_over_verbose() {
+        assert_eq!(Verbosity::from_flags(true, true), Verbosity::Quiet);
+        assert_eq!(Verbosity::from_flags(false, true), Verbosity::Verbose);
+        assert_eq!(Verbosity::from_flags(false, false), Verbosity::Normal);
+    }
+}
@@ -0,0 +1,131 @@
+//! Profiles: separate configs and databases, such as work and personal.
+//!
+//! The default profile lives directly in the config directory; the others
+//! under `profiles/<name>/`. `--profile` or `TODOEE_PROFILE` picks one for a
+//! single run, and `todoee profile switch` changes the one used otherwise.
+
+use std::fs;
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use todoee_core::Config;
+use todoee_core::config::{DEFAULT_PROFILE, PROFILE_ENV, validate_profile_name};
+
+use super::output;
+
+#[derive(Subcommand, Clone)]
+pub enum ProfileCommand {
+    /// List profiles, marking the one in use
+    List,
+    /// Create an empty profile with default settings
+    Create {
+        /// Profile name (letters, digits, '-' and '_')
+        name: String,
+    },
+    /// Use a profile from now on when --profile is not given
+    Switch {
+        /// Profile name, or "default"
+        name: String,
+    },
+}
+
+pub fn run(cmd: ProfileCommand) -> Result<()> {
+    match cmd {
+        ProfileCommand::List => list(),
+        ProfileCommand::Create { name } => create(&name),
+        ProfileCommand::Switch { name } => switch(&name),
+    }
+}
+
+/// Fail early when the profile in use was never created, so a typo doesn't
+/// quietly start an empty database
+pub fn check_exists(profile: Option<String>) -> Result<()> {
+    if let Some(name) = profile
+        && name != DEFAULT_PROFILE
+        && !Config::profile_dir(&name)?.exists()
+    {
+        anyhow::bail!(
+            "Profile '{}' does not exist. Create it with: todoee profile create {}",
+            name,
+            name
+        );
+    }
+    Ok(())
+}
+
+fn list() -> Result<()> {
+    let current = Config::profile().unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+    let mut names = vec![DEFAULT_PROFILE.to_string()];
+    names.extend(Config::list_profiles()?);
+
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "current": current,
+            "profiles": names
+                .iter()
+                .map(|name| Ok(serde_json::json!({
+                    "name": name,
+                    "path": Config::profile_dir(name)?,
+                })))
+                .collect::<Result<Vec<_>>>()?,
+        }));
+    }
+
+    for name in &names {
+        if *name == current {
+            println!("\x1b[32m* {}\x1b[0m", name);
+        } else {
+            println!("  {}", name);
+        }
+    }
+    if std::env::var(PROFILE_ENV).is_ok_and(|v| !v.is_empty()) {
+        println!("\x1b[90m(selected by {})\x1b[0m", PROFILE_ENV);
+    }
+    Ok(())
+}
+
+fn create(name: &str) -> Result<()> {
+    validate_profile_name(name)?;
+    if name == DEFAULT_PROFILE {
+        anyhow::bail!("The default profile always exists");
+    }
+    let dir = Config::profile_dir(name)?;
+    if dir.exists() {
+        anyhow::bail!("Profile '{}' already exists", name);
+    }
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create profile directory: {}", dir.display()))?;
+
+    // Private like the default config directory
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o700))
+            .with_context(|| format!("Failed to set permissions on {}", dir.display()))?;
+    }
+
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({ "name": name, "path": dir }));
+    }
+    output::say!("\u{2713} Created profile '{}'", name);
+    output::say!("  Use it: todoee profile switch {}", name);
+    output::detail!("Directory: {}", dir.display());
+    Ok(())
+}
+
+fn switch(name: &str) -> Result<()> {
+    check_exists(Some(name.to_string()))?;
+    Config::set_active_profile(name)?;
+
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({ "current": name }));
+    }
+    output::say!("\u{2713} Switched to profile '{}'", name);
+    if std::env::var(PROFILE_ENV).is_ok_and(|v| !v.is_empty() && v != name) {
+        output::say!(
+            "  \x1b[33mNote:\x1b[0m {} is set and still wins in this shell",
+            PROFILE_ENV
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,158 @@
+//! Project commands for grouping todos above categories.
+
+use std::fs;
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use todoee_core::{Config, LocalDb, Project};
+use uuid::Uuid;
+
+use super::output;
+
+#[derive(Subcommand, Clone)]
+pub enum ProjectCommand {
+    /// Create a project
+    Create {
+        /// Project name
+        name: String,
+        /// Display color (e.g. "#3b82f6")
+        #[arg(long)]
+        color: Option<String>,
+    },
+    /// List projects with their progress
+    List {
+        /// Include archived projects
+        #[arg(short, long)]
+        all: bool,
+    },
+    /// Archive a project, hiding it from lists (its todos are kept)
+    Archive {
+        /// Project name
+        name: String,
+    },
+}
+
+pub async fn run(cmd: ProjectCommand) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    match cmd {
+        ProjectCommand::Create { name, color } => create(&db, &name, color).await,
+        ProjectCommand::List { all } => list(&db, all).await,
+        ProjectCommand::Archive { name } => archive(&db, &name).await,
+    }
+}
+
+async fn create(db: &LocalDb, name: &str, color: Option<String>) -> Result<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("Project name cannot be empty");
+    }
+
+    let mut project = Project::new(Uuid::nil(), name.to_string());
+    project.color = color;
+    db.create_project(&project).await?;
+
+    println!("\u{2713} Created project: {}", project.name);
+    println!(
+        "  Assign todos with: todoee edit <id> --project \"{}\"",
+        project.name
+    );
+
+    Ok(())
+}
+
+async fn list(db: &LocalDb, all: bool) -> Result<()> {
+    let progress = db.project_progress().await?;
+    let archived: Vec<Project> = if all {
+        db.list_projects(true)
+            .await?
+            .into_iter()
+            .filter(Project::is_archived)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "projects": progress,
+            "archived": archived,
+        }));
+    }
+
+    if progress.is_empty() && archived.is_empty() {
+        println!("No projects. Use 'todoee project create' to create one!");
+        return Ok(());
+    }
+
+    let width = progress
+        .iter()
+        .map(|p| p.project.name.chars().count())
+        .chain(archived.iter().map(|p| p.name.chars().count()))
+        .max()
+        .unwrap_or(0);
+
+    for p in &progress {
+        println!(
+            "{:<width$}  \x1b[32m{}\x1b[0m {}/{}",
+            p.project.name,
+            progress_bar(p.ratio(), 20),
+            p.completed,
+            p.total,
+        );
+    }
+    for project in &archived {
+        println!("{:<width$}  \x1b[90m(archived)\x1b[0m", project.name);
+    }
+
+    Ok(())
+}
+
+async fn archive(db: &LocalDb, name: &str) -> Result<()> {
+    let mut project = db
+        .get_project_by_name(name)
+        .await?
+        .with_context(|| format!("Project '{}' not found", name))?;
+
+    if project.is_archived() {
+        println!("Project '{}' is already archived.", project.name);
+        return Ok(());
+    }
+
+    project.archive();
+    db.update_project(&project).await?;
+
+    println!("\u{2713} Archived project: {}", project.name);
+
+    Ok(())
+}
+
+/// Render a `width`-cell bar filled to `ratio`, e.g. "█████░░░░░".
+pub fn progress_bar(ratio: f64, width: usize) -> String {
+    let filled = (ratio.clamp(0.0, 1.0) * width as f64).round() as usize;
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(progress_bar(0.0, 4), "░░░░");
+        assert_eq!(progress_bar(0.5, 4), "██░░");
+        assert_eq!(progress_bar(1.0, 4), "████");
+        assert_eq!(progress_bar(2.0, 4), "████");
+    }
+}
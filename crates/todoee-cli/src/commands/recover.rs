@@ -0,0 +1,225 @@
+//! Startup check of the local database: a daily backup while it is healthy,
+//! and a guided recovery when it is damaged instead of a crash.
+
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{Local, Utc};
+use serde::Serialize;
+use todoee_core::db::backup::{self, Backup, Health};
+use todoee_core::{Attachment, Category, Config, LocalDb, Project, Todo, TodoNote};
+
+use super::config::{confirm, prompt};
+use super::import::{self, ImportMode};
+use super::output;
+
+/// Readable data from a damaged database, in the JSON export format so it
+/// can be imported again
+#[derive(Serialize)]
+struct Salvage {
+    version: String,
+    exported_at: String,
+    todos: Vec<Todo>,
+    categories: Vec<Category>,
+    projects: Vec<Project>,
+    notes: Vec<TodoNote>,
+    attachments: Vec<Attachment>,
+}
+
+/// Check the local database before a command uses it. A healthy database is
+/// backed up once a day; a damaged one leads to [`recover`].
+pub async fn check_startup() -> Result<()> {
+    // A broken config is reported by the command itself
+    let Ok(config) = Config::load() else {
+        return Ok(());
+    };
+    let db_path = config.local_db_path()?;
+
+    match backup::check(&db_path).await {
+        Health::Ok => {
+            if db_path.exists() && !Config::is_demo() {
+                backup_if_due(&db_path, config.database.backups as usize).await;
+            }
+            Ok(())
+        }
+        Health::Damaged(reason) => recover(&db_path, &reason).await,
+    }
+}
+
+/// A failed backup shouldn't stop the command, only be mentioned
+async fn backup_if_due(db_path: &Path, keep: usize) {
+    let result = match LocalDb::new(db_path).await {
+        Ok(db) => backup::backup_if_due(&db, db_path, keep, Utc::now()).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        eprintln!("\x1b[33mwarning:\x1b[0m automatic backup failed: {:#}", e);
+    }
+}
+
+/// Ask how to get out of a damaged database: restore the latest backup, or
+/// salvage what is readable into a fresh database. Nothing is deleted; the
+/// damaged file is renamed and kept.
+async fn recover(db_path: &Path, reason: &str) -> Result<()> {
+    if output::is_json() || !io::stdin().is_terminal() {
+        anyhow::bail!(
+            "The local database {} is damaged ({}). Run todoee in a terminal to restore a backup or salvage what is readable.",
+            db_path.display(),
+            reason
+        );
+    }
+
+    println!("\x1b[31m\u{2717}\x1b[0m The local database is damaged and can't be used safely.");
+    println!("  File:   {}", db_path.display());
+    println!("  Reason: {}", reason);
+    println!();
+
+    let latest = backup::list(db_path).into_iter().next();
+    match &latest {
+        Some(backup) => println!(
+            "  1) Restore the backup from {} (later changes are lost)",
+            backup
+                .created_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+        ),
+        None => println!("  1) Restore a backup (none found)"),
+    }
+    println!("  2) Salvage: save the readable todos to a file and start a fresh database");
+    println!("  3) Quit and leave the file as it is");
+    println!();
+
+    let default = if latest.is_some() { "1" } else { "2" };
+    let stdin = io::stdin();
+    let choice = prompt(&mut stdin.lock(), "Choose", default)?;
+    match (choice.as_deref().unwrap_or(default), latest) {
+        ("1", Some(backup)) => restore(db_path, &backup),
+        ("1", None) => anyhow::bail!(
+            "There is no backup in {}",
+            backup::backup_dir(db_path).display()
+        ),
+        ("2", _) => salvage(db_path).await,
+        _ => anyhow::bail!("Left {} untouched", db_path.display()),
+    }
+}
+
+fn restore(db_path: &Path, backup: &Backup) -> Result<()> {
+    let moved = backup::restore(db_path, &backup.path, Utc::now())?;
+    println!(
+        "\u{2713} Restored the backup from {}",
+        backup
+            .created_at
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M")
+    );
+    println!("  The damaged file was kept as {}", moved.display());
+    println!();
+    Ok(())
+}
+
+async fn salvage(db_path: &Path) -> Result<()> {
+    let (data, unreadable) = read_salvage(db_path).await;
+    let file = db_path.with_file_name(format!(
+        "salvage-{}.json",
+        Local::now().format("%Y%m%d_%H%M%S")
+    ));
+    let json = serde_json::to_string_pretty(&data).context("Failed to serialize salvage")?;
+    fs::write(&file, json)
+        .with_context(|| format!("Failed to write salvage file: {}", file.display()))?;
+
+    println!(
+        "\u{2713} Saved {} todos, {} categories, {} projects and {} notes to {}",
+        data.todos.len(),
+        data.categories.len(),
+        data.projects.len(),
+        data.notes.len(),
+        file.display()
+    );
+    if !unreadable.is_empty() {
+        println!("  Couldn't read: {}", unreadable.join(", "));
+    }
+
+    let stdin = io::stdin();
+    if !confirm(
+        &mut stdin.lock(),
+        "Move the damaged database aside and start fresh with these todos?",
+        true,
+    )? {
+        anyhow::bail!(
+            "Left {} in place. The salvaged todos are in {}",
+            db_path.display(),
+            file.display()
+        );
+    }
+
+    let moved = backup::set_aside(db_path, Utc::now())?;
+    let db = LocalDb::new(db_path).await?;
+    db.run_migrations().await?;
+    let summary = import::import_todos(&db, &file, ImportMode::Merge).await?;
+    db.insert_todo_notes(&data.notes).await?;
+    println!(
+        "\u{2713} Started a fresh database with {} salvaged todos",
+        summary.todos
+    );
+    println!("  The damaged file was kept as {}", moved.display());
+    println!();
+    Ok(())
+}
+
+/// Everything that can still be read, and the parts that couldn't
+async fn read_salvage(db_path: &Path) -> (Salvage, Vec<&'static str>) {
+    let mut data = Salvage {
+        version: "1.0".to_string(),
+        exported_at: Utc::now().to_rfc3339(),
+        todos: Vec::new(),
+        categories: Vec::new(),
+        projects: Vec::new(),
+        notes: Vec::new(),
+        attachments: Vec::new(),
+    };
+
+    // A damaged WAL can keep the normal read-only open from working
+    let db = match LocalDb::open_read_only(db_path, false).await {
+        Ok(db) => db,
+        Err(_) => match LocalDb::open_read_only(db_path, true).await {
+            Ok(db) => db,
+            Err(_) => return (data, vec!["the whole file"]),
+        },
+    };
+
+    let mut unreadable = Vec::new();
+    match db.readable_todos().await {
+        Ok(todos) => data.todos = todos,
+        Err(_) => unreadable.push("todos"),
+    }
+    match db.list_categories().await {
+        Ok(categories) => data.categories = categories,
+        Err(_) => unreadable.push("categories"),
+    }
+    match db.list_projects(true).await {
+        Ok(projects) => data.projects = projects,
+        Err(_) => unreadable.push("projects"),
+    }
+    // Per todo, so the notes don't depend on reading the todos table again
+    for todo in &data.todos {
+        match db.list_todo_notes(todo.id).await {
+            Ok(notes) => data.notes.extend(notes),
+            Err(_) => {
+                unreadable.push("notes");
+                break;
+            }
+        }
+    }
+    for todo in &data.todos {
+        match db.list_attachments(todo.id).await {
+            Ok(attachments) => data.attachments.extend(attachments),
+            Err(_) => {
+                unreadable.push("attachments");
+                break;
+            }
+        }
+    }
+    (data, unreadable)
+}
@@ -2,6 +2,8 @@ use anyhow::{Context, Result};
 use std::fs;
 use todoee_core::{Config, EntityType, LocalDb, OperationType, Todo};
 
+use super::output;
+
 pub async fn run() -> Result<()> {
     let config = Config::load().context("Failed to load configuration")?;
     let db_path = config.local_db_path()?;
@@ -15,19 +17,39 @@ pub async fn run() -> Result<()> {
 
     let db = LocalDb::new(&db_path).await?;
     db.run_migrations().await?;
+    redo_last(&db).await
+}
 
+/// Redo the change undone last, unless a new change was made since
+pub async fn redo_last(db: &LocalDb) -> Result<()> {
     let Some(op) = db.get_last_redoable_operation().await? else {
-        println!("Nothing to redo");
+        output::say!("Nothing to redo");
         return Ok(());
     };
 
+    if let Some(group_id) = op.group_id {
+        let ops = db.redo_operation_group(group_id).await?;
+        output::say!("\u{21aa} Redone batch of {} changes:", ops.len());
+        for op in &ops {
+            output::say!("  {} {}", op.operation_type, super::undo::op_title(op));
+        }
+        return Ok(());
+    }
+
     match (op.operation_type, op.entity_type) {
         (OperationType::Create, EntityType::Todo) => {
             if let Some(new) = &op.new_state {
                 let todo: Todo = serde_json::from_value(new.clone())?;
-                db.create_todo(&todo).await?;
-                println!("\u{21aa} Redone create: \"{}\"", todo.title);
+                db.restore_todo(&todo).await?;
+                output::say!("\u{21aa} Redone create: \"{}\"", todo.title);
+            }
+        }
+        (OperationType::BatchCreate, EntityType::Todo) => {
+            let todos = op.batch_todos();
+            for todo in &todos {
+                db.restore_todo(todo).await?;
             }
+            output::say!("\u{21aa} Redone batch add: {} todos", todos.len());
         }
         (OperationType::Delete, EntityType::Todo) => {
             db.delete_todo(op.entity_id).await?;
@@ -37,27 +59,27 @@ pub async fn run() -> Result<()> {
                 .and_then(|s| s.get("title"))
                 .and_then(|t| t.as_str())
                 .unwrap_or("todo");
-            println!("\u{21aa} Redone delete: \"{}\"", title);
+            output::say!("\u{21aa} Redone delete: \"{}\"", title);
         }
         (OperationType::Update, EntityType::Todo) => {
             if let Some(new) = &op.new_state {
                 let todo: Todo = serde_json::from_value(new.clone())?;
                 db.update_todo(&todo).await?;
-                println!("\u{21aa} Redone edit: \"{}\"", todo.title);
+                output::say!("\u{21aa} Redone edit: \"{}\"", todo.title);
             }
         }
         (OperationType::Complete, EntityType::Todo) => {
             if let Some(mut todo) = db.get_todo(op.entity_id).await? {
                 todo.mark_complete();
                 db.update_todo(&todo).await?;
-                println!("\u{21aa} Redone complete: \"{}\" is done again", todo.title);
+                output::say!("\u{21aa} Redone complete: \"{}\" is done again", todo.title);
             }
         }
         (OperationType::Uncomplete, EntityType::Todo) => {
             if let Some(mut todo) = db.get_todo(op.entity_id).await? {
                 todo.mark_incomplete();
                 db.update_todo(&todo).await?;
-                println!(
+                output::say!(
                     "\u{21aa} Redone uncomplete: \"{}\" is pending again",
                     todo.title
                 );
@@ -71,11 +93,67 @@ pub async fn run() -> Result<()> {
                 .and_then(|s| s.get("title"))
                 .and_then(|t| t.as_str())
                 .unwrap_or("todo");
-            println!("\u{21aa} Redone stash: \"{}\" stashed again", title);
+            output::say!("\u{21aa} Redone stash: \"{}\" stashed again", title);
         }
-        _ => println!("Cannot redo this operation type"),
+        _ => output::say!("Cannot redo this operation type"),
     }
 
     db.mark_operation_redone(op.id).await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::undo::undo_last;
+    use todoee_core::Operation;
+
+    /// Rename a todo and record the change
+    async fn rename(db: &LocalDb, todo: &mut Todo, title: &str) {
+        let before = serde_json::to_value(&*todo).ok();
+        todo.title = title.to_string();
+        db.update_todo(todo).await.unwrap();
+        let op = Operation::new(
+            OperationType::Update,
+            EntityType::Todo,
+            todo.id,
+            before,
+            serde_json::to_value(&*todo).ok(),
+        );
+        db.record_operation(&op).await.unwrap();
+    }
+
+    async fn title(db: &LocalDb, todo: &Todo) -> String {
+        db.get_todo(todo.id).await.unwrap().unwrap().title
+    }
+
+    #[tokio::test]
+    async fn test_new_change_after_undo_drops_redo() {
+        let db = LocalDb::new_in_memory().await.unwrap();
+        db.run_migrations().await.unwrap();
+        let mut todo = Todo::new("Draft".to_string(), None);
+        db.create_todo(&todo).await.unwrap();
+
+        rename(&db, &mut todo, "First").await;
+        rename(&db, &mut todo, "Second").await;
+        undo_last(&db).await.unwrap();
+        undo_last(&db).await.unwrap();
+        assert_eq!(title(&db, &todo).await, "Draft");
+
+        // Redo brings back the change undone last
+        redo_last(&db).await.unwrap();
+        assert_eq!(title(&db, &todo).await, "First");
+
+        // A new change replaces "Second" as what comes next
+        todo = db.get_todo(todo.id).await.unwrap().unwrap();
+        rename(&db, &mut todo, "Other").await;
+        assert!(db.get_last_redoable_operation().await.unwrap().is_none());
+        redo_last(&db).await.unwrap();
+        assert_eq!(title(&db, &todo).await, "Other");
+
+        undo_last(&db).await.unwrap();
+        assert_eq!(title(&db, &todo).await, "First");
+        redo_last(&db).await.unwrap();
+        assert_eq!(title(&db, &todo).await, "Other");
+    }
+}
@@ -0,0 +1,201 @@
+//! Remind commands: set, clear and list todo reminders.
+
+use std::fs;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use clap::Subcommand;
+use todoee_core::{
+    Config, DateInput, EntityType, LocalDb, Operation, OperationType, SyncStatus, Todo, dates,
+    short_id,
+};
+
+use super::{ids, output};
+
+#[derive(Subcommand, Clone)]
+pub enum RemindCommand {
+    /// Set or move a todo's reminder
+    Set {
+        /// Todo ID (or prefix)
+        id: String,
+        /// When, e.g. "tomorrow 9am", "in 30 minutes" or "2026-03-01 14:30"
+        when: String,
+    },
+    /// Remove a todo's reminder
+    Clear {
+        /// Todo ID (or prefix)
+        id: String,
+    },
+    /// Show upcoming reminders, soonest first
+    List,
+}
+
+pub async fn run(cmd: RemindCommand) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    match cmd {
+        RemindCommand::Set { id, when } => {
+            let at = parse_time(&when, &config.display.date_input(), Utc::now())?;
+            set(&db, &config, &id, Some(at)).await
+        }
+        RemindCommand::Clear { id } => set(&db, &config, &id, None).await,
+        RemindCommand::List => list(&db, &config).await,
+    }
+}
+
+/// Parse a reminder time such as "tomorrow 9am", refusing times that are
+/// not after `now`
+pub fn parse_time(when: &str, date_input: &DateInput, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let at = dates::parse_reminder(when, date_input).with_context(|| {
+        format!(
+            "Could not understand reminder time '{}'. Try 'tomorrow 9am', 'in 30 minutes' or '2026-03-01 14:30'",
+            when
+        )
+    })?;
+    if at <= now {
+        anyhow::bail!(
+            "Reminder time '{}' is in the past ({})",
+            when,
+            at.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+        );
+    }
+    Ok(at)
+}
+
+/// Set the reminder of the pending todo `id` to `at`, or clear it, recorded for undo
+async fn set(db: &LocalDb, config: &Config, id: &str, at: Option<DateTime<Utc>>) -> Result<()> {
+    let mut todo = ids::resolve_todo(db, config, id).await?;
+    if todo.is_completed {
+        anyhow::bail!("'{}' is completed; reopen it to set a reminder", todo.title);
+    }
+
+    if at.is_none() && todo.reminder_at.is_none() {
+        if output::is_json() {
+            return output::print_json(&todo);
+        }
+        output::say!("'{}' has no reminder.", todo.title);
+        return Ok(());
+    }
+
+    let prev_state = serde_json::to_value(&todo)?;
+    todo.reminder_at = at;
+    todo.updated_at = Utc::now();
+    todo.sync_status = SyncStatus::Pending;
+    db.update_todo(&todo).await?;
+
+    let op = Operation::new(
+        OperationType::Update,
+        EntityType::Todo,
+        todo.id,
+        Some(prev_state),
+        Some(serde_json::to_value(&todo)?),
+    );
+    db.record_operation(&op).await?;
+
+    if output::is_json() {
+        return output::print_json(&todo);
+    }
+    match at {
+        Some(at) => {
+            output::say!("\u{23F0} Reminder set: {}", todo.title);
+            output::say!(
+                "  Reminder: {}",
+                at.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+            );
+        }
+        None => output::say!("\u{2713} Reminder cleared: {}", todo.title),
+    }
+    let id_len = ids::id_length(db, config).await?;
+    output::say!("  ID: {}", short_id(&todo.id, id_len));
+    output::detail!("Full ID: {}", todo.id);
+    Ok(())
+}
+
+async fn list(db: &LocalDb, config: &Config) -> Result<()> {
+    let todos = db.list_todos(true).await?;
+    let reminders = upcoming(&todos, Utc::now());
+
+    if output::is_json() {
+        return output::print_json(&reminders);
+    }
+
+    if reminders.is_empty() {
+        println!("No upcoming reminders.");
+        println!("  Set one with: todoee remind set <id> \"tomorrow 9am\"");
+        return Ok(());
+    }
+
+    let id_len = ids::id_length(db, config).await?;
+    for todo in reminders {
+        let at = todo.reminder_at.expect("upcoming reminders have a time");
+        println!(
+            "  \x1b[36m{}\x1b[0m  {} \x1b[90m[{}]\x1b[0m",
+            at.with_timezone(&Local).format("%a %Y-%m-%d %H:%M"),
+            todo.display_title(),
+            short_id(&todo.id, id_len)
+        );
+    }
+    Ok(())
+}
+
+/// Pending todos with a reminder after `now`, soonest first
+fn upcoming(todos: &[Todo], now: DateTime<Utc>) -> Vec<&Todo> {
+    let mut reminders: Vec<&Todo> = todos
+        .iter()
+        .filter(|t| !t.is_completed && t.reminder_at.is_some_and(|at| at > now))
+        .collect();
+    reminders.sort_by_key(|t| t.reminder_at);
+    reminders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_upcoming_reminders_in_order() {
+        let now = Utc::now();
+        let todo = |title: &str, offset: Option<Duration>| {
+            let mut todo = Todo::new(title.to_string(), None);
+            todo.reminder_at = offset.map(|d| now + d);
+            todo
+        };
+        let mut done = todo("Done", Some(Duration::minutes(5)));
+        done.mark_complete();
+        let todos = vec![
+            todo("Tomorrow", Some(Duration::days(1))),
+            todo("Past", Some(-Duration::hours(1))),
+            todo("None", None),
+            todo("Soon", Some(Duration::minutes(10))),
+            done,
+        ];
+
+        let titles: Vec<&str> = upcoming(&todos, now)
+            .iter()
+            .map(|t| t.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Soon", "Tomorrow"]);
+    }
+
+    #[test]
+    fn test_parse_time_rejects_the_past() {
+        let date_input = DateInput::default();
+        let now = Utc::now();
+        let at = parse_time("in 30 minutes", &date_input, now).unwrap();
+        assert!(at > now);
+        assert!(parse_time("2001-01-01 09:00", &date_input, now).is_err());
+        assert!(parse_time("whenever", &date_input, now).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use anyhow::{Context, Result};
+use std::fs;
+use todoee_core::{Config, EntityType, IdMatch, LocalDb, Operation, OperationType, short_id};
+
+use super::{ids, output};
+
+/// Mark a completed todo as incomplete again
+pub async fn run(id: String) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    match ids::resolve(&db, &config, &id).await? {
+        IdMatch::NotFound => {
+            eprintln!("No todo found matching '{}'", id);
+            eprintln!("Hint: Use 'todoee list --all' to see all todos including completed ones.");
+            anyhow::bail!("Todo not found");
+        }
+        IdMatch::Unique(mut todo) => {
+            if !todo.is_completed {
+                if output::is_json() {
+                    return output::print_json(&todo);
+                }
+                output::say!("Todo '{}' is not completed.", todo.title);
+                return Ok(());
+            }
+
+            let prev_state = serde_json::to_value(&todo)?;
+
+            // Leaves the todo pending, so the next sync pushes it
+            todo.mark_incomplete();
+            db.update_todo(&todo).await?;
+
+            let new_state = serde_json::to_value(&todo)?;
+            let op = Operation::new(
+                OperationType::Uncomplete,
+                EntityType::Todo,
+                todo.id,
+                Some(prev_state),
+                Some(new_state),
+            );
+            db.record_operation(&op).await?;
+
+            if output::is_json() {
+                return output::print_json(&todo);
+            }
+            output::say!("\u{21ba} Reopened: {}", todo.title);
+            let id_len = ids::id_length(&db, &config).await?;
+            output::say!("  ID: {}", short_id(&todo.id, id_len));
+            output::detail!("Full ID: {}", todo.id);
+        }
+        IdMatch::Ambiguous(matches) => {
+            eprintln!("Multiple todos match '{}'. Please be more specific:", id);
+            eprintln!();
+            let id_len = ids::match_length(&matches);
+            for todo in &matches {
+                let status = if todo.is_completed { "[x]" } else { "[ ]" };
+                eprintln!(
+                    "  {} {} [{}]",
+                    status,
+                    todo.title,
+                    short_id(&todo.id, id_len)
+                );
+            }
+            anyhow::bail!("Ambiguous ID - provide more characters");
+        }
+    }
+
+    Ok(())
+}
@@ -1,9 +1,13 @@
-//! Fuzzy search command for finding todos by text.
+//! Search command: fuzzy matching on text, or a `:` filter query.
 
 use std::fs;
 
 use anyhow::{Context, Result};
-use todoee_core::{Config, LocalDb, Priority, Todo};
+use chrono::Local;
+use todoee_core::{Config, Filter, LocalDb, Priority, Todo, short_id};
+
+use super::context::ActiveContext;
+use super::{ids, output};
 
 /// Run fuzzy search on todos.
 pub async fn run(query: &str) -> Result<()> {
@@ -19,25 +23,54 @@ pub async fn run(query: &str) -> Result<()> {
 
     let db = LocalDb::new(&db_path).await?;
     db.run_migrations().await?;
+    let id_len = ids::id_length(&db, &config).await?;
+
+    // A ':' query is a filter, as in `todoee list`; anything else is fuzzy
+    // matched against every todo (completed ones included)
+    let filter = match query.strip_prefix(':') {
+        Some(filter) => Some(
+            filter
+                .parse::<Filter>()
+                .with_context(|| format!("Invalid query \"{}\"", filter))?,
+        ),
+        None => None,
+    };
+    let mut all_todos = match &filter {
+        Some(filter) => db.list_todos_filtered(filter, false, &Local::now()).await?,
+        None => db.list_todos(false).await?,
+    };
+    let context = ActiveContext::load(&db, &config).await?;
+    if let Some(context) = &context {
+        context.retain(&mut all_todos);
+    }
 
-    // Get all todos for fuzzy matching (include completed)
-    let all_todos = db.list_todos(false).await?;
-
-    // Fuzzy match
-    let query_lower = query.to_lowercase();
-    let mut matches: Vec<(&Todo, i32)> = all_todos
-        .iter()
-        .filter_map(|todo| {
-            let score = fuzzy_score(&todo.title.to_lowercase(), &query_lower);
-            if score > 0 { Some((todo, score)) } else { None }
-        })
-        .collect();
+    let mut matches: Vec<(&Todo, i32)> = match filter {
+        Some(_) => all_todos.iter().map(|todo| (todo, 0)).collect(),
+        None => {
+            let query_lower = query.to_lowercase();
+            all_todos
+                .iter()
+                .filter_map(|todo| {
+                    let score = fuzzy_score(&todo.title.to_lowercase(), &query_lower);
+                    if score > 0 { Some((todo, score)) } else { None }
+                })
+                .collect()
+        }
+    };
 
     // Sort by score descending
-    matches.sort_by(|a, b| b.1.cmp(&a.1));
+    matches.sort_by_key(|m| std::cmp::Reverse(m.1));
+
+    if output::is_json() {
+        let todos: Vec<&Todo> = matches.iter().take(20).map(|(todo, _)| *todo).collect();
+        return output::print_json(&todos);
+    }
 
     if matches.is_empty() {
-        println!("No matches for \"{}\"", query);
+        match &context {
+            Some(context) => println!("No matches for \"{}\" in context '{}'", query, context.name),
+            None => println!("No matches for \"{}\"", query),
+        }
         return Ok(());
     }
 
@@ -55,12 +88,13 @@ pub async fn run(query: &str) -> Result<()> {
         };
 
         let pri = match todo.priority {
+            Priority::Urgent => "\x1b[1;97;41m!!!\x1b[0m",
             Priority::High => "\x1b[31m!!!\x1b[0m",
             Priority::Medium => "\x1b[33m!! \x1b[0m",
             Priority::Low => "\x1b[90m!  \x1b[0m",
         };
 
-        let id = &todo.id.to_string()[..8];
+        let id = short_id(&todo.id, id_len);
 
         // Highlight matching parts
         let highlighted = highlight_match(&todo.title, query);
@@ -71,7 +105,7 @@ pub async fn run(query: &str) -> Result<()> {
 }
 
 /// Simple fuzzy scoring - higher is better match.
-fn fuzzy_score(haystack: &str, needle: &str) -> i32 {
+pub fn fuzzy_score(haystack: &str, needle: &str) -> i32 {
     // Exact substring match is best
     if haystack.contains(needle) {
         return 100;
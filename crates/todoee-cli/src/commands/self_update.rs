@@ -0,0 +1,225 @@
+//! `todoee self-update` and the weekly new-version notice.
+
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use todoee_core::update::{self, CURRENT_VERSION, Release, UpdateCheck};
+use todoee_core::{Config, net};
+
+use super::config::confirm;
+use super::output;
+
+/// Timeout for downloading a release
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Timeout for the weekly check, so a slow network barely delays a command
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+pub async fn run(check_only: bool, yes: bool) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    if !config.updates.self_update && !check_only {
+        anyhow::bail!(
+            "Self-update is turned off (updates.self_update = false). Update todoee with the package manager you installed it with."
+        );
+    }
+
+    let client = net::http_client(&config.network, DOWNLOAD_TIMEOUT)?;
+    let release = update::latest_release(&client)
+        .await
+        .context("Failed to look up the latest release")?;
+    record_check(&release);
+    let latest = release.version().to_string();
+    let newer = update::is_newer(&latest, CURRENT_VERSION);
+
+    if check_only || !newer {
+        if output::is_json() {
+            return output::print_json(&serde_json::json!({
+                "current": CURRENT_VERSION,
+                "latest": latest,
+                "update_available": newer,
+                "url": release.html_url,
+            }));
+        }
+        if newer {
+            println!(
+                "New version available: {} (installed: {})",
+                latest, CURRENT_VERSION
+            );
+            println!("  {}", release.html_url);
+        } else {
+            println!("\u{2713} todoee {} is up to date", CURRENT_VERSION);
+        }
+        return Ok(());
+    }
+
+    if !update::can_self_update() {
+        anyhow::bail!(
+            "This build of todoee can't install updates (it was built without TODOEE_RELEASE_PUBLIC_KEY). Download {} from {} or use your package manager.",
+            latest,
+            release.html_url
+        );
+    }
+
+    let name = update::asset_name();
+    let (Some(binary), Some(signature)) = (
+        release.asset(&name),
+        release.asset(&format!("{}.sig", name)),
+    ) else {
+        anyhow::bail!(
+            "Release {} has no signed binary for this platform ({}). See {}",
+            latest,
+            name,
+            release.html_url
+        );
+    };
+
+    if !yes && !output::is_json() {
+        let stdin = io::stdin();
+        if !confirm(
+            &mut stdin.lock(),
+            &format!("Update todoee {} -> {}?", CURRENT_VERSION, latest),
+            true,
+        )? {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    if !output::is_json() {
+        println!("Downloading {}...", name);
+    }
+    let signature = update::download(&client, signature).await?;
+    let binary = update::download(&client, binary).await?;
+    update::verify(&binary, &signature)?;
+
+    let exe = std::env::current_exe().context("Failed to locate the running todoee binary")?;
+    replace_exe(&exe, &binary)?;
+
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({
+            "current": CURRENT_VERSION,
+            "latest": latest,
+            "updated": true,
+            "path": exe,
+        }));
+    }
+    println!(
+        "\u{2713} Updated todoee {} -> {} ({})",
+        CURRENT_VERSION,
+        latest,
+        exe.display()
+    );
+    println!("  Restart the daemon to use it too: todoee daemon stop && todoee daemon start");
+    Ok(())
+}
+
+/// Swap the binary at `exe` for `binary`. The new file is written next to
+/// it first so an interrupted download never leaves a broken install.
+fn replace_exe(exe: &Path, binary: &[u8]) -> Result<()> {
+    let staged = exe.with_extension("new");
+    let hint = || {
+        format!(
+            "Failed to write to {}. Run the update with permission to change it, or use your package manager",
+            exe.parent().unwrap_or(exe).display()
+        )
+    };
+    fs::write(&staged, binary).with_context(hint)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755)).with_context(hint)?;
+    }
+
+    // Windows can't overwrite a running executable, but it can rename it
+    #[cfg(windows)]
+    {
+        let old = exe.with_extension("old");
+        let _ = fs::remove_file(&old);
+        fs::rename(exe, &old).with_context(hint)?;
+    }
+
+    fs::rename(&staged, exe).with_context(hint)
+}
+
+/// Remember what the latest release is, for the new-version notice
+fn record_check(release: &Release) {
+    let Ok(dir) = Config::config_dir() else {
+        return;
+    };
+    let check = UpdateCheck {
+        checked_at: Some(Utc::now()),
+        latest: Some(release.version().to_string()),
+    };
+    let _ = check.save(&UpdateCheck::path(&dir));
+}
+
+/// Check for a new release if the last check is over a week old. Failures
+/// are ignored and retried next week, so being offline stays quiet.
+pub async fn refresh(config: &Config) {
+    let Ok(dir) = Config::config_dir() else {
+        return;
+    };
+    let path = UpdateCheck::path(&dir);
+    let mut check = UpdateCheck::load(&path);
+    if !config.updates.check || !check.is_due(Utc::now()) {
+        return;
+    }
+
+    // Record the attempt up front, so an unreachable server isn't retried
+    // on every command
+    check.checked_at = Some(Utc::now());
+    if let Ok(client) = net::http_client(&config.network, CHECK_TIMEOUT)
+        && let Ok(release) = update::latest_release(&client).await
+    {
+        check.latest = Some(release.version().to_string());
+    }
+    let _ = check.save(&path);
+}
+
+/// The newer version found by the last check, if notices are enabled
+pub fn available(config: &Config) -> Option<String> {
+    if !config.updates.check {
+        return None;
+    }
+    let dir = Config::config_dir().ok()?;
+    UpdateCheck::load(&UpdateCheck::path(&dir))
+        .newer_version()
+        .map(String::from)
+}
+
+/// One line for the new-version notice
+pub fn notice_text(config: &Config, version: &str) -> String {
+    if config.updates.self_update {
+        format!(
+            "todoee {} is available (installed: {}). Run 'todoee self-update' to upgrade.",
+            version, CURRENT_VERSION
+        )
+    } else {
+        format!(
+            "todoee {} is available (installed: {}).",
+            version, CURRENT_VERSION
+        )
+    }
+}
+
+/// After a command: run the weekly check if due and mention a newer version
+/// on stderr. Skipped for JSON output and when stderr isn't a terminal, so
+/// scripts never see it.
+pub async fn notice() {
+    if output::is_json() || output::is_quiet() || !io::stderr().is_terminal() || Config::is_demo() {
+        return;
+    }
+    let Ok(config) = Config::load() else {
+        return;
+    };
+    refresh(&config).await;
+    if let Some(version) = available(&config) {
+        eprintln!();
+        eprintln!("\x1b[33m{}\x1b[0m", notice_text(&config, &version));
+    }
+}
@@ -0,0 +1,760 @@
+//! JSON API over HTTP for web and mobile frontends to the local database.
+//!
+//! Every request needs `Authorization: Bearer <token>`, with the token from
+//! `auth.json`. Todos are addressed by ID or ID prefix:
+//!
+//! - `GET /api/todos`: open todos; `?all=true` includes completed ones and
+//!   `?q=` takes a filter query as in `todoee list`
+//! - `POST /api/todos`: create a todo
+//! - `GET`, `PATCH`, `DELETE /api/todos/{id}`: read, change or delete one.
+//!   A PATCH with `If-Match: "<updated_at>"`, the `updated_at` the client last
+//!   saw, fails with 409 if the todo changed since. An `updated_at` field in
+//!   the body works the same when the header is absent.
+//! - `POST /api/todos/{id}/complete`: mark a todo done
+//! - `GET`, `POST /api/categories`: list or create categories
+//! - `GET /api/search?q=`: fuzzy search, as in `todoee search`
+//!
+//! Changes are recorded like the CLI's, so `todoee undo` reverts them.
+
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Value, json};
+use todoee_core::{
+    Category, Config, EntityType, Filter, IdMatch, LocalDb, Operation, OperationType, Priority,
+    SyncStatus, Todo, TodoeeError, auth, db::ConditionalUpdate,
+};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::add;
+use super::http::response;
+use super::output;
+use super::search::fuzzy_score;
+
+/// Largest request accepted, head and body, in bytes
+const MAX_REQUEST_BYTES: u64 = 64 * 1024;
+
+/// How long a client may take to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Results returned by search unless `?limit=` says otherwise
+const SEARCH_LIMIT: usize = 20;
+
+pub async fn run(port: u16, bind: String) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::with_pool_size(&db_path, config.database.pool_size).await?;
+    db.run_migrations().await?;
+    let token = auth::api_token()?;
+
+    let listener = TcpListener::bind((bind.as_str(), port))
+        .await
+        .with_context(|| format!("Failed to listen on {}:{}", bind, port))?;
+    let url = format!("http://{}/api", listener.local_addr()?);
+
+    if output::is_json() {
+        output::print_json(&json!({ "url": url, "token": token }))?;
+    } else {
+        println!("Serving the todoee API at {}", url);
+        println!("Send this token as \"Authorization: Bearer <token>\":");
+        println!("  {}", token);
+        println!("Press Ctrl+C to stop.");
+    }
+
+    serve(listener, Arc::new(db), Arc::from(token)).await
+}
+
+/// Answer connections until the process is stopped
+async fn serve(listener: TcpListener, db: Arc<LocalDb>, token: Arc<str>) -> Result<()> {
+    loop {
+        let (stream, _) = listener
+            .accept()
+            .await
+            .context("Failed to accept connection")?;
+        let db = Arc::clone(&db);
+        let token = Arc::clone(&token);
+        tokio::spawn(async move {
+            if let Err(e) = handle(stream, &db, &token).await {
+                eprintln!("Warning: {:#}", e);
+            }
+        });
+    }
+}
+
+/// A parsed request
+#[derive(Debug, Default)]
+struct Request {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    /// Bearer token from the Authorization header
+    token: Option<String>,
+    /// Value of the If-Match header
+    if_match: Option<String>,
+    body: String,
+}
+
+/// Status and JSON body of a response
+#[derive(Debug)]
+struct Reply {
+    status: &'static str,
+    body: Option<Value>,
+}
+
+impl Reply {
+    fn ok(body: impl Serialize) -> Result<Self> {
+        Ok(Self {
+            status: "200 OK",
+            body: Some(serde_json::to_value(body)?),
+        })
+    }
+
+    fn created(body: impl Serialize) -> Result<Self> {
+        Ok(Self {
+            status: "201 Created",
+            body: Some(serde_json::to_value(body)?),
+        })
+    }
+
+    fn error(status: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: Some(json!({ "error": message.into() })),
+        }
+    }
+}
+
+async fn handle(stream: TcpStream, db: &LocalDb, token: &str) -> Result<()> {
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read.take(MAX_REQUEST_BYTES));
+
+    let request = tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut reader))
+        .await
+        .context("Timed out reading request")?;
+
+    let reply = match request {
+        Err(e) if e.is::<PayloadTooLarge>() => Reply::error("413 Payload Too Large", e.to_string()),
+        Err(e) => Reply::error("400 Bad Request", format!("{:#}", e)),
+        // CORS preflight, so browser frontends on other origins can call in
+        Ok(request) if request.method == "OPTIONS" => Reply {
+            status: "204 No Content",
+            body: None,
+        },
+        Ok(request)
+            if !request
+                .token
+                .as_deref()
+                .is_some_and(|t| same_token(t, token)) =>
+        {
+            Reply::error("401 Unauthorized", "Missing or wrong API token")
+        }
+        Ok(request) => match route(db, &request).await {
+            Ok(reply) => reply,
+            Err(e) => match e.downcast_ref::<TodoeeError>() {
+                Some(TodoeeError::Validation { .. }) => {
+                    Reply::error("400 Bad Request", e.to_string())
+                }
+                _ => {
+                    eprintln!("Warning: {:#}", e);
+                    Reply::error("500 Internal Server Error", format!("{:#}", e))
+                }
+            },
+        },
+    };
+
+    let body = reply
+        .body
+        .map(|body| body.to_string() + "\n")
+        .unwrap_or_default();
+    let mut headers = vec![
+        ("Access-Control-Allow-Origin", "*"),
+        (
+            "Access-Control-Allow-Headers",
+            "Authorization, Content-Type, If-Match",
+        ),
+        (
+            "Access-Control-Allow-Methods",
+            "GET, POST, PATCH, DELETE, OPTIONS",
+        ),
+    ];
+    if !body.is_empty() {
+        headers.push(("Content-Type", "application/json"));
+    }
+    if reply.status.starts_with("401") {
+        headers.push(("WWW-Authenticate", "Bearer"));
+    }
+    write
+        .write_all(response(reply.status, &headers, &body, true).as_bytes())
+        .await
+        .context("Failed to send response")?;
+    write.shutdown().await.ok();
+    Ok(())
+}
+
+/// A request whose Content-Length is over [`MAX_REQUEST_BYTES`]
+#[derive(Debug)]
+struct PayloadTooLarge(u64);
+
+impl std::fmt::Display for PayloadTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Request body of {} bytes is over the {} byte limit",
+            self.0, MAX_REQUEST_BYTES
+        )
+    }
+}
+
+impl std::error::Error for PayloadTooLarge {}
+
+async fn read_request<R: tokio::io::AsyncBufRead + Unpin>(reader: &mut R) -> Result<Request> {
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().context("Empty request")?.to_string();
+    let target = parts.next().context("Request has no path")?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut request = Request {
+        method,
+        path: path.trim_end_matches('/').to_string(),
+        query: query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (percent_decode(key), percent_decode(value))
+            })
+            .collect(),
+        ..Request::default()
+    };
+
+    let mut length: u64 = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "authorization" => {
+                request.token = value
+                    .strip_prefix("Bearer ")
+                    .map(|token| token.trim().to_string());
+            }
+            "if-match" => request.if_match = Some(value.to_string()),
+            "content-length" => length = value.parse().context("Invalid Content-Length")?,
+            _ => {}
+        }
+    }
+
+    // Checked before allocating: the length is whatever the client claims
+    if length > MAX_REQUEST_BYTES {
+        return Err(PayloadTooLarge(length).into());
+    }
+    let mut body = vec![0; length as usize];
+    reader
+        .read_exact(&mut body)
+        .await
+        .context("Request body is incomplete or too large")?;
+    request.body = String::from_utf8(body).context("Request body is not UTF-8")?;
+    Ok(request)
+}
+
+async fn route(db: &LocalDb, request: &Request) -> Result<Reply> {
+    let Some(path) = request.path.strip_prefix("/api/") else {
+        return Ok(Reply::error("404 Not Found", "Not found"));
+    };
+    let segments: Vec<&str> = path.split('/').collect();
+    let method = request.method.as_str();
+
+    match (method, segments.as_slice()) {
+        ("GET", ["todos"]) => {
+            let all = request.query.get("all").is_some_and(|v| v == "true");
+            let todos = match request.query.get("q").filter(|q| !q.is_empty()) {
+                Some(query) => match query.parse::<Filter>() {
+                    Ok(filter) => db.list_todos_filtered(&filter, !all, &Local::now()).await?,
+                    Err(e) => {
+                        return Ok(Reply::error(
+                            "400 Bad Request",
+                            format!("Invalid query \"{}\": {:#}", query, e),
+                        ));
+                    }
+                },
+                None => db.list_todos(!all).await?,
+            };
+            Reply::ok(todos)
+        }
+        ("POST", ["todos"]) => {
+            let input: TodoInput = match parse_body(request) {
+                Ok(input) => input,
+                Err(reply) => return Ok(reply),
+            };
+            let Some(title) = input.title.clone() else {
+                return Ok(Reply::error("400 Bad Request", "A title is required"));
+            };
+            let mut todo = Todo::new(title, None);
+            if let Some(category) = input.apply(db, &mut todo).await? {
+                db.create_category(&category).await?;
+            }
+            db.create_todo(&todo).await?;
+            let op = Operation::new(
+                OperationType::Create,
+                EntityType::Todo,
+                todo.id,
+                None,
+                Some(serde_json::to_value(&todo)?),
+            );
+            db.record_operation(&op).await?;
+            Reply::created(todo)
+        }
+        (_, ["todos", id, rest @ ..]) => {
+            let todo = match db.resolve_short_id(id).await? {
+                IdMatch::Unique(todo) => *todo,
+                IdMatch::Ambiguous(_) => {
+                    return Ok(Reply::error(
+                        "400 Bad Request",
+                        format!("\"{}\" matches more than one todo", id),
+                    ));
+                }
+                IdMatch::NotFound => {
+                    return Ok(Reply::error("404 Not Found", format!("No todo \"{}\"", id)));
+                }
+            };
+            match (method, rest) {
+                ("GET", []) => Reply::ok(todo),
+                ("PATCH", []) => update(db, request, todo).await,
+                ("DELETE", []) => {
+                    let op = Operation::new(
+                        OperationType::Delete,
+                        EntityType::Todo,
+                        todo.id,
+                        Some(serde_json::to_value(&todo)?),
+                        None,
+                    );
+                    db.record_operation(&op).await?;
+                    db.delete_todo(todo.id).await?;
+                    Reply::ok(todo)
+                }
+                ("POST", ["complete"]) => complete(db, todo).await,
+                (_, [] | ["complete"]) => {
+                    Ok(Reply::error("405 Method Not Allowed", "Method not allowed"))
+                }
+                _ => Ok(Reply::error("404 Not Found", "Not found")),
+            }
+        }
+        ("GET", ["categories"]) => Reply::ok(db.list_categories().await?),
+        ("POST", ["categories"]) => {
+            let input: CategoryInput = match parse_body(request) {
+                Ok(input) => input,
+                Err(reply) => return Ok(reply),
+            };
+            let name = input.name.trim();
+            if name.is_empty() {
+                return Ok(Reply::error("400 Bad Request", "A name is required"));
+            }
+            if let Some(existing) = db.get_category_by_name(name).await? {
+                return Ok(Reply::error(
+                    "409 Conflict",
+                    format!("Category \"{}\" already exists ({})", name, existing.id),
+                ));
+            }
+            let mut category = add::new_category(name, None);
+            category.color = input.color;
+            db.create_category(&category).await?;
+            Reply::created(category)
+        }
+        ("GET", ["search"]) => {
+            let query = request
+                .query
+                .get("q")
+                .map(|q| q.to_lowercase())
+                .unwrap_or_default();
+            let limit = request
+                .query
+                .get("limit")
+                .and_then(|l| l.parse().ok())
+                .unwrap_or(SEARCH_LIMIT);
+            let todos = db.list_todos(false).await?;
+            let mut matches: Vec<(Todo, i32)> = todos
+                .into_iter()
+                .map(|todo| {
+                    let score = fuzzy_score(&todo.title.to_lowercase(), &query);
+                    (todo, score)
+                })
+                .filter(|(_, score)| *score > 0)
+                .collect();
+            matches.sort_by_key(|m| std::cmp::Reverse(m.1));
+            let todos: Vec<Todo> = matches
+                .into_iter()
+                .take(limit)
+                .map(|(todo, _)| todo)
+                .collect();
+            Reply::ok(todos)
+        }
+        (_, ["todos"] | ["categories"] | ["search"]) => {
+            Ok(Reply::error("405 Method Not Allowed", "Method not allowed"))
+        }
+        _ => Ok(Reply::error("404 Not Found", "Not found")),
+    }
+}
+
+async fn update(db: &LocalDb, request: &Request, todo: Todo) -> Result<Reply> {
+    let input: TodoInput = match parse_body(request) {
+        Ok(input) => input,
+        Err(reply) => return Ok(reply),
+    };
+    let expected = match request.if_match.as_deref() {
+        Some(tag) => match parse_if_match(tag) {
+            Some(expected) => expected,
+            None => {
+                return Ok(Reply::error(
+                    "400 Bad Request",
+                    format!(
+                        "Invalid If-Match \"{}\": expected the todo's updated_at",
+                        tag
+                    ),
+                ));
+            }
+        },
+        None => input.updated_at,
+    };
+    let prev = serde_json::to_value(&todo)?;
+    let expected = expected.unwrap_or(todo.updated_at);
+    let mut updated = todo;
+    let new_category = input.apply(db, &mut updated).await?;
+    updated.updated_at = Utc::now();
+    updated.sync_status = SyncStatus::Pending;
+
+    match db
+        .update_todo_if_unchanged(&updated, expected, new_category.as_ref())
+        .await?
+    {
+        ConditionalUpdate::Updated => {}
+        ConditionalUpdate::Conflict(current) => {
+            return Ok(Reply {
+                status: "409 Conflict",
+                body: Some(json!({
+                    "error": "The todo changed since it was read",
+                    "current": current,
+                })),
+            });
+        }
+        ConditionalUpdate::NotFound => {
+            return Ok(Reply::error("404 Not Found", "The todo was deleted"));
+        }
+    }
+    let op = Operation::new(
+        OperationType::Update,
+        EntityType::Todo,
+        updated.id,
+        Some(prev),
+        Some(serde_json::to_value(&updated)?),
+    );
+    db.record_operation(&op).await?;
+    Reply::ok(updated)
+}
+
+/// The `updated_at` an If-Match header expects, quoted or not. `Some(None)`
+/// for `*`, which matches any version.
+fn parse_if_match(tag: &str) -> Option<Option<DateTime<Utc>>> {
+    let tag = tag.trim();
+    if tag == "*" {
+        return Some(None);
+    }
+    let tag = tag.strip_prefix("W/").unwrap_or(tag);
+    let tag = tag
+        .strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .unwrap_or(tag);
+    DateTime::parse_from_rfc3339(tag)
+        .ok()
+        .map(|at| Some(at.with_timezone(&Utc)))
+}
+
+async fn complete(db: &LocalDb, mut todo: Todo) -> Result<Reply> {
+    if todo.is_completed {
+        return Reply::ok(todo);
+    }
+    let prev = serde_json::to_value(&todo)?;
+    todo.mark_complete();
+    db.update_todo(&todo).await?;
+    let op = Operation::new(
+        OperationType::Complete,
+        EntityType::Todo,
+        todo.id,
+        Some(prev),
+        Some(serde_json::to_value(&todo)?),
+    );
+    db.record_operation(&op).await?;
+    Reply::ok(todo)
+}
+
+/// Fields a client may set on a todo. Absent fields are left alone; `null`
+/// clears the ones that can be empty.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TodoInput {
+    title: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    due_date: Option<Option<DateTime<Utc>>>,
+    #[serde(default, deserialize_with = "nullable")]
+    reminder_at: Option<Option<DateTime<Utc>>>,
+    priority: Option<Priority>,
+    /// Category name, created if it doesn't exist yet
+    #[serde(default, deserialize_with = "nullable")]
+    category: Option<Option<String>>,
+    /// When set on a PATCH, the `updated_at` the client last saw
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl TodoInput {
+    /// Set the given fields on `todo`. A category that doesn't exist yet is
+    /// returned for the caller to create along with the todo.
+    async fn apply(&self, db: &LocalDb, todo: &mut Todo) -> Result<Option<Category>> {
+        let mut new_category = None;
+        if let Some(title) = &self.title {
+            todo.title = title.clone();
+        }
+        if let Some(description) = &self.description {
+            todo.description = description.clone();
+        }
+        if let Some(due_date) = self.due_date {
+            todo.due_date = due_date;
+        }
+        if let Some(reminder_at) = self.reminder_at {
+            todo.reminder_at = reminder_at;
+        }
+        if let Some(priority) = self.priority {
+            todo.priority = priority;
+        }
+        if let Some(category) = &self.category {
+            todo.category_id = match category {
+                Some(name) => Some(match db.get_category_by_name(name).await? {
+                    Some(existing) => existing.id,
+                    None => new_category.insert(add::new_category(name, None)).id,
+                }),
+                None => None,
+            };
+        }
+        Ok(new_category)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CategoryInput {
+    name: String,
+    #[serde(default)]
+    color: Option<String>,
+}
+
+/// `Some(None)` for an explicit `null`, so it can be told from an absent field
+fn nullable<'de, D, T>(deserializer: D) -> std::result::Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+fn parse_body<T: for<'de> Deserialize<'de>>(request: &Request) -> Result<T, Reply> {
+    serde_json::from_str(&request.body)
+        .map_err(|e| Reply::error("400 Bad Request", format!("Invalid JSON body: {}", e)))
+}
+
+/// Compare tokens without stopping at the first difference
+fn same_token(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Decode `%XX` escapes and `+` in a query string component
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        out.push(byte);
+                        i += 2;
+                    }
+                    Err(_) => out.push(b'%'),
+                }
+            }
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn request(addr: std::net::SocketAddr, request: &str) -> (String, Value) {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.lines().next().unwrap().to_string();
+        (status, serde_json::from_str(body).unwrap_or(Value::Null))
+    }
+
+    fn with_body(method: &str, path: &str, body: &str) -> String {
+        format!(
+            "{} {} HTTP/1.1\r\nAuthorization: Bearer secret\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+    }
+
+    #[tokio::test]
+    async fn test_api_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDb::new(&temp_dir.path().join("test.db"))
+            .await
+            .unwrap();
+        db.run_migrations().await.unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, Arc::new(db), Arc::from("secret")));
+
+        let (status, _) = request(addr, "GET /api/todos HTTP/1.1\r\n\r\n").await;
+        assert_eq!(status, "HTTP/1.1 401 Unauthorized");
+        let (status, _) = request(
+            addr,
+            "GET /api/todos HTTP/1.1\r\nAuthorization: Bearer wrong!\r\n\r\n",
+        )
+        .await;
+        assert_eq!(status, "HTTP/1.1 401 Unauthorized");
+
+        let body = r#"{"title":"Write report","priority":"high","category":"Work"}"#;
+        let (status, created) = request(addr, &with_body("POST", "/api/todos", body)).await;
+        assert_eq!(status, "HTTP/1.1 201 Created");
+        assert_eq!(created["priority"], "high");
+        let id = created["id"].as_str().unwrap().to_string();
+
+        let (_, categories) = request(addr, &with_body("GET", "/api/categories", "")).await;
+        assert_eq!(categories[0]["name"], "Work");
+
+        // Edits based on a stale read are refused, without leaving a category
+        let stale = format!(
+            r#"{{"title":"Mine","category":"Home","updated_at":"{}"}}"#,
+            "2020-01-01T00:00:00Z"
+        );
+        let path = format!("/api/todos/{}", &id[..8]);
+        let (status, conflict) = request(addr, &with_body("PATCH", &path, &stale)).await;
+        assert_eq!(status, "HTTP/1.1 409 Conflict");
+        assert_eq!(conflict["current"]["title"], "Write report");
+        let (_, categories) = request(addr, &with_body("GET", "/api/categories", "")).await;
+        assert_eq!(categories.as_array().unwrap().len(), 1);
+        let fresh = format!(
+            r#"{{"title":"Write the report","description":null,"updated_at":"{}"}}"#,
+            created["updated_at"].as_str().unwrap()
+        );
+        let (status, updated) = request(addr, &with_body("PATCH", &path, &fresh)).await;
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert_eq!(updated["title"], "Write the report");
+
+        // The same through If-Match, which wins over the body
+        let if_match = |tag: &str, body: &str| {
+            with_body("PATCH", &path, body).replacen(
+                "\r\n\r\n",
+                &format!("\r\nIf-Match: {}\r\n\r\n", tag),
+                1,
+            )
+        };
+        let body = format!(
+            r#"{{"priority":"low","updated_at":"{}"}}"#,
+            updated["updated_at"].as_str().unwrap()
+        );
+        let (status, _) = request(addr, &if_match(r#""2020-01-01T00:00:00Z""#, &body)).await;
+        assert_eq!(status, "HTTP/1.1 409 Conflict");
+        let (status, _) = request(addr, &if_match("yesterday", &body)).await;
+        assert_eq!(status, "HTTP/1.1 400 Bad Request");
+        let tag = format!(r#""{}""#, updated["updated_at"].as_str().unwrap());
+        let (status, updated) = request(addr, &if_match(&tag, r#"{"priority":"high"}"#)).await;
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert_eq!(updated["priority"], "high");
+
+        let (status, _) = request(addr, &with_body("PATCH", &path, r#"{"title":""}"#)).await;
+        assert_eq!(status, "HTTP/1.1 400 Bad Request");
+
+        let (_, found) = request(addr, &with_body("GET", "/api/search?q=wrt+rep", "")).await;
+        assert_eq!(found.as_array().unwrap().len(), 1);
+        let (_, filtered) =
+            request(addr, &with_body("GET", "/api/todos?q=priority%3Alow", "")).await;
+        assert_eq!(filtered, json!([]));
+
+        let done = format!("{}/complete", path);
+        let (status, completed) = request(addr, &with_body("POST", &done, "")).await;
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert_eq!(completed["is_completed"], true);
+        let (_, open) = request(addr, &with_body("GET", "/api/todos", "")).await;
+        assert_eq!(open, json!([]));
+        let (_, all) = request(addr, &with_body("GET", "/api/todos?all=true", "")).await;
+        assert_eq!(all.as_array().unwrap().len(), 1);
+
+        let (status, _) = request(addr, &with_body("DELETE", &path, "")).await;
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        let (status, _) = request(addr, &with_body("GET", &path, "")).await;
+        assert_eq!(status, "HTTP/1.1 404 Not Found");
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_is_refused() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDb::new(&temp_dir.path().join("test.db"))
+            .await
+            .unwrap();
+        db.run_migrations().await.unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, Arc::new(db), Arc::from("secret")));
+
+        let (status, _) = request(
+            addr,
+            "GET /api/todos HTTP/1.1\r\nContent-Length: 99999999999999\r\n\r\n",
+        )
+        .await;
+        assert_eq!(status, "HTTP/1.1 413 Payload Too Large");
+
+        // The server is still up
+        let (status, _) = request(addr, &with_body("GET", "/api/todos", "")).await;
+        assert_eq!(status, "HTTP/1.1 200 OK");
+    }
+}
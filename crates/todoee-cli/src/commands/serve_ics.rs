@@ -0,0 +1,188 @@
+//! Read-only iCalendar feed over HTTP, for calendar apps that subscribe to
+//! a URL instead of importing a file.
+
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use todoee_core::{Config, LocalDb, ics};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::http::response;
+use super::output;
+
+/// Path the feed is served at; `/` serves it too
+const FEED_PATH: &str = "/todoee.ics";
+
+/// Largest request head accepted, in bytes
+const MAX_REQUEST_BYTES: u64 = 8 * 1024;
+
+/// How long a client may take to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub async fn run(port: u16, bind: String, include_completed: bool) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::with_pool_size(&db_path, config.database.pool_size).await?;
+    db.run_migrations().await?;
+
+    let listener = TcpListener::bind((bind.as_str(), port))
+        .await
+        .with_context(|| format!("Failed to listen on {}:{}", bind, port))?;
+    let url = format!("http://{}{}", listener.local_addr()?, FEED_PATH);
+
+    if output::is_json() {
+        output::print_json(&serde_json::json!({ "url": url }))?;
+    } else {
+        println!("Serving iCalendar feed at {}", url);
+        println!("Subscribe to this URL from your calendar app. Press Ctrl+C to stop.");
+    }
+
+    serve(listener, Arc::new(db), include_completed).await
+}
+
+/// Answer connections until the process is stopped. The feed is rebuilt
+/// from the database on every request, so it is always current.
+async fn serve(listener: TcpListener, db: Arc<LocalDb>, include_completed: bool) -> Result<()> {
+    loop {
+        let (stream, _) = listener
+            .accept()
+            .await
+            .context("Failed to accept connection")?;
+        let db = Arc::clone(&db);
+        tokio::spawn(async move {
+            if let Err(e) = handle(stream, &db, include_completed).await {
+                eprintln!("Warning: {:#}", e);
+            }
+        });
+    }
+}
+
+async fn handle(stream: TcpStream, db: &LocalDb, include_completed: bool) -> Result<()> {
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read.take(MAX_REQUEST_BYTES));
+
+    let request_line = tokio::time::timeout(REQUEST_TIMEOUT, async {
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).await?;
+        // Headers carry nothing the feed needs, but must be read off the socket
+        let mut header = String::new();
+        while reader.read_line(&mut header).await? > 0 && !header.trim().is_empty() {
+            header.clear();
+        }
+        std::io::Result::Ok(request_line)
+    })
+    .await
+    .context("Timed out reading request")?
+    .context("Failed to read request")?;
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts
+        .next()
+        .and_then(|target| target.split('?').next())
+        .unwrap_or_default();
+    let with_body = method != "HEAD";
+
+    let response = match (method, path) {
+        ("GET" | "HEAD", "/" | FEED_PATH) => {
+            let todos = db.list_todos(!include_completed).await?;
+            let categories = db.list_categories().await?;
+            let body = ics::write(&todos, &categories, chrono::Utc::now());
+            response(
+                "200 OK",
+                &[
+                    ("Content-Type", "text/calendar; charset=utf-8"),
+                    ("Content-Disposition", "inline; filename=\"todoee.ics\""),
+                    ("Cache-Control", "no-cache"),
+                ],
+                &body,
+                with_body,
+            )
+        }
+        ("GET" | "HEAD", _) => response(
+            "404 Not Found",
+            &[("Content-Type", "text/plain; charset=utf-8")],
+            "Not found\n",
+            with_body,
+        ),
+        _ => response(
+            "405 Method Not Allowed",
+            &[
+                ("Allow", "GET, HEAD"),
+                ("Content-Type", "text/plain; charset=utf-8"),
+            ],
+            "This feed is read-only\n",
+            true,
+        ),
+    };
+
+    write
+        .write_all(response.as_bytes())
+        .await
+        .context("Failed to send response")?;
+    write.shutdown().await.ok();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use todoee_core::Todo;
+
+    async fn request(addr: std::net::SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_serves_read_only_feed() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDb::new(&temp_dir.path().join("test.db"))
+            .await
+            .unwrap();
+        db.run_migrations().await.unwrap();
+        let mut done = Todo::new("Finished task".to_string(), None);
+        done.mark_complete();
+        db.create_todo(&Todo::new("Test task".to_string(), None))
+            .await
+            .unwrap();
+        db.create_todo(&done).await.unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, Arc::new(db), false));
+
+        let feed = request(addr, "GET /todoee.ics HTTP/1.1\r\nHost: x\r\n\r\n").await;
+        assert!(feed.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(feed.contains("Content-Type: text/calendar; charset=utf-8\r\n"));
+        assert!(feed.contains("\r\n\r\nBEGIN:VCALENDAR\r\n"));
+        assert!(feed.contains("SUMMARY:Test task\r\n"));
+        assert!(!feed.contains("Finished task"));
+
+        let head = request(addr, "HEAD / HTTP/1.1\r\n\r\n").await;
+        assert!(head.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(head.ends_with("\r\n\r\n"));
+
+        let missing = request(addr, "GET /other HTTP/1.1\r\n\r\n").await;
+        assert!(missing.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        let put = request(addr, "PUT /todoee.ics HTTP/1.1\r\n\r\n").await;
+        assert!(put.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+        assert!(put.contains("Allow: GET, HEAD\r\n"));
+    }
+}
@@ -3,8 +3,10 @@
 use std::fs;
 
 use anyhow::{Context, Result};
-use chrono::{Local, TimeZone};
-use todoee_core::{Config, LocalDb, Priority, Todo};
+use chrono::{Duration, Local, TimeZone};
+use todoee_core::{Config, IdMatch, LocalDb, Priority, Todo, dates, short_id};
+
+use super::{ids, output};
 
 /// Run the show command to display detailed info about a todo.
 pub async fn run(id: &str) -> Result<()> {
@@ -21,31 +23,34 @@ pub async fn run(id: &str) -> Result<()> {
     let db = LocalDb::new(&db_path).await?;
     db.run_migrations().await?;
 
-    // Find todos matching the partial ID (include completed)
-    let all_todos = db.list_todos(false).await?;
-    let id_lower = id.to_lowercase();
+    // Find the todo matching the list number or partial ID (include completed)
+    let found = ids::resolve(&db, &config, id).await?;
 
-    let matching: Vec<&Todo> = all_todos
-        .iter()
-        .filter(|t| t.id.to_string().to_lowercase().starts_with(&id_lower))
-        .collect();
+    if output::is_json() {
+        return match found {
+            IdMatch::Unique(todo) => output::print_json(&todo),
+            IdMatch::NotFound => anyhow::bail!("No todo found with ID starting with '{}'", id),
+            IdMatch::Ambiguous(_) => {
+                anyhow::bail!("Multiple todos match '{}'; be more specific", id)
+            }
+        };
+    }
 
-    match matching.len() {
-        0 => {
+    match found {
+        IdMatch::NotFound => {
             println!("No todo found with ID starting with '{}'", id);
             println!("Hint: Use 'todoee list --all' to see all todos including completed ones.");
         }
-        1 => {
-            let todo = matching[0];
-            print_detailed_todo(&db, todo).await?;
+        IdMatch::Unique(todo) => {
+            print_detailed_todo(&db, &todo).await?;
         }
-        _ => {
+        IdMatch::Ambiguous(matching) => {
             println!("Multiple matches for '{}'. Be more specific:", id);
             println!();
+            let id_len = ids::match_length(&matching);
             for t in matching {
                 let status = if t.is_completed { "[x]" } else { "[ ]" };
-                let short_id = &t.id.to_string()[..8];
-                println!("  {} {} - {}", short_id, status, t.title);
+                println!("  {} {} - {}", short_id(&t.id, id_len), status, t.title);
             }
         }
     }
@@ -69,6 +74,7 @@ async fn print_detailed_todo(db: &LocalDb, todo: &Todo) -> Result<()> {
     };
 
     let priority_display = match todo.priority {
+        Priority::Urgent => "\x1b[1;31mUrgent (!!!!)\x1b[0m",
         Priority::High => "\x1b[31mHigh (!!!)\x1b[0m",
         Priority::Medium => "\x1b[33mMedium (!!)\x1b[0m",
         Priority::Low => "Low (!)",
@@ -103,6 +109,17 @@ async fn print_detailed_todo(db: &LocalDb, todo: &Todo) -> Result<()> {
         println!("\u{2502} Reminder:   {}", local.format("%Y-%m-%d %H:%M"));
     }
 
+    if let Some(minutes) = todo.remind_before_minutes {
+        println!(
+            "\u{2502} Notify:     {} before due",
+            dates::format_duration(Duration::minutes(minutes))
+        );
+    }
+
+    if todo.no_nag {
+        println!("\u{2502} Repeat:     off once overdue");
+    }
+
     if let Some(completed) = todo.completed_at {
         let local = Local.from_utc_datetime(&completed.naive_utc());
         println!("\u{2502} Completed:  {}", local.format("%Y-%m-%d %H:%M"));
@@ -113,6 +130,40 @@ async fn print_detailed_todo(db: &LocalDb, todo: &Todo) -> Result<()> {
     println!("\u{2502} Created:    {}", created.format("%Y-%m-%d %H:%M"));
     println!("\u{2502} Updated:    {}", updated.format("%Y-%m-%d %H:%M"));
     println!("\u{2502} Sync:       {:?}", todo.sync_status);
+    let ai_fields = todo.ai_fields();
+    if !ai_fields.is_empty() {
+        println!(
+            "\u{2502} Set by AI:  {}",
+            super::ai::field_names(&ai_fields)
+        );
+    }
+
+    let attachments = db.list_attachments(todo.id).await?;
+    if !attachments.is_empty() {
+        println!("\u{2502} Attachments:");
+        for (i, attachment) in attachments.iter().enumerate() {
+            println!("\u{2502}   {}. {}", i + 1, attachment.target);
+        }
+    }
+
+    let notes = db.list_todo_notes(todo.id).await?;
+    if !notes.is_empty() {
+        println!("\u{2502} Notes:");
+        for note in &notes {
+            let at = note
+                .created_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M");
+            for (i, line) in note.body.lines().enumerate() {
+                let at = if i == 0 {
+                    at.to_string()
+                } else {
+                    String::new()
+                };
+                println!("\u{2502}   \x1b[90m{:16}\x1b[0m  {}", at, line);
+            }
+        }
+    }
     println!(
         "\u{2514}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2518}"
     );
@@ -0,0 +1,95 @@
+//! Snooze command: push a todo's reminder later.
+
+use std::fs;
+
+use anyhow::{Context, Result};
+use chrono::{Duration, Local, Utc};
+use todoee_core::{Config, EntityType, LocalDb, Operation, OperationType, dates, short_id};
+
+use super::{ids, output};
+
+pub async fn run(id: String, duration: String) -> Result<()> {
+    let duration = parse_duration(&duration)?;
+
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    let mut todo = ids::resolve_todo(&db, &config, &id).await?;
+    if todo.is_completed {
+        anyhow::bail!("'{}' is completed; reopen it to snooze it", todo.title);
+    }
+    let prev_state = serde_json::to_value(&todo)?;
+
+    let until = todo.snooze(duration, Utc::now());
+    db.update_todo(&todo).await?;
+
+    let op = Operation::new(
+        OperationType::Update,
+        EntityType::Todo,
+        todo.id,
+        Some(prev_state),
+        Some(serde_json::to_value(&todo)?),
+    );
+    db.record_operation(&op).await?;
+
+    output::say!("\u{23F0} Snoozed: {}", todo.title);
+    output::say!(
+        "  Reminder: {}",
+        until.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+    );
+    let id_len = ids::id_length(&db, &config).await?;
+    output::say!("  ID: {}", short_id(&todo.id, id_len));
+    output::detail!("Full ID: {}", todo.id);
+
+    Ok(())
+}
+
+/// Parse a snooze duration: "10m", "1h", "1h30m", "2d", or plain minutes ("45").
+pub fn parse_duration(input: &str) -> Result<Duration> {
+    dates::parse_duration(input)
+        // Also too long to add to the current time
+        .filter(|d| *d > Duration::zero() && Utc::now().checked_add_signed(*d).is_some())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid duration '{}' (try 10m, 1h, 1h30m or 2d)",
+                input.trim().to_lowercase()
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_units() {
+        assert_eq!(parse_duration("10m").unwrap(), Duration::minutes(10));
+        assert_eq!(parse_duration("1h").unwrap(), Duration::hours(1));
+        assert_eq!(parse_duration("2d").unwrap(), Duration::days(2));
+        assert_eq!(parse_duration("1H30M").unwrap(), Duration::minutes(90));
+        assert!(parse_duration("99999999999999d").is_err());
+        assert!(parse_duration("100000000000d").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration_plain_minutes() {
+        assert_eq!(parse_duration("45").unwrap(), Duration::minutes(45));
+    }
+
+    #[test]
+    fn test_parse_duration_rejects_invalid() {
+        for input in ["", "0", "-5", "10x", "h", "1h30", "0m"] {
+            assert!(parse_duration(input).is_err(), "accepted '{}'", input);
+        }
+    }
+}
@@ -2,7 +2,9 @@
 
 use anyhow::Result;
 use clap::Subcommand;
-use todoee_core::{Config, EntityType, LocalDb, Operation, OperationType};
+use todoee_core::{Config, EntityType, IdMatch, LocalDb, Operation, OperationType, short_id};
+
+use super::{ids, output};
 
 #[derive(Subcommand, Clone)]
 pub enum StashCommand {
@@ -29,26 +31,20 @@ pub async fn run(cmd: StashCommand) -> Result<()> {
     db.run_migrations().await?;
 
     match cmd {
-        StashCommand::Push { id, message } => push(&db, &id, message.as_deref()).await,
+        StashCommand::Push { id, message } => push(&db, &config, &id, message.as_deref()).await,
         StashCommand::Pop => pop(&db).await,
         StashCommand::List => list(&db).await,
         StashCommand::Clear => clear(&db).await,
     }
 }
 
-async fn push(db: &LocalDb, id: &str, message: Option<&str>) -> Result<()> {
-    let todos = db.list_todos(false).await?;
-    let matching: Vec<_> = todos
-        .iter()
-        .filter(|t| t.id.to_string().starts_with(id))
-        .collect();
-
-    match matching.len() {
-        0 => {
+async fn push(db: &LocalDb, config: &Config, id: &str, message: Option<&str>) -> Result<()> {
+    match ids::resolve(db, config, id).await? {
+        IdMatch::NotFound => {
             println!("No todo found with ID '{}'", id);
         }
-        1 => {
-            let todo = db.stash_todo(matching[0].id, message).await?;
+        IdMatch::Unique(todo) => {
+            let todo = db.stash_todo(todo.id, message).await?;
 
             let op = Operation::new(
                 OperationType::Stash,
@@ -62,10 +58,11 @@ async fn push(db: &LocalDb, id: &str, message: Option<&str>) -> Result<()> {
             let msg_display = message.map(|m| format!(": {}", m)).unwrap_or_default();
             println!("Stashed{}: {}", msg_display, todo.title);
         }
-        _ => {
+        IdMatch::Ambiguous(matching) => {
             println!("Multiple matches. Be more specific:");
+            let id_len = ids::match_length(&matching);
             for t in matching {
-                println!("  {} - {}", &t.id.to_string()[..8], t.title);
+                println!("  {} - {}", short_id(&t.id, id_len), t.title);
             }
         }
     }
@@ -97,6 +94,16 @@ async fn pop(db: &LocalDb) -> Result<()> {
 async fn list(db: &LocalDb) -> Result<()> {
     let stashed = db.stash_list().await?;
 
+    if output::is_json() {
+        let entries: Vec<_> = stashed
+            .iter()
+            .map(|(todo, at, msg)| {
+                serde_json::json!({ "todo": todo, "stashed_at": at, "message": msg })
+            })
+            .collect();
+        return output::print_json(&entries);
+    }
+
     if stashed.is_empty() {
         println!("Stash is empty");
     } else {
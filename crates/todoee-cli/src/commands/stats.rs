@@ -0,0 +1,268 @@
+//! Historical time series of created and completed todos.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use chrono::{Datelike, Duration, Local, NaiveDate, TimeZone, Utc};
+use serde::Serialize;
+use todoee_core::{ActivityBucket, Config, LocalDb, dates};
+
+use super::output;
+
+/// Length of one row of the series
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interval {
+    Day,
+    /// ISO weeks, Monday to Sunday
+    Week,
+}
+
+impl FromStr for Interval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "day" | "daily" => Ok(Self::Day),
+            "week" | "weekly" => Ok(Self::Week),
+            _ => Err(format!("Unknown interval '{}' (expected day or week)", s)),
+        }
+    }
+}
+
+impl Interval {
+    /// First day of the period containing `date`
+    fn start_of(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => date,
+            Self::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+        }
+    }
+
+    fn length(self) -> Duration {
+        match self {
+            Self::Day => Duration::days(1),
+            Self::Week => Duration::weeks(1),
+        }
+    }
+}
+
+/// One period of the series
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatsRow {
+    /// First day of the period
+    pub period: NaiveDate,
+    pub created: u64,
+    pub completed: u64,
+    /// Created minus completed
+    pub net: i64,
+    /// Open todos at the end of the period
+    pub backlog: i64,
+}
+
+pub async fn run(
+    since: Option<String>,
+    until: Option<String>,
+    interval: Interval,
+    export: Option<String>,
+    output_path: Option<String>,
+) -> Result<()> {
+    let config = Config::load().context("Failed to load config")?;
+    let date_input = config.display.date_input();
+    let now = Local::now();
+    let parse = |flag: &str, input: &str| {
+        dates::parse_with(input, &now, &date_input)
+            .map(|parsed| parsed.date)
+            .with_context(|| format!("Could not understand {} date '{}'", flag, input))
+    };
+    let until = match until {
+        Some(input) => parse("--until", &input)?,
+        None => now.date_naive(),
+    };
+    let since = match since {
+        Some(input) => parse("--since", &input)?,
+        None => until - Duration::days(29),
+    };
+    if since > until {
+        anyhow::bail!("--since {} is after --until {}", since, until);
+    }
+
+    let db_path = config.local_db_path()?;
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    // Activity up to now, not just to --until: the backlog is worked out
+    // backwards from today's open todos
+    let start = interval.start_of(since);
+    let midnight = start
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time");
+    let start_utc = Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc());
+    let activity = db.activity_by_hour(start_utc).await?;
+    let pending = db.todo_stats(start_utc, Utc::now()).await?.pending;
+    let rows = series(&activity, &Local, start, until, interval, pending);
+
+    let format = match export {
+        Some(format) => format.to_lowercase(),
+        None if output::is_json() => "json".to_string(),
+        None => {
+            print_table(&rows, interval);
+            return Ok(());
+        }
+    };
+    let content = match format.as_str() {
+        "csv" => to_csv(&rows)?,
+        "json" => serde_json::to_string_pretty(&rows).context("Failed to serialize stats")?,
+        other => anyhow::bail!("Unknown format '{}' (expected csv or json)", other),
+    };
+
+    match output_path {
+        Some(path) => {
+            fs::write(&path, &content)
+                .with_context(|| format!("Failed to write export file: {}", path))?;
+            println!(
+                "\u{2713} Exported {} row{} to {}",
+                rows.len(),
+                if rows.len() == 1 { "" } else { "s" },
+                path
+            );
+        }
+        None => {
+            print!("{}", content);
+            if !content.ends_with('\n') {
+                println!();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Bucket hourly activity into periods from `start` through `until`, empty
+/// periods included. `pending` is the number of open todos now; each
+/// period's backlog is that minus the net change since the period ended.
+pub fn series<Tz: TimeZone>(
+    activity: &[ActivityBucket],
+    tz: &Tz,
+    start: NaiveDate,
+    until: NaiveDate,
+    interval: Interval,
+    pending: u64,
+) -> Vec<StatsRow> {
+    let mut by_day: BTreeMap<NaiveDate, (u64, u64)> = BTreeMap::new();
+    for bucket in activity {
+        let day = bucket.hour.with_timezone(tz).date_naive();
+        let entry = by_day.entry(day).or_default();
+        entry.0 += bucket.created;
+        entry.1 += bucket.completed;
+    }
+
+    let mut rows = Vec::new();
+    let mut period = interval.start_of(start);
+    while period <= until {
+        let end = period + interval.length();
+        let (mut created, mut completed) = (0, 0);
+        let mut net_after = 0i64;
+        for (day, (c, d)) in &by_day {
+            if *day >= end {
+                net_after += *c as i64 - *d as i64;
+            } else if *day >= period {
+                created += c;
+                completed += d;
+            }
+        }
+        rows.push(StatsRow {
+            period,
+            created,
+            completed,
+            net: created as i64 - completed as i64,
+            backlog: (pending as i64 - net_after).max(0),
+        });
+        period = end;
+    }
+    rows
+}
+
+fn to_csv(rows: &[StatsRow]) -> Result<String> {
+    let mut wtr = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        wtr.serialize(row).context("Failed to write CSV row")?;
+    }
+    let bytes = wtr.into_inner().context("Failed to finish CSV")?;
+    String::from_utf8(bytes).context("CSV is not UTF-8")
+}
+
+fn print_table(rows: &[StatsRow], interval: Interval) {
+    let label = match interval {
+        Interval::Day => "Day",
+        Interval::Week => "Week of",
+    };
+    println!(
+        "\x1b[1m{:<10}  {:>7}  {:>9}  {:>5}  {:>7}\x1b[0m",
+        label, "Created", "Completed", "Net", "Backlog"
+    );
+    for row in rows {
+        let net_color = if row.net > 0 { "33" } else { "32" };
+        println!(
+            "{:<10}  {:>7}  {:>9}  \x1b[{}m{:>+5}\x1b[0m  {:>7}",
+            row.period.to_string(),
+            row.created,
+            row.completed,
+            net_color,
+            row.net,
+            row.backlog
+        );
+    }
+    let created: u64 = rows.iter().map(|r| r.created).sum();
+    let completed: u64 = rows.iter().map(|r| r.completed).sum();
+    println!(
+        "\x1b[90m{:<10}  {:>7}  {:>9}  {:>+5}\x1b[0m",
+        "Total",
+        created,
+        completed,
+        created as i64 - completed as i64
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_series_buckets_and_backlog() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+        let bucket = |d: u32, created, completed| ActivityBucket {
+            hour: day(d).and_hms_opt(10, 0, 0).unwrap().and_utc(),
+            created,
+            completed,
+        };
+        // Mon 12th to Sun 18th, then activity after the range on the 20th
+        let activity = [bucket(12, 3, 1), bucket(14, 1, 2), bucket(20, 2, 0)];
+
+        let daily = series(&activity, &Utc, day(12), day(14), Interval::Day, 5);
+        let summary: Vec<_> = daily
+            .iter()
+            .map(|r| (r.created, r.completed, r.net, r.backlog))
+            .collect();
+        assert_eq!(summary, [(3, 1, 2, 4), (0, 0, 0, 4), (1, 2, -1, 3)]);
+
+        let weekly = series(&activity, &Utc, day(14), day(20), Interval::Week, 5);
+        assert_eq!(weekly.len(), 2);
+        assert_eq!(weekly[0].period, day(12));
+        assert_eq!((weekly[0].created, weekly[0].backlog), (4, 3));
+        assert_eq!((weekly[1].created, weekly[1].backlog), (2, 5));
+
+        assert_eq!("weekly".parse::<Interval>(), Ok(Interval::Week));
+        assert!("monthly".parse::<Interval>().is_err());
+    }
+}
@@ -1,15 +1,27 @@
+use std::io::{self, IsTerminal, Write};
+
 use anyhow::{Context, Result};
-use todoee_core::{config::Config, sync::SyncService};
+use todoee_core::{TodoeeError, config::Config, sync::SyncService};
 
 pub async fn run(force: bool) -> Result<()> {
     // Note: `force` parameter reserved for future use (e.g., skip "recently synced" check)
     let _ = force;
 
     let config = Config::load().context("Failed to load configuration")?;
+    if Config::is_demo() {
+        anyhow::bail!("Sync is turned off in demo mode");
+    }
 
-    let service = SyncService::new(&config)
-        .await
-        .context("Failed to initialize sync service")?;
+    let service = match SyncService::new(&config).await {
+        Ok(service) => service,
+        Err(TodoeeError::Network(reason)) => {
+            anyhow::bail!(
+                "Offline - changes are saved locally and will sync next time ({})",
+                reason
+            );
+        }
+        Err(e) => return Err(e).context("Failed to initialize sync service"),
+    };
 
     if !service.is_configured() {
         println!("\u{2139}  Cloud sync is not configured.\n");
@@ -18,20 +30,71 @@ pub async fn run(force: bool) -> Result<()> {
         println!("  2. Copy your connection string");
         println!("  3. Set the environment variable:");
         println!("     export NEON_DATABASE_URL=\"postgres://...\"");
+        println!("\nOr sync through a folder shared with Dropbox, Syncthing or git:");
+        println!("  todoee config set database.sync_dir ~/Dropbox/todoee");
         println!("\nThen run `todoee sync` again.");
         return Ok(());
     }
 
+    sync_now(&service).await
+}
+
+/// Sync once, showing download progress and a summary of what changed
+pub async fn sync_now(service: &SyncService) -> Result<()> {
     println!("\u{1F504} Syncing with cloud...");
 
-    let result = service.sync().await.context("Sync failed")?;
+    // Large first syncs download in batches; show how far along they are
+    let interactive = io::stdout().is_terminal();
+    let mut progress_shown = false;
+    let result = service
+        .sync_with_progress(|checked| {
+            if interactive && checked > 0 {
+                print!("\r  Checked {} cloud todos...", checked);
+                let _ = io::stdout().flush();
+                progress_shown = true;
+            }
+        })
+        .await;
+    if progress_shown {
+        print!("\r\x1b[2K");
+    }
+    let result = result.context("Sync failed")?;
 
     println!("\u{2713} Sync complete!");
     println!("  Uploaded:   {} todos", result.uploaded);
     println!("  Downloaded: {} todos", result.downloaded);
+    if result.deletions_uploaded > 0 || result.deletions_downloaded > 0 {
+        println!(
+            "  Deleted:    {} pushed, {} pulled",
+            result.deletions_uploaded, result.deletions_downloaded
+        );
+    }
+    if result.categories_merged > 0 {
+        println!(
+            "  Merged:     {} categor{} with an existing cloud category of the same name",
+            result.categories_merged,
+            if result.categories_merged == 1 {
+                "y"
+            } else {
+                "ies"
+            }
+        );
+    }
+    if result.projects_merged > 0 {
+        println!(
+            "  Merged:     {} project(s) with an existing cloud project of the same name",
+            result.projects_merged
+        );
+    }
+    if result.id_collisions > 0 {
+        println!(
+            "  Skipped:    {} todo(s) whose ID belongs to a different todo on the other side",
+            result.id_collisions
+        );
+    }
     if result.conflicts > 0 {
         println!(
-            "  Conflicts:  {} (resolved with last-write-wins)",
+            "  Conflicts:  {} kept for review (todoee conflicts list)",
             result.conflicts
         );
     }
@@ -0,0 +1,185 @@
+//! Time-blocked plan for today: todos due today laid out between calendar
+//! events, saved as reminders so the daemon pings when each block begins.
+
+use std::fs;
+use std::io::{self, IsTerminal};
+
+use anyhow::{Context, Result};
+use chrono::{Duration, Local, NaiveTime, TimeZone, Utc};
+use todoee_core::timeblock::{self, round_up};
+use todoee_core::{
+    Config, EntityType, LocalDb, Operation, OperationType, SyncStatus, TimeBlock, Todo, short_id,
+};
+use uuid::Uuid;
+
+use super::context::ActiveContext;
+use super::journal::local_midnight;
+use super::snooze::parse_duration;
+use super::{ids, output};
+use crate::tui::theme::Theme;
+use crate::tui::timeblock::{Busy, Planner};
+
+pub async fn run(length: Option<String>, yes: bool) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let length = match length {
+        Some(input) => parse_duration(&input)?,
+        None => Duration::minutes(config.timeblock.block_minutes as i64),
+    };
+
+    let db_path = config.local_db_path()?;
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+    let id_len = ids::id_length(&db, &config).await?;
+
+    let today = Local::now().date_naive();
+    let at = |time: NaiveTime| {
+        Local
+            .from_local_datetime(&today.and_time(time))
+            .earliest()
+            .map(|t| t.with_timezone(&Utc))
+            .unwrap_or_else(|| today.and_time(time).and_utc())
+    };
+    let (day_start, day_end) = config.timeblock.hours();
+    let from = round_up(at(day_start).max(Utc::now()));
+    let until = at(day_end);
+    if from + length > until {
+        output::say!(
+            "No time left today before {}. Change it with: todoee config set timeblock.day_end <HH:MM>",
+            day_end.format("%H:%M")
+        );
+        return Ok(());
+    }
+
+    // Open todos due by the end of today, overdue ones included, most
+    // important first
+    let tomorrow = local_midnight(today + Duration::days(1));
+    let mut todos: Vec<Todo> = db
+        .list_todos(true)
+        .await?
+        .into_iter()
+        .filter(|t| t.due_date.is_some_and(|due| due < tomorrow))
+        .collect();
+    if let Some(context) = ActiveContext::load(&db, &config).await? {
+        context.retain(&mut todos);
+    }
+    todos.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then(a.due_date.cmp(&b.due_date))
+    });
+    if todos.is_empty() {
+        output::say!("Nothing due today to plan.");
+        return Ok(());
+    }
+
+    let busy: Vec<Busy> = db
+        .list_event_occurrences(local_midnight(today), tomorrow)
+        .await?
+        .into_iter()
+        .map(|(event, start)| (event.title.clone(), start, start + event.duration()))
+        .collect();
+    let spans: Vec<_> = busy.iter().map(|(_, start, end)| (*start, *end)).collect();
+    let mut blocks = timeblock::schedule(&todos, &spans, from, until, length);
+    blocks.sort_by_key(|b| b.start);
+
+    if !yes && !output::is_json() && io::stdin().is_terminal() && io::stdout().is_terminal() {
+        let planner = Planner::new(blocks, busy, from, until);
+        match crate::tui::timeblock::run(planner, Theme::from_config(&config.display))? {
+            Some(edited) => {
+                blocks = edited;
+                blocks.sort_by_key(|b| b.start);
+            }
+            None => {
+                println!("Plan discarded, no reminders changed.");
+                return Ok(());
+            }
+        }
+    } else if !yes {
+        if output::is_json() {
+            return output::print_json(&blocks);
+        }
+        print_plan(&blocks, &todos, id_len);
+        println!();
+        println!("Set these reminders with: todoee timeblock --yes");
+        return Ok(());
+    }
+
+    let changed = save(&db, &todos, &blocks).await?;
+    if output::is_json() {
+        return output::print_json(&blocks);
+    }
+    print_plan(&blocks, &todos, id_len);
+    println!();
+    output::say!(
+        "\u{2713} Set {} reminder{}",
+        changed,
+        if changed == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+/// Set each block's todo to be reminded when the block starts, as one undo
+/// step. Returns how many todos changed.
+async fn save(db: &LocalDb, todos: &[Todo], blocks: &[TimeBlock]) -> Result<usize> {
+    let group_id = Uuid::new_v4();
+    let mut changed = 0;
+    for block in blocks {
+        let Some(todo) = todos.iter().find(|t| t.id == block.todo_id) else {
+            continue;
+        };
+        if todo.reminder_at == Some(block.start) {
+            continue;
+        }
+        let mut updated = todo.clone();
+        let prev = serde_json::to_value(&updated)?;
+        updated.reminder_at = Some(block.start);
+        updated.updated_at = Utc::now();
+        updated.sync_status = SyncStatus::Pending;
+        db.update_todo(&updated).await?;
+
+        let op = Operation::new(
+            OperationType::Update,
+            EntityType::Todo,
+            updated.id,
+            Some(prev),
+            Some(serde_json::to_value(&updated)?),
+        )
+        .in_group(group_id);
+        db.record_operation(&op).await?;
+        changed += 1;
+    }
+    Ok(changed)
+}
+
+fn print_plan(blocks: &[TimeBlock], todos: &[Todo], id_len: usize) {
+    for block in blocks {
+        println!(
+            "  \x1b[36m{}-{}\x1b[0m {} \x1b[90m[{}]\x1b[0m",
+            block.start.with_timezone(&Local).format("%H:%M"),
+            block.end.with_timezone(&Local).format("%H:%M"),
+            block.title,
+            short_id(&block.todo_id, id_len)
+        );
+    }
+    let unplanned: Vec<&Todo> = todos
+        .iter()
+        .filter(|t| !blocks.iter().any(|b| b.todo_id == t.id))
+        .collect();
+    if !unplanned.is_empty() {
+        println!();
+        println!("\x1b[33mNot planned ({}):\x1b[0m", unplanned.len());
+        for todo in unplanned {
+            println!(
+                "  {} \x1b[90m[{}]\x1b[0m",
+                todo.title,
+                short_id(&todo.id, id_len)
+            );
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Time tracking commands: export focus sessions to other tools.
+
+use std::fs;
+
+use anyhow::{Context, Result};
+use chrono::{Duration, Utc};
+use clap::Subcommand;
+use todoee_core::timetrack::{self, BeeminderUnit};
+use todoee_core::{Config, LocalDb, TimeEntry};
+
+#[derive(Subcommand, Clone)]
+pub enum TrackCommand {
+    /// Export focus sessions as time entries
+    Export {
+        /// Output format: toggl-csv, beeminder or json
+        #[arg(short, long, default_value = "json")]
+        format: String,
+        /// Only sessions from the last N days (default: all)
+        #[arg(long)]
+        days: Option<i64>,
+        /// Write to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Email for Toggl's Email column
+        #[arg(long, default_value = "")]
+        email: String,
+        /// What a Beeminder datapoint counts: hours, minutes or sessions
+        #[arg(long, default_value = "hours")]
+        unit: BeeminderUnit,
+    },
+}
+
+pub async fn run(cmd: TrackCommand) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let db_path = config.local_db_path()?;
+
+    if let Some(parent) = db_path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    let db = LocalDb::new(&db_path).await?;
+    db.run_migrations().await?;
+
+    match cmd {
+        TrackCommand::Export {
+            format,
+            days,
+            output,
+            email,
+            unit,
+        } => export(&db, &format, days, output, &email, unit).await,
+    }
+}
+
+async fn export(
+    db: &LocalDb,
+    format: &str,
+    days: Option<i64>,
+    output: Option<String>,
+    email: &str,
+    unit: BeeminderUnit,
+) -> Result<()> {
+    let since = match days {
+        Some(days) => Utc::now() - Duration::days(days),
+        None => chrono::DateTime::UNIX_EPOCH,
+    };
+    let sessions = db.list_focus_sessions(since).await?;
+    let todos = db.list_todos(false).await?;
+    let categories = db.list_categories().await?;
+    let projects = db.list_projects(true).await?;
+    let entries: Vec<TimeEntry> = sessions
+        .into_iter()
+        .map(|s| TimeEntry::new(s, &todos, &categories, &projects))
+        .collect();
+
+    let content = match format.to_lowercase().as_str() {
+        "toggl-csv" | "toggl" => timetrack::toggl_csv(&entries, email, &chrono::Local),
+        "beeminder" => serde_json::to_string_pretty(&timetrack::beeminder(&entries, unit))
+            .context("Failed to serialize datapoints")?,
+        "json" => {
+            serde_json::to_string_pretty(&entries).context("Failed to serialize time entries")?
+        }
+        other => anyhow::bail!(
+            "Unknown format '{}' (expected toggl-csv, beeminder or json)",
+            other
+        ),
+    };
+
+    match output {
+        Some(path) => {
+            fs::write(&path, &content)
+                .with_context(|| format!("Failed to write export file: {}", path))?;
+            println!(
+                "\u{2713} Exported {} focus session{} to {}",
+                entries.len(),
+                if entries.len() == 1 { "" } else { "s" },
+                path
+            );
+        }
+        None => {
+            print!("{}", content);
+            if !content.ends_with('\n') {
+                println!();
+            }
+        }
+    }
+    Ok(())
+}
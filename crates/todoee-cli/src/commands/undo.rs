@@ -1,6 +1,8 @@
 use anyhow::{Context, Result};
 use std::fs;
-use todoee_core::{Config, EntityType, LocalDb, OperationType, Todo};
+use todoee_core::{Config, EntityType, LocalDb, Operation, OperationType, Todo};
+
+use super::output;
 
 pub async fn run() -> Result<()> {
     let config = Config::load().context("Failed to load configuration")?;
@@ -15,12 +17,25 @@ pub async fn run() -> Result<()> {
 
     let db = LocalDb::new(&db_path).await?;
     db.run_migrations().await?;
+    undo_last(&db).await
+}
 
+/// Undo the newest change not yet undone
+pub async fn undo_last(db: &LocalDb) -> Result<()> {
     let Some(op) = db.get_last_undoable_operation().await? else {
-        println!("Nothing to undo");
+        output::say!("Nothing to undo");
         return Ok(());
     };
 
+    if let Some(group_id) = op.group_id {
+        let ops = db.undo_operation_group(group_id).await?;
+        output::say!("\u{21a9} Undone batch of {} changes:", ops.len());
+        for op in &ops {
+            output::say!("  {} {}", op.operation_type, op_title(op));
+        }
+        return Ok(());
+    }
+
     match (op.operation_type, op.entity_type) {
         (OperationType::Create, EntityType::Todo) => {
             db.delete_todo(op.entity_id).await?;
@@ -30,27 +45,34 @@ pub async fn run() -> Result<()> {
                 .and_then(|s| s.get("title"))
                 .and_then(|t| t.as_str())
                 .unwrap_or("todo");
-            println!("\u{21a9} Undone create: deleted \"{}\"", title);
+            output::say!("\u{21a9} Undone create: deleted \"{}\"", title);
+        }
+        (OperationType::BatchCreate, EntityType::Todo) => {
+            let todos = op.batch_todos();
+            for todo in &todos {
+                db.delete_todo(todo.id).await?;
+            }
+            output::say!("\u{21a9} Undone batch add: deleted {} todos", todos.len());
         }
         (OperationType::Delete, EntityType::Todo) => {
             if let Some(prev) = &op.previous_state {
                 let todo: Todo = serde_json::from_value(prev.clone())?;
-                db.create_todo(&todo).await?;
-                println!("\u{21a9} Undone delete: restored \"{}\"", todo.title);
+                db.restore_todo(&todo).await?;
+                output::say!("\u{21a9} Undone delete: restored \"{}\"", todo.title);
             }
         }
         (OperationType::Update, EntityType::Todo) => {
             if let Some(prev) = &op.previous_state {
                 let todo: Todo = serde_json::from_value(prev.clone())?;
                 db.update_todo(&todo).await?;
-                println!("\u{21a9} Undone edit: reverted \"{}\"", todo.title);
+                output::say!("\u{21a9} Undone edit: reverted \"{}\"", todo.title);
             }
         }
         (OperationType::Complete, EntityType::Todo) => {
             if let Some(mut todo) = db.get_todo(op.entity_id).await? {
                 todo.mark_incomplete();
                 db.update_todo(&todo).await?;
-                println!(
+                output::say!(
                     "\u{21a9} Undone complete: \"{}\" is pending again",
                     todo.title
                 );
@@ -60,7 +82,7 @@ pub async fn run() -> Result<()> {
             if let Some(mut todo) = db.get_todo(op.entity_id).await? {
                 todo.mark_complete();
                 db.update_todo(&todo).await?;
-                println!(
+                output::say!(
                     "\u{21a9} Undone uncomplete: \"{}\" is done again",
                     todo.title
                 );
@@ -69,13 +91,23 @@ pub async fn run() -> Result<()> {
         (OperationType::Stash, EntityType::Todo) => {
             if let Some(prev) = &op.previous_state {
                 let todo: Todo = serde_json::from_value(prev.clone())?;
-                db.create_todo(&todo).await?;
-                println!("\u{21a9} Undone stash: \"{}\" restored", todo.title);
+                db.restore_todo(&todo).await?;
+                output::say!("\u{21a9} Undone stash: \"{}\" restored", todo.title);
             }
         }
-        _ => println!("Cannot undo this operation type"),
+        _ => output::say!("Cannot undo this operation type"),
     }
 
     db.mark_operation_undone(op.id).await?;
     Ok(())
 }
+
+/// Title of the todo an operation touched, from either side of the change
+pub fn op_title(op: &Operation) -> &str {
+    op.new_state
+        .as_ref()
+        .or(op.previous_state.as_ref())
+        .and_then(|s| s.get("title"))
+        .and_then(|t| t.as_str())
+        .unwrap_or("todo")
+}
@@ -4,7 +4,9 @@ use std::fs;
 
 use anyhow::{Context, Result};
 use chrono::{Local, TimeZone, Utc};
-use todoee_core::{Config, LocalDb, Priority, Todo};
+use todoee_core::{Config, LocalDb, Priority, Todo, short_id};
+
+use super::{ids, output};
 
 /// Show the next N upcoming todos by due date.
 pub async fn upcoming(count: usize) -> Result<()> {
@@ -23,13 +25,17 @@ pub async fn upcoming(count: usize) -> Result<()> {
 
     let todos = db.list_todos_upcoming(count).await?;
 
+    if output::is_json() {
+        return output::print_json(&todos);
+    }
+
     if todos.is_empty() {
         println!("No upcoming todos with due dates.");
         return Ok(());
     }
 
     println!("Next {} upcoming:\n", todos.len());
-    print_upcoming_todos(&todos);
+    print_upcoming_todos(&todos, ids::id_length(&db, &config).await?);
     Ok(())
 }
 
@@ -50,25 +56,30 @@ pub async fn overdue() -> Result<()> {
 
     let todos = db.list_todos_overdue().await?;
 
+    if output::is_json() {
+        return output::print_json(&todos);
+    }
+
     if todos.is_empty() {
         println!("\x1b[32mNo overdue todos!\x1b[0m");
         return Ok(());
     }
 
     println!("\x1b[31m{} overdue:\x1b[0m\n", todos.len());
-    print_overdue_todos(&todos);
+    print_overdue_todos(&todos, ids::id_length(&db, &config).await?);
     Ok(())
 }
 
-fn print_upcoming_todos(todos: &[Todo]) {
+fn print_upcoming_todos(todos: &[Todo], id_len: usize) {
     for todo in todos {
         let pri = match todo.priority {
+            Priority::Urgent => "\x1b[1;97;41m!!!\x1b[0m",
             Priority::High => "\x1b[31m!!!\x1b[0m",
             Priority::Medium => "\x1b[33m!! \x1b[0m",
             Priority::Low => "\x1b[90m!  \x1b[0m",
         };
 
-        let id = &todo.id.to_string()[..8];
+        let id = short_id(&todo.id, id_len);
 
         let due = todo
             .due_date
@@ -96,15 +107,16 @@ fn print_upcoming_todos(todos: &[Todo]) {
     }
 }
 
-fn print_overdue_todos(todos: &[Todo]) {
+fn print_overdue_todos(todos: &[Todo], id_len: usize) {
     for todo in todos {
         let pri = match todo.priority {
+            Priority::Urgent => "\x1b[1;97;41m!!!\x1b[0m",
             Priority::High => "\x1b[31m!!!\x1b[0m",
             Priority::Medium => "\x1b[33m!! \x1b[0m",
             Priority::Low => "\x1b[90m!  \x1b[0m",
         };
 
-        let id = &todo.id.to_string()[..8];
+        let id = short_id(&todo.id, id_len);
 
         let overdue_by = todo
             .due_date
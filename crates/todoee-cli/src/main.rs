@@ -1,5 +1,5 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 
 mod commands;
 mod tui;
@@ -22,6 +22,7 @@ mod tui;
 ///   todoee done abc1                           Complete task by short ID
 ///   todoee undo                                Undo last action
 ///   todoee focus                               Start 25-min focus session
+///   todoee list --json | jq '.[].title'        JSON output for scripts
 #[derive(Parser)]
 #[command(name = "todoee")]
 #[command(author, version)]
@@ -38,6 +39,26 @@ struct Cli {
     /// Run in interactive TUI mode (default when no command given)
     #[arg(short, long, global = true)]
     interactive: bool,
+
+    /// Print machine-readable JSON instead of text (also: TODOEE_OUTPUT=json)
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Print only errors; the exit code tells whether the command worked
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Also print what was looked up and where (on stderr)
+    #[arg(long, global = true)]
+    verbose: bool,
+
+    /// Show made-up task names for screenshots and bug reports; changes are not saved
+    #[arg(long, global = true)]
+    demo: bool,
+
+    /// Use a separate config and database (also: TODOEE_PROFILE)
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -61,28 +82,63 @@ enum Commands {
     ///   todoee add "Buy groceries"
     ///   todoee add "Fix bug" -p 3 -c work
     ///   todoee add "Review PR by Friday" --ai
+    ///   todoee add "Review PR by Friday" --ai --dry-run
     ///   todoee add "Meeting" -r "in 30 minutes"
+    ///   todoee add "Deploy 2026-03-01 14:00" --remind-before 2h
+    ///   todoee add "Water plants today" -p 3 --no-nag
+    ///   todoee add "Deploy 🚀"
+    ///   todoee add --stdin -c work < tasks.txt
+    ///   todoee add --from-branch -c work
     #[command(visible_alias = "a")]
     Add {
         /// Task description (AI parses dates, priorities from natural language)
-        #[arg(required = true)]
+        #[arg(required_unless_present_any = ["stdin", "from_branch"])]
         description: Vec<String>,
 
         /// Enable AI parsing for natural language (requires API key)
         #[arg(long)]
         ai: bool,
 
+        /// Read tasks from stdin, one per line (one undo removes them all)
+        #[arg(long, conflicts_with_all = ["description", "ai", "reminder", "remind_before", "no_nag", "icon"])]
+        stdin: bool,
+
+        /// Name the todo after the current git branch
+        #[arg(long, conflicts_with_all = ["description", "stdin", "ai"])]
+        from_branch: bool,
+
         /// Category for the todo
         #[arg(short, long)]
         category: Option<String>,
 
-        /// Priority: 1=low, 2=medium, 3=high
-        #[arg(short, long, value_parser = clap::value_parser!(i32).range(1..=3))]
+        /// Priority: 1=low, 2=medium, 3=high, 4=urgent
+        #[arg(short, long, value_parser = clap::value_parser!(i32).range(1..=4))]
         priority: Option<i32>,
 
         /// Set a reminder (e.g., "in 30 minutes", "in 1 hour", "tomorrow")
         #[arg(short = 'r', long)]
         reminder: Option<String>,
+
+        /// Notify this long before the due time instead of the per-priority
+        /// notifications.advance_* setting (e.g. "2h", "30m", "0")
+        #[arg(long, value_name = "DURATION")]
+        remind_before: Option<String>,
+
+        /// Notify once when overdue, never again every notifications.nag_minutes
+        #[arg(long)]
+        no_nag: bool,
+
+        /// Emoji or symbol shown before the title (default: an emoji at the start or end of it)
+        #[arg(long)]
+        icon: Option<String>,
+
+        /// Save AI-parsed tasks without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Print the parsed task without saving it
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// List todos with optional filters
@@ -91,9 +147,21 @@ enum Commands {
     ///   todoee list              Show pending todos
     ///   todoee list --today      Show today's todos
     ///   todoee list -c work      Filter by category
+    ///   todoee list -P launch    Filter by project
+    ///   todoee list --week 42    Due in ISO week 42 of this year
     ///   todoee list --all        Include completed
+    ///   todoee list --sort urgency   Most urgent first, with the score
+    ///   todoee list "priority:high and due<=+3d and not tag:waiting"
+    ///
+    /// Query terms: category: (tag:), project:, priority:, due: and title
+    /// words, joined with and, or, not and parentheses. priority and due also
+    /// take <, <=, >, >= (due<today, due<=+3d, due>=2026-03-01).
     #[command(visible_alias = "ls")]
     List {
+        /// Filter query, e.g. "priority:high and due<=+3d"
+        #[arg(conflicts_with_all = ["today", "category", "project"])]
+        query: Option<String>,
+
         /// Show only today's todos
         #[arg(long)]
         today: bool,
@@ -102,36 +170,72 @@ enum Commands {
         #[arg(short, long)]
         category: Option<String>,
 
+        /// Filter by project name
+        #[arg(short = 'P', long, conflicts_with = "category")]
+        project: Option<String>,
+
+        /// Show only todos due in this ISO week number (1-53) of the current year
+        #[arg(short, long, conflicts_with = "today")]
+        week: Option<u32>,
+
         /// Show all todos including completed
         #[arg(short, long)]
         all: bool,
+
+        /// One list in this order instead of grouped by category: created,
+        /// due, priority, title or urgency
+        #[arg(short, long)]
+        sort: Option<commands::list::ListSort>,
     },
 
     /// Mark a todo as complete
     ///
-    /// Use short ID prefix (e.g., "abc1") or full UUID
+    /// Use short ID prefix (e.g., "abc1"), full UUID, or the number from the
+    /// last `todoee list` (e.g., 2)
     #[command(visible_alias = "d")]
     Done {
-        /// Todo ID (short prefix like "abc1" or full UUID)
+        /// Todo ID (short prefix like "abc1", full UUID, or list number)
+        id: String,
+    },
+
+    /// Mark a completed todo as incomplete again
+    ///
+    /// Use short ID prefix (e.g., "abc1"), full UUID, or the number from the
+    /// last `todoee list --all` (e.g., 2)
+    #[command(visible_alias = "undone")]
+    Reopen {
+        /// Todo ID (short prefix like "abc1", full UUID, or list number)
         id: String,
     },
 
     /// Permanently delete a todo
     ///
-    /// Use short ID prefix (e.g., "abc1") or full UUID
+    /// Use short ID prefix (e.g., "abc1"), full UUID, or the number from the
+    /// last `todoee list` (e.g., 2)
     #[command(visible_alias = "rm")]
     Delete {
-        /// Todo ID (short prefix like "abc1" or full UUID)
+        /// Todo ID (short prefix like "abc1", full UUID, or list number)
         id: String,
     },
 
-    /// Edit a todo's title, category, or priority
+    /// Edit a todo's title, category, priority, or due date
     ///
     /// Examples:
     ///   todoee edit abc1 --title "New title"
     ///   todoee edit abc1 -p 3 -c work
+    ///   todoee edit abc1 --due "2026-03-01 14:30"
+    ///   todoee edit abc1 --due "friday 3pm"
+    ///   todoee edit abc1 --due none
+    ///   todoee edit abc1 --clear-due
+    ///   todoee edit abc1 --reminder "tomorrow 9am"
+    ///   todoee edit abc1 --clear-reminder
+    ///   todoee edit abc1 --description "Ask about the invoice"
+    ///   todoee edit abc1 -P launch
+    ///   todoee edit abc1 --remind-before 1h
+    ///   todoee edit abc1 --remind-before none
+    ///   todoee edit 2 -p 3             Todo #2 of the last `todoee list`
     Edit {
-        /// Todo ID (short prefix or full UUID)
+        /// Todo ID (short prefix, full UUID, or list number)
         id: String,
 
         /// New title
@@ -142,9 +246,56 @@ enum Commands {
         #[arg(short, long)]
         category: Option<String>,
 
-        /// New priority: 1=low, 2=medium, 3=high
-        #[arg(short, long, value_parser = clap::value_parser!(i32).range(1..=3))]
+        /// New priority: 1=low, 2=medium, 3=high, 4=urgent
+        #[arg(short, long, value_parser = clap::value_parser!(i32).range(1..=4))]
         priority: Option<i32>,
+
+        /// New description; "" clears it
+        #[arg(long)]
+        description: Option<String>,
+
+        /// New due date: YYYY-MM-DD, YYYY-MM-DD HH:MM, a phrase like "friday 3pm", or "none"
+        #[arg(short, long)]
+        due: Option<String>,
+
+        /// Remove the due date
+        #[arg(long, conflicts_with = "due")]
+        clear_due: bool,
+
+        /// New reminder time, e.g. "tomorrow 9am" or "in 30 minutes", or "none"
+        #[arg(short, long)]
+        reminder: Option<String>,
+
+        /// Remove the reminder
+        #[arg(long, conflicts_with = "reminder")]
+        clear_reminder: bool,
+
+        /// Move to a project by name, or "none" to remove it from its project
+        #[arg(short = 'P', long)]
+        project: Option<String>,
+
+        /// Notify this long before the due time (e.g. "2h"), or "none" to
+        /// use the per-priority setting again
+        #[arg(long, value_name = "DURATION")]
+        remind_before: Option<String>,
+
+        /// Stop repeat notifications once the todo is overdue
+        #[arg(long, conflicts_with = "nag")]
+        no_nag: bool,
+
+        /// Repeat notifications again once overdue, undoing --no-nag
+        #[arg(long)]
+        nag: bool,
+    },
+
+    /// AI helpers
+    ///
+    /// Subcommands: revert
+    ///
+    /// Example: todoee ai revert abc1
+    Ai {
+        #[command(subcommand)]
+        command: commands::ai::AiCommand,
     },
 
     // ═══════════════════════════════════════════════════════════════════
@@ -164,6 +315,7 @@ enum Commands {
     ///   todoee log              Show last 10 operations
     ///   todoee log -n 20        Show last 20 operations
     ///   todoee log --oneline    Compact format
+    ///   todoee log --stats      How much history is kept
     Log {
         /// Number of operations to show
         #[arg(short = 'n', long, default_value = "10")]
@@ -172,6 +324,10 @@ enum Commands {
         /// Show one operation per line (compact)
         #[arg(long)]
         oneline: bool,
+
+        /// Show the size of the history instead of its entries
+        #[arg(long)]
+        stats: bool,
     },
 
     /// Show recent changes (like git diff)
@@ -183,6 +339,89 @@ enum Commands {
         hours: Option<i64>,
     },
 
+    /// Show the commands you ran (unlike log, which shows changes made)
+    ///
+    /// Examples:
+    ///   todoee history          Show the last 20 commands
+    ///   todoee history -n 50    Show the last 50 commands
+    History {
+        /// Number of commands to show
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: i64,
+    },
+
+    /// Re-run the last command (like !! in a shell)
+    ///
+    /// Examples:
+    ///   todoee repeat           Re-run the last command
+    ///   todoee repeat 42        Re-run command 42 from 'todoee history'
+    #[command(alias = "!!")]
+    Repeat {
+        /// History number of the command to re-run (default: the last one)
+        id: Option<i64>,
+    },
+
+    /// List your command aliases from the [aliases] config table
+    ///
+    /// Examples:
+    ///   todoee config set aliases.today "list --today"
+    ///   todoee today            Runs 'todoee list --today'
+    ///   todoee alias            Show all aliases
+    Alias,
+
+    /// Named filters applied to list, search, now and the TUI until cleared
+    ///
+    /// Filters combine category:, project:, priority:, due: and title words
+    /// with and, or, not and parentheses.
+    ///
+    /// Examples:
+    ///   todoee context define work "category:work or priority:high"
+    ///   todoee context set work          Apply it from now on
+    ///   todoee context none              Back to everything
+    ///   todoee context list              Show contexts, * marks the active one
+    Context {
+        #[command(subcommand)]
+        command: commands::context::ContextCommand,
+    },
+
+    /// Keep separate configs and databases, such as work and personal
+    ///
+    /// Examples:
+    ///   todoee profile create work       New profile with default settings
+    ///   todoee profile switch work       Use it from now on
+    ///   todoee profile list              Show profiles, * marks the current one
+    ///   todoee --profile work list       One command in another profile
+    Profile {
+        #[command(subcommand)]
+        command: commands::profile::ProfileCommand,
+    },
+
+    /// Complete todos from git commit messages
+    ///
+    /// Commit messages with "todoee:done abc1" or "fixes #abc1" complete the
+    /// todo once committed.
+    ///
+    /// Examples:
+    ///   todoee hook install              Add the post-commit hook to this repo
+    ///   todoee hook uninstall            Remove it
+    Hook {
+        #[command(subcommand)]
+        command: commands::hook::HookCommand,
+    },
+
+    /// Import GitHub issues assigned to you as todos
+    ///
+    /// Reads a token from $GITHUB_TOKEN (github.token_env). Labels map to
+    /// categories with `todoee config set github.labels.<label> <category>`.
+    ///
+    /// Examples:
+    ///   todoee github pull               Issues assigned to you anywhere
+    ///   todoee github pull --repo acme/web
+    Github {
+        #[command(subcommand)]
+        command: commands::github::GithubCommand,
+    },
+
     /// Stash todos temporarily (like git stash)
     ///
     /// Subcommands: push, pop, list, clear
@@ -240,19 +479,65 @@ enum Commands {
 
     /// Search todos with fuzzy matching
     ///
-    /// Searches title and description, ranks by relevance
+    /// Searches title and description, ranks by relevance. Start the query
+    /// with ':' to use the filter syntax of `todoee list` instead.
     ///
-    /// Example: todoee search "meeting"
+    /// Examples:
+    ///   todoee search "meeting"
+    ///   todoee search ":pri:high due<=+3d"
     Search {
-        /// Search query (fuzzy matched)
+        /// Search query (fuzzy matched, or a ':' filter query)
         query: String,
     },
 
+    /// Show today's summary, then events and due todos day by day
+    ///
+    /// Examples:
+    ///   todoee agenda             Today and the next 6 days
+    ///   todoee agenda -d 1        Just today
+    ///   todoee agenda --summary   Due today, overdue and what to start with
+    Agenda {
+        /// Number of days to show, starting today
+        #[arg(short, long, default_value = "7")]
+        days: i64,
+
+        /// Only the summary the daemon sends at notifications.daily_summary_time
+        #[arg(short, long)]
+        summary: bool,
+    },
+
+    /// Manage calendar events
+    ///
+    /// Subcommands: add, list, delete
+    ///
+    /// Examples:
+    ///   todoee event add "standup" 9:30-9:45 --recur weekdays
+    ///   todoee event add "dentist" 14:00 -d tomorrow
+    ///   todoee event list
+    ///   todoee event delete abc1
+    Event {
+        #[command(subcommand)]
+        command: commands::event::EventCommand,
+    },
+
+    /// Show pending todos in an Eisenhower (urgent/important) matrix
+    ///
+    /// Urgent means due within two days; important means high priority.
+    /// Moving a todo adjusts its priority and due date to match.
+    ///
+    /// Examples:
+    ///   todoee matrix
+    ///   todoee matrix move abc1 schedule
+    Matrix {
+        #[command(subcommand)]
+        command: Option<commands::matrix::MatrixCommand>,
+    },
+
     /// Show detailed view of a single todo
     ///
     /// Displays all fields including metadata
     Show {
-        /// Todo ID (short prefix or full UUID)
+        /// Todo ID (short prefix, full UUID, or list number)
         id: String,
     },
 
@@ -262,12 +547,15 @@ enum Commands {
     /// Start a focus session (Pomodoro timer)
     ///
     /// Interactive timer with keyboard controls:
-    ///   Space: pause/resume, q: quit, Enter: complete early
+    ///   Space: pause/resume, q: quit, Enter: complete early,
+    ///   i: log an interruption
     ///
     /// Examples:
     ///   todoee focus              Focus on highest priority (25 min)
     ///   todoee focus abc1         Focus on specific todo
     ///   todoee focus -d 45        Custom duration (45 min)
+    ///   todoee focus --fullscreen Big countdown only, e.g. for a second monitor
+    ///   todoee focus --history    Sessions from the last 30 days
     Focus {
         /// Todo ID to focus on (auto-picks if not specified)
         id: Option<String>,
@@ -275,11 +563,82 @@ enum Commands {
         /// Duration in minutes
         #[arg(short, long, default_value = "25")]
         duration: u32,
+
+        /// Show only the countdown, in big digits (same keys)
+        #[arg(long)]
+        fullscreen: bool,
+
+        /// List recent sessions instead of starting one
+        #[arg(long, conflicts_with_all = ["id", "duration", "fullscreen"])]
+        history: bool,
+    },
+
+    /// Push a todo's reminder later
+    ///
+    /// Duration accepts minutes (45), or units: 10m, 1h, 1h30m, 2d
+    ///
+    /// Examples:
+    ///   todoee snooze abc1 10m
+    ///   todoee snooze abc1 1h30m
+    Snooze {
+        /// Todo ID (or prefix)
+        id: String,
+
+        /// How long to snooze for
+        duration: String,
+    },
+
+    /// Plan today in time blocks and set a reminder for each
+    ///
+    /// Lays the todos due today, most important first, into free time
+    /// between today's events within timeblock.day_start and
+    /// timeblock.day_end. Adjust the blocks on the timeline, then Enter sets
+    /// each todo's reminder to its block's start so the daemon pings you.
+    ///
+    /// Examples:
+    ///   todoee timeblock                 Plan and adjust interactively
+    ///   todoee timeblock --length 45m    45 minute blocks
+    ///   todoee timeblock --yes           Save the suggested plan as is
+    Timeblock {
+        /// Length of each block (default: timeblock.block_minutes)
+        #[arg(short, long)]
+        length: Option<String>,
+
+        /// Save the plan without opening the timeline
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Compact summary for your shell's startup file
+    ///
+    /// Streak, todos due today and overdue, and what `todoee now` would pick.
+    /// Add it to ~/.zshrc or ~/.bashrc. Numbers are cached for up to 10
+    /// minutes (until the next change); if working them out takes longer
+    /// than the budget, the last numbers are shown and refreshed in the
+    /// background.
+    ///
+    /// Examples:
+    ///   todoee greet
+    ///   todoee greet --quote             With a quote of the day
+    ///   todoee greet --budget 50         Wait at most 50ms for fresh numbers
+    Greet {
+        /// Add a quote of the day
+        #[arg(long)]
+        quote: bool,
+
+        /// Milliseconds to spend working out fresh numbers
+        #[arg(long, default_value = "100")]
+        budget: u64,
+
+        /// Update the cache without printing anything
+        #[arg(long, hide = true)]
+        refresh: bool,
     },
 
     /// Suggest what to work on right now
     ///
-    /// Recommends based on priority, due date, and time of day
+    /// Recommends the most urgent todos by their urgency score: due date,
+    /// priority, age, category and project, like taskwarrior's
     Now,
 
     /// Show productivity insights and analytics
@@ -293,6 +652,77 @@ enum Commands {
         days: Option<i64>,
     },
 
+    /// Created and completed todos over time, as a table, CSV or JSON
+    ///
+    /// One row per day or week with todos created, completed, the net change
+    /// and the open backlog at the end of the period.
+    ///
+    /// Examples:
+    ///   todoee stats                                 Last 30 days, daily
+    ///   todoee stats --interval week --since 2026-01-01
+    ///   todoee stats --export csv -o backlog.csv     For a spreadsheet chart
+    Stats {
+        /// First day to include (default: 30 days before --until)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Last day to include (default: today)
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Row length: day or week
+        #[arg(long, default_value = "day")]
+        interval: commands::stats::Interval,
+
+        /// Print the series as csv or json instead of a table
+        #[arg(long, value_name = "FORMAT")]
+        export: Option<String>,
+
+        /// Write the export to a file instead of stdout
+        #[arg(short, long, requires = "export")]
+        output: Option<String>,
+    },
+
+    /// What you completed on a day, with a short reflection
+    ///
+    /// Notes are kept per day; each --note adds a line to that day's entry.
+    ///
+    /// Examples:
+    ///   todoee journal                          Today
+    ///   todoee journal yesterday
+    ///   todoee journal --note "Deep work before lunch helped"
+    Journal {
+        /// Day to show (default: today)
+        date: Option<String>,
+
+        /// Append a line to the day's reflection
+        #[arg(short, long)]
+        note: Option<String>,
+    },
+
+    /// Learn the TUI keys step by step on sample todos
+    ///
+    /// Opens the TUI on a throwaway in-memory database with a checklist of
+    /// steps: moving, adding, completing, filtering, stashing and focusing.
+    /// Your todos and settings are not touched.
+    Tutorial,
+
+    /// Export focus sessions to time-tracking tools
+    ///
+    /// Subcommands: export
+    ///
+    /// Formats: toggl-csv (Toggl Track CSV import), beeminder (JSON for
+    /// Beeminder's datapoints/create_all) and json
+    ///
+    /// Examples:
+    ///   todoee track export --format toggl-csv --email me@example.com -o toggl.csv
+    ///   todoee track export --format beeminder --unit sessions --days 7
+    ///   todoee track export --days 30              All fields as JSON
+    Track {
+        #[command(subcommand)]
+        command: commands::track::TrackCommand,
+    },
+
     // ═══════════════════════════════════════════════════════════════════
     // BATCH & MAINTENANCE
     // ═══════════════════════════════════════════════════════════════════
@@ -309,41 +739,139 @@ enum Commands {
         command: commands::batch::BatchCommand,
     },
 
-    /// Clean up old completed todos and operations
+    /// Move the due times of pending todos to one time of day, undoable as one change
+    ///
+    /// Examples:
+    ///   todoee normalize-dates --time 09:00                    Preview, then confirm
+    ///   todoee normalize-dates --time 09:00 --timezone Europe/Berlin
+    ///   todoee normalize-dates --time 17:00 -c work --preview  Only show the changes
+    ///   todoee normalize-dates --time none -y                  Drop times, keep dates
+    NormalizeDates {
+        /// Time of day as HH:MM, or "none" to keep only the date
+        #[arg(short, long)]
+        time: String,
+
+        /// IANA timezone the time is in, e.g. Europe/Berlin (default: local time)
+        #[arg(short = 'z', long)]
+        timezone: Option<String>,
+
+        /// Only todos in this category
+        #[arg(short, long)]
+        category: Option<String>,
+
+        /// Only todos in this project
+        #[arg(short = 'P', long, conflicts_with = "category")]
+        project: Option<String>,
+
+        /// Also give date-only due dates the time
+        #[arg(long)]
+        include_date_only: bool,
+
+        /// Show what would change without rewriting anything
+        #[arg(long)]
+        preview: bool,
+
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Clean up old completed todos, deleted todos, operations and expired AI results
     ///
     /// Examples:
-    ///   todoee gc                 Delete items older than 30 days
+    ///   todoee gc                 Delete items older than gc.days (30 by default)
     ///   todoee gc --days 7        Delete items older than 7 days
     ///   todoee gc --dry-run       Preview what would be deleted
+    ///   todoee gc --compact       Collapse old edit history instead of deleting it
+    ///
+    /// Set gc.auto to clean up on a schedule (gc.interval: daily, weekly or
+    /// monthly); gc.exclude_categories keeps completed todos in those categories.
     Gc {
-        /// Delete items older than N days
-        #[arg(short, long, default_value = "30")]
+        /// Delete items older than N days [default: gc.days]
+        #[arg(short, long)]
         days: Option<i64>,
 
         /// Preview only, don't actually delete
         #[arg(long)]
         dry_run: bool,
+
+        /// Collapse old consecutive updates per todo into one snapshot
+        /// instead of deleting old operation history
+        #[arg(long)]
+        compact: bool,
     },
 
-    /// Export todos to JSON or CSV file
+    /// Export todos to a JSON, CSV, org-mode, markdown, iCalendar or Taskwarrior file
     ///
     /// Examples:
     ///   todoee export                          Export to JSON (default)
     ///   todoee export -o backup.json           Export to specific file
     ///   todoee export --format csv             Export as CSV
+    ///   todoee export --format org             Export as org-mode headings
+    ///   todoee export --format md              Export as a markdown checklist
+    ///   todoee export --format ics             Export as iCalendar VTODOs
+    ///   todoee export --format taskwarrior     Export for `task import`
     ///   todoee export --include-completed      Include completed todos
+    ///   todoee export --id abc1 --with-history One todo with its history as JSON
     Export {
         /// Output file path (default: todoee_export_<timestamp>.<format>)
         #[arg(short, long)]
         output: Option<String>,
 
-        /// Export format: json or csv
+        /// Export format: json, csv, org, md, ics or taskwarrior
         #[arg(short, long, default_value = "json")]
         format: String,
 
         /// Include completed todos in export
         #[arg(long)]
         include_completed: bool,
+
+        /// Export only this todo (ID prefix), with its category, project and note
+        #[arg(long, value_name = "ID", conflicts_with_all = ["format", "include_completed"])]
+        id: Option<String>,
+
+        /// Include the todo's operation history (with --id)
+        #[arg(long, requires = "id")]
+        with_history: bool,
+    },
+
+    /// Serve a JSON API over the local database for web and mobile frontends
+    ///
+    /// Requests need "Authorization: Bearer <token>"; the token is created in
+    /// auth.json on first run and printed at startup.
+    ///
+    /// Examples:
+    ///   todoee serve                           Serve on http://127.0.0.1:8080/api
+    ///   todoee serve --port 9000               Use another port
+    ///   todoee serve --bind 0.0.0.0            Reachable from other machines
+    Serve {
+        /// Port to listen on
+        #[arg(short, long, default_value = "8080")]
+        port: u16,
+
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: String,
+    },
+
+    /// Serve a read-only iCalendar feed for calendar apps to subscribe to
+    ///
+    /// Examples:
+    ///   todoee serve-ics                       Serve on http://127.0.0.1:8080/todoee.ics
+    ///   todoee serve-ics --port 9000           Use another port
+    ///   todoee serve-ics --bind 0.0.0.0        Reachable from other machines
+    ServeIcs {
+        /// Port to listen on
+        #[arg(short, long, default_value = "8080")]
+        port: u16,
+
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: String,
+
+        /// Include completed todos in the feed
+        #[arg(long)]
+        include_completed: bool,
     },
 
     /// Import todos from a file
@@ -352,13 +880,41 @@ enum Commands {
     ///   todoee import backup.json              Import from JSON file
     ///   todoee import backup.json --mode merge Skip existing todos
     ///   todoee import backup.json --mode replace Overwrite existing todos
+    ///   todoee import tasks.org                Import org-mode TODO headings
+    ///   todoee import tasks.txt -c work -p 3   Import one task per line
+    ///   todoee import tasks.md                 Import a markdown checklist
+    ///   todoee import tw.json -f taskwarrior   Import a Taskwarrior `task export`
+    ///   todoee import list.md --format lines   Force plain text parsing
+    ///   todoee import --from-markdown-dir ~/notes  Import unchecked "- [ ]" items
+    ///   todoee import --from-markdown-dir ~/notes --two-way
     Import {
-        /// Input file path
-        input: String,
+        /// Input file path (.json export, .org file, .md checklist or .txt task list)
+        #[arg(required_unless_present = "from_markdown_dir")]
+        input: Option<String>,
 
         /// Import mode: merge (skip existing) or replace (overwrite)
         #[arg(short, long, default_value = "merge")]
         mode: String,
+
+        /// File format: json, org, md, taskwarrior or lines (default: from the file extension)
+        #[arg(short, long)]
+        format: Option<String>,
+
+        /// Category for every task (lines format)
+        #[arg(short, long)]
+        category: Option<String>,
+
+        /// Priority for every task (lines format): 1=low, 2=medium, 3=high, 4=urgent
+        #[arg(short, long, value_parser = clap::value_parser!(i32).range(1..=4))]
+        priority: Option<i32>,
+
+        /// Import unchecked checkboxes from a directory of markdown notes
+        #[arg(long, value_name = "DIR", conflicts_with = "input")]
+        from_markdown_dir: Option<String>,
+
+        /// Tick the checkbox in the note when its todo is completed, and the reverse
+        #[arg(long, requires = "from_markdown_dir")]
+        two_way: bool,
     },
 
     /// Sync with cloud storage
@@ -368,13 +924,136 @@ enum Commands {
         force: bool,
     },
 
+    /// List and rename categories
+    ///
+    /// Subcommands: list, rename
+    ///
+    /// Examples:
+    ///   todoee category list                      Show categories with todo counts
+    ///   todoee category rename work job --preview See what a rename would change
+    ///   todoee category rename work job           Rename after confirming
+    Category {
+        #[command(subcommand)]
+        command: commands::category::CategoryCommand,
+    },
+
+    /// Group todos into projects
+    ///
+    /// Subcommands: create, list, archive
+    ///
+    /// Examples:
+    ///   todoee project create launch     Create a project
+    ///   todoee project list              Show projects with progress bars
+    ///   todoee project archive launch    Hide a finished project
+    Project {
+        #[command(subcommand)]
+        command: commands::project::ProjectCommand,
+    },
+
+    /// Keep timestamped progress notes on a todo
+    ///
+    /// Subcommands: add, list
+    ///
+    /// Examples:
+    ///   todoee note add abc1 "spoke with vendor"  Append a note
+    ///   todoee note list abc1                     Show the todo's notes
+    Note {
+        #[command(subcommand)]
+        command: commands::note::NoteCommand,
+    },
+
+    /// Set, clear and list todo reminders
+    ///
+    /// Subcommands: set, clear, list
+    ///
+    /// Examples:
+    ///   todoee remind set abc1 "tomorrow 9am"   Remind at 9:00 tomorrow
+    ///   todoee remind clear abc1                Remove the reminder
+    ///   todoee remind list                      Upcoming reminders, soonest first
+    Remind {
+        #[command(subcommand)]
+        command: commands::remind::RemindCommand,
+    },
+
+    /// Link a file or URL to a todo
+    ///
+    /// Attachments are listed by `todoee show` and opened from the TUI
+    /// detail view with `o`.
+    ///
+    /// Examples:
+    ///   todoee attach abc1 ~/docs/spec.pdf          Attach a file
+    ///   todoee attach abc1 https://example.com/pr   Attach a URL
+    Attach {
+        /// Todo ID (or prefix)
+        id: String,
+        /// File path or URL
+        target: String,
+    },
+
+    /// Manage devices allowed to sync
+    ///
+    /// Examples:
+    ///   todoee devices list              Show registered devices
+    ///   todoee devices revoke abc1       Stop a lost device from syncing
+    ///   todoee devices wipe              Delete this device's local database
+    Devices {
+        #[command(subcommand)]
+        command: commands::devices::DevicesCommand,
+    },
+
+    /// Review todos changed both here and on another device
+    ///
+    /// Sync keeps both versions of such a todo instead of letting the newer
+    /// edit win, and stops syncing it until you choose.
+    ///
+    /// Examples:
+    ///   todoee conflicts list                        Both versions, field by field
+    ///   todoee conflicts resolve abc1 --keep remote
+    ///   todoee conflicts resolve abc1 --keep merge   Pick each field
+    Conflicts {
+        #[command(subcommand)]
+        command: commands::conflicts::ConflictsCommand,
+    },
+
+    /// Log in so sync only exchanges your todos
+    ///
+    /// Creates the account the first time, and the first account takes over
+    /// the todos already in the database. Log in on each device that shares
+    /// it.
+    ///
+    /// Examples:
+    ///   todoee login                     Prompt for email and password
+    ///   todoee login --email ada@example.com
+    Login {
+        /// Email to log in as
+        #[arg(long)]
+        email: Option<String>,
+    },
+
+    /// Log out and end this device's session
+    Logout,
+
+    /// Show the account this device syncs as
+    Whoami,
+
     /// Configure todoee settings
     ///
     /// Use --init for interactive setup wizard
+    ///
+    /// Examples:
+    ///   todoee config                          Show current configuration
+    ///   todoee config --init                   Run the setup wizard
+    ///   todoee config get ai.model             Print a single setting
+    ///   todoee config set display.theme light  Change a setting
+    ///   todoee config export settings.toml     Save settings for another machine
+    ///   todoee config import settings.toml     Apply saved settings
     Config {
         /// Run interactive configuration wizard
         #[arg(long)]
         init: bool,
+
+        #[command(subcommand)]
+        command: Option<commands::config::ConfigCommand>,
     },
 
     /// Manage the background daemon
@@ -390,6 +1069,38 @@ enum Commands {
         action: DaemonAction,
     },
 
+    /// Download and install the latest release
+    ///
+    /// The download is only installed if its signature checks out. Turn this
+    /// off with updates.self_update = false when todoee comes from a package
+    /// manager.
+    ///
+    /// Examples:
+    ///   todoee self-update            Update to the latest release
+    ///   todoee self-update --check    Only show whether there is one
+    #[command(name = "self-update")]
+    SelfUpdate {
+        /// Only check for a new version, don't install it
+        #[arg(long)]
+        check: bool,
+
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Developer tools (built with --features dev-tools)
+    ///
+    /// Examples:
+    ///   todoee dev seed --todos 5000 --categories 20 --days 365
+    ///   todoee dev seed --db /tmp/bench.db --seed 42
+    #[cfg(feature = "dev-tools")]
+    #[command(hide = true)]
+    Dev {
+        #[command(subcommand)]
+        command: commands::dev::DevCommand,
+    },
+
     // ═══════════════════════════════════════════════════════════════════
     // HELP
     // ═══════════════════════════════════════════════════════════════════
@@ -403,36 +1114,174 @@ enum Commands {
     Help,
 }
 
+/// Names and aliases of every built-in subcommand, which user aliases can't override.
+fn builtin_commands() -> Vec<String> {
+    let mut names = vec!["help".to_string()];
+    for cmd in Cli::command().get_subcommands() {
+        names.push(cmd.get_name().to_string());
+        names.extend(cmd.get_all_aliases().map(String::from));
+    }
+    names
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let args: Vec<String> = std::env::args_os()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect();
+    // Before anything reads the config, which lives in the profile's directory
+    if let Some(name) = commands::alias::profile_flag(&args) {
+        todoee_core::Config::use_profile(&name)?;
+    }
+
+    // Expand user aliases before clap sees the arguments, like git does
+    let builtins = builtin_commands();
+    let aliases = todoee_core::Config::load()
+        .map(|config| config.aliases)
+        .unwrap_or_default();
+    if let Some(name) = commands::alias::command_position(&args).map(|pos| &args[pos])
+        && aliases.contains_key(name)
+        && builtins.contains(name)
+    {
+        eprintln!(
+            "\x1b[33mwarning:\x1b[0m alias '{}' is ignored because it collides with a built-in command",
+            name
+        );
+    }
+    let cli = Cli::parse_from(commands::alias::expand(args, &aliases, &builtins)?);
+    commands::output::init(
+        cli.json,
+        commands::output::Verbosity::from_flags(cli.quiet, cli.verbose),
+    );
+    if !matches!(cli.command, Some(Commands::Profile { .. })) {
+        commands::profile::check_exists(cli.profile.or_else(todoee_core::Config::profile))?;
+    }
+    if commands::output::verbosity() == commands::output::Verbosity::Verbose
+        && let Ok(path) = todoee_core::Config::load().and_then(|c| c.local_db_path())
+    {
+        eprintln!("Database: {}", path.display());
+    }
+    if cli.demo {
+        commands::demo::start().await?;
+    }
+
+    // Catch a damaged database before anything uses it; commands that don't
+    // touch the database still work
+    if !matches!(
+        cli.command,
+        Some(
+            Commands::Config { .. }
+                | Commands::Help
+                | Commands::Profile { .. }
+                | Commands::Context { .. }
+                | Commands::SelfUpdate { .. }
+                | Commands::Tutorial
+                | Commands::Greet { .. }
+        )
+    ) {
+        commands::recover::check_startup().await?;
+    }
 
     // If no command provided or -i flag, run interactive mode
     if cli.command.is_none() || cli.interactive {
         return run_interactive().await;
     }
 
+    let command = cli.command.unwrap();
+    // Greet runs at every shell startup and must stay quick; the git hook
+    // prints into git's output
+    let update_notice = !matches!(
+        command,
+        Commands::SelfUpdate { .. }
+            | Commands::Greet { .. }
+            | Commands::Hook {
+                command: commands::hook::HookCommand::PostCommit
+            }
+    );
+    let auto_gc = !matches!(
+        command,
+        Commands::Gc { .. }
+            | Commands::Config { .. }
+            | Commands::Help
+            | Commands::SelfUpdate { .. }
+            | Commands::Tutorial
+            | Commands::Greet { .. }
+    );
+
+    // Remember the invocation for `history` and `repeat`. Best effort: a
+    // failure here must not stop the command itself.
+    if !matches!(
+        command,
+        Commands::History { .. }
+            | Commands::Repeat { .. }
+            | Commands::Greet { .. }
+            | Commands::Hook {
+                command: commands::hook::HookCommand::PostCommit
+            }
+    ) {
+        let args: Vec<String> = std::env::args_os()
+            .skip(1)
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        let _ = commands::history::record(&args).await;
+    }
+
     // Handle subcommands
-    match cli.command.unwrap() {
+    match command {
         Commands::Add {
             description,
             ai,
             category,
             priority,
             reminder,
+            remind_before,
+            no_nag,
+            icon,
+            yes,
+            dry_run,
+            stdin,
+            from_branch,
         } => {
-            commands::add(description, ai, category, priority, reminder).await?;
+            let description = if from_branch {
+                let dir = std::env::current_dir()?;
+                vec![todoee_core::git::branch_title(
+                    &todoee_core::git::current_branch(&dir)?,
+                )]
+            } else {
+                description
+            };
+            commands::add(
+                description,
+                ai,
+                category,
+                priority,
+                reminder,
+                remind_before,
+                no_nag,
+                icon,
+                yes,
+                dry_run,
+                stdin,
+            )
+            .await?;
         }
         Commands::List {
+            query,
             today,
             category,
+            project,
+            week,
             all,
+            sort,
         } => {
-            commands::list(today, category, all).await?;
+            commands::list(query, today, category, project, week, all, sort).await?;
         }
         Commands::Done { id } => {
             commands::done(id).await?;
         }
+        Commands::Reopen { id } => {
+            commands::reopen::run(id).await?;
+        }
         Commands::Delete { id } => {
             commands::delete(id).await?;
         }
@@ -441,14 +1290,68 @@ async fn main() -> Result<()> {
             title,
             category,
             priority,
+            description,
+            due,
+            clear_due,
+            reminder,
+            clear_reminder,
+            project,
+            remind_before,
+            no_nag,
+            nag,
         } => {
-            commands::edit(id, title, category, priority).await?;
+            let no_nag = (no_nag || nag).then_some(no_nag);
+            commands::edit(
+                id,
+                title,
+                category,
+                priority,
+                description,
+                due,
+                clear_due,
+                reminder,
+                clear_reminder,
+                project,
+                remind_before,
+                no_nag,
+            )
+            .await?;
         }
         Commands::Sync { force } => {
             commands::sync(force).await?;
         }
-        Commands::Config { init } => {
-            commands::config(init).await?;
+        Commands::Category { command } => {
+            commands::category::run(command).await?;
+        }
+        Commands::Project { command } => {
+            commands::project::run(command).await?;
+        }
+        Commands::Note { command } => {
+            commands::note::run(command).await?;
+        }
+        Commands::Remind { command } => {
+            commands::remind::run(command).await?;
+        }
+        Commands::Attach { id, target } => {
+            commands::attach::run(id, target).await?;
+        }
+        Commands::Devices { command } => {
+            commands::devices::run(command).await?;
+        }
+        Commands::Conflicts { command } => {
+            commands::conflicts::run(command).await?;
+        }
+        Commands::Login { email } => {
+            commands::login::login(email).await?;
+        }
+        Commands::Logout => {
+            commands::login::logout().await?;
+        }
+        Commands::Whoami => {
+            commands::login::whoami()?;
+        }
+        Commands::Config { init, command } => {
+            commands::config(init, command).await?;
         }
         Commands::Daemon { action } => match action {
             DaemonAction::Start => commands::daemon::run_start().await?,
@@ -461,12 +1364,25 @@ async fn main() -> Result<()> {
         Commands::Redo => {
             commands::redo().await?;
         }
-        Commands::Log { limit, oneline } => {
-            commands::log::run(limit, oneline).await?;
+        Commands::Log {
+            limit,
+            oneline,
+            stats,
+        } => {
+            commands::log::run(limit, oneline, stats).await?;
         }
         Commands::Diff { hours } => {
             commands::diff::run(hours).await?;
         }
+        Commands::History { limit } => {
+            commands::history::run(limit).await?;
+        }
+        Commands::Repeat { id } => {
+            commands::history::repeat(id).await?;
+        }
+        Commands::Alias => {
+            commands::alias::list(&builtin_commands())?;
+        }
         Commands::Head { count, all } => {
             commands::head::head(count, all).await?;
         }
@@ -485,27 +1401,122 @@ async fn main() -> Result<()> {
         Commands::Show { id } => {
             commands::show::run(&id).await?;
         }
+        Commands::Ai { command } => {
+            commands::ai::run(command).await?;
+        }
+        Commands::Agenda { days, summary } => {
+            commands::agenda::run(days, summary).await?;
+        }
+        Commands::Event { command } => {
+            commands::event::run(command).await?;
+        }
+        Commands::Matrix { command } => {
+            commands::matrix::run(command).await?;
+        }
+        Commands::Context { command } => {
+            commands::context::run(command)?;
+        }
+        Commands::Hook { command } => {
+            commands::hook::run(command).await?;
+        }
+        Commands::Github { command } => {
+            commands::github::run(command).await?;
+        }
+        Commands::Profile { command } => {
+            commands::profile::run(command)?;
+        }
         Commands::Stash { command } => {
             commands::stash::run(command).await?;
         }
         Commands::Batch { command } => {
             commands::batch::run(command).await?;
         }
-        Commands::Gc { days, dry_run } => {
-            commands::gc::run(days, dry_run).await?;
+        Commands::NormalizeDates {
+            time,
+            timezone,
+            category,
+            project,
+            include_date_only,
+            preview,
+            yes,
+        } => {
+            commands::normalize::run(
+                time,
+                timezone,
+                category,
+                project,
+                include_date_only,
+                preview,
+                yes,
+            )
+            .await?;
+        }
+        Commands::Gc {
+            days,
+            dry_run,
+            compact,
+        } => {
+            commands::gc::run(days, dry_run, compact).await?;
         }
         Commands::Export {
             output,
             format,
             include_completed,
+            id,
+            with_history,
+        } => match id {
+            Some(id) => commands::export::run_todo(&id, output, with_history).await?,
+            None => commands::export::run(output, format, include_completed).await?,
+        },
+        Commands::Serve { port, bind } => {
+            commands::serve::run(port, bind).await?;
+        }
+        Commands::ServeIcs {
+            port,
+            bind,
+            include_completed,
+        } => {
+            commands::serve_ics::run(port, bind, include_completed).await?;
+        }
+        Commands::Import {
+            input,
+            mode,
+            format,
+            category,
+            priority,
+            from_markdown_dir,
+            two_way,
+        } => match (from_markdown_dir, input) {
+            (Some(dir), _) => commands::import::run_markdown(dir, two_way).await?,
+            (None, Some(input)) => {
+                commands::import::run(input, mode, format, category, priority).await?
+            }
+            (None, None) => unreachable!("clap requires an input file or --from-markdown-dir"),
+        },
+        Commands::Focus {
+            id,
+            duration,
+            fullscreen,
+            history,
         } => {
-            commands::export::run(output, format, include_completed).await?;
+            if history {
+                commands::focus::history().await?;
+            } else {
+                commands::focus::run(id, duration, fullscreen).await?;
+            }
         }
-        Commands::Import { input, mode } => {
-            commands::import::run(input, mode).await?;
+        Commands::Snooze { id, duration } => {
+            commands::snooze::run(id, duration).await?;
         }
-        Commands::Focus { id, duration } => {
-            commands::focus::run(id, duration).await?;
+        Commands::Timeblock { length, yes } => {
+            commands::timeblock::run(length, yes).await?;
+        }
+        Commands::Greet {
+            quote,
+            budget,
+            refresh,
+        } => {
+            commands::greet::run(quote, budget, refresh).await?;
         }
         Commands::Now => {
             commands::now::run().await?;
@@ -513,18 +1524,62 @@ async fn main() -> Result<()> {
         Commands::Insights { days } => {
             commands::insights::run(days).await?;
         }
+        Commands::Stats {
+            since,
+            until,
+            interval,
+            export,
+            output,
+        } => {
+            commands::stats::run(since, until, interval, export, output).await?;
+        }
+        Commands::Journal { date, note } => {
+            commands::journal::run(date, note).await?;
+        }
+        Commands::Tutorial => {
+            run_tui(tui::tutorial::app().await?).await?;
+        }
+        Commands::Track { command } => {
+            commands::track::run(command).await?;
+        }
+        #[cfg(feature = "dev-tools")]
+        Commands::Dev { command } => {
+            commands::dev::run(command).await?;
+        }
+        Commands::SelfUpdate { check, yes } => {
+            commands::self_update::run(check, yes).await?;
+        }
         Commands::Help => {
             commands::help()?;
         }
     }
 
+    if auto_gc {
+        commands::gc::auto().await;
+    }
+    if update_notice {
+        commands::self_update::notice().await;
+    }
+
     Ok(())
 }
 
 /// Run the interactive TUI
 async fn run_interactive() -> Result<()> {
-    // Initialize application state
-    let mut app = tui::App::new().await?;
+    run_tui(tui::App::new().await?).await
+}
+
+/// Run the TUI until the user quits
+async fn run_tui(mut app: tui::App) -> Result<()> {
+    // Help lists the same commands as `todoee --help`
+    app.cli_commands = Cli::command()
+        .get_subcommands()
+        .filter(|cmd| !cmd.is_hide_set())
+        .map(|cmd| {
+            let about = cmd.get_about().map(|a| a.to_string()).unwrap_or_default();
+            (cmd.get_name().to_string(), about)
+        })
+        .collect();
 
     // Initialize terminal
     let mut terminal = tui::Tui::new()?;
@@ -545,6 +1600,9 @@ async fn run_interactive() -> Result<()> {
             tui::Event::Key(key) => {
                 tui::handle_key_event(&mut app, key).await?;
             }
+            tui::Event::Paste(text) => {
+                tui::handle_paste(&mut app, &text);
+            }
             tui::Event::Mouse(_) => {
                 // Mouse support could be added here
             }
@@ -1,11 +1,26 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::time::Duration;
+
 use anyhow::Result;
-use chrono::{Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, Local, Utc};
+use todoee_core::conflict::{Field as ConflictField, Side};
 use todoee_core::{
-    Category, Config, EntityType, LocalDb, Operation, OperationType, Priority, Todo,
+    Attachment, Category, CategoryCount, Config, Conflict, DaemonStatus, DateInput, DueUrgency,
+    EntityType, FocusOutcome, FocusSession, FocusTime, LocalDb, MIN_ID_LENGTH, Operation,
+    OperationType, Priority, Project, ProjectProgress, Quadrant, SyncHealth, SyncService, Todo,
+    TodoNote, TodoeeError, dates, urgency,
 };
 use tui_input::Input;
 
+use crate::commands::context::ActiveContext;
+use crate::commands::journal::local_midnight;
+
+use super::keymap::{HelpContext, HelpLine, help_lines};
 use super::spinner::Spinner;
+use super::theme::Theme;
+use super::tutorial::TutorialState;
+use super::widgets::celebration;
 
 /// Progress state for multi-step loading operations
 #[derive(Debug, Clone, Default)]
@@ -44,6 +59,9 @@ impl LoadingProgress {
     }
 }
 
+/// Tasks shown in zen mode
+pub const ZEN_TASKS: usize = 3;
+
 /// Main view/tab of the application
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum View {
@@ -51,6 +69,7 @@ pub enum View {
     Todos,
     Categories,
     Settings,
+    Projects,
 }
 
 /// Application mode
@@ -73,12 +92,22 @@ pub enum Mode {
     ViewingDetail,
     /// Adding a new category
     AddingCategory,
+    /// Adding a new project
+    AddingProject,
     /// Adding a new task with full fields
     AddingFull,
     /// Viewing insights
     Insights,
     /// Focus/pomodoro mode
     Focus,
+    /// Eisenhower matrix view
+    Matrix,
+    /// Typing a new value for the selected setting
+    EditingSetting,
+    /// Only the top recommended tasks, nothing else on screen
+    Zen,
+    /// Reviewing a sync conflict field by field
+    Conflicts,
 }
 
 /// State for focus/pomodoro mode
@@ -90,6 +119,10 @@ pub struct FocusState {
     pub started_at: std::time::Instant,
     pub paused: bool,
     pub paused_remaining: Option<u64>,
+    /// Saved when the session ends, with its interruptions
+    pub session: FocusSession,
+    /// Reason being typed after `i`; the timer keeps running meanwhile
+    pub reason_input: Option<String>,
 }
 
 impl FocusState {
@@ -101,6 +134,18 @@ impl FocusState {
             started_at: std::time::Instant::now(),
             paused: false,
             paused_remaining: None,
+            session: FocusSession {
+                planned_minutes: u32::try_from(duration_mins).ok(),
+                ..FocusSession::new(todo.id, chrono::Utc::now())
+            },
+            reason_input: None,
+        }
+    }
+
+    /// Log the typed reason as an interruption and stop typing
+    pub fn log_interruption(&mut self) {
+        if let Some(reason) = self.reason_input.take() {
+            self.session.interrupt(&reason, chrono::Utc::now());
         }
     }
 
@@ -133,6 +178,206 @@ impl FocusState {
     }
 }
 
+/// State for the help modal
+#[derive(Debug, Clone)]
+pub struct HelpState {
+    /// Where help was opened, which decides the sections shown open
+    pub context: HelpContext,
+    /// Mode to return to when help closes
+    pub return_mode: Mode,
+    /// First visible line
+    pub scroll: usize,
+    /// Lines that fit on screen, updated on every draw
+    pub page: Cell<usize>,
+    /// Search text typed after `/`
+    pub query: String,
+    /// Whether keys are going into the search
+    pub searching: bool,
+    /// Open every section, not only the ones for the context
+    pub show_all: bool,
+}
+
+impl HelpState {
+    pub fn new(context: HelpContext, return_mode: Mode) -> Self {
+        Self {
+            context,
+            return_mode,
+            scroll: 0,
+            page: Cell::new(1),
+            query: String::new(),
+            searching: false,
+            show_all: false,
+        }
+    }
+
+    /// Lines to show, from the keymap and `commands`
+    pub fn lines(&self, commands: &[(String, String)]) -> Vec<HelpLine> {
+        help_lines(self.context, commands, &self.query, self.show_all)
+    }
+
+    /// Scroll by `delta` lines, staying within `line_count`
+    pub fn scroll_by(&mut self, delta: isize, line_count: usize) {
+        let max = line_count.saturating_sub(self.page.get());
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+}
+
+/// State for the Eisenhower matrix modal
+#[derive(Debug, Clone, Default)]
+pub struct MatrixState {
+    /// Pending todos per quadrant, indexed by `Quadrant::index`
+    pub quadrants: [Vec<Todo>; 4],
+    /// Quadrant holding the cursor
+    pub active: usize,
+    /// Selected row within the active quadrant
+    pub selected: usize,
+}
+
+impl MatrixState {
+    /// Bucket pending todos into quadrants as of `now`.
+    pub fn new(todos: Vec<Todo>, now: chrono::DateTime<Utc>) -> Self {
+        let mut quadrants: [Vec<Todo>; 4] = Default::default();
+        for todo in todos.into_iter().filter(|t| !t.is_completed) {
+            quadrants[todo.quadrant(now).index()].push(todo);
+        }
+        for todos in &mut quadrants {
+            todos.sort_by_key(|t| {
+                (
+                    t.due_date.is_none(),
+                    t.due_date,
+                    std::cmp::Reverse(t.priority),
+                )
+            });
+        }
+        Self {
+            quadrants,
+            active: 0,
+            selected: 0,
+        }
+    }
+
+    pub fn selected_todo(&self) -> Option<&Todo> {
+        self.quadrants[self.active].get(self.selected)
+    }
+
+    /// Put the cursor on the todo with the given ID, if present.
+    pub fn select_todo(&mut self, id: uuid::Uuid) {
+        for (q, todos) in self.quadrants.iter().enumerate() {
+            if let Some(row) = todos.iter().position(|t| t.id == id) {
+                self.active = q;
+                self.selected = row;
+                return;
+            }
+        }
+        self.clamp();
+    }
+
+    /// Move down, continuing into the quadrant below at the end of the list.
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.quadrants[self.active].len() {
+            self.selected += 1;
+        } else if self.active < 2 {
+            self.active += 2;
+            self.selected = 0;
+        }
+    }
+
+    /// Move up, continuing into the quadrant above at the top of the list.
+    pub fn select_previous(&mut self) {
+        if self.selected > 0 {
+            self.selected -= 1;
+        } else if self.active >= 2 {
+            self.active -= 2;
+            self.selected = self.quadrants[self.active].len().saturating_sub(1);
+        }
+    }
+
+    /// Switch between the left and right column.
+    pub fn switch_column(&mut self) {
+        self.active ^= 1;
+        self.clamp();
+    }
+
+    fn clamp(&mut self) {
+        self.selected = self
+            .selected
+            .min(self.quadrants[self.active].len().saturating_sub(1));
+    }
+}
+
+/// State for the sync conflict review modal
+#[derive(Debug, Clone)]
+pub struct ConflictState {
+    pub conflict: Conflict,
+    /// Each field the two versions disagree on, with the side kept
+    pub picks: Vec<(ConflictField, Side)>,
+    /// Selected row in `picks`
+    pub selected: usize,
+    /// Conflicts left to review, this one included
+    pub remaining: usize,
+    /// Category and project names, to show them by name
+    pub names: HashMap<uuid::Uuid, String>,
+}
+
+impl ConflictState {
+    /// Review `conflict`, keeping the local side of every field to start with
+    pub fn new(conflict: Conflict, remaining: usize, names: HashMap<uuid::Uuid, String>) -> Self {
+        let picks = conflict
+            .fields()
+            .into_iter()
+            .map(|f| (f, Side::Local))
+            .collect();
+        Self {
+            conflict,
+            picks,
+            selected: 0,
+            remaining,
+            names,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.picks.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Keep `side` of the selected field
+    pub fn pick(&mut self, side: Side) {
+        if let Some((_, picked)) = self.picks.get_mut(self.selected) {
+            *picked = side;
+        }
+    }
+
+    /// Switch the selected field to the other side
+    pub fn toggle(&mut self) {
+        if let Some((_, picked)) = self.picks.get_mut(self.selected) {
+            *picked = picked.other();
+        }
+    }
+
+    /// Keep `side` of every field
+    pub fn pick_all(&mut self, side: Side) {
+        for (_, picked) in &mut self.picks {
+            *picked = side;
+        }
+    }
+
+    /// The todo the current picks resolve to
+    pub fn resolved(&self) -> Todo {
+        self.conflict.resolve(|field| {
+            self.picks
+                .iter()
+                .find(|(f, _)| *f == field)
+                .map_or(Side::Local, |(_, side)| *side)
+        })
+    }
+}
+
 /// Productivity insights data
 #[derive(Debug, Clone, Default)]
 pub struct InsightsData {
@@ -143,6 +388,19 @@ pub struct InsightsData {
     pub high_priority_pending: usize,
     pub medium_priority_pending: usize,
     pub low_priority_pending: usize,
+    /// Completed/total todos per active project
+    pub projects: Vec<ProjectProgress>,
+    /// Completions per category over the 7 days
+    pub categories: Vec<CategoryCount>,
+    /// Completions per local weekday over the 7 days, Monday first
+    pub completed_by_weekday: [u64; 7],
+    /// Mean creation-to-completion time over the 7 days
+    pub average_completion: Option<chrono::Duration>,
+    pub oldest_open: Option<Todo>,
+    /// Focus sessions per local day over the 7 days, days without any left out
+    pub focus_by_day: Vec<(chrono::NaiveDate, u64)>,
+    /// Minutes focused per todo over the 7 days, most first
+    pub focus_by_todo: Vec<FocusTime>,
 }
 
 /// Field being edited in full edit mode
@@ -178,6 +436,50 @@ pub enum SettingsSection {
     Database,
 }
 
+/// A setting that can be changed in the Settings view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettingField {
+    /// Key for [`Config::get_value`] and [`Config::set_value`]
+    pub key: &'static str,
+    /// On/off settings flip on Enter instead of opening an input
+    pub toggle: bool,
+}
+
+const fn field(key: &'static str) -> SettingField {
+    SettingField { key, toggle: false }
+}
+
+const fn toggle(key: &'static str) -> SettingField {
+    SettingField { key, toggle: true }
+}
+
+const AI_FIELDS: &[SettingField] = &[field("ai.provider"), field("ai.model")];
+const DISPLAY_FIELDS: &[SettingField] = &[
+    field("display.theme"),
+    field("display.date_format"),
+    field("display.density"),
+    toggle("display.animations"),
+    toggle("display.celebrate"),
+];
+const NOTIFICATION_FIELDS: &[SettingField] = &[
+    toggle("notifications.enabled"),
+    toggle("notifications.sound"),
+    field("notifications.advance_minutes"),
+    field("notifications.batch_threshold"),
+];
+
+impl SettingsSection {
+    /// Settings the section can edit, in the order they're shown
+    pub fn fields(self) -> &'static [SettingField] {
+        match self {
+            Self::Ai => AI_FIELDS,
+            Self::Display => DISPLAY_FIELDS,
+            Self::Notifications => NOTIFICATION_FIELDS,
+            Self::Database => &[],
+        }
+    }
+}
+
 /// Sort field for the task list
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SortBy {
@@ -185,6 +487,8 @@ pub enum SortBy {
     CreatedAt,
     DueDate,
     Priority,
+    /// Most urgent first when ascending, see [`todoee_core::urgency`]
+    Urgency,
     Title,
 }
 
@@ -221,7 +525,7 @@ impl EditState {
             title: todo.title.clone(),
             description: todo.description.clone().unwrap_or_default(),
             priority: todo.priority,
-            due_date: todo.due_date.map(|d| d.format("%Y-%m-%d").to_string()),
+            due_date: todo.due_date.map(|d| dates::format_due(&d, &Local)),
             category_name,
             active_field: EditField::Title,
         }
@@ -235,8 +539,8 @@ pub struct AddState {
     pub title: String,
     pub description: String,
     pub priority: Priority,
-    pub due_date: Option<String>, // YYYY-MM-DD format
-    pub reminder: Option<String>, // YYYY-MM-DD HH:MM format
+    pub due_date: Option<String>, // YYYY-MM-DD [HH:MM] or a phrase like "friday 3pm"
+    pub reminder: Option<String>, // YYYY-MM-DD HH:MM or a phrase like "in 2 hours"
     pub category_name: Option<String>,
     pub active_field: AddField,
 }
@@ -252,45 +556,13 @@ impl AddState {
     }
 }
 
-/// Parse a date string that can be:
-/// - Absolute: "2026-01-30"
-/// - Relative: "today", "tomorrow", "+3d", "+1w"
-fn parse_due_date(input: &str) -> Option<chrono::DateTime<Utc>> {
-    let input = input.trim().to_lowercase();
-    let today = Local::now().date_naive();
-
-    let date = match input.as_str() {
-        "today" => today,
-        "tomorrow" => today + Duration::days(1),
-        s if s.starts_with('+') && s.ends_with('d') => {
-            let days: i64 = s[1..s.len() - 1].parse().ok()?;
-            today + Duration::days(days)
-        }
-        s if s.starts_with('+') && s.ends_with('w') => {
-            let weeks: i64 = s[1..s.len() - 1].parse().ok()?;
-            today + Duration::weeks(weeks)
-        }
-        _ => NaiveDate::parse_from_str(&input, "%Y-%m-%d").ok()?,
-    };
-
-    date.and_hms_opt(12, 0, 0)
-        .map(|dt| Utc.from_utc_datetime(&dt))
-}
-
-/// Parse a reminder datetime string: "2026-01-30 14:00"
-fn parse_reminder(input: &str) -> Option<chrono::DateTime<Utc>> {
-    let input = input.trim();
-    NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M")
-        .ok()
-        .map(|dt| Utc.from_utc_datetime(&dt))
-}
-
 /// Filter state for the task list
 #[derive(Debug, Clone, Default)]
 pub struct Filter {
     pub today_only: bool,
     pub overdue_only: bool,
     pub category: Option<String>,
+    pub project: Option<String>,
     pub show_completed: bool,
     pub search_query: String,
     pub priority: Option<Priority>,
@@ -306,10 +578,18 @@ pub struct App {
     pub mode: Mode,
     /// List of todos
     pub todos: Vec<Todo>,
+    /// Characters shown of todo IDs, from `[display] id_length`
+    pub id_length: usize,
     /// List of categories
     pub categories: Vec<Category>,
+    /// Active projects with their progress
+    pub projects: Vec<ProjectProgress>,
     /// Currently selected index
     pub selected: usize,
+    /// First todo row on screen, moved on draw to keep the selection visible
+    pub task_offset: Cell<usize>,
+    /// Todo rows that fit on screen, updated on every draw
+    pub task_page: Cell<usize>,
     /// Input field for adding/editing/searching
     pub input: Input,
     /// Current filter
@@ -318,6 +598,8 @@ pub struct App {
     pub status_message: Option<String>,
     /// Frame when status message was set (for animation)
     pub status_set_frame: Option<usize>,
+    /// Frame the inbox-zero toast went up, and how many tasks were due today
+    pub celebration: Option<(usize, u64)>,
     /// Database connection
     pub db: LocalDb,
     /// Configuration
@@ -334,8 +616,12 @@ pub struct App {
     pub view_changed_frame: Option<usize>,
     /// Selected category index
     pub category_selected: usize,
+    /// Selected project index
+    pub project_selected: usize,
     /// Current settings section
     pub settings_section: SettingsSection,
+    /// Index of the selected field in `settings_section.fields()`
+    pub settings_field: usize,
     /// Whether an async operation is in progress
     pub is_loading: bool,
     /// Loading message to display
@@ -344,16 +630,46 @@ pub struct App {
     pub loading_progress: Option<LoadingProgress>,
     /// Priority to apply when adding a task
     pub pending_priority: Option<Priority>,
+    /// Lines pasted into quick add after the first, saved as the description
+    pub pending_description: Option<String>,
     /// Insights data for the insights modal
     pub insights_data: Option<InsightsData>,
     /// Frame when insights modal was opened
     pub insights_opened_frame: Option<usize>,
     /// Focus/pomodoro state
     pub focus_state: Option<FocusState>,
+    /// Eisenhower matrix state
+    pub matrix_state: Option<MatrixState>,
+    /// Sync conflict under review
+    pub conflict_state: Option<ConflictState>,
+    /// Notes on the todo shown in the detail view
+    pub detail_notes: Vec<TodoNote>,
+    /// Files and URLs attached to the todo shown in the detail view
+    pub detail_attachments: Vec<Attachment>,
+    /// Help modal state
+    pub help_state: Option<HelpState>,
+    /// CLI commands (name, description) listed in help
+    pub cli_commands: Vec<(String, String)>,
     /// Animation frame counter for tick-based animations
     pub animation_frame: usize,
     /// Current spinner style for loading animations
     pub spinner_style: Spinner,
+    /// Sync health shown in the Database settings
+    pub sync_health: SyncHealth,
+    /// Todos changed both here and remotely, awaiting review
+    pub sync_conflicts: Vec<Conflict>,
+    /// Whether the Database settings list the conflicting todos
+    pub show_sync_conflicts: bool,
+    /// Result of the last remote ping (round-trip time or error)
+    pub sync_latency: Option<std::result::Result<Duration, String>>,
+    /// Reminder daemon's last status, shown in the Notifications settings
+    pub daemon_status: Option<DaemonStatus>,
+    /// Profile in use, shown in the tab bar; `None` is the default profile
+    pub profile: Option<String>,
+    /// Checklist of `todoee tutorial`; the database is in memory while set
+    pub tutorial: Option<TutorialState>,
+    /// Colors from `display.theme`
+    pub theme: Theme,
 }
 
 /// Calculate fuzzy match score (higher = better match)
@@ -413,18 +729,41 @@ impl App {
     pub async fn new() -> Result<Self> {
         let config = Config::load()?;
         let db_path = config.local_db_path()?;
-        let db = LocalDb::new(&db_path).await?;
+        let db = LocalDb::with_pool_size(&db_path, config.database.pool_size).await?;
+        let mut app = Self::with_db(config, db).await?;
+
+        // Mention a new release found by an earlier check, and check again in
+        // the background if that was over a week ago
+        if let Some(version) = crate::commands::self_update::available(&app.config) {
+            app.status_message = Some(crate::commands::self_update::notice_text(
+                &app.config,
+                &version,
+            ));
+        }
+        let config = app.config.clone();
+        tokio::spawn(async move { crate::commands::self_update::refresh(&config).await });
 
+        Ok(app)
+    }
+
+    /// Create an application instance on an already open database
+    pub async fn with_db(config: Config, db: LocalDb) -> Result<Self> {
+        let theme = Theme::from_config(&config.display);
         let mut app = Self {
             running: true,
             mode: Mode::Normal,
             todos: Vec::new(),
+            id_length: MIN_ID_LENGTH,
             categories: Vec::new(),
+            projects: Vec::new(),
             selected: 0,
+            task_offset: Cell::new(0),
+            task_page: Cell::new(1),
             input: Input::default(),
             filter: Filter::default(),
             status_message: None,
             status_set_frame: None,
+            celebration: None,
             db,
             config,
             edit_state: None,
@@ -433,26 +772,58 @@ impl App {
             previous_view: None,
             view_changed_frame: None,
             category_selected: 0,
+            project_selected: 0,
             settings_section: SettingsSection::default(),
+            settings_field: 0,
             is_loading: false,
             loading_message: None,
             loading_progress: None,
             pending_priority: None,
+            pending_description: None,
             insights_data: None,
             insights_opened_frame: None,
             focus_state: None,
+            matrix_state: None,
+            conflict_state: None,
+            detail_notes: Vec::new(),
+            detail_attachments: Vec::new(),
+            help_state: None,
+            cli_commands: Vec::new(),
             animation_frame: 0,
             spinner_style: Spinner::default(),
+            sync_health: SyncHealth::default(),
+            sync_conflicts: Vec::new(),
+            show_sync_conflicts: false,
+            sync_latency: None,
+            daemon_status: None,
+            profile: Config::profile(),
+            tutorial: None,
+            theme,
         };
 
         app.refresh_todos().await?;
         app.refresh_categories().await?;
+        app.refresh_projects().await?;
+        app.refresh_sync_health().await?;
 
         Ok(app)
     }
 
     /// Refresh the todo list from database
     pub async fn refresh_todos(&mut self) -> Result<()> {
+        // A search starting with ':' is a filter query, as in `todoee list`
+        let query = match self.filter.search_query.strip_prefix(':') {
+            Some(query) => match query.parse::<todoee_core::Filter>() {
+                Ok(query) => Some(query),
+                Err(e) => {
+                    self.status_message = Some(format!("✗ Invalid query: {:#}", e));
+                    None
+                }
+            },
+            None => None,
+        };
+        let mut queried = false;
+
         self.todos = if self.filter.overdue_only {
             self.db.list_todos_overdue().await?
         } else if self.filter.today_only {
@@ -463,12 +834,40 @@ impl App {
             } else {
                 Vec::new()
             }
+        } else if let Some(ref project_name) = self.filter.project {
+            if let Some(project) = self.db.get_project_by_name(project_name).await? {
+                let mut todos = self.db.list_todos_by_project(project.id).await?;
+                if !self.filter.show_completed {
+                    todos.retain(|t| !t.is_completed);
+                }
+                todos
+            } else {
+                Vec::new()
+            }
+        } else if let Some(ref query) = query {
+            queried = true;
+            self.db
+                .list_todos_filtered(query, !self.filter.show_completed, &Local::now())
+                .await?
         } else {
             self.db.list_todos(!self.filter.show_completed).await?
         };
+        if let Some(query) = query.filter(|_| !queried) {
+            let names = self.db.filter_names().await?;
+            let now = Local::now();
+            self.todos.retain(|t| query.matches(t, &names, &now));
+        }
+        self.id_length = crate::commands::ids::id_length(&self.db, &self.config).await?;
+
+        // Apply the active context, like the CLI's list does
+        match ActiveContext::load(&self.db, &self.config).await {
+            Ok(Some(context)) => context.retain(&mut self.todos),
+            Ok(None) => {}
+            Err(e) => self.status_message = Some(format!("{:#}", e)),
+        }
 
         // Apply search filter with fuzzy matching
-        if !self.filter.search_query.is_empty() {
+        if !self.filter.search_query.is_empty() && !self.filter.search_query.starts_with(':') {
             let query = &self.filter.search_query;
             // Score and filter todos
             let mut scored: Vec<_> = self
@@ -486,7 +885,7 @@ impl App {
                 })
                 .collect();
             // Sort by score descending
-            scored.sort_by(|a, b| b.1.cmp(&a.1));
+            scored.sort_by_key(|s| std::cmp::Reverse(s.1));
             self.todos = scored.into_iter().map(|(t, _)| t).collect();
         }
 
@@ -497,30 +896,38 @@ impl App {
 
         // Sort todos
         match (self.filter.sort_by, self.filter.sort_order) {
-            (SortBy::CreatedAt, SortOrder::Ascending) => {
-                self.todos.sort_by(|a, b| a.created_at.cmp(&b.created_at))
-            }
+            (SortBy::CreatedAt, SortOrder::Ascending) => self.todos.sort_by_key(|a| a.created_at),
             (SortBy::CreatedAt, SortOrder::Descending) => {
-                self.todos.sort_by(|a, b| b.created_at.cmp(&a.created_at))
-            }
-            (SortBy::DueDate, SortOrder::Ascending) => {
-                self.todos.sort_by(|a, b| a.due_date.cmp(&b.due_date))
+                self.todos.sort_by_key(|a| std::cmp::Reverse(a.created_at))
             }
+            (SortBy::DueDate, SortOrder::Ascending) => self.todos.sort_by_key(|a| a.due_date),
             (SortBy::DueDate, SortOrder::Descending) => {
-                self.todos.sort_by(|a, b| b.due_date.cmp(&a.due_date))
-            }
-            (SortBy::Priority, SortOrder::Ascending) => {
-                self.todos.sort_by(|a, b| a.priority.cmp(&b.priority))
+                self.todos.sort_by_key(|a| std::cmp::Reverse(a.due_date))
             }
+            (SortBy::Priority, SortOrder::Ascending) => self.todos.sort_by_key(|a| a.priority),
             (SortBy::Priority, SortOrder::Descending) => {
-                self.todos.sort_by(|a, b| b.priority.cmp(&a.priority))
+                self.todos.sort_by_key(|a| std::cmp::Reverse(a.priority))
+            }
+            (SortBy::Urgency, order) => {
+                let now = chrono::Utc::now();
+                let ranking = &self.config.ranking;
+                self.todos.sort_by(|a, b| {
+                    let (a, b) = (
+                        urgency::urgency(a, ranking, now),
+                        urgency::urgency(b, ranking, now),
+                    );
+                    match order {
+                        SortOrder::Ascending => b.total_cmp(&a),
+                        SortOrder::Descending => a.total_cmp(&b),
+                    }
+                })
+            }
+            (SortBy::Title, SortOrder::Ascending) => {
+                self.todos.sort_by_key(|a| a.title.to_lowercase())
             }
-            (SortBy::Title, SortOrder::Ascending) => self
-                .todos
-                .sort_by(|a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase())),
             (SortBy::Title, SortOrder::Descending) => self
                 .todos
-                .sort_by(|a, b| b.title.to_lowercase().cmp(&a.title.to_lowercase())),
+                .sort_by_key(|a| std::cmp::Reverse(a.title.to_lowercase())),
         }
 
         // Ensure selected index is valid
@@ -539,6 +946,80 @@ impl App {
         Ok(())
     }
 
+    /// Refresh active projects and their progress from database
+    pub async fn refresh_projects(&mut self) -> Result<()> {
+        self.projects = self.db.project_progress().await?;
+        if self.project_selected >= self.projects.len() {
+            self.project_selected = self.projects.len().saturating_sub(1);
+        }
+        Ok(())
+    }
+
+    /// Reload sync health and the todos in conflict from the local database
+    pub async fn refresh_sync_health(&mut self) -> Result<()> {
+        self.sync_health = SyncHealth::load(&self.db).await?;
+        self.sync_conflicts = self.db.list_conflicts().await?;
+        Ok(())
+    }
+
+    /// Reread the daemon status file. A missing or unreadable one shows as
+    /// no daemon rather than failing the TUI.
+    pub fn refresh_daemon_status(&mut self) {
+        self.daemon_status = DaemonStatus::load().ok().flatten();
+    }
+
+    /// Sync with the remote database now
+    pub async fn sync_now(&mut self) -> Result<()> {
+        if self.tutorial.is_some() {
+            self.status_message = Some("✗ Sync is off in the tutorial".to_string());
+            return Ok(());
+        }
+        self.set_loading("Syncing...");
+        let outcome = match SyncService::new(&self.config).await {
+            Ok(service) if !service.is_configured() => {
+                Err("Cloud sync is not configured".to_string())
+            }
+            Ok(service) => service.sync().await.map_err(|e| sync_error_message(&e)),
+            Err(e) => Err(sync_error_message(&e)),
+        };
+        self.clear_loading();
+
+        self.status_message = Some(match outcome {
+            Ok(result) if result.conflicts > 0 => format!(
+                "✓ Synced: {} up, {} down, {} conflicts (v to review)",
+                result.uploaded, result.downloaded, result.conflicts
+            ),
+            Ok(result) => format!(
+                "✓ Synced: {} up, {} down",
+                result.uploaded, result.downloaded
+            ),
+            Err(e) => format!("✗ Sync failed: {}", e),
+        });
+
+        self.refresh_sync_health().await?;
+        self.refresh_todos().await?;
+        self.refresh_categories().await?;
+        self.refresh_projects().await?;
+        Ok(())
+    }
+
+    /// Measure the round-trip time to the remote database
+    pub async fn ping_remote(&mut self) -> Result<()> {
+        if self.tutorial.is_some() {
+            self.status_message = Some("✗ Sync is off in the tutorial".to_string());
+            return Ok(());
+        }
+        self.set_loading("Pinging cloud database...");
+        let latency = match SyncService::new(&self.config).await {
+            Ok(service) => service.ping().await.map_err(|e| sync_error_message(&e)),
+            Err(e) => Err(sync_error_message(&e)),
+        };
+        self.clear_loading();
+
+        self.sync_latency = Some(latency);
+        Ok(())
+    }
+
     /// Get the currently selected todo
     pub fn selected_todo(&self) -> Option<&Todo> {
         self.todos.get(self.selected)
@@ -558,6 +1039,15 @@ impl App {
         }
     }
 
+    /// Move selection by `pages` screens of todos, stopping at either end
+    pub fn select_page(&mut self, pages: isize) {
+        let delta = pages.saturating_mul(self.task_page.get().max(1) as isize);
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.todos.len().saturating_sub(1));
+    }
+
     /// Mark selected todo as done
     pub async fn mark_selected_done(&mut self) -> Result<()> {
         // Check if selected todo exists and is not completed
@@ -578,6 +1068,9 @@ impl App {
             let previous_state = serde_json::to_value(&*todo).ok();
             let todo_id = todo.id;
 
+            let due_today = todo
+                .due_date
+                .is_some_and(|d| d.with_timezone(&Local).date_naive() == Local::now().date_naive());
             todo.mark_complete();
             let title = todo.title.clone();
 
@@ -598,6 +1091,9 @@ impl App {
 
             self.clear_loading();
             self.status_message = Some(format!("✓ Completed: {}", title));
+            if due_today && self.config.display.celebrate {
+                self.celebrate_if_cleared().await?;
+            }
             self.refresh_todos().await?;
             self.clamp_selection();
         } else if self.todos.get(self.selected).is_some() {
@@ -606,6 +1102,73 @@ impl App {
         Ok(())
     }
 
+    /// Put up the inbox-zero toast, and a desktop notification if those are
+    /// on, when every task due today is done
+    async fn celebrate_if_cleared(&mut self) -> Result<()> {
+        let today = Local::now().date_naive();
+        let progress = self
+            .db
+            .due_progress(
+                local_midnight(today),
+                local_midnight(today + chrono::Duration::days(1)),
+            )
+            .await?;
+        if !progress.is_cleared() {
+            return Ok(());
+        }
+
+        self.celebration = Some((self.animation_frame, progress.due));
+        if self.config.notifications.enabled {
+            let sound = self.config.notifications.sound;
+            // Off the UI thread: showing a notification can wait on D-Bus
+            std::thread::spawn(move || {
+                let mut notification = notify_rust::Notification::new();
+                notification
+                    .summary(celebration::MESSAGE)
+                    .body("Everything due today is done")
+                    .appname("todoee");
+                if sound {
+                    notification.sound_name("complete");
+                }
+                // The toast already says it; a missing notification server
+                // is no reason to interrupt
+                let _ = notification.show();
+            });
+        }
+        Ok(())
+    }
+
+    /// Mark selected completed todo as incomplete
+    pub async fn reopen_selected(&mut self) -> Result<()> {
+        let Some(todo) = self.todos.get_mut(self.selected) else {
+            return Ok(());
+        };
+        if !todo.is_completed {
+            self.status_message = Some("Not completed".to_string());
+            return Ok(());
+        }
+
+        let previous_state = serde_json::to_value(&*todo).ok();
+        todo.mark_incomplete();
+        let (todo_id, title) = (todo.id, todo.title.clone());
+        let new_state = serde_json::to_value(&*todo).ok();
+        self.db.update_todo(todo).await?;
+
+        let op = Operation::new(
+            OperationType::Uncomplete,
+            EntityType::Todo,
+            todo_id,
+            previous_state,
+            new_state,
+        );
+        self.db.record_operation(&op).await?;
+
+        self.status_message = Some(format!("\u{21ba} Reopened: {}", title));
+        self.refresh_todos().await?;
+        self.clamp_selection();
+        Ok(())
+    }
+
     /// Delete selected todo
     pub async fn delete_selected(&mut self) -> Result<()> {
         // Prevent deleting a todo that's currently being focused on
@@ -673,6 +1236,102 @@ impl App {
         self.filter.show_completed = !self.filter.show_completed;
     }
 
+    /// Switch list density and persist it to the config file (not in the
+    /// tutorial, which leaves settings alone)
+    pub fn toggle_density(&mut self) {
+        let density = self.config.display.toggle_density().to_string();
+        if self.tutorial.is_some() {
+            self.status_message = Some(format!("✓ Density: {}", density));
+            return;
+        }
+        self.status_message = Some(match self.config.save() {
+            Ok(()) => format!("✓ Density: {}", density),
+            Err(e) => format!("Density: {} (not saved: {})", density, e),
+        });
+    }
+
+    /// The setting selected in the Settings view, if the section has any
+    pub fn selected_setting(&self) -> Option<SettingField> {
+        self.settings_section
+            .fields()
+            .get(self.settings_field)
+            .copied()
+    }
+
+    /// Edit the selected setting: on/off settings flip at once, others
+    /// open an input holding the current value
+    pub fn start_setting_edit(&mut self) {
+        let Some(field) = self.selected_setting() else {
+            return;
+        };
+        let current = self.config.get_value(field.key).unwrap_or_default();
+        if field.toggle {
+            let flipped = if current == "true" { "false" } else { "true" };
+            self.set_setting(field.key, flipped);
+        } else {
+            self.input = Input::new(current);
+            self.mode = Mode::EditingSetting;
+        }
+    }
+
+    /// Set a config key, apply it and save the config (not in the tutorial,
+    /// which leaves settings alone). Invalid values change nothing.
+    pub fn set_setting(&mut self, key: &str, value: &str) {
+        let mut config = self.config.clone();
+        if let Err(e) = config.set_value(key, value.trim()) {
+            self.status_message = Some(format!("✗ {}", e));
+            return;
+        }
+        self.config = config;
+        self.theme = Theme::from_config(&self.config.display);
+        let shown = format!(
+            "{} = {}",
+            key,
+            self.config.get_value(key).unwrap_or_default()
+        );
+        if self.tutorial.is_some() {
+            self.status_message = Some(format!("✓ {}", shown));
+            return;
+        }
+        self.status_message = Some(match self.config.save() {
+            Ok(()) => format!("✓ {}", shown),
+            Err(e) => format!("{} (not saved: {})", shown, e),
+        });
+    }
+
+    /// `animation_frame`, held at 0 when `display.animations` is off so
+    /// looping animations rest on their first frame
+    pub fn motion_frame(&self) -> usize {
+        if self.config.display.animate() {
+            self.animation_frame
+        } else {
+            0
+        }
+    }
+
+    /// Frames since `frame`; with animations off, transitions are always over
+    pub fn frames_since(&self, frame: usize) -> usize {
+        if self.config.display.animate() {
+            self.animation_frame.wrapping_sub(frame)
+        } else {
+            usize::MAX
+        }
+    }
+
+    /// Switch to the next theme and save it
+    pub fn cycle_theme(&mut self) {
+        let name = self.config.display.cycle_theme().to_string();
+        self.theme = Theme::from_config(&self.config.display);
+        if self.tutorial.is_some() {
+            self.status_message = Some(format!("✓ Theme: {}", name));
+            return;
+        }
+        self.status_message = Some(match self.config.save() {
+            Ok(()) => format!("✓ Theme: {}", name),
+            Err(e) => format!("Theme: {} (not saved: {})", name, e),
+        });
+    }
+
     /// Set search query from input
     pub fn apply_search(&mut self) {
         self.filter.search_query = self.input.value().to_string();
@@ -723,8 +1382,12 @@ impl App {
         if let Some(priority) = self.pending_priority.take() {
             todo.priority = priority;
         }
+        if let Some(description) = self.pending_description.take() {
+            todo.description = Some(description);
+        }
+        todo.detect_icon();
 
-        let title = todo.title.clone();
+        let title = todo.display_title();
         self.db.create_todo(&todo).await?;
 
         // Record operation for undo/redo
@@ -744,29 +1407,69 @@ impl App {
         Ok(())
     }
 
+    /// Insert pasted text into whatever is being typed. In quick add and the
+    /// title of the full forms, the first line is the title and the rest goes
+    /// to the description; one-line inputs get the lines joined.
+    pub fn paste(&mut self, text: &str) {
+        match self.mode {
+            Mode::Adding => {
+                let (line, rest) = split_paste(text);
+                insert_text(&mut self.input, &line);
+                if let Some(rest) = rest {
+                    append_line(self.pending_description.get_or_insert_default(), &rest);
+                }
+            }
+            Mode::Searching
+            | Mode::Editing
+            | Mode::AddingCategory
+            | Mode::AddingProject
+            | Mode::EditingSetting => insert_text(&mut self.input, &paste_line(text)),
+            Mode::AddingFull => {
+                if let Some(state) = self.add_state.as_mut() {
+                    match state.active_field {
+                        AddField::Title => {
+                            let (line, rest) = split_paste(text);
+                            state.title.push_str(&line);
+                            if let Some(rest) = rest {
+                                append_line(&mut state.description, &rest);
+                            }
+                        }
+                        AddField::Description => {
+                            state.description.push_str(&normalize_newlines(text))
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Mode::EditingFull => {
+                if let Some(state) = self.edit_state.as_mut() {
+                    match state.active_field {
+                        EditField::Title => {
+                            let (line, rest) = split_paste(text);
+                            state.title.push_str(&line);
+                            if let Some(rest) = rest {
+                                append_line(&mut state.description, &rest);
+                            }
+                        }
+                        EditField::Description => {
+                            state.description.push_str(&normalize_newlines(text))
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
     async fn parse_with_ai(&self, description: &str) -> Result<Todo> {
         use todoee_core::AiClient;
 
         let client = AiClient::new(&self.config)?;
-        let parsed = client.parse_task(description).await?;
-
-        let mut todo = Todo::new(parsed.title, None);
-        todo.description = parsed.description;
-        todo.due_date = parsed.due_date;
-        todo.reminder_at = parsed.reminder_at;
-
-        if let Some(p) = parsed.priority {
-            todo.priority = match p {
-                1 => Priority::Low,
-                3 => Priority::High,
-                _ => Priority::Medium,
-            };
-        }
+        let parsed = client.parse_task_cached(&self.db, description).await?;
 
-        todo.ai_metadata = Some(serde_json::json!({
-            "original_input": description,
-            "parsed_category": parsed.category,
-        }));
+        let mut todo = Todo::new(description.to_string(), None);
+        todo.apply_ai_parse(&parsed, description);
 
         Ok(todo)
     }
@@ -878,6 +1581,66 @@ impl App {
         Ok(())
     }
 
+    /// Add a new project
+    pub async fn add_project(&mut self, name: String) -> Result<()> {
+        if name.is_empty() {
+            self.status_message = Some("Project name cannot be empty".to_string());
+            return Ok(());
+        }
+
+        if self.db.get_project_by_name(&name).await?.is_some() {
+            self.status_message = Some(format!("Project '{}' already exists", name));
+            return Ok(());
+        }
+
+        let project = Project::new(uuid::Uuid::nil(), name.clone());
+        self.db.create_project(&project).await?;
+        self.status_message = Some(format!("✓ Created project: {}", name));
+        self.refresh_projects().await?;
+        Ok(())
+    }
+
+    /// Archive the selected project, keeping its todos
+    pub async fn archive_selected_project(&mut self) -> Result<()> {
+        let Some(progress) = self.projects.get(self.project_selected) else {
+            return Ok(());
+        };
+        let mut project = progress.project.clone();
+        project.archive();
+        self.db.update_project(&project).await?;
+
+        if self.filter.project.as_ref() == Some(&project.name) {
+            self.filter.project = None;
+            self.refresh_todos().await?;
+        }
+        self.status_message = Some(format!("Archived project: {}", project.name));
+        self.refresh_projects().await?;
+        Ok(())
+    }
+
+    /// Show only the selected project's todos, or all todos if it is already shown
+    pub async fn toggle_project_filter(&mut self) -> Result<()> {
+        let Some(progress) = self.projects.get(self.project_selected) else {
+            return Ok(());
+        };
+        let name = progress.project.name.clone();
+
+        if self.filter.project.as_ref() == Some(&name) {
+            self.filter.project = None;
+            self.status_message = Some("Showing all tasks".to_string());
+        } else {
+            self.filter.project = Some(name.clone());
+            self.filter.category = None;
+            self.filter.today_only = false;
+            self.filter.overdue_only = false;
+            self.status_message = Some(format!("Showing project: {}", name));
+            self.previous_view = Some(self.current_view);
+            self.view_changed_frame = Some(self.animation_frame);
+            self.current_view = View::Todos;
+        }
+        self.refresh_todos().await
+    }
+
     /// Undo the last operation
     pub async fn undo(&mut self) -> Result<()> {
         let Some(op) = self.db.get_last_undoable_operation().await? else {
@@ -891,6 +1654,13 @@ impl App {
             return Ok(());
         }
 
+        if let Some(group_id) = op.group_id {
+            let ops = self.db.undo_operation_group(group_id).await?;
+            self.status_message = Some(format!("↶ Undone: batch of {} changes", ops.len()));
+            self.refresh_todos().await?;
+            return Ok(());
+        }
+
         self.apply_undo(&op).await?;
         self.db.mark_operation_undone(op.id).await?;
 
@@ -902,6 +1672,8 @@ impl App {
             OperationType::Uncomplete => "uncomplete",
             OperationType::Stash => "stash",
             OperationType::Unstash => "unstash",
+            OperationType::BatchCreate => "batch add",
+            OperationType::Summary => "compacted changes",
         };
         self.status_message = Some(format!("↶ Undone: {}", op_name));
         self.refresh_todos().await?;
@@ -920,7 +1692,7 @@ impl App {
                 // Undo delete by restoring from previous_state
                 if let Some(ref state) = op.previous_state {
                     let todo: Todo = serde_json::from_value(state.clone())?;
-                    self.db.create_todo(&todo).await?;
+                    self.db.restore_todo(&todo).await?;
                 }
             }
             OperationType::Update => {
@@ -945,9 +1717,18 @@ impl App {
                     self.db.update_todo(&todo).await?;
                 }
             }
+            OperationType::BatchCreate => {
+                // Undo batch create by deleting every todo in the batch
+                for todo in op.batch_todos() {
+                    self.db.delete_todo(todo.id).await?;
+                }
+            }
             OperationType::Stash | OperationType::Unstash => {
                 // Stash operations not yet implemented in Todo model
             }
+            OperationType::Summary => {
+                // Summaries keep their group and are handled with it
+            }
         }
         Ok(())
     }
@@ -965,6 +1746,13 @@ impl App {
             return Ok(());
         }
 
+        if let Some(group_id) = op.group_id {
+            let ops = self.db.redo_operation_group(group_id).await?;
+            self.status_message = Some(format!("↷ Redone: batch of {} changes", ops.len()));
+            self.refresh_todos().await?;
+            return Ok(());
+        }
+
         self.apply_redo(&op).await?;
         self.db.mark_operation_redone(op.id).await?;
 
@@ -976,6 +1764,8 @@ impl App {
             OperationType::Uncomplete => "uncomplete",
             OperationType::Stash => "stash",
             OperationType::Unstash => "unstash",
+            OperationType::BatchCreate => "batch add",
+            OperationType::Summary => "compacted changes",
         };
         self.status_message = Some(format!("↷ Redone: {}", op_name));
         self.refresh_todos().await?;
@@ -990,7 +1780,7 @@ impl App {
                 // Redo create by creating from new_state
                 if let Some(ref state) = op.new_state {
                     let todo: Todo = serde_json::from_value(state.clone())?;
-                    self.db.create_todo(&todo).await?;
+                    self.db.restore_todo(&todo).await?;
                 }
             }
             OperationType::Delete => {
@@ -1019,13 +1809,35 @@ impl App {
                     self.db.update_todo(&todo).await?;
                 }
             }
+            OperationType::BatchCreate => {
+                // Redo batch create by restoring every todo in the batch
+                for todo in op.batch_todos() {
+                    self.db.restore_todo(&todo).await?;
+                }
+            }
             OperationType::Stash | OperationType::Unstash => {
                 // Stash operations not yet implemented in Todo model
             }
+            OperationType::Summary => {
+                // Summaries keep their group and are handled with it
+            }
         }
         Ok(())
     }
 
+    /// Parse a due date typed into a form field. Blank means no due date.
+    pub fn parse_due_date(
+        input: Option<&str>,
+        date_input: &DateInput,
+    ) -> Result<Option<DateTime<Utc>>, String> {
+        match input.map(str::trim).filter(|s| !s.is_empty()) {
+            None => Ok(None),
+            Some(s) => dates::parse_due(s, date_input)
+                .map(Some)
+                .ok_or_else(|| format!("Could not understand due date '{}'", s)),
+        }
+    }
+
     /// Create a todo from the current add state
     pub async fn create_todo_from_add_state(&mut self) -> Result<()> {
         let Some(ref state) = self.add_state else {
@@ -1040,13 +1852,20 @@ impl App {
             Some(state.description.clone())
         };
         let priority = state.priority;
-        let due_date = state.due_date.as_ref().and_then(|s| parse_due_date(s));
-        let reminder_at = state.reminder.as_ref().and_then(|s| parse_reminder(s));
+        let date_input = self.config.display.date_input();
+        let due_date = Self::parse_due_date(state.due_date.as_deref(), &date_input)
+            .ok()
+            .flatten();
+        let reminder_at = state
+            .reminder
+            .as_ref()
+            .and_then(|s| dates::parse_reminder(s, &date_input));
         let category_name = state.category_name.clone();
 
         self.set_loading("Creating task...");
 
-        let mut todo = Todo::new(title.clone(), None);
+        let mut todo = Todo::new(title, None);
+        todo.detect_icon();
         todo.description = description;
         todo.priority = priority;
         todo.due_date = due_date;
@@ -1072,7 +1891,7 @@ impl App {
         self.db.record_operation(&op).await?;
 
         self.clear_loading();
-        self.status_message = Some(format!("✓ Added: {}", title));
+        self.status_message = Some(format!("✓ Added: {}", todo.display_title()));
         self.refresh_todos().await?;
 
         Ok(())
@@ -1142,11 +1961,12 @@ impl App {
 
     /// Get the recommended "now" todo index based on priority, due date, and time
     pub fn get_now_recommendation(&self) -> Option<usize> {
-        use chrono::Timelike;
+        self.now_ranking().first().copied()
+    }
 
-        if self.todos.is_empty() {
-            return None;
-        }
+    /// Indices of pending todos, best to work on now first
+    pub fn now_ranking(&self) -> Vec<usize> {
+        use chrono::Timelike;
 
         let now = chrono::Utc::now();
         let hour = chrono::Local::now().hour();
@@ -1161,11 +1981,7 @@ impl App {
                 let mut score = 0i32;
 
                 // Priority weight
-                score += match t.priority {
-                    Priority::High => 100,
-                    Priority::Medium => 50,
-                    Priority::Low => 10,
-                };
+                score += self.config.ranking.weight(t.priority) as i32;
 
                 // Due date urgency
                 if let Some(due) = t.due_date {
@@ -1174,10 +1990,12 @@ impl App {
                         score += 200; // Overdue = highest priority
                     } else if days_until == 0 {
                         score += 150; // Due today
-                    } else if days_until == 1 {
-                        score += 100; // Due tomorrow
-                    } else if days_until <= 3 {
-                        score += 50; // Due soon
+                    } else {
+                        score += match self.config.display.due_urgency(&due, now) {
+                            DueUrgency::Urgent => 100, // E.g. due tomorrow
+                            DueUrgency::Soon => 50,
+                            _ => 0,
+                        };
                     }
                 }
 
@@ -1186,7 +2004,7 @@ impl App {
                 // Afternoon: prefer medium tasks
                 // Evening: prefer low priority / quick wins
                 score += match hour {
-                    6..=11 if t.priority == Priority::High => 30,
+                    6..=11 if t.priority >= Priority::High => 30,
                     12..=16 if t.priority == Priority::Medium => 30,
                     17..=22 if t.priority == Priority::Low => 30,
                     _ => 0,
@@ -1196,8 +2014,17 @@ impl App {
             })
             .collect();
 
-        scored.sort_by(|a, b| b.1.cmp(&a.1));
-        scored.first().map(|(i, _)| *i)
+        scored.sort_by_key(|s| std::cmp::Reverse(s.1));
+        scored.into_iter().map(|(i, _)| i).collect()
+    }
+
+    /// The tasks zen mode shows
+    pub fn zen_todos(&self) -> Vec<&Todo> {
+        self.now_ranking()
+            .into_iter()
+            .take(ZEN_TASKS)
+            .map(|i| &self.todos[i])
+            .collect()
     }
 
     /// Compute productivity insights
@@ -1205,52 +2032,32 @@ impl App {
         let now = chrono::Utc::now();
         let seven_days_ago = now - chrono::Duration::days(7);
 
-        let all_todos = self.db.list_todos(false).await?;
-
-        let completed_7d = all_todos
-            .iter()
-            .filter(|t| {
-                t.is_completed && t.completed_at.map(|c| c > seven_days_ago).unwrap_or(false)
-            })
-            .count();
-
-        let created_7d = all_todos
-            .iter()
-            .filter(|t| t.created_at > seven_days_ago)
-            .count();
+        let stats = self.db.todo_stats(seven_days_ago, now).await?;
+        let activity = self.db.activity_by_hour(seven_days_ago).await?;
 
-        let overdue = all_todos
-            .iter()
-            .filter(|t| !t.is_completed && t.due_date.map(|d| d < now).unwrap_or(false))
-            .count();
-
-        let completion_rate = if created_7d > 0 {
-            (completed_7d as f64 / created_7d as f64) * 100.0
+        let completion_rate = if stats.created > 0 {
+            (stats.completed as f64 / stats.created as f64) * 100.0
         } else {
             0.0
         };
 
-        let high = all_todos
-            .iter()
-            .filter(|t| t.priority == Priority::High && !t.is_completed)
-            .count();
-        let med = all_todos
-            .iter()
-            .filter(|t| t.priority == Priority::Medium && !t.is_completed)
-            .count();
-        let low = all_todos
-            .iter()
-            .filter(|t| t.priority == Priority::Low && !t.is_completed)
-            .count();
-
         Ok(InsightsData {
-            total_completed_7d: completed_7d,
-            total_created_7d: created_7d,
+            total_completed_7d: stats.completed as usize,
+            total_created_7d: stats.created as usize,
             completion_rate,
-            overdue_count: overdue,
-            high_priority_pending: high,
-            medium_priority_pending: med,
-            low_priority_pending: low,
+            overdue_count: stats.overdue as usize,
+            high_priority_pending: stats.pending_high as usize,
+            medium_priority_pending: stats.pending_medium as usize,
+            low_priority_pending: stats.pending_low as usize,
+            projects: self.db.project_progress().await?,
+            categories: self.db.completions_by_category(seven_days_ago).await?,
+            completed_by_weekday: crate::commands::insights::completions_by_weekday(&activity),
+            average_completion: self.db.average_completion_time(seven_days_ago).await?,
+            oldest_open: self.db.list_todos_tail(1, false).await?.into_iter().next(),
+            focus_by_day: crate::commands::insights::focus_sessions_by_day(
+                &self.db.list_focus_sessions(seven_days_ago).await?,
+            ),
+            focus_by_todo: self.db.focus_time_by_todo(seven_days_ago).await?,
         })
     }
 
@@ -1263,8 +2070,10 @@ impl App {
     }
 
     /// Complete focus session and return to normal mode
-    pub fn complete_focus(&mut self) {
+    pub async fn complete_focus(&mut self) -> Result<()> {
         if let Some(state) = self.focus_state.take() {
+            self.save_focus_session(state.session.clone(), FocusOutcome::Completed)
+                .await?;
             // Check if the focused todo still exists
             let todo_exists = self.todos.iter().any(|t| t.id == state.todo_id);
 
@@ -1280,19 +2089,307 @@ impl App {
 
             self.mode = Mode::Normal;
         }
+        Ok(())
+    }
+
+    /// Record a finished session, unless its todo was deleted meanwhile
+    async fn save_focus_session(
+        &self,
+        mut session: FocusSession,
+        outcome: FocusOutcome,
+    ) -> Result<()> {
+        if self.db.get_todo(session.todo_id).await?.is_none() {
+            return Ok(());
+        }
+        session.ended_at = chrono::Utc::now();
+        session.outcome = Some(outcome);
+        self.db.save_focus_session(&session).await
+    }
+
+    /// Open help on the sections for the current mode and view
+    pub fn open_help(&mut self) {
+        let context = HelpContext::of(self.mode, self.current_view, self.settings_section);
+        self.help_state = Some(HelpState::new(context, self.mode));
+        self.mode = Mode::Help;
+    }
+
+    /// Close help and go back to where it was opened
+    pub fn close_help(&mut self) {
+        if let Some(state) = self.help_state.take() {
+            self.mode = state.return_mode;
+        } else {
+            self.mode = Mode::Normal;
+        }
+    }
+
+    /// Show the selected todo's details and notes
+    pub async fn open_detail(&mut self) -> Result<()> {
+        let Some(id) = self.selected_todo().map(|t| t.id) else {
+            return Ok(());
+        };
+        self.detail_notes = self.db.list_todo_notes(id).await?;
+        self.detail_attachments = self.db.list_attachments(id).await?;
+        self.mode = Mode::ViewingDetail;
+        Ok(())
+    }
+
+    /// Open attachment `index` of the detail view with the platform opener
+    pub fn open_attachment(&mut self, index: usize) {
+        let Some(attachment) = self.detail_attachments.get(index) else {
+            self.status_message = Some(match self.detail_attachments.len() {
+                0 => "No attachments (add one with: todoee attach <id> <path|url>)".to_string(),
+                n => format!("Only {} attachments", n),
+            });
+            return;
+        };
+        self.status_message = Some(match crate::commands::attach::open(&attachment.target) {
+            Ok(()) => format!("Opened {}", attachment.target),
+            Err(e) => format!("{:#}", e),
+        });
+    }
+
+    /// Open the Eisenhower matrix with all pending todos
+    pub async fn open_matrix(&mut self) -> Result<()> {
+        let todos = self.db.list_todos(true).await?;
+        let mut state = MatrixState::new(todos, Utc::now());
+        if let Some(todo) = self.selected_todo() {
+            state.select_todo(todo.id);
+        }
+        self.matrix_state = Some(state);
+        self.mode = Mode::Matrix;
+        Ok(())
+    }
+
+    /// Move the todo under the matrix cursor to another quadrant
+    pub async fn move_matrix_selected(&mut self, target: Quadrant) -> Result<()> {
+        let Some(mut todo) = self
+            .matrix_state
+            .as_ref()
+            .and_then(|s| s.selected_todo())
+            .cloned()
+        else {
+            self.status_message = Some("No task selected".to_string());
+            return Ok(());
+        };
+
+        let previous_state = serde_json::to_value(&todo).ok();
+        if !todo.move_to_quadrant(target, Utc::now()) {
+            self.status_message = Some(format!("Already in {}", target.title()));
+            return Ok(());
+        }
+
+        self.db.update_todo(&todo).await?;
+
+        // Record operation for undo/redo
+        let op = Operation::new(
+            OperationType::Update,
+            EntityType::Todo,
+            todo.id,
+            previous_state,
+            serde_json::to_value(&todo).ok(),
+        );
+        self.db.record_operation(&op).await?;
+
+        let todos = self.db.list_todos(true).await?;
+        let mut state = MatrixState::new(todos, Utc::now());
+        state.select_todo(todo.id);
+        self.matrix_state = Some(state);
+
+        self.status_message = Some(format!("✓ Moved to {}: {}", target.title(), todo.title));
+        self.refresh_todos().await?;
+        Ok(())
+    }
+
+    /// Close the matrix and return to normal mode
+    pub fn close_matrix(&mut self) {
+        self.matrix_state = None;
+        self.mode = Mode::Normal;
+    }
+
+    /// Review the oldest sync conflict, if there is one
+    pub async fn open_conflicts(&mut self) -> Result<()> {
+        let conflicts = self.db.list_conflicts().await?;
+        let remaining = conflicts.len();
+        let Some(conflict) = conflicts.into_iter().next() else {
+            self.close_conflicts();
+            self.status_message = Some("No sync conflicts".to_string());
+            return Ok(());
+        };
+
+        let mut names: HashMap<uuid::Uuid, String> = self
+            .categories
+            .iter()
+            .map(|c| (c.id, c.name.clone()))
+            .collect();
+        for project in self.db.list_projects(true).await? {
+            names.insert(project.id, project.name);
+        }
+        self.conflict_state = Some(ConflictState::new(conflict, remaining, names));
+        self.mode = Mode::Conflicts;
+        Ok(())
+    }
+
+    /// Resolve the conflict under review with the picked fields, then move
+    /// on to the next one
+    pub async fn resolve_conflict(&mut self) -> Result<()> {
+        let Some(state) = self.conflict_state.as_ref() else {
+            return Ok(());
+        };
+        let resolved = state.resolved();
+        let op = Operation::new(
+            OperationType::Update,
+            EntityType::Todo,
+            resolved.id,
+            serde_json::to_value(&state.conflict.local).ok(),
+            serde_json::to_value(&resolved).ok(),
+        );
+        self.db.resolve_conflict(&resolved, &op).await?;
+
+        self.refresh_sync_health().await?;
+        self.refresh_todos().await?;
+        self.open_conflicts().await?;
+        self.status_message = Some(format!("✓ Resolved: {}", resolved.title));
+        Ok(())
+    }
+
+    /// Close the conflict review and return to normal mode
+    pub fn close_conflicts(&mut self) {
+        self.conflict_state = None;
+        self.mode = Mode::Normal;
     }
 
     /// Cancel focus session and return to normal mode
-    pub fn cancel_focus(&mut self) {
-        self.focus_state = None;
+    pub async fn cancel_focus(&mut self) -> Result<()> {
+        if let Some(state) = self.focus_state.take() {
+            self.save_focus_session(state.session, FocusOutcome::Stopped)
+                .await?;
+        }
         self.mode = Mode::Normal;
         self.status_message = Some("Focus cancelled".to_string());
+        Ok(())
+    }
+}
+
+/// One-line message for a failed sync; the offline hint is spelled out
+/// for the terminal and too long for the status bar
+fn sync_error_message(error: &TodoeeError) -> String {
+    match error {
+        TodoeeError::Network(_) => "offline - changes saved locally, will sync later".to_string(),
+        e => e.to_string(),
+    }
+}
+
+/// First row to show so that `selected` is on screen, moving as little as
+/// possible from `offset` with `page` rows visible out of `len`
+pub fn scroll_offset(offset: usize, selected: usize, page: usize, len: usize) -> usize {
+    let page = page.max(1);
+    let offset = if selected < offset {
+        selected
+    } else if selected >= offset + page {
+        selected + 1 - page
+    } else {
+        offset
+    };
+    // No blank space below the last todo when the list shrinks
+    offset.min(len.saturating_sub(page))
+}
+
+/// Pasted text with `\r\n` and lone `\r` line endings turned into `\n`
+fn normalize_newlines(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Split pasted text into its first non-blank line and the lines after it,
+/// without surrounding blank lines
+pub fn split_paste(text: &str) -> (String, Option<String>) {
+    let text = normalize_newlines(text);
+    let text = text.trim_start();
+    let (first, rest) = text.split_once('\n').unwrap_or((text, ""));
+    let rest = rest.trim_matches('\n').trim_end();
+    (
+        first.trim().replace('\t', " "),
+        (!rest.is_empty()).then(|| rest.to_string()),
+    )
+}
+
+/// Pasted text as one line: each line trimmed, joined with spaces
+pub fn paste_line(text: &str) -> String {
+    normalize_newlines(text)
+        .lines()
+        .map(|line| line.trim().replace('\t', " "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Append `text` on a new line unless `target` is empty
+fn append_line(target: &mut String, text: &str) {
+    if !target.is_empty() {
+        target.push('\n');
+    }
+    target.push_str(text);
+}
+
+/// Type `text` into `input` at its cursor
+fn insert_text(input: &mut Input, text: &str) {
+    use crossterm::event::{Event, KeyCode, KeyEvent};
+    use tui_input::backend::crossterm::EventHandler;
+
+    for c in text.chars() {
+        input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Char(c))));
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_scroll_offset_keeps_selection_visible() {
+        // Already visible: no movement
+        assert_eq!(scroll_offset(0, 5, 10, 100), 0);
+        assert_eq!(scroll_offset(20, 25, 10, 100), 20);
+        // Below the viewport: selection becomes the last row
+        assert_eq!(scroll_offset(0, 10, 10, 100), 1);
+        assert_eq!(scroll_offset(0, 99, 10, 100), 90);
+        // Above the viewport: selection becomes the first row
+        assert_eq!(scroll_offset(50, 3, 10, 100), 3);
+        // Shrunk list or taller screen pulls the offset back
+        assert_eq!(scroll_offset(90, 95, 10, 50), 40);
+        assert_eq!(scroll_offset(5, 7, 20, 12), 0);
+    }
+
+    #[test]
+    fn test_settings_fields_are_config_keys() {
+        let config = Config::default();
+        for section in [
+            SettingsSection::Ai,
+            SettingsSection::Display,
+            SettingsSection::Notifications,
+            SettingsSection::Database,
+        ] {
+            for field in section.fields() {
+                let value = config.get_value(field.key).unwrap();
+                if field.toggle {
+                    assert!(value == "true" || value == "false", "{}", field.key);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_paste() {
+        assert_eq!(split_paste("Buy milk"), ("Buy milk".to_string(), None));
+        assert_eq!(
+            split_paste("\r\n  Fix login\t bug \r\nSteps:\r\n  1. open app\r\n\r\n"),
+            (
+                "Fix login  bug".to_string(),
+                Some("Steps:\n  1. open app".to_string())
+            )
+        );
+        assert_eq!(paste_line("one\n\n  two\r\nthree "), "one two three");
+    }
 
     #[test]
     fn test_loading_progress_percentage() {
@@ -1314,6 +2411,64 @@ mod tests {
         assert!((progress.percentage() - 0.0).abs() < f64::EPSILON);
     }
 
+    fn matrix_with(counts: [usize; 4]) -> MatrixState {
+        let mut state = MatrixState::default();
+        for (q, count) in counts.iter().enumerate() {
+            state.quadrants[q] = (0..*count)
+                .map(|i| Todo::new(format!("q{} #{}", q, i), None))
+                .collect();
+        }
+        state
+    }
+
+    #[test]
+    fn test_matrix_state_buckets_todos() {
+        let now = Utc::now();
+        let mut urgent = Todo::new("Urgent".to_string(), None);
+        urgent.priority = Priority::High;
+        urgent.due_date = Some(now + Duration::hours(3));
+        let mut done = Todo::new("Done".to_string(), None);
+        done.mark_complete();
+        let someday = Todo::new("Someday".to_string(), None);
+
+        let state = MatrixState::new(vec![urgent, done, someday], now);
+        assert_eq!(state.quadrants[Quadrant::DoFirst.index()].len(), 1);
+        assert_eq!(state.quadrants[Quadrant::Eliminate.index()].len(), 1);
+        assert_eq!(state.quadrants.iter().map(Vec::len).sum::<usize>(), 2);
+    }
+
+    #[test]
+    fn test_matrix_state_navigation_crosses_quadrants() {
+        let mut state = matrix_with([2, 1, 1, 0]);
+
+        state.select_next();
+        assert_eq!((state.active, state.selected), (0, 1));
+        state.select_next();
+        assert_eq!((state.active, state.selected), (2, 0));
+        state.select_next();
+        assert_eq!((state.active, state.selected), (2, 0));
+
+        state.select_previous();
+        assert_eq!((state.active, state.selected), (0, 1));
+
+        state.switch_column();
+        assert_eq!((state.active, state.selected), (1, 0));
+        state.active = 2;
+        state.switch_column();
+        assert_eq!(state.active, 3);
+        assert!(state.selected_todo().is_none());
+    }
+
+    #[test]
+    fn test_matrix_state_select_todo() {
+        let mut state = matrix_with([1, 0, 0, 3]);
+        let id = state.quadrants[3][2].id;
+
+        state.select_todo(id);
+        assert_eq!((state.active, state.selected), (3, 2));
+        assert_eq!(state.selected_todo().map(|t| t.id), Some(id));
+    }
+
     #[test]
     fn test_loading_progress_advance() {
         let mut progress = LoadingProgress::new(3);
@@ -1327,4 +2482,56 @@ mod tests {
         assert_eq!(progress.current, 2);
         assert_eq!(progress.step_name, None);
     }
+
+    #[tokio::test]
+    async fn test_redo_after_undo_and_new_change_does_nothing() {
+        let db = LocalDb::new_in_memory().await.unwrap();
+        db.run_migrations().await.unwrap();
+        let first = Todo::new("First".to_string(), None);
+        let second = Todo::new("Second".to_string(), None);
+        db.create_todo(&first).await.unwrap();
+        db.create_todo(&second).await.unwrap();
+        let mut app = App::with_db(Config::default(), db).await.unwrap();
+        let select = |app: &mut App, id| {
+            app.selected = app.todos.iter().position(|t| t.id == id).unwrap();
+        };
+        let is_done =
+            async |app: &App, id| app.db.get_todo(id).await.unwrap().unwrap().is_completed;
+
+        select(&mut app, first.id);
+        app.mark_selected_done().await.unwrap();
+        app.undo().await.unwrap();
+        assert!(!is_done(&app, first.id).await);
+
+        // Completing another todo leaves nothing to redo
+        select(&mut app, second.id);
+        app.mark_selected_done().await.unwrap();
+        app.redo().await.unwrap();
+        assert_eq!(app.status_message.as_deref(), Some("Nothing to redo"));
+        assert!(!is_done(&app, first.id).await);
+        assert!(is_done(&app, second.id).await);
+
+        app.undo().await.unwrap();
+        app.redo().await.unwrap();
+        assert!(is_done(&app, second.id).await);
+    }
+
+    #[test]
+    fn test_parse_due_date_keeps_time() {
+        assert_eq!(App::parse_due_date(None, &DateInput::default()), Ok(None));
+        assert_eq!(
+            App::parse_due_date(Some("  "), &DateInput::default()),
+            Ok(None)
+        );
+        assert!(App::parse_due_date(Some("someday"), &DateInput::default()).is_err());
+
+        let due = App::parse_due_date(Some("2030-01-02 08:15"), &DateInput::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(dates::format_due(&due, &Local), "2030-01-02 08:15");
+        let due = App::parse_due_date(Some("2030-01-02"), &DateInput::default())
+            .unwrap()
+            .unwrap();
+        assert!(dates::is_date_only(&due));
+    }
 }
@@ -0,0 +1,44 @@
+//! Reading the system clipboard for Ctrl+V, through the platform's own tool.
+//!
+//! Most terminals paste with their own shortcut (Cmd+V, Ctrl+Shift+V), which
+//! arrives as a bracketed paste instead; this covers the ones that pass
+//! Ctrl+V through.
+
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+
+/// Commands that print the clipboard, tried in order
+fn readers() -> Vec<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        vec![("pbpaste", &[])]
+    } else if cfg!(windows) {
+        vec![("powershell", &["-NoProfile", "-Command", "Get-Clipboard"])]
+    } else {
+        let mut readers: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            readers.push(("wl-paste", &["--no-newline"]));
+        }
+        readers.push(("xclip", &["-selection", "clipboard", "-out"]));
+        readers.push(("xsel", &["--clipboard", "--output"]));
+        readers
+    }
+}
+
+/// The clipboard's text
+pub fn read() -> Result<String> {
+    for (program, args) in readers() {
+        let Ok(output) = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+        else {
+            continue;
+        };
+        if output.status.success() {
+            return String::from_utf8(output.stdout).context("Clipboard is not text");
+        }
+    }
+    anyhow::bail!("No clipboard tool found (install wl-clipboard, xclip or xsel)")
+}
@@ -16,6 +16,8 @@ pub enum Event {
     Mouse(MouseEvent),
     /// Terminal resize
     Resize(u16, u16),
+    /// Text pasted in one go (bracketed paste), line breaks included
+    Paste(String),
 }
 
 /// Handles terminal events
@@ -42,24 +44,20 @@ impl EventHandler {
 
                 match event::poll(timeout) {
                     Ok(true) => match event::read() {
-                        Ok(event) => match event {
-                            CrosstermEvent::Key(e) => {
-                                if handler_sender.send(Event::Key(e)).is_err() {
-                                    break;
-                                }
+                        Ok(event) => {
+                            let event = match event {
+                                CrosstermEvent::Key(e) => Some(Event::Key(e)),
+                                CrosstermEvent::Mouse(e) => Some(Event::Mouse(e)),
+                                CrosstermEvent::Resize(w, h) => Some(Event::Resize(w, h)),
+                                CrosstermEvent::Paste(text) => Some(Event::Paste(text)),
+                                _ => None,
+                            };
+                            if let Some(event) = event
+                                && handler_sender.send(event).is_err()
+                            {
+                                break;
                             }
-                            CrosstermEvent::Mouse(e) => {
-                                if handler_sender.send(Event::Mouse(e)).is_err() {
-                                    break;
-                                }
-                            }
-                            CrosstermEvent::Resize(w, h) => {
-                                if handler_sender.send(Event::Resize(w, h)).is_err() {
-                                    break;
-                                }
-                            }
-                            _ => {}
-                        },
+                        }
                         Err(_) => continue,
                     },
                     Ok(false) => {}
@@ -1,7 +1,7 @@
 use anyhow::Result;
-use chrono::TimeZone;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use todoee_core::{EntityType, Operation, OperationType, Priority};
+use todoee_core::conflict::Side;
+use todoee_core::{EntityType, Operation, OperationType, Priority, Quadrant, TodoeeError};
 use tui_input::backend::crossterm::EventHandler as InputHandler;
 
 #[allow(unused_imports)]
@@ -9,13 +9,34 @@ use super::app::InsightsData;
 use super::app::{
     AddField, AddState, App, EditField, EditState, Mode, SettingsSection, SortBy, SortOrder, View,
 };
+use super::theme::Theme;
 use todoee_core::Config;
 
 /// Handle key events and update app state
 pub async fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
-    // Clear status message on any key press
+    // Clear status message and toast on any key press
     app.status_message = None;
+    app.celebration = None;
 
+    if let Err(e) = dispatch_key(app, key).await {
+        // A rejected edit is the user's to fix, not a reason to quit
+        match e.downcast_ref::<TodoeeError>() {
+            Some(err @ TodoeeError::Validation { .. }) => {
+                app.status_message = Some(err.to_string());
+                app.clear_loading();
+            }
+            _ => return Err(e),
+        }
+    }
+
+    if app.tutorial.is_some() {
+        super::tutorial::update(app).await?;
+    }
+
+    Ok(())
+}
+
+async fn dispatch_key(app: &mut App, key: KeyEvent) -> Result<()> {
     match app.mode {
         Mode::Normal => handle_normal_mode(app, key).await?,
         Mode::Adding => handle_adding_mode(app, key).await?,
@@ -25,27 +46,79 @@ pub async fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
         Mode::Help => handle_help_mode(app, key),
         Mode::ViewingDetail => handle_viewing_detail_mode(app, key),
         Mode::AddingCategory => handle_adding_category_mode(app, key).await?,
+        Mode::AddingProject => handle_adding_project_mode(app, key).await?,
         Mode::AddingFull => handle_adding_full_mode(app, key).await?,
         Mode::Insights => {
             app.mode = Mode::Normal;
             app.insights_data = None;
         }
-        Mode::Focus => match key.code {
-            KeyCode::Char(' ') => {
-                if let Some(ref mut state) = app.focus_state {
-                    state.toggle_pause();
-                }
-            }
-            KeyCode::Char('q') | KeyCode::Esc => {
-                app.cancel_focus();
+        Mode::Focus => handle_focus_mode(app, key).await?,
+        Mode::Matrix => handle_matrix_mode(app, key).await?,
+        Mode::Conflicts => handle_conflicts_mode(app, key).await?,
+        Mode::EditingSetting => handle_editing_setting_mode(app, key),
+        Mode::Zen => {
+            if matches!(
+                key.code,
+                KeyCode::Char('N') | KeyCode::Esc | KeyCode::Char('q')
+            ) {
+                app.mode = Mode::Normal;
             }
+        }
+    }
+    Ok(())
+}
+
+/// Handle text pasted into the terminal
+pub fn handle_paste(app: &mut App, text: &str) {
+    app.status_message = None;
+    app.paste(text);
+}
+
+/// Paste from the system clipboard, for terminals that pass Ctrl+V through
+fn paste_clipboard(app: &mut App) {
+    match super::clipboard::read() {
+        Ok(text) => app.paste(&text),
+        Err(e) => app.status_message = Some(format!("Paste failed: {}", e)),
+    }
+}
+
+async fn handle_focus_mode(app: &mut App, key: KeyEvent) -> Result<()> {
+    // Typing the reason for an interruption
+    if let Some(state) = app.focus_state.as_mut()
+        && let Some(reason) = state.reason_input.as_mut()
+    {
+        match key.code {
             KeyCode::Enter => {
-                app.complete_focus();
+                state.log_interruption();
+                let count = state.session.interruptions.len();
+                app.status_message = Some(format!("Interruption logged ({} so far)", count));
+            }
+            KeyCode::Esc => state.reason_input = None,
+            KeyCode::Backspace => {
+                reason.pop();
             }
+            KeyCode::Char(c) => reason.push(c),
             _ => {}
-        },
+        }
+        return Ok(());
     }
 
+    match key.code {
+        KeyCode::Char(' ') => {
+            if let Some(ref mut state) = app.focus_state {
+                state.toggle_pause();
+            }
+        }
+        KeyCode::Char('i') => {
+            if let Some(ref mut state) = app.focus_state {
+                state.reason_input = Some(String::new());
+            }
+        }
+        KeyCode::Char('q') | KeyCode::Esc => app.cancel_focus().await?,
+        KeyCode::Enter => app.complete_focus().await?,
+        KeyCode::Char('?') => app.open_help(),
+        _ => {}
+    }
     Ok(())
 }
 
@@ -73,6 +146,17 @@ async fn handle_normal_mode(app: &mut App, key: KeyEvent) -> Result<()> {
                 app.previous_view = Some(app.current_view);
                 app.view_changed_frame = Some(app.animation_frame);
                 app.current_view = View::Settings;
+                app.refresh_sync_health().await?;
+                app.refresh_daemon_status();
+            }
+            return Ok(());
+        }
+        KeyCode::Char('4') => {
+            if app.current_view != View::Projects {
+                app.previous_view = Some(app.current_view);
+                app.view_changed_frame = Some(app.animation_frame);
+                app.current_view = View::Projects;
+                app.refresh_projects().await?;
             }
             return Ok(());
         }
@@ -81,7 +165,7 @@ async fn handle_normal_mode(app: &mut App, key: KeyEvent) -> Result<()> {
             return Ok(());
         }
         KeyCode::Char('?') => {
-            app.mode = Mode::Help;
+            app.open_help();
             return Ok(());
         }
         _ => {}
@@ -91,7 +175,8 @@ async fn handle_normal_mode(app: &mut App, key: KeyEvent) -> Result<()> {
     match app.current_view {
         View::Todos => handle_todos_view(app, key).await?,
         View::Categories => handle_categories_view(app, key).await?,
-        View::Settings => handle_settings_view(app, key)?,
+        View::Settings => handle_settings_view(app, key).await?,
+        View::Projects => handle_projects_view(app, key).await?,
     }
 
     Ok(())
@@ -102,12 +187,12 @@ async fn handle_todos_view(app: &mut App, key: KeyEvent) -> Result<()> {
         // Navigation
         KeyCode::Char('j') | KeyCode::Down => app.select_next(),
         KeyCode::Char('k') | KeyCode::Up => app.select_previous(),
-        KeyCode::Char('g') => app.selected = 0,
-        KeyCode::Char('G') => {
-            if !app.todos.is_empty() {
-                app.selected = app.todos.len() - 1;
-            }
+        KeyCode::Char('g') | KeyCode::Home => app.selected = 0,
+        KeyCode::Char('G') | KeyCode::End if !app.todos.is_empty() => {
+            app.selected = app.todos.len() - 1;
         }
+        KeyCode::PageDown => app.select_page(1),
+        KeyCode::PageUp => app.select_page(-1),
 
         // Actions
         KeyCode::Char('a') => {
@@ -119,10 +204,14 @@ async fn handle_todos_view(app: &mut App, key: KeyEvent) -> Result<()> {
             app.mode = Mode::Adding;
             app.input.reset();
             app.pending_priority = None;
+            app.pending_description = None;
         }
         KeyCode::Char('d') | KeyCode::Enter => {
             app.mark_selected_done().await?;
         }
+        KeyCode::Char('R') => {
+            app.reopen_selected().await?;
+        }
         KeyCode::Char('x') => {
             app.delete_selected().await?;
         }
@@ -130,17 +219,15 @@ async fn handle_todos_view(app: &mut App, key: KeyEvent) -> Result<()> {
             if let Some(todo) = app.selected_todo() {
                 if todo.is_completed {
                     app.status_message =
-                        Some("Cannot edit completed todo (uncomplete first)".to_string());
+                        Some("Cannot edit completed todo (R to reopen)".to_string());
                 } else {
                     app.edit_state = Some(EditState::from_todo(todo, &app.categories));
                     app.mode = Mode::EditingFull;
                 }
             }
         }
-        KeyCode::Char('v') | KeyCode::Char(' ') => {
-            if app.selected_todo().is_some() {
-                app.mode = Mode::ViewingDetail;
-            }
+        KeyCode::Char('v') | KeyCode::Char(' ') if app.selected_todo().is_some() => {
+            app.open_detail().await?;
         }
 
         // Filtering
@@ -185,13 +272,15 @@ async fn handle_todos_view(app: &mut App, key: KeyEvent) -> Result<()> {
             } else {
                 app.filter.category = Some(app.categories[0].name.clone());
             }
+            app.filter.project = None;
             app.filter.today_only = false;
             app.refresh_todos().await?;
         }
         KeyCode::Char('p') => {
-            // Cycle priority filter: None -> High -> Medium -> Low -> None
+            // Cycle priority filter: None -> Urgent -> High -> Medium -> Low -> None
             app.filter.priority = match app.filter.priority {
-                None => Some(Priority::High),
+                None => Some(Priority::Urgent),
+                Some(Priority::Urgent) => Some(Priority::High),
                 Some(Priority::High) => Some(Priority::Medium),
                 Some(Priority::Medium) => Some(Priority::Low),
                 Some(Priority::Low) => None,
@@ -199,11 +288,12 @@ async fn handle_todos_view(app: &mut App, key: KeyEvent) -> Result<()> {
             app.refresh_todos().await?;
         }
         KeyCode::Char('s') => {
-            // Cycle sort: Created -> DueDate -> Priority -> Title -> Created
+            // Cycle sort: Created -> DueDate -> Priority -> Urgency -> Title -> Created
             app.filter.sort_by = match app.filter.sort_by {
                 SortBy::CreatedAt => SortBy::DueDate,
                 SortBy::DueDate => SortBy::Priority,
-                SortBy::Priority => SortBy::Title,
+                SortBy::Priority => SortBy::Urgency,
+                SortBy::Urgency => SortBy::Title,
                 SortBy::Title => SortBy::CreatedAt,
             };
             app.refresh_todos().await?;
@@ -211,6 +301,7 @@ async fn handle_todos_view(app: &mut App, key: KeyEvent) -> Result<()> {
                 SortBy::CreatedAt => "Created",
                 SortBy::DueDate => "Due Date",
                 SortBy::Priority => "Priority",
+                SortBy::Urgency => "Urgency",
                 SortBy::Title => "Title",
             };
             app.status_message = Some(format!("Sorted by: {}", sort_name));
@@ -257,6 +348,14 @@ async fn handle_todos_view(app: &mut App, key: KeyEvent) -> Result<()> {
             app.mode = Mode::Insights;
         }
 
+        // Display density
+        KeyCode::Char('D') => app.toggle_density(),
+
+        // Eisenhower matrix
+        KeyCode::Char('m') => {
+            app.open_matrix().await?;
+        }
+
         // Now recommendation
         KeyCode::Char('n') => {
             if let Some(idx) = app.get_now_recommendation() {
@@ -269,6 +368,9 @@ async fn handle_todos_view(app: &mut App, key: KeyEvent) -> Result<()> {
             }
         }
 
+        // Zen mode: just the top few recommendations
+        KeyCode::Char('N') => app.mode = Mode::Zen,
+
         // Focus mode
         KeyCode::Char('f') => {
             if app.selected_todo().is_some() {
@@ -292,15 +394,13 @@ async fn handle_todos_view(app: &mut App, key: KeyEvent) -> Result<()> {
 
 async fn handle_categories_view(app: &mut App, key: KeyEvent) -> Result<()> {
     match key.code {
-        KeyCode::Char('j') | KeyCode::Down => {
-            if app.category_selected < app.categories.len().saturating_sub(1) {
-                app.category_selected += 1;
-            }
+        KeyCode::Char('j') | KeyCode::Down
+            if app.category_selected < app.categories.len().saturating_sub(1) =>
+        {
+            app.category_selected += 1;
         }
-        KeyCode::Char('k') | KeyCode::Up => {
-            if app.category_selected > 0 {
-                app.category_selected -= 1;
-            }
+        KeyCode::Char('k') | KeyCode::Up if app.category_selected > 0 => {
+            app.category_selected -= 1;
         }
         KeyCode::Char('a') => {
             app.mode = Mode::AddingCategory;
@@ -314,7 +414,32 @@ async fn handle_categories_view(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
-fn handle_settings_view(app: &mut App, key: KeyEvent) -> Result<()> {
+async fn handle_projects_view(app: &mut App, key: KeyEvent) -> Result<()> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down
+            if app.project_selected < app.projects.len().saturating_sub(1) =>
+        {
+            app.project_selected += 1;
+        }
+        KeyCode::Char('k') | KeyCode::Up if app.project_selected > 0 => {
+            app.project_selected -= 1;
+        }
+        KeyCode::Char('a') => {
+            app.mode = Mode::AddingProject;
+            app.input.reset();
+        }
+        KeyCode::Char('x') => {
+            app.archive_selected_project().await?;
+        }
+        KeyCode::Enter => {
+            app.toggle_project_filter().await?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+async fn handle_settings_view(app: &mut App, key: KeyEvent) -> Result<()> {
     match key.code {
         KeyCode::Char('j') | KeyCode::Down => {
             app.settings_section = match app.settings_section {
@@ -323,6 +448,7 @@ fn handle_settings_view(app: &mut App, key: KeyEvent) -> Result<()> {
                 SettingsSection::Notifications => SettingsSection::Database,
                 SettingsSection::Database => SettingsSection::Database,
             };
+            app.settings_field = 0;
         }
         KeyCode::Char('k') | KeyCode::Up => {
             app.settings_section = match app.settings_section {
@@ -331,27 +457,79 @@ fn handle_settings_view(app: &mut App, key: KeyEvent) -> Result<()> {
                 SettingsSection::Notifications => SettingsSection::Display,
                 SettingsSection::Database => SettingsSection::Notifications,
             };
+            app.settings_field = 0;
+        }
+        KeyCode::Tab => {
+            let count = app.settings_section.fields().len();
+            if count > 0 {
+                app.settings_field = (app.settings_field + 1) % count;
+            }
         }
+        KeyCode::BackTab => {
+            let count = app.settings_section.fields().len();
+            if count > 0 {
+                app.settings_field = (app.settings_field + count - 1) % count;
+            }
+        }
+        KeyCode::Enter => app.start_setting_edit(),
         KeyCode::Char('r') => {
             // Reload config
             if let Ok(config) = Config::load() {
                 app.config = config;
+                app.theme = Theme::from_config(&app.config.display);
                 app.status_message = Some("✓ Configuration reloaded".to_string());
             } else {
                 app.status_message = Some("✗ Failed to reload configuration".to_string());
             }
         }
+        KeyCode::Char('t') if app.settings_section == SettingsSection::Display => {
+            app.cycle_theme();
+        }
+        // Sync health actions
+        KeyCode::Char('s') if app.settings_section == SettingsSection::Database => {
+            app.sync_now().await?;
+        }
+        KeyCode::Char('p') if app.settings_section == SettingsSection::Database => {
+            app.ping_remote().await?;
+        }
+        KeyCode::Char('c') if app.settings_section == SettingsSection::Database => {
+            app.show_sync_conflicts = !app.show_sync_conflicts;
+        }
+        KeyCode::Char('v') if app.settings_section == SettingsSection::Database => {
+            app.open_conflicts().await?;
+        }
         _ => {}
     }
     Ok(())
 }
 
+fn handle_editing_setting_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.mode = Mode::Normal;
+            app.input.reset();
+        }
+        KeyCode::Enter => {
+            if let Some(field) = app.selected_setting() {
+                let value = app.input.value().to_string();
+                app.set_setting(field.key, &value);
+            }
+            app.input.reset();
+            app.mode = Mode::Normal;
+        }
+        _ => {
+            app.input.handle_event(&crossterm::event::Event::Key(key));
+        }
+    }
+}
+
 async fn handle_adding_mode(app: &mut App, key: KeyEvent) -> Result<()> {
     match key.code {
         KeyCode::Esc => {
             app.mode = Mode::Normal;
             app.input.reset();
             app.pending_priority = None;
+            app.pending_description = None;
         }
         KeyCode::Enter => {
             // Use AI only if Shift held AND AI is configured
@@ -359,8 +537,16 @@ async fn handle_adding_mode(app: &mut App, key: KeyEvent) -> Result<()> {
             app.add_todo_with_ai(use_ai).await?;
             app.mode = Mode::Normal;
             app.pending_priority = None;
+            app.pending_description = None;
+        }
+        KeyCode::Char('v')
+            if key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::SUPER) =>
+        {
+            paste_clipboard(app);
         }
-        // Priority shortcuts: Ctrl+1/2/3 or Alt+1/2/3
+        // Priority shortcuts: Ctrl+1/2/3/4 or Alt+1/2/3/4
         KeyCode::Char('1')
             if key.modifiers.contains(KeyModifiers::CONTROL)
                 || key.modifiers.contains(KeyModifiers::ALT) =>
@@ -379,13 +565,20 @@ async fn handle_adding_mode(app: &mut App, key: KeyEvent) -> Result<()> {
         {
             app.pending_priority = Some(Priority::High);
         }
+        KeyCode::Char('4')
+            if key.modifiers.contains(KeyModifiers::CONTROL)
+                || key.modifiers.contains(KeyModifiers::ALT) =>
+        {
+            app.pending_priority = Some(Priority::Urgent);
+        }
         // Tab cycles priority
         KeyCode::Tab => {
             app.pending_priority = match app.pending_priority {
                 None => Some(Priority::Low),
                 Some(Priority::Low) => Some(Priority::Medium),
                 Some(Priority::Medium) => Some(Priority::High),
-                Some(Priority::High) => None,
+                Some(Priority::High) => Some(Priority::Urgent),
+                Some(Priority::Urgent) => None,
             };
         }
         _ => {
@@ -440,6 +633,13 @@ async fn handle_searching_mode(app: &mut App, key: KeyEvent) -> Result<()> {
         KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             app.input.reset();
         }
+        KeyCode::Char('v')
+            if key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::SUPER) =>
+        {
+            paste_clipboard(app);
+        }
         _ => {
             app.input.handle_event(&crossterm::event::Event::Key(key));
         }
@@ -448,8 +648,111 @@ async fn handle_searching_mode(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
-fn handle_help_mode(app: &mut App, _key: KeyEvent) {
-    app.mode = Mode::Normal;
+async fn handle_matrix_mode(app: &mut App, key: KeyEvent) -> Result<()> {
+    let Some(ref mut state) = app.matrix_state else {
+        app.mode = Mode::Normal;
+        return Ok(());
+    };
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('m') => app.close_matrix(),
+        KeyCode::Char('?') => app.open_help(),
+        KeyCode::Char('j') | KeyCode::Down => state.select_next(),
+        KeyCode::Char('k') | KeyCode::Up => state.select_previous(),
+        KeyCode::Char('h') | KeyCode::Left | KeyCode::Char('l') | KeyCode::Right => {
+            state.switch_column();
+        }
+        KeyCode::Char(c @ '1'..='4') => {
+            let target = Quadrant::ALL[c as usize - '1' as usize];
+            app.move_matrix_selected(target).await?;
+        }
+        KeyCode::Char('u') => {
+            app.undo().await?;
+            app.open_matrix().await?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+async fn handle_conflicts_mode(app: &mut App, key: KeyEvent) -> Result<()> {
+    let Some(ref mut state) = app.conflict_state else {
+        app.mode = Mode::Normal;
+        return Ok(());
+    };
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_conflicts(),
+        KeyCode::Char('?') => app.open_help(),
+        KeyCode::Char('j') | KeyCode::Down => state.select_next(),
+        KeyCode::Char('k') | KeyCode::Up => state.select_previous(),
+        KeyCode::Char('h') | KeyCode::Left => state.pick(Side::Local),
+        KeyCode::Char('l') | KeyCode::Right => state.pick(Side::Remote),
+        KeyCode::Char(' ') | KeyCode::Tab => state.toggle(),
+        KeyCode::Char('L') => state.pick_all(Side::Local),
+        KeyCode::Char('R') => state.pick_all(Side::Remote),
+        KeyCode::Enter => app.resolve_conflict().await?,
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn handle_help_mode(app: &mut App, key: KeyEvent) {
+    let Some(ref mut state) = app.help_state else {
+        app.mode = Mode::Normal;
+        return;
+    };
+
+    if state.searching {
+        match key.code {
+            KeyCode::Enter => state.searching = false,
+            KeyCode::Esc => {
+                state.searching = false;
+                state.query.clear();
+            }
+            KeyCode::Backspace => {
+                state.query.pop();
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                state.query.clear();
+            }
+            KeyCode::Char(c) => state.query.push(c),
+            _ => {}
+        }
+        state.scroll = 0;
+        return;
+    }
+
+    let line_count = state.lines(&app.cli_commands).len();
+    let page = state.page.get().max(1) as isize;
+    match key.code {
+        KeyCode::Char('/') => state.searching = true,
+        KeyCode::Char('j') | KeyCode::Down => state.scroll_by(1, line_count),
+        KeyCode::Char('k') | KeyCode::Up => state.scroll_by(-1, line_count),
+        KeyCode::PageDown | KeyCode::Char(' ') => state.scroll_by(page, line_count),
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.scroll_by(page, line_count)
+        }
+        KeyCode::PageUp => state.scroll_by(-page, line_count),
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.scroll_by(-page, line_count)
+        }
+        KeyCode::Char('g') | KeyCode::Home => state.scroll = 0,
+        KeyCode::Char('G') | KeyCode::End => state.scroll_by(isize::MAX, line_count),
+        KeyCode::Tab => {
+            state.show_all = !state.show_all;
+            state.scroll = 0;
+        }
+        // Esc first drops a search, then closes
+        KeyCode::Esc if !state.query.is_empty() => {
+            state.query.clear();
+            state.scroll = 0;
+        }
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => app.close_help(),
+        _ => {}
+    }
 }
 
 fn handle_viewing_detail_mode(app: &mut App, key: KeyEvent) {
@@ -461,6 +764,8 @@ fn handle_viewing_detail_mode(app: &mut App, key: KeyEvent) {
         | KeyCode::Enter => {
             app.mode = Mode::Normal;
         }
+        KeyCode::Char('o') => app.open_attachment(0),
+        KeyCode::Char(c @ '1'..='9') => app.open_attachment(c as usize - '1' as usize),
         _ => {}
     }
 }
@@ -496,6 +801,16 @@ async fn handle_editing_full_mode(app: &mut App, key: KeyEvent) -> Result<()> {
         }
         KeyCode::Enter => {
             // Save changes
+            let due_date = match App::parse_due_date(
+                state.due_date.as_deref(),
+                &app.config.display.date_input(),
+            ) {
+                Ok(due) => due,
+                Err(msg) => {
+                    app.status_message = Some(msg);
+                    return Ok(());
+                }
+            };
             let todo_id = state.todo_id;
             let category_name = state.category_name.clone();
 
@@ -518,12 +833,7 @@ async fn handle_editing_full_mode(app: &mut App, key: KeyEvent) -> Result<()> {
                 Some(state.description.clone())
             };
             todo.priority = state.priority;
-            todo.due_date = state.due_date.as_ref().and_then(|s| {
-                chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
-                    .ok()
-                    .and_then(|d| d.and_hms_opt(12, 0, 0))
-                    .map(|dt| chrono::Utc.from_utc_datetime(&dt))
-            });
+            todo.due_date = due_date;
             // Set category_id from name
             todo.category_id = category_name.as_ref().and_then(|name| {
                 app.categories
@@ -564,12 +874,13 @@ async fn handle_editing_full_mode(app: &mut App, key: KeyEvent) -> Result<()> {
                         '1' => Priority::Low,
                         '2' => Priority::Medium,
                         '3' => Priority::High,
+                        '4' => Priority::Urgent,
                         _ => state.priority,
                     };
                 }
                 EditField::DueDate => {
                     let due = state.due_date.get_or_insert_with(String::new);
-                    if c.is_ascii_digit() || c == '-' {
+                    if is_date_char(c) {
                         due.push(c);
                     }
                 }
@@ -640,6 +951,30 @@ async fn handle_adding_category_mode(app: &mut App, key: KeyEvent) -> Result<()>
     Ok(())
 }
 
+async fn handle_adding_project_mode(app: &mut App, key: KeyEvent) -> Result<()> {
+    match key.code {
+        KeyCode::Esc => {
+            app.mode = Mode::Normal;
+            app.input.reset();
+        }
+        KeyCode::Enter => {
+            let name = app.input.value().trim().to_string();
+            app.add_project(name).await?;
+            app.input.reset();
+            app.mode = Mode::Normal;
+        }
+        _ => {
+            app.input.handle_event(&crossterm::event::Event::Key(key));
+        }
+    }
+    Ok(())
+}
+
+/// Characters accepted by date fields: "2026-03-01 14:30", "friday 3pm", "+3d"
+fn is_date_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | ':' | ' ')
+}
+
 async fn handle_adding_full_mode(app: &mut App, key: KeyEvent) -> Result<()> {
     let Some(ref mut state) = app.add_state else {
         app.mode = Mode::Normal;
@@ -672,12 +1007,16 @@ async fn handle_adding_full_mode(app: &mut App, key: KeyEvent) -> Result<()> {
             };
         }
         KeyCode::Enter => {
-            if state.is_valid() {
+            if !state.is_valid() {
+                app.status_message = Some("Title is required".to_string());
+            } else if let Err(msg) =
+                App::parse_due_date(state.due_date.as_deref(), &app.config.display.date_input())
+            {
+                app.status_message = Some(msg);
+            } else {
                 app.create_todo_from_add_state().await?;
                 app.add_state = None;
                 app.mode = Mode::Normal;
-            } else {
-                app.status_message = Some("Title is required".to_string());
             }
         }
         KeyCode::Char(c) => {
@@ -689,18 +1028,19 @@ async fn handle_adding_full_mode(app: &mut App, key: KeyEvent) -> Result<()> {
                         '1' => Priority::Low,
                         '2' => Priority::Medium,
                         '3' => Priority::High,
+                        '4' => Priority::Urgent,
                         _ => state.priority,
                     };
                 }
                 AddField::DueDate => {
                     let due = state.due_date.get_or_insert_with(String::new);
-                    if c.is_ascii_alphanumeric() || c == '-' || c == '+' {
+                    if is_date_char(c) {
                         due.push(c);
                     }
                 }
                 AddField::Reminder => {
                     let rem = state.reminder.get_or_insert_with(String::new);
-                    if c.is_ascii_digit() || c == '-' || c == ':' || c == ' ' {
+                    if is_date_char(c) {
                         rem.push(c);
                     }
                 }
@@ -0,0 +1,411 @@
+//! Keyboard shortcuts of the TUI, grouped by where they apply.
+//!
+//! The help screen (`?`) is built from this table and from the CLI's
+//! command list, so a key added to `handler.rs` belongs here too.
+
+use super::app::{Mode, SettingsSection, View};
+
+/// Where a group of shortcuts applies; help opens the matching sections
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpContext {
+    Todos,
+    Categories,
+    Projects,
+    Settings,
+    SettingsDatabase,
+    Focus,
+    Matrix,
+    Conflicts,
+}
+
+impl HelpContext {
+    /// The context help was opened from
+    pub fn of(mode: Mode, view: View, settings_section: SettingsSection) -> Self {
+        match (mode, view) {
+            (Mode::Focus, _) => Self::Focus,
+            (Mode::Matrix, _) => Self::Matrix,
+            (Mode::Conflicts, _) => Self::Conflicts,
+            (_, View::Todos) => Self::Todos,
+            (_, View::Categories) => Self::Categories,
+            (_, View::Projects) => Self::Projects,
+            (_, View::Settings) if settings_section == SettingsSection::Database => {
+                Self::SettingsDatabase
+            }
+            (_, View::Settings) => Self::Settings,
+        }
+    }
+}
+
+/// One shortcut
+pub struct Binding {
+    pub keys: &'static str,
+    pub action: &'static str,
+}
+
+/// A titled group of shortcuts
+pub struct Section {
+    pub title: &'static str,
+    /// Contexts in which the section is opened; empty means every context
+    pub contexts: &'static [HelpContext],
+    pub bindings: &'static [Binding],
+}
+
+impl Section {
+    fn applies_to(&self, context: HelpContext) -> bool {
+        self.contexts.is_empty() || self.contexts.contains(&context)
+    }
+}
+
+const fn key(keys: &'static str, action: &'static str) -> Binding {
+    Binding { keys, action }
+}
+
+use HelpContext::*;
+
+pub const SECTIONS: &[Section] = &[
+    Section {
+        title: "NAVIGATION",
+        contexts: &[Todos, Categories, Projects, Settings, SettingsDatabase],
+        bindings: &[
+            key("j / ↓", "Move down"),
+            key("k / ↑", "Move up"),
+            key("g / G, Home / End", "Jump to top / bottom (todos)"),
+            key("PgDn / PgUp", "Move a screen down / up (todos)"),
+            key("1 - 4", "Switch views (Todos/Categories/Settings/Projects)"),
+        ],
+    },
+    Section {
+        title: "CORE ACTIONS",
+        contexts: &[Todos],
+        bindings: &[
+            key("a", "Add task (full editor with all fields)"),
+            key("A", "Quick add (offline, Shift+Enter for AI)"),
+            key("e", "Edit selected task"),
+            key("d / Enter", "Mark as done"),
+            key("R", "Reopen completed task"),
+            key("x", "Delete task"),
+            key("v / Space", "View task details"),
+        ],
+    },
+    Section {
+        title: "GIT-LIKE COMMANDS",
+        contexts: &[Todos],
+        bindings: &[
+            key("u", "Undo last action"),
+            key("Ctrl+r", "Redo last undone action"),
+            key("z", "Stash selected task (hide temporarily)"),
+            key("Z", "Pop from stash (restore last stashed)"),
+        ],
+    },
+    Section {
+        title: "FILTERS & SORTING",
+        contexts: &[Todos],
+        bindings: &[
+            key("/", "Search (fuzzy matching)"),
+            key("t", "Toggle today filter"),
+            key("o", "Toggle overdue filter"),
+            key("p", "Cycle priority filter (All→High→Med→Low)"),
+            key("c", "Cycle category filter"),
+            key("s", "Cycle sort (Created→Due→Priority→Urgency→Title)"),
+            key("S", "Toggle sort order (Asc/Desc)"),
+            key("Tab", "Toggle show/hide completed"),
+        ],
+    },
+    Section {
+        title: "SEARCH (/)",
+        contexts: &[Todos],
+        bindings: &[
+            key("Enter", "Apply search"),
+            key(":", "Start a filter query (pri:high due<=+3d)"),
+            key("Ctrl+u", "Clear the query"),
+            key("Ctrl+v", "Paste from the clipboard"),
+            key("Esc", "Cancel and show all tasks"),
+        ],
+    },
+    Section {
+        title: "PRODUCTIVITY",
+        contexts: &[Todos],
+        bindings: &[
+            key("n", "Jump to recommended task (smart pick)"),
+            key("N", "Zen mode: only the top 3 tasks (N again to leave)"),
+            key("f", "Start focus session (25 min pomodoro)"),
+            key("F", "Quick focus (5 min)"),
+            key("i", "View productivity insights"),
+            key("m", "Eisenhower matrix"),
+            key("D", "Toggle density (comfortable/compact, saved)"),
+        ],
+    },
+    Section {
+        title: "FOCUS MODE",
+        contexts: &[Focus],
+        bindings: &[
+            key("Space", "Pause / Resume timer"),
+            key("i", "Log an interruption, with an optional reason"),
+            key("Enter", "Complete early"),
+            key("q / Esc", "Cancel focus session"),
+        ],
+    },
+    Section {
+        title: "EISENHOWER MATRIX (m)",
+        contexts: &[Matrix],
+        bindings: &[
+            key("j / k", "Move within a quadrant"),
+            key("h / l", "Switch column"),
+            key("1 - 4", "Move task to quadrant"),
+            key("u", "Undo last move"),
+            key("m / q / Esc", "Close the matrix"),
+        ],
+    },
+    Section {
+        title: "CATEGORIES TAB",
+        contexts: &[Categories],
+        bindings: &[key("a", "New category"), key("x", "Delete category")],
+    },
+    Section {
+        title: "PROJECTS TAB",
+        contexts: &[Projects],
+        bindings: &[
+            key("a", "New project"),
+            key("x", "Archive project"),
+            key("Enter", "Show the project's todos (again to show all)"),
+        ],
+    },
+    Section {
+        title: "SETTINGS TAB",
+        contexts: &[Settings, SettingsDatabase],
+        bindings: &[
+            key("j / k", "Move between sections"),
+            key("Tab / Shift+Tab", "Select a setting"),
+            key("Enter", "Edit the setting, or flip it if on/off (saved)"),
+            key("r", "Reload configuration"),
+            key("t", "Switch theme in Display (saved)"),
+        ],
+    },
+    Section {
+        title: "SETTINGS › DATABASE",
+        contexts: &[SettingsDatabase],
+        bindings: &[
+            key("s", "Sync now"),
+            key("p", "Ping cloud database"),
+            key("c", "Show sync conflicts"),
+            key("v", "Review sync conflicts field by field"),
+        ],
+    },
+    Section {
+        title: "SYNC CONFLICTS (v)",
+        contexts: &[Conflicts],
+        bindings: &[
+            key("j / k", "Move between fields"),
+            key("h / l", "Keep the local / remote value"),
+            key("Space / Tab", "Switch the field to the other side"),
+            key("L / R", "Keep every local / remote value"),
+            key("Enter", "Resolve and review the next conflict"),
+            key("q / Esc", "Close without resolving"),
+        ],
+    },
+    Section {
+        title: "TASK DETAILS (v)",
+        contexts: &[Todos],
+        bindings: &[
+            key("o", "Open the first attachment"),
+            key("1 - 9", "Open attachment by number"),
+            key("v / q / Esc", "Close details"),
+        ],
+    },
+    Section {
+        title: "EDITOR (a / e)",
+        contexts: &[Todos],
+        bindings: &[
+            key("Tab", "Next field"),
+            key("Shift+Tab", "Previous field"),
+            key("1 / 2 / 3", "Set priority (on priority field)"),
+            key("Enter", "Save changes"),
+            key("Esc", "Cancel"),
+        ],
+    },
+    Section {
+        title: "QUICK ADD (A)",
+        contexts: &[Todos],
+        bindings: &[
+            key("Enter", "Submit (offline)"),
+            key("Shift+Enter", "Submit with AI parsing"),
+            key("Tab", "Cycle priority"),
+            key("Ctrl+1/2/3/4", "Set priority"),
+            key(
+                "Ctrl+v",
+                "Paste; lines after the first become the description",
+            ),
+        ],
+    },
+    Section {
+        title: "HELP (?)",
+        contexts: &[],
+        bindings: &[
+            key("j / k", "Scroll"),
+            key("PgDn / PgUp", "Scroll a page"),
+            key("/", "Search shortcuts and commands"),
+            key("Tab", "Open or close all sections"),
+            key("q / ? / Esc", "Close help"),
+        ],
+    },
+    Section {
+        title: "GENERAL",
+        contexts: &[],
+        bindings: &[key("?", "Toggle this help"), key("q / Esc", "Quit")],
+    },
+];
+
+/// Title of the section listing CLI commands
+pub const COMMANDS_TITLE: &str = "CLI COMMANDS (todoee <command>)";
+
+/// A line of the help screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HelpLine {
+    /// Section title; a closed section shows how many entries it hides
+    Section {
+        title: String,
+        open: bool,
+        entries: usize,
+    },
+    Entry {
+        keys: String,
+        action: String,
+    },
+    Blank,
+}
+
+/// The help screen's lines.
+///
+/// Sections for `context` come first and open; the rest, and the CLI
+/// `commands` (name, description), follow closed unless `show_all` is set.
+/// A `query` keeps only the shortcuts and commands matching it, with their
+/// sections open.
+pub fn help_lines(
+    context: HelpContext,
+    commands: &[(String, String)],
+    query: &str,
+    show_all: bool,
+) -> Vec<HelpLine> {
+    let query = query.trim().to_lowercase();
+    let matches = |keys: &str, action: &str| {
+        query.is_empty()
+            || keys.to_lowercase().contains(&query)
+            || action.to_lowercase().contains(&query)
+    };
+
+    let mut groups: Vec<_> = SECTIONS
+        .iter()
+        .map(|section| {
+            let entries: Vec<(&str, &str)> = section
+                .bindings
+                .iter()
+                .map(|b| (b.keys, b.action))
+                .collect();
+            (section.applies_to(context), section.title, entries)
+        })
+        .collect();
+    groups.push((
+        false,
+        COMMANDS_TITLE,
+        commands
+            .iter()
+            .map(|(name, about)| (name.as_str(), about.as_str()))
+            .collect(),
+    ));
+    // Stable sort keeps the table's order within each half
+    groups.sort_by_key(|(relevant, _, _)| !relevant);
+
+    let mut lines = Vec::new();
+    for (relevant, title, entries) in groups {
+        let entries: Vec<_> = entries
+            .into_iter()
+            .filter(|(keys, action)| matches(keys, action))
+            .collect();
+        if entries.is_empty() {
+            continue;
+        }
+        let open = relevant || show_all || !query.is_empty();
+        lines.push(HelpLine::Section {
+            title: title.to_string(),
+            open,
+            entries: entries.len(),
+        });
+        if open {
+            lines.extend(entries.into_iter().map(|(keys, action)| HelpLine::Entry {
+                keys: keys.to_string(),
+                action: action.to_string(),
+            }));
+            lines.push(HelpLine::Blank);
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn titles(lines: &[HelpLine]) -> Vec<(&str, bool)> {
+        lines
+            .iter()
+            .filter_map(|line| match line {
+                HelpLine::Section { title, open, .. } => Some((title.as_str(), *open)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_context_sections_open_first() {
+        let commands = vec![("sync".to_string(), "Sync with the cloud".to_string())];
+        let lines = help_lines(HelpContext::Projects, &commands, "", false);
+        let shown = titles(&lines);
+        assert_eq!(shown[0], ("NAVIGATION", true));
+        assert_eq!(shown[1], ("PROJECTS TAB", true));
+        assert!(shown.contains(&("CORE ACTIONS", false)));
+        assert!(shown.contains(&("GENERAL", true)));
+        assert_eq!(shown.last(), Some(&(COMMANDS_TITLE, false)));
+
+        let all = help_lines(HelpContext::Projects, &commands, "", true);
+        assert!(titles(&all).iter().all(|(_, open)| *open));
+    }
+
+    #[test]
+    fn test_search_filters_shortcuts_and_commands() {
+        let commands = vec![
+            ("undo".to_string(), "Undo the last operation".to_string()),
+            ("sync".to_string(), "Sync with the cloud".to_string()),
+        ];
+        let lines = help_lines(HelpContext::Focus, &commands, "UNDO", false);
+        let entries: Vec<_> = lines
+            .iter()
+            .filter_map(|line| match line {
+                HelpLine::Entry { keys, .. } => Some(keys.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(entries.contains(&"u"));
+        assert!(entries.contains(&"Ctrl+r"));
+        assert!(entries.contains(&"undo"));
+        assert!(!entries.contains(&"sync"));
+        assert!(titles(&lines).iter().all(|(_, open)| *open));
+
+        assert!(help_lines(HelpContext::Todos, &commands, "no such thing", false).is_empty());
+    }
+
+    #[test]
+    fn test_context_of_mode() {
+        assert_eq!(
+            HelpContext::of(Mode::Matrix, View::Todos, SettingsSection::Ai),
+            HelpContext::Matrix
+        );
+        assert_eq!(
+            HelpContext::of(Mode::Conflicts, View::Settings, SettingsSection::Database),
+            HelpContext::Conflicts
+        );
+        assert_eq!(
+            HelpContext::of(Mode::Normal, View::Settings, SettingsSection::Database),
+            HelpContext::SettingsDatabase
+        );
+    }
+}
@@ -1,14 +1,17 @@
 pub mod app;
+pub mod clipboard;
 pub mod event;
 pub mod handler;
+pub mod keymap;
 pub mod spinner;
 pub mod terminal;
 pub mod theme;
+pub mod timeblock;
+pub mod tutorial;
 pub mod ui;
 pub mod widgets;
 
 pub use app::App;
 pub use event::{Event, EventHandler};
-pub use handler::handle_key_event;
+pub use handler::{handle_key_event, handle_paste};
 pub use terminal::Tui;
-// Theme is available for future customization: pub use theme::Theme;
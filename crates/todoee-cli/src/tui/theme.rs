@@ -1,13 +1,27 @@
-use ratatui::style::{Color, Modifier, Style};
+//! Colors of the TUI, picked with `display.theme`
+//!
+//! Widgets take every color from a [`Theme`] role rather than naming one,
+//! so a palette applies everywhere. `dark` is the original look; `custom`
+//! starts from it and replaces the roles set in `[display.colors]`.
 
-/// Application theme colors - provides centralized styling for future customization
-#[allow(dead_code)]
+use std::collections::BTreeMap;
+
+use ratatui::style::Color;
+use todoee_core::{DisplayConfig, Priority};
+
+/// Application theme colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Theme {
+    /// Cursor, titles and the active tab
     pub primary: Color,
+    /// Due dates later this week, links
     pub secondary: Color,
+    /// AI badges and paused timers
+    pub accent: Color,
     pub success: Color,
     pub warning: Color,
     pub error: Color,
+    /// Hints, IDs and completed todos
     pub muted: Color,
     pub border: Color,
     pub border_focused: Color,
@@ -18,57 +32,164 @@ pub struct Theme {
 
 impl Default for Theme {
     fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
         Self {
             primary: Color::Cyan,
             secondary: Color::Blue,
+            accent: Color::Magenta,
             success: Color::Green,
             warning: Color::Yellow,
             error: Color::Red,
             muted: Color::DarkGray,
             border: Color::DarkGray,
             border_focused: Color::Cyan,
-            selection_bg: Color::Rgb(40, 40, 50),
+            selection_bg: Color::DarkGray,
             text: Color::White,
             text_muted: Color::Gray,
         }
     }
-}
 
-#[allow(dead_code)]
-impl Theme {
-    pub fn border_style(&self, focused: bool) -> Style {
-        Style::default().fg(if focused {
-            self.border_focused
-        } else {
-            self.border
-        })
+    /// For terminals with a light background
+    pub fn light() -> Self {
+        Self {
+            primary: Color::Blue,
+            secondary: Color::Rgb(0, 110, 160),
+            accent: Color::Magenta,
+            success: Color::Rgb(0, 128, 0),
+            warning: Color::Rgb(175, 95, 0),
+            error: Color::Rgb(190, 0, 0),
+            muted: Color::Rgb(120, 120, 120),
+            border: Color::Rgb(170, 170, 170),
+            border_focused: Color::Blue,
+            selection_bg: Color::Rgb(215, 225, 240),
+            text: Color::Black,
+            text_muted: Color::Rgb(90, 90, 90),
+        }
     }
 
-    pub fn title_style(&self) -> Style {
-        Style::default()
-            .fg(self.primary)
-            .add_modifier(Modifier::BOLD)
+    /// Ethan Schoonover's Solarized (dark)
+    pub fn solarized() -> Self {
+        Self {
+            primary: Color::Rgb(0x26, 0x8b, 0xd2),
+            secondary: Color::Rgb(0x2a, 0xa1, 0x98),
+            accent: Color::Rgb(0xd3, 0x36, 0x82),
+            success: Color::Rgb(0x85, 0x99, 0x00),
+            warning: Color::Rgb(0xb5, 0x89, 0x00),
+            error: Color::Rgb(0xdc, 0x32, 0x2f),
+            muted: Color::Rgb(0x58, 0x6e, 0x75),
+            border: Color::Rgb(0x58, 0x6e, 0x75),
+            border_focused: Color::Rgb(0x26, 0x8b, 0xd2),
+            selection_bg: Color::Rgb(0x07, 0x36, 0x42),
+            text: Color::Rgb(0x93, 0xa1, 0xa1),
+            text_muted: Color::Rgb(0x83, 0x94, 0x96),
+        }
     }
 
-    pub fn selected_style(&self) -> Style {
-        Style::default().bg(self.selection_bg)
+    /// Pavel Pertsev's gruvbox (dark)
+    pub fn gruvbox() -> Self {
+        Self {
+            primary: Color::Rgb(0x8e, 0xc0, 0x7c),
+            secondary: Color::Rgb(0x83, 0xa5, 0x98),
+            accent: Color::Rgb(0xd3, 0x86, 0x9b),
+            success: Color::Rgb(0xb8, 0xbb, 0x26),
+            warning: Color::Rgb(0xfa, 0xbd, 0x2f),
+            error: Color::Rgb(0xfb, 0x49, 0x34),
+            muted: Color::Rgb(0x92, 0x83, 0x74),
+            border: Color::Rgb(0x66, 0x5c, 0x54),
+            border_focused: Color::Rgb(0x8e, 0xc0, 0x7c),
+            selection_bg: Color::Rgb(0x50, 0x49, 0x45),
+            text: Color::Rgb(0xeb, 0xdb, 0xb2),
+            text_muted: Color::Rgb(0xbd, 0xae, 0x93),
+        }
     }
 
-    pub fn priority_high(&self) -> Style {
-        Style::default().fg(self.error).add_modifier(Modifier::BOLD)
+    /// The theme named by `display.theme`; unknown names get the dark one
+    pub fn from_config(display: &DisplayConfig) -> Self {
+        match display.theme.as_str() {
+            "light" => Self::light(),
+            "solarized" => Self::solarized(),
+            "gruvbox" => Self::gruvbox(),
+            "custom" => Self::dark().with_colors(&display.colors),
+            _ => Self::dark(),
+        }
     }
 
-    pub fn priority_medium(&self) -> Style {
-        Style::default().fg(self.warning)
+    /// Replace roles with the hex colors in `colors`, skipping unknown
+    /// roles and colors that don't parse
+    fn with_colors(mut self, colors: &BTreeMap<String, String>) -> Self {
+        for (role, hex) in colors {
+            let Some(color) = parse_hex_color(hex) else {
+                continue;
+            };
+            let slot = match role.as_str() {
+                "primary" => &mut self.primary,
+                "secondary" => &mut self.secondary,
+                "accent" => &mut self.accent,
+                "success" => &mut self.success,
+                "warning" => &mut self.warning,
+                "error" => &mut self.error,
+                "muted" => &mut self.muted,
+                "border" => &mut self.border,
+                "border_focused" => &mut self.border_focused,
+                "selection_bg" => &mut self.selection_bg,
+                "text" => &mut self.text,
+                "text_muted" => &mut self.text_muted,
+                _ => continue,
+            };
+            *slot = color;
+        }
+        self
     }
 
-    pub fn priority_low(&self) -> Style {
-        Style::default().fg(self.success)
+    /// Red, yellow and green in the dark theme
+    pub fn priority(&self, priority: Priority) -> Color {
+        match priority {
+            Priority::Urgent | Priority::High => self.error,
+            Priority::Medium => self.warning,
+            Priority::Low => self.success,
+        }
     }
+}
+
+/// `#rrggbb` (the `#` is optional) as a color
+pub fn parse_hex_color(hex: &str) -> Option<Color> {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some(Color::Rgb(r, g, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_from_config() {
+        let mut display = DisplayConfig::default();
+        assert_eq!(Theme::from_config(&display), Theme::dark());
+        display.theme = "gruvbox".to_string();
+        assert_eq!(Theme::from_config(&display), Theme::gruvbox());
 
-    pub fn completed_style(&self) -> Style {
-        Style::default()
-            .fg(self.muted)
-            .add_modifier(Modifier::CROSSED_OUT)
+        // Colors only apply to the custom theme
+        display
+            .colors
+            .insert("primary".to_string(), "#ff8800".to_string());
+        display
+            .colors
+            .insert("error".to_string(), "not a color".to_string());
+        assert_eq!(Theme::from_config(&display), Theme::gruvbox());
+        display.theme = "custom".to_string();
+        let custom = Theme::from_config(&display);
+        assert_eq!(custom.primary, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(custom.error, Theme::dark().error);
     }
 }
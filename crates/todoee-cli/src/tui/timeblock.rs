@@ -0,0 +1,183 @@
+//! Timeline editor for `todoee timeblock`
+//!
+//! Shows the day's blocks between calendar events, one row per slot, and
+//! lets the blocks be moved, resized or dropped before they are saved as
+//! reminders. Blocks may overlap while editing; overlaps are shown in the
+//! warning color.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use todoee_core::TimeBlock;
+use todoee_core::timeblock::SLOT_MINUTES;
+
+use super::theme::Theme;
+use super::widgets::TimeblockWidget;
+use super::{Event, EventHandler, Tui};
+
+/// An event that takes time: title, start and end
+pub type Busy = (String, DateTime<Utc>, DateTime<Utc>);
+
+/// The plan being edited
+#[derive(Debug, Clone)]
+pub struct Planner {
+    pub blocks: Vec<TimeBlock>,
+    pub busy: Vec<Busy>,
+    /// First slot on the timeline
+    pub from: DateTime<Utc>,
+    /// End of the last slot
+    pub until: DateTime<Utc>,
+    pub selected: usize,
+}
+
+impl Planner {
+    pub fn new(
+        blocks: Vec<TimeBlock>,
+        busy: Vec<Busy>,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            blocks,
+            busy,
+            from,
+            until,
+            selected: 0,
+        }
+    }
+
+    /// Apply a key. `Some(true)` saves the plan, `Some(false)` discards it.
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<bool> {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('s') => return Some(true),
+            KeyCode::Esc | KeyCode::Char('q') => return Some(false),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(false);
+            }
+            KeyCode::Down if shift => self.shift(1),
+            KeyCode::Up if shift => self.shift(-1),
+            KeyCode::Char('J') => self.shift(1),
+            KeyCode::Char('K') => self.shift(-1),
+            KeyCode::Down | KeyCode::Char('j') if self.selected + 1 < self.blocks.len() => {
+                self.selected += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.resize(1),
+            KeyCode::Char('-') => self.resize(-1),
+            KeyCode::Char('x') | KeyCode::Delete | KeyCode::Backspace
+                if self.selected < self.blocks.len() =>
+            {
+                self.blocks.remove(self.selected);
+                self.selected = self.selected.min(self.blocks.len().saturating_sub(1));
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Move the selected block by `slots`, keeping it within the day
+    fn shift(&mut self, slots: i64) {
+        let (from, until) = (self.from, self.until);
+        if let Some(block) = self.blocks.get_mut(self.selected) {
+            let length = block.end - block.start;
+            let start = (block.start + Duration::minutes(slots * SLOT_MINUTES))
+                .max(from)
+                .min(until - length);
+            block.start = start;
+            block.end = start + length;
+        }
+    }
+
+    /// Lengthen or shorten the selected block by `slots`, to at least one
+    fn resize(&mut self, slots: i64) {
+        let until = self.until;
+        if let Some(block) = self.blocks.get_mut(self.selected) {
+            block.end = (block.end + Duration::minutes(slots * SLOT_MINUTES))
+                .min(until)
+                .max(block.start + Duration::minutes(SLOT_MINUTES));
+        }
+    }
+
+    /// Whether block `index` overlaps an event or another block
+    pub fn conflicts(&self, index: usize) -> bool {
+        let block = &self.blocks[index];
+        self.busy
+            .iter()
+            .any(|(_, start, end)| block.overlaps(*start, *end))
+            || self
+                .blocks
+                .iter()
+                .enumerate()
+                .any(|(i, other)| i != index && block.overlaps(other.start, other.end))
+    }
+}
+
+/// Edit `planner` full screen. Returns the blocks to save, or `None` if the
+/// plan was discarded.
+pub fn run(mut planner: Planner, theme: Theme) -> Result<Option<Vec<TimeBlock>>> {
+    let mut terminal = Tui::new()?;
+    let events = EventHandler::new(250);
+
+    loop {
+        terminal
+            .draw(|frame| TimeblockWidget::new(&planner, &theme).render(frame, frame.area()))?;
+        if let Event::Key(key) = events.next()?
+            && let Some(save) = planner.handle_key(key)
+        {
+            return Ok(save.then_some(planner.blocks));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_planner_keys() {
+        let at = |h: u32, m: u32| Utc.with_ymd_and_hms(2026, 10, 16, h, m, 0).unwrap();
+        let block = |title: &str, start, end| TimeBlock {
+            todo_id: Uuid::new_v4(),
+            title: title.to_string(),
+            start,
+            end,
+        };
+        let mut planner = Planner::new(
+            vec![
+                block("Report", at(9, 0), at(9, 30)),
+                block("Email", at(10, 0), at(10, 30)),
+            ],
+            vec![("Standup".to_string(), at(9, 30), at(10, 0))],
+            at(9, 0),
+            at(11, 0),
+        );
+        let press = |planner: &mut Planner, code| planner.handle_key(KeyEvent::from(code));
+
+        assert!(!planner.conflicts(0));
+        press(&mut planner, KeyCode::Char('J'));
+        assert_eq!(planner.blocks[0].start, at(9, 15));
+        assert!(planner.conflicts(0), "runs into the standup");
+        press(&mut planner, KeyCode::Char('K'));
+        press(&mut planner, KeyCode::Char('K'));
+        assert_eq!(planner.blocks[0].start, at(9, 0), "stays within the day");
+
+        press(&mut planner, KeyCode::Char('j'));
+        for _ in 0..4 {
+            press(&mut planner, KeyCode::Char('+'));
+        }
+        assert_eq!(planner.blocks[1].end, at(11, 0), "ends with the day");
+        for _ in 0..8 {
+            press(&mut planner, KeyCode::Char('-'));
+        }
+        assert_eq!(planner.blocks[1].end, at(10, 15), "one slot at least");
+
+        press(&mut planner, KeyCode::Char('x'));
+        assert_eq!(planner.blocks.len(), 1);
+        assert_eq!(planner.selected, 0);
+        assert_eq!(press(&mut planner, KeyCode::Enter), Some(true));
+        assert_eq!(press(&mut planner, KeyCode::Esc), Some(false));
+    }
+}
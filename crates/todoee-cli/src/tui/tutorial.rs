@@ -0,0 +1,275 @@
+//! Guided tutorial (`todoee tutorial`)
+//!
+//! Runs the TUI on an in-memory database holding a few sample todos, with a
+//! checklist beside the list. A step is ticked off once the app shows it was
+//! done, whichever keys got there, so the tutorial teaches the real
+//! bindings. Nothing is written to disk and sync is off.
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use todoee_core::{Category, Config, LocalDb, Priority, Todo};
+use uuid::Uuid;
+
+use super::app::{App, Mode};
+
+/// One thing to learn, in the order they are taught
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Move,
+    Add,
+    Complete,
+    Filter,
+    Stash,
+    Pop,
+    Focus,
+}
+
+impl Step {
+    pub const ALL: [Step; 7] = [
+        Step::Move,
+        Step::Add,
+        Step::Complete,
+        Step::Filter,
+        Step::Stash,
+        Step::Pop,
+        Step::Focus,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Step::Move => "Move around",
+            Step::Add => "Add a todo",
+            Step::Complete => "Complete a todo",
+            Step::Filter => "Filter the list",
+            Step::Stash => "Stash a todo",
+            Step::Pop => "Bring it back",
+            Step::Focus => "Focus on a todo",
+        }
+    }
+
+    /// What to press, shown under the current step
+    pub fn hint(self) -> &'static str {
+        match self {
+            Step::Move => "Press j and k (or the arrow keys) to move the selection.",
+            Step::Add => "Press a, type a title and press Enter to save it.",
+            Step::Complete => "Select a todo and press d to mark it done.",
+            Step::Filter => {
+                "Press p to show only high priority todos, then p until all show again. t (today) and o (overdue) work the same way."
+            }
+            Step::Stash => "Press z to set the selected todo aside for later.",
+            Step::Pop => "Press Z to take it back out of the stash.",
+            Step::Focus => "Press f to start a focus timer, then Enter to finish early.",
+        }
+    }
+}
+
+/// What the tutorial looks at after each key
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    pub selected: usize,
+    /// Todos that aren't deleted or stashed
+    pub todos: usize,
+    pub completed: usize,
+    pub stashed: usize,
+    /// Whether a today, overdue, priority, category or search filter is on
+    pub filtered: bool,
+    pub focusing: bool,
+}
+
+impl Snapshot {
+    pub async fn of(app: &App) -> Result<Self> {
+        let todos = app.db.list_todos(false).await?;
+        let filter = &app.filter;
+        Ok(Self {
+            selected: app.selected,
+            todos: todos.len(),
+            completed: todos.iter().filter(|t| t.is_completed).count(),
+            stashed: app.db.stash_list().await?.len(),
+            filtered: filter.today_only
+                || filter.overdue_only
+                || filter.priority.is_some()
+                || filter.category.is_some()
+                || !filter.search_query.is_empty(),
+            focusing: app.mode == Mode::Focus,
+        })
+    }
+}
+
+/// Progress through the steps
+#[derive(Debug, Clone)]
+pub struct TutorialState {
+    /// Index into [`Step::ALL`]; past the end once every step is done
+    pub step: usize,
+    last: Snapshot,
+    /// The filter or focus step is half done: it was turned on and has to
+    /// be turned off again
+    started: bool,
+}
+
+impl TutorialState {
+    pub fn new(snapshot: Snapshot) -> Self {
+        Self {
+            step: 0,
+            last: snapshot,
+            started: false,
+        }
+    }
+
+    pub fn current(&self) -> Option<Step> {
+        Step::ALL.get(self.step).copied()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current().is_none()
+    }
+
+    /// Move past the current step if `now` shows it was done. Returns
+    /// whether a step was completed.
+    pub fn observe(&mut self, now: Snapshot) -> bool {
+        let last = std::mem::replace(&mut self.last, now);
+        let now = &self.last;
+        let done = match self.current() {
+            None => false,
+            Some(Step::Move) => now.selected != last.selected,
+            Some(Step::Add) => now.todos > last.todos,
+            Some(Step::Complete) => now.completed > last.completed,
+            Some(Step::Stash) => now.stashed > last.stashed,
+            Some(Step::Pop) => now.stashed < last.stashed,
+            Some(Step::Filter) => self.on_then_off(now.filtered),
+            Some(Step::Focus) => self.on_then_off(now.focusing),
+        };
+        if done {
+            self.step += 1;
+            self.started = false;
+        }
+        done
+    }
+
+    fn on_then_off(&mut self, on: bool) -> bool {
+        if on {
+            self.started = true;
+            false
+        } else {
+            self.started
+        }
+    }
+}
+
+/// Check whether the last key finished the current step
+pub async fn update(app: &mut App) -> Result<()> {
+    let snapshot = Snapshot::of(app).await?;
+    let Some(state) = app.tutorial.as_mut() else {
+        return Ok(());
+    };
+    let step = state.current();
+    if state.observe(snapshot)
+        && let Some(step) = step
+    {
+        app.status_message = Some(if state.is_finished() {
+            "✓ Tutorial complete! Press q to leave.".to_string()
+        } else {
+            format!("✓ {}", step.title())
+        });
+        app.status_set_frame = Some(app.animation_frame);
+    }
+    Ok(())
+}
+
+/// An app on a fresh in-memory database with sample todos and the
+/// tutorial running
+pub async fn app() -> Result<App> {
+    // Display settings carry over; a broken config file shouldn't stop the tutorial
+    let config = Config::load().unwrap_or_default();
+    let db = sample_db().await?;
+
+    let mut app = App::with_db(config, db).await?;
+    app.tutorial = Some(TutorialState::new(Snapshot::of(&app).await?));
+    app.status_message =
+        Some("Welcome! Follow the steps on the right. Nothing here is saved.".to_string());
+    Ok(app)
+}
+
+async fn sample_db() -> Result<LocalDb> {
+    let db = LocalDb::new_in_memory().await?;
+    db.run_migrations().await?;
+
+    let work = Category::new(Uuid::nil(), "Work".to_string());
+    let home = Category::new(Uuid::nil(), "Home".to_string());
+    db.create_category(&work).await?;
+    db.create_category(&home).await?;
+
+    let now = Utc::now();
+    let samples = [
+        (
+            "Reply to the client's email",
+            Priority::High,
+            Some(0),
+            &work,
+        ),
+        ("Renew passport", Priority::High, Some(-2), &home),
+        (
+            "Book a dentist appointment",
+            Priority::Medium,
+            Some(1),
+            &home,
+        ),
+        (
+            "Prepare slides for Monday",
+            Priority::Medium,
+            Some(4),
+            &work,
+        ),
+        ("Water the plants", Priority::Low, None, &home),
+        ("Read the onboarding guide", Priority::Low, None, &work),
+    ];
+    for (title, priority, due_in_days, category) in samples {
+        let mut todo = Todo::new(title.to_string(), None);
+        todo.priority = priority;
+        todo.due_date = due_in_days.map(|days| now + Duration::days(days));
+        todo.category_id = Some(category.id);
+        db.create_todo(&todo).await?;
+    }
+    Ok(db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_complete_in_order() {
+        let mut state = TutorialState::new(Snapshot {
+            todos: 6,
+            ..Default::default()
+        });
+        let mut now = state.last.clone();
+
+        // Doing a later step first does not count
+        now.completed = 1;
+        assert!(!state.observe(now.clone()));
+        now.selected = 1;
+        assert!(state.observe(now.clone()));
+        assert_eq!(state.current(), Some(Step::Add));
+        now.todos = 7;
+        assert!(state.observe(now.clone()));
+        now.completed = 2;
+        assert!(state.observe(now.clone()));
+
+        // Filters and focus count once they are turned off again
+        now.filtered = true;
+        assert!(!state.observe(now.clone()));
+        now.filtered = false;
+        assert!(state.observe(now.clone()));
+        now.stashed = 1;
+        assert!(state.observe(now.clone()));
+        now.stashed = 0;
+        assert!(state.observe(now.clone()));
+        now.focusing = true;
+        assert!(!state.observe(now.clone()));
+        now.focusing = false;
+        assert!(state.observe(now.clone()));
+
+        assert!(state.is_finished());
+        assert!(!state.observe(now));
+    }
+}
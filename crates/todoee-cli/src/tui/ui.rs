@@ -1,21 +1,28 @@
-use chrono::Utc;
+use chrono::{Local, Utc};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style, Stylize},
+    style::{Modifier, Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
 };
-use todoee_core::Priority;
+use todoee_core::{DueUrgency, Priority, dates, short_id};
 
-use super::app::{App, Mode, View};
+use super::app::{App, Mode, SettingsSection, View, scroll_offset};
 use super::widgets::{
-    CategoryListWidget, FocusWidget, InsightsWidget, SettingsWidget, TodoAddWidget,
-    TodoDetailWidget, TodoEditorWidget,
+    CategoryListWidget, CelebrationWidget, ConflictWidget, FocusWidget, HelpWidget, InsightsWidget,
+    MatrixWidget, ProjectListWidget, SettingsCursor, SettingsWidget, SyncPanel, TodoAddWidget,
+    TodoDetailWidget, TodoEditorWidget, TutorialWidget, ZenWidget, celebration,
 };
 
 /// Main UI rendering function
 pub fn render(app: &App, frame: &mut Frame) {
+    if app.mode == Mode::Zen {
+        let todos = app.zen_todos();
+        ZenWidget::new(&todos, &app.theme).render(frame, frame.area());
+        return;
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -29,18 +36,35 @@ pub fn render(app: &App, frame: &mut Frame) {
 
     render_tabs(app, frame, chunks[0]);
 
+    // The tutorial checklist takes the right side of the content area
+    let content = match app.tutorial {
+        Some(ref state) => {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Min(30), Constraint::Length(42)])
+                .split(chunks[2]);
+            TutorialWidget::new(state, &app.theme).render(frame, columns[1]);
+            columns[0]
+        }
+        None => chunks[2],
+    };
+
     match app.current_view {
         View::Todos => {
             render_input(app, frame, chunks[1]);
-            render_tasks(app, frame, chunks[2]);
+            render_tasks(app, frame, content);
         }
         View::Categories => {
             render_category_header(app, frame, chunks[1]);
-            render_categories(app, frame, chunks[2]);
+            render_categories(app, frame, content);
         }
         View::Settings => {
             render_settings_header(app, frame, chunks[1]);
-            render_settings_content(app, frame, chunks[2]);
+            render_settings_content(app, frame, content);
+        }
+        View::Projects => {
+            render_project_header(app, frame, chunks[1]);
+            render_projects(app, frame, content);
         }
     }
 
@@ -49,38 +73,57 @@ pub fn render(app: &App, frame: &mut Frame) {
 
     // Modals
     if app.mode == Mode::Help {
-        render_help_modal(frame);
+        render_help_modal(app, frame);
     }
     if app.mode == Mode::ViewingDetail
         && let Some(todo) = app.selected_todo()
     {
         let area = centered_rect(70, 80, frame.area());
-        TodoDetailWidget::new(todo).render(frame, area);
+        TodoDetailWidget::new(todo, &app.detail_notes, &app.detail_attachments, &app.theme)
+            .render(frame, area);
     }
     if app.mode == Mode::EditingFull
         && let Some(ref state) = app.edit_state
     {
         let area = centered_rect(60, 50, frame.area());
-        TodoEditorWidget::new(state).render(frame, area);
+        TodoEditorWidget::new(state, &app.theme).render(frame, area);
     }
     if app.mode == Mode::AddingFull
         && let Some(ref state) = app.add_state
     {
         let area = centered_rect(65, 60, frame.area());
-        TodoAddWidget::new(state).render(frame, area);
+        TodoAddWidget::new(state, &app.theme).render(frame, area);
     }
     if app.mode == Mode::Insights
         && let Some(ref data) = app.insights_data
     {
-        let area = centered_rect(50, 55, frame.area());
-        let opened = app.insights_opened_frame.unwrap_or(0);
-        InsightsWidget::new(data, app.animation_frame, opened).render(frame, area);
+        let area = centered_rect(60, 85, frame.area());
+        let elapsed = app.frames_since(app.insights_opened_frame.unwrap_or(0));
+        InsightsWidget::new(data, elapsed, &app.theme).render(frame, area);
     }
     if app.mode == Mode::Focus
         && let Some(ref state) = app.focus_state
     {
         let area = centered_rect(50, 50, frame.area());
-        FocusWidget::new(state, app.animation_frame).render(frame, area);
+        FocusWidget::new(state, app.motion_frame(), &app.theme).render(frame, area);
+    }
+    if app.mode == Mode::Matrix
+        && let Some(ref state) = app.matrix_state
+    {
+        let area = centered_rect(85, 80, frame.area());
+        MatrixWidget::new(state, &app.theme).render(frame, area);
+    }
+    if app.mode == Mode::Conflicts
+        && let Some(ref state) = app.conflict_state
+    {
+        let area = centered_rect(80, 60, frame.area());
+        ConflictWidget::new(state, &app.theme).render(frame, area);
+    }
+
+    if let Some((started, due)) = app.celebration
+        && app.animation_frame.wrapping_sub(started) < celebration::TOAST_FRAMES
+    {
+        CelebrationWidget::new(due, app.motion_frame(), &app.theme).render(frame, frame.area());
     }
 
     // Loading overlay (always on top)
@@ -90,10 +133,11 @@ pub fn render(app: &App, frame: &mut Frame) {
 }
 
 fn render_tabs(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = &app.theme;
     // Calculate transition animation
     let transition_frame = app
         .view_changed_frame
-        .map(|f| app.animation_frame.wrapping_sub(f))
+        .map(|f| app.frames_since(f))
         .unwrap_or(10);
     let is_transitioning = transition_frame < 4;
 
@@ -101,6 +145,7 @@ fn render_tabs(app: &App, frame: &mut Frame, area: Rect) {
         ("1: Todos", View::Todos),
         ("2: Categories", View::Categories),
         ("3: Settings", View::Settings),
+        ("4: Projects", View::Projects),
     ];
 
     let mut spans: Vec<Span> = tabs
@@ -111,25 +156,35 @@ fn render_tabs(app: &App, frame: &mut Frame, area: Rect) {
 
             let style = if is_active {
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.primary)
                     .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
             } else if was_active && is_transitioning {
                 // Fading out previous tab
-                Style::default().fg(Color::Gray)
+                Style::default().fg(theme.text_muted)
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.muted)
             };
             vec![Span::styled(format!(" {} ", label), style), Span::raw("  ")]
         })
         .collect();
 
+    if let Some(profile) = &app.profile {
+        spans.push(Span::styled(
+            format!(" [{}] ", profile),
+            Style::default()
+                .fg(theme.secondary)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
     // Add filter indicators for Todos view
     if app.current_view == View::Todos {
         if let Some(priority) = app.filter.priority {
             let (text, color) = match priority {
-                Priority::High => ("HIGH", Color::Red),
-                Priority::Medium => ("MEDIUM", Color::Yellow),
-                Priority::Low => ("LOW", Color::Green),
+                Priority::Urgent => ("URGENT", theme.error),
+                Priority::High => ("HIGH", theme.error),
+                Priority::Medium => ("MEDIUM", theme.warning),
+                Priority::Low => ("LOW", theme.success),
             };
             spans.push(Span::styled(
                 format!(" [{}] ", text),
@@ -140,7 +195,23 @@ fn render_tabs(app: &App, frame: &mut Frame, area: Rect) {
         if app.filter.overdue_only {
             spans.push(Span::styled(
                 " [OVERDUE] ",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        if let Some(project) = &app.filter.project {
+            spans.push(Span::styled(
+                format!(" [PROJECT: {}] ", project),
+                Style::default().fg(theme.accent),
+            ));
+        }
+
+        if let Some(context) = &app.config.contexts.active {
+            spans.push(Span::styled(
+                format!(" [CONTEXT: {}] ", context),
+                Style::default().fg(theme.accent),
             ));
         }
     }
@@ -148,23 +219,24 @@ fn render_tabs(app: &App, frame: &mut Frame, area: Rect) {
     let tabs_line = Paragraph::new(Line::from(spans)).block(
         Block::default()
             .borders(Borders::BOTTOM)
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(Style::default().fg(theme.border)),
     );
 
     frame.render_widget(tabs_line, area);
 }
 
 fn render_category_header(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = &app.theme;
     if app.mode == Mode::AddingCategory {
         let input = Paragraph::new(Line::from(vec![
-            Span::styled("> New category: ", Style::default().fg(Color::Green)),
+            Span::styled("> New category: ", Style::default().fg(theme.success)),
             Span::raw(app.input.value()),
-            Span::styled("|", Style::default().fg(Color::White)),
+            Span::styled("|", Style::default().fg(theme.text)),
         ]))
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(Style::default().fg(theme.border_focused)),
         );
         frame.render_widget(input, area);
     } else {
@@ -172,53 +244,132 @@ fn render_category_header(app: &App, frame: &mut Frame, area: Rect) {
             Span::styled(
                 " Categories ",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.primary)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("  a", Style::default().fg(Color::Yellow)),
+            Span::styled("  a", Style::default().fg(theme.warning)),
             Span::raw(":add  "),
-            Span::styled("x", Style::default().fg(Color::Yellow)),
+            Span::styled("x", Style::default().fg(theme.warning)),
             Span::raw(":delete"),
         ]))
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(Style::default().fg(theme.border)),
         );
         frame.render_widget(header, area);
     }
 }
 
 fn render_categories(app: &App, frame: &mut Frame, area: Rect) {
-    CategoryListWidget::new(&app.categories, app.category_selected, app.animation_frame)
-        .render(frame, area);
+    CategoryListWidget::new(
+        &app.categories,
+        app.category_selected,
+        app.motion_frame(),
+        &app.theme,
+    )
+    .render(frame, area);
 }
 
-fn render_settings_header(_app: &App, frame: &mut Frame, area: Rect) {
+fn render_project_header(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = &app.theme;
+    if app.mode == Mode::AddingProject {
+        let input = Paragraph::new(Line::from(vec![
+            Span::styled("> New project: ", Style::default().fg(theme.success)),
+            Span::raw(app.input.value()),
+            Span::styled("|", Style::default().fg(theme.text)),
+        ]))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border_focused)),
+        );
+        frame.render_widget(input, area);
+    } else {
+        let header = Paragraph::new(Line::from(vec![
+            Span::styled(
+                " Projects ",
+                Style::default()
+                    .fg(theme.primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  a", Style::default().fg(theme.warning)),
+            Span::raw(":add  "),
+            Span::styled("x", Style::default().fg(theme.warning)),
+            Span::raw(":archive  "),
+            Span::styled("Enter", Style::default().fg(theme.warning)),
+            Span::raw(":show todos"),
+        ]))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+        );
+        frame.render_widget(header, area);
+    }
+}
+
+fn render_projects(app: &App, frame: &mut Frame, area: Rect) {
+    ProjectListWidget::new(
+        &app.projects,
+        app.project_selected,
+        app.filter.project.as_deref(),
+        app.motion_frame(),
+        &app.theme,
+    )
+    .render(frame, area);
+}
+
+fn render_settings_header(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = &app.theme;
     let header = Paragraph::new(" Settings ")
         .style(
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.primary)
                 .add_modifier(Modifier::BOLD),
         )
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(Style::default().fg(theme.border)),
         );
     frame.render_widget(header, area);
 }
 
 fn render_settings_content(app: &App, frame: &mut Frame, area: Rect) {
-    SettingsWidget::new(&app.config, app.settings_section).render(frame, area);
+    let sync = SyncPanel {
+        health: &app.sync_health,
+        conflicts: &app.sync_conflicts,
+        show_conflicts: app.show_sync_conflicts,
+        id_length: app.id_length,
+        latency: app.sync_latency.as_ref(),
+    };
+    let editing = (app.mode == Mode::EditingSetting).then(|| app.input.value());
+    let cursor = SettingsCursor {
+        field: app.selected_setting(),
+        editing,
+    };
+    SettingsWidget::new(
+        &app.config,
+        app.settings_section,
+        cursor,
+        sync,
+        app.daemon_status.as_ref(),
+        &app.theme,
+    )
+    .render(frame, area);
 }
 
 fn render_input(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = &app.theme;
     let (prompt, style) = match app.mode {
-        Mode::Adding => ("> Add task: ", Style::default().fg(Color::Green)),
-        Mode::Searching => ("> Search: ", Style::default().fg(Color::Yellow)),
-        Mode::Editing => ("> Edit: ", Style::default().fg(Color::Blue)),
-        _ => ("> ", Style::default().fg(Color::DarkGray)),
+        Mode::Adding => ("> Add task: ", Style::default().fg(theme.success)),
+        Mode::Searching if app.input.value().starts_with(':') => {
+            ("> Query: ", Style::default().fg(theme.warning))
+        }
+        Mode::Searching => ("> Search: ", Style::default().fg(theme.warning)),
+        Mode::Editing => ("> Edit: ", Style::default().fg(theme.secondary)),
+        _ => ("> ", Style::default().fg(theme.muted)),
     };
 
     let input_text = if matches!(app.mode, Mode::Adding | Mode::Searching | Mode::Editing) {
@@ -230,10 +381,11 @@ fn render_input(app: &App, frame: &mut Frame, area: Rect) {
     // Priority indicator for Adding mode
     let priority_indicator = if app.mode == Mode::Adding {
         let (text, color) = match app.pending_priority {
-            Some(Priority::High) => (" [!!!]", Color::Red),
-            Some(Priority::Medium) => (" [!!]", Color::Yellow),
-            Some(Priority::Low) => (" [!]", Color::Green),
-            None => (" [--]", Color::DarkGray),
+            Some(Priority::Urgent) => (" [!!!!]", theme.error),
+            Some(Priority::High) => (" [!!!]", theme.error),
+            Some(Priority::Medium) => (" [!!]", theme.warning),
+            Some(Priority::Low) => (" [!]", theme.success),
+            None => (" [--]", theme.muted),
         };
         Span::styled(text, Style::default().fg(color))
     } else {
@@ -243,22 +395,39 @@ fn render_input(app: &App, frame: &mut Frame, area: Rect) {
     let mut spans = vec![Span::styled(prompt, style), Span::raw(input_text)];
 
     if matches!(app.mode, Mode::Adding | Mode::Searching | Mode::Editing) {
+        let cursor = Style::default().fg(theme.text);
         spans.push(Span::styled(
             "│",
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::SLOW_BLINK),
+            if app.config.display.animate() {
+                cursor.add_modifier(Modifier::SLOW_BLINK)
+            } else {
+                cursor
+            },
         ));
     }
 
     spans.push(priority_indicator);
 
+    if app.mode == Mode::Adding
+        && let Some(description) = &app.pending_description
+    {
+        let lines = description.lines().count();
+        spans.push(Span::styled(
+            format!(
+                " [+{} line{} of description]",
+                lines,
+                if lines == 1 { "" } else { "s" }
+            ),
+            Style::default().fg(theme.muted),
+        ));
+    }
+
     let input = Paragraph::new(Line::from(spans)).block(
         Block::default().borders(Borders::ALL).border_style(
             if matches!(app.mode, Mode::Adding | Mode::Searching | Mode::Editing) {
-                Style::default().fg(Color::Cyan)
+                Style::default().fg(theme.primary)
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.muted)
             },
         ),
     );
@@ -267,7 +436,10 @@ fn render_input(app: &App, frame: &mut Frame, area: Rect) {
 }
 
 fn render_tasks(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = &app.theme;
     let now = Utc::now();
+    let compact = app.config.display.is_compact();
+    let icon_column = app.todos.iter().any(|t| t.icon.is_some());
 
     if app.todos.is_empty() {
         // Animated empty state
@@ -277,17 +449,17 @@ fn render_tasks(app: &App, frame: &mut Frame, area: Rect) {
             "Empty list. Time to plan ahead!",
             "No todos here. Press 'a' to get started.",
         ];
-        let message = messages[(app.animation_frame / 8) % messages.len()];
+        let message = messages[(app.motion_frame() / 8) % messages.len()];
 
         let icons = ['*', '+', '*', '+'];
-        let icon = icons[(app.animation_frame / 4) % icons.len()];
+        let icon = icons[(app.motion_frame() / 4) % icons.len()];
 
         let content = vec![
             Line::from(""),
             Line::from(""),
             Line::from(Span::styled(
                 format!("  {}  {}", icon, message),
-                Style::default().fg(Color::DarkGray).italic(),
+                Style::default().fg(theme.muted).italic(),
             )),
             Line::from(""),
         ];
@@ -297,7 +469,7 @@ fn render_tasks(app: &App, frame: &mut Frame, area: Rect) {
                 Block::default()
                     .title(" Tasks (0) ")
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::DarkGray)),
+                    .border_style(Style::default().fg(theme.border)),
             )
             .alignment(Alignment::Center);
 
@@ -305,10 +477,19 @@ fn render_tasks(app: &App, frame: &mut Frame, area: Rect) {
         return;
     }
 
+    // Only the rows on screen are built, so long lists stay fast
+    let row_height = if compact { 1 } else { 2 };
+    let page = (area.height.saturating_sub(2) as usize / row_height).max(1);
+    let offset = scroll_offset(app.task_offset.get(), app.selected, page, app.todos.len());
+    app.task_page.set(page);
+    app.task_offset.set(offset);
+
     let items: Vec<ListItem> = app
         .todos
         .iter()
         .enumerate()
+        .skip(offset)
+        .take(page)
         .map(|(i, todo)| {
             let is_selected = i == app.selected;
 
@@ -317,13 +498,16 @@ fn render_tasks(app: &App, frame: &mut Frame, area: Rect) {
 
             // Priority indicator
             let priority = match todo.priority {
-                Priority::High => Span::styled("!!!", Style::default().fg(Color::Red).bold()),
-                Priority::Medium => Span::styled("!! ", Style::default().fg(Color::Yellow)),
-                Priority::Low => Span::styled("!  ", Style::default().fg(Color::Green)),
+                // Urgent keeps the column width, standing out in reverse
+                Priority::Urgent => {
+                    Span::styled("!!!", Style::default().fg(theme.error).bold().reversed())
+                }
+                Priority::High => Span::styled("!!!", Style::default().fg(theme.error).bold()),
+                Priority::Medium => Span::styled("!! ", Style::default().fg(theme.warning)),
+                Priority::Low => Span::styled("!  ", Style::default().fg(theme.success)),
             };
 
-            // Short ID
-            let short_id = &todo.id.to_string()[..8];
+            let short_id = short_id(&todo.id, app.id_length);
 
             // Due date
             let due_str = if let Some(due) = todo.due_date {
@@ -331,17 +515,36 @@ fn render_tasks(app: &App, frame: &mut Frame, area: Rect) {
                 match days_until {
                     d if d < 0 => Span::styled(
                         format!(" [OVERDUE {}d]", -d),
-                        Style::default().fg(Color::Red).bold(),
+                        Style::default().fg(theme.error).bold(),
                     ),
-                    0 => Span::styled(" [TODAY]", Style::default().fg(Color::Yellow).bold()),
-                    1 => Span::styled(" [Tomorrow]", Style::default().fg(Color::Cyan)),
-                    d if d <= 7 => {
-                        Span::styled(format!(" [{}d]", d), Style::default().fg(Color::Blue))
+                    0 => match dates::due_time(&due, &Local) {
+                        // Past its time today: shown in red like overdue
+                        Some(time) => Span::styled(
+                            format!(" [TODAY {}]", time.format("%H:%M")),
+                            Style::default()
+                                .fg(if due < now {
+                                    theme.error
+                                } else {
+                                    theme.warning
+                                })
+                                .bold(),
+                        ),
+                        None => Span::styled(" [TODAY]", Style::default().fg(theme.warning).bold()),
+                    },
+                    d => {
+                        let urgency = app.config.display.due_urgency(&due, now);
+                        let color = match urgency {
+                            DueUrgency::Overdue | DueUrgency::Urgent => theme.primary,
+                            DueUrgency::Soon => theme.secondary,
+                            DueUrgency::Later => theme.muted,
+                        };
+                        let text = match (d, urgency) {
+                            (1, _) => " [Tomorrow]".to_string(),
+                            (_, DueUrgency::Later) => format!(" [{}]", due.format("%m/%d")),
+                            (d, _) => format!(" [{}d]", d),
+                        };
+                        Span::styled(text, Style::default().fg(color))
                     }
-                    _ => Span::styled(
-                        format!(" [{}]", due.format("%m/%d")),
-                        Style::default().fg(Color::DarkGray),
-                    ),
                 }
             } else {
                 Span::raw("")
@@ -349,9 +552,9 @@ fn render_tasks(app: &App, frame: &mut Frame, area: Rect) {
 
             // Build the line
             let line_style = if is_selected {
-                Style::default().bg(Color::DarkGray)
+                Style::default().bg(theme.selection_bg)
             } else if todo.is_completed {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.muted)
             } else {
                 Style::default()
             };
@@ -359,17 +562,17 @@ fn render_tasks(app: &App, frame: &mut Frame, area: Rect) {
             // Animated cursor: alternates between filled and outline arrow
             let selector = if is_selected {
                 let cursors = ['▸', '▹', '▸', '▹'];
-                format!("{} ", cursors[app.animation_frame % cursors.len()])
+                format!("{} ", cursors[app.motion_frame() % cursors.len()])
             } else {
                 "  ".to_string()
             };
 
-            let content = Line::from(vec![
+            let mut spans = vec![
                 Span::styled(
                     selector,
                     if is_selected {
                         Style::default()
-                            .fg(Color::Cyan)
+                            .fg(theme.primary)
                             .add_modifier(Modifier::BOLD)
                     } else {
                         Style::default()
@@ -378,7 +581,7 @@ fn render_tasks(app: &App, frame: &mut Frame, area: Rect) {
                 Span::styled(
                     status,
                     if todo.is_completed {
-                        Style::default().fg(Color::Green)
+                        Style::default().fg(theme.success)
                     } else {
                         Style::default()
                     },
@@ -386,6 +589,7 @@ fn render_tasks(app: &App, frame: &mut Frame, area: Rect) {
                 Span::raw(" "),
                 priority,
                 Span::raw(" "),
+                Span::raw(todo.icon_prefix(icon_column)),
                 Span::styled(
                     &todo.title,
                     if todo.is_completed {
@@ -394,34 +598,54 @@ fn render_tasks(app: &App, frame: &mut Frame, area: Rect) {
                         Style::default()
                     },
                 ),
-                Span::styled(
+            ];
+            // Compact rows drop the ID column and the spacer line
+            if !compact {
+                spans.push(Span::styled(
                     format!("  {}", short_id),
-                    Style::default().fg(Color::DarkGray),
-                ),
-                due_str,
-            ]);
+                    Style::default().fg(theme.muted),
+                ));
+            }
+            spans.push(due_str);
 
-            ListItem::new(content).style(line_style)
+            let content = Line::from(spans);
+            if compact {
+                ListItem::new(content).style(line_style)
+            } else {
+                ListItem::new(vec![content, Line::from("")]).style(line_style)
+            }
         })
         .collect();
 
+    // Position in the list once it no longer fits
+    let title = if app.todos.len() > page {
+        format!(
+            " Tasks ({}) {}/{} ",
+            app.todos.len(),
+            app.selected + 1,
+            app.todos.len()
+        )
+    } else {
+        format!(" Tasks ({}) ", app.todos.len())
+    };
     let tasks = List::new(items).block(
         Block::default()
-            .title(format!(" Tasks ({}) ", app.todos.len()))
+            .title(title)
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(Style::default().fg(theme.border)),
     );
 
     frame.render_widget(tasks, area);
 }
 
 fn render_status(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = &app.theme;
     let status_text = app.status_message.as_deref().unwrap_or("");
 
     // Calculate age of status message in frames
     let age = app
         .status_set_frame
-        .map(|set_frame| app.animation_frame.wrapping_sub(set_frame))
+        .map(|set_frame| app.frames_since(set_frame))
         .unwrap_or(0);
 
     // Icon animation: pulse for first few frames
@@ -455,196 +679,100 @@ fn render_status(app: &App, frame: &mut Frame, area: Rect) {
     };
 
     let status_style = if status_text.starts_with('✓') {
-        Style::default().fg(Color::Green)
+        Style::default().fg(theme.success)
     } else if status_text.starts_with('✗') {
-        Style::default().fg(Color::Red)
+        Style::default().fg(theme.error)
     } else {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(theme.warning)
     };
 
     let status = Paragraph::new(Span::styled(&display_text, status_style)).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(Style::default().fg(theme.border)),
     );
 
     frame.render_widget(status, area);
 }
 
 fn render_help(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = &app.theme;
     let help_text = match app.mode {
         Mode::Adding => "Enter:submit  Shift+Enter:with-AI  Tab:priority  Esc:cancel",
         Mode::Editing => "Enter:submit  Esc:cancel",
         Mode::EditingFull => "Tab:next  Shift+Tab:prev  Enter:save  Esc:cancel",
         Mode::AddingFull => "Tab:next  Shift+Tab:prev  Enter:save  Esc:cancel",
-        Mode::Searching => "Enter:apply  Esc:cancel  Ctrl+U:clear",
-        Mode::Help => "Press any key to close",
+        Mode::Searching => "Enter:apply  Esc:cancel  Ctrl+U:clear  :query (pri:high due<=+3d)",
+        Mode::Help => "/:search  Tab:all sections  j/k:scroll  PgDn/PgUp:page  q/Esc:close",
+        Mode::ViewingDetail if !app.detail_attachments.is_empty() => {
+            "o/1-9: open attachment  Esc/q/v/Enter: close detail view"
+        }
         Mode::ViewingDetail => "Esc/q/v/Enter: close detail view",
         Mode::AddingCategory => "Enter:create  Esc:cancel",
+        Mode::AddingProject => "Enter:create  Esc:cancel",
+        Mode::EditingSetting => "Enter:save  Esc:cancel",
         Mode::Insights => "Press any key to close",
-        Mode::Focus => "Space:pause  q/Esc:cancel  Enter:complete early",
+        Mode::Focus
+            if app
+                .focus_state
+                .as_ref()
+                .is_some_and(|s| s.reason_input.is_some()) =>
+        {
+            "Enter:log interruption  Esc:cancel"
+        }
+        Mode::Focus => "Space:pause  i:interrupted  q/Esc:cancel  Enter:complete early  ?:help",
+        Mode::Zen => "N/Esc: back to the list",
+        Mode::Matrix => "j/k:nav  h/l:column  1-4:move to quadrant  u:undo  m/q/Esc:close  ?:help",
+        Mode::Conflicts => {
+            "j/k:field  h/l:local/remote  Space:switch  L/R:all  Enter:resolve  q/Esc:close  ?:help"
+        }
         Mode::Normal => match app.current_view {
             View::Todos => {
-                "j/k:nav a:add d:done x:del u:undo z:stash o:overdue i:insights f:focus n:now ?:help q:quit"
+                "j/k:nav a:add d:done x:del u:undo z:stash o:overdue i:insights m:matrix f:focus n:now N:zen D:density ?:help q:quit"
+            }
+            View::Categories => "j/k:nav  a:add  x:delete  1-4:tabs  q:quit",
+            View::Projects => "j/k:nav  a:add  x:archive  Enter:show todos  1-4:tabs  q:quit",
+            View::Settings if app.settings_section == SettingsSection::Database => {
+                "j/k:nav sections  s:sync  p:ping  c:conflicts  v:review  r:reload config  1-4:tabs  q:quit"
             }
-            View::Categories => "j/k:nav  a:add  x:delete  1/2/3:tabs  q:quit",
-            View::Settings => "j/k:nav sections  r:reload config  1/2/3:tabs  q:quit",
+            View::Settings if app.settings_section == SettingsSection::Display => {
+                "j/k:sections  Tab:setting  Enter:edit  t:theme  r:reload  1-4:tabs  q:quit"
+            }
+            View::Settings => "j/k:sections  Tab:setting  Enter:edit  r:reload  1-4:tabs  q:quit",
         },
     };
 
-    let help = Paragraph::new(Span::styled(
-        help_text,
-        Style::default().fg(Color::DarkGray),
-    ));
+    let help = Paragraph::new(Span::styled(help_text, Style::default().fg(theme.muted)));
     frame.render_widget(help, area);
 }
 
-fn render_help_modal(frame: &mut Frame) {
+fn render_help_modal(app: &App, frame: &mut Frame) {
+    let Some(ref state) = app.help_state else {
+        return;
+    };
     let area = centered_rect(75, 90, frame.area());
-
-    let help_text = vec![
-        Line::from(Span::styled(
-            "═══ TODOEE KEYBOARD SHORTCUTS ═══",
-            Style::default().bold().fg(Color::Cyan),
-        )),
-        Line::from(""),
-        // ─────────────────────────────────────────────────────────────────
-        Line::from(Span::styled(
-            "─── NAVIGATION ───",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from("  j / ↓       Move down            g           Jump to top"),
-        Line::from("  k / ↑       Move up              G           Jump to bottom"),
-        Line::from("  1 / 2 / 3   Switch views (Todos/Categories/Settings)"),
-        Line::from(""),
-        // ─────────────────────────────────────────────────────────────────
-        Line::from(Span::styled(
-            "─── CORE ACTIONS ───",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from("  a           Add task (full editor with all fields)"),
-        Line::from("  A           Quick add (offline, Shift+Enter for AI)"),
-        Line::from("  e           Edit selected task"),
-        Line::from("  d / Enter   Mark as done"),
-        Line::from("  x           Delete task"),
-        Line::from("  v / Space   View task details"),
-        Line::from(""),
-        // ─────────────────────────────────────────────────────────────────
-        Line::from(Span::styled(
-            "─── GIT-LIKE COMMANDS ───",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from("  u           Undo last action"),
-        Line::from("  Ctrl+r      Redo last undone action"),
-        Line::from("  z           Stash selected task (hide temporarily)"),
-        Line::from("  Z           Pop from stash (restore last stashed)"),
-        Line::from(""),
-        // ─────────────────────────────────────────────────────────────────
-        Line::from(Span::styled(
-            "─── FILTERS & SORTING ───",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from("  /           Search (fuzzy matching)"),
-        Line::from("  t           Toggle today filter"),
-        Line::from("  o           Toggle overdue filter"),
-        Line::from("  p           Cycle priority filter (All→High→Med→Low)"),
-        Line::from("  c           Cycle category filter"),
-        Line::from("  s           Cycle sort (Created→Due→Priority→Title)"),
-        Line::from("  S           Toggle sort order (Asc/Desc)"),
-        Line::from("  Tab         Toggle show/hide completed"),
-        Line::from(""),
-        // ─────────────────────────────────────────────────────────────────
-        Line::from(Span::styled(
-            "─── PRODUCTIVITY ───",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from("  n           Jump to recommended task (smart pick)"),
-        Line::from("  f           Start focus session (25 min pomodoro)"),
-        Line::from("  F           Quick focus (5 min)"),
-        Line::from("  i           View productivity insights"),
-        Line::from(""),
-        // ─────────────────────────────────────────────────────────────────
-        Line::from(Span::styled(
-            "─── FOCUS MODE ───",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from("  Space       Pause / Resume timer"),
-        Line::from("  Enter       Complete early"),
-        Line::from("  q / Esc     Cancel focus session"),
-        Line::from(""),
-        // ─────────────────────────────────────────────────────────────────
-        Line::from(Span::styled(
-            "─── EDITOR MODE ───",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from("  Tab         Next field           Shift+Tab   Previous field"),
-        Line::from("  1 / 2 / 3   Set priority (on priority field)"),
-        Line::from("  Enter       Save changes         Esc         Cancel"),
-        Line::from(""),
-        // ─────────────────────────────────────────────────────────────────
-        Line::from(Span::styled(
-            "─── QUICK ADD (A) ───",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from("  Enter       Submit (offline)"),
-        Line::from("  Shift+Enter Submit with AI parsing"),
-        Line::from("  Tab         Cycle priority       Ctrl+1/2/3  Set priority"),
-        Line::from(""),
-        // ─────────────────────────────────────────────────────────────────
-        Line::from(Span::styled(
-            "─── GENERAL ───",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from("  ?           Toggle this help     q           Quit"),
-        Line::from("  Esc         Close modal / Cancel"),
-        Line::from(""),
-        Line::from(Span::styled(
-            "Tip: Use CLI for batch ops: todoee batch done id1 id2 id3",
-            Style::default().fg(Color::DarkGray).italic(),
-        )),
-    ];
-
-    let help = Paragraph::new(help_text)
-        .block(
-            Block::default()
-                .title(" Help (press any key to close) ")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
-        )
-        .wrap(Wrap { trim: false });
-
-    frame.render_widget(Clear, area);
-    frame.render_widget(help, area);
+    let lines = state.lines(&app.cli_commands);
+    HelpWidget::new(state, &lines, &app.theme).render(frame, area);
 }
 
 fn render_loading_overlay(app: &App, frame: &mut Frame) {
+    let theme = &app.theme;
     use super::spinner::bracketed_progress;
 
     let area = centered_rect(50, 25, frame.area());
 
-    let spinner_char = app.spinner_style.frame(app.animation_frame);
+    // The spinner keeps turning without animations, as it shows work is
+    // under way; with reduced motion it rests with all three dots shown
+    let spin_frame = if app.config.display.reduced_motion {
+        3
+    } else {
+        app.animation_frame
+    };
+    let spinner_char = app.spinner_style.frame(spin_frame);
     let message = app.loading_message.as_deref().unwrap_or("Loading...");
 
     // Animated dots
-    let dots_count = app.animation_frame % 4;
+    let dots_count = spin_frame % 4;
     let dots = ".".repeat(dots_count);
     let dots_padding = " ".repeat(3 - dots_count);
 
@@ -653,7 +781,7 @@ fn render_loading_overlay(app: &App, frame: &mut Frame) {
         Line::from(Span::styled(
             format!("  {}  {}{}{}", spinner_char, message, dots, dots_padding),
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.primary)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
@@ -666,7 +794,7 @@ fn render_loading_overlay(app: &App, frame: &mut Frame) {
 
         content.push(Line::from(Span::styled(
             format!("  {} {}%", bar, percentage),
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme.success),
         )));
 
         // Show step name if available
@@ -674,14 +802,14 @@ fn render_loading_overlay(app: &App, frame: &mut Frame) {
             content.push(Line::from(""));
             content.push(Line::from(Span::styled(
                 format!("  {}", step),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             )));
         }
 
         // Show progress count
         content.push(Line::from(Span::styled(
             format!("  ({}/{})", progress.current, progress.total),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )));
     }
 
@@ -691,7 +819,7 @@ fn render_loading_overlay(app: &App, frame: &mut Frame) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
+                .border_style(Style::default().fg(theme.border_focused))
                 .title(" Processing "),
         )
         .alignment(Alignment::Center);
@@ -1,24 +1,33 @@
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem},
 };
 use todoee_core::Category;
 
+use crate::tui::theme::{Theme, parse_hex_color};
+
 pub struct CategoryListWidget<'a> {
     categories: &'a [Category],
     selected: usize,
     animation_frame: usize,
+    theme: &'a Theme,
 }
 
 impl<'a> CategoryListWidget<'a> {
-    pub fn new(categories: &'a [Category], selected: usize, animation_frame: usize) -> Self {
+    pub fn new(
+        categories: &'a [Category],
+        selected: usize,
+        animation_frame: usize,
+        theme: &'a Theme,
+    ) -> Self {
         Self {
             categories,
             selected,
             animation_frame,
+            theme,
         }
     }
 
@@ -42,10 +51,10 @@ impl<'a> CategoryListWidget<'a> {
                     .color
                     .as_ref()
                     .and_then(|c| parse_hex_color(c))
-                    .unwrap_or(Color::White);
+                    .unwrap_or(self.theme.text);
 
                 let ai_badge = if cat.is_ai_generated {
-                    Span::styled(" [AI]", Style::default().fg(Color::Magenta))
+                    Span::styled(" [AI]", Style::default().fg(self.theme.accent))
                 } else {
                     Span::raw("")
                 };
@@ -55,7 +64,7 @@ impl<'a> CategoryListWidget<'a> {
                         selector,
                         if is_selected {
                             Style::default()
-                                .fg(Color::Cyan)
+                                .fg(self.theme.primary)
                                 .add_modifier(Modifier::BOLD)
                         } else {
                             Style::default()
@@ -67,7 +76,7 @@ impl<'a> CategoryListWidget<'a> {
                 ]);
 
                 let style = if is_selected {
-                    Style::default().bg(Color::DarkGray)
+                    Style::default().bg(self.theme.selection_bg)
                 } else {
                     Style::default()
                 };
@@ -80,20 +89,9 @@ impl<'a> CategoryListWidget<'a> {
             Block::default()
                 .title(format!(" Categories ({}) ", self.categories.len()))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(Style::default().fg(self.theme.border_focused)),
         );
 
         frame.render_widget(list, area);
     }
 }
-
-fn parse_hex_color(hex: &str) -> Option<Color> {
-    let hex = hex.trim_start_matches('#');
-    if hex.len() != 6 {
-        return None;
-    }
-    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
-    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
-    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
-    Some(Color::Rgb(r, g, b))
-}
@@ -0,0 +1,92 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::tui::theme::Theme;
+
+/// Frames the toast stays up, four seconds at the 250ms tick
+pub const TOAST_FRAMES: usize = 16;
+
+pub const MESSAGE: &str = "Inbox zero for today \u{1f389}";
+
+const CONFETTI: [char; 6] = [
+    '\u{2726}', '\u{00b7}', '*', '\u{2727}', '\u{00b7}', '\u{2736}',
+];
+
+/// Toast shown when the last task due today is completed
+pub struct CelebrationWidget<'a> {
+    /// Tasks that were due today
+    due: u64,
+    /// Frame of the confetti, 0 when animations are off
+    animation_frame: usize,
+    theme: &'a Theme,
+}
+
+impl<'a> CelebrationWidget<'a> {
+    pub fn new(due: u64, animation_frame: usize, theme: &'a Theme) -> Self {
+        Self {
+            due,
+            animation_frame,
+            theme,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let width = 38.min(area.width);
+        let height = 6.min(area.height);
+        // Top of the task list, below the tab bar and input line
+        let toast = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + 6.min(area.height - height),
+            width,
+            height,
+        };
+
+        let colors = [self.theme.primary, self.theme.accent, self.theme.success];
+        let confetti = |offset: usize| {
+            let spans: Vec<Span> = (0..(width as usize).saturating_sub(4) / 2)
+                .map(|i| {
+                    let n = i + offset + self.animation_frame;
+                    Span::styled(
+                        format!("{} ", CONFETTI[n % CONFETTI.len()]),
+                        Style::default().fg(colors[n % colors.len()]),
+                    )
+                })
+                .collect();
+            Line::from(spans)
+        };
+        let detail = if self.due == 1 {
+            "The task due today is done".to_string()
+        } else {
+            format!("All {} tasks due today are done", self.due)
+        };
+
+        let lines = vec![
+            confetti(0),
+            Line::from(Span::styled(
+                MESSAGE,
+                Style::default()
+                    .fg(self.theme.success)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(
+                detail,
+                Style::default().fg(self.theme.text_muted),
+            )),
+            confetti(3),
+        ];
+
+        let paragraph = Paragraph::new(lines).alignment(Alignment::Center).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.success)),
+        );
+
+        frame.render_widget(Clear, toast);
+        frame.render_widget(paragraph, toast);
+    }
+}
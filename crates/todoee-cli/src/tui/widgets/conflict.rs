@@ -0,0 +1,128 @@
+use chrono::Local;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+use todoee_core::conflict::Side;
+
+use crate::tui::app::ConflictState;
+use crate::tui::theme::Theme;
+
+/// Width of the field name column
+const NAME_WIDTH: usize = 14;
+
+pub struct ConflictWidget<'a> {
+    state: &'a ConflictState,
+    theme: &'a Theme,
+}
+
+impl<'a> ConflictWidget<'a> {
+    pub fn new(state: &'a ConflictState, theme: &'a Theme) -> Self {
+        Self { state, theme }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let outer = Block::default()
+            .title(format!(" Sync Conflict ({} left) ", self.state.remaining))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.warning));
+        let inner = outer.inner(area);
+        frame.render_widget(outer, area);
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(1)])
+            .split(inner);
+
+        let conflict = &self.state.conflict;
+        let header = vec![
+            Line::from(Span::styled(
+                conflict.local.display_title(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(
+                format!(
+                    "Changed here and on another device; found {}",
+                    conflict
+                        .detected_at
+                        .with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M")
+                ),
+                Style::default().fg(self.theme.muted),
+            )),
+            Line::from(vec![
+                Span::styled(
+                    format!("  {:<NAME_WIDTH$}", "field"),
+                    Style::default().fg(self.theme.muted),
+                ),
+                Span::styled(
+                    pad("local", self.column_width(inner)),
+                    Style::default().fg(self.theme.muted),
+                ),
+                Span::styled("remote", Style::default().fg(self.theme.muted)),
+            ]),
+        ];
+        frame.render_widget(Paragraph::new(header), rows[0]);
+
+        let width = self.column_width(inner);
+        let items: Vec<ListItem> = self
+            .state
+            .picks
+            .iter()
+            .enumerate()
+            .map(|(i, &(field, side))| {
+                let is_selected = i == self.state.selected;
+                let value = |todo, kept: bool| {
+                    let style = if kept {
+                        Style::default()
+                            .fg(self.theme.success)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(self.theme.muted)
+                    };
+                    Span::styled(pad(&field.display(todo, &self.state.names), width), style)
+                };
+                let content = Line::from(vec![
+                    Span::styled(
+                        if is_selected { "▸ " } else { "  " },
+                        Style::default().fg(self.theme.primary),
+                    ),
+                    Span::raw(format!("{:<NAME_WIDTH$}", field.name())),
+                    value(&conflict.local, side == Side::Local),
+                    value(&conflict.remote, side == Side::Remote),
+                ]);
+
+                let style = if is_selected {
+                    Style::default().bg(self.theme.selection_bg)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(content).style(style)
+            })
+            .collect();
+
+        let mut list_state = ListState::default().with_selected(Some(self.state.selected));
+        frame.render_stateful_widget(List::new(items), rows[1], &mut list_state);
+    }
+
+    /// Width of the local and remote columns
+    fn column_width(&self, inner: Rect) -> usize {
+        (inner.width as usize).saturating_sub(NAME_WIDTH + 2) / 2
+    }
+}
+
+/// `text` cut or padded to `width` columns, leaving a gap before the next
+fn pad(text: &str, width: usize) -> String {
+    let room = width.saturating_sub(2);
+    let mut cell: String = text.chars().take(room).collect();
+    if text.chars().count() > room && room > 0 {
+        cell.pop();
+        cell.push('…');
+    }
+    format!("{:<width$}", cell)
+}
@@ -1,24 +1,27 @@
 use ratatui::{
     Frame,
     layout::{Alignment, Rect},
-    style::{Color, Modifier, Style, Stylize},
+    style::{Modifier, Style, Stylize},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
 use crate::tui::app::FocusState;
 use crate::tui::spinner::bracketed_progress;
+use crate::tui::theme::Theme;
 
 pub struct FocusWidget<'a> {
     state: &'a FocusState,
     animation_frame: usize,
+    theme: &'a Theme,
 }
 
 impl<'a> FocusWidget<'a> {
-    pub fn new(state: &'a FocusState, animation_frame: usize) -> Self {
+    pub fn new(state: &'a FocusState, animation_frame: usize, theme: &'a Theme) -> Self {
         Self {
             state,
             animation_frame,
+            theme,
         }
     }
 
@@ -34,11 +37,11 @@ impl<'a> FocusWidget<'a> {
         };
 
         let time_color = if remaining <= 60 {
-            Color::Red
+            self.theme.error
         } else if remaining <= 300 {
-            Color::Yellow
+            self.theme.warning
         } else {
-            Color::Green
+            self.theme.success
         };
 
         // Animated header when paused
@@ -53,7 +56,7 @@ impl<'a> FocusWidget<'a> {
         let separator = if self.state.paused {
             ':'
         } else {
-            [' ', ':'][self.animation_frame % 2]
+            [':', ' '][self.animation_frame % 2]
         };
 
         // Enhanced progress bar
@@ -74,9 +77,9 @@ impl<'a> FocusWidget<'a> {
                 header,
                 Style::default()
                     .fg(if self.state.paused {
-                        Color::Yellow
+                        self.theme.warning
                     } else {
-                        Color::Magenta
+                        self.theme.accent
                     })
                     .add_modifier(Modifier::BOLD),
             )),
@@ -84,7 +87,7 @@ impl<'a> FocusWidget<'a> {
             Line::from(Span::styled(
                 &self.state.todo_title,
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(self.theme.primary)
                     .add_modifier(Modifier::BOLD),
             )),
             Line::from(""),
@@ -97,17 +100,32 @@ impl<'a> FocusWidget<'a> {
             Line::from(""),
             Line::from(Span::styled(
                 motivation,
-                Style::default().fg(Color::DarkGray).italic(),
+                Style::default().fg(self.theme.muted).italic(),
             )),
             Line::from(""),
+            match (
+                &self.state.reason_input,
+                self.state.session.interruptions.len(),
+            ) {
+                (Some(reason), _) => Line::from(vec![
+                    Span::styled("Interrupted by: ", Style::default().fg(self.theme.warning)),
+                    Span::raw(reason.as_str()),
+                    Span::styled("\u{2588}", Style::default().fg(self.theme.warning)),
+                ]),
+                (None, 0) => Line::from(""),
+                (None, n) => Line::from(Span::styled(
+                    format!("Interruptions: {}", n),
+                    Style::default().fg(self.theme.muted),
+                )),
+            },
             Line::from(Span::styled(
-                "Space: pause  q/Esc: cancel  Enter: complete",
-                Style::default().fg(Color::DarkGray),
+                "Space: pause  i: interrupted  q/Esc: cancel  Enter: complete",
+                Style::default().fg(self.theme.muted),
             )),
         ];
 
         let border_color = if self.state.paused {
-            Color::Yellow
+            self.theme.warning
         } else {
             time_color
         };
@@ -0,0 +1,108 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::tui::app::HelpState;
+use crate::tui::keymap::HelpLine;
+use crate::tui::theme::Theme;
+
+pub struct HelpWidget<'a> {
+    state: &'a HelpState,
+    lines: &'a [HelpLine],
+    theme: &'a Theme,
+}
+
+impl<'a> HelpWidget<'a> {
+    pub fn new(state: &'a HelpState, lines: &'a [HelpLine], theme: &'a Theme) -> Self {
+        Self {
+            state,
+            lines,
+            theme,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(" Help ")
+            .title_bottom(" /:search  Tab:all sections  j/k:scroll  Esc:close ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.border_focused));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(1)])
+            .split(inner);
+
+        frame.render_widget(Paragraph::new(self.search_line()), chunks[0]);
+
+        let page = chunks[1].height as usize;
+        self.state.page.set(page);
+        let scroll = self.state.scroll.min(self.lines.len().saturating_sub(page));
+
+        let body: Vec<Line> = if self.lines.is_empty() {
+            vec![Line::from(Span::styled(
+                "  No shortcuts or commands match",
+                Style::default().fg(self.theme.muted).italic(),
+            ))]
+        } else {
+            self.lines
+                .iter()
+                .skip(scroll)
+                .take(page)
+                .map(|line| render_line(line, self.theme))
+                .collect()
+        };
+        frame.render_widget(Paragraph::new(body), chunks[1]);
+    }
+
+    fn search_line(&self) -> Line<'_> {
+        if !self.state.searching && self.state.query.is_empty() {
+            return Line::from(Span::styled(
+                "═══ TODOEE KEYBOARD SHORTCUTS ═══",
+                Style::default().bold().fg(self.theme.primary),
+            ));
+        }
+        let cursor = if self.state.searching { "▏" } else { "" };
+        Line::from(vec![
+            Span::styled("Search: ", Style::default().fg(self.theme.primary)),
+            Span::raw(format!("{}{}", self.state.query, cursor)),
+        ])
+    }
+}
+
+fn render_line<'a>(line: &'a HelpLine, theme: &Theme) -> Line<'a> {
+    match line {
+        HelpLine::Section {
+            title, open: true, ..
+        } => Line::from(Span::styled(
+            format!("▾ {}", title),
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        )),
+        HelpLine::Section {
+            title,
+            open: false,
+            entries,
+        } => Line::from(vec![
+            Span::styled(format!("▸ {}", title), Style::default().fg(theme.warning)),
+            Span::styled(format!("  ({})", entries), Style::default().fg(theme.muted)),
+        ]),
+        HelpLine::Entry { keys, action } => Line::from(vec![
+            Span::styled(
+                format!("  {:<14}", keys),
+                Style::default().fg(theme.primary),
+            ),
+            Span::raw(action.as_str()),
+        ]),
+        HelpLine::Blank => Line::from(""),
+    }
+}
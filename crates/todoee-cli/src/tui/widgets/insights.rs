@@ -1,50 +1,86 @@
+use chrono::Utc;
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
 
+use crate::commands::insights::WEEKDAYS;
+use crate::commands::project::progress_bar;
 use crate::tui::app::InsightsData;
+use crate::tui::theme::Theme;
+use todoee_core::dates;
 
 pub struct InsightsWidget<'a> {
     data: &'a InsightsData,
-    animation_frame: usize,
-    opened_frame: usize,
+    /// Frames since the view opened
+    elapsed: usize,
+    theme: &'a Theme,
 }
 
 impl<'a> InsightsWidget<'a> {
-    pub fn new(data: &'a InsightsData, animation_frame: usize, opened_frame: usize) -> Self {
+    pub fn new(data: &'a InsightsData, elapsed: usize, theme: &'a Theme) -> Self {
         Self {
             data,
-            animation_frame,
-            opened_frame,
+            elapsed,
+            theme,
         }
     }
 
     /// Animate a number from 0 to target over animation_duration frames
     fn animated_value(&self, target: usize, animation_duration: usize) -> usize {
-        let elapsed = self.animation_frame.wrapping_sub(self.opened_frame);
-        if elapsed >= animation_duration {
+        if self.elapsed >= animation_duration {
             target
         } else {
-            let progress = elapsed as f64 / animation_duration as f64;
+            let progress = self.elapsed as f64 / animation_duration as f64;
             (target as f64 * progress).round() as usize
         }
     }
 
     /// Animate a float value
     fn animated_float(&self, target: f64, animation_duration: usize) -> f64 {
-        let elapsed = self.animation_frame.wrapping_sub(self.opened_frame);
-        if elapsed >= animation_duration {
+        if self.elapsed >= animation_duration {
             target
         } else {
-            let progress = elapsed as f64 / animation_duration as f64;
+            let progress = self.elapsed as f64 / animation_duration as f64;
             target * progress
         }
     }
 
+    /// Push a titled `label  bar count` chart, bars scaled to the largest count
+    fn bar_chart(
+        &self,
+        lines: &mut Vec<Line<'a>>,
+        title: &'static str,
+        rows: &[(String, u64)],
+        anim_duration: usize,
+    ) {
+        let width = rows
+            .iter()
+            .map(|(l, _)| l.chars().count())
+            .max()
+            .unwrap_or(0);
+        let max = rows.iter().map(|(_, c)| *c).max().unwrap_or(0).max(1);
+        lines.push(Line::from(Span::styled(
+            title,
+            Style::default().fg(self.theme.warning),
+        )));
+        for (label, count) in rows {
+            let ratio = self.animated_float(*count as f64 / max as f64, anim_duration);
+            lines.push(Line::from(vec![
+                Span::raw(format!("    {:<width$}  ", label)),
+                Span::styled(
+                    progress_bar(ratio, 16),
+                    Style::default().fg(self.theme.accent),
+                ),
+                Span::raw(format!(" {}", count)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
     pub fn render(&self, frame: &mut Frame, area: Rect) {
         let anim_duration = 8; // Animate over 8 frames (2 seconds at 250ms tick)
 
@@ -58,18 +94,18 @@ impl<'a> InsightsWidget<'a> {
         let low = self.animated_value(self.data.low_priority_pending, anim_duration);
 
         let rate_color = if rate >= 70.0 {
-            Color::Green
+            self.theme.success
         } else if rate >= 40.0 {
-            Color::Yellow
+            self.theme.warning
         } else {
-            Color::Red
+            self.theme.error
         };
 
-        let lines = vec![
+        let mut lines = vec![
             Line::from(Span::styled(
                 " Productivity Insights (7 days)",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(self.theme.primary)
                     .add_modifier(Modifier::BOLD),
             )),
             Line::from(""),
@@ -78,13 +114,16 @@ impl<'a> InsightsWidget<'a> {
                 Span::styled(
                     completed.to_string(),
                     Style::default()
-                        .fg(Color::Green)
+                        .fg(self.theme.success)
                         .add_modifier(Modifier::BOLD),
                 ),
             ]),
             Line::from(vec![
                 Span::raw("  Created:   "),
-                Span::styled(created.to_string(), Style::default().fg(Color::Blue)),
+                Span::styled(
+                    created.to_string(),
+                    Style::default().fg(self.theme.secondary),
+                ),
             ]),
             Line::from(vec![
                 Span::raw("  Rate:      "),
@@ -96,45 +135,142 @@ impl<'a> InsightsWidget<'a> {
                 Span::styled(
                     overdue.to_string(),
                     if overdue > 0 {
-                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+                        Style::default()
+                            .fg(self.theme.error)
+                            .add_modifier(Modifier::BOLD)
                     } else {
-                        Style::default().fg(Color::Green)
+                        Style::default().fg(self.theme.success)
                     },
                 ),
             ]),
+        ];
+
+        if let Some(average) = self.data.average_completion {
+            lines.push(Line::from(vec![
+                Span::raw("  Avg. time: "),
+                Span::styled(
+                    dates::format_duration(average),
+                    Style::default().fg(self.theme.secondary),
+                ),
+            ]));
+        }
+        if let Some(ref todo) = self.data.oldest_open {
+            let age = Utc::now().signed_duration_since(todo.created_at).num_days();
+            lines.push(Line::from(vec![
+                Span::raw("  Oldest:    "),
+                Span::styled(todo.title.clone(), Style::default().fg(self.theme.text)),
+                Span::styled(
+                    format!(" ({}d)", age),
+                    Style::default().fg(self.theme.text_muted),
+                ),
+            ]));
+        }
+
+        lines.extend([
             Line::from(""),
             Line::from(Span::styled(
                 "  Pending by Priority:",
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(self.theme.warning),
             )),
             Line::from(vec![
                 Span::raw("    "),
-                Span::styled("!!!", Style::default().fg(Color::Red)),
+                Span::styled("!!!", Style::default().fg(self.theme.error)),
                 Span::raw(format!(" High:   {}", high)),
             ]),
             Line::from(vec![
                 Span::raw("    "),
-                Span::styled("!! ", Style::default().fg(Color::Yellow)),
+                Span::styled("!! ", Style::default().fg(self.theme.warning)),
                 Span::raw(format!(" Medium: {}", medium)),
             ]),
             Line::from(vec![
                 Span::raw("    "),
-                Span::styled("!  ", Style::default().fg(Color::Green)),
+                Span::styled("!  ", Style::default().fg(self.theme.success)),
                 Span::raw(format!(" Low:    {}", low)),
             ]),
             Line::from(""),
-            Line::from(Span::styled(
-                "  Press any key to close",
-                Style::default().fg(Color::DarkGray),
-            )),
-        ];
+        ]);
+
+        if self.data.total_completed_7d > 0 {
+            let rows: Vec<(String, u64)> = WEEKDAYS
+                .iter()
+                .zip(self.data.completed_by_weekday)
+                .map(|(day, count)| (format!("{:?}", day), count))
+                .collect();
+            self.bar_chart(&mut lines, "  Completed by Weekday:", &rows, anim_duration);
+        }
+
+        if !self.data.categories.is_empty() {
+            let rows: Vec<(String, u64)> = self
+                .data
+                .categories
+                .iter()
+                .map(|c| {
+                    let name = c.category.as_deref().unwrap_or("Uncategorized");
+                    (name.to_string(), c.completed)
+                })
+                .collect();
+            self.bar_chart(&mut lines, "  Completed by Category:", &rows, anim_duration);
+        }
+
+        if !self.data.focus_by_day.is_empty() {
+            let rows: Vec<(String, u64)> = self
+                .data
+                .focus_by_day
+                .iter()
+                .map(|(day, sessions)| (day.format("%a %d").to_string(), *sessions))
+                .collect();
+            self.bar_chart(&mut lines, "  Focus Sessions by Day:", &rows, anim_duration);
+
+            let rows: Vec<(String, u64)> = self
+                .data
+                .focus_by_todo
+                .iter()
+                .take(5)
+                .map(|t| {
+                    let title = t.title.as_deref().unwrap_or("(deleted)");
+                    (title.chars().take(24).collect(), t.minutes)
+                })
+                .collect();
+            self.bar_chart(&mut lines, "  Focus Minutes by Todo:", &rows, anim_duration);
+        }
+
+        if !self.data.projects.is_empty() {
+            let name_width = self
+                .data
+                .projects
+                .iter()
+                .map(|p| p.project.name.chars().count())
+                .max()
+                .unwrap_or(0);
+            lines.push(Line::from(Span::styled(
+                "  Projects:",
+                Style::default().fg(self.theme.warning),
+            )));
+            for p in &self.data.projects {
+                let ratio = self.animated_float(p.ratio(), anim_duration);
+                lines.push(Line::from(vec![
+                    Span::raw(format!("    {:<name_width$}  ", p.project.name)),
+                    Span::styled(
+                        progress_bar(ratio, 16),
+                        Style::default().fg(self.theme.success),
+                    ),
+                    Span::raw(format!(" {}/{}", p.completed, p.total)),
+                ]));
+            }
+            lines.push(Line::from(""));
+        }
+
+        lines.push(Line::from(Span::styled(
+            "  Press any key to close",
+            Style::default().fg(self.theme.muted),
+        )));
 
         let paragraph = Paragraph::new(lines)
             .block(
                 Block::default()
                     .title(" Insights ")
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Cyan)),
+                    .border_style(Style::default().fg(self.theme.border_focused)),
             )
             .wrap(Wrap { trim: false });
 
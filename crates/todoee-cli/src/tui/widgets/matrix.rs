@@ -0,0 +1,121 @@
+use chrono::Utc;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+};
+use todoee_core::Quadrant;
+
+use crate::tui::app::MatrixState;
+use crate::tui::theme::Theme;
+
+pub struct MatrixWidget<'a> {
+    state: &'a MatrixState,
+    theme: &'a Theme,
+}
+
+impl<'a> MatrixWidget<'a> {
+    pub fn new(state: &'a MatrixState, theme: &'a Theme) -> Self {
+        Self { state, theme }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let outer = Block::default()
+            .title(" Eisenhower Matrix ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.border_focused));
+        let inner = outer.inner(area);
+        frame.render_widget(outer, area);
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(inner);
+
+        for (row, row_area) in rows.iter().enumerate() {
+            let cells = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(*row_area);
+            for (col, cell_area) in cells.iter().enumerate() {
+                self.render_quadrant(frame, *cell_area, Quadrant::ALL[row * 2 + col]);
+            }
+        }
+    }
+
+    fn render_quadrant(&self, frame: &mut Frame, area: Rect, quadrant: Quadrant) {
+        let now = Utc::now();
+        let todos = &self.state.quadrants[quadrant.index()];
+        let is_active = self.state.active == quadrant.index();
+
+        let color = match quadrant {
+            Quadrant::DoFirst => self.theme.error,
+            Quadrant::Schedule => self.theme.warning,
+            Quadrant::Delegate => self.theme.primary,
+            Quadrant::Eliminate => self.theme.muted,
+        };
+
+        let items: Vec<ListItem> = todos
+            .iter()
+            .enumerate()
+            .map(|(i, todo)| {
+                let is_selected = is_active && i == self.state.selected;
+
+                let due = todo
+                    .due_date
+                    .map(|due| {
+                        let days = (due.date_naive() - now.date_naive()).num_days();
+                        match days {
+                            d if d < 0 => format!(" ({}d late)", -d),
+                            0 => " (today)".to_string(),
+                            1 => " (tomorrow)".to_string(),
+                            d => format!(" ({}d)", d),
+                        }
+                    })
+                    .unwrap_or_default();
+
+                let content = Line::from(vec![
+                    Span::styled(
+                        if is_selected { "▸ " } else { "  " },
+                        Style::default().fg(self.theme.primary),
+                    ),
+                    Span::raw(&todo.title),
+                    Span::styled(due, Style::default().fg(self.theme.muted)),
+                ]);
+
+                let style = if is_selected {
+                    Style::default().bg(self.theme.selection_bg)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(content).style(style)
+            })
+            .collect();
+
+        let border_style = if is_active {
+            Style::default().fg(color).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(color)
+        };
+
+        let list = List::new(items).block(
+            Block::default()
+                .title(format!(
+                    " {} {} ({}) ",
+                    quadrant.index() + 1,
+                    quadrant.title(),
+                    todos.len()
+                ))
+                .borders(Borders::ALL)
+                .border_style(border_style),
+        );
+
+        let mut list_state =
+            ListState::default().with_selected(is_active.then_some(self.state.selected));
+        frame.render_stateful_widget(list, area, &mut list_state);
+    }
+}
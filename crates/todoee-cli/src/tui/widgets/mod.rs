@@ -1,15 +1,31 @@
 pub mod category_list;
+pub mod celebration;
+pub mod conflict;
 pub mod focus;
+pub mod help;
 pub mod insights;
+pub mod matrix;
+pub mod project_list;
 pub mod settings;
+pub mod timeblock;
 pub mod todo_add;
 pub mod todo_detail;
 pub mod todo_editor;
+pub mod tutorial;
+pub mod zen;
 
 pub use category_list::CategoryListWidget;
+pub use celebration::CelebrationWidget;
+pub use conflict::ConflictWidget;
 pub use focus::FocusWidget;
+pub use help::HelpWidget;
 pub use insights::InsightsWidget;
-pub use settings::SettingsWidget;
+pub use matrix::MatrixWidget;
+pub use project_list::ProjectListWidget;
+pub use settings::{SettingsCursor, SettingsWidget, SyncPanel};
+pub use timeblock::TimeblockWidget;
 pub use todo_add::TodoAddWidget;
 pub use todo_detail::TodoDetailWidget;
 pub use todo_editor::TodoEditorWidget;
+pub use tutorial::TutorialWidget;
+pub use zen::ZenWidget;
@@ -0,0 +1,113 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem},
+};
+use todoee_core::ProjectProgress;
+
+use crate::commands::project::progress_bar;
+use crate::tui::theme::Theme;
+
+pub struct ProjectListWidget<'a> {
+    projects: &'a [ProjectProgress],
+    selected: usize,
+    /// Name of the project the todo list is filtered to, if any
+    filtered: Option<&'a str>,
+    animation_frame: usize,
+    theme: &'a Theme,
+}
+
+impl<'a> ProjectListWidget<'a> {
+    pub fn new(
+        projects: &'a [ProjectProgress],
+        selected: usize,
+        filtered: Option<&'a str>,
+        animation_frame: usize,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            projects,
+            selected,
+            filtered,
+            animation_frame,
+            theme,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let name_width = self
+            .projects
+            .iter()
+            .map(|p| p.project.name.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let items: Vec<ListItem> = self
+            .projects
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let is_selected = i == self.selected;
+
+                // Animated cursor
+                let selector = if is_selected {
+                    let cursors = ['▸', '▹', '▸', '▹'];
+                    format!("{} ", cursors[self.animation_frame % cursors.len()])
+                } else {
+                    "  ".to_string()
+                };
+
+                let bar_color = if p.total > 0 && p.completed == p.total {
+                    self.theme.success
+                } else {
+                    self.theme.primary
+                };
+
+                let filter_badge = if self.filtered == Some(p.project.name.as_str()) {
+                    Span::styled(" [FILTER]", Style::default().fg(self.theme.warning))
+                } else {
+                    Span::raw("")
+                };
+
+                let content = Line::from(vec![
+                    Span::styled(
+                        selector,
+                        if is_selected {
+                            Style::default()
+                                .fg(self.theme.primary)
+                                .add_modifier(Modifier::BOLD)
+                        } else {
+                            Style::default()
+                        },
+                    ),
+                    Span::raw(format!("{:<name_width$}  ", p.project.name)),
+                    Span::styled(progress_bar(p.ratio(), 20), Style::default().fg(bar_color)),
+                    Span::styled(
+                        format!(" {}/{}", p.completed, p.total),
+                        Style::default().fg(self.theme.muted),
+                    ),
+                    filter_badge,
+                ]);
+
+                let style = if is_selected {
+                    Style::default().bg(self.theme.selection_bg)
+                } else {
+                    Style::default()
+                };
+
+                ListItem::new(content).style(style)
+            })
+            .collect();
+
+        let list = List::new(items).block(
+            Block::default()
+                .title(format!(" Projects ({}) ", self.projects.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.border_focused)),
+        );
+
+        frame.render_widget(list, area);
+    }
+}
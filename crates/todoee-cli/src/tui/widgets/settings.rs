@@ -1,22 +1,91 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
 };
-use todoee_core::Config;
+use std::time::Duration;
 
-use crate::tui::app::SettingsSection;
+use chrono::{Local, Utc};
+use todoee_core::config::THEMES;
+use todoee_core::{Config, Conflict, DaemonStatus, SyncHealth, short_id};
+
+use crate::tui::app::{SettingField, SettingsSection};
+use crate::tui::theme::Theme;
+
+/// Sync state shown in the Database section
+pub struct SyncPanel<'a> {
+    pub health: &'a SyncHealth,
+    pub conflicts: &'a [Conflict],
+    pub show_conflicts: bool,
+    pub id_length: usize,
+    pub latency: Option<&'a Result<Duration, String>>,
+}
+
+/// The selected setting and, while it's being edited, the typed value
+pub struct SettingsCursor<'a> {
+    pub field: Option<SettingField>,
+    pub editing: Option<&'a str>,
+}
 
 pub struct SettingsWidget<'a> {
     config: &'a Config,
     section: SettingsSection,
+    cursor: SettingsCursor<'a>,
+    sync: SyncPanel<'a>,
+    daemon: Option<&'a DaemonStatus>,
+    theme: &'a Theme,
 }
 
 impl<'a> SettingsWidget<'a> {
-    pub fn new(config: &'a Config, section: SettingsSection) -> Self {
-        Self { config, section }
+    pub fn new(
+        config: &'a Config,
+        section: SettingsSection,
+        cursor: SettingsCursor<'a>,
+        sync: SyncPanel<'a>,
+        daemon: Option<&'a DaemonStatus>,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            config,
+            section,
+            cursor,
+            sync,
+            daemon,
+            theme,
+        }
+    }
+
+    /// A setting's line: highlighted when selected, and showing the input
+    /// instead of `value` while it's edited
+    fn field(&self, key: &str, label: &'static str, value: Vec<Span<'static>>) -> Line<'static> {
+        let selected = self.cursor.field.is_some_and(|f| f.key == key);
+        let label = Span::styled(label, Style::default().add_modifier(Modifier::BOLD));
+        match self.cursor.editing {
+            Some(input) if selected => Line::from(vec![
+                label,
+                Span::styled(input.to_string(), Style::default().fg(self.theme.primary)),
+                Span::styled("│", Style::default().fg(self.theme.text)),
+            ]),
+            _ if selected => {
+                let mut spans = vec![label];
+                spans.extend(value);
+                Line::from(spans).style(Style::default().bg(self.theme.selection_bg))
+            }
+            _ => {
+                let mut spans = vec![label];
+                spans.extend(value);
+                Line::from(spans)
+            }
+        }
+    }
+
+    fn edit_hint(&self) -> Line<'static> {
+        Line::from(Span::styled(
+            "Tab selects a setting, Enter edits it; the rest are in ~/.config/todoee/config.toml",
+            Style::default().fg(self.theme.muted),
+        ))
     }
 
     pub fn render(&self, frame: &mut Frame, area: Rect) {
@@ -46,10 +115,10 @@ impl<'a> SettingsWidget<'a> {
                 let is_selected = self.section == *sec;
                 let style = if is_selected {
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(self.theme.primary)
                         .add_modifier(Modifier::BOLD)
                 } else {
-                    Style::default().fg(Color::White)
+                    Style::default().fg(self.theme.text)
                 };
                 let prefix = if is_selected { "▸ " } else { "  " };
                 ListItem::new(format!("{}{}", prefix, label)).style(style)
@@ -60,7 +129,7 @@ impl<'a> SettingsWidget<'a> {
             Block::default()
                 .title(" Sections ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(Style::default().fg(self.theme.border)),
         );
 
         frame.render_widget(list, area);
@@ -85,7 +154,7 @@ impl<'a> SettingsWidget<'a> {
             Block::default()
                 .title(title)
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(Style::default().fg(self.theme.border_focused)),
         );
 
         frame.render_widget(paragraph, area);
@@ -100,27 +169,35 @@ impl<'a> SettingsWidget<'a> {
             .map(|m| format!("✓ {}", m))
             .unwrap_or_else(|| "✗ Not configured".to_string());
 
-        let api_key_status = std::env::var(&self.config.ai.api_key_env)
-            .map(|_| "✓ Set".to_string())
-            .unwrap_or_else(|_| "✗ Not set".to_string());
+        let api_key_ok =
+            !self.config.ai.needs_api_key() || std::env::var(&self.config.ai.api_key_env).is_ok();
+        let api_key_status = if !self.config.ai.needs_api_key() {
+            "✓ Not needed".to_string()
+        } else if api_key_ok {
+            "✓ Set".to_string()
+        } else {
+            "✗ Not set".to_string()
+        };
 
         vec![
-            Line::from(vec![
-                Span::styled("Provider: ", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(self.config.ai.provider.clone()),
-            ]),
+            self.field(
+                "ai.provider",
+                "Provider: ",
+                vec![Span::raw(self.config.ai.provider.clone())],
+            ),
             Line::from(""),
-            Line::from(vec![
-                Span::styled("Model: ", Style::default().add_modifier(Modifier::BOLD)),
-                Span::styled(
+            self.field(
+                "ai.model",
+                "Model: ",
+                vec![Span::styled(
                     model_status,
                     Style::default().fg(if self.config.ai.model.is_some() {
-                        Color::Green
+                        self.theme.success
                     } else {
-                        Color::Red
+                        self.theme.error
                     }),
-                ),
-            ]),
+                )],
+            ),
             Line::from(""),
             Line::from(vec![
                 Span::styled(
@@ -130,86 +207,169 @@ impl<'a> SettingsWidget<'a> {
                 Span::raw(self.config.ai.api_key_env.clone()),
             ]),
             Line::from(vec![
-                Span::styled("  Status: ", Style::default().fg(Color::DarkGray)),
+                Span::styled("  Status: ", Style::default().fg(self.theme.muted)),
                 Span::styled(
                     api_key_status,
-                    Style::default().fg(if std::env::var(&self.config.ai.api_key_env).is_ok() {
-                        Color::Green
+                    Style::default().fg(if api_key_ok {
+                        self.theme.success
                     } else {
-                        Color::Red
+                        self.theme.error
                     }),
                 ),
             ]),
             Line::from(""),
-            Line::from(Span::styled(
-                "Edit ~/.config/todoee/config.toml to configure",
-                Style::default().fg(Color::DarkGray),
-            )),
+            self.edit_hint(),
         ]
     }
 
     fn render_display_settings(&self) -> Vec<Line<'static>> {
         vec![
-            Line::from(vec![
-                Span::styled("Theme: ", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(self.config.display.theme.clone()),
-            ]),
+            self.field(
+                "display.theme",
+                "Theme: ",
+                vec![
+                    Span::raw(self.config.display.theme.clone()),
+                    Span::styled(
+                        format!("  (t switches: {})", THEMES.join(", ")),
+                        Style::default().fg(self.theme.muted),
+                    ),
+                ],
+            ),
             Line::from(""),
-            Line::from(vec![
-                Span::styled(
-                    "Date Format: ",
-                    Style::default().add_modifier(Modifier::BOLD),
-                ),
-                Span::raw(self.config.display.date_format.clone()),
-            ]),
+            self.field(
+                "display.date_format",
+                "Date Format: ",
+                vec![Span::raw(self.config.display.date_format.clone())],
+            ),
+            Line::from(""),
+            self.field(
+                "display.density",
+                "Density: ",
+                vec![
+                    Span::raw(self.config.display.density.clone()),
+                    Span::styled(
+                        "  (D toggles in the task list)",
+                        Style::default().fg(self.theme.muted),
+                    ),
+                ],
+            ),
+            Line::from(""),
+            self.field(
+                "display.animations",
+                "Animations: ",
+                vec![Span::raw(if self.config.display.reduced_motion {
+                    "off (reduced motion)"
+                } else if self.config.display.animations {
+                    "on"
+                } else {
+                    "off"
+                })],
+            ),
+            self.field(
+                "display.celebrate",
+                "Celebrate inbox zero: ",
+                vec![Span::raw(if self.config.display.celebrate {
+                    "on"
+                } else {
+                    "off"
+                })],
+            ),
+            Line::from(""),
+            self.edit_hint(),
         ]
     }
 
     fn render_notification_settings(&self) -> Vec<Line<'static>> {
-        vec![
-            Line::from(vec![
-                Span::styled("Enabled: ", Style::default().add_modifier(Modifier::BOLD)),
-                Span::styled(
-                    if self.config.notifications.enabled {
-                        "Yes"
-                    } else {
-                        "No"
-                    },
-                    Style::default().fg(if self.config.notifications.enabled {
-                        Color::Green
-                    } else {
-                        Color::Red
-                    }),
-                ),
-            ]),
+        let yes_no = |on: bool| {
+            Span::styled(
+                if on { "Yes" } else { "No" },
+                Style::default().fg(if on {
+                    self.theme.success
+                } else {
+                    self.theme.error
+                }),
+            )
+        };
+        let mut lines = vec![
+            self.field(
+                "notifications.enabled",
+                "Enabled: ",
+                vec![yes_no(self.config.notifications.enabled)],
+            ),
             Line::from(""),
-            Line::from(vec![
-                Span::styled("Sound: ", Style::default().add_modifier(Modifier::BOLD)),
+            self.field(
+                "notifications.sound",
+                "Sound: ",
+                vec![yes_no(self.config.notifications.sound)],
+            ),
+            Line::from(""),
+            self.field(
+                "notifications.advance_minutes",
+                "Advance Notice: ",
+                vec![Span::raw(format!(
+                    "{} minutes",
+                    self.config.notifications.advance_minutes
+                ))],
+            ),
+            Line::from(""),
+            self.field(
+                "notifications.batch_threshold",
+                "Combine At: ",
+                vec![Span::raw(match self.config.notifications.batch_threshold {
+                    0 => "never".to_string(),
+                    n => format!("{} at once", n),
+                })],
+            ),
+            Line::from(""),
+        ];
+        lines.extend(self.daemon_lines());
+        lines.extend([Line::from(""), self.edit_hint()]);
+        lines
+    }
+
+    /// Whether the reminder daemon is checking, from its status file
+    fn daemon_lines(&self) -> Vec<Line<'static>> {
+        let label = Span::styled("Daemon: ", Style::default().add_modifier(Modifier::BOLD));
+        let now = Utc::now();
+        let Some(status) = self.daemon.filter(|s| !s.is_stale(now)) else {
+            return vec![Line::from(vec![
+                label,
                 Span::styled(
-                    if self.config.notifications.sound {
-                        "Yes"
-                    } else {
-                        "No"
-                    },
-                    Style::default().fg(if self.config.notifications.sound {
-                        Color::Green
-                    } else {
-                        Color::Red
-                    }),
+                    "Not running (todoee daemon start)",
+                    Style::default().fg(self.theme.text_muted),
                 ),
-            ]),
-            Line::from(""),
+            ])];
+        };
+
+        let local =
+            |at: chrono::DateTime<Utc>| at.with_timezone(&Local).format("%H:%M").to_string();
+        let mut lines = vec![
             Line::from(vec![
+                label,
                 Span::styled(
-                    "Advance Notice: ",
-                    Style::default().add_modifier(Modifier::BOLD),
+                    format!("Running (PID {})", status.pid),
+                    Style::default().fg(self.theme.success),
                 ),
-                Span::raw(format!(
-                    "{} minutes",
-                    self.config.notifications.advance_minutes
-                )),
             ]),
-        ]
+            Line::from(format!(
+                "  Last check {}, {} reminders sent since {}",
+                status
+                    .last_check
+                    .map_or_else(|| "pending".to_string(), local),
+                status.reminders_sent,
+                local(status.started_at)
+            )),
+        ];
+        if let Some(at) = status.last_sync {
+            lines.push(Line::from(format!("  Last sync {}", local(at))));
+        }
+        if let Some(error) = status.errors.last() {
+            lines.push(Line::from(Span::styled(
+                format!("  Last error ({}): {}", local(error.at), error.message),
+                Style::default().fg(self.theme.error),
+            )));
+        }
+        lines
     }
 
     fn render_database_settings(&self) -> Vec<Line<'static>> {
@@ -223,7 +383,7 @@ impl<'a> SettingsWidget<'a> {
             .map(|p| p.display().to_string())
             .unwrap_or_else(|_| "Error loading path".to_string());
 
-        vec![
+        let mut lines = vec![
             Line::from(vec![
                 Span::styled(
                     "Cloud Sync Env: ",
@@ -232,13 +392,13 @@ impl<'a> SettingsWidget<'a> {
                 Span::raw(self.config.database.url_env.clone()),
             ]),
             Line::from(vec![
-                Span::styled("  Status: ", Style::default().fg(Color::DarkGray)),
+                Span::styled("  Status: ", Style::default().fg(self.theme.muted)),
                 Span::styled(
                     neon_status,
                     Style::default().fg(if std::env::var(&self.config.database.url_env).is_ok() {
-                        Color::Green
+                        self.theme.success
                     } else {
-                        Color::Yellow
+                        self.theme.warning
                     }),
                 ),
             ]),
@@ -247,7 +407,105 @@ impl<'a> SettingsWidget<'a> {
                 "Local Database: ",
                 Style::default().add_modifier(Modifier::BOLD),
             )]),
-            Line::from(Span::styled(local_db, Style::default().fg(Color::DarkGray))),
-        ]
+            Line::from(Span::styled(
+                local_db,
+                Style::default().fg(self.theme.muted),
+            )),
+        ];
+        lines.extend(self.render_sync_health());
+        lines
+    }
+
+    fn render_sync_health(&self) -> Vec<Line<'static>> {
+        let health = self.sync.health;
+        let label = |text: &'static str| Span::styled(text, Style::default().fg(self.theme.muted));
+
+        let last_sync = match health.last_success {
+            Some(at) => Span::raw(
+                at.with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string(),
+            ),
+            None => Span::styled("Never", Style::default().fg(self.theme.warning)),
+        };
+
+        let pending_style = Style::default().fg(if health.pending_uploads > 0 {
+            self.theme.warning
+        } else {
+            self.theme.success
+        });
+
+        let last_error = match health.last_error() {
+            // Errors may carry hints on later lines; the first line is the cause
+            Some(e) => Span::styled(
+                format!("✗ {}", e.lines().next().unwrap_or(e)),
+                Style::default().fg(self.theme.error),
+            ),
+            None => Span::styled("None", Style::default().fg(self.theme.success)),
+        };
+
+        let latency = match self.sync.latency {
+            Some(Ok(rtt)) => Span::styled(
+                format!("{} ms", rtt.as_millis()),
+                Style::default().fg(self.theme.success),
+            ),
+            Some(Err(e)) => Span::styled(
+                format!("✗ {}", e.lines().next().unwrap_or(e)),
+                Style::default().fg(self.theme.error),
+            ),
+            None => Span::styled("press p to measure", Style::default().fg(self.theme.muted)),
+        };
+
+        let conflict_count = self.sync.conflicts.len();
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "Sync Health",
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(vec![label("  Last sync:       "), last_sync]),
+            Line::from(vec![
+                label("  Pending uploads: "),
+                Span::styled(health.pending_uploads.to_string(), pending_style),
+            ]),
+            Line::from(vec![label("  Last error:      "), last_error]),
+            Line::from(vec![label("  Latency:         "), latency]),
+            Line::from(vec![
+                label("  Conflicts:       "),
+                Span::styled(
+                    format!("{} awaiting review", conflict_count),
+                    Style::default().fg(if conflict_count > 0 {
+                        self.theme.warning
+                    } else {
+                        self.theme.success
+                    }),
+                ),
+            ]),
+        ];
+
+        if self.sync.show_conflicts {
+            if self.sync.conflicts.is_empty() {
+                lines.push(Line::from(label("    No conflicts")));
+            }
+            for conflict in self.sync.conflicts {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!(
+                            "    {}  ",
+                            short_id(&conflict.local.id, self.sync.id_length)
+                        ),
+                        Style::default().fg(self.theme.muted),
+                    ),
+                    Span::raw(conflict.local.display_title()),
+                ]));
+            }
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "s: sync now  p: ping  c: show conflicts  v: review conflicts",
+            Style::default().fg(self.theme.muted),
+        )));
+        lines
     }
 }
@@ -0,0 +1,124 @@
+use chrono::{Duration, Local, Timelike};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+use todoee_core::timeblock::SLOT_MINUTES;
+
+use crate::tui::theme::Theme;
+use crate::tui::timeblock::Planner;
+
+/// Timeline of `todoee timeblock`, one row per slot
+pub struct TimeblockWidget<'a> {
+    planner: &'a Planner,
+    theme: &'a Theme,
+}
+
+impl<'a> TimeblockWidget<'a> {
+    pub fn new(planner: &'a Planner, theme: &'a Theme) -> Self {
+        Self { planner, theme }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let planner = self.planner;
+        let slot = Duration::minutes(SLOT_MINUTES);
+        let outer = Block::default()
+            .title(format!(
+                " Time blocks for {} ",
+                planner.from.with_timezone(&Local).format("%a, %b %d")
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.border_focused));
+        let inner = outer.inner(area);
+        frame.render_widget(outer, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        // Keep the selected block in view, a third of the way down
+        let rows = ((planner.until - planner.from).num_minutes() / SLOT_MINUTES).max(0) as usize;
+        let height = chunks[0].height as usize;
+        let selected_row = planner
+            .blocks
+            .get(planner.selected)
+            .map(|b| ((b.start - planner.from).num_minutes() / SLOT_MINUTES).max(0) as usize)
+            .unwrap_or(0);
+        let offset = selected_row
+            .saturating_sub(height / 3)
+            .min(rows.saturating_sub(height));
+
+        let mut lines = Vec::new();
+        for row in offset..rows.min(offset + height) {
+            let start = planner.from + slot * row as i32;
+            let end = start + slot;
+            let local = start.with_timezone(&Local);
+            let label_style = if local.minute() == 0 {
+                Style::default().fg(self.theme.text)
+            } else {
+                Style::default().fg(self.theme.muted)
+            };
+            let mut spans = vec![
+                Span::styled(local.format("%H:%M").to_string(), label_style),
+                Span::styled(" \u{2502} ", Style::default().fg(self.theme.border)),
+            ];
+            // Titles only on the first visible row of each entry
+            let first = |entry_start| entry_start >= start || row == offset;
+
+            for (title, busy_start, busy_end) in &planner.busy {
+                if *busy_start < end && *busy_end > start {
+                    let text = if first(*busy_start) {
+                        format!("\u{2592} {}  ", title)
+                    } else {
+                        "\u{2592}  ".to_string()
+                    };
+                    spans.push(Span::styled(text, Style::default().fg(self.theme.muted)));
+                }
+            }
+            for (i, block) in planner.blocks.iter().enumerate() {
+                if !block.overlaps(start, end) {
+                    continue;
+                }
+                let mut style = if planner.conflicts(i) {
+                    Style::default().fg(self.theme.warning)
+                } else {
+                    Style::default().fg(self.theme.text)
+                };
+                if i == planner.selected {
+                    style = style
+                        .bg(self.theme.selection_bg)
+                        .add_modifier(Modifier::BOLD);
+                }
+                let text = if first(block.start) {
+                    format!(
+                        "\u{2588} {} ({}m)  ",
+                        block.title,
+                        (block.end - block.start).num_minutes()
+                    )
+                } else {
+                    "\u{2588}  ".to_string()
+                };
+                spans.push(Span::styled(text, style));
+            }
+            lines.push(Line::from(spans));
+        }
+        frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+        let footer = if planner.blocks.is_empty() {
+            " No blocks left. Enter saves no reminders, Esc cancels"
+        } else {
+            " j/k select  J/K move  +/- length  x drop  Enter save  Esc cancel"
+        };
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                footer,
+                Style::default().fg(self.theme.text_muted),
+            )),
+            chunks[1],
+        );
+    }
+}
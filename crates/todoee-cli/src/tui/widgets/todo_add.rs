@@ -1,21 +1,23 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::Span,
     widgets::{Block, Borders, Clear, Paragraph},
 };
 use todoee_core::Priority;
 
 use crate::tui::app::{AddField, AddState};
+use crate::tui::theme::Theme;
 
 pub struct TodoAddWidget<'a> {
     state: &'a AddState,
+    theme: &'a Theme,
 }
 
 impl<'a> TodoAddWidget<'a> {
-    pub fn new(state: &'a AddState) -> Self {
-        Self { state }
+    pub fn new(state: &'a AddState, theme: &'a Theme) -> Self {
+        Self { state, theme }
     }
 
     pub fn render(&self, frame: &mut Frame, area: Rect) {
@@ -24,7 +26,7 @@ impl<'a> TodoAddWidget<'a> {
         let block = Block::default()
             .title(" Add New Task (Tab: next, Shift+Tab: prev, Enter: save, Esc: cancel) ")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Green));
+            .border_style(Style::default().fg(self.theme.success));
 
         let inner = block.inner(area);
         frame.render_widget(block, area);
@@ -64,9 +66,10 @@ impl<'a> TodoAddWidget<'a> {
 
         // Priority field
         let priority_text = match self.state.priority {
-            Priority::High => "!!! High (1/2/3 to change)",
-            Priority::Medium => "!!  Medium (1/2/3 to change)",
-            Priority::Low => "!   Low (1/2/3 to change)",
+            Priority::Urgent => "!!!! Urgent (1-4 to change)",
+            Priority::High => "!!!  High (1-4 to change)",
+            Priority::Medium => "!!   Medium (1-4 to change)",
+            Priority::Low => "!    Low (1-4 to change)",
         };
         self.render_field(
             frame,
@@ -82,7 +85,7 @@ impl<'a> TodoAddWidget<'a> {
             .state
             .due_date
             .as_deref()
-            .unwrap_or("(YYYY-MM-DD or 'today', 'tomorrow', '+3d')");
+            .unwrap_or("(YYYY-MM-DD [HH:MM], 'tomorrow 3pm', 'next friday', 'end of month')");
         self.render_field(
             frame,
             chunks[3],
@@ -97,7 +100,7 @@ impl<'a> TodoAddWidget<'a> {
             .state
             .reminder
             .as_deref()
-            .unwrap_or("(YYYY-MM-DD HH:MM)");
+            .unwrap_or("(YYYY-MM-DD HH:MM, 'tomorrow 3pm', 'in 2 hours')");
         self.render_field(
             frame,
             chunks[4],
@@ -132,20 +135,24 @@ impl<'a> TodoAddWidget<'a> {
         active: bool,
         _required: bool,
     ) {
-        let border_color = if active { Color::Cyan } else { Color::DarkGray };
+        let border_color = if active {
+            self.theme.primary
+        } else {
+            self.theme.muted
+        };
 
         let label_style = if active {
             Style::default()
-                .fg(Color::Yellow)
+                .fg(self.theme.warning)
                 .add_modifier(Modifier::BOLD)
         } else {
             Style::default()
         };
 
         let text_style = if active {
-            Style::default().fg(Color::White)
+            Style::default().fg(self.theme.text)
         } else {
-            Style::default().fg(Color::DarkGray)
+            Style::default().fg(self.theme.muted)
         };
 
         let cursor = if active { "|" } else { "" };
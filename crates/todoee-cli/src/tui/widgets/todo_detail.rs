@@ -1,42 +1,54 @@
-use chrono::Utc;
+use chrono::{Local, Utc};
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
-use todoee_core::{Priority, Todo};
+use todoee_core::{Attachment, Priority, Todo, TodoNote, dates};
+
+use crate::tui::theme::Theme;
 
 pub struct TodoDetailWidget<'a> {
     todo: &'a Todo,
+    notes: &'a [TodoNote],
+    attachments: &'a [Attachment],
+    theme: &'a Theme,
 }
 
 impl<'a> TodoDetailWidget<'a> {
-    pub fn new(todo: &'a Todo) -> Self {
-        Self { todo }
+    pub fn new(
+        todo: &'a Todo,
+        notes: &'a [TodoNote],
+        attachments: &'a [Attachment],
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            todo,
+            notes,
+            attachments,
+            theme,
+        }
     }
 
     pub fn render(&self, frame: &mut Frame, area: Rect) {
         // Clear background
         frame.render_widget(Clear, area);
 
-        let priority_color = match self.todo.priority {
-            Priority::High => Color::Red,
-            Priority::Medium => Color::Yellow,
-            Priority::Low => Color::Green,
-        };
+        let priority_color = self.theme.priority(self.todo.priority);
 
         let priority_text = match self.todo.priority {
+            Priority::Urgent => "Urgent",
             Priority::High => "High",
             Priority::Medium => "Medium",
             Priority::Low => "Low",
         };
 
         let status = if self.todo.is_completed {
-            Span::styled("Completed", Style::default().fg(Color::Green))
+            Span::styled("Completed", Style::default().fg(self.theme.success))
         } else {
-            Span::styled("Pending", Style::default().fg(Color::Yellow))
+            Span::styled("Pending", Style::default().fg(self.theme.warning))
         };
 
         let now = Utc::now();
@@ -44,9 +56,12 @@ impl<'a> TodoDetailWidget<'a> {
             let days = (due.date_naive() - now.date_naive()).num_days();
             match days {
                 d if d < 0 => format!("OVERDUE by {} days", -d),
-                0 => "Due TODAY".to_string(),
+                0 => match dates::due_time(&due, &Local) {
+                    Some(time) => format!("Due TODAY at {}", time.format("%H:%M")),
+                    None => "Due TODAY".to_string(),
+                },
                 1 => "Due tomorrow".to_string(),
-                d => format!("Due in {} days ({})", d, due.format("%Y-%m-%d")),
+                d => format!("Due in {} days ({})", d, dates::format_due(&due, &Local)),
             }
         } else {
             "No due date".to_string()
@@ -67,10 +82,10 @@ impl<'a> TodoDetailWidget<'a> {
         let created = format!("Created: {}", self.todo.created_at.format("%Y-%m-%d %H:%M"));
         let updated = format!("Updated: {}", self.todo.updated_at.format("%Y-%m-%d %H:%M"));
 
-        let content = vec![
+        let mut content = vec![
             Line::from(vec![
                 Span::styled("Title: ", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(&self.todo.title),
+                Span::raw(self.todo.display_title()),
             ]),
             Line::from(""),
             Line::from(vec![
@@ -91,44 +106,91 @@ impl<'a> TodoDetailWidget<'a> {
             Line::from(vec![Span::styled(
                 &due_text,
                 Style::default().fg(if self.todo.due_date.is_some() {
-                    Color::Cyan
+                    self.theme.primary
                 } else {
-                    Color::DarkGray
+                    self.theme.muted
                 }),
             )]),
             Line::from(vec![Span::styled(
                 &reminder_text,
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(self.theme.muted),
             )]),
             Line::from(vec![Span::styled(
                 &category_text,
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(self.theme.muted),
             )]),
             Line::from(""),
             Line::from(vec![Span::styled(
                 &created,
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(self.theme.muted),
             )]),
             Line::from(vec![Span::styled(
                 &updated,
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(self.theme.muted),
             )]),
             Line::from(""),
             Line::from(vec![
-                Span::styled("ID: ", Style::default().fg(Color::DarkGray)),
+                Span::styled("ID: ", Style::default().fg(self.theme.muted)),
                 Span::styled(
                     self.todo.id.to_string(),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(self.theme.muted),
                 ),
             ]),
         ];
 
+        if !self.attachments.is_empty() {
+            content.push(Line::from(""));
+            content.push(Line::from(vec![
+                Span::styled(
+                    format!("Attachments ({}): ", self.attachments.len()),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("o or 1-9 to open", Style::default().fg(self.theme.muted)),
+            ]));
+            for (i, attachment) in self.attachments.iter().enumerate() {
+                content.push(Line::from(vec![
+                    Span::styled(
+                        format!("{}. ", i + 1),
+                        Style::default().fg(self.theme.muted),
+                    ),
+                    Span::styled(
+                        attachment.target.as_str(),
+                        Style::default().fg(if attachment.is_url() {
+                            self.theme.secondary
+                        } else {
+                            self.theme.primary
+                        }),
+                    ),
+                ]));
+            }
+        }
+
+        if !self.notes.is_empty() {
+            content.push(Line::from(""));
+            content.push(Line::from(Span::styled(
+                format!("Notes ({}):", self.notes.len()),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            for note in self.notes {
+                content.push(Line::from(vec![
+                    Span::styled(
+                        note.created_at
+                            .with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M  ")
+                            .to_string(),
+                        Style::default().fg(self.theme.muted),
+                    ),
+                    Span::raw(note.body.replace('\n', " ")),
+                ]));
+            }
+        }
+
         let paragraph = Paragraph::new(content)
             .block(
                 Block::default()
                     .title(" Todo Details ")
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Cyan)),
+                    .border_style(Style::default().fg(self.theme.border_focused)),
             )
             .wrap(Wrap { trim: false });
 
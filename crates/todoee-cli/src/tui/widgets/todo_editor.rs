@@ -1,20 +1,22 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::Style,
     widgets::{Block, Borders, Clear, Paragraph},
 };
 use todoee_core::Priority;
 
 use crate::tui::app::{EditField, EditState};
+use crate::tui::theme::Theme;
 
 pub struct TodoEditorWidget<'a> {
     state: &'a EditState,
+    theme: &'a Theme,
 }
 
 impl<'a> TodoEditorWidget<'a> {
-    pub fn new(state: &'a EditState) -> Self {
-        Self { state }
+    pub fn new(state: &'a EditState, theme: &'a Theme) -> Self {
+        Self { state, theme }
     }
 
     pub fn render(&self, frame: &mut Frame, area: Rect) {
@@ -23,7 +25,7 @@ impl<'a> TodoEditorWidget<'a> {
         let block = Block::default()
             .title(" Edit Todo (Tab: next, Shift+Tab: prev, Enter: save, Esc: cancel) ")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan));
+            .border_style(Style::default().fg(self.theme.border_focused));
 
         let inner = block.inner(area);
         frame.render_widget(block, area);
@@ -60,9 +62,10 @@ impl<'a> TodoEditorWidget<'a> {
 
         // Priority field
         let priority_text = match self.state.priority {
-            Priority::High => "High (press 1/2/3 to change)",
-            Priority::Medium => "Medium (press 1/2/3 to change)",
-            Priority::Low => "Low (press 1/2/3 to change)",
+            Priority::Urgent => "Urgent (press 1-4 to change)",
+            Priority::High => "High (press 1-4 to change)",
+            Priority::Medium => "Medium (press 1-4 to change)",
+            Priority::Low => "Low (press 1-4 to change)",
         };
         self.render_field(
             frame,
@@ -77,7 +80,7 @@ impl<'a> TodoEditorWidget<'a> {
             .state
             .due_date
             .as_deref()
-            .unwrap_or("(none - type YYYY-MM-DD)");
+            .unwrap_or("(none - YYYY-MM-DD [HH:MM], 'friday 3pm', 'in 2 hours')");
         self.render_field(
             frame,
             chunks[3],
@@ -103,15 +106,15 @@ impl<'a> TodoEditorWidget<'a> {
 
     fn render_field(&self, frame: &mut Frame, area: Rect, label: &str, value: &str, active: bool) {
         let border_style = if active {
-            Style::default().fg(Color::Cyan)
+            Style::default().fg(self.theme.primary)
         } else {
-            Style::default().fg(Color::DarkGray)
+            Style::default().fg(self.theme.muted)
         };
 
         let text_style = if active {
-            Style::default().fg(Color::White)
+            Style::default().fg(self.theme.text)
         } else {
-            Style::default().fg(Color::DarkGray)
+            Style::default().fg(self.theme.muted)
         };
 
         let cursor = if active { "|" } else { "" };
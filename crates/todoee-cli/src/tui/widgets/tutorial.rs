@@ -0,0 +1,89 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::tui::theme::Theme;
+use crate::tui::tutorial::{Step, TutorialState};
+
+/// Checklist beside the todo list in `todoee tutorial`
+pub struct TutorialWidget<'a> {
+    state: &'a TutorialState,
+    theme: &'a Theme,
+}
+
+impl<'a> TutorialWidget<'a> {
+    pub fn new(state: &'a TutorialState, theme: &'a Theme) -> Self {
+        Self { state, theme }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        // Hints are wrapped by hand to keep them indented under the step
+        let hint_width = (area.width as usize).saturating_sub(8).max(10);
+        let mut lines = vec![Line::from("")];
+        for (i, step) in Step::ALL.iter().enumerate() {
+            let (mark, style) = if i < self.state.step {
+                ("[x]", Style::default().fg(self.theme.success))
+            } else if i == self.state.step {
+                (
+                    "[ ]",
+                    Style::default()
+                        .fg(self.theme.primary)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("[ ]", Style::default().fg(self.theme.muted))
+            };
+            lines.push(Line::from(Span::styled(
+                format!(" {} {}. {}", mark, i + 1, step.title()),
+                style,
+            )));
+            if i == self.state.step {
+                for row in wrap(step.hint(), hint_width) {
+                    lines.push(Line::from(Span::styled(
+                        format!("     {}", row),
+                        Style::default().fg(self.theme.text),
+                    )));
+                }
+                lines.push(Line::from(""));
+            }
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            if self.state.is_finished() {
+                " All done! Press q to leave."
+            } else {
+                " ? lists every key, q quits."
+            },
+            Style::default().fg(self.theme.warning),
+        )));
+
+        let done = self.state.step.min(Step::ALL.len());
+        let panel = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .title(format!(" Tutorial ({}/{}) ", done, Step::ALL.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.border_focused)),
+        );
+        frame.render_widget(panel, area);
+    }
+}
+
+/// Split `text` into rows of at most `width` characters at spaces
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut rows: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match rows.last_mut() {
+            Some(row) if row.chars().count() + 1 + word.chars().count() <= width => {
+                row.push(' ');
+                row.push_str(word);
+            }
+            _ => rows.push(word.to_string()),
+        }
+    }
+    rows
+}
@@ -0,0 +1,90 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Wrap},
+};
+use todoee_core::Todo;
+
+use crate::tui::theme::Theme;
+
+/// Full-screen view of the few tasks that matter now: titles only, centered
+pub struct ZenWidget<'a> {
+    todos: &'a [&'a Todo],
+    theme: &'a Theme,
+}
+
+impl<'a> ZenWidget<'a> {
+    pub fn new(todos: &'a [&'a Todo], theme: &'a Theme) -> Self {
+        Self { todos, theme }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let mut lines = Vec::new();
+        if self.todos.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "Nothing left to do.",
+                Style::default()
+                    .fg(self.theme.success)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+        for (i, todo) in self.todos.iter().enumerate() {
+            if i > 0 {
+                lines.extend([Line::from(""), Line::from("")]);
+            }
+            // The first task is the one to do now; the others wait their turn
+            let style = if i == 0 {
+                Style::default()
+                    .fg(self.theme.primary)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.text_muted)
+            };
+            lines.push(Line::from(Span::styled(
+                format!("{}.  {}", i + 1, todo.title),
+                style,
+            )));
+        }
+
+        let width = area.width.saturating_sub(8).min(72);
+        // Long titles wrap, so count the rows they will take
+        let rows_needed: usize = lines
+            .iter()
+            .map(|line| line.width().div_ceil(width.max(1) as usize).max(1))
+            .sum();
+        let height = (rows_needed as u16).min(area.height);
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(height),
+                Constraint::Fill(1),
+                Constraint::Length(1),
+            ])
+            .split(area);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(width),
+                Constraint::Fill(1),
+            ])
+            .split(rows[1]);
+
+        let tasks = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(tasks, columns[1]);
+
+        let hint = Paragraph::new(Span::styled(
+            "N / Esc: back to the list",
+            Style::default().fg(self.theme.muted),
+        ))
+        .alignment(Alignment::Center);
+        frame.render_widget(hint, rows[3]);
+    }
+}
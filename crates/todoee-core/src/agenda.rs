@@ -0,0 +1,160 @@
+//! The daily agenda: what is due today, how much is overdue and what to
+//! start with.
+//!
+//! todoee-daemon sends it as one notification at
+//! `notifications.daily_summary_time`, and `todoee agenda` prints it.
+
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use serde::Serialize;
+
+use crate::config::RankingConfig;
+use crate::dates;
+use crate::models::Todo;
+use crate::urgency::urgency;
+
+/// Titles named in the due-today line before it trails off
+const TITLES_SHOWN: usize = 3;
+
+/// Today at a glance
+#[derive(Debug, Clone, Serialize)]
+pub struct DailySummary {
+    pub date: NaiveDate,
+    /// Pending todos due later today, soonest first
+    pub due_today: Vec<Todo>,
+    /// Pending todos whose due date has passed
+    pub overdue: usize,
+    /// The todo `todoee now` recommends first
+    pub top: Option<Todo>,
+}
+
+impl DailySummary {
+    /// Summarize `todos` for the day `now` falls on in `tz`. Completed todos
+    /// are left out; date-only due dates are overdue once their day is over.
+    pub fn new<Tz: TimeZone>(
+        todos: &[Todo],
+        ranking: &RankingConfig,
+        now: DateTime<Utc>,
+        tz: &Tz,
+    ) -> Self {
+        let date = now.with_timezone(tz).date_naive();
+        let pending: Vec<&Todo> = todos.iter().filter(|t| !t.is_completed).collect();
+
+        let mut due_today = Vec::new();
+        let mut overdue = 0;
+        for todo in &pending {
+            let Some(due) = todo.due_date else {
+                continue;
+            };
+            let day = if dates::is_date_only(&due) {
+                due.date_naive()
+            } else {
+                due.with_timezone(tz).date_naive()
+            };
+            if day < date || (day == date && !dates::is_date_only(&due) && due < now) {
+                overdue += 1;
+            } else if day == date {
+                due_today.push((*todo).clone());
+            }
+        }
+        due_today.sort_by_key(|t| t.due_date);
+
+        // The first of the highest, as `todoee now` ranks them
+        let mut top: Option<(&Todo, f64)> = None;
+        for todo in pending {
+            let score = urgency(todo, ranking, now);
+            if top.is_none_or(|(_, best)| score > best) {
+                top = Some((todo, score));
+            }
+        }
+
+        Self {
+            date,
+            due_today,
+            overdue,
+            top: top.map(|(todo, _)| todo.clone()),
+        }
+    }
+
+    /// The summary as lines of text, e.g. "3 due today: A, B, C",
+    /// "2 overdue", "Start with: A"
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        let titles: Vec<String> = self
+            .due_today
+            .iter()
+            .take(TITLES_SHOWN)
+            .map(|t| t.display_title())
+            .collect();
+        lines.push(match self.due_today.len() {
+            0 => "Nothing due today".to_string(),
+            n => format!(
+                "{} due today: {}{}",
+                n,
+                titles.join(", "),
+                if n > TITLES_SHOWN { "\u{2026}" } else { "" }
+            ),
+        });
+        if self.overdue > 0 {
+            lines.push(format!("{} overdue", self.overdue));
+        }
+        if let Some(top) = &self.top {
+            lines.push(format!("Start with: {}", top.display_title()));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveTime};
+
+    use crate::models::Priority;
+
+    #[test]
+    fn test_summary_of_today() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 8, 30, 0).unwrap();
+        let todo = |title: &str, due: Option<DateTime<Utc>>, priority| {
+            let mut todo = Todo::new(title.to_string(), None);
+            todo.due_date = due;
+            todo.priority = priority;
+            todo
+        };
+        let today = dates::with_due_time(&now, None, &Utc);
+        let mut done = todo("Done", Some(now + Duration::hours(2)), Priority::Urgent);
+        done.mark_complete();
+        let todos = vec![
+            todo("Standup", Some(now + Duration::hours(1)), Priority::Medium),
+            todo("Pay rent", Some(today), Priority::Low),
+            todo("Taxes", Some(now - Duration::days(2)), Priority::High),
+            todo("Early call", Some(now - Duration::hours(1)), Priority::Low),
+            todo("Later", Some(now + Duration::days(3)), Priority::Low),
+            todo("Someday", None, Priority::Low),
+            done,
+        ];
+
+        let summary = DailySummary::new(&todos, &RankingConfig::default(), now, &Utc);
+        assert_eq!(summary.date, now.date_naive());
+        let titles: Vec<&str> = summary.due_today.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, ["Standup", "Pay rent"]);
+        assert_eq!(summary.overdue, 2);
+        assert_eq!(
+            summary.lines(),
+            [
+                "2 due today: Standup, Pay rent",
+                "2 overdue",
+                "Start with: Taxes"
+            ]
+        );
+
+        // A date-only todo is not overdue until its day is over
+        let evening = now
+            .date_naive()
+            .and_time(NaiveTime::from_hms_opt(20, 0, 0).unwrap());
+        let summary = DailySummary::new(&todos, &RankingConfig::default(), evening.and_utc(), &Utc);
+        assert!(summary.due_today.iter().any(|t| t.title == "Pay rent"));
+
+        let empty = DailySummary::new(&[], &RankingConfig::default(), now, &Utc);
+        assert_eq!(empty.lines(), ["Nothing due today"]);
+    }
+}
@@ -1,17 +1,22 @@
-//! OpenRouter AI client for natural language task parsing
+//! AI client for natural language task parsing
 //!
-//! This module provides an AI client that communicates with OpenRouter's API
-//! to parse natural language input into structured task data.
+//! This module provides an AI client that parses natural language input into
+//! structured task data. The backend is chosen by `config.ai.provider`:
+//! OpenRouter, OpenAI, Anthropic or a local Ollama server, each implementing
+//! [`AiProvider`].
 
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use zeroize::ZeroizeOnDrop;
 
-use crate::config::Config;
+use crate::config::{AI_PROVIDERS, AiConfig, Config, NetworkConfig};
+use crate::db::LocalDb;
 use crate::error::TodoeeError;
+use crate::net;
 
 /// A parsed task extracted from natural language input
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -88,42 +93,408 @@ pub fn extract_json(text: &str) -> Option<&str> {
 }
 
 // ============================================================================
-// OpenRouter API Types
+// Providers
 // ============================================================================
 
-/// Request body for OpenRouter chat completions API
-#[derive(Debug, Serialize)]
-struct OpenRouterRequest {
-    model: String,
-    messages: Vec<Message>,
-    temperature: f32,
-    max_tokens: u32,
+/// A chat request, independent of any provider's wire format
+#[derive(Debug, Clone)]
+pub struct ChatRequest {
+    pub model: String,
+    pub system: String,
+    pub user: String,
+    pub temperature: f32,
+    pub max_tokens: u32,
+}
+
+/// An AI backend: its endpoints, authentication and wire format.
+///
+/// Providers only build requests and read responses; `AiClient` sends them,
+/// so every backend shares the same timeout and error handling.
+pub trait AiProvider: Send + Sync {
+    /// Name used in messages ("OpenRouter")
+    fn name(&self) -> &'static str;
+
+    /// Endpoint requests are sent to
+    fn base_url(&self) -> &str;
+
+    /// Build the chat completion request
+    fn chat_request(
+        &self,
+        client: &Client,
+        api_key: Option<&str>,
+        request: &ChatRequest,
+    ) -> RequestBuilder;
+
+    /// Extract the reply text from a successful chat response body
+    fn reply_text(&self, body: &str) -> Result<String, TodoeeError>;
+
+    /// A request that checks the endpoint and API key without using tokens
+    fn ping_request(&self, client: &Client, api_key: Option<&str>) -> RequestBuilder;
+}
+
+/// Create the provider selected by `config.provider`.
+///
+/// `config.base_url` overrides the provider's default endpoint, e.g. for an
+/// Ollama server on another machine.
+pub fn provider_for(config: &AiConfig) -> Result<Box<dyn AiProvider>, TodoeeError> {
+    let base_url = config.base_url.as_deref().map(|u| u.trim_end_matches('/'));
+    let base = |default: &str| base_url.unwrap_or(default).to_string();
+
+    Ok(match config.provider.as_str() {
+        "openrouter" => Box::new(OpenRouter {
+            base_url: base("https://openrouter.ai/api/v1"),
+        }),
+        "openai" => Box::new(OpenAi {
+            base_url: base("https://api.openai.com/v1"),
+        }),
+        "anthropic" => Box::new(Anthropic {
+            base_url: base("https://api.anthropic.com/v1"),
+        }),
+        "ollama" => Box::new(Ollama {
+            base_url: base("http://localhost:11434"),
+        }),
+        other => {
+            return Err(TodoeeError::AiService {
+                message: format!(
+                    "Unknown AI provider '{}'. Supported: {}",
+                    other,
+                    AI_PROVIDERS.join(", ")
+                ),
+            });
+        }
+    })
 }
 
-/// A message in the chat conversation
+/// A message in a chat conversation
 #[derive(Debug, Serialize, Deserialize)]
 struct Message {
     role: String,
     content: String,
 }
 
-/// Response from OpenRouter chat completions API
+impl Message {
+    fn new(role: &str, content: &str) -> Self {
+        Self {
+            role: role.to_string(),
+            content: content.to_string(),
+        }
+    }
+}
+
+fn parse_body<T: for<'de> Deserialize<'de>>(provider: &str, body: &str) -> Result<T, TodoeeError> {
+    serde_json::from_str(body).map_err(|e| TodoeeError::AiService {
+        message: format!("Failed to parse {} response: {}", provider, e),
+    })
+}
+
+fn no_reply() -> TodoeeError {
+    TodoeeError::AiParsing {
+        message: "No response from AI model".to_string(),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// OpenAI-compatible chat completions (OpenRouter, OpenAI)
+// ----------------------------------------------------------------------------
+
+/// Request body for OpenAI-style chat completions
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest {
+    model: String,
+    messages: Vec<Message>,
+    temperature: f32,
+    max_tokens: u32,
+}
+
+/// Response from OpenAI-style chat completions
 #[derive(Debug, Deserialize)]
-struct OpenRouterResponse {
+struct ChatCompletionResponse {
     choices: Vec<Choice>,
 }
 
-/// A choice in the OpenRouter response
+/// A choice in a chat completion response
 #[derive(Debug, Deserialize)]
 struct Choice {
     message: Message,
 }
 
+fn chat_completion_request(
+    client: &Client,
+    url: String,
+    api_key: Option<&str>,
+    request: &ChatRequest,
+) -> RequestBuilder {
+    let body = ChatCompletionRequest {
+        model: request.model.clone(),
+        messages: vec![
+            Message::new("system", &request.system),
+            Message::new("user", &request.user),
+        ],
+        temperature: request.temperature,
+        max_tokens: request.max_tokens,
+    };
+    client
+        .post(url)
+        .bearer_auth(api_key.unwrap_or_default())
+        .json(&body)
+}
+
+fn chat_completion_reply(provider: &str, body: &str) -> Result<String, TodoeeError> {
+    let response: ChatCompletionResponse = parse_body(provider, body)?;
+    response
+        .choices
+        .into_iter()
+        .next()
+        .map(|c| c.message.content)
+        .ok_or_else(no_reply)
+}
+
+/// OpenRouter: many hosted models behind one OpenAI-compatible API
+pub struct OpenRouter {
+    base_url: String,
+}
+
+impl AiProvider for OpenRouter {
+    fn name(&self) -> &'static str {
+        "OpenRouter"
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn chat_request(
+        &self,
+        client: &Client,
+        api_key: Option<&str>,
+        request: &ChatRequest,
+    ) -> RequestBuilder {
+        let url = format!("{}/chat/completions", self.base_url);
+        chat_completion_request(client, url, api_key, request)
+    }
+
+    fn reply_text(&self, body: &str) -> Result<String, TodoeeError> {
+        chat_completion_reply(self.name(), body)
+    }
+
+    fn ping_request(&self, client: &Client, api_key: Option<&str>) -> RequestBuilder {
+        // Key info endpoint, costs no tokens
+        client
+            .get(format!("{}/auth/key", self.base_url))
+            .bearer_auth(api_key.unwrap_or_default())
+    }
+}
+
+/// OpenAI's own API
+pub struct OpenAi {
+    base_url: String,
+}
+
+impl AiProvider for OpenAi {
+    fn name(&self) -> &'static str {
+        "OpenAI"
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn chat_request(
+        &self,
+        client: &Client,
+        api_key: Option<&str>,
+        request: &ChatRequest,
+    ) -> RequestBuilder {
+        let url = format!("{}/chat/completions", self.base_url);
+        chat_completion_request(client, url, api_key, request)
+    }
+
+    fn reply_text(&self, body: &str) -> Result<String, TodoeeError> {
+        chat_completion_reply(self.name(), body)
+    }
+
+    fn ping_request(&self, client: &Client, api_key: Option<&str>) -> RequestBuilder {
+        client
+            .get(format!("{}/models", self.base_url))
+            .bearer_auth(api_key.unwrap_or_default())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Anthropic Messages API
+// ----------------------------------------------------------------------------
+
+/// API version sent in the `anthropic-version` header
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Request body for the Anthropic Messages API
+#[derive(Debug, Serialize)]
+struct AnthropicRequest {
+    model: String,
+    system: String,
+    messages: Vec<Message>,
+    temperature: f32,
+    max_tokens: u32,
+}
+
+/// Response from the Anthropic Messages API
+#[derive(Debug, Deserialize)]
+struct AnthropicResponse {
+    content: Vec<AnthropicContent>,
+}
+
+/// A content block in an Anthropic response
+#[derive(Debug, Deserialize)]
+struct AnthropicContent {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    text: String,
+}
+
+/// Anthropic's Messages API: system prompt is a top-level field, key in `x-api-key`
+pub struct Anthropic {
+    base_url: String,
+}
+
+impl Anthropic {
+    fn authorized(&self, builder: RequestBuilder, api_key: Option<&str>) -> RequestBuilder {
+        builder
+            .header("x-api-key", api_key.unwrap_or_default())
+            .header("anthropic-version", ANTHROPIC_VERSION)
+    }
+}
+
+impl AiProvider for Anthropic {
+    fn name(&self) -> &'static str {
+        "Anthropic"
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn chat_request(
+        &self,
+        client: &Client,
+        api_key: Option<&str>,
+        request: &ChatRequest,
+    ) -> RequestBuilder {
+        let body = AnthropicRequest {
+            model: request.model.clone(),
+            system: request.system.clone(),
+            messages: vec![Message::new("user", &request.user)],
+            temperature: request.temperature,
+            max_tokens: request.max_tokens,
+        };
+        let builder = client.post(format!("{}/messages", self.base_url));
+        self.authorized(builder, api_key).json(&body)
+    }
+
+    fn reply_text(&self, body: &str) -> Result<String, TodoeeError> {
+        let response: AnthropicResponse = parse_body(self.name(), body)?;
+        response
+            .content
+            .into_iter()
+            .find(|c| c.kind == "text")
+            .map(|c| c.text)
+            .ok_or_else(no_reply)
+    }
+
+    fn ping_request(&self, client: &Client, api_key: Option<&str>) -> RequestBuilder {
+        let builder = client.get(format!("{}/models", self.base_url));
+        self.authorized(builder, api_key)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Ollama (local)
+// ----------------------------------------------------------------------------
+
+/// Request body for Ollama's chat API
+#[derive(Debug, Serialize)]
+struct OllamaRequest {
+    model: String,
+    messages: Vec<Message>,
+    stream: bool,
+    options: OllamaOptions,
+}
+
+/// Sampling options for Ollama
+#[derive(Debug, Serialize)]
+struct OllamaOptions {
+    temperature: f32,
+    num_predict: u32,
+}
+
+/// Response from Ollama's chat API (non-streaming)
+#[derive(Debug, Deserialize)]
+struct OllamaResponse {
+    message: Message,
+}
+
+/// A local Ollama server. No API key; one is sent as a bearer token if set,
+/// for servers behind an authenticating proxy.
+pub struct Ollama {
+    base_url: String,
+}
+
+impl AiProvider for Ollama {
+    fn name(&self) -> &'static str {
+        "Ollama"
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn chat_request(
+        &self,
+        client: &Client,
+        api_key: Option<&str>,
+        request: &ChatRequest,
+    ) -> RequestBuilder {
+        let body = OllamaRequest {
+            model: request.model.clone(),
+            messages: vec![
+                Message::new("system", &request.system),
+                Message::new("user", &request.user),
+            ],
+            stream: false,
+            options: OllamaOptions {
+                temperature: request.temperature,
+                num_predict: request.max_tokens,
+            },
+        };
+        let builder = client
+            .post(format!("{}/api/chat", self.base_url))
+            .json(&body);
+        match api_key {
+            Some(key) => builder.bearer_auth(key),
+            None => builder,
+        }
+    }
+
+    fn reply_text(&self, body: &str) -> Result<String, TodoeeError> {
+        let response: OllamaResponse = parse_body(self.name(), body)?;
+        Ok(response.message.content)
+    }
+
+    fn ping_request(&self, client: &Client, api_key: Option<&str>) -> RequestBuilder {
+        // Lists installed models; fails fast if the server isn't running
+        let builder = client.get(format!("{}/api/tags", self.base_url));
+        match api_key {
+            Some(key) => builder.bearer_auth(key),
+            None => builder,
+        }
+    }
+}
+
 // ============================================================================
 // AI Client
 // ============================================================================
 
-/// Client for communicating with OpenRouter AI API
+/// Client for parsing tasks with the configured AI provider
 ///
 /// The api_key field is automatically zeroed when the struct is dropped
 /// to prevent sensitive data from remaining in memory.
@@ -131,9 +502,15 @@ struct Choice {
 pub struct AiClient {
     #[zeroize(skip)]
     client: Client,
-    api_key: String,
+    api_key: Option<String>,
     #[zeroize(skip)]
     model: String,
+    #[zeroize(skip)]
+    provider: Box<dyn AiProvider>,
+    #[zeroize(skip)]
+    cache_hours: u32,
+    #[zeroize(skip)]
+    network: NetworkConfig,
 }
 
 impl AiClient {
@@ -142,14 +519,21 @@ impl AiClient {
     /// # Errors
     ///
     /// Returns `TodoeeError::AiService` if:
-    /// - The API key environment variable is not set
+    /// - The provider is not supported
+    /// - The provider needs an API key and its environment variable is not set
     /// - The AI model is not configured
     pub fn new(config: &Config) -> Result<Self, TodoeeError> {
-        let api_key = config
-            .get_ai_api_key()
-            .map_err(|e| TodoeeError::AiService {
-                message: format!("Failed to get AI API key: {}", e),
-            })?;
+        let provider = provider_for(&config.ai)?;
+
+        let api_key = match config.get_ai_api_key() {
+            Ok(key) => Some(key),
+            Err(_) if !config.ai.needs_api_key() => None,
+            Err(e) => {
+                return Err(TodoeeError::AiService {
+                    message: format!("Failed to get AI API key: {}", e),
+                });
+            }
+        };
 
         let model = config
             .ai
@@ -160,17 +544,52 @@ impl AiClient {
             })?;
 
         Ok(Self {
-            client: Client::builder()
-                .timeout(Duration::from_secs(30))
-                .build()
-                .map_err(|e| TodoeeError::AiService {
-                    message: format!("Failed to build HTTP client: {}", e),
-                })?,
+            client: net::http_client(&config.network, Duration::from_secs(30))?,
             api_key,
             model,
+            provider,
+            cache_hours: config.ai.cache_hours,
+            network: config.network.clone(),
         })
     }
 
+    /// Fail fast with `TodoeeError::Network` if the provider can't be reached
+    pub async fn check_online(&self) -> Result<(), TodoeeError> {
+        net::check_url_reachable(&self.network, self.provider.base_url()).await
+    }
+
+    /// Check that the provider is reachable and accepts the configured API key
+    ///
+    /// Uses a metadata endpoint, which costs no tokens.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TodoeeError::AiService` if the request fails or the key is rejected
+    pub async fn ping(&self) -> Result<(), TodoeeError> {
+        self.check_online().await?;
+        let name = self.provider.name();
+        let response = self
+            .provider
+            .ping_request(&self.client, self.api_key.as_deref())
+            .send()
+            .await
+            .map_err(|e| {
+                net::tls_error(&e).unwrap_or_else(|| TodoeeError::AiService {
+                    message: format!("Failed to reach {}: {}", name, net::error_chain(&e)),
+                })
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(TodoeeError::AiService {
+                message: format!("{} rejected the API key ({}): {}", name, status, body),
+            });
+        }
+
+        Ok(())
+    }
+
     /// Parse natural language input into a structured task
     ///
     /// # Arguments
@@ -186,6 +605,7 @@ impl AiClient {
     /// Returns `TodoeeError::AiService` if the API request fails
     /// Returns `TodoeeError::AiParsing` if the response cannot be parsed
     pub async fn parse_task(&self, input: &str) -> Result<ParsedTask, TodoeeError> {
+        self.check_online().await?;
         let current_date = Utc::now().format("%Y-%m-%d").to_string();
 
         let system_prompt = format!(
@@ -214,57 +634,100 @@ Respond with ONLY the JSON object, no additional text."#,
             current_date
         );
 
-        let request = OpenRouterRequest {
+        let request = ChatRequest {
             model: self.model.clone(),
-            messages: vec![
-                Message {
-                    role: "system".to_string(),
-                    content: system_prompt,
-                },
-                Message {
-                    role: "user".to_string(),
-                    content: input.to_string(),
-                },
-            ],
+            system: system_prompt,
+            user: input.to_string(),
             temperature: 0.1,
             max_tokens: 500,
         };
 
+        let name = self.provider.name();
         let response = self
-            .client
-            .post("https://openrouter.ai/api/v1/chat/completions")
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
+            .provider
+            .chat_request(&self.client, self.api_key.as_deref(), &request)
             .send()
             .await
-            .map_err(|e| TodoeeError::AiService {
-                message: format!("Failed to send request to OpenRouter: {}", e),
+            .map_err(|e| {
+                net::tls_error(&e).unwrap_or_else(|| TodoeeError::AiService {
+                    message: format!(
+                        "Failed to send request to {}: {}",
+                        name,
+                        net::error_chain(&e)
+                    ),
+                })
             })?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
             return Err(TodoeeError::AiService {
-                message: format!("OpenRouter API error ({}): {}", status, body),
+                message: format!("{} API error ({}): {}", name, status, body),
             });
         }
 
-        let api_response: OpenRouterResponse =
-            response.json().await.map_err(|e| TodoeeError::AiService {
-                message: format!("Failed to parse OpenRouter response: {}", e),
-            })?;
+        let content = self.provider.reply_text(&body)?;
+        ParsedTask::from_json(&content)
+    }
 
-        let content = api_response
-            .choices
-            .first()
-            .map(|c| c.message.content.as_str())
-            .ok_or_else(|| TodoeeError::AiParsing {
-                message: "No response from AI model".to_string(),
-            })?;
+    /// Parse a task, reusing a cached result for identical input
+    ///
+    /// Results are kept in `db` for `ai.cache_hours`. The key includes the
+    /// provider, model and today's date, so relative dates such as "tomorrow"
+    /// are never reused across days. Cache failures are logged and ignored.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`AiClient::parse_task`] on a cache miss
+    pub async fn parse_task_cached(
+        &self,
+        db: &LocalDb,
+        input: &str,
+    ) -> Result<ParsedTask, TodoeeError> {
+        if self.cache_hours == 0 {
+            return self.parse_task(input).await;
+        }
+
+        let key = cache_key(
+            self.provider.name(),
+            &self.model,
+            &Utc::now().format("%Y-%m-%d").to_string(),
+            input,
+        );
+        match db.get_ai_cache(&key).await {
+            Ok(Some(json)) => match serde_json::from_str(&json) {
+                Ok(parsed) => return Ok(parsed),
+                Err(e) => tracing::warn!("Ignoring unreadable AI cache entry: {}", e),
+            },
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to read AI cache: {:#}", e),
+        }
+
+        let parsed = self.parse_task(input).await?;
+        if let Ok(json) = serde_json::to_string(&parsed) {
+            let ttl = chrono::Duration::hours(self.cache_hours as i64);
+            if let Err(e) = db.put_ai_cache(&key, &json, ttl).await {
+                tracing::warn!("Failed to write AI cache: {:#}", e);
+            }
+        }
+        Ok(parsed)
+    }
+}
 
-        ParsedTask::from_json(content)
+/// Cache key for an AI parse: a SHA-256 over the provider, model, date and
+/// the input with case and whitespace normalized
+fn cache_key(provider: &str, model: &str, date: &str, input: &str) -> String {
+    let normalized = input
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    let mut hasher = Sha256::new();
+    for part in [provider, model, date, &normalized] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
     }
+    format!("{:x}", hasher.finalize())
 }
 
 // ============================================================================
@@ -389,6 +852,158 @@ I hope this helps!"#;
         assert!(extract_json(text).is_none());
     }
 
+    fn ai_config(provider: &str) -> AiConfig {
+        AiConfig {
+            provider: provider.to_string(),
+            ..AiConfig::default()
+        }
+    }
+
+    fn chat_request() -> ChatRequest {
+        ChatRequest {
+            model: "test-model".to_string(),
+            system: "Be brief".to_string(),
+            user: "Buy milk".to_string(),
+            temperature: 0.1,
+            max_tokens: 500,
+        }
+    }
+
+    /// Build a provider's chat request and return its URL, headers and JSON body
+    fn built_request(
+        provider: &str,
+        api_key: Option<&str>,
+    ) -> (String, reqwest::header::HeaderMap, serde_json::Value) {
+        let provider = provider_for(&ai_config(provider)).unwrap();
+        let request = provider
+            .chat_request(&Client::new(), api_key, &chat_request())
+            .build()
+            .unwrap();
+        let body = request.body().and_then(|b| b.as_bytes()).unwrap();
+        (
+            request.url().to_string(),
+            request.headers().clone(),
+            serde_json::from_slice(body).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_provider_for_unknown_provider() {
+        assert!(provider_for(&ai_config("skynet")).is_err());
+        for name in AI_PROVIDERS {
+            assert!(provider_for(&ai_config(name)).is_ok(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_openai_compatible_requests() {
+        let (url, headers, body) = built_request("openrouter", Some("sk-or"));
+        assert_eq!(url, "https://openrouter.ai/api/v1/chat/completions");
+        assert_eq!(headers["authorization"], "Bearer sk-or");
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][1]["content"], "Buy milk");
+
+        let (url, _, body) = built_request("openai", Some("sk"));
+        assert_eq!(url, "https://api.openai.com/v1/chat/completions");
+        assert_eq!(body["model"], "test-model");
+
+        let reply = r#"{"choices": [{"message": {"role": "assistant", "content": "{}"}}]}"#;
+        let provider = provider_for(&ai_config("openai")).unwrap();
+        assert_eq!(provider.reply_text(reply).unwrap(), "{}");
+        assert!(provider.reply_text(r#"{"choices": []}"#).is_err());
+    }
+
+    #[test]
+    fn test_anthropic_request_and_reply() {
+        let (url, headers, body) = built_request("anthropic", Some("sk-ant"));
+        assert_eq!(url, "https://api.anthropic.com/v1/messages");
+        assert_eq!(headers["x-api-key"], "sk-ant");
+        assert_eq!(headers["anthropic-version"], ANTHROPIC_VERSION);
+        assert!(!headers.contains_key("authorization"));
+        assert_eq!(body["system"], "Be brief");
+        assert_eq!(body["messages"].as_array().unwrap().len(), 1);
+
+        let reply = r#"{"content": [{"type": "text", "text": "Buy milk"}]}"#;
+        let provider = provider_for(&ai_config("anthropic")).unwrap();
+        assert_eq!(provider.reply_text(reply).unwrap(), "Buy milk");
+    }
+
+    #[test]
+    fn test_ollama_request_and_reply() {
+        let (url, headers, body) = built_request("ollama", None);
+        assert_eq!(url, "http://localhost:11434/api/chat");
+        assert!(!headers.contains_key("authorization"));
+        assert_eq!(body["stream"], false);
+        assert_eq!(body["options"]["num_predict"], 500);
+
+        let config = AiConfig {
+            base_url: Some("http://gpu-box:11434/".to_string()),
+            ..ai_config("ollama")
+        };
+        let request = provider_for(&config)
+            .unwrap()
+            .ping_request(&Client::new(), None)
+            .build()
+            .unwrap();
+        assert_eq!(request.url().as_str(), "http://gpu-box:11434/api/tags");
+
+        let reply = r#"{"message": {"role": "assistant", "content": "{}"}, "done": true}"#;
+        let provider = provider_for(&ai_config("ollama")).unwrap();
+        assert_eq!(provider.reply_text(reply).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_cache_key_normalizes_input() {
+        let key = cache_key("OpenRouter", "m", "2026-01-14", "Buy  milk\ttomorrow");
+        assert_eq!(
+            key,
+            cache_key("OpenRouter", "m", "2026-01-14", " buy milk TOMORROW ")
+        );
+        assert_ne!(
+            key,
+            cache_key("OpenRouter", "m", "2026-01-15", "buy milk tomorrow")
+        );
+        assert_ne!(
+            key,
+            cache_key("OpenAI", "m", "2026-01-14", "buy milk tomorrow")
+        );
+        assert_eq!(key.len(), 64);
+    }
+
+    #[tokio::test]
+    async fn test_parse_task_cached_uses_cache() {
+        let mut config = Config::default();
+        config.ai.provider = "ollama".to_string();
+        config.ai.model = Some("llama3.2".to_string());
+        // Nothing listens here, so only a cache hit can succeed
+        config.ai.base_url = Some("http://127.0.0.1:9".to_string());
+        let client = AiClient::new(&config).unwrap();
+
+        let db = LocalDb::new_in_memory().await.unwrap();
+        db.run_migrations().await.unwrap();
+        assert!(client.parse_task_cached(&db, "buy milk").await.is_err());
+
+        let key = cache_key(
+            "Ollama",
+            "llama3.2",
+            &Utc::now().format("%Y-%m-%d").to_string(),
+            "buy milk",
+        );
+        let cached = r#"{"title": "Buy milk", "priority": 3}"#;
+        db.put_ai_cache(&key, cached, chrono::Duration::hours(1))
+            .await
+            .unwrap();
+
+        let parsed = client.parse_task_cached(&db, "Buy milk").await.unwrap();
+        assert_eq!(parsed.title, "Buy milk");
+        assert_eq!(parsed.priority, Some(3));
+
+        // A zero TTL bypasses the cache
+        config.ai.cache_hours = 0;
+        let client = AiClient::new(&config).unwrap();
+        assert!(client.parse_task_cached(&db, "buy milk").await.is_err());
+    }
+
     #[test]
     fn test_parsed_task_with_null_values() {
         let json = r#"{
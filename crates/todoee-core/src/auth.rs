@@ -1 +1,272 @@
-// Placeholder for authentication module
+//! Sync identity stored in `auth.json`.
+//!
+//! `todoee login` writes it: the user this device syncs as and the session
+//! token that proves it. `todoee config --init` links a user without a
+//! session, as before accounts had passwords. The database URL stays in the
+//! environment.
+//!
+//! It also holds the bearer token for `todoee serve`, created on first use.
+
+use std::fs;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use argon2::Argon2;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::models::User;
+
+/// Key of the `todoee serve` token in `auth.json`
+const API_TOKEN_KEY: &str = "api_token";
+
+/// Random bytes in an API or session token
+const TOKEN_BYTES: usize = 32;
+
+/// Shortest password a new account may have
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+/// The remote user this device is associated with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Auth {
+    pub user_id: Uuid,
+    pub email: String,
+    /// When this device was associated with the user
+    pub linked_at: DateTime<Utc>,
+    /// Set by `todoee login`. Sync then only exchanges this user's data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_token: Option<String>,
+}
+
+impl Auth {
+    pub fn new(user: &User) -> Self {
+        Self {
+            user_id: user.id,
+            email: user.email.clone(),
+            linked_at: Utc::now(),
+            session_token: None,
+        }
+    }
+
+    /// Signed in to `user` with `session_token`
+    pub fn with_session(user: &User, session_token: String) -> Self {
+        Self {
+            session_token: Some(session_token),
+            ..Self::new(user)
+        }
+    }
+
+    /// Read `auth.json`, or `None` if sync was never set up.
+    pub fn load() -> Result<Option<Self>> {
+        Self::load_from(&Config::auth_path()?)
+    }
+
+    /// Write `auth.json` next to the config file.
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Config::auth_path()?)
+    }
+
+    /// Forget the user, keeping the `todoee serve` token.
+    pub fn clear() -> Result<()> {
+        clear_at(&Config::auth_path()?)
+    }
+
+    fn load_from(path: &Path) -> Result<Option<Self>> {
+        let fields = read_fields(path)?;
+        // Only an API token: sync was never set up
+        if !fields.contains_key("user_id") {
+            return Ok(None);
+        }
+        let auth = serde_json::from_value(Value::Object(fields))
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(Some(auth))
+    }
+
+    fn save_to(&self, path: &Path) -> Result<()> {
+        let mut fields = read_fields(path)?;
+        match serde_json::to_value(self).context("Failed to serialize auth")? {
+            Value::Object(auth) => fields.extend(auth),
+            _ => unreachable!("Auth serializes to an object"),
+        }
+        write_fields(path, &fields)
+    }
+}
+
+fn clear_at(path: &Path) -> Result<()> {
+    let mut fields = read_fields(path)?;
+    fields.retain(|key, _| key == API_TOKEN_KEY);
+    if fields.is_empty() {
+        if path.exists() {
+            fs::remove_file(path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        return Ok(());
+    }
+    write_fields(path, &fields)
+}
+
+/// A new random session token
+pub fn new_session_token() -> Result<String> {
+    random_token()
+}
+
+/// What the remote stores of a session token, so reading the database doesn't
+/// reveal live tokens
+pub fn token_hash(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Argon2 hash of `password`, with a random salt
+pub fn hash_password(password: &str) -> Result<String> {
+    let mut salt = [0u8; 16];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| anyhow::anyhow!("Failed to generate a salt"))?;
+    let salt = SaltString::encode_b64(&salt).map_err(|e| anyhow::anyhow!("{}", e))?;
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))
+}
+
+/// Whether `password` matches a hash from [`hash_password`]
+pub fn verify_password(password: &str, hash: &str) -> bool {
+    PasswordHash::new(hash).is_ok_and(|hash| {
+        Argon2::default()
+            .verify_password(password.as_bytes(), &hash)
+            .is_ok()
+    })
+}
+
+/// The token `todoee serve` expects in `Authorization: Bearer`, created and
+/// saved to `auth.json` the first time it is asked for.
+pub fn api_token() -> Result<String> {
+    api_token_at(&Config::auth_path()?)
+}
+
+fn api_token_at(path: &Path) -> Result<String> {
+    let mut fields = read_fields(path)?;
+    if let Some(Value::String(token)) = fields.get(API_TOKEN_KEY) {
+        return Ok(token.clone());
+    }
+
+    let token = random_token()?;
+    fields.insert(API_TOKEN_KEY.to_string(), Value::String(token.clone()));
+    write_fields(path, &fields)?;
+    Ok(token)
+}
+
+fn random_token() -> Result<String> {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| anyhow::anyhow!("Failed to generate a token"))?;
+    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Every key in `auth.json`, none if it doesn't exist
+fn read_fields(path: &Path) -> Result<Map<String, Value>> {
+    if !path.exists() {
+        return Ok(Map::new());
+    }
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn write_fields(path: &Path, fields: &Map<String, Value>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let content = serde_json::to_string_pretty(fields).context("Failed to serialize auth")?;
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    // Owner read/write only, like config.toml
+    #[cfg(unix)]
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+        .with_context(|| format!("Failed to set permissions on {}", path.display()))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_auth_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("auth.json");
+        assert!(Auth::load_from(&path).unwrap().is_none());
+
+        let user = User {
+            id: Uuid::new_v4(),
+            email: "ada@example.com".to_string(),
+            created_at: Utc::now(),
+        };
+        let auth = Auth::new(&user);
+        auth.save_to(&path).unwrap();
+        assert_eq!(Auth::load_from(&path).unwrap(), Some(auth));
+        #[cfg(unix)]
+        {
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn test_api_token_kept_beside_auth() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("auth.json");
+
+        let token = api_token_at(&path).unwrap();
+        assert_eq!(token.len(), 43);
+        assert!(Auth::load_from(&path).unwrap().is_none());
+        assert_eq!(api_token_at(&path).unwrap(), token);
+
+        let user = User {
+            id: Uuid::new_v4(),
+            email: "ada@example.com".to_string(),
+            created_at: Utc::now(),
+        };
+        let auth = Auth::new(&user);
+        auth.save_to(&path).unwrap();
+        assert_eq!(Auth::load_from(&path).unwrap(), Some(auth));
+        assert_eq!(api_token_at(&path).unwrap(), token, "saving auth keeps it");
+
+        let signed_in = Auth::with_session(&user, new_session_token().unwrap());
+        signed_in.save_to(&path).unwrap();
+        assert_eq!(Auth::load_from(&path).unwrap(), Some(signed_in));
+        clear_at(&path).unwrap();
+        assert!(Auth::load_from(&path).unwrap().is_none());
+        assert_eq!(api_token_at(&path).unwrap(), token, "logging out keeps it");
+    }
+
+    #[test]
+    fn test_passwords_and_session_hashes() {
+        let hash = hash_password("correct horse").unwrap();
+        assert!(verify_password("correct horse", &hash));
+        assert!(!verify_password("correct horse!", &hash));
+        assert!(!verify_password("correct horse", "not a hash"));
+        assert_ne!(hash_password("correct horse").unwrap(), hash, "salted");
+
+        let token = new_session_token().unwrap();
+        assert_eq!(token_hash(&token), token_hash(&token));
+        assert_eq!(token_hash(&token).len(), 64);
+        assert_ne!(token_hash(&token), token);
+    }
+}
@@ -0,0 +1,241 @@
+//! Todos changed both here and on another device between two syncs.
+//!
+//! Sync keeps the remote version of such a todo in the local `conflicts`
+//! table and marks the local one [`SyncStatus::Conflict`] instead of letting
+//! the newer edit silently win. Neither side is uploaded or overwritten until
+//! the conflict is resolved, keeping one side or picking each field.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Local, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::{Priority, SyncStatus, Todo, short_id};
+
+/// A todo whose local and remote versions both changed since the last sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Conflict {
+    pub local: Todo,
+    pub remote: Todo,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Which version of a field to keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Local,
+    Remote,
+}
+
+impl Side {
+    pub fn other(self) -> Self {
+        match self {
+            Side::Local => Side::Remote,
+            Side::Remote => Side::Local,
+        }
+    }
+}
+
+/// A user-visible part of a todo that can differ between the two versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Field {
+    Title,
+    Icon,
+    Description,
+    Category,
+    Project,
+    Due,
+    Reminder,
+    RemindBefore,
+    NoNag,
+    Priority,
+    Completed,
+}
+
+impl Field {
+    pub const ALL: [Field; 11] = [
+        Field::Title,
+        Field::Icon,
+        Field::Description,
+        Field::Category,
+        Field::Project,
+        Field::Due,
+        Field::Reminder,
+        Field::RemindBefore,
+        Field::NoNag,
+        Field::Priority,
+        Field::Completed,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Field::Title => "title",
+            Field::Icon => "icon",
+            Field::Description => "description",
+            Field::Category => "category",
+            Field::Project => "project",
+            Field::Due => "due",
+            Field::Reminder => "reminder",
+            Field::RemindBefore => "remind before",
+            Field::NoNag => "no nag",
+            Field::Priority => "priority",
+            Field::Completed => "completed",
+        }
+    }
+
+    /// Whether `a` and `b` differ in this field. Times are compared to the
+    /// microsecond, the precision the remote keeps.
+    pub fn differs(self, a: &Todo, b: &Todo) -> bool {
+        let micros = |time: Option<DateTime<Utc>>| time.map(|t| t.timestamp_micros());
+        match self {
+            Field::Title => a.title != b.title,
+            Field::Icon => a.icon != b.icon,
+            Field::Description => a.description != b.description,
+            Field::Category => a.category_id != b.category_id,
+            Field::Project => a.project_id != b.project_id,
+            Field::Due => micros(a.due_date) != micros(b.due_date),
+            Field::Reminder => micros(a.reminder_at) != micros(b.reminder_at),
+            Field::RemindBefore => a.remind_before_minutes != b.remind_before_minutes,
+            Field::NoNag => a.no_nag != b.no_nag,
+            Field::Priority => a.priority != b.priority,
+            Field::Completed => a.is_completed != b.is_completed,
+        }
+    }
+
+    /// Copy this field from `from` into `to`
+    pub fn copy(self, from: &Todo, to: &mut Todo) {
+        match self {
+            Field::Title => to.title = from.title.clone(),
+            Field::Icon => to.icon = from.icon.clone(),
+            Field::Description => to.description = from.description.clone(),
+            Field::Category => to.category_id = from.category_id,
+            Field::Project => to.project_id = from.project_id,
+            Field::Due => to.due_date = from.due_date,
+            Field::Reminder => to.reminder_at = from.reminder_at,
+            Field::RemindBefore => to.remind_before_minutes = from.remind_before_minutes,
+            Field::NoNag => to.no_nag = from.no_nag,
+            Field::Priority => to.priority = from.priority,
+            Field::Completed => {
+                to.is_completed = from.is_completed;
+                to.completed_at = from.completed_at;
+            }
+        }
+    }
+
+    /// The field's value in `todo` for display. Categories and projects are
+    /// shown by their name in `names`, or a short ID when it isn't there.
+    pub fn display(self, todo: &Todo, names: &HashMap<Uuid, String>) -> String {
+        let none = || "-".to_string();
+        let name = |id: Option<Uuid>| {
+            id.map_or_else(none, |id| {
+                names.get(&id).cloned().unwrap_or_else(|| short_id(&id, 8))
+            })
+        };
+        let time = |time: Option<DateTime<Utc>>| {
+            time.map_or_else(none, |t| {
+                t.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string()
+            })
+        };
+        match self {
+            Field::Title => todo.title.clone(),
+            Field::Icon => todo.icon.clone().unwrap_or_else(none),
+            Field::Description => todo.description.clone().unwrap_or_else(none),
+            Field::Category => name(todo.category_id),
+            Field::Project => name(todo.project_id),
+            Field::Due => time(todo.due_date),
+            Field::Reminder => time(todo.reminder_at),
+            Field::RemindBefore => todo
+                .remind_before_minutes
+                .map_or_else(none, |m| format!("{} min", m)),
+            Field::NoNag => if todo.no_nag { "yes" } else { "no" }.to_string(),
+            Field::Priority => match todo.priority {
+                Priority::Low => "Low",
+                Priority::Medium => "Medium",
+                Priority::High => "High",
+                Priority::Urgent => "Urgent",
+            }
+            .to_string(),
+            Field::Completed => if todo.is_completed { "yes" } else { "no" }.to_string(),
+        }
+    }
+}
+
+impl Conflict {
+    /// The fields the two versions disagree on
+    pub fn fields(&self) -> Vec<Field> {
+        Field::ALL
+            .into_iter()
+            .filter(|f| f.differs(&self.local, &self.remote))
+            .collect()
+    }
+
+    /// The todo to keep, taking each differing field from the side `pick`
+    /// chooses.
+    ///
+    /// Keeping only remote fields is the remote version, already synced.
+    /// Anything else is a new edit of the local version, newer than the
+    /// remote one so the next sync uploads it everywhere.
+    pub fn resolve(&self, pick: impl Fn(Field) -> Side) -> Todo {
+        let fields = self.fields();
+        if fields.iter().all(|&f| pick(f) == Side::Remote) {
+            let mut todo = self.remote.clone();
+            todo.sync_status = SyncStatus::Synced;
+            return todo;
+        }
+
+        let mut todo = self.local.clone();
+        for field in fields {
+            if pick(field) == Side::Remote {
+                field.copy(&self.remote, &mut todo);
+            }
+        }
+        todo.updated_at = Utc::now().max(self.remote.updated_at + Duration::milliseconds(1));
+        todo.sync_status = SyncStatus::Pending;
+        todo
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_picks_fields_per_side() {
+        let mut local = Todo::new("Buy milk".to_string(), None);
+        local.priority = Priority::High;
+        local.sync_status = SyncStatus::Conflict;
+        let mut remote = local.clone();
+        remote.title = "Buy oat milk".to_string();
+        remote.mark_complete();
+        remote.updated_at = Utc::now() + Duration::hours(1);
+        remote.sync_status = SyncStatus::Synced;
+        let conflict = Conflict {
+            local: local.clone(),
+            remote: remote.clone(),
+            detected_at: Utc::now(),
+        };
+        assert_eq!(conflict.fields(), [Field::Title, Field::Completed]);
+
+        let theirs = conflict.resolve(|_| Side::Remote);
+        assert_eq!(theirs.title, "Buy oat milk");
+        assert_eq!(theirs.updated_at, remote.updated_at);
+        assert_eq!(theirs.sync_status, SyncStatus::Synced);
+
+        let merged = conflict.resolve(|f| match f {
+            Field::Title => Side::Remote,
+            _ => Side::Local,
+        });
+        assert_eq!(merged.title, "Buy oat milk");
+        assert!(!merged.is_completed);
+        assert_eq!(merged.priority, Priority::High);
+        assert!(merged.updated_at > remote.updated_at);
+        assert_eq!(merged.sync_status, SyncStatus::Pending);
+
+        let ours = conflict.resolve(|_| Side::Local);
+        assert_eq!(ours.title, "Buy milk");
+        assert_eq!(Field::Title.display(&ours, &HashMap::new()), "Buy milk");
+        assert_eq!(Field::Category.display(&ours, &HashMap::new()), "-");
+    }
+}
@@ -0,0 +1,138 @@
+//! What the reminder daemon is doing, in `daemon-status.json`.
+//!
+//! The daemon rewrites the file after every check; `todoee daemon status` and
+//! the TUI read it. A daemon that was killed leaves its last status behind, so
+//! readers go by [`DaemonStatus::is_stale`] rather than the file existing.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+
+/// Seconds between the daemon's checks
+pub const CHECK_INTERVAL_SECS: u64 = 60;
+
+/// Errors kept in the file, newest last
+const ERRORS_KEPT: usize = 10;
+
+/// File in the config directory holding the status
+const STATUS_FILE: &str = "daemon-status.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+    /// End of the last check, `None` before the first one finishes
+    pub last_check: Option<DateTime<Utc>>,
+    /// Todos and events notified since the daemon started, counting each
+    /// one in a combined notification
+    pub reminders_sent: u64,
+    /// Last sync of this database, by any process
+    pub last_sync: Option<DateTime<Utc>>,
+    /// Recent failures, oldest first
+    pub errors: Vec<DaemonError>,
+}
+
+/// A check that failed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonError {
+    pub at: DateTime<Utc>,
+    pub message: String,
+}
+
+impl DaemonStatus {
+    pub fn new(pid: u32, started_at: DateTime<Utc>) -> Self {
+        Self {
+            pid,
+            started_at,
+            last_check: None,
+            reminders_sent: 0,
+            last_sync: None,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Remember a failure, dropping the oldest beyond the last few
+    pub fn record_error(&mut self, at: DateTime<Utc>, message: String) {
+        self.errors.push(DaemonError { at, message });
+        if self.errors.len() > ERRORS_KEPT {
+            self.errors.drain(..self.errors.len() - ERRORS_KEPT);
+        }
+    }
+
+    /// Whether the daemon has missed a few checks, so is most likely gone
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        let last = self.last_check.unwrap_or(self.started_at);
+        now - last > Duration::seconds(3 * CHECK_INTERVAL_SECS as i64)
+    }
+
+    /// Read the status file, or `None` if no daemon has written one.
+    pub fn load() -> Result<Option<Self>> {
+        Self::load_from(&Self::path()?)
+    }
+
+    /// Replace the status file.
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::path()?)
+    }
+
+    fn path() -> Result<PathBuf> {
+        Ok(Config::config_dir()?.join(STATUS_FILE))
+    }
+
+    fn load_from(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let status = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(Some(status))
+    }
+
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let content =
+            serde_json::to_string_pretty(self).context("Failed to serialize daemon status")?;
+        // Written aside and renamed, so a reader never sees half a file
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, content).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_daemon_status_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(STATUS_FILE);
+        assert!(DaemonStatus::load_from(&path).unwrap().is_none());
+
+        let started = Utc::now() - Duration::hours(1);
+        let mut status = DaemonStatus::new(4242, started);
+        assert!(status.is_stale(Utc::now()));
+        status.last_check = Some(Utc::now() - Duration::seconds(30));
+        assert!(!status.is_stale(Utc::now()));
+
+        for i in 0..12 {
+            status.record_error(started, format!("error {}", i));
+        }
+        assert_eq!(status.errors.len(), ERRORS_KEPT);
+        assert_eq!(status.errors[0].message, "error 2");
+
+        status.save_to(&path).unwrap();
+        assert_eq!(DaemonStatus::load_from(&path).unwrap(), Some(status));
+    }
+}
@@ -0,0 +1,826 @@
+//! Offline natural-language date parsing
+//!
+//! Understands the phrases people actually type after a task: "tomorrow 3pm",
+//! "friday", "next friday", "in 2 weeks", "end of month", "+3d", "jan 5",
+//! "2026-03-01 14:00". Everything is resolved against a caller-supplied
+//! `now` so the parser is deterministic; the `parse_due` / `parse_reminder`
+//! helpers use the local clock.
+//!
+//! Numeric dates such as "05/03" are read in the user's [`DateOrder`], and
+//! the configured display format is accepted as input too (see [`DateInput`]).
+//!
+//! Wall-clock times are interpreted in the timezone of `now`. Date-only due
+//! dates are stored at noon UTC, matching the rest of todoee; date-only
+//! reminders fire at 09:00 local time.
+
+use chrono::{
+    DateTime, Datelike, Duration, Local, Month, Months, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone, Utc, Weekday,
+};
+
+/// Default time for a date-only reminder ("remind me tomorrow")
+const REMINDER_HOUR: u32 = 9;
+
+/// Time used for "tonight"
+const TONIGHT_HOUR: u32 = 20;
+
+/// Longest phrase (in words) `split_due` looks for at the end of a title
+const MAX_PHRASE_WORDS: usize = 4;
+
+/// Order of day and month in numeric dates such as "05/03/2026"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateOrder {
+    /// "05/03" is May 3rd
+    #[default]
+    MonthFirst,
+    /// "05/03" is March 5th
+    DayFirst,
+    /// "2026/05/03"; a short "05/03" is month first
+    YearFirst,
+}
+
+impl DateOrder {
+    /// The order a strftime format writes dates in, month first when unclear
+    pub fn from_format(format: &str) -> Self {
+        if format.contains("%F") {
+            return DateOrder::YearFirst;
+        }
+        if format.contains("%D") {
+            return DateOrder::MonthFirst;
+        }
+        let find = |specs: &[&str]| specs.iter().filter_map(|s| format.find(s)).min();
+        let year = find(&["%Y", "%y", "%C"]);
+        let month = find(&["%m", "%b", "%B", "%h"]);
+        let day = find(&["%d", "%e"]);
+        match (year, month, day) {
+            (Some(y), Some(m), _) if y < m => DateOrder::YearFirst,
+            (_, Some(m), Some(d)) if d < m => DateOrder::DayFirst,
+            _ => DateOrder::MonthFirst,
+        }
+    }
+}
+
+/// How typed dates are read, from the `[display]` settings
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DateInput {
+    pub order: DateOrder,
+    /// The display date format, accepted as input as well ("%d %b %Y")
+    pub format: Option<String>,
+}
+
+/// A parsed date with an optional wall-clock time, in the caller's timezone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParsedDate {
+    pub date: NaiveDate,
+    pub time: Option<NaiveTime>,
+}
+
+impl ParsedDate {
+    /// Resolve as a due date. Without a time the date is stored at noon UTC.
+    pub fn due_date<Tz: TimeZone>(&self, tz: &Tz) -> DateTime<Utc> {
+        match self.time {
+            Some(time) => to_utc(tz, self.date.and_time(time)),
+            None => self.date.and_time(noon()).and_utc(),
+        }
+    }
+
+    /// Resolve as a reminder. Without a time the reminder fires at 09:00.
+    pub fn reminder_at<Tz: TimeZone>(&self, tz: &Tz) -> DateTime<Utc> {
+        let time = self
+            .time
+            .unwrap_or_else(|| NaiveTime::from_hms_opt(REMINDER_HOUR, 0, 0).unwrap());
+        to_utc(tz, self.date.and_time(time))
+    }
+}
+
+/// Parse a date phrase relative to `now`. Returns `None` if any word is not understood.
+pub fn parse<Tz: TimeZone>(input: &str, now: &DateTime<Tz>) -> Option<ParsedDate> {
+    parse_with(input, now, &DateInput::default())
+}
+
+/// Like [`parse`], reading numeric dates and the display format as `dates` says
+pub fn parse_with<Tz: TimeZone>(
+    input: &str,
+    now: &DateTime<Tz>,
+    dates: &DateInput,
+) -> Option<ParsedDate> {
+    let input = input.trim().to_lowercase();
+    let tokens: Vec<&str> = input
+        .split_whitespace()
+        .map(|t| t.trim_end_matches([',', '.', '!']))
+        .filter(|t| !t.is_empty())
+        .collect();
+    // Dates past year 9999 parse but can't be stored as RFC 3339
+    parse_tokens(&tokens, now.naive_local(), dates).filter(|p| p.date.year() <= 9999)
+}
+
+/// Parse a due date relative to the local clock
+pub fn parse_due(input: &str, dates: &DateInput) -> Option<DateTime<Utc>> {
+    let now = Local::now();
+    parse_with(input, &now, dates).map(|p| p.due_date(&Local))
+}
+
+/// Parse a reminder time relative to the local clock
+pub fn parse_reminder(input: &str, dates: &DateInput) -> Option<DateTime<Utc>> {
+    let now = Local::now();
+    parse_with(input, &now, dates).map(|p| p.reminder_at(&Local))
+}
+
+/// The Monday and Sunday of ISO week `week` of `year`
+pub fn iso_week_bounds(year: i32, week: u32) -> Option<(NaiveDate, NaiveDate)> {
+    Some((
+        NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)?,
+        NaiveDate::from_isoywd_opt(year, week, Weekday::Sun)?,
+    ))
+}
+
+/// Whether a due date was set without a time (stored at noon UTC)
+pub fn is_date_only(due: &DateTime<Utc>) -> bool {
+    due.time() == noon()
+}
+
+/// The wall-clock time a due date falls at in `tz`, or `None` if it has no time
+pub fn due_time<Tz: TimeZone>(due: &DateTime<Utc>, tz: &Tz) -> Option<NaiveTime> {
+    (!is_date_only(due)).then(|| due.with_timezone(tz).time())
+}
+
+/// Move a due date to `time` on the day it falls on in `tz`, or make it
+/// date-only when `time` is `None`. Date-only dues keep their date.
+pub fn with_due_time<Tz: TimeZone>(
+    due: &DateTime<Utc>,
+    time: Option<NaiveTime>,
+    tz: &Tz,
+) -> DateTime<Utc> {
+    let date = if is_date_only(due) {
+        due.date_naive()
+    } else {
+        due.with_timezone(tz).date_naive()
+    };
+    ParsedDate { date, time }.due_date(tz)
+}
+
+/// Format a due date so `parse` reads it back: "2026-03-01" or "2026-03-01 14:00"
+pub fn format_due<Tz: TimeZone>(due: &DateTime<Utc>, tz: &Tz) -> String {
+    if is_date_only(due) {
+        due.format("%Y-%m-%d").to_string()
+    } else {
+        due.with_timezone(tz)
+            .naive_local()
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+    }
+}
+
+/// Split a trailing date phrase off a task title.
+///
+/// "pay rent friday" becomes ("pay rent", friday). Connecting words such as
+/// "by", "on" or "due" before the phrase are dropped. Returns `None` when the
+/// title does not end in a date, or when nothing would be left of it.
+/// Short numeric dates are not split off: "read pages 3/5" is not due in March.
+pub fn split_due<Tz: TimeZone>(
+    text: &str,
+    now: &DateTime<Tz>,
+    dates: &DateInput,
+) -> Option<(String, ParsedDate)> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    // Prefer the longest phrase so "next friday" wins over "friday"
+    for len in (1..=MAX_PHRASE_WORDS.min(words.len().saturating_sub(1))).rev() {
+        let (title, phrase) = words.split_at(words.len() - len);
+        if phrase.iter().any(|w| {
+            w.bytes()
+                .all(|b| b.is_ascii_digit() || b == b'/' || b == b'.')
+                && w.matches(['/', '.']).count() == 1
+        }) {
+            continue;
+        }
+        let Some(parsed) = parse_with(&phrase.join(" "), now, dates) else {
+            continue;
+        };
+
+        let mut title = title.to_vec();
+        while let Some(last) = title.last()
+            && matches!(last.to_lowercase().as_str(), "by" | "on" | "at" | "due")
+        {
+            title.pop();
+        }
+        if title.is_empty() {
+            return None;
+        }
+        return Some((title.join(" "), parsed));
+    }
+
+    None
+}
+
+/// Parse a duration: "10m", "1h", "1h30m", "2d", or plain minutes ("45").
+/// "0" is a zero duration; negative or unit-less mixes are rejected.
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    let input = input.trim().to_lowercase();
+    if let Ok(minutes) = input.parse::<i64>() {
+        return Duration::try_minutes(minutes).filter(|_| minutes >= 0);
+    }
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let value: i64 = number.parse().ok()?;
+        number.clear();
+        // Out-of-range amounts are invalid, not a panic
+        let part = match c {
+            'd' => Duration::try_days(value)?,
+            'h' => Duration::try_hours(value)?,
+            'm' => Duration::try_minutes(value)?,
+            _ => return None,
+        };
+        total = total.checked_add(&part)?;
+    }
+
+    (!input.is_empty() && number.is_empty()).then_some(total)
+}
+
+/// Format a duration the way [`parse_duration`] reads it: "1h30m", "2d", "0"
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes();
+    if minutes <= 0 {
+        return "0".to_string();
+    }
+    let (days, hours, minutes) = (minutes / 1440, minutes % 1440 / 60, minutes % 60);
+    let mut out = String::new();
+    for (value, unit) in [(days, 'd'), (hours, 'h'), (minutes, 'm')] {
+        if value > 0 {
+            out.push_str(&format!("{}{}", value, unit));
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Grammar
+// ============================================================================
+
+fn parse_tokens(tokens: &[&str], now: NaiveDateTime, dates: &DateInput) -> Option<ParsedDate> {
+    let tokens = match tokens.first() {
+        Some(&"on" | &"by" | &"due") => &tokens[1..],
+        _ => tokens,
+    };
+    if tokens.is_empty() {
+        return None;
+    }
+
+    // "in 30 minutes", "in an hour" are exact instants
+    if let Some(offset) = parse_offset(tokens) {
+        let at = now.checked_add_signed(offset)?;
+        return Some(ParsedDate {
+            date: at.date(),
+            time: Some(at.time()),
+        });
+    }
+
+    // "2026-03-01 14:00" as a single phrase
+    if let Ok(dt) = NaiveDateTime::parse_from_str(&tokens.join(" "), "%Y-%m-%d %H:%M") {
+        return Some(ParsedDate {
+            date: dt.date(),
+            time: Some(dt.time()),
+        });
+    }
+
+    // Date only
+    if let Some((date, time)) = parse_date(tokens, now.date(), dates) {
+        return Some(ParsedDate { date, time });
+    }
+
+    // Time only: today, or tomorrow if that time has passed
+    if let Some(time) = parse_time(tokens) {
+        let date = if time > now.time() {
+            now.date()
+        } else {
+            now.date() + Duration::days(1)
+        };
+        return Some(ParsedDate {
+            date,
+            time: Some(time),
+        });
+    }
+
+    // Date followed by time ("friday 3pm") or time followed by date ("3pm friday")
+    for split in 1..tokens.len() {
+        let (head, tail) = tokens.split_at(split);
+        if let Some((date, _)) = parse_date(head, now.date(), dates)
+            && let Some(time) = parse_time(tail)
+        {
+            return Some(ParsedDate {
+                date,
+                time: Some(time),
+            });
+        }
+        if let Some(time) = parse_time(head)
+            && let Some((date, _)) = parse_date(tail, now.date(), dates)
+        {
+            return Some(ParsedDate {
+                date,
+                time: Some(time),
+            });
+        }
+    }
+
+    None
+}
+
+/// "in N minutes/hours"
+fn parse_offset(tokens: &[&str]) -> Option<Duration> {
+    let ["in", count, unit] = tokens else {
+        return None;
+    };
+    let count = parse_count(count)?;
+    match *unit {
+        "min" | "mins" | "minute" | "minutes" => Duration::try_minutes(count),
+        "hr" | "hrs" | "hour" | "hours" => Duration::try_hours(count),
+        _ => None,
+    }
+}
+
+/// A date phrase, with the time it implies ("tonight") if any
+fn parse_date(
+    tokens: &[&str],
+    today: NaiveDate,
+    dates: &DateInput,
+) -> Option<(NaiveDate, Option<NaiveTime>)> {
+    if let Some(format) = &dates.format
+        && let Ok(date) = NaiveDate::parse_from_str(&tokens.join(" "), format)
+    {
+        return Some((date, None));
+    }
+
+    let date = match tokens {
+        ["today"] => today,
+        ["tonight"] => {
+            return Some((today, NaiveTime::from_hms_opt(TONIGHT_HOUR, 0, 0)));
+        }
+        ["tomorrow" | "tmr" | "tmrw"] => today + Duration::days(1),
+        ["next", "week"] => today + Duration::weeks(1),
+        ["next", "month"] => today.checked_add_months(Months::new(1))?,
+        ["next", "year"] => today.checked_add_months(Months::new(12))?,
+        ["end", "of", "week"] | ["eow"] => upcoming(today, Weekday::Sun, true),
+        ["end", "of", "month"] | ["eom"] => last_day_of_month(today)?,
+        ["end", "of", "year"] | ["eoy"] => NaiveDate::from_ymd_opt(today.year(), 12, 31)?,
+        ["in", count, unit] => {
+            let count = parse_count(count)?;
+            // Numbers too large for a date are not one, rather than a panic
+            match *unit {
+                "day" | "days" => today.checked_add_signed(Duration::try_days(count)?)?,
+                "week" | "weeks" => today.checked_add_signed(Duration::try_weeks(count)?)?,
+                "month" | "months" => {
+                    today.checked_add_months(Months::new(u32::try_from(count).ok()?))?
+                }
+                _ => return None,
+            }
+        }
+        ["next", day] => upcoming(today, parse_weekday(day)?, false),
+        ["this", day] => upcoming(today, parse_weekday(day)?, true),
+        [word] => {
+            if let Some(day) = parse_weekday(word) {
+                upcoming(today, day, true)
+            } else if let Some(rest) = word.strip_prefix('+') {
+                let unit = rest.chars().last()?;
+                let count: i64 = rest.strip_suffix(['d', 'w'])?.parse().ok()?;
+                let offset = match unit {
+                    'd' => Duration::try_days(count)?,
+                    'w' => Duration::try_weeks(count)?,
+                    _ => return None,
+                };
+                today.checked_add_signed(offset)?
+            } else {
+                NaiveDate::parse_from_str(word, "%Y-%m-%d")
+                    .ok()
+                    .or_else(|| parse_numeric(word, today, dates.order))?
+            }
+        }
+        [a, b] => {
+            // "jan 5" or "5 jan"
+            let (month, day) = match (parse_month(a), parse_month(b)) {
+                (Some(month), None) => (month, parse_day(b)?),
+                (None, Some(month)) => (month, parse_day(a)?),
+                _ => return None,
+            };
+            next_month_day(today, month, day)?
+        }
+        _ => return None,
+    };
+    Some((date, None))
+}
+
+/// "05/03", "5.3.26" or "05/03/2026", read in `order`. A four-digit first
+/// part is always the year.
+fn parse_numeric(word: &str, today: NaiveDate, order: DateOrder) -> Option<NaiveDate> {
+    let parts: Vec<&str> = word.split(['/', '.']).collect();
+    if parts
+        .iter()
+        .any(|p| p.is_empty() || !p.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return None;
+    }
+    let num = |s: &str| s.parse::<u32>().ok();
+
+    match parts.as_slice() {
+        [a, b] => {
+            let (month, day) = match order {
+                DateOrder::DayFirst => (num(b)?, num(a)?),
+                DateOrder::MonthFirst | DateOrder::YearFirst => (num(a)?, num(b)?),
+            };
+            next_month_day(today, month, day)
+        }
+        [a, b, c] => {
+            let (year, month, day) = match order {
+                _ if a.len() == 4 => (a, b, c),
+                DateOrder::YearFirst => (a, b, c),
+                DateOrder::DayFirst => (c, b, a),
+                DateOrder::MonthFirst => (c, a, b),
+            };
+            let year = match year.len() {
+                2 => 2000 + num(year)? as i32,
+                4 => num(year)? as i32,
+                _ => return None,
+            };
+            NaiveDate::from_ymd_opt(year, num(month)?, num(day)?)
+        }
+        _ => None,
+    }
+}
+
+/// The next `month`/`day` on or after `today`
+fn next_month_day(today: NaiveDate, month: u32, day: u32) -> Option<NaiveDate> {
+    match NaiveDate::from_ymd_opt(today.year(), month, day)? {
+        this_year if this_year >= today => Some(this_year),
+        _ => NaiveDate::from_ymd_opt(today.year() + 1, month, day),
+    }
+}
+
+/// A time of day: "3pm", "3:30pm", "3 pm", "15:30", "noon", "midnight", optionally after "at"
+fn parse_time(tokens: &[&str]) -> Option<NaiveTime> {
+    let tokens = match tokens.first() {
+        Some(&"at") => &tokens[1..],
+        _ => tokens,
+    };
+
+    let (clock, meridiem) = match tokens {
+        ["noon"] => return Some(noon()),
+        ["midnight"] => return NaiveTime::from_hms_opt(0, 0, 0),
+        [word] => {
+            if let Some(clock) = word.strip_suffix("am") {
+                (clock, Some(false))
+            } else if let Some(clock) = word.strip_suffix("pm") {
+                (clock, Some(true))
+            } else {
+                (*word, None)
+            }
+        }
+        [clock, "am"] => (*clock, Some(false)),
+        [clock, "pm"] => (*clock, Some(true)),
+        _ => return None,
+    };
+
+    let (hour, minute) = match clock.split_once(':') {
+        Some((h, m)) if m.len() == 2 => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+        // A bare number is only a time with am/pm ("3" alone could be anything)
+        None if meridiem.is_some() => (clock.parse::<u32>().ok()?, 0),
+        _ => return None,
+    };
+
+    let hour = match meridiem {
+        Some(pm) => {
+            if !(1..=12).contains(&hour) {
+                return None;
+            }
+            (hour % 12) + if pm { 12 } else { 0 }
+        }
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+fn parse_count(word: &str) -> Option<i64> {
+    match word {
+        "a" | "an" | "one" => Some(1),
+        "two" => Some(2),
+        "three" => Some(3),
+        _ => word.parse().ok().filter(|n| *n > 0),
+    }
+}
+
+/// Full weekday names only: "sun" or "sat" at the end of a title is usually not a date
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    if word.len() < 6 {
+        return None;
+    }
+    word.parse().ok()
+}
+
+fn parse_month(word: &str) -> Option<u32> {
+    if word.len() < 3 {
+        return None;
+    }
+    word.parse::<Month>().ok().map(|m| m.number_from_month())
+}
+
+fn parse_day(word: &str) -> Option<u32> {
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    digits.parse().ok().filter(|d| (1..=31).contains(d))
+}
+
+/// The next `day` after `today`; `today` itself counts when `include_today` is set
+fn upcoming(today: NaiveDate, day: Weekday, include_today: bool) -> NaiveDate {
+    let ahead =
+        (7 + day.num_days_from_monday() as i64 - today.weekday().num_days_from_monday() as i64) % 7;
+    let ahead = if ahead == 0 && !include_today {
+        7
+    } else {
+        ahead
+    };
+    today + Duration::days(ahead)
+}
+
+fn last_day_of_month(date: NaiveDate) -> Option<NaiveDate> {
+    let first = date.with_day(1)?;
+    Some(first.checked_add_months(Months::new(1))? - Duration::days(1))
+}
+
+fn noon() -> NaiveTime {
+    NaiveTime::from_hms_opt(12, 0, 0).unwrap()
+}
+
+/// Convert a wall-clock time in `tz` to UTC, treating DST gaps as UTC
+fn to_utc<Tz: TimeZone>(tz: &Tz, naive: NaiveDateTime) -> DateTime<Utc> {
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| naive.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wednesday 2026-01-14 10:00 UTC
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 14, 10, 0, 0).unwrap()
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn at(h: u32, m: u32) -> Option<NaiveTime> {
+        NaiveTime::from_hms_opt(h, m, 0)
+    }
+
+    fn p(input: &str) -> Option<ParsedDate> {
+        parse(input, &now())
+    }
+
+    #[test]
+    fn test_relative_days() {
+        assert_eq!(p("today").unwrap().date, date(2026, 1, 14));
+        assert_eq!(p("Tomorrow").unwrap().date, date(2026, 1, 15));
+        assert_eq!(p("+3d").unwrap().date, date(2026, 1, 17));
+        assert_eq!(p("+1w").unwrap().date, date(2026, 1, 21));
+        assert_eq!(p("in 2 weeks").unwrap().date, date(2026, 1, 28));
+        assert_eq!(p("in a month").unwrap().date, date(2026, 2, 14));
+        assert_eq!(p("next week").unwrap().date, date(2026, 1, 21));
+    }
+
+    #[test]
+    fn test_weekdays() {
+        assert_eq!(p("friday").unwrap().date, date(2026, 1, 16));
+        assert_eq!(p("Wednesday").unwrap().date, date(2026, 1, 14));
+        assert_eq!(p("next wednesday").unwrap().date, date(2026, 1, 21));
+        assert_eq!(p("next friday").unwrap().date, date(2026, 1, 16));
+        assert_eq!(p("by monday").unwrap().date, date(2026, 1, 19));
+    }
+
+    #[test]
+    fn test_end_of_period() {
+        assert_eq!(p("end of month").unwrap().date, date(2026, 1, 31));
+        assert_eq!(p("end of week").unwrap().date, date(2026, 1, 18));
+        assert_eq!(p("eoy").unwrap().date, date(2026, 12, 31));
+    }
+
+    #[test]
+    fn test_month_day() {
+        assert_eq!(p("feb 3").unwrap().date, date(2026, 2, 3));
+        assert_eq!(p("3rd march").unwrap().date, date(2026, 3, 3));
+        // Already passed this year
+        assert_eq!(p("jan 2").unwrap().date, date(2027, 1, 2));
+        assert!(p("feb 30").is_none());
+    }
+
+    #[test]
+    fn test_times() {
+        let parsed = p("tomorrow 3pm").unwrap();
+        assert_eq!(parsed.date, date(2026, 1, 15));
+        assert_eq!(parsed.time, at(15, 0));
+
+        let parsed = p("at 9:30am friday").unwrap();
+        assert_eq!(parsed.date, date(2026, 1, 16));
+        assert_eq!(parsed.time, at(9, 30));
+
+        assert_eq!(p("12am").unwrap().time, at(0, 0));
+        assert_eq!(p("tonight").unwrap().time, at(20, 0));
+        assert_eq!(p("2026-03-01 14:00").unwrap().time, at(14, 0));
+    }
+
+    #[test]
+    fn test_time_only_rolls_to_tomorrow_when_passed() {
+        assert_eq!(p("3pm").unwrap().date, date(2026, 1, 14));
+        assert_eq!(p("9am").unwrap().date, date(2026, 1, 15));
+    }
+
+    #[test]
+    fn test_offsets_are_exact() {
+        let parsed = p("in 90 minutes").unwrap();
+        assert_eq!(parsed.reminder_at(&Utc), now() + Duration::minutes(90));
+        let parsed = p("in an hour").unwrap();
+        assert_eq!(parsed.reminder_at(&Utc), now() + Duration::hours(1));
+    }
+
+    #[test]
+    fn test_resolution_defaults() {
+        let parsed = p("friday").unwrap();
+        assert_eq!(
+            parsed.due_date(&Utc).to_rfc3339(),
+            "2026-01-16T12:00:00+00:00"
+        );
+        assert_eq!(
+            parsed.reminder_at(&Utc).to_rfc3339(),
+            "2026-01-16T09:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_due_time_round_trip() {
+        let timed = p("2026-03-01 14:30").unwrap().due_date(&Utc);
+        assert_eq!(due_time(&timed, &Utc), at(14, 30));
+        assert_eq!(format_due(&timed, &Utc), "2026-03-01 14:30");
+        assert_eq!(p(&format_due(&timed, &Utc)).unwrap().due_date(&Utc), timed);
+
+        let date_only = p("friday").unwrap().due_date(&Utc);
+        assert!(is_date_only(&date_only));
+        assert_eq!(due_time(&date_only, &Utc), None);
+        assert_eq!(format_due(&date_only, &Utc), "2026-01-16");
+    }
+
+    #[test]
+    fn test_with_due_time() {
+        // 23:30 in UTC is already the next day at UTC+2
+        let tz = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+        let late = p("2026-03-01 23:30").unwrap().due_date(&Utc);
+        let moved = with_due_time(&late, at(9, 0), &tz);
+        assert_eq!(moved.to_rfc3339(), "2026-03-02T07:00:00+00:00");
+
+        let date_only = p("2026-03-01").unwrap().due_date(&Utc);
+        let moved = with_due_time(&date_only, at(9, 0), &tz);
+        assert_eq!(moved.to_rfc3339(), "2026-03-01T07:00:00+00:00");
+
+        let cleared = with_due_time(&late, None, &tz);
+        assert!(is_date_only(&cleared));
+        assert_eq!(format_due(&cleared, &tz), "2026-03-02");
+    }
+
+    #[test]
+    fn test_rejects_non_dates() {
+        for input in [
+            "",
+            "3",
+            "someday",
+            "13pm",
+            "25:00",
+            "in 2 fortnights",
+            "next",
+            "fri",
+            "+5€",
+            "+é",
+            "in 99999999999 days",
+            "in 99999999999 weeks",
+            "in 9999999999999 minutes",
+            "in 99999999999 hours",
+            "in 99999999999 months",
+            "+99999999999999d",
+            "+9999999999w",
+            "in 9999999 weeks",
+        ] {
+            assert!(p(input).is_none(), "accepted '{}'", input);
+        }
+    }
+
+    #[test]
+    fn test_split_due() {
+        let (title, parsed) = split_due("pay rent friday", &now(), &DateInput::default()).unwrap();
+        assert_eq!(title, "pay rent");
+        assert_eq!(parsed.date, date(2026, 1, 16));
+
+        let (title, parsed) = split_due(
+            "Review PR by next friday 3pm",
+            &now(),
+            &DateInput::default(),
+        )
+        .unwrap();
+        assert_eq!(title, "Review PR");
+        assert_eq!(parsed.date, date(2026, 1, 16));
+        assert_eq!(parsed.time, at(15, 0));
+
+        let (title, _) =
+            split_due("file taxes end of month", &now(), &DateInput::default()).unwrap();
+        assert_eq!(title, "file taxes");
+    }
+
+    #[test]
+    fn test_split_due_leaves_plain_titles() {
+        assert!(split_due("read chapter 3", &now(), &DateInput::default()).is_none());
+        assert!(split_due("tomorrow", &now(), &DateInput::default()).is_none());
+        assert!(split_due("by friday", &now(), &DateInput::default()).is_none());
+        assert!(split_due("walk in the sun", &now(), &DateInput::default()).is_none());
+        assert!(split_due("read pages 3/5", &now(), &DateInput::default()).is_none());
+        assert!(split_due("tip waiter +5€", &now(), &DateInput::default()).is_none());
+        assert!(split_due("trip in 99999999999 days", &now(), &DateInput::default()).is_none());
+    }
+
+    #[test]
+    fn test_numeric_dates_follow_order() {
+        let day_first = DateInput {
+            order: DateOrder::DayFirst,
+            format: None,
+        };
+        let parse_dmy = |input: &str| parse_with(input, &now(), &day_first).map(|p| p.date);
+
+        assert_eq!(p("05/03").unwrap().date, date(2026, 5, 3));
+        assert_eq!(parse_dmy("05/03"), Some(date(2026, 3, 5)));
+        assert_eq!(parse_dmy("5.3.27"), Some(date(2027, 3, 5)));
+        assert_eq!(p("05/03/2027").unwrap().date, date(2027, 5, 3));
+        assert_eq!(parse_dmy("2027/03/05"), Some(date(2027, 3, 5)));
+        // Already passed this year
+        assert_eq!(parse_dmy("01/01"), Some(date(2027, 1, 1)));
+        assert_eq!(parse_dmy("05/03 3pm").unwrap(), date(2026, 3, 5));
+        assert_eq!(parse_dmy("13/13"), None);
+        assert_eq!(p("13/05"), None);
+
+        let (title, parsed) = split_due("pay rent 01/02/2026", &now(), &day_first).unwrap();
+        assert_eq!(title, "pay rent");
+        assert_eq!(parsed.date, date(2026, 2, 1));
+    }
+
+    #[test]
+    fn test_display_format_is_accepted() {
+        let dates = DateInput {
+            order: DateOrder::DayFirst,
+            format: Some("%d %b %Y".to_string()),
+        };
+        let parsed = parse_with("5 Mar 2027", &now(), &dates).unwrap();
+        assert_eq!(parsed.date, date(2027, 3, 5));
+        assert_eq!(parsed.time, None);
+    }
+
+    #[test]
+    fn test_date_order_from_format() {
+        assert_eq!(DateOrder::from_format("%Y-%m-%d"), DateOrder::YearFirst);
+        assert_eq!(DateOrder::from_format("%F"), DateOrder::YearFirst);
+        assert_eq!(DateOrder::from_format("%d/%m/%Y"), DateOrder::DayFirst);
+        assert_eq!(DateOrder::from_format("%e %B"), DateOrder::DayFirst);
+        assert_eq!(DateOrder::from_format("%m/%d/%Y"), DateOrder::MonthFirst);
+        assert_eq!(DateOrder::from_format("%b %d, %Y"), DateOrder::MonthFirst);
+        assert_eq!(DateOrder::from_format("%A"), DateOrder::MonthFirst);
+    }
+
+    #[test]
+    fn test_parse_and_format_duration() {
+        assert_eq!(parse_duration("0"), Some(Duration::zero()));
+        assert_eq!(parse_duration("1h30m"), Some(Duration::minutes(90)));
+        assert_eq!(parse_duration("2D"), Some(Duration::days(2)));
+        assert_eq!(parse_duration("9999999999999999"), None);
+        assert_eq!(parse_duration("99999999999999h"), None);
+        assert_eq!(parse_duration("99999999999999d"), None);
+        assert_eq!(parse_duration("100000000000d100000000000d"), None);
+        for input in ["", "-5", "1h30", "h", "10x"] {
+            assert_eq!(parse_duration(input), None, "accepted '{}'", input);
+        }
+
+        assert_eq!(format_duration(Duration::minutes(90)), "1h30m");
+        assert_eq!(
+            format_duration(Duration::days(1) + Duration::minutes(5)),
+            "1d5m"
+        );
+        assert_eq!(format_duration(Duration::zero()), "0");
+    }
+
+    #[test]
+    fn test_iso_week_bounds() {
+        let (monday, sunday) = iso_week_bounds(2026, 1).unwrap();
+        assert_eq!(monday, date(2025, 12, 29));
+        assert_eq!(sunday, date(2026, 1, 4));
+        assert_eq!(now().date_naive().iso_week().week(), 3);
+        assert!(iso_week_bounds(2026, 54).is_none());
+    }
+}
@@ -0,0 +1,138 @@
+//! What [`SyncService`](crate::sync::SyncService) needs from the other side of
+//! a sync.
+//!
+//! [`RemoteDb`](super::RemoteDb) is the Postgres implementation. Another
+//! store (a file, S3, CalDAV, a todoee server) only has to implement
+//! [`SyncBackend`] to sync with the same conflict rules.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::remote::TodoCursor;
+use crate::Result as TodoeeResult;
+use crate::models::{Category, Device, Project, Todo, User};
+
+/// How a backend stored an uploaded category or project
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Upserted {
+    /// Stored under its own ID
+    Stored,
+    /// Another one with the same name already exists; its ID, when it could
+    /// be found. Names are unique per user.
+    NameTaken(Option<Uuid>),
+}
+
+/// Storage a [`SyncService`](crate::sync::SyncService) uploads to and
+/// downloads from.
+///
+/// Writes use last-write-wins on `updated_at`: an older write never
+/// overwrites a newer one, and a write never changes another user's row.
+/// Deletions are soft, so other devices can learn of them with
+/// [`SyncBackend::get_deleted_todo_ids_since`].
+///
+/// Reads, deletions and device management take an `owner`: with `Some(user)`
+/// they only see that user's rows and devices, with `None` every one, as
+/// before accounts existed.
+#[async_trait]
+pub trait SyncBackend: Send + Sync {
+    /// Round-trip time of a trivial request
+    async fn ping(&self) -> TodoeeResult<Duration>;
+
+    /// Schema version the backend is at
+    async fn schema_version(&self) -> TodoeeResult<i32>;
+
+    /// Store a todo unless the stored copy is newer. A todo with the same ID
+    /// but a different `created_at` is a different todo and is left alone.
+    /// Returns whether the todo was written.
+    async fn upsert_todo(&self, todo: &Todo) -> TodoeeResult<bool>;
+
+    /// One page of live todos updated since `since`, oldest first, after the
+    /// `(updated_at, id)` cursor `after`. A page shorter than `limit` is the
+    /// last.
+    async fn get_todos_page(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+        after: Option<TodoCursor>,
+        limit: u32,
+    ) -> TodoeeResult<Vec<Todo>>;
+
+    /// The live todos with these IDs; unknown ones are left out
+    async fn get_todos(&self, owner: Option<Uuid>, ids: &[Uuid]) -> TodoeeResult<Vec<Todo>>;
+
+    /// Mark a todo deleted
+    async fn soft_delete_todo(&self, owner: Option<Uuid>, id: Uuid) -> TodoeeResult<()>;
+
+    /// IDs of todos deleted since `since`
+    async fn get_deleted_todo_ids_since(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> TodoeeResult<Vec<Uuid>>;
+
+    /// Store a category unless the stored copy is newer
+    async fn upsert_category(&self, category: &Category) -> TodoeeResult<Upserted>;
+
+    /// Live categories updated since `since`
+    async fn get_categories_since(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> TodoeeResult<Vec<Category>>;
+
+    /// Store a project unless the stored copy is newer
+    async fn upsert_project(&self, project: &Project) -> TodoeeResult<Upserted>;
+
+    /// Live projects updated since `since`, archived ones included
+    async fn get_projects_since(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> TodoeeResult<Vec<Project>>;
+
+    /// Register a device, or record that a known one was seen again
+    async fn register_device(&self, id: Uuid, name: &str) -> TodoeeResult<Device>;
+
+    /// All registered devices, most recently seen first
+    async fn list_devices(&self, owner: Option<Uuid>) -> TodoeeResult<Vec<Device>>;
+
+    /// Revoke a device. Returns false if it was unknown or already revoked.
+    async fn revoke_device(&self, owner: Option<Uuid>, id: Uuid) -> TodoeeResult<bool>;
+
+    /// The user with `email`, created if there is none
+    async fn ensure_user(&self, email: &str) -> TodoeeResult<User>;
+
+    /// Record that a registered device syncs as `user_id`
+    async fn link_device(&self, device_id: Uuid, user_id: Uuid) -> TodoeeResult<()>;
+
+    /// The user with `email`, if there is one
+    async fn find_user(&self, email: &str) -> TodoeeResult<Option<User>>;
+
+    /// The password hash of a user who has logged in before
+    async fn password_hash(&self, user_id: Uuid) -> TodoeeResult<Option<String>>;
+
+    /// Set the password hash of a user
+    async fn set_password_hash(&self, user_id: Uuid, hash: &str) -> TodoeeResult<()>;
+
+    /// Give every todo, category and project without an owner to `user_id`,
+    /// unless another user already has a password. Returns the number of
+    /// todos claimed.
+    async fn claim_unowned(&self, user_id: Uuid) -> TodoeeResult<u64>;
+
+    /// Record a session of `user_id` on `device_id`, by the hash of its token
+    async fn start_session(
+        &self,
+        token_hash: &str,
+        user_id: Uuid,
+        device_id: Uuid,
+    ) -> TodoeeResult<()>;
+
+    /// The user a session that hasn't ended belongs to
+    async fn session_user(&self, token_hash: &str) -> TodoeeResult<Option<Uuid>>;
+
+    /// End a session. Ending an unknown or ended session does nothing.
+    async fn end_session(&self, token_hash: &str) -> TodoeeResult<()>;
+}
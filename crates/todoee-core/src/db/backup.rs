@@ -0,0 +1,244 @@
+//! Automatic backups of the local database, and recovery when it is damaged
+//!
+//! A copy of the database is written to `backups/` in the config directory
+//! at most once a day, named `<database>-YYYYMMDD-HHMMSS.db`, and only the
+//! newest few are kept. A damaged database is never deleted: restoring or
+//! starting fresh first renames it (and its WAL files) to
+//! `<database>.damaged-YYYYMMDD-HHMMSS`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+
+use super::LocalDb;
+
+/// Directory next to the database that holds the backups
+const BACKUP_DIR: &str = "backups";
+
+/// Timestamp format in backup and damaged file names
+const STAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Minimum time between automatic backups
+pub const BACKUP_INTERVAL_HOURS: i64 = 24;
+
+/// A backup file of the local database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    pub path: PathBuf,
+    pub created_at: DateTime<Utc>,
+}
+
+/// State of the database file found at startup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Health {
+    /// Opens and passes the integrity check, or doesn't exist yet
+    Ok,
+    /// Can't be opened, or SQLite reports damage
+    Damaged(String),
+}
+
+/// Directory holding the backups of the database at `db_path`
+pub fn backup_dir(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(BACKUP_DIR)
+}
+
+fn db_stem(db_path: &Path) -> String {
+    db_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "cache".to_string())
+}
+
+/// Backups of the database at `db_path`, newest first
+pub fn list(db_path: &Path) -> Vec<Backup> {
+    let prefix = format!("{}-", db_stem(db_path));
+    let Ok(entries) = fs::read_dir(backup_dir(db_path)) else {
+        return Vec::new();
+    };
+
+    let mut backups: Vec<Backup> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let stamp = name.strip_prefix(&prefix)?.strip_suffix(".db")?;
+            let created_at = NaiveDateTime::parse_from_str(stamp, STAMP_FORMAT)
+                .ok()?
+                .and_utc();
+            Some(Backup {
+                path: entry.path(),
+                created_at,
+            })
+        })
+        .collect();
+    backups.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+    backups
+}
+
+/// Back up `db` if the newest backup is older than [`BACKUP_INTERVAL_HOURS`],
+/// then delete all but the newest `keep`. `keep` of 0 turns backups off.
+/// Returns the new backup, if one was made.
+pub async fn backup_if_due(
+    db: &LocalDb,
+    db_path: &Path,
+    keep: usize,
+    now: DateTime<Utc>,
+) -> Result<Option<PathBuf>> {
+    if keep == 0 {
+        return Ok(None);
+    }
+    let existing = list(db_path);
+    if existing
+        .first()
+        .is_some_and(|newest| now - newest.created_at < Duration::hours(BACKUP_INTERVAL_HOURS))
+    {
+        return Ok(None);
+    }
+
+    let dir = backup_dir(db_path);
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create backup directory: {}", dir.display()))?;
+    let path = dir.join(format!(
+        "{}-{}.db",
+        db_stem(db_path),
+        now.format(STAMP_FORMAT)
+    ));
+    db.backup_to(&path).await?;
+
+    for old in list(db_path).into_iter().skip(keep) {
+        let _ = fs::remove_file(&old.path);
+    }
+    Ok(Some(path))
+}
+
+/// Open the database at `db_path` and run SQLite's integrity check
+pub async fn check(db_path: &Path) -> Health {
+    if !db_path.exists() {
+        return Health::Ok;
+    }
+    let db = match LocalDb::with_pool_size(db_path, 1).await {
+        Ok(db) => db,
+        Err(e) => return Health::Damaged(e.root_cause().to_string()),
+    };
+    let health = match db.integrity_problems().await {
+        Ok(problems) if problems.is_empty() => Health::Ok,
+        Ok(problems) => Health::Damaged(problems.join("; ")),
+        Err(e) => Health::Damaged(e.root_cause().to_string()),
+    };
+    // Closed before the file is moved or replaced: a connection dropped
+    // without closing checkpoints the WAL into the file later
+    db.close().await;
+    health
+}
+
+/// Rename the database and its WAL files out of the way, keeping them for
+/// inspection. Returns the new path of the database file.
+pub fn set_aside(db_path: &Path, now: DateTime<Utc>) -> Result<PathBuf> {
+    let suffix = format!(".damaged-{}", now.format(STAMP_FORMAT));
+    let moved = append_to_name(db_path, &suffix);
+    fs::rename(db_path, &moved)
+        .with_context(|| format!("Failed to move {} aside", db_path.display()))?;
+
+    for extra in ["-wal", "-shm"] {
+        let path = append_to_name(db_path, extra);
+        if path.exists() {
+            fs::rename(&path, append_to_name(&moved, extra))
+                .with_context(|| format!("Failed to move {} aside", path.display()))?;
+        }
+    }
+    Ok(moved)
+}
+
+/// Replace the database with `backup`, setting the current file aside.
+/// Returns where the old file went.
+pub fn restore(db_path: &Path, backup: &Path, now: DateTime<Utc>) -> Result<PathBuf> {
+    let moved = set_aside(db_path, now)?;
+    fs::copy(backup, db_path)
+        .with_context(|| format!("Failed to copy {} into place", backup.display()))?;
+    Ok(moved)
+}
+
+fn append_to_name(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Todo;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_daily_backups_are_rotated() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("cache.db");
+        let db = LocalDb::new(&db_path).await.unwrap();
+        db.run_migrations().await.unwrap();
+        db.create_todo(&Todo::new("Backed up".to_string(), None))
+            .await
+            .unwrap();
+
+        let start = Utc::now();
+        let first = backup_if_due(&db, &db_path, 2, start).await.unwrap();
+        assert!(first.is_some());
+        // Not again on the same day
+        let again = backup_if_due(&db, &db_path, 2, start + Duration::hours(3))
+            .await
+            .unwrap();
+        assert!(again.is_none());
+
+        for day in 1..=3 {
+            backup_if_due(&db, &db_path, 2, start + Duration::days(day))
+                .await
+                .unwrap()
+                .unwrap();
+        }
+        let backups = list(&db_path);
+        assert_eq!(backups.len(), 2);
+        assert!(backups[0].created_at > backups[1].created_at);
+        assert!(!first.unwrap().exists());
+
+        let copy = LocalDb::new(&backups[0].path).await.unwrap();
+        assert_eq!(copy.list_todos(false).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_damaged_database_is_detected_and_restored() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("cache.db");
+        assert_eq!(check(&db_path).await, Health::Ok);
+
+        {
+            let db = LocalDb::new(&db_path).await.unwrap();
+            db.run_migrations().await.unwrap();
+            db.create_todo(&Todo::new("Keep me".to_string(), None))
+                .await
+                .unwrap();
+            backup_if_due(&db, &db_path, 3, Utc::now()).await.unwrap();
+            db.pool().close().await;
+        }
+        assert_eq!(check(&db_path).await, Health::Ok);
+
+        // A new file rather than overwriting: a connection that is still
+        // shutting down would checkpoint into the old one
+        let garbage = b"this is not a database".repeat(200);
+        fs::remove_file(&db_path).unwrap();
+        let _ = fs::remove_file(append_to_name(&db_path, "-wal"));
+        let _ = fs::remove_file(append_to_name(&db_path, "-shm"));
+        fs::write(&db_path, &garbage).unwrap();
+        assert!(matches!(check(&db_path).await, Health::Damaged(_)));
+
+        let backup = list(&db_path).remove(0);
+        let moved = restore(&db_path, &backup.path, Utc::now()).unwrap();
+        assert_eq!(fs::read(&moved).unwrap(), garbage);
+        assert_eq!(check(&db_path).await, Health::Ok);
+        let db = LocalDb::new(&db_path).await.unwrap();
+        assert_eq!(db.list_todos(false).await.unwrap()[0].title, "Keep me");
+    }
+}
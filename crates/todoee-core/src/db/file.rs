@@ -0,0 +1,701 @@
+//! A sync backend in a directory of JSON Lines changelogs, for syncing
+//! through Dropbox, Syncthing or a git repository instead of Postgres.
+//!
+//! Each device appends to its own `<device id>.jsonl` and reads everyone's,
+//! so no two devices write the same file and file syncers never see a
+//! conflict. Every line is one change; replaying all of them in time order
+//! gives the current state, with the same last-write-wins rules as
+//! [`RemoteDb`](super::RemoteDb). Lines that can't be read, such as a
+//! half-synced last line or a change from a newer todoee, are skipped.
+//!
+//! Accounts and sessions are changes too, so `todoee login` works the same,
+//! but anyone who can read the folder can read every user's todos.
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::backend::{SyncBackend, Upserted};
+use super::remote::TodoCursor;
+use crate::models::{Category, Device, Project, SyncStatus, Todo, User};
+use crate::{Result as TodoeeResult, TodoeeError};
+
+/// Changelog format this build writes, reported as the schema version
+pub const FORMAT_VERSION: i32 = 1;
+
+/// Extension of changelog files
+const EXTENSION: &str = "jsonl";
+
+/// One line of a changelog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Record {
+    at: DateTime<Utc>,
+    #[serde(flatten)]
+    change: Change,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+enum Change {
+    Todo {
+        todo: Box<Todo>,
+    },
+    TodoDeleted {
+        id: Uuid,
+    },
+    Category {
+        category: Category,
+    },
+    Project {
+        project: Project,
+    },
+    Device {
+        device: Device,
+    },
+    DeviceRevoked {
+        id: Uuid,
+    },
+    User {
+        user: User,
+    },
+    DeviceLinked {
+        device_id: Uuid,
+        user_id: Uuid,
+    },
+    Password {
+        user_id: Uuid,
+        hash: String,
+    },
+    Claimed {
+        user_id: Uuid,
+    },
+    SessionStarted {
+        token_hash: String,
+        user_id: Uuid,
+        device_id: Uuid,
+    },
+    SessionEnded {
+        token_hash: String,
+    },
+}
+
+/// Everything the changelogs add up to
+#[derive(Debug, Default)]
+struct State {
+    /// Deleted todos included, as their last version
+    todos: HashMap<Uuid, Todo>,
+    /// When each deleted todo was deleted
+    deleted: HashMap<Uuid, DateTime<Utc>>,
+    /// Categories and when they were last written
+    categories: HashMap<Uuid, (Category, DateTime<Utc>)>,
+    projects: HashMap<Uuid, Project>,
+    devices: HashMap<Uuid, Device>,
+    users: Vec<User>,
+    /// User each device syncs as
+    links: HashMap<Uuid, Uuid>,
+    /// Password hash of each user who has logged in
+    passwords: HashMap<Uuid, String>,
+    /// User of each session that hasn't ended, by token hash
+    sessions: HashMap<String, Uuid>,
+}
+
+impl State {
+    /// Apply one change. Returns false if it changes nothing, e.g. a todo
+    /// older than the stored one.
+    fn apply(&mut self, at: DateTime<Utc>, change: Change) -> bool {
+        match change {
+            Change::Todo { todo } => {
+                let stored_at = self
+                    .todos
+                    .get(&todo.id)
+                    .map(|t| t.updated_at)
+                    .max(self.deleted.get(&todo.id).copied());
+                let same_todo = self
+                    .todos
+                    .get(&todo.id)
+                    .is_none_or(|t| t.created_at == todo.created_at && t.user_id == todo.user_id);
+                if !same_todo || stored_at.is_some_and(|at| at >= todo.updated_at) {
+                    return false;
+                }
+                // A newer write restores a deleted todo
+                self.deleted.remove(&todo.id);
+                self.todos.insert(todo.id, *todo);
+                true
+            }
+            Change::TodoDeleted { id } => {
+                // An edit made after the deletion wins, whichever is replayed first
+                if self.deleted.contains_key(&id)
+                    || self.todos.get(&id).is_some_and(|t| t.updated_at > at)
+                {
+                    return false;
+                }
+                self.deleted.insert(id, at);
+                true
+            }
+            Change::Category { category } => {
+                if self.category_named(&category).is_some()
+                    || self
+                        .categories
+                        .get(&category.id)
+                        .is_some_and(|(c, written)| *written >= at || c.user_id != category.user_id)
+                {
+                    return false;
+                }
+                self.categories.insert(category.id, (category, at));
+                true
+            }
+            Change::Project { project } => {
+                if self.project_named(&project).is_some()
+                    || self.projects.get(&project.id).is_some_and(|p| {
+                        p.updated_at >= project.updated_at || p.user_id != project.user_id
+                    })
+                {
+                    return false;
+                }
+                self.projects.insert(project.id, project);
+                true
+            }
+            Change::Device { mut device } => {
+                if let Some(known) = self.devices.get(&device.id) {
+                    device.registered_at = known.registered_at;
+                    device.revoked_at = known.revoked_at;
+                    if known.is_revoked() {
+                        device.last_seen_at = known.last_seen_at;
+                    }
+                }
+                self.devices.insert(device.id, device);
+                true
+            }
+            Change::DeviceRevoked { id } => match self.devices.get_mut(&id) {
+                Some(device) if !device.is_revoked() => {
+                    device.revoked_at = Some(at);
+                    true
+                }
+                _ => false,
+            },
+            Change::User { user } => {
+                if self.users.iter().any(|u| u.email == user.email) {
+                    return false;
+                }
+                self.users.push(user);
+                true
+            }
+            Change::DeviceLinked { device_id, user_id } => {
+                self.links.insert(device_id, user_id) != Some(user_id)
+            }
+            Change::Password { user_id, hash } => {
+                self.passwords.insert(user_id, hash.clone()) != Some(hash)
+            }
+            Change::Claimed { user_id } => {
+                if !self.can_claim(user_id) {
+                    return false;
+                }
+                for todo in self.todos.values_mut().filter(|t| t.user_id.is_none()) {
+                    todo.user_id = Some(user_id);
+                }
+                for (category, _) in self.categories.values_mut() {
+                    if category.user_id.is_nil() {
+                        category.user_id = user_id;
+                    }
+                }
+                for project in self.projects.values_mut().filter(|p| p.user_id.is_nil()) {
+                    project.user_id = user_id;
+                }
+                true
+            }
+            Change::SessionStarted {
+                token_hash,
+                user_id,
+                device_id: _,
+            } => self.sessions.insert(token_hash, user_id).is_none(),
+            Change::SessionEnded { token_hash } => self.sessions.remove(&token_hash).is_some(),
+        }
+    }
+
+    /// Whether `user_id` may claim rows without an owner: only while no
+    /// other user has a password
+    fn can_claim(&self, user_id: Uuid) -> bool {
+        self.passwords.keys().all(|&id| id == user_id)
+    }
+
+    /// ID of another live category of the same user with this name
+    fn category_named(&self, category: &Category) -> Option<Uuid> {
+        self.categories
+            .values()
+            .map(|(c, _)| c)
+            .find(|c| {
+                c.id != category.id && c.user_id == category.user_id && c.name == category.name
+            })
+            .map(|c| c.id)
+    }
+
+    /// ID of another project of the same user with this name
+    fn project_named(&self, project: &Project) -> Option<Uuid> {
+        self.projects
+            .values()
+            .find(|p| p.id != project.id && p.user_id == project.user_id && p.name == project.name)
+            .map(|p| p.id)
+    }
+}
+
+/// Changelogs in a directory, written as one device
+pub struct FileBackend {
+    dir: PathBuf,
+    /// This device's changelog
+    path: PathBuf,
+    state: Mutex<State>,
+}
+
+impl FileBackend {
+    /// Read every changelog in `dir`; changes are written to the one for
+    /// `device_id`. The directory is created if missing.
+    pub fn open(dir: &Path, device_id: Uuid) -> TodoeeResult<Self> {
+        fs::create_dir_all(dir).map_err(|e| io_error("create", dir, e))?;
+
+        let mut records = Vec::new();
+        for entry in fs::read_dir(dir).map_err(|e| io_error("read", dir, e))? {
+            let path = entry.map_err(|e| io_error("read", dir, e))?.path();
+            if path.extension().is_none_or(|ext| ext != EXTENSION) {
+                continue;
+            }
+            let content = fs::read_to_string(&path).map_err(|e| io_error("read", &path, e))?;
+            for (number, line) in content.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<Record>(line) {
+                    Ok(record) => records.push(record),
+                    Err(e) => tracing::warn!(
+                        file = %path.display(),
+                        line = number + 1,
+                        error = %e,
+                        "Skipping unreadable sync change"
+                    ),
+                }
+            }
+        }
+        // Stable, so one device's changes at the same instant keep their order
+        records.sort_by_key(|record| record.at);
+
+        let mut state = State::default();
+        for record in records {
+            state.apply(record.at, record.change);
+        }
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            path: dir.join(format!("{}.{}", device_id, EXTENSION)),
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Apply `change` and, if it changed anything, append it to this
+    /// device's changelog
+    fn record(&self, change: Change) -> TodoeeResult<bool> {
+        let record = Record {
+            at: Utc::now(),
+            change,
+        };
+        if !self.state().apply(record.at, record.change.clone()) {
+            return Ok(false);
+        }
+        let mut line = serde_json::to_string(&record).map_err(|e| {
+            TodoeeError::InvalidInput(format!("Failed to serialize sync change: {}", e))
+        })?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| io_error("write", &self.path, e))?;
+        Ok(true)
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait]
+impl SyncBackend for FileBackend {
+    async fn ping(&self) -> TodoeeResult<Duration> {
+        let start = Instant::now();
+        fs::read_dir(&self.dir).map_err(|e| io_error("read", &self.dir, e))?;
+        Ok(start.elapsed())
+    }
+
+    async fn schema_version(&self) -> TodoeeResult<i32> {
+        Ok(FORMAT_VERSION)
+    }
+
+    async fn upsert_todo(&self, todo: &Todo) -> TodoeeResult<bool> {
+        self.record(Change::Todo {
+            todo: Box::new(todo.clone()),
+        })
+    }
+
+    async fn get_todos_page(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+        after: Option<TodoCursor>,
+        limit: u32,
+    ) -> TodoeeResult<Vec<Todo>> {
+        let state = self.state();
+        let mut todos: Vec<Todo> = state
+            .todos
+            .values()
+            .filter(|t| !state.deleted.contains_key(&t.id) && t.updated_at > since)
+            .filter(|t| owner.is_none() || t.user_id == owner)
+            .filter(|t| after.is_none_or(|cursor| (t.updated_at, t.id) > cursor))
+            .cloned()
+            .collect();
+        todos.sort_by_key(|t| (t.updated_at, t.id));
+        todos.truncate(limit as usize);
+        Ok(todos)
+    }
+
+    async fn get_todos(&self, owner: Option<Uuid>, ids: &[Uuid]) -> TodoeeResult<Vec<Todo>> {
+        let state = self.state();
+        Ok(ids
+            .iter()
+            .filter(|id| !state.deleted.contains_key(id))
+            .filter_map(|id| state.todos.get(id))
+            .filter(|t| owner.is_none() || t.user_id == owner)
+            .cloned()
+            .collect())
+    }
+
+    async fn soft_delete_todo(&self, owner: Option<Uuid>, id: Uuid) -> TodoeeResult<()> {
+        let theirs = owner.is_some()
+            && self
+                .state()
+                .todos
+                .get(&id)
+                .is_some_and(|t| t.user_id != owner);
+        if !theirs {
+            self.record(Change::TodoDeleted { id })?;
+        }
+        Ok(())
+    }
+
+    async fn get_deleted_todo_ids_since(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> TodoeeResult<Vec<Uuid>> {
+        let state = self.state();
+        let mut deleted: Vec<(DateTime<Utc>, Uuid)> = state
+            .deleted
+            .iter()
+            .filter(|(_, at)| **at > since)
+            .filter(|(id, _)| {
+                owner.is_none() || state.todos.get(id).is_some_and(|t| t.user_id == owner)
+            })
+            .map(|(id, at)| (*at, *id))
+            .collect();
+        deleted.sort();
+        Ok(deleted.into_iter().map(|(_, id)| id).collect())
+    }
+
+    async fn upsert_category(&self, category: &Category) -> TodoeeResult<Upserted> {
+        if let Some(id) = self.state().category_named(category) {
+            return Ok(Upserted::NameTaken(Some(id)));
+        }
+        self.record(Change::Category {
+            category: category.clone(),
+        })?;
+        Ok(Upserted::Stored)
+    }
+
+    async fn get_categories_since(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> TodoeeResult<Vec<Category>> {
+        let mut categories: Vec<(Category, DateTime<Utc>)> = self
+            .state()
+            .categories
+            .values()
+            .filter(|(_, written)| *written > since)
+            .filter(|(c, _)| owner.is_none_or(|owner| c.user_id == owner))
+            .cloned()
+            .collect();
+        categories.sort_by_key(|(_, written)| *written);
+        Ok(categories
+            .into_iter()
+            .map(|(mut category, _)| {
+                category.sync_status = SyncStatus::Synced;
+                category
+            })
+            .collect())
+    }
+
+    async fn upsert_project(&self, project: &Project) -> TodoeeResult<Upserted> {
+        if let Some(id) = self.state().project_named(project) {
+            return Ok(Upserted::NameTaken(Some(id)));
+        }
+        self.record(Change::Project {
+            project: project.clone(),
+        })?;
+        Ok(Upserted::Stored)
+    }
+
+    async fn get_projects_since(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> TodoeeResult<Vec<Project>> {
+        let mut projects: Vec<Project> = self
+            .state()
+            .projects
+            .values()
+            .filter(|p| p.updated_at > since)
+            .filter(|p| owner.is_none_or(|owner| p.user_id == owner))
+            .cloned()
+            .collect();
+        projects.sort_by_key(|p| p.updated_at);
+        Ok(projects)
+    }
+
+    async fn register_device(&self, id: Uuid, name: &str) -> TodoeeResult<Device> {
+        let now = Utc::now();
+        self.record(Change::Device {
+            device: Device {
+                id,
+                name: name.to_string(),
+                registered_at: now,
+                last_seen_at: now,
+                revoked_at: None,
+            },
+        })?;
+        Ok(self.state().devices[&id].clone())
+    }
+
+    async fn list_devices(&self, owner: Option<Uuid>) -> TodoeeResult<Vec<Device>> {
+        let state = self.state();
+        let mut devices: Vec<Device> = state
+            .devices
+            .values()
+            .filter(|d| owner.is_none() || state.links.get(&d.id).copied() == owner)
+            .cloned()
+            .collect();
+        devices.sort_by_key(|d| std::cmp::Reverse(d.last_seen_at));
+        Ok(devices)
+    }
+
+    async fn revoke_device(&self, owner: Option<Uuid>, id: Uuid) -> TodoeeResult<bool> {
+        if owner.is_some() && self.state().links.get(&id).copied() != owner {
+            return Ok(false);
+        }
+        self.record(Change::DeviceRevoked { id })
+    }
+
+    async fn ensure_user(&self, email: &str) -> TodoeeResult<User> {
+        if let Some(user) = self.state().users.iter().find(|u| u.email == email) {
+            return Ok(user.clone());
+        }
+        let user = User {
+            id: Uuid::new_v4(),
+            email: email.to_string(),
+            created_at: Utc::now(),
+        };
+        self.record(Change::User { user: user.clone() })?;
+        Ok(user)
+    }
+
+    async fn link_device(&self, device_id: Uuid, user_id: Uuid) -> TodoeeResult<()> {
+        self.record(Change::DeviceLinked { device_id, user_id })?;
+        Ok(())
+    }
+
+    async fn find_user(&self, email: &str) -> TodoeeResult<Option<User>> {
+        Ok(self
+            .state()
+            .users
+            .iter()
+            .find(|u| u.email == email)
+            .cloned())
+    }
+
+    async fn password_hash(&self, user_id: Uuid) -> TodoeeResult<Option<String>> {
+        Ok(self.state().passwords.get(&user_id).cloned())
+    }
+
+    async fn set_password_hash(&self, user_id: Uuid, hash: &str) -> TodoeeResult<()> {
+        self.record(Change::Password {
+            user_id,
+            hash: hash.to_string(),
+        })?;
+        Ok(())
+    }
+
+    async fn claim_unowned(&self, user_id: Uuid) -> TodoeeResult<u64> {
+        let claimed = {
+            let state = self.state();
+            if !state.can_claim(user_id) {
+                return Ok(0);
+            }
+            state.todos.values().filter(|t| t.user_id.is_none()).count()
+        };
+        self.record(Change::Claimed { user_id })?;
+        Ok(claimed as u64)
+    }
+
+    async fn start_session(
+        &self,
+        token_hash: &str,
+        user_id: Uuid,
+        device_id: Uuid,
+    ) -> TodoeeResult<()> {
+        self.record(Change::SessionStarted {
+            token_hash: token_hash.to_string(),
+            user_id,
+            device_id,
+        })?;
+        Ok(())
+    }
+
+    async fn session_user(&self, token_hash: &str) -> TodoeeResult<Option<Uuid>> {
+        Ok(self.state().sessions.get(token_hash).copied())
+    }
+
+    async fn end_session(&self, token_hash: &str) -> TodoeeResult<()> {
+        self.record(Change::SessionEnded {
+            token_hash: token_hash.to_string(),
+        })?;
+        Ok(())
+    }
+}
+
+fn io_error(action: &str, path: &Path, e: std::io::Error) -> TodoeeError {
+    TodoeeError::Config(format!(
+        "Failed to {} sync directory {}: {}",
+        action,
+        path.display(),
+        e
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration as ChronoDuration;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_devices_merge_through_changelogs() {
+        let dir = TempDir::new().unwrap();
+        let (laptop, phone) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let backend = FileBackend::open(dir.path(), laptop).unwrap();
+        let mut todo = Todo::new("Water plants".to_string(), None);
+        backend.upsert_todo(&todo).await.unwrap();
+        let gone = Todo::new("Old idea".to_string(), None);
+        backend.upsert_todo(&gone).await.unwrap();
+        backend.soft_delete_todo(None, gone.id).await.unwrap();
+        let work = Category::new(Uuid::nil(), "Work".to_string());
+        assert_eq!(
+            backend.upsert_category(&work).await.unwrap(),
+            Upserted::Stored
+        );
+        backend.register_device(laptop, "laptop").await.unwrap();
+
+        // A stale copy from another device doesn't win
+        let mut stale = todo.clone();
+        stale.title = "Water plant".to_string();
+        stale.updated_at = todo.updated_at - ChronoDuration::minutes(5);
+        let other = FileBackend::open(dir.path(), phone).unwrap();
+        assert!(!other.upsert_todo(&stale).await.unwrap());
+        todo.title = "Water the plants".to_string();
+        todo.updated_at += ChronoDuration::minutes(1);
+        assert!(other.upsert_todo(&todo).await.unwrap());
+        let same_name = Category::new(Uuid::nil(), "Work".to_string());
+        assert_eq!(
+            other.upsert_category(&same_name).await.unwrap(),
+            Upserted::NameTaken(Some(work.id))
+        );
+        assert!(other.revoke_device(None, laptop).await.unwrap());
+
+        // Each device only ever wrote its own file
+        let mut files: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        let mut expected = [format!("{}.jsonl", laptop), format!("{}.jsonl", phone)];
+        expected.sort();
+        assert_eq!(files, expected);
+
+        // Half a line from a file syncer still copying is skipped
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(dir.path().join(format!("{}.jsonl", phone)))
+            .unwrap();
+        file.write_all(br#"{"at":"2026-10-16T"#).unwrap();
+
+        let merged = FileBackend::open(dir.path(), laptop).unwrap();
+        let epoch = DateTime::from_timestamp(0, 0).unwrap();
+        let todos = merged.get_todos_page(None, epoch, None, 10).await.unwrap();
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].title, "Water the plants");
+        assert_eq!(
+            merged
+                .get_deleted_todo_ids_since(None, epoch)
+                .await
+                .unwrap(),
+            [gone.id]
+        );
+        let device = merged.register_device(laptop, "laptop").await.unwrap();
+        assert!(device.is_revoked());
+    }
+
+    #[tokio::test]
+    async fn test_owners_only_reach_their_rows() {
+        let dir = TempDir::new().unwrap();
+        let (laptop, phone) = (Uuid::new_v4(), Uuid::new_v4());
+        let (ada, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let backend = FileBackend::open(dir.path(), laptop).unwrap();
+        backend.register_device(laptop, "laptop").await.unwrap();
+        backend.register_device(phone, "phone").await.unwrap();
+        backend.link_device(laptop, ada).await.unwrap();
+        backend.link_device(phone, bob).await.unwrap();
+
+        let adas = Todo::new("Ada's".to_string(), Some(ada));
+        backend.upsert_todo(&adas).await.unwrap();
+        let mut takeover = adas.clone();
+        takeover.user_id = Some(bob);
+        takeover.updated_at += ChronoDuration::minutes(1);
+        assert!(!backend.upsert_todo(&takeover).await.unwrap());
+        backend.soft_delete_todo(Some(bob), adas.id).await.unwrap();
+
+        let epoch = DateTime::from_timestamp(0, 0).unwrap();
+        let todos = backend
+            .get_todos_page(Some(ada), epoch, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].user_id, Some(ada));
+        assert!(
+            backend
+                .get_todos_page(Some(bob), epoch, None, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let devices = backend.list_devices(Some(ada)).await.unwrap();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].id, laptop);
+        assert!(!backend.revoke_device(Some(ada), phone).await.unwrap());
+        assert!(backend.revoke_device(Some(bob), phone).await.unwrap());
+    }
+}
@@ -1,18 +1,38 @@
 //! Local SQLite database for offline-first storage.
 //!
 //! This module provides `LocalDb`, a wrapper around a SQLite connection pool
-//! that handles CRUD operations for todos and categories.
+//! that handles CRUD operations for todos, categories and projects.
 
+use std::collections::HashSet;
 use std::path::Path;
 use std::str::FromStr;
 
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
 use sqlx::FromRow;
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteConnection, SqliteJournalMode, SqlitePool, SqlitePoolOptions,
+    SqliteSynchronous,
+};
 use uuid::Uuid;
 
-use crate::models::{Category, EntityType, Operation, OperationType, Priority, SyncStatus, Todo};
+use crate::conflict::Conflict;
+use crate::error::TodoeeError;
+use crate::filter::{Filter, FilterNames};
+use crate::models::{
+    ActivityBucket, Attachment, Category, CategoryCount, CommandHistoryEntry, DueProgress,
+    EntityType, Event, FocusInterruption, FocusSession, FocusStats, FocusTime, IdMatch,
+    JournalEntry, Operation, OperationStats, OperationType, PendingAiParse, Priority, Project,
+    ProjectProgress, SyncRun, SyncStatus, Todo, TodoNote, TodoStats, id_prefix,
+};
+use crate::notes::{self, NoteLink};
+use crate::validation;
+
+/// Number of sync attempts kept in `sync_runs`
+const SYNC_RUNS_KEPT: i64 = 50;
+
+/// Number of CLI invocations kept in `command_history`
+const COMMAND_HISTORY_KEPT: i64 = 1000;
 
 /// Helper struct for mapping todo rows from SQLite.
 #[derive(Debug, FromRow)]
@@ -20,10 +40,16 @@ struct TodoRow {
     id: String,
     user_id: Option<String>,
     category_id: Option<String>,
+    project_id: Option<String>,
     title: String,
+    icon: Option<String>,
     description: Option<String>,
     due_date: Option<String>,
     reminder_at: Option<String>,
+    remind_before_minutes: Option<i64>,
+    no_nag: i32,
+    notified_at: Option<String>,
+    external_ref: Option<String>,
     priority: i32,
     is_completed: i32,
     completed_at: Option<String>,
@@ -49,7 +75,13 @@ impl TryFrom<TodoRow> for Todo {
                 .map(|s| Uuid::parse_str(&s))
                 .transpose()
                 .context("Invalid category_id")?,
+            project_id: row
+                .project_id
+                .map(|s| Uuid::parse_str(&s))
+                .transpose()
+                .context("Invalid project_id")?,
             title: row.title,
+            icon: row.icon,
             description: row.description,
             due_date: row
                 .due_date
@@ -61,6 +93,14 @@ impl TryFrom<TodoRow> for Todo {
                 .map(|s| DateTime::parse_from_rfc3339(&s).map(|dt| dt.with_timezone(&Utc)))
                 .transpose()
                 .context("Invalid reminder_at")?,
+            remind_before_minutes: row.remind_before_minutes,
+            no_nag: row.no_nag != 0,
+            notified_at: row
+                .notified_at
+                .map(|s| DateTime::parse_from_rfc3339(&s).map(|dt| dt.with_timezone(&Utc)))
+                .transpose()
+                .context("Invalid notified_at")?,
+            external_ref: row.external_ref,
             priority: Priority::from_i32(row.priority),
             is_completed: row.is_completed != 0,
             completed_at: row
@@ -119,6 +159,46 @@ impl TryFrom<CategoryRow> for Category {
     }
 }
 
+/// Helper struct for mapping project rows from SQLite.
+#[derive(Debug, FromRow)]
+struct ProjectRow {
+    id: String,
+    user_id: Option<String>,
+    name: String,
+    color: Option<String>,
+    archived_at: Option<String>,
+    created_at: String,
+    updated_at: String,
+    sync_status: String,
+}
+
+impl TryFrom<ProjectRow> for Project {
+    type Error = anyhow::Error;
+
+    fn try_from(row: ProjectRow) -> Result<Self> {
+        let parse_time = |s: &str| {
+            DateTime::parse_from_rfc3339(s)
+                .map(|dt| dt.with_timezone(&Utc))
+                .context("Invalid project timestamp")
+        };
+        Ok(Project {
+            id: Uuid::parse_str(&row.id).context("Invalid project id")?,
+            user_id: Uuid::parse_str(&row.user_id.unwrap_or_else(|| Uuid::nil().to_string()))
+                .context("Invalid user_id")?,
+            name: row.name,
+            color: row.color,
+            archived_at: row.archived_at.as_deref().map(parse_time).transpose()?,
+            created_at: parse_time(&row.created_at)?,
+            updated_at: parse_time(&row.updated_at)?,
+            sync_status: match row.sync_status.as_str() {
+                "synced" => SyncStatus::Synced,
+                "conflict" => SyncStatus::Conflict,
+                _ => SyncStatus::Pending,
+            },
+        })
+    }
+}
+
 /// Helper struct for mapping operation rows from SQLite.
 #[derive(Debug, FromRow)]
 struct OperationRow {
@@ -130,6 +210,8 @@ struct OperationRow {
     new_state: Option<String>,
     created_at: String,
     undone: i32,
+    operation_group_id: Option<String>,
+    seq: Option<i64>,
 }
 
 impl TryFrom<OperationRow> for Operation {
@@ -144,6 +226,8 @@ impl TryFrom<OperationRow> for Operation {
             "uncomplete" => OperationType::Uncomplete,
             "stash" => OperationType::Stash,
             "unstash" => OperationType::Unstash,
+            "batch_create" => OperationType::BatchCreate,
+            "summary" => OperationType::Summary,
             _ => anyhow::bail!("Invalid operation type: {}", row.operation_type),
         };
 
@@ -172,10 +256,81 @@ impl TryFrom<OperationRow> for Operation {
                 .map(|dt| dt.with_timezone(&Utc))
                 .context("Invalid created_at")?,
             undone: row.undone != 0,
+            group_id: row
+                .operation_group_id
+                .map(|id| Uuid::parse_str(&id))
+                .transpose()
+                .context("Invalid operation_group_id")?,
+        })
+    }
+}
+
+/// Helper struct for mapping event rows from SQLite.
+#[derive(Debug, FromRow)]
+struct EventRow {
+    id: String,
+    user_id: Option<String>,
+    title: String,
+    description: Option<String>,
+    start_time: String,
+    end_time: String,
+    reminder_at: Option<String>,
+    recurrence_rule: Option<String>,
+    created_at: String,
+    sync_status: String,
+}
+
+impl TryFrom<EventRow> for Event {
+    type Error = anyhow::Error;
+
+    fn try_from(row: EventRow) -> Result<Self> {
+        Ok(Event {
+            id: Uuid::parse_str(&row.id).context("Invalid event id")?,
+            user_id: Uuid::parse_str(&row.user_id.unwrap_or_else(|| Uuid::nil().to_string()))
+                .context("Invalid user_id")?,
+            title: row.title,
+            description: row.description,
+            start_time: DateTime::parse_from_rfc3339(&row.start_time)
+                .map(|dt| dt.with_timezone(&Utc))
+                .context("Invalid start_time")?,
+            end_time: DateTime::parse_from_rfc3339(&row.end_time)
+                .map(|dt| dt.with_timezone(&Utc))
+                .context("Invalid end_time")?,
+            reminder_at: row
+                .reminder_at
+                .map(|s| DateTime::parse_from_rfc3339(&s).map(|dt| dt.with_timezone(&Utc)))
+                .transpose()
+                .context("Invalid reminder_at")?,
+            recurrence_rule: row.recurrence_rule,
+            created_at: DateTime::parse_from_rfc3339(&row.created_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .context("Invalid created_at")?,
+            sync_status: match row.sync_status.as_str() {
+                "synced" => SyncStatus::Synced,
+                "conflict" => SyncStatus::Conflict,
+                _ => SyncStatus::Pending,
+            },
         })
     }
 }
 
+/// Connections kept open by [`LocalDb::new`]
+pub const DEFAULT_POOL_SIZE: u32 = 5;
+
+/// How long a connection waits for another process's write lock before
+/// giving up with SQLITE_BUSY
+const BUSY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Outcome of [`LocalDb::update_todo_if_unchanged`]
+#[derive(Debug)]
+pub enum ConditionalUpdate {
+    Updated,
+    /// The todo changed since it was read; holds the stored version
+    Conflict(Box<Todo>),
+    /// No such todo, or it was deleted
+    NotFound,
+}
+
 /// Local SQLite database for offline-first storage.
 pub struct LocalDb {
     pool: SqlitePool,
@@ -202,11 +357,42 @@ impl LocalDb {
 
     /// Create a file-based database at the specified path.
     pub async fn new(path: &Path) -> Result<Self> {
+        Self::with_pool_size(path, DEFAULT_POOL_SIZE).await
+    }
+
+    /// Open a file-based database keeping up to `pool_size` connections.
+    ///
+    /// The database is put in WAL mode so the CLI, TUI and daemon can read
+    /// while another process writes, and a writer waits for a busy lock
+    /// instead of failing straight away.
+    pub async fn with_pool_size(path: &Path, pool_size: u32) -> Result<Self> {
+        let options = SqliteConnectOptions::from_str(&format!("sqlite:{}", path.display()))?
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .synchronous(SqliteSynchronous::Normal)
+            .busy_timeout(BUSY_TIMEOUT)
+            .foreign_keys(true);
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(pool_size.max(1))
+            .connect_with(options)
+            .await
+            .context("Failed to open database")?;
+
+        Ok(Self { pool })
+    }
+
+    /// Open a database without writing to it, to salvage a damaged file.
+    ///
+    /// With `immutable` SQLite reads the main file as it is and ignores the
+    /// WAL, which still works when the WAL itself can't be opened.
+    pub async fn open_read_only(path: &Path, immutable: bool) -> Result<Self> {
         let options = SqliteConnectOptions::from_str(&format!("sqlite:{}", path.display()))?
-            .create_if_missing(true);
+            .read_only(true)
+            .immutable(immutable);
 
         let pool = SqlitePoolOptions::new()
-            .max_connections(5)
+            .max_connections(1)
             .connect_with(options)
             .await
             .context("Failed to open database")?;
@@ -214,6 +400,63 @@ impl LocalDb {
         Ok(Self { pool })
     }
 
+    /// Close every connection, checkpointing the WAL into the database file.
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
+
+    /// Problems found by SQLite's `quick_check`; empty when the file is sound.
+    pub async fn integrity_problems(&self) -> Result<Vec<String>> {
+        let rows: Vec<(String,)> = sqlx::query_as("PRAGMA quick_check")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to check database integrity")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(row,)| row)
+            .filter(|row| row != "ok")
+            .collect())
+    }
+
+    /// Write a consistent copy of the database to `path`, which must not exist.
+    pub async fn backup_to(&self, path: &Path) -> Result<()> {
+        sqlx::query("VACUUM INTO ?1")
+            .bind(path.to_string_lossy().into_owned())
+            .execute(&self.pool)
+            .await
+            .with_context(|| format!("Failed to back up database to {}", path.display()))?;
+
+        Ok(())
+    }
+
+    /// Todos that can still be read, skipping rows that can't. Used to
+    /// salvage a damaged database, where one bad page shouldn't lose the rest.
+    pub async fn readable_todos(&self) -> Result<Vec<Todo>> {
+        if let Ok(todos) = self.list_todos(false).await {
+            return Ok(todos);
+        }
+
+        let (max,): (Option<i64>,) = sqlx::query_as("SELECT MAX(rowid) FROM todos")
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to read todos")?;
+        let mut todos = Vec::new();
+        for rowid in 1..=max.unwrap_or(0) {
+            let row: std::result::Result<Option<TodoRow>, _> =
+                sqlx::query_as("SELECT * FROM todos WHERE rowid = ?1 AND deleted_at IS NULL")
+                    .bind(rowid)
+                    .fetch_optional(&self.pool)
+                    .await;
+            if let Ok(Some(row)) = row
+                && let Ok(todo) = Todo::try_from(row)
+            {
+                todos.push(todo);
+            }
+        }
+        Ok(todos)
+    }
+
     /// Run database migrations to create tables and indexes.
     pub async fn run_migrations(&self) -> Result<()> {
         // Create categories table
@@ -233,6 +476,32 @@ impl LocalDb {
         .await
         .context("Failed to create categories table")?;
 
+        // Create projects table
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS projects (
+                id TEXT PRIMARY KEY,
+                user_id TEXT,
+                name TEXT NOT NULL,
+                color TEXT,
+                archived_at TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                sync_status TEXT NOT NULL DEFAULT 'pending'
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create projects table")?;
+
+        sqlx::query(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_projects_user_name ON projects(COALESCE(user_id, ''), name)",
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create project name index")?;
+
         // Create todos table
         sqlx::query(
             r#"
@@ -250,7 +519,14 @@ impl LocalDb {
                 ai_metadata TEXT,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
-                sync_status TEXT NOT NULL DEFAULT 'pending'
+                sync_status TEXT NOT NULL DEFAULT 'pending',
+                deleted_at TEXT,
+                icon TEXT,
+                project_id TEXT REFERENCES projects(id),
+                remind_before_minutes INTEGER,
+                external_ref TEXT,
+                no_nag INTEGER NOT NULL DEFAULT 0,
+                notified_at TEXT
             )
             "#,
         )
@@ -258,6 +534,110 @@ impl LocalDb {
         .await
         .context("Failed to create todos table")?;
 
+        // Add deleted_at to todos tables created before soft deletes
+        let has_deleted_at: Option<(i32,)> =
+            sqlx::query_as("SELECT 1 FROM pragma_table_info('todos') WHERE name = 'deleted_at'")
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to inspect todos table")?;
+        if has_deleted_at.is_none() {
+            sqlx::query("ALTER TABLE todos ADD COLUMN deleted_at TEXT")
+                .execute(&self.pool)
+                .await
+                .context("Failed to add deleted_at column")?;
+        }
+
+        // Add icon to todos tables created before per-todo icons
+        let has_icon: Option<(i32,)> =
+            sqlx::query_as("SELECT 1 FROM pragma_table_info('todos') WHERE name = 'icon'")
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to inspect todos table")?;
+        if has_icon.is_none() {
+            sqlx::query("ALTER TABLE todos ADD COLUMN icon TEXT")
+                .execute(&self.pool)
+                .await
+                .context("Failed to add icon column")?;
+        }
+
+        // Add project_id to todos tables created before projects
+        let has_project_id: Option<(i32,)> =
+            sqlx::query_as("SELECT 1 FROM pragma_table_info('todos') WHERE name = 'project_id'")
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to inspect todos table")?;
+        if has_project_id.is_none() {
+            sqlx::query("ALTER TABLE todos ADD COLUMN project_id TEXT REFERENCES projects(id)")
+                .execute(&self.pool)
+                .await
+                .context("Failed to add project_id column")?;
+        }
+
+        // Add remind_before_minutes to todos tables created before per-todo advance notice
+        let has_remind_before: Option<(i32,)> = sqlx::query_as(
+            "SELECT 1 FROM pragma_table_info('todos') WHERE name = 'remind_before_minutes'",
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to inspect todos table")?;
+        if has_remind_before.is_none() {
+            sqlx::query("ALTER TABLE todos ADD COLUMN remind_before_minutes INTEGER")
+                .execute(&self.pool)
+                .await
+                .context("Failed to add remind_before_minutes column")?;
+        }
+
+        // Add external_ref to todos tables created before GitHub issue links
+        let has_external_ref: Option<(i32,)> =
+            sqlx::query_as("SELECT 1 FROM pragma_table_info('todos') WHERE name = 'external_ref'")
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to inspect todos table")?;
+        if has_external_ref.is_none() {
+            sqlx::query("ALTER TABLE todos ADD COLUMN external_ref TEXT")
+                .execute(&self.pool)
+                .await
+                .context("Failed to add external_ref column")?;
+        }
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_todos_external_ref ON todos(external_ref) WHERE external_ref IS NOT NULL",
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create external_ref index")?;
+
+        // Add no_nag to todos tables created before repeat notifications
+        let has_no_nag: Option<(i32,)> =
+            sqlx::query_as("SELECT 1 FROM pragma_table_info('todos') WHERE name = 'no_nag'")
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to inspect todos table")?;
+        if has_no_nag.is_none() {
+            sqlx::query("ALTER TABLE todos ADD COLUMN no_nag INTEGER NOT NULL DEFAULT 0")
+                .execute(&self.pool)
+                .await
+                .context("Failed to add no_nag column")?;
+        }
+
+        // Add notified_at to todos tables created before the daemon kept it.
+        // Reminders already past count as sent, so they don't all fire at once.
+        let has_notified_at: Option<(i32,)> =
+            sqlx::query_as("SELECT 1 FROM pragma_table_info('todos') WHERE name = 'notified_at'")
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to inspect todos table")?;
+        if has_notified_at.is_none() {
+            sqlx::query("ALTER TABLE todos ADD COLUMN notified_at TEXT")
+                .execute(&self.pool)
+                .await
+                .context("Failed to add notified_at column")?;
+            sqlx::query("UPDATE todos SET notified_at = reminder_at WHERE reminder_at <= ?1")
+                .bind(Utc::now().to_rfc3339())
+                .execute(&self.pool)
+                .await
+                .context("Failed to backfill notified_at")?;
+        }
+
         // Create indexes
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_todos_due_date ON todos(due_date)")
             .execute(&self.pool)
@@ -269,6 +649,25 @@ impl LocalDb {
             .await
             .context("Failed to create sync_status index")?;
 
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_todos_deleted_at ON todos(deleted_at)")
+            .execute(&self.pool)
+            .await
+            .context("Failed to create deleted_at index")?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_todos_project_id ON todos(project_id)")
+            .execute(&self.pool)
+            .await
+            .context("Failed to create project_id index")?;
+
+        // Category names are unique per user (todos must exist to merge duplicates)
+        self.merge_duplicate_categories().await?;
+        sqlx::query(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_categories_user_name ON categories(COALESCE(user_id, ''), name)",
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create category name index")?;
+
         // Create operations table for undo/redo and analytics
         sqlx::query(
             r#"
@@ -295,6 +694,60 @@ impl LocalDb {
         .await
         .context("Failed to create operations created_at index")?;
 
+        // Add operation_group_id to operations tables created before grouped undo
+        let has_group_id: Option<(i32,)> = sqlx::query_as(
+            "SELECT 1 FROM pragma_table_info('operations') WHERE name = 'operation_group_id'",
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to inspect operations table")?;
+        if has_group_id.is_none() {
+            sqlx::query("ALTER TABLE operations ADD COLUMN operation_group_id TEXT")
+                .execute(&self.pool)
+                .await
+                .context("Failed to add operation_group_id column")?;
+        }
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_operations_group_id ON operations(operation_group_id)",
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create operations group index")?;
+
+        // Add seq to operations tables created before the undo stack was
+        // ordered by it. Rows are numbered in the order they were recorded,
+        // and undone operations a later change was made on top of are dropped.
+        let has_seq: Option<(i32,)> =
+            sqlx::query_as("SELECT 1 FROM pragma_table_info('operations') WHERE name = 'seq'")
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to inspect operations table")?;
+        if has_seq.is_none() {
+            sqlx::query("ALTER TABLE operations ADD COLUMN seq INTEGER")
+                .execute(&self.pool)
+                .await
+                .context("Failed to add seq column")?;
+            sqlx::query("UPDATE operations SET seq = rowid")
+                .execute(&self.pool)
+                .await
+                .context("Failed to number operations")?;
+            sqlx::query(
+                r#"
+                DELETE FROM operations
+                WHERE undone = 1 AND seq < (SELECT MAX(seq) FROM operations WHERE undone = 0)
+                "#,
+            )
+            .execute(&self.pool)
+            .await
+            .context("Failed to drop stale redoable operations")?;
+        }
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_operations_seq ON operations(seq DESC)")
+            .execute(&self.pool)
+            .await
+            .context("Failed to create operations seq index")?;
+
         // Create stash table for temporarily storing todos
         sqlx::query(
             r#"
@@ -324,74 +777,375 @@ impl LocalDb {
         .await
         .context("Failed to create deleted_todos table")?;
 
-        Ok(())
-    }
+        // Create events table for calendar entries
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS events (
+                id TEXT PRIMARY KEY,
+                user_id TEXT,
+                title TEXT NOT NULL,
+                description TEXT,
+                start_time TEXT NOT NULL,
+                end_time TEXT NOT NULL,
+                reminder_at TEXT,
+                recurrence_rule TEXT,
+                created_at TEXT NOT NULL,
+                sync_status TEXT NOT NULL DEFAULT 'pending'
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create events table")?;
 
-    // ==================== Todo CRUD Operations ====================
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_events_start_time ON events(start_time)")
+            .execute(&self.pool)
+            .await
+            .context("Failed to create events start_time index")?;
 
-    /// Create a new todo in the database.
-    pub async fn create_todo(&self, todo: &Todo) -> Result<()> {
-        let priority_val = match todo.priority {
-            Priority::Low => 1,
-            Priority::Medium => 2,
-            Priority::High => 3,
-        };
+        // Create note_links table: backlinks from todos to markdown checkboxes.
+        // No foreign key, restoring a todo recreates its row.
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS note_links (
+                todo_id TEXT PRIMARY KEY,
+                path TEXT NOT NULL,
+                line INTEGER NOT NULL,
+                text TEXT NOT NULL,
+                two_way INTEGER NOT NULL DEFAULT 0
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create note_links table")?;
 
-        let sync_status = match todo.sync_status {
-            SyncStatus::Pending => "pending",
-            SyncStatus::Synced => "synced",
-            SyncStatus::Conflict => "conflict",
-        };
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_note_links_path ON note_links(path)")
+            .execute(&self.pool)
+            .await
+            .context("Failed to create note_links path index")?;
 
+        // Create notes table: progress notes appended to todos. Like note_links
+        // no foreign key, so notes survive a delete that is later undone.
         sqlx::query(
             r#"
-            INSERT INTO todos (
-                id, user_id, category_id, title, description, due_date, reminder_at,
-                priority, is_completed, completed_at, ai_metadata, created_at, updated_at, sync_status
-            ) VALUES (
-                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14
+            CREATE TABLE IF NOT EXISTS notes (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                todo_id TEXT NOT NULL,
+                body TEXT NOT NULL,
+                created_at TEXT NOT NULL
             )
             "#,
         )
-        .bind(todo.id.to_string())
-        .bind(todo.user_id.map(|u| u.to_string()))
-        .bind(todo.category_id.map(|c| c.to_string()))
-        .bind(&todo.title)
-        .bind(&todo.description)
-        .bind(todo.due_date.map(|d| d.to_rfc3339()))
-        .bind(todo.reminder_at.map(|r| r.to_rfc3339()))
-        .bind(priority_val)
-        .bind(if todo.is_completed { 1 } else { 0 })
-        .bind(todo.completed_at.map(|c| c.to_rfc3339()))
-        .bind(todo.ai_metadata.as_ref().map(|m| m.to_string()))
-        .bind(todo.created_at.to_rfc3339())
-        .bind(todo.updated_at.to_rfc3339())
-        .bind(sync_status)
         .execute(&self.pool)
         .await
-        .context("Failed to create todo")?;
+        .context("Failed to create notes table")?;
 
-        Ok(())
-    }
-
-    /// Get a todo by its ID.
-    pub async fn get_todo(&self, id: Uuid) -> Result<Option<Todo>> {
-        let row: Option<TodoRow> = sqlx::query_as("SELECT * FROM todos WHERE id = ?1")
-            .bind(id.to_string())
-            .fetch_optional(&self.pool)
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_notes_todo_id ON notes(todo_id)")
+            .execute(&self.pool)
             .await
-            .context("Failed to fetch todo")?;
+            .context("Failed to create notes todo_id index")?;
 
-        row.map(|r| r.try_into()).transpose()
+        // Create attachments table: files and URLs linked to todos
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS attachments (
+                id TEXT PRIMARY KEY,
+                todo_id TEXT NOT NULL,
+                target TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create attachments table")?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_attachments_todo_id ON attachments(todo_id)")
+            .execute(&self.pool)
+            .await
+            .context("Failed to create attachments todo_id index")?;
+
+        // Create focus session tables: timer runs and what interrupted them
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS focus_sessions (
+                id TEXT PRIMARY KEY,
+                todo_id TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                ended_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create focus_sessions table")?;
+
+        // Planned length and outcome, for sessions table created before they were kept
+        for (column, definition) in [
+            ("planned_minutes", "planned_minutes INTEGER"),
+            ("outcome", "outcome TEXT"),
+        ] {
+            let exists: Option<(i32,)> =
+                sqlx::query_as("SELECT 1 FROM pragma_table_info('focus_sessions') WHERE name = ?1")
+                    .bind(column)
+                    .fetch_optional(&self.pool)
+                    .await
+                    .context("Failed to inspect focus_sessions table")?;
+            if exists.is_none() {
+                sqlx::query(&format!(
+                    "ALTER TABLE focus_sessions ADD COLUMN {}",
+                    definition
+                ))
+                .execute(&self.pool)
+                .await
+                .with_context(|| format!("Failed to add {} column", column))?;
+            }
+        }
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS focus_interruptions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL REFERENCES focus_sessions(id) ON DELETE CASCADE,
+                at TEXT NOT NULL,
+                reason TEXT
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create focus_interruptions table")?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_focus_interruptions_session_id ON focus_interruptions(session_id)",
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create focus_interruptions session_id index")?;
+
+        // Create ai_cache table: parsed AI results keyed by a hash of the input
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS ai_cache (
+                key TEXT PRIMARY KEY,
+                response TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                expires_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create ai_cache table")?;
+
+        // Create sync_meta table: per-database settings such as the device ID
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS sync_meta (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create sync_meta table")?;
+
+        // Create sync_runs table: recent sync attempts for the sync health view
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS sync_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                finished_at TEXT NOT NULL,
+                uploaded INTEGER NOT NULL,
+                downloaded INTEGER NOT NULL,
+                conflict_ids TEXT NOT NULL,
+                error TEXT
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create sync_runs table")?;
+
+        // Create conflicts table: the remote version of todos changed both
+        // here and elsewhere, kept until the conflict is resolved
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS conflicts (
+                todo_id TEXT PRIMARY KEY,
+                remote TEXT NOT NULL,
+                detected_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create conflicts table")?;
+
+        // Create ai_parse_queue table: AI parses to retry once back online
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS ai_parse_queue (
+                todo_id TEXT PRIMARY KEY,
+                input TEXT NOT NULL,
+                queued_at TEXT NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create ai_parse_queue table")?;
+
+        // Create command_history table: CLI invocations for `history` and `repeat`
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS command_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                args TEXT NOT NULL,
+                invoked_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create command_history table")?;
+
+        // Create journal table: one reflection note per local day
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS journal (
+                date TEXT PRIMARY KEY,
+                note TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create journal table")?;
+
+        Ok(())
+    }
+
+    // ==================== Todo CRUD Operations ====================
+
+    /// Create a new todo in the database, if it passes [`validation`].
+    pub async fn create_todo(&self, todo: &Todo) -> Result<()> {
+        check_todo(&self.pool, todo, None).await?;
+        insert_todo(&self.pool, todo).await
+    }
+
+    /// Store a todo downloaded by sync without validating it: it was checked
+    /// on the device that made it, and rejecting it here would stall sync.
+    pub(crate) async fn create_synced_todo(&self, todo: &Todo) -> Result<()> {
+        insert_todo(&self.pool, todo).await
+    }
+
+    /// Create several todos and the operation recording them in one transaction.
+    /// Either everything is saved or nothing is.
+    pub async fn create_todos(&self, todos: &[Todo], op: &Operation) -> Result<()> {
+        for todo in todos {
+            check_todo(&self.pool, todo, None).await?;
+        }
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        for todo in todos {
+            insert_todo(&mut *tx, todo).await?;
+        }
+        push_operation(&mut tx, op).await?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Get a todo by its ID. Soft-deleted todos are not returned.
+    pub async fn get_todo(&self, id: Uuid) -> Result<Option<Todo>> {
+        let row: Option<TodoRow> =
+            sqlx::query_as("SELECT * FROM todos WHERE id = ?1 AND deleted_at IS NULL")
+                .bind(id.to_string())
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to fetch todo")?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Todos linked elsewhere with an `external_ref` starting with `prefix`,
+    /// e.g. `github:owner/repo#`. Soft-deleted todos are not returned.
+    pub async fn list_linked_todos(&self, prefix: &str) -> Result<Vec<Todo>> {
+        let rows: Vec<TodoRow> = sqlx::query_as(
+            "SELECT * FROM todos WHERE substr(external_ref, 1, length(?1)) = ?1 AND deleted_at IS NULL",
+        )
+        .bind(prefix)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list linked todos")?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// Every `external_ref` starting with `prefix`, deleted todos included, so
+    /// what was deleted on purpose isn't imported again
+    pub async fn external_refs(&self, prefix: &str) -> Result<HashSet<String>> {
+        let refs: Vec<(String,)> = sqlx::query_as(
+            "SELECT external_ref FROM todos WHERE substr(external_ref, 1, length(?1)) = ?1",
+        )
+        .bind(prefix)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list external refs")?;
+
+        Ok(refs.into_iter().map(|(r,)| r).collect())
+    }
+
+    /// IDs of all todos that aren't deleted, for working out short ID lengths.
+    pub async fn todo_ids(&self) -> Result<Vec<Uuid>> {
+        let ids: Vec<(String,)> = sqlx::query_as("SELECT id FROM todos WHERE deleted_at IS NULL")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to list todo ids")?;
+
+        ids.into_iter()
+            .map(|(id,)| Uuid::parse_str(&id).context("Invalid todo id"))
+            .collect()
+    }
+
+    /// Find the todo whose ID starts with `prefix`, ignoring case. Completed
+    /// todos count; deleted ones don't.
+    pub async fn resolve_short_id(&self, prefix: &str) -> Result<IdMatch> {
+        let Some(prefix) = id_prefix(prefix) else {
+            return Ok(IdMatch::NotFound);
+        };
+
+        let rows: Vec<TodoRow> = sqlx::query_as(
+            "SELECT * FROM todos WHERE id LIKE ?1 AND deleted_at IS NULL ORDER BY created_at DESC",
+        )
+        .bind(format!("{}%", prefix))
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to look up todo by ID")?;
+
+        let todos = rows
+            .into_iter()
+            .map(Todo::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(IdMatch::from_matches(todos))
     }
 
     /// List todos, optionally excluding completed ones.
     /// If `exclude_completed` is true, only non-completed todos are returned.
     pub async fn list_todos(&self, exclude_completed: bool) -> Result<Vec<Todo>> {
         let query = if exclude_completed {
-            "SELECT * FROM todos WHERE is_completed = 0 ORDER BY created_at DESC"
+            "SELECT * FROM todos WHERE is_completed = 0 AND deleted_at IS NULL ORDER BY created_at DESC"
         } else {
-            "SELECT * FROM todos ORDER BY created_at DESC"
+            "SELECT * FROM todos WHERE deleted_at IS NULL ORDER BY created_at DESC"
         };
 
         let rows: Vec<TodoRow> = sqlx::query_as(query)
@@ -402,6 +1156,63 @@ impl LocalDb {
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
+    /// List todos matching `filter`, newest first. What SQLite can check is
+    /// checked in the query; the rest is checked here, with `now` deciding
+    /// `due` terms in its time zone.
+    pub async fn list_todos_filtered<Tz: TimeZone>(
+        &self,
+        filter: &Filter,
+        exclude_completed: bool,
+        now: &DateTime<Tz>,
+    ) -> Result<Vec<Todo>> {
+        let mut query = String::from("SELECT * FROM todos WHERE deleted_at IS NULL");
+        if exclude_completed {
+            query.push_str(" AND is_completed = 0");
+        }
+        let sql = filter.to_sql(now);
+        if let Some(sql) = &sql {
+            query.push_str(&format!(" AND ({})", sql.condition));
+        }
+        query.push_str(" ORDER BY created_at DESC");
+
+        let mut rows = sqlx::query_as::<_, TodoRow>(&query);
+        for param in sql.iter().flat_map(|sql| &sql.params) {
+            rows = rows.bind(param);
+        }
+        let rows = rows
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to list todos")?;
+
+        let names = self.filter_names().await?;
+        let mut todos = Vec::with_capacity(rows.len());
+        for row in rows {
+            let todo: Todo = row.try_into()?;
+            if filter.matches(&todo, &names, now) {
+                todos.push(todo);
+            }
+        }
+        Ok(todos)
+    }
+
+    /// Category and project names, for [`Filter::matches`]
+    pub async fn filter_names(&self) -> Result<FilterNames> {
+        Ok(FilterNames {
+            categories: self
+                .list_categories()
+                .await?
+                .into_iter()
+                .map(|c| (c.id, c.name))
+                .collect(),
+            projects: self
+                .list_projects(true)
+                .await?
+                .into_iter()
+                .map(|p| (p.id, p.name))
+                .collect(),
+        })
+    }
+
     /// List all todos due today.
     pub async fn list_todos_due_today(&self) -> Result<Vec<Todo>> {
         let today = Utc::now().date_naive();
@@ -417,7 +1228,7 @@ impl LocalDb {
         let end_str = DateTime::<Utc>::from_naive_utc_and_offset(end, Utc).to_rfc3339();
 
         let rows: Vec<TodoRow> = sqlx::query_as(
-            "SELECT * FROM todos WHERE due_date >= ?1 AND due_date <= ?2 ORDER BY due_date ASC",
+            "SELECT * FROM todos WHERE due_date >= ?1 AND due_date <= ?2 AND deleted_at IS NULL ORDER BY due_date ASC",
         )
         .bind(start_str)
         .bind(end_str)
@@ -431,7 +1242,7 @@ impl LocalDb {
     /// List all todos in a specific category.
     pub async fn list_todos_by_category(&self, category_id: Uuid) -> Result<Vec<Todo>> {
         let rows: Vec<TodoRow> =
-            sqlx::query_as("SELECT * FROM todos WHERE category_id = ?1 ORDER BY created_at DESC")
+            sqlx::query_as("SELECT * FROM todos WHERE category_id = ?1 AND deleted_at IS NULL ORDER BY created_at DESC")
                 .bind(category_id.to_string())
                 .fetch_all(&self.pool)
                 .await
@@ -440,10 +1251,22 @@ impl LocalDb {
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
+    /// List all todos in a specific project.
+    pub async fn list_todos_by_project(&self, project_id: Uuid) -> Result<Vec<Todo>> {
+        let rows: Vec<TodoRow> =
+            sqlx::query_as("SELECT * FROM todos WHERE project_id = ?1 AND deleted_at IS NULL ORDER BY created_at DESC")
+                .bind(project_id.to_string())
+                .fetch_all(&self.pool)
+                .await
+                .context("Failed to list todos by project")?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
     /// List all todos pending sync.
     pub async fn list_pending_sync(&self) -> Result<Vec<Todo>> {
         let rows: Vec<TodoRow> = sqlx::query_as(
-            "SELECT * FROM todos WHERE sync_status = 'pending' ORDER BY updated_at ASC",
+            "SELECT * FROM todos WHERE sync_status = 'pending' AND deleted_at IS NULL ORDER BY updated_at ASC",
         )
         .fetch_all(&self.pool)
         .await
@@ -454,108 +1277,165 @@ impl LocalDb {
 
     /// Update an existing todo.
     pub async fn update_todo(&self, todo: &Todo) -> Result<()> {
-        let priority_val = match todo.priority {
-            Priority::Low => 1,
-            Priority::Medium => 2,
-            Priority::High => 3,
-        };
+        let previous = self.get_todo(todo.id).await?;
+        check_todo(&self.pool, todo, previous.as_ref()).await?;
+        self.update_synced_todo(todo).await
+    }
 
-        let sync_status = match todo.sync_status {
-            SyncStatus::Pending => "pending",
-            SyncStatus::Synced => "synced",
-            SyncStatus::Conflict => "conflict",
-        };
+    /// [`LocalDb::update_todo`] without validation, for todos downloaded by
+    /// sync (see [`LocalDb::create_synced_todo`]).
+    pub(crate) async fn update_synced_todo(&self, todo: &Todo) -> Result<()> {
+        write_todo_update(&self.pool, todo).await?;
 
-        sqlx::query(
-            r#"
-            UPDATE todos SET
-                user_id = ?1,
-                category_id = ?2,
-                title = ?3,
-                description = ?4,
-                due_date = ?5,
-                reminder_at = ?6,
-                priority = ?7,
-                is_completed = ?8,
-                completed_at = ?9,
-                ai_metadata = ?10,
-                updated_at = ?11,
-                sync_status = ?12
-            WHERE id = ?13
-            "#,
-        )
-        .bind(todo.user_id.map(|u| u.to_string()))
-        .bind(todo.category_id.map(|c| c.to_string()))
-        .bind(&todo.title)
-        .bind(&todo.description)
-        .bind(todo.due_date.map(|d| d.to_rfc3339()))
-        .bind(todo.reminder_at.map(|r| r.to_rfc3339()))
-        .bind(priority_val)
-        .bind(if todo.is_completed { 1 } else { 0 })
-        .bind(todo.completed_at.map(|c| c.to_rfc3339()))
-        .bind(todo.ai_metadata.as_ref().map(|m| m.to_string()))
-        .bind(todo.updated_at.to_rfc3339())
-        .bind(sync_status)
-        .bind(todo.id.to_string())
-        .execute(&self.pool)
-        .await
-        .context("Failed to update todo")?;
+        // The todo is saved; a note that can't be updated only gets a warning
+        if let Err(e) = self.sync_note_checkbox(todo).await {
+            tracing::warn!(error = %e, todo = %todo.id, "Failed to update markdown checkbox");
+        }
 
         Ok(())
     }
 
-    /// Mark a todo as synced.
+    /// Update a todo only if it hasn't changed since the caller read it, i.e.
+    /// the stored `updated_at` is still `expected`. Lets clients that hold a
+    /// todo for a while (external GUIs) save without overwriting someone
+    /// else's edit; on a conflict they get the stored todo to merge with.
+    ///
+    /// `new_category`, which `todo` may reference, is created in the same
+    /// transaction once the check passes, so a refused update leaves none behind.
+    pub async fn update_todo_if_unchanged(
+        &self,
+        todo: &Todo,
+        expected: DateTime<Utc>,
+        new_category: Option<&Category>,
+    ) -> Result<ConditionalUpdate> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        let row: Option<TodoRow> =
+            sqlx::query_as("SELECT * FROM todos WHERE id = ?1 AND deleted_at IS NULL")
+                .bind(todo.id.to_string())
+                .fetch_optional(&mut *tx)
+                .await
+                .context("Failed to fetch todo")?;
+        let Some(current) = row.map(Todo::try_from).transpose()? else {
+            return Ok(ConditionalUpdate::NotFound);
+        };
+        if current.updated_at != expected {
+            return Ok(ConditionalUpdate::Conflict(Box::new(current)));
+        }
+        if let Some(category) = new_category {
+            insert_category(&mut *tx, category).await?;
+        }
+        check_todo(&mut *tx, todo, Some(&current)).await?;
+
+        write_todo_update(&mut *tx, todo).await?;
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        if let Err(e) = self.sync_note_checkbox(todo).await {
+            tracing::warn!(error = %e, todo = %todo.id, "Failed to update markdown checkbox");
+        }
+
+        Ok(ConditionalUpdate::Updated)
+    }
+
+    /// Mark a todo as synced. A conflict it had is settled by the upload.
     pub async fn mark_synced(&self, id: Uuid) -> Result<()> {
         sqlx::query("UPDATE todos SET sync_status = 'synced' WHERE id = ?1")
             .bind(id.to_string())
             .execute(&self.pool)
             .await
             .context("Failed to mark todo as synced")?;
+        sqlx::query("DELETE FROM conflicts WHERE todo_id = ?1")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to clear conflict")?;
 
         Ok(())
     }
 
-    /// Delete a todo by its ID.
+    /// Soft delete a todo by setting its deleted_at timestamp.
+    ///
+    /// The row is kept so the delete can be undone; `purge_deleted_todos`
+    /// removes it for good.
     pub async fn delete_todo(&self, id: Uuid) -> Result<()> {
-        // Record deletion for sync tracking BEFORE deleting
-        self.record_deleted_todo(id).await?;
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .context("Failed to acquire connection")?;
+        soft_delete_todo(&mut conn, id).await
+    }
 
-        sqlx::query("DELETE FROM todos WHERE id = ?1")
-            .bind(id.to_string())
+    /// Bring back a deleted todo with the given state.
+    ///
+    /// Replaces any soft-deleted row, clears the deletion record and marks the
+    /// todo pending so the restore is synced.
+    pub async fn restore_todo(&self, todo: &Todo) -> Result<()> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .context("Failed to acquire connection")?;
+        restore_todo_row(&mut conn, todo).await
+    }
+
+    /// Count todos soft-deleted more than `days` days ago (0 counts all of them).
+    pub async fn count_deleted_todos(&self, days: i64) -> Result<u64> {
+        let cutoff = Utc::now() - chrono::Duration::days(days);
+        let (count,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM todos WHERE deleted_at IS NOT NULL AND deleted_at <= ?1",
+        )
+        .bind(cutoff.to_rfc3339())
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to count deleted todos")?;
+
+        Ok(count as u64)
+    }
+
+    /// Permanently remove todos soft-deleted more than `days` days ago.
+    /// Returns the number of purged todos.
+    pub async fn purge_deleted_todos(&self, days: i64) -> Result<u64> {
+        let cutoff = Utc::now() - chrono::Duration::days(days);
+        let result =
+            sqlx::query("DELETE FROM todos WHERE deleted_at IS NOT NULL AND deleted_at < ?1")
+                .bind(cutoff.to_rfc3339())
+                .execute(&self.pool)
+                .await
+                .context("Failed to purge deleted todos")?;
+
+        sqlx::query("DELETE FROM note_links WHERE todo_id NOT IN (SELECT id FROM todos)")
             .execute(&self.pool)
             .await
-            .context("Failed to delete todo")?;
+            .context("Failed to purge note links")?;
 
-        Ok(())
+        sqlx::query("DELETE FROM notes WHERE todo_id NOT IN (SELECT id FROM todos)")
+            .execute(&self.pool)
+            .await
+            .context("Failed to purge notes")?;
+
+        sqlx::query("DELETE FROM attachments WHERE todo_id NOT IN (SELECT id FROM todos)")
+            .execute(&self.pool)
+            .await
+            .context("Failed to purge attachments")?;
+
+        sqlx::query("DELETE FROM focus_sessions WHERE todo_id NOT IN (SELECT id FROM todos)")
+            .execute(&self.pool)
+            .await
+            .context("Failed to purge focus sessions")?;
+
+        Ok(result.rows_affected())
     }
 
     // ==================== Category CRUD Operations ====================
 
     /// Create a new category in the database.
     pub async fn create_category(&self, category: &Category) -> Result<()> {
-        let sync_status = match category.sync_status {
-            SyncStatus::Pending => "pending",
-            SyncStatus::Synced => "synced",
-            SyncStatus::Conflict => "conflict",
-        };
-
-        sqlx::query(
-            r#"
-            INSERT INTO categories (id, user_id, name, color, is_ai_generated, sync_status)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
-            "#,
-        )
-        .bind(category.id.to_string())
-        .bind(Some(category.user_id.to_string()))
-        .bind(&category.name)
-        .bind(&category.color)
-        .bind(if category.is_ai_generated { 1 } else { 0 })
-        .bind(sync_status)
-        .execute(&self.pool)
-        .await
-        .context("Failed to create category")?;
-
-        Ok(())
+        insert_category(&self.pool, category).await
     }
 
     /// Get a category by its name.
@@ -579,6 +1459,31 @@ impl LocalDb {
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
+    /// Rename a category and mark it for sync.
+    ///
+    /// Todos reference categories by ID, so they follow the new name without
+    /// being touched. Fails if another category already has the name.
+    pub async fn rename_category(&self, id: Uuid, name: &str) -> Result<()> {
+        let updated =
+            sqlx::query("UPDATE categories SET name = ?1, sync_status = 'pending' WHERE id = ?2")
+                .bind(name)
+                .bind(id.to_string())
+                .execute(&self.pool)
+                .await
+                .map_err(|e| match e {
+                    sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+                        anyhow::anyhow!("Category '{}' already exists", name)
+                    }
+                    e => anyhow::Error::new(e).context("Failed to rename category"),
+                })?
+                .rows_affected();
+        if updated == 0 {
+            anyhow::bail!("Category {} not found", id);
+        }
+
+        Ok(())
+    }
+
     /// Delete a category by ID.
     pub async fn delete_category(&self, id: Uuid) -> Result<()> {
         sqlx::query("DELETE FROM categories WHERE id = ?")
@@ -613,8 +1518,94 @@ impl LocalDb {
         Ok(())
     }
 
-    /// Clear category_id for all todos that belong to a category.
-    /// Call this before deleting a category to prevent orphaned references.
+    /// Give a category a new ID, moving its todos along with it.
+    ///
+    /// Used when sync finds the same category name already exists remotely
+    /// under another ID. The category is marked synced.
+    pub async fn rekey_category(&self, old_id: Uuid, new_id: Uuid) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        // Todos point at the old ID until the second update; check at commit
+        sqlx::query("PRAGMA defer_foreign_keys = ON")
+            .execute(&mut *tx)
+            .await
+            .context("Failed to defer foreign keys")?;
+
+        sqlx::query("UPDATE categories SET id = ?1, sync_status = 'synced' WHERE id = ?2")
+            .bind(new_id.to_string())
+            .bind(old_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to rekey category")?;
+
+        sqlx::query("UPDATE todos SET category_id = ?1 WHERE category_id = ?2")
+            .bind(new_id.to_string())
+            .bind(old_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to move todos to rekeyed category")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(())
+    }
+
+    /// Merge categories that share a name for the same user.
+    ///
+    /// The oldest row survives and todos of the duplicates are moved to it.
+    /// Databases created before names were unique may contain such duplicates.
+    async fn merge_duplicate_categories(&self) -> Result<()> {
+        let duplicates: Vec<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT c.id, (
+                SELECT k.id FROM categories k
+                WHERE COALESCE(k.user_id, '') = COALESCE(c.user_id, '') AND k.name = c.name
+                ORDER BY k.rowid ASC LIMIT 1
+            ) AS keep_id
+            FROM categories c
+            WHERE c.id != keep_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to find duplicate categories")?;
+
+        if duplicates.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        for (id, keep_id) in duplicates {
+            sqlx::query("UPDATE todos SET category_id = ?1 WHERE category_id = ?2")
+                .bind(&keep_id)
+                .bind(&id)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to move todos to merged category")?;
+
+            sqlx::query("DELETE FROM categories WHERE id = ?1")
+                .bind(&id)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to delete duplicate category")?;
+        }
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(())
+    }
+
+    /// Clear category_id for all todos that belong to a category.
+    /// Call this before deleting a category to prevent orphaned references.
     pub async fn clear_category_from_todos(&self, category_id: Uuid) -> Result<u64> {
         let result = sqlx::query("UPDATE todos SET category_id = NULL WHERE category_id = ?")
             .bind(category_id.to_string())
@@ -625,57 +1616,323 @@ impl LocalDb {
         Ok(result.rows_affected())
     }
 
-    // ==================== Operation CRUD Operations ====================
+    // ==================== Project CRUD Operations ====================
+
+    /// Create a new project in the database.
+    pub async fn create_project(&self, project: &Project) -> Result<()> {
+        let sync_status = match project.sync_status {
+            SyncStatus::Pending => "pending",
+            SyncStatus::Synced => "synced",
+            SyncStatus::Conflict => "conflict",
+        };
 
-    /// Record an operation in the history.
-    pub async fn record_operation(&self, op: &Operation) -> Result<()> {
         sqlx::query(
             r#"
-            INSERT INTO operations (
-                id, operation_type, entity_type, entity_id,
-                previous_state, new_state, created_at, undone
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            INSERT INTO projects (id, user_id, name, color, archived_at, created_at, updated_at, sync_status)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
             "#,
         )
-        .bind(op.id.to_string())
-        .bind(op.operation_type.to_string())
-        .bind(op.entity_type.to_string())
-        .bind(op.entity_id.to_string())
-        .bind(op.previous_state.as_ref().map(|v| v.to_string()))
-        .bind(op.new_state.as_ref().map(|v| v.to_string()))
-        .bind(op.created_at.to_rfc3339())
-        .bind(if op.undone { 1 } else { 0 })
+        .bind(project.id.to_string())
+        .bind(Some(project.user_id.to_string()))
+        .bind(&project.name)
+        .bind(&project.color)
+        .bind(project.archived_at.map(|a| a.to_rfc3339()))
+        .bind(project.created_at.to_rfc3339())
+        .bind(project.updated_at.to_rfc3339())
+        .bind(sync_status)
         .execute(&self.pool)
         .await
-        .context("Failed to record operation")?;
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+                anyhow::anyhow!("Project '{}' already exists", project.name)
+            }
+            e => anyhow::Error::new(e).context("Failed to create project"),
+        })?;
 
         Ok(())
     }
 
-    /// Get the last operation that can be undone (not yet undone).
-    pub async fn get_last_undoable_operation(&self) -> Result<Option<Operation>> {
-        let row: Option<OperationRow> = sqlx::query_as(
-            "SELECT * FROM operations WHERE undone = 0 ORDER BY created_at DESC LIMIT 1",
+    /// Update an existing project's name, color, archive state and sync status.
+    pub async fn update_project(&self, project: &Project) -> Result<()> {
+        let sync_status = match project.sync_status {
+            SyncStatus::Pending => "pending",
+            SyncStatus::Synced => "synced",
+            SyncStatus::Conflict => "conflict",
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE projects SET
+                name = ?1,
+                color = ?2,
+                archived_at = ?3,
+                updated_at = ?4,
+                sync_status = ?5
+            WHERE id = ?6
+            "#,
         )
-        .fetch_optional(&self.pool)
+        .bind(&project.name)
+        .bind(&project.color)
+        .bind(project.archived_at.map(|a| a.to_rfc3339()))
+        .bind(project.updated_at.to_rfc3339())
+        .bind(sync_status)
+        .bind(project.id.to_string())
+        .execute(&self.pool)
         .await
-        .context("Failed to fetch last undoable operation")?;
+        .context("Failed to update project")?;
+
+        Ok(())
+    }
+
+    /// Get a project by its ID.
+    pub async fn get_project(&self, id: Uuid) -> Result<Option<Project>> {
+        let row: Option<ProjectRow> = sqlx::query_as("SELECT * FROM projects WHERE id = ?1")
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to fetch project")?;
 
         row.map(|r| r.try_into()).transpose()
     }
 
-    /// Get the last operation that can be redone (already undone).
-    pub async fn get_last_redoable_operation(&self) -> Result<Option<Operation>> {
-        let row: Option<OperationRow> = sqlx::query_as(
-            "SELECT * FROM operations WHERE undone = 1 ORDER BY created_at DESC LIMIT 1",
+    /// Get a project by its name, archived or not.
+    pub async fn get_project_by_name(&self, name: &str) -> Result<Option<Project>> {
+        let row: Option<ProjectRow> = sqlx::query_as("SELECT * FROM projects WHERE name = ?1")
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to fetch project by name")?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// List projects by name, leaving out archived ones unless `include_archived`.
+    pub async fn list_projects(&self, include_archived: bool) -> Result<Vec<Project>> {
+        let query = if include_archived {
+            "SELECT * FROM projects ORDER BY name ASC"
+        } else {
+            "SELECT * FROM projects WHERE archived_at IS NULL ORDER BY name ASC"
+        };
+        let rows: Vec<ProjectRow> = sqlx::query_as(query)
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to list projects")?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// Completed and total todo counts for each active project, by name.
+    pub async fn project_progress(&self) -> Result<Vec<ProjectProgress>> {
+        let counts: Vec<(String, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT project_id, SUM(is_completed), COUNT(*)
+            FROM todos
+            WHERE project_id IS NOT NULL AND deleted_at IS NULL
+            GROUP BY project_id
+            "#,
         )
-        .fetch_optional(&self.pool)
+        .fetch_all(&self.pool)
         .await
-        .context("Failed to fetch last redoable operation")?;
+        .context("Failed to count project todos")?;
+
+        let projects = self.list_projects(false).await?;
+        Ok(projects
+            .into_iter()
+            .map(|project| {
+                let id = project.id.to_string();
+                let (completed, total) = counts
+                    .iter()
+                    .find(|(project_id, _, _)| *project_id == id)
+                    .map_or((0, 0), |(_, completed, total)| {
+                        (*completed as u64, *total as u64)
+                    });
+                ProjectProgress {
+                    project,
+                    completed,
+                    total,
+                }
+            })
+            .collect())
+    }
+
+    /// List all projects pending sync.
+    pub async fn list_pending_projects(&self) -> Result<Vec<Project>> {
+        let rows: Vec<ProjectRow> =
+            sqlx::query_as("SELECT * FROM projects WHERE sync_status = 'pending'")
+                .fetch_all(&self.pool)
+                .await
+                .context("Failed to list pending sync projects")?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// Mark a project as synced.
+    pub async fn mark_project_synced(&self, id: Uuid) -> Result<()> {
+        sqlx::query("UPDATE projects SET sync_status = 'synced' WHERE id = ?")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to mark project as synced")?;
+
+        Ok(())
+    }
+
+    /// Give a project a new ID, moving its todos along with it.
+    ///
+    /// Used when sync finds the same project name already exists remotely
+    /// under another ID. The project is marked synced.
+    pub async fn rekey_project(&self, old_id: Uuid, new_id: Uuid) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        // Todos point at the old ID until the second update; check at commit
+        sqlx::query("PRAGMA defer_foreign_keys = ON")
+            .execute(&mut *tx)
+            .await
+            .context("Failed to defer foreign keys")?;
+
+        sqlx::query("UPDATE projects SET id = ?1, sync_status = 'synced' WHERE id = ?2")
+            .bind(new_id.to_string())
+            .bind(old_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to rekey project")?;
+
+        sqlx::query("UPDATE todos SET project_id = ?1 WHERE project_id = ?2")
+            .bind(new_id.to_string())
+            .bind(old_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to move todos to rekeyed project")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(())
+    }
+
+    // ==================== Operation CRUD Operations ====================
+
+    /// Record an operation on top of the undo stack. Operations undone
+    /// before it can no longer be redone.
+    pub async fn record_operation(&self, op: &Operation) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+        push_operation(&mut tx, op).await?;
+        tx.commit().await.context("Failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Get the last operation that can be undone (not yet undone).
+    pub async fn get_last_undoable_operation(&self) -> Result<Option<Operation>> {
+        let row: Option<OperationRow> =
+            sqlx::query_as("SELECT * FROM operations WHERE undone = 0 ORDER BY seq DESC LIMIT 1")
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to fetch last undoable operation")?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// Get the operation redo brings back: the one undone last. Undone
+    /// operations are always the top of the stack, since recording a new one
+    /// drops them.
+    pub async fn get_last_redoable_operation(&self) -> Result<Option<Operation>> {
+        let row: Option<OperationRow> =
+            sqlx::query_as("SELECT * FROM operations WHERE undone = 1 ORDER BY seq ASC LIMIT 1")
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to fetch last redoable operation")?;
 
         row.map(|r| r.try_into()).transpose()
     }
 
+    /// Undo every not-yet-undone operation in a group, newest first, in one
+    /// transaction. Returns the operations that were undone.
+    pub async fn undo_operation_group(&self, group_id: Uuid) -> Result<Vec<Operation>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        let rows: Vec<OperationRow> = sqlx::query_as(
+            "SELECT * FROM operations WHERE operation_group_id = ?1 AND undone = 0 ORDER BY seq DESC",
+        )
+        .bind(group_id.to_string())
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to list grouped operations")?;
+        let ops: Vec<Operation> = rows
+            .into_iter()
+            .map(|r| r.try_into())
+            .collect::<Result<_>>()?;
+
+        for op in &ops {
+            apply_todo_state(&mut tx, op, op.previous_state.as_ref(), true).await?;
+            sqlx::query("UPDATE operations SET undone = 1 WHERE id = ?1")
+                .bind(op.id.to_string())
+                .execute(&mut *tx)
+                .await
+                .context("Failed to mark operation as undone")?;
+        }
+
+        tx.commit().await.context("Failed to commit group undo")?;
+        self.sync_note_checkboxes(&ops).await;
+        Ok(ops)
+    }
+
+    /// Redo every undone operation in a group, oldest first, in one
+    /// transaction. Returns the operations that were redone.
+    pub async fn redo_operation_group(&self, group_id: Uuid) -> Result<Vec<Operation>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        let rows: Vec<OperationRow> = sqlx::query_as(
+            "SELECT * FROM operations WHERE operation_group_id = ?1 AND undone = 1 ORDER BY seq ASC",
+        )
+        .bind(group_id.to_string())
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to list grouped operations")?;
+        let ops: Vec<Operation> = rows
+            .into_iter()
+            .map(|r| r.try_into())
+            .collect::<Result<_>>()?;
+
+        for op in &ops {
+            apply_todo_state(&mut tx, op, op.new_state.as_ref(), false).await?;
+            sqlx::query("UPDATE operations SET undone = 0 WHERE id = ?1")
+                .bind(op.id.to_string())
+                .execute(&mut *tx)
+                .await
+                .context("Failed to mark operation as redone")?;
+        }
+
+        tx.commit().await.context("Failed to commit group redo")?;
+        self.sync_note_checkboxes(&ops).await;
+        Ok(ops)
+    }
+
+    /// Update linked note checkboxes for todos touched by grouped undo/redo
+    async fn sync_note_checkboxes(&self, ops: &[Operation]) {
+        for op in ops {
+            if let Ok(Some(todo)) = self.get_todo(op.entity_id).await
+                && let Err(e) = self.sync_note_checkbox(&todo).await
+            {
+                tracing::warn!(error = %e, todo = %todo.id, "Failed to update markdown checkbox");
+            }
+        }
+    }
+
     /// Mark an operation as undone.
     pub async fn mark_operation_undone(&self, id: Uuid) -> Result<()> {
         sqlx::query("UPDATE operations SET undone = 1 WHERE id = ?1")
@@ -710,6 +1967,18 @@ impl LocalDb {
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
+    /// Operations on one todo or other entity, oldest first.
+    pub async fn list_operations_for(&self, entity_id: Uuid) -> Result<Vec<Operation>> {
+        let rows: Vec<OperationRow> =
+            sqlx::query_as("SELECT * FROM operations WHERE entity_id = ?1 ORDER BY created_at ASC")
+                .bind(entity_id.to_string())
+                .fetch_all(&self.pool)
+                .await
+                .context("Failed to list operations for entity")?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
     /// List operations since a given timestamp.
     pub async fn list_operations_since(&self, since: DateTime<Utc>) -> Result<Vec<Operation>> {
         let rows: Vec<OperationRow> = sqlx::query_as(
@@ -736,6 +2005,230 @@ impl LocalDb {
         Ok(result.rows_affected())
     }
 
+    /// Compact operations older than the specified number of days.
+    ///
+    /// Each run of consecutive Update operations on the same entity is
+    /// collapsed into a single Update that goes from the state before the
+    /// first update to the state after the last one. Other operation types,
+    /// undone and grouped operations and recent history are left untouched,
+    /// so undo keeps working step by step for recent changes.
+    /// Returns the number of operations removed.
+    pub async fn compact_operations(&self, days: i64) -> Result<u64> {
+        let cutoff = Utc::now() - chrono::Duration::days(days);
+        let rows: Vec<OperationRow> = sqlx::query_as(
+            r#"
+            SELECT * FROM operations
+            WHERE created_at < ?1
+            ORDER BY entity_type, entity_id, created_at ASC
+            "#,
+        )
+        .bind(cutoff.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list operations for compaction")?;
+
+        // Split into runs of consecutive updates per entity
+        let mut runs: Vec<Vec<OperationRow>> = Vec::new();
+        let mut current: Vec<OperationRow> = Vec::new();
+        for row in rows {
+            let continues = current.last().is_some_and(|last| {
+                last.entity_type == row.entity_type && last.entity_id == row.entity_id
+            });
+            let compactable = row.operation_type == "update"
+                && row.undone == 0
+                && row.operation_group_id.is_none();
+            if !continues || !compactable {
+                if current.len() > 1 {
+                    runs.push(std::mem::take(&mut current));
+                }
+                current.clear();
+            }
+            if compactable {
+                current.push(row);
+            }
+        }
+        if current.len() > 1 {
+            runs.push(current);
+        }
+
+        if runs.is_empty() {
+            return Ok(0);
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start compaction transaction")?;
+        let mut removed = 0;
+
+        for run in runs {
+            let (first, rest) = run.split_first().expect("runs have at least two entries");
+            let last = rest.last().expect("runs have at least two entries");
+
+            // Keep the newest operation, widened to cover the whole run
+            sqlx::query("UPDATE operations SET previous_state = ?1 WHERE id = ?2")
+                .bind(&first.previous_state)
+                .bind(&last.id)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to update compacted operation")?;
+
+            for row in run.iter().take(run.len() - 1) {
+                sqlx::query("DELETE FROM operations WHERE id = ?1")
+                    .bind(&row.id)
+                    .execute(&mut *tx)
+                    .await
+                    .context("Failed to delete compacted operation")?;
+                removed += 1;
+            }
+        }
+
+        tx.commit()
+            .await
+            .context("Failed to commit operation compaction")?;
+
+        Ok(removed)
+    }
+
+    /// Replace each undo group whose operations are all older than the
+    /// specified number of days, and none undone, with one
+    /// [`OperationType::Summary`] in the same group. Undoing it still undoes
+    /// the whole group. Returns the number of operations removed.
+    pub async fn summarize_operation_groups(&self, days: i64) -> Result<u64> {
+        let cutoff = Utc::now() - chrono::Duration::days(days);
+        let groups: Vec<(String,)> = sqlx::query_as(
+            r#"
+            SELECT operation_group_id FROM operations
+            WHERE operation_group_id IS NOT NULL
+            GROUP BY operation_group_id
+            HAVING COUNT(*) > 1 AND MAX(created_at) < ?1 AND SUM(undone) = 0
+                AND SUM(entity_type != 'todo') = 0
+            "#,
+        )
+        .bind(cutoff.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list operation groups for compaction")?;
+
+        let mut removed = 0;
+        for (group_id,) in groups {
+            let mut tx = self
+                .pool
+                .begin()
+                .await
+                .context("Failed to start compaction transaction")?;
+
+            let rows: Vec<OperationRow> = sqlx::query_as(
+                "SELECT * FROM operations WHERE operation_group_id = ?1 ORDER BY created_at ASC",
+            )
+            .bind(&group_id)
+            .fetch_all(&mut *tx)
+            .await
+            .context("Failed to list grouped operations")?;
+            // The summary keeps the group's place in the undo stack
+            let seq = rows.iter().filter_map(|r| r.seq).max();
+            let ops: Vec<Operation> = rows
+                .into_iter()
+                .map(|r| r.try_into())
+                .collect::<Result<_>>()?;
+
+            sqlx::query("DELETE FROM operations WHERE operation_group_id = ?1")
+                .bind(&group_id)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to delete compacted operations")?;
+            insert_operation(&mut tx, &Operation::summarize(&ops), seq).await?;
+
+            tx.commit()
+                .await
+                .context("Failed to commit operation compaction")?;
+            removed += ops.len() as u64 - 1;
+        }
+
+        Ok(removed)
+    }
+
+    /// Delete the oldest operations so that at most `max` are left. A group
+    /// is deleted whole, as undo can only take it back whole.
+    /// Returns the number of deleted operations.
+    pub async fn trim_operations(&self, max: u64) -> Result<u64> {
+        let oldest_kept: Option<(String,)> = sqlx::query_as(
+            "SELECT created_at FROM operations ORDER BY created_at DESC LIMIT 1 OFFSET ?1",
+        )
+        .bind(max.saturating_sub(1) as i64)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to find the oldest operation to keep")?;
+        let Some((oldest_kept,)) = oldest_kept else {
+            return Ok(0);
+        };
+
+        let result = sqlx::query(
+            r#"
+            DELETE FROM operations
+            WHERE created_at < ?1
+                OR operation_group_id IN (
+                    SELECT operation_group_id FROM operations
+                    WHERE created_at < ?1 AND operation_group_id IS NOT NULL
+                )
+            "#,
+        )
+        .bind(oldest_kept)
+        .execute(&self.pool)
+        .await
+        .context("Failed to trim operations")?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// How much undo history is kept.
+    pub async fn operation_stats(&self) -> Result<OperationStats> {
+        let (operations, undone, grouped, summaries, bytes, oldest, newest): (
+            i64,
+            i64,
+            i64,
+            i64,
+            i64,
+            Option<String>,
+            Option<String>,
+        ) = sqlx::query_as(
+            r#"
+            SELECT
+                COUNT(*),
+                COALESCE(SUM(undone), 0),
+                COUNT(operation_group_id),
+                COALESCE(SUM(operation_type = 'summary'), 0),
+                COALESCE(SUM(COALESCE(LENGTH(previous_state), 0) + COALESCE(LENGTH(new_state), 0)), 0),
+                MIN(created_at),
+                MAX(created_at)
+            FROM operations
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to count operations")?;
+
+        let time = |value: Option<String>| -> Result<Option<DateTime<Utc>>> {
+            value
+                .map(|v| {
+                    DateTime::parse_from_rfc3339(&v)
+                        .map(|t| t.with_timezone(&Utc))
+                        .context("Invalid operation created_at")
+                })
+                .transpose()
+        };
+        Ok(OperationStats {
+            operations: operations as u64,
+            undone: undone as u64,
+            grouped: grouped as u64,
+            summaries: summaries as u64,
+            bytes: bytes as u64,
+            oldest: time(oldest)?,
+            newest: time(newest)?,
+        })
+    }
+
     // ==================== Head/Tail/Upcoming/Overdue Queries ====================
 
     /// List N most recently created todos.
@@ -745,9 +2238,9 @@ impl LocalDb {
         include_completed: bool,
     ) -> Result<Vec<Todo>> {
         let query = if include_completed {
-            "SELECT * FROM todos ORDER BY created_at DESC LIMIT ?1"
+            "SELECT * FROM todos WHERE deleted_at IS NULL ORDER BY created_at DESC LIMIT ?1"
         } else {
-            "SELECT * FROM todos WHERE is_completed = 0 ORDER BY created_at DESC LIMIT ?1"
+            "SELECT * FROM todos WHERE is_completed = 0 AND deleted_at IS NULL ORDER BY created_at DESC LIMIT ?1"
         };
 
         let rows: Vec<TodoRow> = sqlx::query_as(query)
@@ -766,9 +2259,9 @@ impl LocalDb {
         include_completed: bool,
     ) -> Result<Vec<Todo>> {
         let query = if include_completed {
-            "SELECT * FROM todos ORDER BY created_at ASC LIMIT ?1"
+            "SELECT * FROM todos WHERE deleted_at IS NULL ORDER BY created_at ASC LIMIT ?1"
         } else {
-            "SELECT * FROM todos WHERE is_completed = 0 ORDER BY created_at ASC LIMIT ?1"
+            "SELECT * FROM todos WHERE is_completed = 0 AND deleted_at IS NULL ORDER BY created_at ASC LIMIT ?1"
         };
 
         let rows: Vec<TodoRow> = sqlx::query_as(query)
@@ -785,7 +2278,7 @@ impl LocalDb {
         let now = Utc::now().to_rfc3339();
 
         let rows: Vec<TodoRow> = sqlx::query_as(
-            "SELECT * FROM todos WHERE is_completed = 0 AND due_date IS NOT NULL AND due_date >= ?1 ORDER BY due_date ASC LIMIT ?2",
+            "SELECT * FROM todos WHERE is_completed = 0 AND deleted_at IS NULL AND due_date IS NOT NULL AND due_date >= ?1 ORDER BY due_date ASC LIMIT ?2",
         )
         .bind(&now)
         .bind(limit as i64)
@@ -801,7 +2294,7 @@ impl LocalDb {
         let now = Utc::now().to_rfc3339();
 
         let rows: Vec<TodoRow> = sqlx::query_as(
-            "SELECT * FROM todos WHERE is_completed = 0 AND due_date IS NOT NULL AND due_date < ?1 ORDER BY due_date ASC",
+            "SELECT * FROM todos WHERE is_completed = 0 AND deleted_at IS NULL AND due_date IS NOT NULL AND due_date < ?1 ORDER BY due_date ASC",
         )
         .bind(&now)
         .fetch_all(&self.pool)
@@ -825,12 +2318,14 @@ impl LocalDb {
             r#"
             SELECT id, user_id, category_id, title, description, due_date,
                    reminder_at, priority, is_completed, completed_at,
-                   ai_metadata, created_at, updated_at, sync_status
+                   ai_metadata, created_at, updated_at, sync_status, icon, project_id,
+                   remind_before_minutes, external_ref, no_nag, notified_at
             FROM todos
             WHERE reminder_at IS NOT NULL
               AND reminder_at <= ?1
               AND reminder_at > ?2
               AND is_completed = 0
+              AND deleted_at IS NULL
             ORDER BY reminder_at ASC
             "#,
         )
@@ -843,28 +2338,249 @@ impl LocalDb {
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
-    // ==================== Stash Operations ====================
-
-    /// Stash a todo (hide it temporarily).
-    ///
-    /// # Errors
-    /// Returns error if todo doesn't exist or is already stashed.
-    pub async fn stash_todo(&self, todo_id: Uuid, message: Option<&str>) -> Result<Todo> {
-        // Check if already stashed
-        let already_stashed: Option<(String,)> =
-            sqlx::query_as("SELECT id FROM stash WHERE id = ?")
-                .bind(todo_id.to_string())
-                .fetch_optional(&self.pool)
-                .await
-                .context("Failed to check stash")?;
+    /// Pending todos due within `window` from now, including those that fell
+    /// due in the last 5 minutes, soonest first.
+    pub async fn list_todos_due_within(&self, window: chrono::Duration) -> Result<Vec<Todo>> {
+        let now = Utc::now();
+        let rows: Vec<TodoRow> = sqlx::query_as(
+            "SELECT * FROM todos WHERE is_completed = 0 AND deleted_at IS NULL AND due_date IS NOT NULL AND due_date <= ?1 AND due_date > ?2 ORDER BY due_date ASC",
+        )
+        .bind((now + window).to_rfc3339())
+        .bind((now - chrono::Duration::minutes(5)).to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list todos due soon")?;
 
-        if already_stashed.is_some() {
-            anyhow::bail!("Todo is already stashed");
-        }
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
 
-        let todo = self
-            .get_todo(todo_id)
-            .await?
+    /// Pending todos whose reminder came round by `now`, no earlier than
+    /// `catch_up` before it, that this device hasn't notified about since.
+    /// Reminders missed while the daemon was stopped are still sent.
+    pub async fn list_reminders_to_send(
+        &self,
+        now: DateTime<Utc>,
+        catch_up: chrono::Duration,
+    ) -> Result<Vec<Todo>> {
+        let rows: Vec<TodoRow> = sqlx::query_as(
+            r#"
+            SELECT * FROM todos
+            WHERE reminder_at IS NOT NULL
+              AND reminder_at <= ?1
+              AND reminder_at > ?2
+              AND (notified_at IS NULL OR notified_at < reminder_at)
+              AND is_completed = 0
+              AND deleted_at IS NULL
+            ORDER BY reminder_at ASC
+            "#,
+        )
+        .bind(now.to_rfc3339())
+        .bind((now - catch_up).to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list reminders to send")?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// Record that this device notified about `ids` at `at`. Not a change to
+    /// the todos: they are neither synced nor added to history for it.
+    pub async fn mark_notified(&self, ids: &[Uuid], at: DateTime<Utc>) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+        for id in ids {
+            sqlx::query("UPDATE todos SET notified_at = ?1 WHERE id = ?2")
+                .bind(at.to_rfc3339())
+                .bind(id.to_string())
+                .execute(&mut *tx)
+                .await
+                .context("Failed to record notification")?;
+        }
+        tx.commit().await.context("Failed to commit transaction")?;
+        Ok(())
+    }
+
+    // ==================== Insights ====================
+    //
+    // Counted by SQLite, so a long range over an old database costs a few
+    // aggregate rows rather than every todo and operation in memory.
+
+    /// Todo counts for insights: created and completed since `since`, and
+    /// pending ones by state and priority as of `now`.
+    pub async fn todo_stats(&self, since: DateTime<Utc>, now: DateTime<Utc>) -> Result<TodoStats> {
+        let row: (i64, i64, i64, i64, i64, i64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                COALESCE(SUM(created_at >= ?1), 0),
+                COALESCE(SUM(is_completed = 1 AND completed_at >= ?1), 0),
+                COALESCE(SUM(is_completed = 0), 0),
+                COALESCE(SUM(is_completed = 0 AND due_date < ?2), 0),
+                COALESCE(SUM(is_completed = 0 AND priority >= ?3), 0),
+                COALESCE(SUM(is_completed = 0 AND priority = ?4), 0),
+                COALESCE(SUM(is_completed = 0 AND priority = ?5), 0)
+            FROM todos
+            WHERE deleted_at IS NULL
+            "#,
+        )
+        .bind(since.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .bind(Priority::High as i32)
+        .bind(Priority::Medium as i32)
+        .bind(Priority::Low as i32)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to count todos")?;
+
+        Ok(TodoStats {
+            created: row.0 as u64,
+            completed: row.1 as u64,
+            pending: row.2 as u64,
+            overdue: row.3 as u64,
+            pending_high: row.4 as u64,
+            pending_medium: row.5 as u64,
+            pending_low: row.6 as u64,
+        })
+    }
+
+    /// Todos due from `start` up to `end`, and how many of them are done.
+    pub async fn due_progress(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<DueProgress> {
+        let row: (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*), COALESCE(SUM(is_completed = 1), 0)
+            FROM todos
+            WHERE deleted_at IS NULL AND due_date >= ? AND due_date < ?
+            "#,
+        )
+        .bind(start.to_rfc3339())
+        .bind(end.to_rfc3339())
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to count todos due")?;
+
+        Ok(DueProgress {
+            due: row.0 as u64,
+            completed: row.1 as u64,
+        })
+    }
+
+    /// Todos created and completed per UTC hour since `since`, from the
+    /// operation log, oldest first. Hours without activity are left out.
+    pub async fn activity_by_hour(&self, since: DateTime<Utc>) -> Result<Vec<ActivityBucket>> {
+        let rows: Vec<(String, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT
+                substr(created_at, 1, 13) AS hour,
+                SUM(CASE operation_type
+                    WHEN 'create' THEN 1
+                    WHEN 'batch_create' THEN
+                        CASE WHEN json_valid(new_state) THEN json_array_length(new_state) ELSE 0 END
+                    ELSE 0
+                END),
+                SUM(operation_type = 'complete')
+            FROM operations
+            WHERE created_at >= ?1 AND entity_type = 'todo'
+            GROUP BY hour
+            ORDER BY hour
+            "#,
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to count activity")?;
+
+        rows.into_iter()
+            .map(|(hour, created, completed)| {
+                let hour = chrono::NaiveDateTime::parse_from_str(
+                    &format!("{}:00:00", hour),
+                    "%Y-%m-%dT%H:%M:%S",
+                )
+                .with_context(|| format!("Invalid operation timestamp: {}", hour))?
+                .and_utc();
+                Ok(ActivityBucket {
+                    hour,
+                    created: created as u64,
+                    completed: completed as u64,
+                })
+            })
+            .collect()
+    }
+
+    /// Todos completed since `since`, per category, most first.
+    pub async fn completions_by_category(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<CategoryCount>> {
+        let rows: Vec<(Option<String>, i64)> = sqlx::query_as(
+            r#"
+            SELECT c.name, COUNT(*) AS completed
+            FROM todos t
+            LEFT JOIN categories c ON c.id = t.category_id
+            WHERE t.deleted_at IS NULL AND t.is_completed = 1 AND t.completed_at >= ?1
+            GROUP BY c.name
+            ORDER BY completed DESC, c.name
+            "#,
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to count completions by category")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(category, completed)| CategoryCount {
+                category,
+                completed: completed as u64,
+            })
+            .collect())
+    }
+
+    /// Mean time from creation to completion for todos completed since
+    /// `since`, or `None` when nothing was completed.
+    pub async fn average_completion_time(&self, since: DateTime<Utc>) -> Result<Option<Duration>> {
+        let (avg,): (Option<f64>,) = sqlx::query_as(
+            r#"
+            SELECT AVG((julianday(completed_at) - julianday(created_at)) * 86400.0)
+            FROM todos
+            WHERE deleted_at IS NULL AND is_completed = 1 AND completed_at >= ?1
+            "#,
+        )
+        .bind(since.to_rfc3339())
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to average completion time")?;
+
+        Ok(avg.map(|secs| Duration::seconds(secs.max(0.0).round() as i64)))
+    }
+
+    // ==================== Stash Operations ====================
+
+    /// Stash a todo (hide it temporarily).
+    ///
+    /// # Errors
+    /// Returns error if todo doesn't exist or is already stashed.
+    pub async fn stash_todo(&self, todo_id: Uuid, message: Option<&str>) -> Result<Todo> {
+        // Check if already stashed
+        let already_stashed: Option<(String,)> =
+            sqlx::query_as("SELECT id FROM stash WHERE id = ?")
+                .bind(todo_id.to_string())
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to check stash")?;
+
+        if already_stashed.is_some() {
+            anyhow::bail!("Todo is already stashed");
+        }
+
+        let todo = self
+            .get_todo(todo_id)
+            .await?
             .ok_or_else(|| anyhow::anyhow!("Todo not found"))?;
 
         sqlx::query("INSERT INTO stash (id, todo_json, stashed_at, message) VALUES (?, ?, ?, ?)")
@@ -899,14 +2615,7 @@ impl LocalDb {
                 .await
                 .context("Failed to delete from stash")?;
 
-            self.create_todo(&todo).await?;
-
-            // Clear deletion record since we're restoring the todo
-            sqlx::query("DELETE FROM deleted_todos WHERE id = ?")
-                .bind(todo.id.to_string())
-                .execute(&self.pool)
-                .await
-                .context("Failed to clear deletion record for restored todo")?;
+            self.restore_todo(&todo).await?;
 
             Ok(Some(todo))
         } else {
@@ -944,21 +2653,152 @@ impl LocalDb {
         Ok(result.rows_affected())
     }
 
-    // ==================== Deleted Todo Tracking ====================
+    // ==================== Event Operations ====================
+
+    /// Create a new event in the database.
+    pub async fn create_event(&self, event: &Event) -> Result<()> {
+        let sync_status = match event.sync_status {
+            SyncStatus::Pending => "pending",
+            SyncStatus::Synced => "synced",
+            SyncStatus::Conflict => "conflict",
+        };
 
-    /// Record a todo deletion for sync tracking.
-    pub async fn record_deleted_todo(&self, id: Uuid) -> Result<()> {
         sqlx::query(
-            "INSERT OR REPLACE INTO deleted_todos (id, deleted_at, synced) VALUES (?, datetime('now'), 0)",
+            r#"
+            INSERT INTO events (
+                id, user_id, title, description, start_time, end_time,
+                reminder_at, recurrence_rule, created_at, sync_status
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            "#,
         )
-        .bind(id.to_string())
+        .bind(event.id.to_string())
+        .bind(event.user_id.to_string())
+        .bind(&event.title)
+        .bind(&event.description)
+        .bind(event.start_time.to_rfc3339())
+        .bind(event.end_time.to_rfc3339())
+        .bind(event.reminder_at.map(|r| r.to_rfc3339()))
+        .bind(&event.recurrence_rule)
+        .bind(event.created_at.to_rfc3339())
+        .bind(sync_status)
         .execute(&self.pool)
         .await
-        .context("Failed to record deleted todo")?;
+        .context("Failed to create event")?;
+
+        Ok(())
+    }
+
+    /// Get an event by its ID.
+    pub async fn get_event(&self, id: Uuid) -> Result<Option<Event>> {
+        let row: Option<EventRow> = sqlx::query_as("SELECT * FROM events WHERE id = ?1")
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to fetch event")?;
+
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    /// List all events ordered by start time.
+    pub async fn list_events(&self) -> Result<Vec<Event>> {
+        let rows: Vec<EventRow> = sqlx::query_as("SELECT * FROM events ORDER BY start_time ASC")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to list events")?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// List occurrences of events overlapping `[from, to)`, expanding recurring
+    /// events. Returns `(event, occurrence_start)` pairs sorted by start time.
+    pub async fn list_event_occurrences(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<(Event, DateTime<Utc>)>> {
+        // Recurring events may have started long before the window
+        let rows: Vec<EventRow> = sqlx::query_as(
+            r#"
+            SELECT * FROM events
+            WHERE start_time < ?1
+              AND (recurrence_rule IS NOT NULL OR end_time > ?2)
+            "#,
+        )
+        .bind(to.to_rfc3339())
+        .bind(from.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list events in range")?;
+
+        let mut occurrences = Vec::new();
+        for row in rows {
+            let event: Event = row.try_into()?;
+            for start in event.occurrences_between(from, to) {
+                occurrences.push((event.clone(), start));
+            }
+        }
+        occurrences.sort_by_key(|(_, start)| *start);
+
+        Ok(occurrences)
+    }
+
+    /// List event occurrences starting within the given window from now.
+    /// Includes a 5-minute grace period for recently started events.
+    pub async fn list_events_starting_within(
+        &self,
+        window: chrono::Duration,
+    ) -> Result<Vec<(Event, DateTime<Utc>)>> {
+        let now = Utc::now();
+        let grace_start = now - chrono::Duration::minutes(5);
+        let occurrences = self
+            .list_event_occurrences(grace_start, now + window)
+            .await?;
+
+        Ok(occurrences
+            .into_iter()
+            .filter(|(_, start)| *start >= grace_start)
+            .collect())
+    }
+
+    /// Find the event a typed ID prefix refers to, like [`Self::resolve_short_id`]
+    /// does for todos
+    pub async fn resolve_short_event_id(&self, prefix: &str) -> Result<IdMatch<Event>> {
+        let Some(prefix) = id_prefix(prefix) else {
+            return Ok(IdMatch::NotFound);
+        };
+
+        let rows: Vec<EventRow> =
+            sqlx::query_as("SELECT * FROM events WHERE id LIKE ?1 ORDER BY start_time ASC")
+                .bind(format!("{}%", prefix))
+                .fetch_all(&self.pool)
+                .await
+                .context("Failed to look up event by ID")?;
+
+        let events = rows
+            .into_iter()
+            .map(Event::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(IdMatch::from_matches(events))
+    }
+
+    /// Delete an event by its ID.
+    pub async fn delete_event(&self, id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM events WHERE id = ?1")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to delete event")?;
 
         Ok(())
     }
 
+    // ==================== Deleted Todo Tracking ====================
+
+    /// Record a todo deletion for sync tracking.
+    pub async fn record_deleted_todo(&self, id: Uuid) -> Result<()> {
+        record_deletion(&self.pool, id).await
+    }
+
     /// List all deleted todo IDs that haven't been synced to remote yet.
     pub async fn list_unsynced_deletions(&self) -> Result<Vec<Uuid>> {
         let rows: Vec<(String,)> = sqlx::query_as("SELECT id FROM deleted_todos WHERE synced = 0")
@@ -982,6 +2822,40 @@ impl LocalDb {
         Ok(())
     }
 
+    /// Apply a deletion that originated remotely.
+    ///
+    /// Soft deletes the todo locally and records the deletion as already synced so
+    /// it is not pushed back to remote. Returns `true` if a local todo was removed.
+    pub async fn apply_remote_deletion(&self, id: Uuid) -> Result<bool> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        let removed = sqlx::query(
+            "UPDATE todos SET deleted_at = ?1, updated_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+        )
+        .bind(Utc::now().to_rfc3339())
+        .bind(id.to_string())
+        .execute(&mut *tx)
+            .await
+            .context("Failed to delete todo")?
+            .rows_affected();
+
+        sqlx::query(
+            "INSERT OR REPLACE INTO deleted_todos (id, deleted_at, synced) VALUES (?, datetime('now'), 1)",
+        )
+        .bind(id.to_string())
+        .execute(&mut *tx)
+        .await
+        .context("Failed to record deleted todo")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(removed > 0)
+    }
+
     /// Check if a todo was locally deleted (to skip re-downloading).
     pub async fn is_locally_deleted(&self, id: Uuid) -> Result<bool> {
         let result: Option<(i32,)> = sqlx::query_as("SELECT 1 FROM deleted_todos WHERE id = ?")
@@ -992,140 +2866,1930 @@ impl LocalDb {
 
         Ok(result.is_some())
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    async fn setup_db() -> LocalDb {
-        let db = LocalDb::new_in_memory().await.unwrap();
-        db.run_migrations().await.unwrap();
-        db
-    }
+    // ==================== Note Links ====================
 
-    #[tokio::test]
-    async fn test_create_and_get_todo() {
-        let db = setup_db().await;
+    /// Link a todo to the markdown checkbox it was imported from.
+    pub async fn create_note_link(&self, link: &NoteLink) -> Result<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO note_links (todo_id, path, line, text, two_way) VALUES (?1, ?2, ?3, ?4, ?5)",
+        )
+        .bind(link.todo_id.to_string())
+        .bind(&link.path)
+        .bind(link.line as i64)
+        .bind(&link.text)
+        .bind(if link.two_way { 1 } else { 0 })
+        .execute(&self.pool)
+        .await
+        .context("Failed to create note link")?;
 
-        let todo = Todo::new("Buy groceries".to_string(), None);
-        db.create_todo(&todo).await.unwrap();
+        Ok(())
+    }
 
-        let retrieved = db.get_todo(todo.id).await.unwrap();
-        assert!(retrieved.is_some());
+    /// Get the note link for a todo, if it was imported from a note.
+    pub async fn get_note_link(&self, todo_id: Uuid) -> Result<Option<NoteLink>> {
+        let row: Option<NoteLinkRow> = sqlx::query_as(
+            "SELECT todo_id, path, line, text, two_way FROM note_links WHERE todo_id = ?1",
+        )
+        .bind(todo_id.to_string())
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to get note link")?;
 
-        let retrieved = retrieved.unwrap();
-        assert_eq!(retrieved.id, todo.id);
-        assert_eq!(retrieved.title, "Buy groceries");
-        assert!(!retrieved.is_completed);
-        assert_eq!(retrieved.priority, Priority::Medium);
+        row.map(note_link_from_row).transpose()
     }
 
-    #[tokio::test]
-    async fn test_list_todos_not_completed() {
-        let db = setup_db().await;
+    /// Find the link for a checkbox by file and item text.
+    pub async fn find_note_link(&self, path: &str, text: &str) -> Result<Option<NoteLink>> {
+        let row: Option<NoteLinkRow> = sqlx::query_as(
+            "SELECT todo_id, path, line, text, two_way FROM note_links WHERE path = ?1 AND text = ?2",
+        )
+        .bind(path)
+        .bind(text)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to find note link")?;
 
-        // Create some todos
-        let todo1 = Todo::new("Task 1".to_string(), None);
-        let mut todo2 = Todo::new("Task 2".to_string(), None);
-        todo2.mark_complete();
-        let todo3 = Todo::new("Task 3".to_string(), None);
+        row.map(note_link_from_row).transpose()
+    }
 
-        db.create_todo(&todo1).await.unwrap();
-        db.create_todo(&todo2).await.unwrap();
-        db.create_todo(&todo3).await.unwrap();
+    /// Record that a linked checkbox moved to a different line.
+    pub async fn update_note_link_line(&self, todo_id: Uuid, line: u32) -> Result<()> {
+        sqlx::query("UPDATE note_links SET line = ?1 WHERE todo_id = ?2")
+            .bind(line as i64)
+            .bind(todo_id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to update note link")?;
 
-        // list_todos(true) returns only non-completed
-        let todos = db.list_todos(true).await.unwrap();
-        assert_eq!(todos.len(), 2);
+        Ok(())
+    }
 
-        // list_todos(false) returns all todos
-        let all_todos = db.list_todos(false).await.unwrap();
-        assert_eq!(all_todos.len(), 3);
+    /// Mirror a todo's completion into its markdown checkbox (two-way links only).
+    async fn sync_note_checkbox(&self, todo: &Todo) -> Result<()> {
+        let Some(link) = self.get_note_link(todo.id).await? else {
+            return Ok(());
+        };
+        if !link.two_way {
+            return Ok(());
+        }
 
-        // Verify none of them are completed
-        for todo in &todos {
-            assert!(!todo.is_completed);
+        let found = notes::set_checkbox(
+            Path::new(&link.path),
+            link.line,
+            &link.text,
+            todo.is_completed,
+        )?;
+        if let Some(line) = found
+            && line != link.line
+        {
+            self.update_note_link_line(todo.id, line).await?;
         }
+        Ok(())
     }
 
-    #[tokio::test]
-    async fn test_update_todo() {
-        let db = setup_db().await;
+    // ==================== Sync Device ====================
 
-        let mut todo = Todo::new("Original title".to_string(), None);
-        db.create_todo(&todo).await.unwrap();
+    /// The ID this database syncs as, created on first use.
+    pub async fn device_id(&self) -> Result<Uuid> {
+        sqlx::query("INSERT OR IGNORE INTO sync_meta (key, value) VALUES ('device_id', ?1)")
+            .bind(Uuid::new_v4().to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to create device ID")?;
 
-        // Update the todo
-        todo.title = "Updated title".to_string();
-        todo.description = Some("A description".to_string());
-        todo.priority = Priority::High;
-        db.update_todo(&todo).await.unwrap();
+        let (value,): (String,) =
+            sqlx::query_as("SELECT value FROM sync_meta WHERE key = 'device_id'")
+                .fetch_one(&self.pool)
+                .await
+                .context("Failed to read device ID")?;
 
-        // Verify the update
-        let retrieved = db.get_todo(todo.id).await.unwrap().unwrap();
-        assert_eq!(retrieved.title, "Updated title");
+        Uuid::parse_str(&value).context("Invalid device ID")
+    }
+
+    /// The local day the daily agenda was last sent on, if ever
+    pub async fn daily_summary_sent_on(&self) -> Result<Option<NaiveDate>> {
+        let value: Option<(String,)> =
+            sqlx::query_as("SELECT value FROM sync_meta WHERE key = 'daily_summary_sent_on'")
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to read when the daily agenda was sent")?;
+
+        value
+            .map(|(v,)| NaiveDate::parse_from_str(&v, "%Y-%m-%d"))
+            .transpose()
+            .context("Invalid daily agenda date")
+    }
+
+    /// Record that the daily agenda for `date` was sent
+    pub async fn record_daily_summary(&self, date: NaiveDate) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO sync_meta (key, value) VALUES ('daily_summary_sent_on', ?1) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        )
+        .bind(date.format("%Y-%m-%d").to_string())
+        .execute(&self.pool)
+        .await
+        .context("Failed to record the daily agenda")?;
+        Ok(())
+    }
+
+    // ==================== Sync Conflicts ====================
+
+    /// Keep `remote` as the other side of a conflict with the local todo of
+    /// the same ID, and mark the local one as in conflict.
+    pub async fn save_conflict(&self, remote: &Todo) -> Result<()> {
+        let json = serde_json::to_string(remote).context("Failed to serialize remote todo")?;
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO conflicts (todo_id, remote, detected_at) VALUES (?1, ?2, ?3)
+            ON CONFLICT(todo_id) DO UPDATE SET remote = excluded.remote
+            "#,
+        )
+        .bind(remote.id.to_string())
+        .bind(json)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&mut *tx)
+        .await
+        .context("Failed to save conflict")?;
+        sqlx::query("UPDATE todos SET sync_status = 'conflict' WHERE id = ?1")
+            .bind(remote.id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to mark todo as in conflict")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Unresolved conflicts, oldest first. Todos edited or deleted here
+    /// since are no longer in conflict.
+    pub async fn list_conflicts(&self) -> Result<Vec<Conflict>> {
+        let rows: Vec<(String, String, String)> = sqlx::query_as(
+            r#"
+            SELECT c.todo_id, c.remote, c.detected_at FROM conflicts c
+            JOIN todos t ON t.id = c.todo_id
+            WHERE t.sync_status = 'conflict' AND t.deleted_at IS NULL
+            ORDER BY c.detected_at ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list conflicts")?;
+
+        let mut conflicts = Vec::with_capacity(rows.len());
+        for (todo_id, remote, detected_at) in rows {
+            let id = Uuid::parse_str(&todo_id).context("Invalid conflict todo ID")?;
+            if let Some(local) = self.get_todo(id).await? {
+                conflicts.push(conflict_from_row(local, &remote, &detected_at)?);
+            }
+        }
+        Ok(conflicts)
+    }
+
+    /// The unresolved conflict of a todo, if it has one.
+    pub async fn get_conflict(&self, id: Uuid) -> Result<Option<Conflict>> {
+        let Some(local) = self.get_todo(id).await? else {
+            return Ok(None);
+        };
+        if local.sync_status != SyncStatus::Conflict {
+            return Ok(None);
+        }
+        let row: Option<(String, String)> =
+            sqlx::query_as("SELECT remote, detected_at FROM conflicts WHERE todo_id = ?1")
+                .bind(id.to_string())
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to fetch conflict")?;
+
+        row.map(|(remote, detected_at)| conflict_from_row(local, &remote, &detected_at))
+            .transpose()
+    }
+
+    /// Save the todo a conflict was resolved to, forget the conflict and
+    /// record `op`, in one transaction.
+    pub async fn resolve_conflict(&self, resolved: &Todo, op: &Operation) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        write_todo_update(&mut *tx, resolved).await?;
+        sqlx::query("DELETE FROM conflicts WHERE todo_id = ?1")
+            .bind(resolved.id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear conflict")?;
+        push_operation(&mut tx, op).await?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        if let Err(e) = self.sync_note_checkbox(resolved).await {
+            tracing::warn!(error = %e, todo = %resolved.id, "Failed to update markdown checkbox");
+        }
+        Ok(())
+    }
+
+    // ==================== Sync Runs ====================
+
+    /// Record a sync attempt, keeping only the most recent runs.
+    pub async fn record_sync_run(&self, run: &SyncRun) -> Result<()> {
+        let conflict_ids =
+            serde_json::to_string(&run.conflict_ids).context("Failed to serialize conflict IDs")?;
+        sqlx::query(
+            r#"
+            INSERT INTO sync_runs (finished_at, uploaded, downloaded, conflict_ids, error)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+        )
+        .bind(run.finished_at.to_rfc3339())
+        .bind(run.uploaded as i64)
+        .bind(run.downloaded as i64)
+        .bind(conflict_ids)
+        .bind(&run.error)
+        .execute(&self.pool)
+        .await
+        .context("Failed to record sync run")?;
+
+        sqlx::query(
+            "DELETE FROM sync_runs WHERE id NOT IN (SELECT id FROM sync_runs ORDER BY id DESC LIMIT ?1)",
+        )
+        .bind(SYNC_RUNS_KEPT)
+        .execute(&self.pool)
+        .await
+        .context("Failed to prune sync runs")?;
+
+        Ok(())
+    }
+
+    /// The most recent sync attempt, successful or not.
+    pub async fn last_sync_run(&self) -> Result<Option<SyncRun>> {
+        let row: Option<SyncRunRow> = sqlx::query_as(
+            "SELECT finished_at, uploaded, downloaded, conflict_ids, error FROM sync_runs ORDER BY id DESC LIMIT 1",
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to read last sync run")?;
+
+        row.map(sync_run_from_row).transpose()
+    }
+
+    /// When the last successful sync finished.
+    pub async fn last_successful_sync(&self) -> Result<Option<DateTime<Utc>>> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT finished_at FROM sync_runs WHERE error IS NULL ORDER BY id DESC LIMIT 1",
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to read last successful sync")?;
+
+        row.map(|(at,)| {
+            DateTime::parse_from_rfc3339(&at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .context("Invalid sync run finished_at")
+        })
+        .transpose()
+    }
+
+    /// Number of local changes waiting to be uploaded: pending todos and
+    /// categories plus deletions not yet pushed.
+    pub async fn count_pending_uploads(&self) -> Result<u64> {
+        let (count,): (i64,) = sqlx::query_as(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM todos WHERE sync_status = 'pending' AND deleted_at IS NULL)
+                + (SELECT COUNT(*) FROM categories WHERE sync_status = 'pending')
+                + (SELECT COUNT(*) FROM deleted_todos WHERE synced = 0)
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to count pending uploads")?;
+
+        Ok(count as u64)
+    }
+
+    // ==================== AI Response Cache ====================
+
+    /// Get a cached AI response that has not expired yet.
+    pub async fn get_ai_cache(&self, key: &str) -> Result<Option<String>> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT response FROM ai_cache WHERE key = ?1 AND expires_at > ?2")
+                .bind(key)
+                .bind(Utc::now().to_rfc3339())
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to read AI cache")?;
+
+        Ok(row.map(|(response,)| response))
+    }
+
+    /// Cache an AI response for `ttl`, replacing any previous entry for `key`.
+    pub async fn put_ai_cache(&self, key: &str, response: &str, ttl: Duration) -> Result<()> {
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT OR REPLACE INTO ai_cache (key, response, created_at, expires_at) VALUES (?1, ?2, ?3, ?4)",
+        )
+        .bind(key)
+        .bind(response)
+        .bind(now.to_rfc3339())
+        .bind((now + ttl).to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to write AI cache")?;
+
+        Ok(())
+    }
+
+    // ==================== AI Parse Queue ====================
+
+    /// Queue an AI parse of `input` for a todo saved while offline.
+    pub async fn queue_ai_parse(&self, todo_id: Uuid, input: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO ai_parse_queue (todo_id, input, queued_at, attempts) VALUES (?1, ?2, ?3, 0)",
+        )
+        .bind(todo_id.to_string())
+        .bind(input)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to queue AI parse")?;
+
+        Ok(())
+    }
+
+    /// Queued AI parses, oldest first.
+    pub async fn list_pending_ai_parses(&self) -> Result<Vec<PendingAiParse>> {
+        let rows: Vec<(String, String, String, i64)> = sqlx::query_as(
+            "SELECT todo_id, input, queued_at, attempts FROM ai_parse_queue ORDER BY queued_at",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list queued AI parses")?;
+
+        rows.into_iter()
+            .map(|(todo_id, input, queued_at, attempts)| {
+                Ok(PendingAiParse {
+                    todo_id: Uuid::parse_str(&todo_id).context("Invalid queued todo ID")?,
+                    input,
+                    queued_at: DateTime::parse_from_rfc3339(&queued_at)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .context("Invalid queued_at")?,
+                    attempts: attempts as u32,
+                })
+            })
+            .collect()
+    }
+
+    /// Count one failed retry of a queued AI parse.
+    pub async fn record_ai_parse_attempt(&self, todo_id: Uuid) -> Result<()> {
+        sqlx::query("UPDATE ai_parse_queue SET attempts = attempts + 1 WHERE todo_id = ?1")
+            .bind(todo_id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to update queued AI parse")?;
+
+        Ok(())
+    }
+
+    /// Drop a queued AI parse, once applied or abandoned.
+    pub async fn remove_pending_ai_parse(&self, todo_id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM ai_parse_queue WHERE todo_id = ?1")
+            .bind(todo_id.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to remove queued AI parse")?;
+
+        Ok(())
+    }
+
+    // ==================== Notes ====================
+
+    /// Append a note to a todo.
+    pub async fn add_todo_note(&self, todo_id: Uuid, body: &str) -> Result<TodoNote> {
+        let created_at = Utc::now();
+        let id = sqlx::query("INSERT INTO notes (todo_id, body, created_at) VALUES (?1, ?2, ?3)")
+            .bind(todo_id.to_string())
+            .bind(body)
+            .bind(created_at.to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .context("Failed to add note")?
+            .last_insert_rowid();
+
+        Ok(TodoNote {
+            id,
+            todo_id,
+            body: body.to_string(),
+            created_at,
+        })
+    }
+
+    /// Notes on a todo, oldest first.
+    pub async fn list_todo_notes(&self, todo_id: Uuid) -> Result<Vec<TodoNote>> {
+        let rows: Vec<TodoNoteRow> = sqlx::query_as(
+            "SELECT id, todo_id, body, created_at FROM notes WHERE todo_id = ?1 ORDER BY id",
+        )
+        .bind(todo_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list notes")?;
+
+        rows.into_iter().map(todo_note_from_row).collect()
+    }
+
+    /// Insert notes as they are, keeping their timestamps, e.g. to carry
+    /// them over to a fresh database. Their IDs are assigned anew.
+    pub async fn insert_todo_notes(&self, notes: &[TodoNote]) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        for note in notes {
+            sqlx::query("INSERT INTO notes (todo_id, body, created_at) VALUES (?1, ?2, ?3)")
+                .bind(note.todo_id.to_string())
+                .bind(&note.body)
+                .bind(note.created_at.to_rfc3339())
+                .execute(&mut *tx)
+                .await
+                .context("Failed to add note")?;
+        }
+
+        tx.commit().await.context("Failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Notes on all todos that aren't deleted, oldest first.
+    pub async fn list_all_todo_notes(&self) -> Result<Vec<TodoNote>> {
+        let rows: Vec<TodoNoteRow> = sqlx::query_as(
+            "SELECT id, todo_id, body, created_at FROM notes WHERE todo_id IN (SELECT id FROM todos WHERE deleted_at IS NULL) ORDER BY id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list notes")?;
+
+        rows.into_iter().map(todo_note_from_row).collect()
+    }
+
+    // ==================== Attachments ====================
+
+    /// Link a file or URL to a todo. An attachment that already exists (same
+    /// ID, e.g. from importing a file twice) is left as it is.
+    pub async fn add_attachment(&self, attachment: &Attachment) -> Result<()> {
+        sqlx::query(
+            "INSERT OR IGNORE INTO attachments (id, todo_id, target, created_at) VALUES (?1, ?2, ?3, ?4)",
+        )
+        .bind(attachment.id.to_string())
+        .bind(attachment.todo_id.to_string())
+        .bind(&attachment.target)
+        .bind(attachment.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to add attachment")?;
+
+        Ok(())
+    }
+
+    /// Attachments of a todo, oldest first.
+    pub async fn list_attachments(&self, todo_id: Uuid) -> Result<Vec<Attachment>> {
+        let rows: Vec<AttachmentRow> = sqlx::query_as(
+            "SELECT id, todo_id, target, created_at FROM attachments WHERE todo_id = ?1 ORDER BY created_at, rowid",
+        )
+        .bind(todo_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list attachments")?;
+
+        rows.into_iter().map(attachment_from_row).collect()
+    }
+
+    /// Attachments of all todos that aren't deleted, oldest first.
+    pub async fn list_all_attachments(&self) -> Result<Vec<Attachment>> {
+        let rows: Vec<AttachmentRow> = sqlx::query_as(
+            "SELECT id, todo_id, target, created_at FROM attachments WHERE todo_id IN (SELECT id FROM todos WHERE deleted_at IS NULL) ORDER BY created_at, rowid",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list attachments")?;
+
+        rows.into_iter().map(attachment_from_row).collect()
+    }
+
+    // ==================== Focus Sessions ====================
+
+    /// Save a finished focus session with its interruptions.
+    pub async fn save_focus_session(&self, session: &FocusSession) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        sqlx::query(
+            "INSERT INTO focus_sessions (id, todo_id, started_at, ended_at, planned_minutes, outcome) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )
+        .bind(session.id.to_string())
+        .bind(session.todo_id.to_string())
+        .bind(session.started_at.to_rfc3339())
+        .bind(session.ended_at.to_rfc3339())
+        .bind(session.planned_minutes)
+        .bind(session.outcome.map(|o| o.to_string()))
+        .execute(&mut *tx)
+        .await
+        .context("Failed to save focus session")?;
+
+        for interruption in &session.interruptions {
+            sqlx::query(
+                "INSERT INTO focus_interruptions (session_id, at, reason) VALUES (?1, ?2, ?3)",
+            )
+            .bind(session.id.to_string())
+            .bind(interruption.at.to_rfc3339())
+            .bind(&interruption.reason)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to save focus interruption")?;
+        }
+
+        tx.commit().await.context("Failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Sessions started since `since` with their interruptions, oldest first.
+    pub async fn list_focus_sessions(&self, since: DateTime<Utc>) -> Result<Vec<FocusSession>> {
+        let rows: Vec<FocusSessionRow> = sqlx::query_as(
+            "SELECT id, todo_id, started_at, ended_at, planned_minutes, outcome FROM focus_sessions WHERE started_at >= ?1 ORDER BY started_at",
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list focus sessions")?;
+
+        let mut sessions = Vec::with_capacity(rows.len());
+        for row in rows {
+            let interruptions: Vec<(String, Option<String>)> = sqlx::query_as(
+                "SELECT at, reason FROM focus_interruptions WHERE session_id = ?1 ORDER BY at, id",
+            )
+            .bind(&row.0)
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to list focus interruptions")?;
+
+            sessions.push(focus_session_from_row(row, interruptions)?);
+        }
+        Ok(sessions)
+    }
+
+    /// Sessions ended since `since` and their interruptions, with reasons
+    /// grouped ignoring case.
+    pub async fn focus_stats(&self, since: DateTime<Utc>) -> Result<FocusStats> {
+        let (sessions, interruptions): (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                COUNT(*),
+                COALESCE(SUM((SELECT COUNT(*) FROM focus_interruptions i WHERE i.session_id = s.id)), 0)
+            FROM focus_sessions s
+            WHERE s.ended_at >= ?1
+            "#,
+        )
+        .bind(since.to_rfc3339())
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to count focus sessions")?;
+
+        let reasons: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT MIN(i.reason), COUNT(*) AS times
+            FROM focus_interruptions i
+            JOIN focus_sessions s ON s.id = i.session_id
+            WHERE s.ended_at >= ?1 AND i.reason IS NOT NULL
+            GROUP BY lower(i.reason)
+            ORDER BY times DESC, MIN(i.id)
+            "#,
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to count focus interruption reasons")?;
+
+        Ok(FocusStats {
+            sessions: sessions as u64,
+            interruptions: interruptions as u64,
+            reasons: reasons
+                .into_iter()
+                .map(|(reason, times)| (reason, times as u64))
+                .collect(),
+        })
+    }
+
+    /// Minutes focused per todo over sessions ended since `since`, most first.
+    pub async fn focus_time_by_todo(&self, since: DateTime<Utc>) -> Result<Vec<FocusTime>> {
+        let rows: Vec<(String, Option<String>, i64, f64)> = sqlx::query_as(
+            r#"
+            SELECT
+                s.todo_id,
+                t.title,
+                COUNT(*),
+                SUM((julianday(s.ended_at) - julianday(s.started_at)) * 1440.0) AS minutes
+            FROM focus_sessions s
+            LEFT JOIN todos t ON t.id = s.todo_id AND t.deleted_at IS NULL
+            WHERE s.ended_at >= ?1
+            GROUP BY s.todo_id
+            ORDER BY minutes DESC
+            "#,
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to sum focus time")?;
+
+        rows.into_iter()
+            .map(|(todo_id, title, sessions, minutes)| {
+                Ok(FocusTime {
+                    todo_id: Uuid::parse_str(&todo_id).context("Invalid focus session todo_id")?,
+                    title,
+                    sessions: sessions as u64,
+                    minutes: minutes.max(0.0).round() as u64,
+                })
+            })
+            .collect()
+    }
+
+    // ==================== Journal ====================
+
+    /// Add a line to the reflection for `date`, creating it if needed.
+    pub async fn append_journal(&self, date: NaiveDate, text: &str) -> Result<JournalEntry> {
+        sqlx::query(
+            r#"
+            INSERT INTO journal (date, note, updated_at) VALUES (?1, ?2, ?3)
+            ON CONFLICT(date) DO UPDATE SET
+                note = note || char(10) || excluded.note,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(date.to_string())
+        .bind(text)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to save journal note")?;
+
+        self.get_journal(date)
+            .await?
+            .context("Journal note missing after save")
+    }
+
+    /// The reflection for one day, if any was written.
+    pub async fn get_journal(&self, date: NaiveDate) -> Result<Option<JournalEntry>> {
+        let row: Option<JournalRow> =
+            sqlx::query_as("SELECT date, note, updated_at FROM journal WHERE date = ?1")
+                .bind(date.to_string())
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to read journal")?;
+
+        row.map(journal_from_row).transpose()
+    }
+
+    /// Reflections for `since` and later days, oldest first.
+    pub async fn list_journal(&self, since: NaiveDate) -> Result<Vec<JournalEntry>> {
+        let rows: Vec<JournalRow> = sqlx::query_as(
+            "SELECT date, note, updated_at FROM journal WHERE date >= ?1 ORDER BY date",
+        )
+        .bind(since.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list journal")?;
+
+        rows.into_iter().map(journal_from_row).collect()
+    }
+
+    /// Todo completions recorded in `[start, end)` that were not undone,
+    /// oldest first.
+    pub async fn list_completions(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Operation>> {
+        let rows: Vec<OperationRow> = sqlx::query_as(
+            r#"
+            SELECT * FROM operations
+            WHERE operation_type = 'complete' AND entity_type = 'todo' AND undone = 0
+                AND created_at >= ?1 AND created_at < ?2
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(start.to_rfc3339())
+        .bind(end.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list completions")?;
+
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    // ==================== Command History ====================
+
+    /// Record a CLI invocation, keeping only the most recent ones.
+    pub async fn record_command(&self, args: &[String]) -> Result<()> {
+        let args = serde_json::to_string(args).context("Failed to serialize command")?;
+        sqlx::query("INSERT INTO command_history (args, invoked_at) VALUES (?1, ?2)")
+            .bind(args)
+            .bind(Utc::now().to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .context("Failed to record command")?;
+
+        sqlx::query(
+            "DELETE FROM command_history WHERE id NOT IN (SELECT id FROM command_history ORDER BY id DESC LIMIT ?1)",
+        )
+        .bind(COMMAND_HISTORY_KEPT)
+        .execute(&self.pool)
+        .await
+        .context("Failed to prune command history")?;
+
+        Ok(())
+    }
+
+    /// The `limit` most recent CLI invocations, newest first.
+    pub async fn list_command_history(&self, limit: i64) -> Result<Vec<CommandHistoryEntry>> {
+        let rows: Vec<(i64, String, String)> = sqlx::query_as(
+            "SELECT id, args, invoked_at FROM command_history ORDER BY id DESC LIMIT ?1",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list command history")?;
+
+        rows.into_iter().map(command_from_row).collect()
+    }
+
+    /// A CLI invocation by its history number.
+    pub async fn get_command(&self, id: i64) -> Result<Option<CommandHistoryEntry>> {
+        let row: Option<(i64, String, String)> =
+            sqlx::query_as("SELECT id, args, invoked_at FROM command_history WHERE id = ?1")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to read command history")?;
+
+        row.map(command_from_row).transpose()
+    }
+
+    /// Count expired AI cache entries.
+    pub async fn count_expired_ai_cache(&self) -> Result<u64> {
+        let (count,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM ai_cache WHERE expires_at <= ?1")
+                .bind(Utc::now().to_rfc3339())
+                .fetch_one(&self.pool)
+                .await
+                .context("Failed to count expired AI cache entries")?;
+
+        Ok(count as u64)
+    }
+
+    /// Delete expired AI cache entries. Returns the number removed.
+    pub async fn purge_expired_ai_cache(&self) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM ai_cache WHERE expires_at <= ?1")
+            .bind(Utc::now().to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .context("Failed to purge AI cache")?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+/// Check `todo` against [`validation`], and that its category exists,
+/// before saving it over `previous`
+async fn check_todo<'e, E: sqlx::SqliteExecutor<'e>>(
+    executor: E,
+    todo: &Todo,
+    previous: Option<&Todo>,
+) -> Result<()> {
+    validation::validate_todo(todo, previous, Utc::now())?;
+    if let Some(category_id) = todo.category_id
+        && previous.is_none_or(|p| p.category_id != todo.category_id)
+    {
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM categories WHERE id = ?1")
+            .bind(category_id.to_string())
+            .fetch_one(executor)
+            .await
+            .context("Failed to look up category")?;
+        if count == 0 {
+            return Err(TodoeeError::Validation {
+                field: "category",
+                message: format!("{} does not exist", category_id),
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Insert a todo row using any SQLite executor (pool or transaction)
+async fn insert_todo<'e, E: sqlx::SqliteExecutor<'e>>(executor: E, todo: &Todo) -> Result<()> {
+    let priority_val = todo.priority as i32;
+
+    let sync_status = match todo.sync_status {
+        SyncStatus::Pending => "pending",
+        SyncStatus::Synced => "synced",
+        SyncStatus::Conflict => "conflict",
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO todos (
+            id, user_id, category_id, title, description, due_date, reminder_at,
+            priority, is_completed, completed_at, ai_metadata, created_at, updated_at, sync_status,
+            icon, project_id, remind_before_minutes, external_ref, no_nag
+        ) VALUES (
+            ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19
+        )
+        "#,
+    )
+    .bind(todo.id.to_string())
+    .bind(todo.user_id.map(|u| u.to_string()))
+    .bind(todo.category_id.map(|c| c.to_string()))
+    .bind(&todo.title)
+    .bind(&todo.description)
+    .bind(todo.due_date.map(|d| d.to_rfc3339()))
+    .bind(todo.reminder_at.map(|r| r.to_rfc3339()))
+    .bind(priority_val)
+    .bind(if todo.is_completed { 1 } else { 0 })
+    .bind(todo.completed_at.map(|c| c.to_rfc3339()))
+    .bind(todo.ai_metadata.as_ref().map(|m| m.to_string()))
+    .bind(todo.created_at.to_rfc3339())
+    .bind(todo.updated_at.to_rfc3339())
+    .bind(sync_status)
+    .bind(&todo.icon)
+    .bind(todo.project_id.map(|p| p.to_string()))
+    .bind(todo.remind_before_minutes)
+    .bind(&todo.external_ref)
+    .bind(if todo.no_nag { 1 } else { 0 })
+    .execute(executor)
+    .await
+    .context("Failed to create todo")?;
+
+    Ok(())
+}
+
+/// Overwrite a todo row with the given state using any SQLite executor
+async fn write_todo_update<'e, E: sqlx::SqliteExecutor<'e>>(
+    executor: E,
+    todo: &Todo,
+) -> Result<()> {
+    let priority_val = todo.priority as i32;
+
+    let sync_status = match todo.sync_status {
+        SyncStatus::Pending => "pending",
+        SyncStatus::Synced => "synced",
+        SyncStatus::Conflict => "conflict",
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE todos SET
+            user_id = ?1,
+            category_id = ?2,
+            title = ?3,
+            description = ?4,
+            due_date = ?5,
+            reminder_at = ?6,
+            priority = ?7,
+            is_completed = ?8,
+            completed_at = ?9,
+            ai_metadata = ?10,
+            updated_at = ?11,
+            sync_status = ?12,
+            icon = ?13,
+            project_id = ?14,
+            remind_before_minutes = ?15,
+            external_ref = ?16,
+            no_nag = ?17
+        WHERE id = ?18
+        "#,
+    )
+    .bind(todo.user_id.map(|u| u.to_string()))
+    .bind(todo.category_id.map(|c| c.to_string()))
+    .bind(&todo.title)
+    .bind(&todo.description)
+    .bind(todo.due_date.map(|d| d.to_rfc3339()))
+    .bind(todo.reminder_at.map(|r| r.to_rfc3339()))
+    .bind(priority_val)
+    .bind(if todo.is_completed { 1 } else { 0 })
+    .bind(todo.completed_at.map(|c| c.to_rfc3339()))
+    .bind(todo.ai_metadata.as_ref().map(|m| m.to_string()))
+    .bind(todo.updated_at.to_rfc3339())
+    .bind(sync_status)
+    .bind(&todo.icon)
+    .bind(todo.project_id.map(|p| p.to_string()))
+    .bind(todo.remind_before_minutes)
+    .bind(&todo.external_ref)
+    .bind(if todo.no_nag { 1 } else { 0 })
+    .bind(todo.id.to_string())
+    .execute(executor)
+    .await
+    .context("Failed to update todo")?;
+
+    Ok(())
+}
+
+fn conflict_from_row(local: Todo, remote: &str, detected_at: &str) -> Result<Conflict> {
+    Ok(Conflict {
+        local,
+        remote: serde_json::from_str(remote).context("Invalid remote todo in conflict")?,
+        detected_at: DateTime::parse_from_rfc3339(detected_at)
+            .context("Invalid conflict detected_at")?
+            .with_timezone(&Utc),
+    })
+}
+
+/// Move a todo to `state` as part of undoing (`undo`) or redoing an operation.
+///
+/// A missing state means the todo did not exist on that side of the
+/// operation, so it is deleted; a state for a deleted todo restores it.
+async fn apply_todo_state(
+    conn: &mut SqliteConnection,
+    op: &Operation,
+    state: Option<&serde_json::Value>,
+    undo: bool,
+) -> Result<()> {
+    if op.entity_type != EntityType::Todo {
+        anyhow::bail!("Cannot undo category operations in a group");
+    }
+
+    if op.operation_type == OperationType::BatchCreate {
+        for todo in op.batch_todos() {
+            if undo {
+                soft_delete_todo(&mut *conn, todo.id).await?;
+            } else {
+                restore_todo_row(&mut *conn, &todo).await?;
+            }
+        }
+        return Ok(());
+    }
+
+    if op.operation_type == OperationType::Summary {
+        for (id, before, after) in op.summary_states() {
+            let state = if undo { before } else { after };
+            set_todo_state(&mut *conn, id, state.as_ref()).await?;
+        }
+        return Ok(());
+    }
+
+    set_todo_state(conn, op.entity_id, state).await
+}
+
+/// Move todo `id` to `state` for undo or redo; `None` deletes it.
+async fn set_todo_state(
+    conn: &mut SqliteConnection,
+    id: Uuid,
+    state: Option<&serde_json::Value>,
+) -> Result<()> {
+    let Some(state) = state else {
+        return soft_delete_todo(conn, id).await;
+    };
+    let mut todo: Todo =
+        serde_json::from_value(state.clone()).context("Invalid todo state in operation")?;
+    // The change itself must reach the remote on the next sync
+    todo.updated_at = Utc::now();
+    todo.sync_status = SyncStatus::Pending;
+
+    let (exists,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM todos WHERE id = ?1 AND deleted_at IS NULL")
+            .bind(todo.id.to_string())
+            .fetch_one(&mut *conn)
+            .await
+            .context("Failed to look up todo")?;
+    if exists > 0 {
+        write_todo_update(&mut *conn, &todo).await
+    } else {
+        restore_todo_row(conn, &todo).await
+    }
+}
+
+/// Insert a category using any SQLite executor
+async fn insert_category<'e, E: sqlx::SqliteExecutor<'e>>(
+    executor: E,
+    category: &Category,
+) -> Result<()> {
+    let sync_status = match category.sync_status {
+        SyncStatus::Pending => "pending",
+        SyncStatus::Synced => "synced",
+        SyncStatus::Conflict => "conflict",
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO categories (id, user_id, name, color, is_ai_generated, sync_status)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        "#,
+    )
+    .bind(category.id.to_string())
+    .bind(Some(category.user_id.to_string()))
+    .bind(&category.name)
+    .bind(&category.color)
+    .bind(if category.is_ai_generated { 1 } else { 0 })
+    .bind(sync_status)
+    .execute(executor)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+            anyhow::anyhow!("Category '{}' already exists", category.name)
+        }
+        e => anyhow::Error::new(e).context("Failed to create category"),
+    })?;
+
+    Ok(())
+}
+
+/// Record a deletion for sync using any SQLite executor
+async fn record_deletion<'e, E: sqlx::SqliteExecutor<'e>>(executor: E, id: Uuid) -> Result<()> {
+    sqlx::query(
+        "INSERT OR REPLACE INTO deleted_todos (id, deleted_at, synced) VALUES (?, datetime('now'), 0)",
+    )
+    .bind(id.to_string())
+    .execute(executor)
+    .await
+    .context("Failed to record deleted todo")?;
+
+    Ok(())
+}
+
+/// Soft delete a todo and record the deletion for sync
+async fn soft_delete_todo(conn: &mut SqliteConnection, id: Uuid) -> Result<()> {
+    // Record deletion for sync tracking BEFORE deleting
+    record_deletion(&mut *conn, id).await?;
+
+    let now = Utc::now().to_rfc3339();
+    sqlx::query(
+        "UPDATE todos SET deleted_at = ?1, updated_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+    )
+    .bind(&now)
+    .bind(id.to_string())
+    .execute(&mut *conn)
+    .await
+    .context("Failed to delete todo")?;
+
+    Ok(())
+}
+
+/// Bring back a todo with the given state, replacing any soft-deleted row
+async fn restore_todo_row(conn: &mut SqliteConnection, todo: &Todo) -> Result<()> {
+    let mut restored = todo.clone();
+    restored.updated_at = Utc::now();
+    restored.sync_status = SyncStatus::Pending;
+
+    sqlx::query("DELETE FROM todos WHERE id = ?1 AND deleted_at IS NOT NULL")
+        .bind(todo.id.to_string())
+        .execute(&mut *conn)
+        .await
+        .context("Failed to clear deleted todo")?;
+
+    insert_todo(&mut *conn, &restored).await?;
+
+    sqlx::query("DELETE FROM deleted_todos WHERE id = ?1")
+        .bind(todo.id.to_string())
+        .execute(&mut *conn)
+        .await
+        .context("Failed to clear deletion record for restored todo")?;
+
+    Ok(())
+}
+
+/// Put a new change on top of the undo stack. Operations undone before it
+/// are dropped: redoing them on top of the new change could overwrite it.
+async fn push_operation(conn: &mut SqliteConnection, op: &Operation) -> Result<()> {
+    sqlx::query("DELETE FROM operations WHERE undone = 1")
+        .execute(&mut *conn)
+        .await
+        .context("Failed to discard redoable operations")?;
+    insert_operation(conn, op, None).await
+}
+
+/// Insert an operation at `seq` in the undo stack, or on top of it
+async fn insert_operation(
+    conn: &mut SqliteConnection,
+    op: &Operation,
+    seq: Option<i64>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO operations (
+            id, operation_type, entity_type, entity_id,
+            previous_state, new_state, created_at, undone, operation_group_id, seq
+        ) VALUES (
+            ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9,
+            COALESCE(?10, (SELECT COALESCE(MAX(seq), 0) + 1 FROM operations))
+        )
+        "#,
+    )
+    .bind(op.id.to_string())
+    .bind(op.operation_type.to_string())
+    .bind(op.entity_type.to_string())
+    .bind(op.entity_id.to_string())
+    .bind(op.previous_state.as_ref().map(|v| v.to_string()))
+    .bind(op.new_state.as_ref().map(|v| v.to_string()))
+    .bind(op.created_at.to_rfc3339())
+    .bind(if op.undone { 1 } else { 0 })
+    .bind(op.group_id.map(|id| id.to_string()))
+    .bind(seq)
+    .execute(conn)
+    .await
+    .context("Failed to record operation")?;
+
+    Ok(())
+}
+
+type NoteLinkRow = (String, String, i64, String, i32);
+
+fn note_link_from_row((todo_id, path, line, text, two_way): NoteLinkRow) -> Result<NoteLink> {
+    Ok(NoteLink {
+        todo_id: Uuid::parse_str(&todo_id).context("Invalid note link todo_id")?,
+        path,
+        line: line as u32,
+        text,
+        two_way: two_way != 0,
+    })
+}
+
+type TodoNoteRow = (i64, String, String, String);
+
+fn todo_note_from_row((id, todo_id, body, created_at): TodoNoteRow) -> Result<TodoNote> {
+    Ok(TodoNote {
+        id,
+        todo_id: Uuid::parse_str(&todo_id).context("Invalid note todo_id")?,
+        body,
+        created_at: DateTime::parse_from_rfc3339(&created_at)
+            .map(|dt| dt.with_timezone(&Utc))
+            .context("Invalid note created_at")?,
+    })
+}
+
+type AttachmentRow = (String, String, String, String);
+
+fn attachment_from_row((id, todo_id, target, created_at): AttachmentRow) -> Result<Attachment> {
+    Ok(Attachment {
+        id: Uuid::parse_str(&id).context("Invalid attachment id")?,
+        todo_id: Uuid::parse_str(&todo_id).context("Invalid attachment todo_id")?,
+        target,
+        created_at: DateTime::parse_from_rfc3339(&created_at)
+            .map(|dt| dt.with_timezone(&Utc))
+            .context("Invalid attachment created_at")?,
+    })
+}
+
+type FocusSessionRow = (String, String, String, String, Option<u32>, Option<String>);
+
+fn focus_session_from_row(
+    (id, todo_id, started_at, ended_at, planned_minutes, outcome): FocusSessionRow,
+    interruptions: Vec<(String, Option<String>)>,
+) -> Result<FocusSession> {
+    let parse = |at: &str| {
+        DateTime::parse_from_rfc3339(at)
+            .map(|dt| dt.with_timezone(&Utc))
+            .context("Invalid focus session time")
+    };
+    Ok(FocusSession {
+        id: Uuid::parse_str(&id).context("Invalid focus session id")?,
+        todo_id: Uuid::parse_str(&todo_id).context("Invalid focus session todo_id")?,
+        started_at: parse(&started_at)?,
+        ended_at: parse(&ended_at)?,
+        planned_minutes,
+        outcome: outcome.map(|o| o.parse()).transpose()?,
+        interruptions: interruptions
+            .into_iter()
+            .map(|(at, reason)| {
+                Ok(FocusInterruption {
+                    at: parse(&at)?,
+                    reason,
+                })
+            })
+            .collect::<Result<_>>()?,
+    })
+}
+
+type SyncRunRow = (String, i64, i64, String, Option<String>);
+
+fn sync_run_from_row(
+    (finished_at, uploaded, downloaded, conflict_ids, error): SyncRunRow,
+) -> Result<SyncRun> {
+    Ok(SyncRun {
+        finished_at: DateTime::parse_from_rfc3339(&finished_at)
+            .map(|dt| dt.with_timezone(&Utc))
+            .context("Invalid sync run finished_at")?,
+        uploaded: uploaded as usize,
+        downloaded: downloaded as usize,
+        conflict_ids: serde_json::from_str(&conflict_ids)
+            .context("Invalid sync run conflict_ids")?,
+        error,
+    })
+}
+
+type JournalRow = (String, String, String);
+
+fn journal_from_row((date, note, updated_at): JournalRow) -> Result<JournalEntry> {
+    Ok(JournalEntry {
+        date: NaiveDate::parse_from_str(&date, "%Y-%m-%d").context("Invalid journal date")?,
+        note,
+        updated_at: DateTime::parse_from_rfc3339(&updated_at)
+            .map(|dt| dt.with_timezone(&Utc))
+            .context("Invalid journal updated_at")?,
+    })
+}
+
+fn command_from_row((id, args, invoked_at): (i64, String, String)) -> Result<CommandHistoryEntry> {
+    Ok(CommandHistoryEntry {
+        id,
+        args: serde_json::from_str(&args).context("Invalid command history args")?,
+        invoked_at: DateTime::parse_from_rfc3339(&invoked_at)
+            .map(|dt| dt.with_timezone(&Utc))
+            .context("Invalid command history invoked_at")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::FocusOutcome;
+
+    async fn setup_db() -> LocalDb {
+        let db = LocalDb::new_in_memory().await.unwrap();
+        db.run_migrations().await.unwrap();
+        db
+    }
+
+    #[tokio::test]
+    async fn test_create_and_get_todo() {
+        let db = setup_db().await;
+
+        let todo = Todo::new("Buy groceries".to_string(), None);
+        db.create_todo(&todo).await.unwrap();
+
+        let retrieved = db.get_todo(todo.id).await.unwrap();
+        assert!(retrieved.is_some());
+
+        let retrieved = retrieved.unwrap();
+        assert_eq!(retrieved.id, todo.id);
+        assert_eq!(retrieved.title, "Buy groceries");
+        assert!(!retrieved.is_completed);
+        assert_eq!(retrieved.priority, Priority::Medium);
+    }
+
+    #[tokio::test]
+    async fn test_list_todos_filtered() {
+        let db = setup_db().await;
+        let waiting = Category::new(Uuid::nil(), "Waiting".to_string());
+        db.create_category(&waiting).await.unwrap();
+
+        let now = Utc::now();
+        let mut soon = Todo::new("Call the bank".to_string(), None);
+        soon.priority = Priority::High;
+        soon.due_date = Some(now + Duration::hours(1));
+        let mut blocked = soon.clone();
+        blocked.id = Uuid::new_v4();
+        blocked.category_id = Some(waiting.id);
+        let mut done = soon.clone();
+        done.id = Uuid::new_v4();
+        done.mark_complete();
+        let mut later = soon.clone();
+        later.id = Uuid::new_v4();
+        later.due_date = Some(now + Duration::days(30));
+        let low = Todo::new("Call a friend".to_string(), None);
+        for todo in [&soon, &blocked, &done, &later, &low] {
+            db.create_todo(todo).await.unwrap();
+        }
+
+        let filter: Filter = "priority:high and due<=+3d and not tag:waiting"
+            .parse()
+            .unwrap();
+        let open = db.list_todos_filtered(&filter, true, &now).await.unwrap();
+        assert_eq!(open.iter().map(|t| t.id).collect::<Vec<_>>(), [soon.id]);
+        let all = db.list_todos_filtered(&filter, false, &now).await.unwrap();
+        assert_eq!(all.len(), 2);
+
+        // Title words are checked outside SQLite
+        let filter: Filter = "call friend or tag:WAITING".parse().unwrap();
+        let found = db.list_todos_filtered(&filter, true, &now).await.unwrap();
+        assert_eq!(found.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_list_todos_not_completed() {
+        let db = setup_db().await;
+
+        // Create some todos
+        let todo1 = Todo::new("Task 1".to_string(), None);
+        let mut todo2 = Todo::new("Task 2".to_string(), None);
+        todo2.mark_complete();
+        let todo3 = Todo::new("Task 3".to_string(), None);
+
+        db.create_todo(&todo1).await.unwrap();
+        db.create_todo(&todo2).await.unwrap();
+        db.create_todo(&todo3).await.unwrap();
+
+        // list_todos(true) returns only non-completed
+        let todos = db.list_todos(true).await.unwrap();
+        assert_eq!(todos.len(), 2);
+
+        // list_todos(false) returns all todos
+        let all_todos = db.list_todos(false).await.unwrap();
+        assert_eq!(all_todos.len(), 3);
+
+        // Verify none of them are completed
+        for todo in &todos {
+            assert!(!todo.is_completed);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_todo() {
+        let db = setup_db().await;
+
+        let mut todo = Todo::new("Original title".to_string(), None);
+        db.create_todo(&todo).await.unwrap();
+
+        // Update the todo
+        todo.title = "Updated title".to_string();
+        todo.description = Some("A description".to_string());
+        todo.priority = Priority::High;
+        db.update_todo(&todo).await.unwrap();
+
+        // Verify the update
+        let retrieved = db.get_todo(todo.id).await.unwrap().unwrap();
+        assert_eq!(retrieved.title, "Updated title");
         assert_eq!(retrieved.description, Some("A description".to_string()));
         assert_eq!(retrieved.priority, Priority::High);
     }
 
     #[tokio::test]
-    async fn test_delete_todo() {
+    async fn test_update_todo_if_unchanged() {
+        let db = setup_db().await;
+        let todo = Todo::new("Shared task".to_string(), None);
+        db.create_todo(&todo).await.unwrap();
+        let read_at = todo.updated_at;
+
+        // Another client saves first
+        let mut theirs = todo.clone();
+        theirs.title = "Their title".to_string();
+        theirs.updated_at = read_at + Duration::seconds(1);
+        assert!(matches!(
+            db.update_todo_if_unchanged(&theirs, read_at, None)
+                .await
+                .unwrap(),
+            ConditionalUpdate::Updated
+        ));
+
+        // Saving from the stale copy is refused and returns their version
+        let mut mine = todo.clone();
+        mine.priority = Priority::High;
+        mine.updated_at = read_at + Duration::seconds(2);
+        let errands = Category::new(Uuid::new_v4(), "Errands".to_string());
+        mine.category_id = Some(errands.id);
+        match db
+            .update_todo_if_unchanged(&mine, read_at, Some(&errands))
+            .await
+            .unwrap()
+        {
+            ConditionalUpdate::Conflict(current) => assert_eq!(current.title, "Their title"),
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+        let stored = db.get_todo(todo.id).await.unwrap().unwrap();
+        assert_eq!(stored.title, "Their title");
+        assert_eq!(stored.priority, Priority::Medium);
+        assert!(db.get_category_by_name("Errands").await.unwrap().is_none());
+
+        // Once the check passes the new category is created with the update
+        assert!(matches!(
+            db.update_todo_if_unchanged(&mine, stored.updated_at, Some(&errands))
+                .await
+                .unwrap(),
+            ConditionalUpdate::Updated
+        ));
+        let stored = db.get_todo(todo.id).await.unwrap().unwrap();
+        assert_eq!(stored.category_id, Some(errands.id));
+        assert!(db.get_category_by_name("Errands").await.unwrap().is_some());
+
+        db.delete_todo(todo.id).await.unwrap();
+        assert!(matches!(
+            db.update_todo_if_unchanged(&mine, stored.updated_at, None)
+                .await
+                .unwrap(),
+            ConditionalUpdate::NotFound
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_file_db_uses_wal_and_shares_writes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("shared.db");
+        let tui = LocalDb::with_pool_size(&path, 2).await.unwrap();
+        tui.run_migrations().await.unwrap();
+        let daemon = LocalDb::new(&path).await.unwrap();
+
+        let (mode,): (String,) = sqlx::query_as("PRAGMA journal_mode")
+            .fetch_one(&tui.pool)
+            .await
+            .unwrap();
+        assert_eq!(mode, "wal");
+
+        // Two processes writing at once wait for each other instead of
+        // failing with SQLITE_BUSY
+        for i in 0..10 {
+            let first = Todo::new(format!("TUI task {}", i), None);
+            let second = Todo::new(format!("Daemon task {}", i), None);
+            let (a, b) = tokio::join!(tui.create_todo(&first), daemon.create_todo(&second));
+            a.unwrap();
+            b.unwrap();
+        }
+        assert_eq!(daemon.list_todos(false).await.unwrap().len(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_todos_due_within_window() {
+        let db = LocalDb::new_in_memory().await.unwrap();
+        db.run_migrations().await.unwrap();
+
+        let mut soon = Todo::new("Soon".to_string(), None);
+        soon.due_date = Some(Utc::now() + chrono::Duration::minutes(30));
+        soon.remind_before_minutes = Some(45);
+        let mut later = Todo::new("Later".to_string(), None);
+        later.due_date = Some(Utc::now() + chrono::Duration::days(2));
+        let mut done = Todo::new("Done".to_string(), None);
+        done.due_date = soon.due_date;
+        done.mark_complete();
+        for todo in [&soon, &later, &done] {
+            db.create_todo(todo).await.unwrap();
+        }
+
+        let due = db
+            .list_todos_due_within(chrono::Duration::hours(1))
+            .await
+            .unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, soon.id);
+        assert_eq!(due[0].remind_before_minutes, Some(45));
+    }
+
+    #[tokio::test]
+    async fn test_todo_icon_round_trips() {
+        let db = setup_db().await;
+
+        let mut todo = Todo::new("Deploy".to_string(), None);
+        todo.icon = Some("🚀".to_string());
+        db.create_todo(&todo).await.unwrap();
+        let retrieved = db.get_todo(todo.id).await.unwrap().unwrap();
+        assert_eq!(retrieved.icon.as_deref(), Some("🚀"));
+
+        todo.icon = None;
+        db.update_todo(&todo).await.unwrap();
+        let retrieved = db.get_todo(todo.id).await.unwrap().unwrap();
+        assert!(retrieved.icon.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_todo() {
+        let db = setup_db().await;
+
+        let todo = Todo::new("To be deleted".to_string(), None);
+        db.create_todo(&todo).await.unwrap();
+
+        // Verify it exists
+        assert!(db.get_todo(todo.id).await.unwrap().is_some());
+
+        // Delete it
+        db.delete_todo(todo.id).await.unwrap();
+
+        // Verify it's gone
+        assert!(db.get_todo(todo.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_pending_sync() {
+        let db = setup_db().await;
+
+        // Create some todos with different sync statuses
+        let todo1 = Todo::new("Pending 1".to_string(), None);
+        let todo2 = Todo::new("Pending 2".to_string(), None);
+
+        db.create_todo(&todo1).await.unwrap();
+        db.create_todo(&todo2).await.unwrap();
+
+        // Mark one as synced
+        db.mark_synced(todo1.id).await.unwrap();
+
+        // List pending sync
+        let pending = db.list_pending_sync().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, todo2.id);
+    }
+
+    #[tokio::test]
+    async fn test_categories() {
+        let db = setup_db().await;
+
+        let user_id = Uuid::new_v4();
+        let mut category = Category::new(user_id, "Work".to_string());
+        category.color = Some("#ff0000".to_string());
+
+        db.create_category(&category).await.unwrap();
+
+        // Get by name
+        let retrieved = db.get_category_by_name("Work").await.unwrap();
+        assert!(retrieved.is_some());
+        let retrieved = retrieved.unwrap();
+        assert_eq!(retrieved.name, "Work");
+        assert_eq!(retrieved.color, Some("#ff0000".to_string()));
+
+        // List categories
+        let categories = db.list_categories().await.unwrap();
+        assert_eq!(categories.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_category_name_unique_per_user() {
+        let db = setup_db().await;
+
+        let user_id = Uuid::new_v4();
+        db.create_category(&Category::new(user_id, "Work".to_string()))
+            .await
+            .unwrap();
+
+        let err = db
+            .create_category(&Category::new(user_id, "Work".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Category 'Work' already exists");
+
+        // Another user may use the same name
+        db.create_category(&Category::new(Uuid::new_v4(), "Work".to_string()))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_migrations_merge_duplicate_categories() {
+        let db = setup_db().await;
+        sqlx::query("DROP INDEX idx_categories_user_name")
+            .execute(db.pool())
+            .await
+            .unwrap();
+
+        let first = Category::new(Uuid::nil(), "Work".to_string());
+        let second = Category::new(Uuid::nil(), "Work".to_string());
+        db.create_category(&first).await.unwrap();
+        db.create_category(&second).await.unwrap();
+
+        let mut todo = Todo::new("Filed under the duplicate".to_string(), None);
+        todo.category_id = Some(second.id);
+        db.create_todo(&todo).await.unwrap();
+
+        db.run_migrations().await.unwrap();
+
+        let categories = db.list_categories().await.unwrap();
+        assert_eq!(categories.len(), 1);
+        assert_eq!(categories[0].id, first.id);
+        let todo = db.get_todo(todo.id).await.unwrap().unwrap();
+        assert_eq!(todo.category_id, Some(first.id));
+    }
+
+    #[tokio::test]
+    async fn test_rename_category() {
+        let db = setup_db().await;
+
+        let category = Category::new(Uuid::nil(), "Work".to_string());
+        db.create_category(&category).await.unwrap();
+        db.create_category(&Category::new(Uuid::nil(), "Home".to_string()))
+            .await
+            .unwrap();
+        db.mark_category_synced(category.id).await.unwrap();
+        let mut todo = Todo::new("Report".to_string(), None);
+        todo.category_id = Some(category.id);
+        db.create_todo(&todo).await.unwrap();
+
+        db.rename_category(category.id, "Job").await.unwrap();
+        assert!(db.get_category_by_name("Work").await.unwrap().is_none());
+        let renamed = db.get_category_by_name("Job").await.unwrap().unwrap();
+        assert_eq!(renamed.id, category.id);
+        assert_eq!(renamed.sync_status, SyncStatus::Pending);
+        assert_eq!(
+            db.list_todos_by_category(category.id).await.unwrap().len(),
+            1
+        );
+
+        let err = db.rename_category(category.id, "Home").await.unwrap_err();
+        assert_eq!(err.to_string(), "Category 'Home' already exists");
+    }
+
+    #[tokio::test]
+    async fn test_rekey_category() {
+        let db = setup_db().await;
+
+        let category = Category::new(Uuid::nil(), "Work".to_string());
+        db.create_category(&category).await.unwrap();
+        let mut todo = Todo::new("Report".to_string(), None);
+        todo.category_id = Some(category.id);
+        db.create_todo(&todo).await.unwrap();
+
+        let remote_id = Uuid::new_v4();
+        db.rekey_category(category.id, remote_id).await.unwrap();
+
+        let rekeyed = db.get_category_by_name("Work").await.unwrap().unwrap();
+        assert_eq!(rekeyed.id, remote_id);
+        assert_eq!(rekeyed.sync_status, SyncStatus::Synced);
+        let todo = db.get_todo(todo.id).await.unwrap().unwrap();
+        assert_eq!(todo.category_id, Some(remote_id));
+    }
+
+    #[tokio::test]
+    async fn test_projects_and_progress() {
+        let db = setup_db().await;
+
+        let launch = Project::new(Uuid::nil(), "Launch".to_string());
+        let mut old = Project::new(Uuid::nil(), "Old".to_string());
+        db.create_project(&launch).await.unwrap();
+        db.create_project(&old).await.unwrap();
+        let err = db
+            .create_project(&Project::new(Uuid::nil(), "Launch".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Project 'Launch' already exists");
+
+        let mut done = Todo::new("Write copy".to_string(), None);
+        done.project_id = Some(launch.id);
+        done.mark_complete();
+        let mut open = Todo::new("Ship it".to_string(), None);
+        open.project_id = Some(launch.id);
+        db.create_todo(&done).await.unwrap();
+        db.create_todo(&open).await.unwrap();
+        db.create_todo(&Todo::new("Unrelated".to_string(), None))
+            .await
+            .unwrap();
+
+        assert_eq!(db.list_todos_by_project(launch.id).await.unwrap().len(), 2);
+
+        old.archive();
+        db.update_project(&old).await.unwrap();
+        assert_eq!(db.list_projects(false).await.unwrap().len(), 1);
+        assert_eq!(db.list_projects(true).await.unwrap().len(), 2);
+        assert!(db.get_project(old.id).await.unwrap().unwrap().is_archived());
+
+        let progress = db.project_progress().await.unwrap();
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0].project.name, "Launch");
+        assert_eq!((progress[0].completed, progress[0].total), (1, 2));
+        assert_eq!(progress[0].ratio(), 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_insight_aggregates() {
+        let db = setup_db().await;
+        let now = Utc::now();
+        let since = now - chrono::Duration::days(7);
+
+        let work = Category::new(Uuid::nil(), "Work".to_string());
+        db.create_category(&work).await.unwrap();
+        let mut shipped = Todo::new("Ship".to_string(), None);
+        shipped.category_id = Some(work.id);
+        shipped.created_at = now - chrono::Duration::days(2);
+        shipped.mark_complete();
+        let mut loose = Todo::new("Loose end".to_string(), None);
+        loose.created_at = now - chrono::Duration::days(4);
+        loose.mark_complete();
+        let mut urgent = Todo::new("Urgent".to_string(), None);
+        urgent.priority = Priority::High;
+        urgent.due_date = Some(now - chrono::Duration::days(1));
+        let mut ancient = Todo::new("Ancient".to_string(), None);
+        ancient.created_at = now - chrono::Duration::days(400);
+        for todo in [&shipped, &loose, &urgent, &ancient] {
+            db.create_todo(todo).await.unwrap();
+        }
+
+        let stats = db.todo_stats(since, now).await.unwrap();
+        assert_eq!(
+            stats,
+            TodoStats {
+                created: 3,
+                completed: 2,
+                pending: 2,
+                overdue: 1,
+                pending_high: 1,
+                pending_medium: 1,
+                pending_low: 0,
+            }
+        );
+
+        let progress = db
+            .due_progress(now - chrono::Duration::days(2), now)
+            .await
+            .unwrap();
+        assert_eq!(
+            progress,
+            DueProgress {
+                due: 1,
+                completed: 0
+            }
+        );
+        assert!(!progress.is_cleared());
+
+        let by_category = db.completions_by_category(since).await.unwrap();
+        assert_eq!(by_category.len(), 2);
+        assert!(by_category.iter().all(|c| c.completed == 1));
+        assert!(by_category.iter().any(|c| c.category.is_none()));
+
+        let average = db.average_completion_time(since).await.unwrap().unwrap();
+        assert!((average - chrono::Duration::days(3)).num_seconds().abs() < 60);
+        assert!(
+            db.average_completion_time(now + chrono::Duration::days(1))
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let hour = now - chrono::Duration::days(2);
+        let batch = serde_json::to_value([&urgent, &ancient]).unwrap();
+        let ops = [
+            (OperationType::Create, None, hour),
+            (OperationType::BatchCreate, Some(batch), hour),
+            (OperationType::Complete, None, hour),
+            (OperationType::Complete, None, now),
+            (
+                OperationType::Complete,
+                None,
+                now - chrono::Duration::days(30),
+            ),
+        ];
+        for (op_type, state, at) in ops {
+            let mut op = Operation::new(op_type, EntityType::Todo, shipped.id, None, state);
+            op.created_at = at;
+            db.record_operation(&op).await.unwrap();
+        }
+
+        let buckets = db.activity_by_hour(since).await.unwrap();
+        assert_eq!(buckets.len(), 2);
+        assert_eq!((buckets[0].created, buckets[0].completed), (3, 1));
+        assert_eq!((buckets[1].created, buckets[1].completed), (0, 1));
+        assert!(buckets[0].hour <= hour && hour - buckets[0].hour < chrono::Duration::hours(1));
+    }
+
+    #[tokio::test]
+    async fn test_focus_sessions_and_stats() {
+        let db = setup_db().await;
+        let todo = Todo::new("Write report".to_string(), None);
+        db.create_todo(&todo).await.unwrap();
+        let now = Utc::now();
+
+        let mut first = FocusSession::new(todo.id, now - chrono::Duration::minutes(50));
+        first.planned_minutes = Some(25);
+        first.outcome = Some(FocusOutcome::Completed);
+        first.interrupt("Slack", now - chrono::Duration::minutes(40));
+        first.interrupt("  ", now - chrono::Duration::minutes(35));
+        first.ended_at = now - chrono::Duration::minutes(25);
+        let mut second = FocusSession::new(todo.id, now - chrono::Duration::minutes(25));
+        second.interrupt("slack", now - chrono::Duration::minutes(10));
+        second.interrupt("Phone call", now - chrono::Duration::minutes(5));
+        second.ended_at = now;
+        let mut old = FocusSession::new(todo.id, now - chrono::Duration::days(40));
+        old.interrupt("Meeting", now - chrono::Duration::days(40));
+        for session in [&first, &second, &old] {
+            db.save_focus_session(session).await.unwrap();
+        }
+        assert_eq!(first.interruptions[1].reason, None);
+
+        let stats = db
+            .focus_stats(now - chrono::Duration::days(7))
+            .await
+            .unwrap();
+        assert_eq!((stats.sessions, stats.interruptions), (2, 4));
+        assert_eq!(stats.interruptions_per_session(), 2.0);
+        assert_eq!(
+            stats.reasons,
+            vec![("Slack".to_string(), 2), ("Phone call".to_string(), 1)]
+        );
+
+        let sessions = db
+            .list_focus_sessions(now - chrono::Duration::days(7))
+            .await
+            .unwrap();
+        assert_eq!(sessions, vec![first, second]);
+
+        let by_todo = db
+            .focus_time_by_todo(now - chrono::Duration::days(7))
+            .await
+            .unwrap();
+        assert_eq!(
+            by_todo,
+            vec![FocusTime {
+                todo_id: todo.id,
+                title: Some("Write report".to_string()),
+                sessions: 2,
+                minutes: 50,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_todo_notes() {
+        let db = setup_db().await;
+        let todo = Todo::new("Call vendor".to_string(), None);
+        let other = Todo::new("Other".to_string(), None);
+        db.create_todo(&todo).await.unwrap();
+        db.create_todo(&other).await.unwrap();
+
+        db.add_todo_note(todo.id, "Left a voicemail").await.unwrap();
+        db.add_todo_note(other.id, "Unrelated").await.unwrap();
+        let added = db
+            .add_todo_note(todo.id, "Spoke with vendor")
+            .await
+            .unwrap();
+
+        let notes = db.list_todo_notes(todo.id).await.unwrap();
+        let bodies: Vec<_> = notes.iter().map(|n| n.body.as_str()).collect();
+        assert_eq!(bodies, ["Left a voicemail", "Spoke with vendor"]);
+        assert_eq!(notes[1], added);
+
+        // Purging the todo takes its notes with it
+        sqlx::query("UPDATE todos SET deleted_at = '2000-01-01T00:00:00+00:00' WHERE id = ?1")
+            .bind(todo.id.to_string())
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        db.purge_deleted_todos(30).await.unwrap();
+        assert!(db.list_todo_notes(todo.id).await.unwrap().is_empty());
+        assert_eq!(db.list_todo_notes(other.id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_attachments() {
         let db = setup_db().await;
-
-        let todo = Todo::new("To be deleted".to_string(), None);
+        let todo = Todo::new("Review contract".to_string(), None);
         db.create_todo(&todo).await.unwrap();
 
-        // Verify it exists
-        assert!(db.get_todo(todo.id).await.unwrap().is_some());
+        let link = Attachment::new(todo.id, "https://example.com/contract".to_string());
+        let file = Attachment::new(todo.id, "/home/me/contract.pdf".to_string());
+        db.add_attachment(&link).await.unwrap();
+        db.add_attachment(&file).await.unwrap();
+        // Importing the same attachment again doesn't duplicate it
+        db.add_attachment(&link).await.unwrap();
 
-        // Delete it
-        db.delete_todo(todo.id).await.unwrap();
+        let attachments = db.list_attachments(todo.id).await.unwrap();
+        assert_eq!(attachments, vec![link.clone(), file]);
+        assert!(attachments[0].is_url());
+        assert!(!attachments[1].is_url());
+        assert_eq!(db.list_all_attachments().await.unwrap().len(), 2);
 
-        // Verify it's gone
-        assert!(db.get_todo(todo.id).await.unwrap().is_none());
+        db.delete_todo(todo.id).await.unwrap();
+        assert!(db.list_all_attachments().await.unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn test_list_pending_sync() {
+    async fn test_command_history() {
         let db = setup_db().await;
+        let args = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
 
-        // Create some todos with different sync statuses
-        let todo1 = Todo::new("Pending 1".to_string(), None);
-        let todo2 = Todo::new("Pending 2".to_string(), None);
-
-        db.create_todo(&todo1).await.unwrap();
-        db.create_todo(&todo2).await.unwrap();
+        db.record_command(&args("list -P launch")).await.unwrap();
+        db.record_command(&args("edit abc1 --title Two words"))
+            .await
+            .unwrap();
 
-        // Mark one as synced
-        db.mark_synced(todo1.id).await.unwrap();
+        let history = db.list_command_history(10).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].args, args("edit abc1 --title Two words"));
+        assert_eq!(history[1].args, args("list -P launch"));
 
-        // List pending sync
-        let pending = db.list_pending_sync().await.unwrap();
-        assert_eq!(pending.len(), 1);
-        assert_eq!(pending[0].id, todo2.id);
+        let first = db.get_command(history[1].id).await.unwrap().unwrap();
+        assert_eq!(first, history[1]);
+        assert!(db.get_command(999).await.unwrap().is_none());
+        assert_eq!(db.list_command_history(1).await.unwrap().len(), 1);
     }
 
     #[tokio::test]
-    async fn test_categories() {
+    async fn test_rekey_project() {
         let db = setup_db().await;
 
-        let user_id = Uuid::new_v4();
-        let mut category = Category::new(user_id, "Work".to_string());
-        category.color = Some("#ff0000".to_string());
-
-        db.create_category(&category).await.unwrap();
+        let project = Project::new(Uuid::nil(), "Launch".to_string());
+        db.create_project(&project).await.unwrap();
+        let mut todo = Todo::new("Ship it".to_string(), None);
+        todo.project_id = Some(project.id);
+        db.create_todo(&todo).await.unwrap();
 
-        // Get by name
-        let retrieved = db.get_category_by_name("Work").await.unwrap();
-        assert!(retrieved.is_some());
-        let retrieved = retrieved.unwrap();
-        assert_eq!(retrieved.name, "Work");
-        assert_eq!(retrieved.color, Some("#ff0000".to_string()));
+        let remote_id = Uuid::new_v4();
+        db.rekey_project(project.id, remote_id).await.unwrap();
 
-        // List categories
-        let categories = db.list_categories().await.unwrap();
-        assert_eq!(categories.len(), 1);
+        let rekeyed = db.get_project_by_name("Launch").await.unwrap().unwrap();
+        assert_eq!(rekeyed.id, remote_id);
+        assert_eq!(rekeyed.sync_status, SyncStatus::Synced);
+        assert!(db.list_pending_projects().await.unwrap().is_empty());
+        let todo = db.get_todo(todo.id).await.unwrap().unwrap();
+        assert_eq!(todo.project_id, Some(remote_id));
     }
 
     #[tokio::test]
@@ -1149,6 +4813,46 @@ mod tests {
         assert_eq!(results[0].title, "Reminder task");
     }
 
+    #[tokio::test]
+    async fn test_reminders_are_sent_once_across_restarts() {
+        let db = setup_db().await;
+        let now = Utc::now();
+        let catch_up = chrono::Duration::hours(1);
+
+        // Came round while the daemon was stopped
+        let mut missed = Todo::new("Call back".to_string(), None);
+        missed.reminder_at = Some(now - chrono::Duration::minutes(20));
+        missed.no_nag = true;
+        db.create_todo(&missed).await.unwrap();
+
+        let due = db.list_reminders_to_send(now, catch_up).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert!(due[0].no_nag);
+        assert_eq!(due[0].notified_at, None);
+
+        db.mark_notified(&[missed.id], now).await.unwrap();
+        assert!(
+            db.list_reminders_to_send(now, catch_up)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let stored = db.get_todo(missed.id).await.unwrap().unwrap();
+        assert_eq!(
+            stored.notified_at.map(|t| t.timestamp()),
+            Some(now.timestamp())
+        );
+
+        // A snooze moves the reminder past the last notification
+        let mut snoozed = stored.clone();
+        let later = now + chrono::Duration::minutes(10);
+        snoozed.snooze(chrono::Duration::minutes(10), now);
+        db.update_todo(&snoozed).await.unwrap();
+        let due = db.list_reminders_to_send(later, catch_up).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert!(due[0].notified_at.is_some());
+    }
+
     #[tokio::test]
     async fn test_list_pending_categories() {
         let db = setup_db().await;
@@ -1276,4 +4980,667 @@ mod tests {
         // Should be recorded in deleted_todos
         assert!(db.is_locally_deleted(todo.id).await.unwrap());
     }
+
+    #[tokio::test]
+    async fn test_apply_remote_deletion() {
+        let db = setup_db().await;
+
+        let todo = Todo::new("Deleted elsewhere".to_string(), None);
+        db.create_todo(&todo).await.unwrap();
+
+        assert!(db.apply_remote_deletion(todo.id).await.unwrap());
+        assert!(db.get_todo(todo.id).await.unwrap().is_none());
+
+        // Tracked as deleted, but not queued for upload
+        assert!(db.is_locally_deleted(todo.id).await.unwrap());
+        assert!(db.list_unsynced_deletions().await.unwrap().is_empty());
+
+        // Applying again is a no-op
+        assert!(!db.apply_remote_deletion(todo.id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_delete_todo_is_soft_and_restorable() {
+        let db = setup_db().await;
+
+        let mut todo = Todo::new("Oops".to_string(), None);
+        todo.sync_status = SyncStatus::Synced;
+        db.create_todo(&todo).await.unwrap();
+        db.delete_todo(todo.id).await.unwrap();
+
+        assert!(db.list_todos(false).await.unwrap().is_empty());
+        assert!(db.list_pending_sync().await.unwrap().is_empty());
+        assert_eq!(db.count_deleted_todos(0).await.unwrap(), 1);
+
+        db.restore_todo(&todo).await.unwrap();
+
+        let restored = db.get_todo(todo.id).await.unwrap().unwrap();
+        assert_eq!(restored.title, "Oops");
+        assert_eq!(restored.sync_status, SyncStatus::Pending);
+        assert!(!db.is_locally_deleted(todo.id).await.unwrap());
+        assert_eq!(db.count_deleted_todos(0).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_purge_deleted_todos() {
+        let db = setup_db().await;
+
+        let old = Todo::new("Deleted long ago".to_string(), None);
+        let recent = Todo::new("Deleted today".to_string(), None);
+        db.create_todo(&old).await.unwrap();
+        db.create_todo(&recent).await.unwrap();
+        db.delete_todo(old.id).await.unwrap();
+        db.delete_todo(recent.id).await.unwrap();
+
+        sqlx::query("UPDATE todos SET deleted_at = ?1 WHERE id = ?2")
+            .bind((Utc::now() - chrono::Duration::days(60)).to_rfc3339())
+            .bind(old.id.to_string())
+            .execute(db.pool())
+            .await
+            .unwrap();
+
+        assert_eq!(db.count_deleted_todos(30).await.unwrap(), 1);
+        assert_eq!(db.purge_deleted_todos(30).await.unwrap(), 1);
+        assert_eq!(db.count_deleted_todos(0).await.unwrap(), 1);
+
+        // Purged todos stay tracked so sync does not download them again
+        assert!(db.is_locally_deleted(old.id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_update_todo_checks_two_way_note_checkbox() {
+        let db = setup_db().await;
+        let dir = tempfile::TempDir::new().unwrap();
+        let note = dir.path().join("note.md");
+        std::fs::write(&note, "- [ ] Linked\n- [ ] Backlink only\n").unwrap();
+
+        let mut linked = Todo::new("Linked".to_string(), None);
+        let mut backlink = Todo::new("Backlink only".to_string(), None);
+        for (todo, line, two_way) in [(&linked, 1, true), (&backlink, 2, false)] {
+            db.create_todo(todo).await.unwrap();
+            db.create_note_link(&NoteLink {
+                todo_id: todo.id,
+                path: note.to_string_lossy().into_owned(),
+                line,
+                text: todo.title.clone(),
+                two_way,
+            })
+            .await
+            .unwrap();
+        }
+
+        linked.mark_complete();
+        backlink.mark_complete();
+        db.update_todo(&linked).await.unwrap();
+        db.update_todo(&backlink).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&note).unwrap(),
+            "- [x] Linked\n- [ ] Backlink only\n"
+        );
+
+        let found = db
+            .find_note_link(&note.to_string_lossy(), "Linked")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.todo_id, linked.id);
+    }
+
+    #[tokio::test]
+    async fn test_migrations_add_deleted_at_to_existing_table() {
+        let db = LocalDb::new_in_memory().await.unwrap();
+        sqlx::query(
+            r#"
+            CREATE TABLE todos (
+                id TEXT PRIMARY KEY,
+                user_id TEXT,
+                category_id TEXT,
+                title TEXT NOT NULL,
+                description TEXT,
+                due_date TEXT,
+                reminder_at TEXT,
+                priority INTEGER NOT NULL DEFAULT 2,
+                is_completed INTEGER NOT NULL DEFAULT 0,
+                completed_at TEXT,
+                ai_metadata TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                sync_status TEXT NOT NULL DEFAULT 'pending'
+            )
+            "#,
+        )
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+        db.run_migrations().await.unwrap();
+        db.run_migrations().await.unwrap();
+
+        let todo = Todo::new("Legacy".to_string(), None);
+        db.create_todo(&todo).await.unwrap();
+        db.delete_todo(todo.id).await.unwrap();
+        assert!(db.get_todo(todo.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_compact_operations_collapses_old_updates() {
+        let db = setup_db().await;
+        let todo = Todo::new("Compact me".to_string(), None);
+        let old = Utc::now() - chrono::Duration::days(60);
+
+        let states: Vec<_> = (0..4)
+            .map(|i| serde_json::json!({ "title": format!("v{}", i) }))
+            .collect();
+        for i in 0..3 {
+            let mut op = Operation::new(
+                OperationType::Update,
+                EntityType::Todo,
+                todo.id,
+                Some(states[i].clone()),
+                Some(states[i + 1].clone()),
+            );
+            op.created_at = old + chrono::Duration::minutes(i as i64);
+            db.record_operation(&op).await.unwrap();
+        }
+
+        // A recent update must survive untouched
+        let recent = Operation::new(
+            OperationType::Update,
+            EntityType::Todo,
+            todo.id,
+            Some(states[3].clone()),
+            Some(serde_json::json!({ "title": "v4" })),
+        );
+        db.record_operation(&recent).await.unwrap();
+
+        let removed = db.compact_operations(30).await.unwrap();
+        assert_eq!(removed, 2);
+
+        let ops = db.list_operations(10).await.unwrap();
+        assert_eq!(ops.len(), 2);
+        assert_eq!(ops[0].id, recent.id);
+        assert_eq!(ops[1].previous_state, Some(states[0].clone()));
+        assert_eq!(ops[1].new_state, Some(states[3].clone()));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_short_id() {
+        let db = setup_db().await;
+        let mut first = Todo::new("First".to_string(), None);
+        first.id = Uuid::parse_str("abcd1234-0000-4000-8000-000000000001").unwrap();
+        let mut second = Todo::new("Second".to_string(), None);
+        second.id = Uuid::parse_str("abce5678-0000-4000-8000-000000000002").unwrap();
+        db.create_todo(&first).await.unwrap();
+        db.create_todo(&second).await.unwrap();
+
+        let unique = |m: IdMatch| match m {
+            IdMatch::Unique(todo) => Some(todo.id),
+            _ => None,
+        };
+        assert_eq!(
+            unique(db.resolve_short_id("ABCD").await.unwrap()),
+            Some(first.id)
+        );
+        assert_eq!(
+            unique(db.resolve_short_id(&second.id.to_string()).await.unwrap()),
+            Some(second.id)
+        );
+        match db.resolve_short_id("abc").await.unwrap() {
+            IdMatch::Ambiguous(todos) => assert_eq!(todos.len(), 2),
+            other => panic!("expected ambiguous, got {:?}", other),
+        }
+        for prefix in ["abcf", "%", "", "ab_d"] {
+            assert!(matches!(
+                db.resolve_short_id(prefix).await.unwrap(),
+                IdMatch::NotFound
+            ));
+        }
+
+        db.delete_todo(first.id).await.unwrap();
+        assert_eq!(
+            unique(db.resolve_short_id("abc").await.unwrap()),
+            Some(second.id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_short_event_id() {
+        let db = setup_db().await;
+        let start = Utc::now();
+        let mut standup = Event::new("Standup".to_string(), start, start);
+        standup.id = Uuid::parse_str("abcd1234-0000-4000-8000-000000000001").unwrap();
+        let mut review = Event::new("Review".to_string(), start, start);
+        review.id = Uuid::parse_str("abce5678-0000-4000-8000-000000000002").unwrap();
+        db.create_event(&standup).await.unwrap();
+        db.create_event(&review).await.unwrap();
+
+        match db.resolve_short_event_id("ABCE").await.unwrap() {
+            IdMatch::Unique(event) => assert_eq!(event.id, review.id),
+            other => panic!("expected unique, got {:?}", other),
+        }
+        match db.resolve_short_event_id("abc").await.unwrap() {
+            IdMatch::Ambiguous(events) => assert_eq!(events.len(), 2),
+            other => panic!("expected ambiguous, got {:?}", other),
+        }
+        assert!(matches!(
+            db.resolve_short_event_id("ab%").await.unwrap(),
+            IdMatch::NotFound
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_journal_and_completions() {
+        let db = setup_db().await;
+        let day = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        assert!(db.get_journal(day).await.unwrap().is_none());
+
+        db.append_journal(day, "Good focus").await.unwrap();
+        let entry = db.append_journal(day, "Too many meetings").await.unwrap();
+        assert_eq!(entry.note, "Good focus\nToo many meetings");
+        db.append_journal(day - chrono::Duration::days(3), "Earlier")
+            .await
+            .unwrap();
+        let recent = db
+            .list_journal(day - chrono::Duration::days(1))
+            .await
+            .unwrap();
+        assert_eq!(recent, vec![entry]);
+
+        let todo = Todo::new("Ship it".to_string(), None);
+        let start = Utc::now() - chrono::Duration::hours(2);
+        let ops = [
+            (OperationType::Complete, 10, false),
+            (OperationType::Update, 20, false),
+            (OperationType::Complete, 30, true),
+            (OperationType::Complete, 200, false),
+        ];
+        for (kind, minutes, undone) in ops {
+            let mut op = Operation::new(kind, EntityType::Todo, todo.id, None, None);
+            op.created_at = start + chrono::Duration::minutes(minutes);
+            db.record_operation(&op).await.unwrap();
+            if undone {
+                db.mark_operation_undone(op.id).await.unwrap();
+            }
+        }
+        let completions = db
+            .list_completions(start, start + chrono::Duration::hours(1))
+            .await
+            .unwrap();
+        assert_eq!(completions.len(), 1);
+        assert_eq!(
+            completions[0].created_at,
+            start + chrono::Duration::minutes(10)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compact_operations_keeps_other_types_as_boundaries() {
+        let db = setup_db().await;
+        let todo = Todo::new("Boundaries".to_string(), None);
+        let old = Utc::now() - chrono::Duration::days(60);
+
+        let kinds = [
+            OperationType::Update,
+            OperationType::Complete,
+            OperationType::Update,
+        ];
+        for (i, kind) in kinds.into_iter().enumerate() {
+            let mut op = Operation::new(kind, EntityType::Todo, todo.id, None, None);
+            op.created_at = old + chrono::Duration::minutes(i as i64);
+            db.record_operation(&op).await.unwrap();
+        }
+
+        let removed = db.compact_operations(30).await.unwrap();
+        assert_eq!(removed, 0);
+        assert_eq!(db.list_operations(10).await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_old_groups_are_summarized_and_history_is_capped() {
+        let db = setup_db().await;
+        let group = Uuid::new_v4();
+        let old = Utc::now() - chrono::Duration::days(60);
+
+        // Rename one todo twice and delete another, as one old group
+        let first = Todo::new("First".to_string(), None);
+        let second = Todo::new("Second".to_string(), None);
+        let mut renamed = first.clone();
+        renamed.title = "First, renamed".to_string();
+        let mut renamed_again = renamed.clone();
+        renamed_again.title = "First, renamed again".to_string();
+        db.create_todo(&renamed_again).await.unwrap();
+        let steps = [
+            (
+                OperationType::Update,
+                first.id,
+                Some(&first),
+                Some(&renamed),
+            ),
+            (
+                OperationType::Update,
+                first.id,
+                Some(&renamed),
+                Some(&renamed_again),
+            ),
+            (OperationType::Delete, second.id, Some(&second), None),
+        ];
+        for (i, (kind, id, before, after)) in steps.into_iter().enumerate() {
+            let mut op = Operation::new(
+                kind,
+                EntityType::Todo,
+                id,
+                before.and_then(|t| serde_json::to_value(t).ok()),
+                after.and_then(|t| serde_json::to_value(t).ok()),
+            )
+            .in_group(group);
+            op.created_at = old + chrono::Duration::minutes(i as i64);
+            db.record_operation(&op).await.unwrap();
+        }
+
+        assert_eq!(db.summarize_operation_groups(30).await.unwrap(), 2);
+        let stats = db.operation_stats().await.unwrap();
+        assert_eq!(
+            (stats.operations, stats.summaries, stats.grouped),
+            (1, 1, 1)
+        );
+        assert!(stats.bytes > 0);
+
+        // Undoing the summary undoes the whole group
+        let summary = db.get_last_undoable_operation().await.unwrap().unwrap();
+        assert_eq!(summary.operation_type, OperationType::Summary);
+        assert_eq!(summary.summary_states().len(), 2);
+        db.undo_operation_group(group).await.unwrap();
+        assert_eq!(db.get_todo(first.id).await.unwrap().unwrap().title, "First");
+        assert_eq!(
+            db.get_todo(second.id).await.unwrap().unwrap().title,
+            "Second"
+        );
+        db.redo_operation_group(group).await.unwrap();
+        assert_eq!(
+            db.get_todo(first.id).await.unwrap().unwrap().title,
+            "First, renamed again"
+        );
+        assert!(db.get_todo(second.id).await.unwrap().is_none());
+
+        // The cap drops the oldest, and a group only whole
+        let recent = Uuid::new_v4();
+        for i in 0..3 {
+            let mut op = Operation::new(
+                OperationType::Update,
+                EntityType::Todo,
+                first.id,
+                None,
+                None,
+            );
+            op.created_at = old + chrono::Duration::days(1) + chrono::Duration::minutes(i);
+            if i > 0 {
+                op = op.in_group(recent);
+            }
+            db.record_operation(&op).await.unwrap();
+        }
+        assert_eq!(db.trim_operations(10).await.unwrap(), 0);
+        assert_eq!(db.trim_operations(1).await.unwrap(), 4);
+        assert_eq!(db.operation_stats().await.unwrap().operations, 0);
+    }
+
+    #[tokio::test]
+    async fn test_events_crud() {
+        let db = setup_db().await;
+        let start = Utc::now() + chrono::Duration::hours(1);
+        let event = Event::new(
+            "Standup".to_string(),
+            start,
+            start + chrono::Duration::minutes(15),
+        );
+
+        db.create_event(&event).await.unwrap();
+        let fetched = db.get_event(event.id).await.unwrap().unwrap();
+        assert_eq!(fetched.title, "Standup");
+        assert_eq!(db.list_events().await.unwrap().len(), 1);
+
+        db.delete_event(event.id).await.unwrap();
+        assert!(db.get_event(event.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_event_occurrences_expands_recurring() {
+        let db = setup_db().await;
+        let start = Utc::now() - chrono::Duration::weeks(4);
+        let mut weekly = Event::new(
+            "Review".to_string(),
+            start,
+            start + chrono::Duration::hours(1),
+        );
+        weekly.recurrence_rule = Some(crate::models::RECUR_WEEKLY.to_string());
+        db.create_event(&weekly).await.unwrap();
+
+        // Window excludes the occurrence that started just now
+        let from = Utc::now() + chrono::Duration::hours(2);
+        let occurrences = db
+            .list_event_occurrences(from, from + chrono::Duration::weeks(2))
+            .await
+            .unwrap();
+        assert_eq!(occurrences.len(), 2);
+        assert!(occurrences.iter().all(|(e, _)| e.id == weekly.id));
+    }
+
+    #[tokio::test]
+    async fn test_list_events_starting_within() {
+        let db = setup_db().await;
+        let soon = Utc::now() + chrono::Duration::minutes(10);
+        let later = Utc::now() + chrono::Duration::hours(3);
+        db.create_event(&Event::new(
+            "Soon".to_string(),
+            soon,
+            soon + chrono::Duration::minutes(30),
+        ))
+        .await
+        .unwrap();
+        db.create_event(&Event::new(
+            "Later".to_string(),
+            later,
+            later + chrono::Duration::minutes(30),
+        ))
+        .await
+        .unwrap();
+
+        let due = db
+            .list_events_starting_within(chrono::Duration::minutes(15))
+            .await
+            .unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0.title, "Soon");
+    }
+
+    #[tokio::test]
+    async fn test_ai_cache_expiry_and_purge() {
+        let db = setup_db().await;
+
+        db.put_ai_cache("fresh", "{}", Duration::hours(1))
+            .await
+            .unwrap();
+        db.put_ai_cache("stale", "{}", Duration::hours(-1))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            db.get_ai_cache("fresh").await.unwrap().as_deref(),
+            Some("{}")
+        );
+        assert_eq!(db.get_ai_cache("stale").await.unwrap(), None);
+        assert_eq!(db.get_ai_cache("missing").await.unwrap(), None);
+
+        assert_eq!(db.count_expired_ai_cache().await.unwrap(), 1);
+        assert_eq!(db.purge_expired_ai_cache().await.unwrap(), 1);
+        assert_eq!(db.count_expired_ai_cache().await.unwrap(), 0);
+        assert!(db.get_ai_cache("fresh").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_device_id_is_stable() {
+        let db = setup_db().await;
+        let id = db.device_id().await.unwrap();
+        assert_eq!(db.device_id().await.unwrap(), id);
+
+        let other = setup_db().await;
+        assert_ne!(other.device_id().await.unwrap(), id);
+    }
+
+    #[tokio::test]
+    async fn test_daily_summary_date_is_kept() {
+        let db = setup_db().await;
+        assert_eq!(db.daily_summary_sent_on().await.unwrap(), None);
+
+        let monday = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        db.record_daily_summary(monday).await.unwrap();
+        db.record_daily_summary(monday.succ_opt().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(db.daily_summary_sent_on().await.unwrap(), monday.succ_opt());
+    }
+
+    #[tokio::test]
+    async fn test_sync_runs_track_last_success_and_pending() {
+        let db = setup_db().await;
+        assert!(db.last_sync_run().await.unwrap().is_none());
+
+        db.create_todo(&Todo::new("Pending".to_string(), None))
+            .await
+            .unwrap();
+        assert_eq!(db.count_pending_uploads().await.unwrap(), 1);
+
+        let ok = SyncRun {
+            finished_at: Utc::now() - Duration::minutes(5),
+            uploaded: 1,
+            downloaded: 2,
+            conflict_ids: vec![Uuid::new_v4()],
+            error: None,
+        };
+        db.record_sync_run(&ok).await.unwrap();
+        let failed = SyncRun {
+            finished_at: Utc::now(),
+            uploaded: 0,
+            downloaded: 0,
+            conflict_ids: vec![],
+            error: Some("Network error".to_string()),
+        };
+        db.record_sync_run(&failed).await.unwrap();
+
+        let last = db.last_sync_run().await.unwrap().unwrap();
+        assert_eq!(last.error.as_deref(), Some("Network error"));
+        let last_ok = db.last_successful_sync().await.unwrap().unwrap();
+        assert_eq!(last_ok.timestamp(), ok.finished_at.timestamp());
+
+        for _ in 0..SYNC_RUNS_KEPT {
+            db.record_sync_run(&ok).await.unwrap();
+        }
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM sync_runs")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(count, SYNC_RUNS_KEPT);
+    }
+
+    #[tokio::test]
+    async fn test_ai_parse_queue() {
+        let db = setup_db().await;
+        let todo = Todo::new("call bob".to_string(), None);
+        db.queue_ai_parse(todo.id, "call bob tmrw 3pm urgent")
+            .await
+            .unwrap();
+
+        db.record_ai_parse_attempt(todo.id).await.unwrap();
+        let pending = db.list_pending_ai_parses().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].todo_id, todo.id);
+        assert_eq!(pending[0].input, "call bob tmrw 3pm urgent");
+        assert_eq!(pending[0].attempts, 1);
+
+        db.remove_pending_ai_parse(todo.id).await.unwrap();
+        assert!(db.list_pending_ai_parses().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_undo_and_redo_operation_group() {
+        let db = setup_db().await;
+        let group = Uuid::new_v4();
+
+        let first = Todo::new("First".to_string(), None);
+        let second = Todo::new("Second".to_string(), None);
+        db.create_todo(&first).await.unwrap();
+        db.create_todo(&second).await.unwrap();
+
+        // Complete one and delete the other as one group
+        let mut done = first.clone();
+        done.mark_complete();
+        db.update_todo(&done).await.unwrap();
+        db.record_operation(
+            &Operation::new(
+                OperationType::Complete,
+                EntityType::Todo,
+                first.id,
+                serde_json::to_value(&first).ok(),
+                serde_json::to_value(&done).ok(),
+            )
+            .in_group(group),
+        )
+        .await
+        .unwrap();
+        db.delete_todo(second.id).await.unwrap();
+        db.record_operation(
+            &Operation::new(
+                OperationType::Delete,
+                EntityType::Todo,
+                second.id,
+                serde_json::to_value(&second).ok(),
+                None,
+            )
+            .in_group(group),
+        )
+        .await
+        .unwrap();
+
+        let last = db.get_last_undoable_operation().await.unwrap().unwrap();
+        assert_eq!(last.group_id, Some(group));
+
+        let undone = db.undo_operation_group(group).await.unwrap();
+        assert_eq!(undone.len(), 2);
+        assert!(!db.get_todo(first.id).await.unwrap().unwrap().is_completed);
+        assert!(db.get_todo(second.id).await.unwrap().is_some());
+        assert!(db.get_last_undoable_operation().await.unwrap().is_none());
+
+        let redone = db.redo_operation_group(group).await.unwrap();
+        assert_eq!(redone.len(), 2);
+        assert!(db.get_todo(first.id).await.unwrap().unwrap().is_completed);
+        assert!(db.get_todo(second.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_todos_is_atomic() {
+        let db = setup_db().await;
+
+        let todos = vec![
+            Todo::new("First".to_string(), None),
+            Todo::new("Second".to_string(), None),
+        ];
+        let op = Operation::batch_create(&todos);
+        db.create_todos(&todos, &op).await.unwrap();
+
+        assert_eq!(db.list_todos(false).await.unwrap().len(), 2);
+        let last = db.get_last_undoable_operation().await.unwrap().unwrap();
+        assert_eq!(last.operation_type, OperationType::BatchCreate);
+        let titles: Vec<String> = last.batch_todos().into_iter().map(|t| t.title).collect();
+        assert_eq!(titles, vec!["First", "Second"]);
+
+        // A duplicate ID fails the whole batch, including its operation
+        let third = Todo::new("Third".to_string(), None);
+        let batch = vec![third.clone(), todos[0].clone()];
+        assert!(
+            db.create_todos(&batch, &Operation::batch_create(&batch))
+                .await
+                .is_err()
+        );
+        assert!(db.get_todo(third.id).await.unwrap().is_none());
+        assert_eq!(db.list_operations(10).await.unwrap().len(), 1);
+    }
 }
@@ -1,5 +1,10 @@
+pub mod backend;
+pub mod backup;
+pub mod file;
 pub mod local;
 pub mod remote;
 
-pub use local::LocalDb;
+pub use backend::{SyncBackend, Upserted};
+pub use file::FileBackend;
+pub use local::{ConditionalUpdate, LocalDb};
 pub use remote::RemoteDb;
@@ -3,36 +3,43 @@
 //! This module provides `RemoteDb`, a wrapper around a PostgreSQL connection pool
 //! that handles CRUD operations for syncing todos with the cloud.
 
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use sqlx::{PgPool, Row, postgres::PgPoolOptions};
 use uuid::Uuid;
 
-use crate::models::{Category, Priority, SyncStatus, Todo};
+use super::backend::{SyncBackend, Upserted};
+use crate::config::NetworkConfig;
+use crate::models::{Category, Device, Priority, Project, SyncStatus, Todo, User};
+use crate::net::{self, Tunnel};
 use crate::{Result as TodoeeResult, TodoeeError};
 
-/// Remote PostgreSQL database for cloud sync.
-pub struct RemoteDb {
-    pool: PgPool,
-}
+/// Remote schema version this build migrates to and understands
+pub const SCHEMA_VERSION: i32 = 11;
 
-impl RemoteDb {
-    /// Create a new RemoteDb connection to the specified database URL.
-    pub async fn new(database_url: &str) -> TodoeeResult<Self> {
-        let pool = PgPoolOptions::new()
-            .max_connections(5)
-            .connect(database_url)
-            .await
-            .map_err(TodoeeError::Database)?;
+/// Advisory lock key held while migrating, so clients don't migrate concurrently
+const MIGRATION_LOCK_ID: i64 = 0x746f_646f_6565;
 
-        let db = Self { pool };
-        db.initialize().await?;
-        Ok(db)
-    }
+/// A step in the remote schema history
+struct Migration {
+    version: i32,
+    /// Oldest client schema version that can still sync once this is applied
+    min_client_version: i32,
+    description: &'static str,
+    statements: &'static [&'static str],
+}
 
-    /// Initialize the database schema (create tables if they don't exist).
-    async fn initialize(&self) -> TodoeeResult<()> {
-        // Create categories table with soft delete
-        sqlx::query(
+/// Remote schema history, oldest first. Statements are idempotent so databases
+/// created before versioning are adopted by replaying them. Never edit a
+/// released migration; add a new one and bump [`SCHEMA_VERSION`].
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        min_client_version: 1,
+        description: "Categories and todos with soft delete",
+        statements: &[
             r#"
             CREATE TABLE IF NOT EXISTS categories (
                 id UUID PRIMARY KEY,
@@ -43,16 +50,10 @@ impl RemoteDb {
                 sync_status TEXT NOT NULL DEFAULT 'synced',
                 created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
                 updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-                deleted_at TIMESTAMPTZ
+                deleted_at TIMESTAMPTZ,
+                icon TEXT
             )
             "#,
-        )
-        .execute(&self.pool)
-        .await
-        .map_err(TodoeeError::Database)?;
-
-        // Create todos table with soft delete
-        sqlx::query(
             r#"
             CREATE TABLE IF NOT EXISTS todos (
                 id UUID PRIMARY KEY,
@@ -71,62 +72,300 @@ impl RemoteDb {
                 deleted_at TIMESTAMPTZ
             )
             "#,
-        )
-        .execute(&self.pool)
-        .await
-        .map_err(TodoeeError::Database)?;
+            "CREATE INDEX IF NOT EXISTS idx_todos_updated_at ON todos(updated_at)",
+            "CREATE INDEX IF NOT EXISTS idx_todos_deleted_at ON todos(deleted_at)",
+            "CREATE INDEX IF NOT EXISTS idx_todos_user_id ON todos(user_id)",
+            "CREATE INDEX IF NOT EXISTS idx_categories_updated_at ON categories(updated_at)",
+            "CREATE INDEX IF NOT EXISTS idx_categories_deleted_at ON categories(deleted_at)",
+        ],
+    },
+    Migration {
+        version: 2,
+        min_client_version: 1,
+        description: "Per-todo icons",
+        statements: &["ALTER TABLE todos ADD COLUMN IF NOT EXISTS icon TEXT"],
+    },
+    Migration {
+        version: 3,
+        min_client_version: 3,
+        // Older clients would ignore revocation, so they may no longer sync
+        description: "Devices allowed to sync",
+        statements: &[r#"
+            CREATE TABLE IF NOT EXISTS devices (
+                id UUID PRIMARY KEY,
+                name TEXT NOT NULL,
+                registered_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                revoked_at TIMESTAMPTZ
+            )
+            "#],
+    },
+    Migration {
+        version: 4,
+        min_client_version: 1,
+        // Keyset pagination in sync orders by (updated_at, id)
+        description: "Sync pagination index",
+        statements: &[
+            "CREATE INDEX IF NOT EXISTS idx_todos_updated_at_id ON todos(updated_at, id)",
+        ],
+    },
+    Migration {
+        version: 5,
+        min_client_version: 1,
+        // Older clients leave project_id alone when they update a todo
+        description: "Projects above categories",
+        statements: &[
+            r#"
+            CREATE TABLE IF NOT EXISTS projects (
+                id UUID PRIMARY KEY,
+                user_id UUID,
+                name TEXT NOT NULL,
+                color TEXT,
+                archived_at TIMESTAMPTZ,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                deleted_at TIMESTAMPTZ
+            )
+            "#,
+            "ALTER TABLE todos ADD COLUMN IF NOT EXISTS project_id UUID REFERENCES projects(id)",
+            "CREATE INDEX IF NOT EXISTS idx_projects_updated_at ON projects(updated_at)",
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_projects_user_name ON projects(user_id, name) WHERE deleted_at IS NULL",
+        ],
+    },
+    Migration {
+        version: 6,
+        min_client_version: 1,
+        // Older clients leave the column alone when they update a todo
+        description: "Per-todo notification advance",
+        statements: &["ALTER TABLE todos ADD COLUMN IF NOT EXISTS remind_before_minutes INTEGER"],
+    },
+    Migration {
+        version: 7,
+        min_client_version: 1,
+        // Devices without a user keep syncing as before
+        description: "Users that devices sync as",
+        statements: &[
+            r#"
+            CREATE TABLE IF NOT EXISTS users (
+                id UUID PRIMARY KEY,
+                email TEXT NOT NULL UNIQUE,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+            "#,
+            "ALTER TABLE devices ADD COLUMN IF NOT EXISTS user_id UUID REFERENCES users(id)",
+        ],
+    },
+    Migration {
+        version: 8,
+        min_client_version: 1,
+        // Older clients leave the column alone when they update a todo
+        description: "Links from todos to GitHub issues",
+        statements: &["ALTER TABLE todos ADD COLUMN IF NOT EXISTS external_ref TEXT"],
+    },
+    Migration {
+        version: 9,
+        min_client_version: 9,
+        // Older clients would ignore ownership and sync every user's todos
+        description: "Passwords and sessions, so users can share a database",
+        statements: &[
+            "ALTER TABLE users ADD COLUMN IF NOT EXISTS password_hash TEXT",
+            r#"
+            CREATE TABLE IF NOT EXISTS sessions (
+                token_hash TEXT PRIMARY KEY,
+                user_id UUID NOT NULL REFERENCES users(id),
+                device_id UUID NOT NULL,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                ended_at TIMESTAMPTZ
+            )
+            "#,
+            "CREATE INDEX IF NOT EXISTS idx_categories_user_id ON categories(user_id)",
+            "CREATE INDEX IF NOT EXISTS idx_projects_user_id ON projects(user_id)",
+        ],
+    },
+    Migration {
+        version: 10,
+        min_client_version: 1,
+        // Every query of a logged-in client filters on user_id first
+        description: "Per-user indexes",
+        statements: &[
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_todos_user_id_id ON todos(user_id, id)",
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_categories_user_id_id ON categories(user_id, id)",
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_projects_user_id_id ON projects(user_id, id)",
+            "CREATE INDEX IF NOT EXISTS idx_todos_user_updated_at_id ON todos(user_id, updated_at, id)",
+            "CREATE INDEX IF NOT EXISTS idx_devices_user_id ON devices(user_id)",
+        ],
+    },
+    Migration {
+        version: 11,
+        min_client_version: 1,
+        // Older clients leave the column alone when they update a todo
+        description: "Per-todo opt-out of repeat notifications",
+        statements: &[
+            "ALTER TABLE todos ADD COLUMN IF NOT EXISTS no_nag BOOLEAN NOT NULL DEFAULT FALSE",
+        ],
+    },
+];
 
-        // Create indexes for efficient queries
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_todos_updated_at ON todos(updated_at)")
-            .execute(&self.pool)
-            .await
-            .map_err(TodoeeError::Database)?;
+/// Refuse a remote whose schema requires a newer client than this build.
+fn check_schema_compatible(remote_version: i32, min_client_version: i32) -> TodoeeResult<()> {
+    if min_client_version > SCHEMA_VERSION {
+        return Err(TodoeeError::IncompatibleSchema {
+            remote: remote_version,
+            supported: SCHEMA_VERSION,
+        });
+    }
+    Ok(())
+}
 
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_todos_deleted_at ON todos(deleted_at)")
-            .execute(&self.pool)
+/// Rows per query when [`RemoteDb::get_todos_since`] pages through todos
+const DEFAULT_PAGE_SIZE: u32 = 500;
+
+/// Position of a todo in sync order: its `updated_at` and `id`
+pub type TodoCursor = (DateTime<Utc>, Uuid);
+
+/// Remote PostgreSQL database for cloud sync.
+pub struct RemoteDb {
+    pool: PgPool,
+    /// Proxy tunnel the pool's connections go through, if any
+    _tunnel: Option<Tunnel>,
+}
+
+impl RemoteDb {
+    /// Create a new RemoteDb connection to the specified database URL.
+    ///
+    /// Uses the proxy from the environment, if any; see [`RemoteDb::connect`].
+    pub async fn new(database_url: &str) -> TodoeeResult<Self> {
+        Self::connect(database_url, &NetworkConfig::default()).await
+    }
+
+    /// Connect using the proxy and CA certificate settings in `network`.
+    pub async fn connect(database_url: &str, network: &NetworkConfig) -> TodoeeResult<Self> {
+        let (options, tunnel) = net::pg_connect_options(database_url, network).await?;
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect_with(options)
             .await
-            .map_err(TodoeeError::Database)?;
+            .map_err(net::database_error)?;
 
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_todos_user_id ON todos(user_id)")
+        let db = Self {
+            pool,
+            _tunnel: tunnel,
+        };
+        db.initialize().await?;
+        Ok(db)
+    }
+
+    /// Run a trivial query and return the round-trip time.
+    pub async fn ping(&self) -> TodoeeResult<Duration> {
+        let start = Instant::now();
+        sqlx::query("SELECT 1")
             .execute(&self.pool)
             .await
             .map_err(TodoeeError::Database)?;
+        Ok(start.elapsed())
+    }
 
+    /// Bring the remote schema up to [`SCHEMA_VERSION`].
+    ///
+    /// Migrations run in one transaction under an advisory lock, so two
+    /// clients connecting at once don't race. Fails with
+    /// [`TodoeeError::IncompatibleSchema`] when a newer client has migrated the
+    /// remote past what this build understands.
+    async fn initialize(&self) -> TodoeeResult<()> {
         sqlx::query(
-            "CREATE INDEX IF NOT EXISTS idx_categories_updated_at ON categories(updated_at)",
+            r#"
+            CREATE TABLE IF NOT EXISTS schema_version (
+                version INTEGER PRIMARY KEY,
+                min_client_version INTEGER NOT NULL,
+                description TEXT NOT NULL,
+                applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+            "#,
         )
         .execute(&self.pool)
         .await
         .map_err(TodoeeError::Database)?;
 
-        sqlx::query(
-            "CREATE INDEX IF NOT EXISTS idx_categories_deleted_at ON categories(deleted_at)",
+        let mut tx = self.pool.begin().await.map_err(TodoeeError::Database)?;
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(MIGRATION_LOCK_ID)
+            .execute(&mut *tx)
+            .await
+            .map_err(TodoeeError::Database)?;
+
+        let current: Option<(i32, i32)> = sqlx::query_as(
+            "SELECT version, min_client_version FROM schema_version ORDER BY version DESC LIMIT 1",
         )
-        .execute(&self.pool)
+        .fetch_optional(&mut *tx)
         .await
         .map_err(TodoeeError::Database)?;
+        let (version, min_client_version) = current.unwrap_or((0, 0));
+        check_schema_compatible(version, min_client_version)?;
+
+        for migration in MIGRATIONS.iter().filter(|m| m.version > version) {
+            for statement in migration.statements {
+                sqlx::query(statement)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(TodoeeError::Database)?;
+            }
+            sqlx::query(
+                "INSERT INTO schema_version (version, min_client_version, description) VALUES ($1, $2, $3)",
+            )
+            .bind(migration.version)
+            .bind(migration.min_client_version)
+            .bind(migration.description)
+            .execute(&mut *tx)
+            .await
+            .map_err(TodoeeError::Database)?;
+            tracing::info!(version = migration.version, "Applied remote migration");
+        }
+
+        tx.commit().await.map_err(TodoeeError::Database)?;
+
+        // Live category names are unique per user. Existing duplicates make this
+        // fail; sync still works, it just can't rely on the index.
+        if let Err(e) = sqlx::query(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_categories_user_name ON categories(user_id, name) WHERE deleted_at IS NULL",
+        )
+        .execute(&self.pool)
+        .await
+        {
+            tracing::warn!(error = %e, "Duplicate category names on remote, uniqueness not enforced");
+        }
 
         Ok(())
     }
 
+    /// The schema version the remote database is at.
+    pub async fn schema_version(&self) -> TodoeeResult<i32> {
+        let (version,): (Option<i32>,) = sqlx::query_as("SELECT MAX(version) FROM schema_version")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(TodoeeError::Database)?;
+        Ok(version.unwrap_or(0))
+    }
+
     /// Upsert a todo using last-write-wins conflict resolution.
     /// Only updates if the incoming `updated_at` is greater than the existing one.
-    pub async fn upsert_todo(&self, todo: &Todo) -> TodoeeResult<()> {
-        let priority_val = match todo.priority {
-            Priority::Low => 1,
-            Priority::Medium => 2,
-            Priority::High => 3,
-        };
+    /// A newer write also clears `deleted_at`, so restoring a todo syncs.
+    /// Rows with the same id but a different `created_at` are a different todo
+    /// and are never overwritten, and neither are other users' rows.
+    /// Returns whether a row was written.
+    pub async fn upsert_todo(&self, todo: &Todo) -> TodoeeResult<bool> {
+        let priority_val = todo.priority as i32;
 
         let ai_metadata = todo.ai_metadata.as_ref().map(|v| v.to_string());
 
-        sqlx::query(
+        let result = sqlx::query(
             r#"
             INSERT INTO todos (
                 id, user_id, category_id, title, description, due_date, reminder_at,
-                priority, is_completed, completed_at, ai_metadata, created_at, updated_at
+                priority, is_completed, completed_at, ai_metadata, created_at, updated_at, icon,
+                project_id, remind_before_minutes, external_ref, no_nag
             ) VALUES (
-                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11::jsonb, $12, $13
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11::jsonb, $12, $13, $14, $15, $16, $17,
+                $18
             )
             ON CONFLICT (id) DO UPDATE SET
                 user_id = EXCLUDED.user_id,
@@ -139,8 +378,16 @@ impl RemoteDb {
                 is_completed = EXCLUDED.is_completed,
                 completed_at = EXCLUDED.completed_at,
                 ai_metadata = EXCLUDED.ai_metadata,
-                updated_at = EXCLUDED.updated_at
+                icon = EXCLUDED.icon,
+                project_id = EXCLUDED.project_id,
+                remind_before_minutes = EXCLUDED.remind_before_minutes,
+                external_ref = EXCLUDED.external_ref,
+                no_nag = EXCLUDED.no_nag,
+                updated_at = EXCLUDED.updated_at,
+                deleted_at = NULL
             WHERE todos.updated_at < EXCLUDED.updated_at
+              AND date_trunc('second', todos.created_at) = date_trunc('second', EXCLUDED.created_at)
+              AND todos.user_id IS NOT DISTINCT FROM EXCLUDED.user_id
             "#,
         )
         .bind(todo.id)
@@ -156,70 +403,142 @@ impl RemoteDb {
         .bind(ai_metadata)
         .bind(todo.created_at)
         .bind(todo.updated_at)
+        .bind(&todo.icon)
+        .bind(todo.project_id)
+        .bind(todo.remind_before_minutes)
+        .bind(&todo.external_ref)
+        .bind(todo.no_nag)
         .execute(&self.pool)
         .await
         .map_err(TodoeeError::Database)?;
 
-        Ok(())
+        Ok(result.rows_affected() > 0)
     }
 
     /// Get all todos updated since the given timestamp (for incremental sync).
     /// Excludes soft-deleted todos.
-    pub async fn get_todos_since(&self, since: DateTime<Utc>) -> TodoeeResult<Vec<Todo>> {
+    ///
+    /// With an `owner`, only that user's todos.
+    ///
+    /// Loads every matching row; sync uses [`RemoteDb::get_todos_page`] instead.
+    pub async fn get_todos_since(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> TodoeeResult<Vec<Todo>> {
+        let mut todos = Vec::new();
+        let mut after = None;
+        loop {
+            let page = self
+                .get_todos_page(owner, since, after, DEFAULT_PAGE_SIZE)
+                .await?;
+            let done = page.len() < DEFAULT_PAGE_SIZE as usize;
+            after = page.last().map(|t| (t.updated_at, t.id));
+            todos.extend(page);
+            if done {
+                return Ok(todos);
+            }
+        }
+    }
+
+    /// Get one page of todos updated since `since`, oldest first.
+    ///
+    /// Pages are keyed on `(updated_at, id)`: pass the cursor of the last todo
+    /// of the previous page as `after` to get the next one. A page shorter than
+    /// `limit` is the last. Excludes soft-deleted todos, and todos of users
+    /// other than `owner` when it is given.
+    pub async fn get_todos_page(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+        after: Option<TodoCursor>,
+        limit: u32,
+    ) -> TodoeeResult<Vec<Todo>> {
         let rows = sqlx::query(
             r#"
             SELECT
                 id, user_id, category_id, title, description, due_date, reminder_at,
-                priority, is_completed, completed_at, ai_metadata, created_at, updated_at
+                priority, is_completed, completed_at, ai_metadata, created_at, updated_at, icon,
+                project_id, remind_before_minutes, external_ref, no_nag
             FROM todos
             WHERE updated_at > $1 AND deleted_at IS NULL
-            ORDER BY updated_at ASC
+                AND ($2::timestamptz IS NULL OR (updated_at, id) > ($2, $3))
+                AND ($5::uuid IS NULL OR user_id = $5)
+            ORDER BY updated_at ASC, id ASC
+            LIMIT $4
             "#,
         )
         .bind(since)
+        .bind(after.map(|(updated_at, _)| updated_at))
+        .bind(after.map(|(_, id)| id))
+        .bind(i64::from(limit))
+        .bind(owner)
         .fetch_all(&self.pool)
         .await
         .map_err(TodoeeError::Database)?;
 
-        let mut todos = Vec::with_capacity(rows.len());
-        for row in rows {
-            let ai_metadata: Option<serde_json::Value> = row.get("ai_metadata");
+        Ok(rows.iter().map(todo_from_row).collect())
+    }
 
-            let todo = Todo {
-                id: row.get("id"),
-                user_id: row.get("user_id"),
-                category_id: row.get("category_id"),
-                title: row.get("title"),
-                description: row.get("description"),
-                due_date: row.get("due_date"),
-                reminder_at: row.get("reminder_at"),
-                priority: Priority::from_i32(row.get("priority")),
-                is_completed: row.get("is_completed"),
-                completed_at: row.get("completed_at"),
-                ai_metadata,
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
-                sync_status: SyncStatus::Synced,
-            };
-            todos.push(todo);
-        }
+    /// Get the live todos with the given IDs. With an `owner`, other users'
+    /// todos are left out.
+    pub async fn get_todos(&self, owner: Option<Uuid>, ids: &[Uuid]) -> TodoeeResult<Vec<Todo>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                id, user_id, category_id, title, description, due_date, reminder_at,
+                priority, is_completed, completed_at, ai_metadata, created_at, updated_at, icon,
+                project_id, remind_before_minutes, external_ref, no_nag
+            FROM todos
+            WHERE id = ANY($1) AND deleted_at IS NULL
+                AND ($2::uuid IS NULL OR user_id = $2)
+            "#,
+        )
+        .bind(ids)
+        .bind(owner)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(TodoeeError::Database)?;
 
-        Ok(todos)
+        Ok(rows.iter().map(todo_from_row).collect())
     }
 
-    /// Soft delete a todo by setting its deleted_at timestamp.
-    pub async fn soft_delete_todo(&self, id: Uuid) -> TodoeeResult<()> {
-        sqlx::query("UPDATE todos SET deleted_at = NOW(), updated_at = NOW() WHERE id = $1")
-            .bind(id)
-            .execute(&self.pool)
+    /// Soft delete a todo by setting its deleted_at timestamp. With an
+    /// `owner`, other users' todos are left alone.
+    pub async fn soft_delete_todo(&self, owner: Option<Uuid>, id: Uuid) -> TodoeeResult<()> {
+        sqlx::query(
+            "UPDATE todos SET deleted_at = NOW(), updated_at = NOW() WHERE id = $1 AND ($2::uuid IS NULL OR user_id = $2)",
+        )
+        .bind(id)
+        .bind(owner)
+        .execute(&self.pool)
             .await
             .map_err(TodoeeError::Database)?;
 
         Ok(())
     }
 
+    /// Get IDs of todos soft-deleted since the given timestamp (for incremental sync).
+    pub async fn get_deleted_todo_ids_since(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> TodoeeResult<Vec<Uuid>> {
+        let rows = sqlx::query(
+            "SELECT id FROM todos WHERE deleted_at IS NOT NULL AND deleted_at > $1 AND ($2::uuid IS NULL OR user_id = $2) ORDER BY deleted_at ASC",
+        )
+        .bind(since)
+        .bind(owner)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(TodoeeError::Database)?;
+
+        Ok(rows.iter().map(|row| row.get("id")).collect())
+    }
+
     /// Upsert a category using last-write-wins conflict resolution.
-    /// Only updates if the incoming `updated_at` is greater than the existing one.
+    /// Only updates if the incoming `updated_at` is greater than the existing
+    /// one and the category belongs to the same user.
     pub async fn upsert_category(
         &self,
         category: &Category,
@@ -239,6 +558,7 @@ impl RemoteDb {
                 is_ai_generated = EXCLUDED.is_ai_generated,
                 updated_at = EXCLUDED.updated_at
             WHERE categories.updated_at < EXCLUDED.updated_at
+              AND categories.user_id IS NOT DISTINCT FROM EXCLUDED.user_id
             "#,
         )
         .bind(category.id)
@@ -254,17 +574,24 @@ impl RemoteDb {
         Ok(())
     }
 
-    /// Get all categories updated since the given timestamp.
-    pub async fn get_categories_since(&self, since: DateTime<Utc>) -> TodoeeResult<Vec<Category>> {
+    /// Get all categories updated since the given timestamp, only `owner`'s
+    /// when given.
+    pub async fn get_categories_since(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> TodoeeResult<Vec<Category>> {
         let rows = sqlx::query(
             r#"
             SELECT id, user_id, name, color, is_ai_generated
             FROM categories
             WHERE updated_at > $1 AND deleted_at IS NULL
+                AND ($2::uuid IS NULL OR user_id = $2)
             ORDER BY updated_at ASC
             "#,
         )
         .bind(since)
+        .bind(owner)
         .fetch_all(&self.pool)
         .await
         .map_err(TodoeeError::Database)?;
@@ -285,22 +612,535 @@ impl RemoteDb {
         Ok(categories)
     }
 
-    /// Soft delete a category by setting its deleted_at timestamp.
-    pub async fn soft_delete_category(&self, id: Uuid) -> TodoeeResult<()> {
-        sqlx::query("UPDATE categories SET deleted_at = NOW(), updated_at = NOW() WHERE id = $1")
-            .bind(id)
+    /// Find the ID of a live category by user and name.
+    pub async fn get_category_id_by_name(
+        &self,
+        user_id: Uuid,
+        name: &str,
+    ) -> TodoeeResult<Option<Uuid>> {
+        let row = sqlx::query(
+            "SELECT id FROM categories WHERE user_id = $1 AND name = $2 AND deleted_at IS NULL",
+        )
+        .bind(user_id)
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(TodoeeError::Database)?;
+
+        Ok(row.map(|row| row.get("id")))
+    }
+
+    /// Soft delete a category by setting its deleted_at timestamp. With an
+    /// `owner`, other users' categories are left alone.
+    pub async fn soft_delete_category(&self, owner: Option<Uuid>, id: Uuid) -> TodoeeResult<()> {
+        sqlx::query(
+            "UPDATE categories SET deleted_at = NOW(), updated_at = NOW() WHERE id = $1 AND ($2::uuid IS NULL OR user_id = $2)",
+        )
+        .bind(id)
+        .bind(owner)
+        .execute(&self.pool)
+            .await
+            .map_err(TodoeeError::Database)?;
+
+        Ok(())
+    }
+
+    /// Upsert a project using last-write-wins conflict resolution on its
+    /// `updated_at`. Other users' projects are never overwritten.
+    pub async fn upsert_project(&self, project: &Project) -> TodoeeResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO projects (
+                id, user_id, name, color, archived_at, created_at, updated_at
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6, $7
+            )
+            ON CONFLICT (id) DO UPDATE SET
+                user_id = EXCLUDED.user_id,
+                name = EXCLUDED.name,
+                color = EXCLUDED.color,
+                archived_at = EXCLUDED.archived_at,
+                updated_at = EXCLUDED.updated_at
+            WHERE projects.updated_at < EXCLUDED.updated_at
+              AND projects.user_id IS NOT DISTINCT FROM EXCLUDED.user_id
+            "#,
+        )
+        .bind(project.id)
+        .bind(project.user_id)
+        .bind(&project.name)
+        .bind(&project.color)
+        .bind(project.archived_at)
+        .bind(project.created_at)
+        .bind(project.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(TodoeeError::Database)?;
+
+        Ok(())
+    }
+
+    /// Get all projects updated since the given timestamp, archived ones
+    /// included, only `owner`'s when given.
+    pub async fn get_projects_since(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> TodoeeResult<Vec<Project>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, user_id, name, color, archived_at, created_at, updated_at
+            FROM projects
+            WHERE updated_at > $1 AND deleted_at IS NULL
+                AND ($2::uuid IS NULL OR user_id = $2)
+            ORDER BY updated_at ASC
+            "#,
+        )
+        .bind(since)
+        .bind(owner)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(TodoeeError::Database)?;
+
+        Ok(rows
+            .iter()
+            .map(|row| Project {
+                id: row.get("id"),
+                user_id: row
+                    .get::<Option<Uuid>, _>("user_id")
+                    .unwrap_or_else(Uuid::nil),
+                name: row.get("name"),
+                color: row.get("color"),
+                archived_at: row.get("archived_at"),
+                created_at: row.get("created_at"),
+                updated_at: row.get("updated_at"),
+                sync_status: SyncStatus::Synced,
+            })
+            .collect())
+    }
+
+    /// Find the ID of a live project by user and name.
+    pub async fn get_project_id_by_name(
+        &self,
+        user_id: Uuid,
+        name: &str,
+    ) -> TodoeeResult<Option<Uuid>> {
+        let row = sqlx::query(
+            "SELECT id FROM projects WHERE user_id = $1 AND name = $2 AND deleted_at IS NULL",
+        )
+        .bind(user_id)
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(TodoeeError::Database)?;
+
+        Ok(row.map(|row| row.get("id")))
+    }
+
+    /// Register a device, or record that a known device was seen again.
+    /// Returns the stored device, including its revocation time.
+    pub async fn register_device(&self, id: Uuid, name: &str) -> TodoeeResult<Device> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO devices (id, name) VALUES ($1, $2)
+            ON CONFLICT (id) DO UPDATE SET
+                name = EXCLUDED.name,
+                last_seen_at = CASE WHEN devices.revoked_at IS NULL THEN NOW() ELSE devices.last_seen_at END
+            RETURNING id, name, registered_at, last_seen_at, revoked_at
+            "#,
+        )
+        .bind(id)
+        .bind(name)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(TodoeeError::Database)?;
+
+        Ok(device_from_row(&row))
+    }
+
+    /// Find the user with `email`, creating it if there is none.
+    pub async fn ensure_user(&self, email: &str) -> TodoeeResult<User> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO users (id, email) VALUES ($1, $2)
+            ON CONFLICT (email) DO UPDATE SET email = EXCLUDED.email
+            RETURNING id, email, created_at
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(email)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(TodoeeError::Database)?;
+
+        Ok(User {
+            id: row.get("id"),
+            email: row.get("email"),
+            created_at: row.get("created_at"),
+        })
+    }
+
+    /// Record that a registered device syncs as `user_id`.
+    pub async fn link_device(&self, device_id: Uuid, user_id: Uuid) -> TodoeeResult<()> {
+        sqlx::query("UPDATE devices SET user_id = $2 WHERE id = $1")
+            .bind(device_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(TodoeeError::Database)?;
+        Ok(())
+    }
+
+    /// The user with `email`, if there is one.
+    pub async fn find_user(&self, email: &str) -> TodoeeResult<Option<User>> {
+        let row = sqlx::query("SELECT id, email, created_at FROM users WHERE email = $1")
+            .bind(email)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(TodoeeError::Database)?;
+
+        Ok(row.map(|row| User {
+            id: row.get("id"),
+            email: row.get("email"),
+            created_at: row.get("created_at"),
+        }))
+    }
+
+    /// The password hash of a user who has logged in before.
+    pub async fn password_hash(&self, user_id: Uuid) -> TodoeeResult<Option<String>> {
+        let row = sqlx::query("SELECT password_hash FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(TodoeeError::Database)?;
+
+        Ok(row.and_then(|row| row.get("password_hash")))
+    }
+
+    /// Set the password hash of a user.
+    pub async fn set_password_hash(&self, user_id: Uuid, hash: &str) -> TodoeeResult<()> {
+        sqlx::query("UPDATE users SET password_hash = $2 WHERE id = $1")
+            .bind(user_id)
+            .bind(hash)
             .execute(&self.pool)
             .await
             .map_err(TodoeeError::Database)?;
+        Ok(())
+    }
+
+    /// Give rows without an owner to `user_id`, unless another user already
+    /// has a password. Categories and projects created before accounts have
+    /// the nil UUID as their owner. Returns the number of todos claimed.
+    pub async fn claim_unowned(&self, user_id: Uuid) -> TodoeeResult<u64> {
+        let mut tx = self.pool.begin().await.map_err(TodoeeError::Database)?;
+        let (others,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM users WHERE password_hash IS NOT NULL AND id <> $1",
+        )
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(TodoeeError::Database)?;
+        if others > 0 {
+            return Ok(0);
+        }
+
+        let claimed = sqlx::query("UPDATE todos SET user_id = $1 WHERE user_id IS NULL")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(TodoeeError::Database)?
+            .rows_affected();
+        for table in ["categories", "projects"] {
+            sqlx::query(&format!(
+                "UPDATE {} SET user_id = $1 WHERE user_id IS NULL OR user_id = $2",
+                table
+            ))
+            .bind(user_id)
+            .bind(Uuid::nil())
+            .execute(&mut *tx)
+            .await
+            .map_err(TodoeeError::Database)?;
+        }
+        tx.commit().await.map_err(TodoeeError::Database)?;
+        Ok(claimed)
+    }
 
+    /// Record a session of `user_id` on `device_id`, by the hash of its token.
+    pub async fn start_session(
+        &self,
+        token_hash: &str,
+        user_id: Uuid,
+        device_id: Uuid,
+    ) -> TodoeeResult<()> {
+        sqlx::query("INSERT INTO sessions (token_hash, user_id, device_id) VALUES ($1, $2, $3)")
+            .bind(token_hash)
+            .bind(user_id)
+            .bind(device_id)
+            .execute(&self.pool)
+            .await
+            .map_err(TodoeeError::Database)?;
+        Ok(())
+    }
+
+    /// The user a session that hasn't ended belongs to.
+    pub async fn session_user(&self, token_hash: &str) -> TodoeeResult<Option<Uuid>> {
+        let row =
+            sqlx::query("SELECT user_id FROM sessions WHERE token_hash = $1 AND ended_at IS NULL")
+                .bind(token_hash)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(TodoeeError::Database)?;
+
+        Ok(row.map(|row| row.get("user_id")))
+    }
+
+    /// End a session.
+    pub async fn end_session(&self, token_hash: &str) -> TodoeeResult<()> {
+        sqlx::query(
+            "UPDATE sessions SET ended_at = NOW() WHERE token_hash = $1 AND ended_at IS NULL",
+        )
+        .bind(token_hash)
+        .execute(&self.pool)
+        .await
+        .map_err(TodoeeError::Database)?;
         Ok(())
     }
+
+    /// List all registered devices, most recently seen first. With an
+    /// `owner`, only the devices linked to that user.
+    pub async fn list_devices(&self, owner: Option<Uuid>) -> TodoeeResult<Vec<Device>> {
+        let rows = sqlx::query(
+            "SELECT id, name, registered_at, last_seen_at, revoked_at FROM devices WHERE ($1::uuid IS NULL OR user_id = $1) ORDER BY last_seen_at DESC",
+        )
+        .bind(owner)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(TodoeeError::Database)?;
+
+        Ok(rows.iter().map(device_from_row).collect())
+    }
+
+    /// Revoke a device so it can no longer sync. Returns false if the device
+    /// is unknown, already revoked, or linked to a user other than `owner`.
+    pub async fn revoke_device(&self, owner: Option<Uuid>, id: Uuid) -> TodoeeResult<bool> {
+        let result = sqlx::query(
+            "UPDATE devices SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL AND ($2::uuid IS NULL OR user_id = $2)",
+        )
+        .bind(id)
+        .bind(owner)
+        .execute(&self.pool)
+        .await
+        .map_err(TodoeeError::Database)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+impl SyncBackend for RemoteDb {
+    async fn ping(&self) -> TodoeeResult<Duration> {
+        RemoteDb::ping(self).await
+    }
+
+    async fn schema_version(&self) -> TodoeeResult<i32> {
+        RemoteDb::schema_version(self).await
+    }
+
+    async fn upsert_todo(&self, todo: &Todo) -> TodoeeResult<bool> {
+        RemoteDb::upsert_todo(self, todo).await
+    }
+
+    async fn get_todos_page(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+        after: Option<TodoCursor>,
+        limit: u32,
+    ) -> TodoeeResult<Vec<Todo>> {
+        RemoteDb::get_todos_page(self, owner, since, after, limit).await
+    }
+
+    async fn get_todos(&self, owner: Option<Uuid>, ids: &[Uuid]) -> TodoeeResult<Vec<Todo>> {
+        RemoteDb::get_todos(self, owner, ids).await
+    }
+
+    async fn soft_delete_todo(&self, owner: Option<Uuid>, id: Uuid) -> TodoeeResult<()> {
+        RemoteDb::soft_delete_todo(self, owner, id).await
+    }
+
+    async fn get_deleted_todo_ids_since(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> TodoeeResult<Vec<Uuid>> {
+        RemoteDb::get_deleted_todo_ids_since(self, owner, since).await
+    }
+
+    async fn upsert_category(&self, category: &Category) -> TodoeeResult<Upserted> {
+        match RemoteDb::upsert_category(self, category, Utc::now()).await {
+            Ok(()) => Ok(Upserted::Stored),
+            Err(e) if is_unique_violation(&e) => Ok(Upserted::NameTaken(
+                self.get_category_id_by_name(category.user_id, &category.name)
+                    .await?,
+            )),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn get_categories_since(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> TodoeeResult<Vec<Category>> {
+        RemoteDb::get_categories_since(self, owner, since).await
+    }
+
+    async fn upsert_project(&self, project: &Project) -> TodoeeResult<Upserted> {
+        match RemoteDb::upsert_project(self, project).await {
+            Ok(()) => Ok(Upserted::Stored),
+            Err(e) if is_unique_violation(&e) => Ok(Upserted::NameTaken(
+                self.get_project_id_by_name(project.user_id, &project.name)
+                    .await?,
+            )),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn get_projects_since(
+        &self,
+        owner: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> TodoeeResult<Vec<Project>> {
+        RemoteDb::get_projects_since(self, owner, since).await
+    }
+
+    async fn register_device(&self, id: Uuid, name: &str) -> TodoeeResult<Device> {
+        RemoteDb::register_device(self, id, name).await
+    }
+
+    async fn list_devices(&self, owner: Option<Uuid>) -> TodoeeResult<Vec<Device>> {
+        RemoteDb::list_devices(self, owner).await
+    }
+
+    async fn revoke_device(&self, owner: Option<Uuid>, id: Uuid) -> TodoeeResult<bool> {
+        RemoteDb::revoke_device(self, owner, id).await
+    }
+
+    async fn ensure_user(&self, email: &str) -> TodoeeResult<User> {
+        RemoteDb::ensure_user(self, email).await
+    }
+
+    async fn link_device(&self, device_id: Uuid, user_id: Uuid) -> TodoeeResult<()> {
+        RemoteDb::link_device(self, device_id, user_id).await
+    }
+
+    async fn find_user(&self, email: &str) -> TodoeeResult<Option<User>> {
+        RemoteDb::find_user(self, email).await
+    }
+
+    async fn password_hash(&self, user_id: Uuid) -> TodoeeResult<Option<String>> {
+        RemoteDb::password_hash(self, user_id).await
+    }
+
+    async fn set_password_hash(&self, user_id: Uuid, hash: &str) -> TodoeeResult<()> {
+        RemoteDb::set_password_hash(self, user_id, hash).await
+    }
+
+    async fn claim_unowned(&self, user_id: Uuid) -> TodoeeResult<u64> {
+        RemoteDb::claim_unowned(self, user_id).await
+    }
+
+    async fn start_session(
+        &self,
+        token_hash: &str,
+        user_id: Uuid,
+        device_id: Uuid,
+    ) -> TodoeeResult<()> {
+        RemoteDb::start_session(self, token_hash, user_id, device_id).await
+    }
+
+    async fn session_user(&self, token_hash: &str) -> TodoeeResult<Option<Uuid>> {
+        RemoteDb::session_user(self, token_hash).await
+    }
+
+    async fn end_session(&self, token_hash: &str) -> TodoeeResult<()> {
+        RemoteDb::end_session(self, token_hash).await
+    }
+}
+
+/// Whether a write failed on a unique index, i.e. a name already in use
+fn is_unique_violation(error: &TodoeeError) -> bool {
+    matches!(error, TodoeeError::Database(sqlx::Error::Database(e)) if e.is_unique_violation())
+}
+
+fn todo_from_row(row: &sqlx::postgres::PgRow) -> Todo {
+    Todo {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        category_id: row.get("category_id"),
+        project_id: row.get("project_id"),
+        title: row.get("title"),
+        icon: row.get("icon"),
+        description: row.get("description"),
+        due_date: row.get("due_date"),
+        reminder_at: row.get("reminder_at"),
+        remind_before_minutes: row.get("remind_before_minutes"),
+        no_nag: row.get("no_nag"),
+        notified_at: None,
+        external_ref: row.get("external_ref"),
+        priority: Priority::from_i32(row.get("priority")),
+        is_completed: row.get("is_completed"),
+        completed_at: row.get("completed_at"),
+        ai_metadata: row.get("ai_metadata"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        sync_status: SyncStatus::Synced,
+    }
+}
+
+fn device_from_row(row: &sqlx::postgres::PgRow) -> Device {
+    Device {
+        id: row.get("id"),
+        name: row.get("name"),
+        registered_at: row.get("registered_at"),
+        last_seen_at: row.get("last_seen_at"),
+        revoked_at: row.get("revoked_at"),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_migrations_are_sequential() {
+        let versions: Vec<i32> = MIGRATIONS.iter().map(|m| m.version).collect();
+        assert_eq!(versions, (1..=SCHEMA_VERSION).collect::<Vec<_>>());
+        assert!(MIGRATIONS.iter().all(|m| m.min_client_version <= m.version));
+    }
+
+    #[test]
+    fn test_schema_handshake() {
+        // Fresh and current remotes are fine
+        assert!(check_schema_compatible(0, 0).is_ok());
+        assert!(check_schema_compatible(SCHEMA_VERSION, 3).is_ok());
+        // A newer but backwards-compatible remote still syncs
+        assert!(check_schema_compatible(SCHEMA_VERSION + 1, SCHEMA_VERSION).is_ok());
+        // A remote that requires a newer client is refused
+        assert!(matches!(
+            check_schema_compatible(SCHEMA_VERSION + 2, SCHEMA_VERSION + 1),
+            Err(TodoeeError::IncompatibleSchema { remote, supported })
+                if remote == SCHEMA_VERSION + 2 && supported == SCHEMA_VERSION
+        ));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires NEON_DATABASE_URL environment variable
+    async fn test_remote_db_schema_is_current() {
+        let url = std::env::var("NEON_DATABASE_URL").expect("NEON_DATABASE_URL must be set");
+        let db = RemoteDb::new(&url).await.expect("Failed to connect");
+        assert!(db.schema_version().await.unwrap() >= SCHEMA_VERSION);
+    }
+
     #[tokio::test]
     #[ignore] // Requires NEON_DATABASE_URL environment variable
     async fn test_remote_db_connection() {
@@ -328,7 +1168,7 @@ mod tests {
         // Get todos since before it was created
         let since = todo.created_at - chrono::Duration::seconds(1);
         let todos = db
-            .get_todos_since(since)
+            .get_todos_since(None, since)
             .await
             .expect("Failed to get todos");
 
@@ -338,7 +1178,155 @@ mod tests {
         );
 
         // Clean up - soft delete
-        db.soft_delete_todo(todo.id)
+        db.soft_delete_todo(None, todo.id)
+            .await
+            .expect("Failed to soft delete todo");
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires NEON_DATABASE_URL environment variable
+    async fn test_remote_db_pages_todos_with_same_timestamp() {
+        let url = std::env::var("NEON_DATABASE_URL").expect("NEON_DATABASE_URL must be set");
+        let db = RemoteDb::new(&url).await.expect("Failed to connect");
+
+        // Three todos sharing updated_at must still page without gaps or repeats
+        let updated_at = Utc::now() + chrono::Duration::days(3650);
+        let mut ids = Vec::new();
+        for title in ["Page a", "Page b", "Page c"] {
+            let mut todo = Todo::new(title.to_string(), None);
+            todo.updated_at = updated_at;
+            db.upsert_todo(&todo).await.expect("Failed to upsert todo");
+            ids.push(todo.id);
+        }
+        ids.sort();
+
+        let since = updated_at - chrono::Duration::seconds(1);
+        let first = db.get_todos_page(None, since, None, 2).await.unwrap();
+        assert_eq!(first.len(), 2);
+        let cursor = first.last().map(|t| (t.updated_at, t.id));
+        let second = db.get_todos_page(None, since, cursor, 2).await.unwrap();
+        assert_eq!(second.len(), 1);
+
+        let paged: Vec<Uuid> = first.iter().chain(&second).map(|t| t.id).collect();
+        assert_eq!(paged, ids);
+
+        for id in ids {
+            db.soft_delete_todo(None, id)
+                .await
+                .expect("Failed to soft delete");
+        }
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires NEON_DATABASE_URL environment variable
+    async fn test_remote_db_deleted_ids_since() {
+        let url = std::env::var("NEON_DATABASE_URL").expect("NEON_DATABASE_URL must be set");
+        let db = RemoteDb::new(&url).await.expect("Failed to connect");
+
+        let todo = Todo::new("Test remote deletion".to_string(), None);
+        db.upsert_todo(&todo).await.expect("Failed to upsert todo");
+        db.soft_delete_todo(None, todo.id)
+            .await
+            .expect("Failed to soft delete todo");
+
+        let since = todo.created_at - chrono::Duration::seconds(1);
+        let deleted = db
+            .get_deleted_todo_ids_since(None, since)
+            .await
+            .expect("Failed to get deleted todos");
+        assert!(deleted.contains(&todo.id), "Tombstone not returned");
+
+        let todos = db
+            .get_todos_since(None, since)
+            .await
+            .expect("Failed to get todos");
+        assert!(!todos.iter().any(|t| t.id == todo.id));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires NEON_DATABASE_URL environment variable
+    async fn test_remote_db_upsert_keeps_colliding_todo() {
+        let url = std::env::var("NEON_DATABASE_URL").expect("NEON_DATABASE_URL must be set");
+        let db = RemoteDb::new(&url).await.expect("Failed to connect");
+
+        let todo = Todo::new("Original".to_string(), None);
+        db.upsert_todo(&todo).await.expect("Failed to upsert todo");
+
+        let mut collision = Todo::new("Same id, different todo".to_string(), None);
+        collision.id = todo.id;
+        collision.created_at = todo.created_at - chrono::Duration::days(1);
+        collision.updated_at = todo.updated_at + chrono::Duration::seconds(5);
+        assert!(
+            !db.upsert_todo(&collision)
+                .await
+                .expect("Failed to upsert todo")
+        );
+
+        let since = todo.created_at - chrono::Duration::days(2);
+        let todos = db
+            .get_todos_since(None, since)
+            .await
+            .expect("Failed to get todos");
+        let stored = todos.iter().find(|t| t.id == todo.id).unwrap();
+        assert_eq!(stored.title, "Original");
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires NEON_DATABASE_URL environment variable
+    async fn test_remote_db_revoke_device() {
+        let url = std::env::var("NEON_DATABASE_URL").expect("NEON_DATABASE_URL must be set");
+        let db = RemoteDb::new(&url).await.expect("Failed to connect");
+
+        let id = Uuid::new_v4();
+        let device = db
+            .register_device(id, "test laptop")
+            .await
+            .expect("Failed to register device");
+        assert!(!device.is_revoked());
+
+        assert!(db.revoke_device(None, id).await.expect("Failed to revoke"));
+        assert!(!db.revoke_device(None, id).await.expect("Failed to revoke"));
+
+        let device = db
+            .register_device(id, "test laptop")
+            .await
+            .expect("Failed to register device");
+        assert!(device.is_revoked());
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires NEON_DATABASE_URL environment variable
+    async fn test_remote_db_scopes_rows_to_owner() {
+        let url = std::env::var("NEON_DATABASE_URL").expect("NEON_DATABASE_URL must be set");
+        let db = RemoteDb::new(&url).await.expect("Failed to connect");
+
+        let (ada, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let adas = Todo::new("Ada's".to_string(), Some(ada));
+        db.upsert_todo(&adas).await.expect("Failed to upsert todo");
+
+        // Bob can't overwrite or delete Ada's todo, even knowing its ID
+        let mut takeover = adas.clone();
+        takeover.user_id = Some(bob);
+        takeover.title = "Bob's now".to_string();
+        takeover.updated_at += chrono::Duration::seconds(5);
+        assert!(
+            !db.upsert_todo(&takeover)
+                .await
+                .expect("Failed to upsert todo")
+        );
+        db.soft_delete_todo(Some(bob), adas.id)
+            .await
+            .expect("Failed to soft delete todo");
+
+        let since = adas.created_at - chrono::Duration::seconds(1);
+        let todos = db.get_todos_since(Some(ada), since).await.unwrap();
+        let stored = todos.iter().find(|t| t.id == adas.id).unwrap();
+        assert_eq!(stored.title, "Ada's");
+        assert!(todos.iter().all(|t| t.user_id == Some(ada)));
+        let bobs = db.get_todos_since(Some(bob), since).await.unwrap();
+        assert!(!bobs.iter().any(|t| t.id == adas.id));
+
+        db.soft_delete_todo(Some(ada), adas.id)
             .await
             .expect("Failed to soft delete todo");
     }
@@ -26,6 +26,19 @@ pub enum TodoeeError {
     )]
     Network(String),
 
+    #[error(
+        "TLS error: {0}\n\nThe secure connection could not be established. If your network inspects HTTPS traffic, trust your organisation's CA certificate:\n  todoee config set network.ca_cert /path/to/ca.pem"
+    )]
+    Tls(String),
+
+    #[error(
+        "Cloud database schema is version {remote}, but this todoee supports up to version {supported}.\n\nUpgrade todoee on this device to keep syncing. Your changes are saved locally until then."
+    )]
+    IncompatibleSchema { remote: i32, supported: i32 },
+
+    #[error("Update failed: {0}")]
+    Update(String),
+
     #[error("Sync conflict: {0}")]
     SyncConflict(String),
 
@@ -34,6 +47,13 @@ pub enum TodoeeError {
 
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+
+    /// A todo broke a rule in [`crate::validation`]
+    #[error("Invalid {field}: {message}")]
+    Validation {
+        field: &'static str,
+        message: String,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, TodoeeError>;
@@ -0,0 +1,551 @@
+//! Filter expressions for list, search and contexts, such as
+//! `priority:high and due<=+3d and not tag:waiting`.
+//!
+//! Terms are `category:` (or `cat:` or `tag:`), `project:`, `priority:` (or
+//! `pri:`), `due:` and bare words, which match the title. `priority` and
+//! `due` also compare with `<`, `<=`, `>`, `>=` and `=`. Due values are
+//! `overdue`, `none`, `any`, or a day: `today`, `tomorrow`, `yesterday`,
+//! `+3d`, `-1w` or `2026-03-01`; `due:` a day means on that day.
+//!
+//! `not` negates a term, parentheses group, and `and` binds tighter than
+//! `or`; terms side by side are joined with `and`. Quote values with spaces:
+//! `category:"deep work"`.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use uuid::Uuid;
+
+use crate::config::split_command;
+use crate::models::{Priority, Todo};
+
+/// A parsed filter expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    Or(Vec<Filter>),
+    And(Vec<Filter>),
+    Not(Box<Filter>),
+    /// Category name, case-insensitive
+    Category(String),
+    /// Project name, case-insensitive
+    Project(String),
+    Priority(Comparison, Priority),
+    Due(DueFilter),
+    /// Lowercase text the title must contain
+    Text(String),
+}
+
+/// How a term's value is compared: `priority>=medium`, `due<today`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Eq,
+    Ge,
+    Gt,
+}
+
+/// Values of a `due` term
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DueFilter {
+    /// Open and past its due time
+    Overdue,
+    None,
+    Any,
+    /// Due before, on or after a day
+    Day(Comparison, Day),
+}
+
+/// A day in a `due` term
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Day {
+    /// Days from today: 0 is today, -1 yesterday
+    Relative(i64),
+    Date(NaiveDate),
+}
+
+/// The part of a filter SQLite can check: a condition on the `todos` table,
+/// with a `?` for each parameter. It may let through todos the filter
+/// doesn't match, never the other way round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlFilter {
+    pub condition: String,
+    pub params: Vec<String>,
+}
+
+/// Category and project names, which todos only reference by ID
+#[derive(Debug, Clone, Default)]
+pub struct FilterNames {
+    pub categories: HashMap<Uuid, String>,
+    pub projects: HashMap<Uuid, String>,
+}
+
+impl FromStr for Filter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut tokens = Vec::new();
+        for word in split_command(s)? {
+            let trimmed = word.trim_start_matches('(');
+            tokens.extend(std::iter::repeat_n(
+                "(".to_string(),
+                word.len() - trimmed.len(),
+            ));
+            let inner = trimmed.trim_end_matches(')');
+            if !inner.is_empty() {
+                tokens.push(inner.to_string());
+            }
+            tokens.extend(std::iter::repeat_n(
+                ")".to_string(),
+                trimmed.len() - inner.len(),
+            ));
+        }
+
+        let mut parser = Parser { tokens, pos: 0 };
+        let filter = parser.or()?;
+        if let Some(token) = parser.peek() {
+            anyhow::bail!("Unexpected '{}' in filter", token);
+        }
+        Ok(filter)
+    }
+}
+
+impl Filter {
+    /// Whether `todo` passes the filter. `now` decides `due:` terms, in its
+    /// time zone.
+    pub fn matches<Tz: TimeZone>(
+        &self,
+        todo: &Todo,
+        names: &FilterNames,
+        now: &DateTime<Tz>,
+    ) -> bool {
+        let named = |id: Option<Uuid>, map: &HashMap<Uuid, String>, name: &str| {
+            id.and_then(|id| map.get(&id))
+                .is_some_and(|n| n.eq_ignore_ascii_case(name))
+        };
+        match self {
+            Self::Or(filters) => filters.iter().any(|f| f.matches(todo, names, now)),
+            Self::And(filters) => filters.iter().all(|f| f.matches(todo, names, now)),
+            Self::Not(filter) => !filter.matches(todo, names, now),
+            Self::Category(name) => named(todo.category_id, &names.categories, name),
+            Self::Project(name) => named(todo.project_id, &names.projects, name),
+            Self::Priority(cmp, priority) => cmp.holds(todo.priority.cmp(priority)),
+            Self::Due(due) => match (due, todo.due_date) {
+                (DueFilter::None, due_date) => due_date.is_none(),
+                (DueFilter::Any, due_date) => due_date.is_some(),
+                (DueFilter::Overdue, Some(due_date)) => !todo.is_completed && due_date < *now,
+                (DueFilter::Day(cmp, day), Some(due_date)) => {
+                    let (start, end) = day.bounds(now);
+                    match cmp {
+                        Comparison::Lt => due_date < start,
+                        Comparison::Le => due_date < end,
+                        Comparison::Eq => start <= due_date && due_date < end,
+                        Comparison::Ge => due_date >= start,
+                        Comparison::Gt => due_date >= end,
+                    }
+                }
+                (_, None) => false,
+            },
+            Self::Text(text) => todo.title.to_lowercase().contains(text),
+        }
+    }
+
+    /// A condition for SQLite that narrows todos down before [`Self::matches`]
+    /// checks them, or `None` when nothing in the filter can be pushed down.
+    /// Title text is left to `matches`, as SQLite lowercases only ASCII.
+    pub fn to_sql<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<SqlFilter> {
+        self.sql(now)
+            .map(|(condition, params, _)| SqlFilter { condition, params })
+    }
+
+    /// The condition, its parameters and whether it is exact: `not` can only
+    /// negate a condition that lets nothing extra through
+    fn sql<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<(String, Vec<String>, bool)> {
+        let exact =
+            |condition: &str, params: Vec<String>| Some((condition.to_string(), params, true));
+        match self {
+            Self::Or(filters) => {
+                let parts = filters
+                    .iter()
+                    .map(|f| f.sql(now))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(join(parts, " OR ", true))
+            }
+            Self::And(filters) => {
+                let parts: Vec<_> = filters.iter().filter_map(|f| f.sql(now)).collect();
+                if parts.is_empty() {
+                    return None;
+                }
+                let complete = parts.len() == filters.len();
+                Some(join(parts, " AND ", complete))
+            }
+            Self::Not(filter) => match filter.sql(now)? {
+                (condition, params, true) => Some((format!("NOT ({})", condition), params, true)),
+                _ => None,
+            },
+            Self::Category(name) => exact(
+                "category_id IS NOT NULL AND category_id IN \
+                 (SELECT id FROM categories WHERE name = ? COLLATE NOCASE)",
+                vec![name.clone()],
+            ),
+            Self::Project(name) => exact(
+                "project_id IS NOT NULL AND project_id IN \
+                 (SELECT id FROM projects WHERE name = ? COLLATE NOCASE)",
+                vec![name.clone()],
+            ),
+            Self::Priority(cmp, priority) => Some((
+                format!("priority {} {}", cmp.operator(), *priority as i32),
+                Vec::new(),
+                true,
+            )),
+            Self::Due(DueFilter::None) => exact("due_date IS NULL", Vec::new()),
+            Self::Due(DueFilter::Any) => exact("due_date IS NOT NULL", Vec::new()),
+            Self::Due(DueFilter::Overdue) => exact(
+                "is_completed = 0 AND due_date IS NOT NULL AND due_date < ?",
+                vec![timestamp(now)],
+            ),
+            Self::Due(DueFilter::Day(cmp, day)) => {
+                let (start, end) = day.bounds(now);
+                let (start, end) = (timestamp(&start), timestamp(&end));
+                let (condition, params) = match cmp {
+                    Comparison::Lt => ("due_date < ?", vec![start]),
+                    Comparison::Le => ("due_date < ?", vec![end]),
+                    Comparison::Eq => ("due_date >= ? AND due_date < ?", vec![start, end]),
+                    Comparison::Ge => ("due_date >= ?", vec![start]),
+                    Comparison::Gt => ("due_date >= ?", vec![end]),
+                };
+                exact(&format!("due_date IS NOT NULL AND {}", condition), params)
+            }
+            Self::Text(_) => None,
+        }
+    }
+}
+
+impl Comparison {
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Self::Lt => ordering.is_lt(),
+            Self::Le => ordering.is_le(),
+            Self::Eq => ordering.is_eq(),
+            Self::Ge => ordering.is_ge(),
+            Self::Gt => ordering.is_gt(),
+        }
+    }
+
+    fn operator(self) -> &'static str {
+        match self {
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Eq => "=",
+            Self::Ge => ">=",
+            Self::Gt => ">",
+        }
+    }
+}
+
+impl Day {
+    /// Start of the day and of the next one, in `now`'s time zone
+    fn bounds<Tz: TimeZone>(self, now: &DateTime<Tz>) -> (DateTime<Tz>, DateTime<Tz>) {
+        let date = match self {
+            Self::Relative(days) => now.date_naive() + Duration::days(days),
+            Self::Date(date) => date,
+        };
+        let tz = now.timezone();
+        let midnight = |date: NaiveDate| {
+            let midnight = date.and_hms_opt(0, 0, 0).expect("midnight is a valid time");
+            tz.from_local_datetime(&midnight)
+                .earliest()
+                .unwrap_or_else(|| tz.from_utc_datetime(&midnight))
+        };
+        (midnight(date), midnight(date + Duration::days(1)))
+    }
+}
+
+/// Join conditions, each in parentheses
+fn join(
+    parts: Vec<(String, Vec<String>, bool)>,
+    separator: &str,
+    exact: bool,
+) -> (String, Vec<String>, bool) {
+    let exact = exact && parts.iter().all(|(_, _, e)| *e);
+    let mut conditions = Vec::new();
+    let mut params = Vec::new();
+    for (condition, part_params, _) in parts {
+        conditions.push(format!("({})", condition));
+        params.extend(part_params);
+    }
+    (conditions.join(separator), params, exact)
+}
+
+/// A time as the todos table stores it
+fn timestamp<Tz: TimeZone>(time: &DateTime<Tz>) -> String {
+    time.with_timezone(&Utc).to_rfc3339()
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn keyword(&self, keyword: &str) -> bool {
+        self.peek().is_some_and(|t| t.eq_ignore_ascii_case(keyword))
+    }
+
+    fn or(&mut self) -> Result<Filter> {
+        let mut filters = vec![self.and()?];
+        while self.keyword("or") {
+            self.pos += 1;
+            filters.push(self.and()?);
+        }
+        Ok(if filters.len() == 1 {
+            filters.remove(0)
+        } else {
+            Filter::Or(filters)
+        })
+    }
+
+    fn and(&mut self) -> Result<Filter> {
+        let mut filters = vec![self.unary()?];
+        while self.peek().is_some() && !self.keyword("or") && self.peek() != Some(")") {
+            if self.keyword("and") {
+                self.pos += 1;
+            }
+            filters.push(self.unary()?);
+        }
+        Ok(if filters.len() == 1 {
+            filters.remove(0)
+        } else {
+            Filter::And(filters)
+        })
+    }
+
+    fn unary(&mut self) -> Result<Filter> {
+        let token = self
+            .peek()
+            .context("Filter ends where a term was expected")?
+            .to_string();
+        self.pos += 1;
+        match token.to_lowercase().as_str() {
+            "not" => Ok(Filter::Not(Box::new(self.unary()?))),
+            "(" => {
+                let filter = self.or()?;
+                if self.peek() != Some(")") {
+                    anyhow::bail!("Missing ')' in filter");
+                }
+                self.pos += 1;
+                Ok(filter)
+            }
+            ")" | "and" | "or" => anyhow::bail!("Unexpected '{}' in filter", token),
+            _ => term(&token),
+        }
+    }
+}
+
+fn term(token: &str) -> Result<Filter> {
+    let Some(at) = token.find([':', '<', '>', '=']) else {
+        return Ok(Filter::Text(token.to_lowercase()));
+    };
+    let (key, rest) = token.split_at(at);
+    let (cmp, value) = if let Some(value) = rest.strip_prefix("<=") {
+        (Comparison::Le, value)
+    } else if let Some(value) = rest.strip_prefix(">=") {
+        (Comparison::Ge, value)
+    } else if let Some(value) = rest.strip_prefix('<') {
+        (Comparison::Lt, value)
+    } else if let Some(value) = rest.strip_prefix('>') {
+        (Comparison::Gt, value)
+    } else {
+        // ':' or '='
+        (Comparison::Eq, &rest[1..])
+    };
+    if value.is_empty() {
+        anyhow::bail!("'{}' needs a value", token);
+    }
+    let key = key.to_lowercase();
+    let ordered = matches!(key.as_str(), "priority" | "pri" | "due");
+    if cmp != Comparison::Eq && !ordered {
+        anyhow::bail!("'{}' can't be compared; use {}:{}", key, key, value);
+    }
+
+    match key.as_str() {
+        "category" | "cat" | "tag" => Ok(Filter::Category(value.to_string())),
+        "project" => Ok(Filter::Project(value.to_string())),
+        "priority" | "pri" => match value.to_lowercase().as_str() {
+            "low" | "l" | "1" => Ok(Filter::Priority(cmp, Priority::Low)),
+            "medium" | "med" | "m" | "2" => Ok(Filter::Priority(cmp, Priority::Medium)),
+            "high" | "h" | "3" => Ok(Filter::Priority(cmp, Priority::High)),
+            "urgent" | "u" | "4" => Ok(Filter::Priority(cmp, Priority::Urgent)),
+            _ => anyhow::bail!(
+                "Unknown priority '{}' (expected low, medium, high or urgent)",
+                value
+            ),
+        },
+        "due" => {
+            let value = value.to_lowercase();
+            let due = match value.as_str() {
+                "overdue" | "none" | "any" if cmp != Comparison::Eq => {
+                    anyhow::bail!("'due:{}' can't be compared", value)
+                }
+                "overdue" => DueFilter::Overdue,
+                "none" => DueFilter::None,
+                "any" => DueFilter::Any,
+                _ => DueFilter::Day(cmp, day(&value)?),
+            };
+            Ok(Filter::Due(due))
+        }
+        _ => anyhow::bail!(
+            "Unknown filter '{}' (expected category, tag, project, priority or due)",
+            key
+        ),
+    }
+}
+
+/// `today`, `tomorrow`, `yesterday`, `+3d`, `-2w` or `2026-03-01`
+fn day(value: &str) -> Result<Day> {
+    let unknown = || {
+        anyhow::anyhow!(
+            "Unknown due date '{}' (expected overdue, none, any, today, +3d, -1w or YYYY-MM-DD)",
+            value
+        )
+    };
+    match value {
+        "today" => return Ok(Day::Relative(0)),
+        "tomorrow" => return Ok(Day::Relative(1)),
+        "yesterday" => return Ok(Day::Relative(-1)),
+        _ => {}
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(Day::Date(date));
+    }
+    let (sign, offset) = match value.as_bytes().first() {
+        Some(b'+') => (1, &value[1..]),
+        Some(b'-') => (-1, &value[1..]),
+        _ => return Err(unknown()),
+    };
+    let (count, unit) = offset.split_at(offset.len().saturating_sub(1));
+    let count: i64 = count.parse().map_err(|_| unknown())?;
+    let days = match unit {
+        "d" => count,
+        "w" => count * 7,
+        _ => return Err(unknown()),
+    };
+    Ok(Day::Relative(sign * days))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_match_filter() {
+        let filter: Filter = "category:work or priority:high and not (due:none)"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            filter,
+            Filter::Or(vec![
+                Filter::Category("work".to_string()),
+                Filter::And(vec![
+                    Filter::Priority(Comparison::Eq, Priority::High),
+                    Filter::Not(Box::new(Filter::Due(DueFilter::None))),
+                ]),
+            ])
+        );
+
+        let work = Uuid::new_v4();
+        let names = FilterNames {
+            categories: HashMap::from([(work, "Work".to_string())]),
+            projects: HashMap::new(),
+        };
+        let now = Utc::now();
+        let mut todo = Todo::new("Review budget".to_string(), None);
+        assert!(!filter.matches(&todo, &names, &now));
+        todo.category_id = Some(work);
+        assert!(filter.matches(&todo, &names, &now));
+
+        todo.category_id = None;
+        todo.priority = Priority::High;
+        assert!(!filter.matches(&todo, &names, &now));
+        todo.due_date = Some(now - Duration::hours(1));
+        assert!(filter.matches(&todo, &names, &now));
+
+        let overdue_budget: Filter = "budget due:overdue".parse().unwrap();
+        assert!(overdue_budget.matches(&todo, &names, &now));
+        todo.mark_complete();
+        assert!(!overdue_budget.matches(&todo, &names, &now));
+
+        let quoted: Filter = "cat:\"deep work\"".parse().unwrap();
+        assert_eq!(quoted, Filter::Category("deep work".to_string()));
+        for bad in [
+            "",
+            "priority:critical",
+            "size:big",
+            "cat>work",
+            "due<none",
+            "due<=+3x",
+            "(cat:work",
+            "cat:work or",
+            "x )",
+        ] {
+            assert!(bad.parse::<Filter>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_filter_comparisons_and_sql() {
+        let filter: Filter = "priority>=medium and due<=+3d and not tag:waiting"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            filter,
+            Filter::And(vec![
+                Filter::Priority(Comparison::Ge, Priority::Medium),
+                Filter::Due(DueFilter::Day(Comparison::Le, Day::Relative(3))),
+                Filter::Not(Box::new(Filter::Category("waiting".to_string()))),
+            ])
+        );
+
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        let names = FilterNames::default();
+        let mut todo = Todo::new("Plan".to_string(), None);
+        todo.due_date = Some(Utc.with_ymd_and_hms(2026, 10, 19, 23, 0, 0).unwrap());
+        assert!(filter.matches(&todo, &names, &now));
+        todo.due_date = Some(Utc.with_ymd_and_hms(2026, 10, 20, 0, 0, 0).unwrap());
+        assert!(!filter.matches(&todo, &names, &now));
+        todo.priority = Priority::Low;
+        todo.due_date = None;
+        assert!(!filter.matches(&todo, &names, &now));
+
+        let on_day: Filter = "due:2026-10-20".parse().unwrap();
+        let sql = on_day.to_sql(&now).unwrap();
+        assert_eq!(
+            sql.condition,
+            "due_date IS NOT NULL AND due_date >= ? AND due_date < ?"
+        );
+        assert_eq!(
+            sql.params,
+            ["2026-10-20T00:00:00+00:00", "2026-10-21T00:00:00+00:00"]
+        );
+
+        // Title text stays out of SQL: dropped under `and`, and it stops a
+        // `not` or `or` from being pushed down at all
+        let sql = "pri:high plan"
+            .parse::<Filter>()
+            .unwrap()
+            .to_sql(&now)
+            .unwrap();
+        assert_eq!(sql.condition, "(priority = 3)");
+        for unpushed in ["not (pri:high plan)", "pri:high or plan", "plan"] {
+            let filter: Filter = unpushed.parse().unwrap();
+            assert!(filter.to_sql(&now).is_none(), "{}", unpushed);
+        }
+    }
+}
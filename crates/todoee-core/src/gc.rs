@@ -0,0 +1,302 @@
+//! Cleanup of old data, by hand with `todoee gc` or on a schedule
+//!
+//! With `gc.auto` on, cleanup runs at most once per `gc.interval`, from
+//! todoee-daemon or after a CLI command, whichever comes first. The time of
+//! the last run, by hand or automatic, is kept in `gc-state.json` in the
+//! config directory.
+//!
+//! Undo history is also kept in bounds in between, by [`compact_history`].
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{GcConfig, HistoryConfig};
+use crate::db::LocalDb;
+use crate::models::Todo;
+
+/// File in the config directory holding the last run
+const STATE_FILE: &str = "gc-state.json";
+
+/// What a cleanup removes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GcOptions {
+    /// Data older than this many days is removed
+    pub days: i64,
+    /// Collapse old consecutive updates instead of deleting old history
+    pub compact: bool,
+    /// Categories whose completed todos are kept, matched ignoring case
+    pub exclude_categories: Vec<String>,
+}
+
+impl GcOptions {
+    /// The options automatic runs use
+    pub fn from_config(config: &GcConfig) -> Self {
+        Self {
+            days: config.days as i64,
+            compact: false,
+            exclude_categories: config.excluded_categories(),
+        }
+    }
+}
+
+/// Counts from one cleanup
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GcSummary {
+    pub compacted_operations: u64,
+    pub deleted_operations: u64,
+    pub deleted_todos: u64,
+    pub purged_todos: u64,
+    pub purged_cache: u64,
+}
+
+impl GcSummary {
+    /// Whether nothing was removed
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The non-zero counts, e.g. "3 old completed todos, 120 old operations"
+impl fmt::Display for GcSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = [
+            (
+                self.deleted_todos,
+                "old completed todo",
+                "old completed todos",
+            ),
+            (
+                self.purged_todos,
+                "deleted todo purged",
+                "deleted todos purged",
+            ),
+            (self.deleted_operations, "old operation", "old operations"),
+            (
+                self.compacted_operations,
+                "compacted operation",
+                "compacted operations",
+            ),
+            (
+                self.purged_cache,
+                "expired AI cache entry",
+                "expired AI cache entries",
+            ),
+        ]
+        .into_iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, one, many)| format!("{} {}", count, if count == 1 { one } else { many }))
+        .collect();
+
+        if parts.is_empty() {
+            write!(f, "nothing to clean up")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
+/// Completed todos older than `options.days`, except those in excluded categories
+pub async fn old_completed_todos(
+    db: &LocalDb,
+    options: &GcOptions,
+    now: DateTime<Utc>,
+) -> Result<Vec<Todo>> {
+    let cutoff = now - Duration::days(options.days);
+    let excluded: HashSet<_> = db
+        .list_categories()
+        .await?
+        .into_iter()
+        .filter(|c| {
+            options
+                .exclude_categories
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(&c.name))
+        })
+        .map(|c| c.id)
+        .collect();
+
+    Ok(db
+        .list_todos(false)
+        .await?
+        .into_iter()
+        .filter(|t| t.is_completed && t.completed_at.is_some_and(|c| c < cutoff))
+        .filter(|t| !t.category_id.is_some_and(|id| excluded.contains(&id)))
+        .collect())
+}
+
+/// Remove old completed todos, old history, todos deleted before the cutoff
+/// and expired AI results
+pub async fn collect(db: &LocalDb, options: &GcOptions, now: DateTime<Utc>) -> Result<GcSummary> {
+    let mut summary = GcSummary::default();
+
+    if options.compact {
+        summary.compacted_operations = db.compact_operations(options.days).await?
+            + db.summarize_operation_groups(options.days).await?;
+    } else {
+        summary.deleted_operations = db.clear_old_operations(options.days).await?;
+    }
+
+    // Deleted, not purged: they stay restorable until a later run
+    for todo in old_completed_todos(db, options, now).await? {
+        db.delete_todo(todo.id).await?;
+        summary.deleted_todos += 1;
+    }
+
+    summary.purged_todos = db.purge_deleted_todos(options.days).await?;
+    summary.purged_cache = db.purge_expired_ai_cache().await?;
+    Ok(summary)
+}
+
+/// Compact undo history older than `history.compact_after_days`, then
+/// delete the oldest operations over `history.max_operations`
+pub async fn compact_history(db: &LocalDb, config: &HistoryConfig) -> Result<GcSummary> {
+    let days = config.compact_after_days as i64;
+    let mut summary = GcSummary {
+        compacted_operations: db.compact_operations(days).await?
+            + db.summarize_operation_groups(days).await?,
+        ..Default::default()
+    };
+    if config.max_operations > 0 {
+        summary.deleted_operations = db.trim_operations(config.max_operations as u64).await?;
+    }
+    Ok(summary)
+}
+
+/// When cleanup last ran
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GcState {
+    pub last_run: Option<DateTime<Utc>>,
+}
+
+impl GcState {
+    /// Path of the state file in `config_dir`
+    pub fn path(config_dir: &Path) -> PathBuf {
+        config_dir.join(STATE_FILE)
+    }
+
+    /// Load the last run; a missing or unreadable file counts as never run
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    /// Whether the last run is at least `interval` old
+    pub fn is_due(&self, interval: Duration, now: DateTime<Utc>) -> bool {
+        self.last_run
+            .is_none_or(|at| now - at >= interval || at > now)
+    }
+}
+
+/// Run an automatic cleanup if it is on and due, and record the run.
+/// Returns `None` when nothing ran.
+pub async fn auto_collect(
+    db: &LocalDb,
+    config: &GcConfig,
+    config_dir: &Path,
+    now: DateTime<Utc>,
+) -> Result<Option<GcSummary>> {
+    let path = GcState::path(config_dir);
+    if !config.auto || !GcState::load(&path).is_due(config.interval(), now) {
+        return Ok(None);
+    }
+
+    let summary = collect(db, &GcOptions::from_config(config), now).await?;
+    GcState {
+        last_run: Some(now),
+    }
+    .save(&path)?;
+    Ok(Some(summary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Category;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_auto_collect_respects_interval_and_exclusions() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDb::new_in_memory().await.unwrap();
+        db.run_migrations().await.unwrap();
+
+        let receipts = Category::new(Uuid::nil(), "Receipts".to_string());
+        db.create_category(&receipts).await.unwrap();
+        let now = Utc::now();
+        let mut old = Todo::new("Old errand".to_string(), None);
+        old.mark_complete();
+        old.completed_at = Some(now - Duration::days(40));
+        let mut kept = old.clone();
+        kept.id = Uuid::new_v4();
+        kept.category_id = Some(receipts.id);
+        let mut recent = Todo::new("Recent".to_string(), None);
+        recent.mark_complete();
+        for todo in [&old, &kept, &recent] {
+            db.create_todo(todo).await.unwrap();
+        }
+
+        let mut config = GcConfig::default();
+        assert_eq!(
+            auto_collect(&db, &config, temp_dir.path(), now)
+                .await
+                .unwrap(),
+            None
+        );
+
+        config.auto = true;
+        config.exclude_categories = "receipts".to_string();
+        let summary = auto_collect(&db, &config, temp_dir.path(), now)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(summary.deleted_todos, 1);
+        assert_eq!(summary.to_string(), "1 old completed todo");
+        assert!(db.get_todo(old.id).await.unwrap().is_none());
+        assert!(db.get_todo(kept.id).await.unwrap().is_some());
+        assert!(db.get_todo(recent.id).await.unwrap().is_some());
+
+        // Not again until the interval has passed
+        let tomorrow = now + Duration::days(1);
+        assert!(
+            auto_collect(&db, &config, temp_dir.path(), tomorrow)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let next_week = now + Duration::days(7);
+        assert!(
+            auto_collect(&db, &config, temp_dir.path(), next_week)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_summary_display() {
+        assert_eq!(GcSummary::default().to_string(), "nothing to clean up");
+        let summary = GcSummary {
+            deleted_operations: 120,
+            purged_todos: 2,
+            purged_cache: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            summary.to_string(),
+            "2 deleted todos purged, 120 old operations, 1 expired AI cache entry"
+        );
+    }
+}
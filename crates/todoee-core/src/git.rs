@@ -0,0 +1,127 @@
+//! Git integration for `todoee hook` and `todoee add --from-branch`: todo
+//! references in commit messages, and todo titles from branch names.
+//!
+//! Commit messages complete todos with `todoee:done abc1` or, as on
+//! GitHub, `fixes #abc1` (also fix, fixed, close(s/d) and resolve(s/d)).
+//! References need at least [`MIN_ID_LENGTH`] hex characters so issue
+//! numbers like `#12` aren't mistaken for todos.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::models::MIN_ID_LENGTH;
+
+/// Words that, followed by `#<id>`, complete a todo
+const CLOSING_WORDS: &[&str] = &[
+    "fix", "fixes", "fixed", "close", "closes", "closed", "resolve", "resolves", "resolved",
+];
+
+/// Todo ID prefixes a commit message marks as done, in order, without repeats
+pub fn done_references(message: &str) -> Vec<String> {
+    let words: Vec<&str> = message.split_whitespace().collect();
+    let mut ids = Vec::new();
+    for pair in words.windows(2) {
+        let keyword = pair[0].to_lowercase();
+        let keyword = keyword.trim_end_matches(':');
+        let id = if keyword == "todoee:done" {
+            pair[1]
+        } else if CLOSING_WORDS.contains(&keyword) {
+            match pair[1].strip_prefix('#') {
+                Some(id) => id,
+                None => continue,
+            }
+        } else {
+            continue;
+        };
+        let id = id
+            .trim_end_matches(|c: char| !c.is_ascii_alphanumeric())
+            .to_lowercase();
+        if id.len() >= MIN_ID_LENGTH
+            && id.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+            && !ids.contains(&id)
+        {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+/// A todo title from a branch name: the part after the last `/`, with
+/// dashes and underscores as spaces, e.g. "feature/add-login-page" becomes
+/// "Add login page"
+pub fn branch_title(branch: &str) -> String {
+    let name = branch.rsplit('/').next().unwrap_or(branch);
+    let words: Vec<&str> = name
+        .split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .collect();
+    let title = words.join(" ");
+    let mut chars = title.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => title,
+    }
+}
+
+/// The branch checked out in `dir`
+pub fn current_branch(dir: &Path) -> Result<String> {
+    let branch = git(dir, &["symbolic-ref", "--short", "HEAD"])
+        .context("Not on a branch (detached HEAD?)")?;
+    Ok(branch)
+}
+
+/// Full message of the last commit in `dir`
+pub fn last_commit_message(dir: &Path) -> Result<String> {
+    git(dir, &["log", "-1", "--format=%B"])
+}
+
+/// Abbreviated hash of the last commit in `dir`
+pub fn last_commit_hash(dir: &Path) -> Result<String> {
+    git(dir, &["rev-parse", "--short", "HEAD"])
+}
+
+/// Where git looks for hooks in `dir`, honouring `core.hooksPath`
+pub fn hooks_dir(dir: &Path) -> Result<PathBuf> {
+    let path = PathBuf::from(git(dir, &["rev-parse", "--git-path", "hooks"])?);
+    Ok(if path.is_absolute() {
+        path
+    } else {
+        dir.join(path)
+    })
+}
+
+/// Run git in `dir` and return its trimmed output
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git. Is it installed?")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_references_and_branch_titles() {
+        let message = "Fix login redirect\n\nFixes #ABC1, closes #12 and todoee:done 9f2e4d.\n\
+                       Resolves: #abc1 see #beef";
+        assert_eq!(done_references(message), ["abc1", "9f2e4d"]);
+        assert!(done_references("todoee:done later").is_empty());
+
+        assert_eq!(branch_title("feature/add-login-page"), "Add login page");
+        assert_eq!(branch_title("fix_flaky__tests"), "Fix flaky tests");
+        assert_eq!(branch_title("main"), "Main");
+    }
+}
@@ -0,0 +1,246 @@
+//! GitHub issues for `todoee github`: the issues assigned to you, imported
+//! as todos, and closed again when their todo is done.
+//!
+//! An imported todo keeps the issue in `external_ref` as
+//! `github:owner/name#12`, so pulling again skips it and completing it can
+//! close the issue. The token is read from the environment variable named by
+//! `github.token_env`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::config::GithubConfig;
+use crate::models::Todo;
+use crate::update::CURRENT_VERSION;
+
+/// Prefix of every `external_ref` this module writes
+pub const REF_PREFIX: &str = "github:";
+
+/// Issues asked for per request, the most GitHub allows
+const PER_PAGE: usize = 100;
+
+/// Pages read before giving up, so a runaway listing stays bounded
+const MAX_PAGES: usize = 10;
+
+/// An issue as the REST API returns it
+#[derive(Debug, Clone, Deserialize)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    pub html_url: String,
+    /// e.g. `https://api.github.com/repos/owner/name`
+    pub repository_url: String,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    pub milestone: Option<Milestone>,
+    /// Present when the "issue" is really a pull request
+    pub pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Label {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Milestone {
+    pub due_on: Option<DateTime<Utc>>,
+}
+
+impl Issue {
+    /// `owner/name` of the repository the issue is in
+    pub fn repo(&self) -> &str {
+        self.repository_url
+            .split_once("/repos/")
+            .map_or(&self.repository_url, |(_, repo)| repo)
+    }
+
+    /// The `external_ref` of a todo imported from this issue
+    pub fn external_ref(&self) -> String {
+        external_ref(self.repo(), self.number)
+    }
+
+    pub fn label_names(&self) -> Vec<String> {
+        self.labels.iter().map(|label| label.name.clone()).collect()
+    }
+
+    /// A todo for this issue: its title, a link back, and the milestone's
+    /// due date
+    pub fn to_todo(&self, user_id: Option<Uuid>, category_id: Option<Uuid>) -> Todo {
+        let mut todo = Todo::new(self.title.clone(), user_id);
+        todo.category_id = category_id;
+        todo.description = Some(self.html_url.clone());
+        todo.due_date = self.milestone.as_ref().and_then(|m| m.due_on);
+        todo.external_ref = Some(self.external_ref());
+        todo
+    }
+}
+
+/// `github:owner/name#12`
+pub fn external_ref(repo: &str, number: u64) -> String {
+    format!("{}{}#{}", REF_PREFIX, repo, number)
+}
+
+/// The repository and issue number in an `external_ref` written by
+/// [`external_ref`]
+pub fn parse_ref(reference: &str) -> Option<(&str, u64)> {
+    let (repo, number) = reference.strip_prefix(REF_PREFIX)?.rsplit_once('#')?;
+    if !is_repo(repo) {
+        return None;
+    }
+    Some((repo, number.parse().ok()?))
+}
+
+/// Whether `repo` looks like `owner/name`
+pub fn is_repo(repo: &str) -> bool {
+    let valid = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    repo.split_once('/')
+        .is_some_and(|(owner, name)| valid(owner) && valid(name))
+}
+
+/// The REST API, authenticated with the configured token
+pub struct GithubClient {
+    http: reqwest::Client,
+    api_url: String,
+    token: String,
+}
+
+impl GithubClient {
+    pub fn new(http: reqwest::Client, config: &GithubConfig) -> Result<Self> {
+        Ok(Self {
+            http,
+            api_url: config.api_url.trim_end_matches('/').to_string(),
+            token: config.token()?,
+        })
+    }
+
+    /// Open issues assigned to the token's user, in `repo` or everywhere.
+    /// Pull requests are left out.
+    pub async fn assigned_issues(&self, repo: Option<&str>) -> Result<Vec<Issue>> {
+        let path = match repo {
+            Some(repo) => {
+                let login = self.login().await?;
+                format!("/repos/{}/issues?assignee={}&state=open", repo, login)
+            }
+            None => "/issues?filter=assigned&state=open".to_string(),
+        };
+
+        let mut issues = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let url = format!(
+                "{}{}&per_page={}&page={}",
+                self.api_url, path, PER_PAGE, page
+            );
+            let batch: Vec<Issue> = self
+                .send(self.http.get(&url))
+                .await?
+                .json()
+                .await
+                .with_context(|| format!("Unexpected response from {}", url))?;
+            let done = batch.len() < PER_PAGE;
+            issues.extend(batch.into_iter().filter(|i| i.pull_request.is_none()));
+            if done {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    /// Close issue `number` in `repo`
+    pub async fn close_issue(&self, repo: &str, number: u64) -> Result<()> {
+        let url = format!("{}/repos/{}/issues/{}", self.api_url, repo, number);
+        self.send(self.http.patch(&url).json(&json!({ "state": "closed" })))
+            .await?;
+        Ok(())
+    }
+
+    /// Login of the user the token belongs to
+    async fn login(&self) -> Result<String> {
+        #[derive(Deserialize)]
+        struct User {
+            login: String,
+        }
+        let url = format!("{}/user", self.api_url);
+        let user: User = self
+            .send(self.http.get(&url))
+            .await?
+            .json()
+            .await
+            .with_context(|| format!("Unexpected response from {}", url))?;
+        Ok(user.login)
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let response = request
+            .bearer_auth(&self.token)
+            .header(
+                reqwest::header::USER_AGENT,
+                format!("todoee/{}", CURRENT_VERSION),
+            )
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .send()
+            .await
+            .context("Couldn't reach GitHub")?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let url = response.url().to_string();
+        match status.as_u16() {
+            401 => anyhow::bail!("GitHub rejected the token (401). Check that it hasn't expired"),
+            403 | 404 => anyhow::bail!(
+                "GitHub returned {} for {}. Check the repository name and that the token can access it",
+                status,
+                url
+            ),
+            _ => anyhow::bail!("GitHub returned {} for {}", status, url),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issues_become_linked_todos() {
+        let issues: Vec<Issue> = serde_json::from_str(
+            r#"[
+                {"number": 12, "title": "Fix login", "state": "open",
+                 "html_url": "https://github.com/acme/web/issues/12",
+                 "repository_url": "https://api.github.com/repos/acme/web",
+                 "labels": [{"name": "bug"}],
+                 "milestone": {"title": "v1", "due_on": "2026-11-01T07:00:00Z"}},
+                {"number": 13, "title": "Add dark mode",
+                 "html_url": "https://github.com/acme/web/pull/13",
+                 "repository_url": "https://api.github.com/repos/acme/web",
+                 "labels": [], "milestone": null, "pull_request": {}}
+            ]"#,
+        )
+        .unwrap();
+        let issue = &issues[0];
+        assert_eq!(issue.repo(), "acme/web");
+        assert_eq!(issue.label_names(), ["bug"]);
+        assert!(issues[1].pull_request.is_some());
+
+        let todo = issue.to_todo(None, None);
+        assert_eq!(todo.title, "Fix login");
+        assert_eq!(todo.external_ref.as_deref(), Some("github:acme/web#12"));
+        assert_eq!(todo.description.as_deref(), Some(issue.html_url.as_str()));
+        assert!(todo.due_date.is_some());
+
+        assert_eq!(parse_ref("github:acme/web#12"), Some(("acme/web", 12)));
+        assert_eq!(parse_ref("github:acme#12"), None);
+        assert_eq!(parse_ref("jira:WEB-12"), None);
+        assert!(is_repo("rust-lang/rust.vim"));
+        assert!(!is_repo("acme/web/issues"));
+    }
+}
@@ -0,0 +1,196 @@
+//! iCalendar (RFC 5545) export
+//!
+//! Writes todos as VTODO components of a VCALENDAR, for calendar apps that
+//! import `.ics` files or subscribe to a feed:
+//!
+//! ```text
+//! BEGIN:VTODO
+//! UID:4f1c...@todoee
+//! SUMMARY:Pay rent
+//! DUE;VALUE=DATE:20260301
+//! PRIORITY:1
+//! STATUS:NEEDS-ACTION
+//! END:VTODO
+//! ```
+//!
+//! Date-only due dates become `VALUE=DATE` dates; timed ones and all other
+//! timestamps are written in UTC. Priorities map high/medium/low to 1/5/9,
+//! the category to CATEGORIES and the reminder to a display VALARM.
+
+use chrono::{DateTime, Utc};
+
+use crate::dates;
+use crate::models::{Category, Priority, Todo};
+
+/// Longest content line in octets before it is folded
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Render todos as an iCalendar document. `now` is used as the DTSTAMP.
+pub fn write(todos: &[Todo], categories: &[Category], now: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, "PRODID:-//todoee//todoee//EN");
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, "X-WR-CALNAME:todoee");
+
+    for todo in todos {
+        push_line(&mut out, "BEGIN:VTODO");
+        push_line(&mut out, &format!("UID:{}@todoee", todo.id));
+        push_line(&mut out, &format!("DTSTAMP:{}", timestamp(now)));
+        push_line(&mut out, &format!("CREATED:{}", timestamp(todo.created_at)));
+        push_line(
+            &mut out,
+            &format!("LAST-MODIFIED:{}", timestamp(todo.updated_at)),
+        );
+        push_line(
+            &mut out,
+            &format!("SUMMARY:{}", escape(&todo.display_title())),
+        );
+        if let Some(description) = todo.description.as_deref().filter(|d| !d.trim().is_empty()) {
+            push_line(&mut out, &format!("DESCRIPTION:{}", escape(description)));
+        }
+        if let Some(category) = todo
+            .category_id
+            .and_then(|id| categories.iter().find(|c| c.id == id))
+        {
+            push_line(&mut out, &format!("CATEGORIES:{}", escape(&category.name)));
+        }
+        if let Some(due) = todo.due_date {
+            if dates::is_date_only(&due) {
+                push_line(
+                    &mut out,
+                    &format!("DUE;VALUE=DATE:{}", due.format("%Y%m%d")),
+                );
+            } else {
+                push_line(&mut out, &format!("DUE:{}", timestamp(due)));
+            }
+        }
+        push_line(&mut out, &format!("PRIORITY:{}", priority(todo.priority)));
+        if todo.is_completed {
+            push_line(&mut out, "STATUS:COMPLETED");
+            push_line(&mut out, "PERCENT-COMPLETE:100");
+            if let Some(completed) = todo.completed_at {
+                push_line(&mut out, &format!("COMPLETED:{}", timestamp(completed)));
+            }
+        } else {
+            push_line(&mut out, "STATUS:NEEDS-ACTION");
+        }
+        if let Some(reminder) = todo.reminder_at {
+            push_line(&mut out, "BEGIN:VALARM");
+            push_line(&mut out, "ACTION:DISPLAY");
+            push_line(
+                &mut out,
+                &format!("DESCRIPTION:{}", escape(&todo.display_title())),
+            );
+            push_line(
+                &mut out,
+                &format!("TRIGGER;VALUE=DATE-TIME:{}", timestamp(reminder)),
+            );
+            push_line(&mut out, "END:VALARM");
+        }
+        push_line(&mut out, "END:VTODO");
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+/// PRIORITY values: 1 is highest, 9 lowest
+fn priority(priority: Priority) -> u8 {
+    match priority {
+        Priority::Urgent | Priority::High => 1,
+        Priority::Medium => 5,
+        Priority::Low => 9,
+    }
+}
+
+/// A UTC date-time: 20260301T140000Z
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape a TEXT value: backslash, semicolon, comma and newlines
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Append a content line, folded at 75 octets and ended with CRLF
+fn push_line(out: &mut String, line: &str) {
+    let mut octets = 0;
+    for c in line.chars() {
+        // Continuation lines start with a space, which counts towards the limit
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            octets = 1;
+        }
+        out.push(c);
+        octets += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 14, 10, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_write_vtodo() {
+        let category = Category::new(Uuid::nil(), "Home".to_string());
+        let mut todo = Todo::new("Pay rent, water".to_string(), None);
+        todo.category_id = Some(category.id);
+        todo.priority = Priority::High;
+        todo.description = Some("Joint account\nby transfer".to_string());
+        todo.due_date = Some(Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap());
+        todo.reminder_at = Some(Utc.with_ymd_and_hms(2026, 2, 28, 9, 0, 0).unwrap());
+
+        let mut done = Todo::new("Call bank".to_string(), None);
+        done.due_date = Some(Utc.with_ymd_and_hms(2026, 1, 10, 14, 30, 0).unwrap());
+        done.mark_complete();
+
+        let ics = write(&[todo.clone(), done], &[category], now());
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VTODO").count(), 2);
+        assert!(ics.contains(&format!("UID:{}@todoee\r\n", todo.id)));
+        assert!(ics.contains("DTSTAMP:20260114T100000Z\r\n"));
+        assert!(ics.contains("SUMMARY:Pay rent\\, water\r\n"));
+        assert!(ics.contains("DESCRIPTION:Joint account\\nby transfer\r\n"));
+        assert!(ics.contains("CATEGORIES:Home\r\n"));
+        assert!(ics.contains("DUE;VALUE=DATE:20260301\r\n"));
+        assert!(ics.contains("PRIORITY:1\r\n"));
+        assert!(ics.contains("TRIGGER;VALUE=DATE-TIME:20260228T090000Z\r\n"));
+        assert!(ics.contains("DUE:20260110T143000Z\r\n"));
+        assert!(ics.contains("STATUS:COMPLETED\r\nPERCENT-COMPLETE:100\r\nCOMPLETED:"));
+        assert_eq!(ics.matches("STATUS:NEEDS-ACTION").count(), 1);
+    }
+
+    #[test]
+    fn test_long_lines_are_folded() {
+        let todo = Todo::new("é".repeat(60), None);
+        let ics = write(&[todo], &[], now());
+
+        for line in ics.split("\r\n") {
+            assert!(line.len() <= MAX_LINE_OCTETS, "line too long: {}", line);
+        }
+        let unfolded = ics.replace("\r\n ", "");
+        assert!(unfolded.contains(&format!("SUMMARY:{}\r\n", "é".repeat(60))));
+    }
+}
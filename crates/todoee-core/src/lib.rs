@@ -1,14 +1,53 @@
+pub mod agenda;
 pub mod ai;
 pub mod auth;
 pub mod config;
+pub mod conflict;
+pub mod daemon_status;
+pub mod dates;
 pub mod db;
 pub mod error;
+pub mod filter;
+pub mod gc;
+pub mod git;
+pub mod github;
+pub mod ics;
+pub mod markdown;
 pub mod models;
+pub mod net;
+pub mod notes;
+pub mod org;
+pub mod recurrence;
 pub mod sync;
+pub mod taskwarrior;
+pub mod timeblock;
+pub mod timetrack;
+pub mod update;
+pub mod urgency;
+pub mod validation;
+pub mod webhook;
 
+pub use agenda::DailySummary;
 pub use ai::{AiClient, ParsedTask};
-pub use config::{AiConfig, Config, DatabaseConfig, DisplayConfig, NotificationConfig};
-pub use db::{LocalDb, RemoteDb};
+pub use config::{
+    AiConfig, Config, ContextConfig, DatabaseConfig, DisplayConfig, DueUrgency, GcConfig,
+    GithubConfig, HistoryConfig, NetworkConfig, NotificationConfig, RankingConfig, TimeblockConfig,
+    UpdateConfig,
+};
+pub use conflict::Conflict;
+pub use daemon_status::DaemonStatus;
+pub use dates::{DateInput, DateOrder, ParsedDate};
+pub use db::{LocalDb, RemoteDb, SyncBackend};
 pub use error::{Result, TodoeeError};
+pub use filter::{Comparison, Day, DueFilter, Filter, FilterNames, SqlFilter};
+pub use markdown::MarkdownTodo;
 pub use models::*;
-pub use sync::{SyncResult, SyncService};
+pub use notes::{NoteLink, NoteTask};
+pub use org::OrgTodo;
+pub use recurrence::RecurrenceRule;
+pub use sync::{SyncHealth, SyncResult, SyncService};
+pub use taskwarrior::TaskwarriorTodo;
+pub use timeblock::TimeBlock;
+pub use timetrack::TimeEntry;
+pub use urgency::Urgency;
+pub use webhook::{Webhook, WebhookEvent};
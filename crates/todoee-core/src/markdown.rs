@@ -0,0 +1,388 @@
+//! Markdown checklist interop
+//!
+//! Writes todos as a checklist with one section per category, for notes apps
+//! such as Obsidian and Logseq, and reads it back:
+//!
+//! ```markdown
+//! ## Home
+//!
+//! - [ ] Pay rent (due: 2026-03-01) (remind: 2026-02-28 09:00) #Home !p3 <!-- todoee:4f1c... 2026-02-01T10:00:00Z -->
+//!   Transfer from the joint account
+//! - [x] Call bank #Home
+//! ```
+//!
+//! `!p1`/`!p2`/`!p3`/`!p4` are low/medium/high/urgent priority. The heading an item sits
+//! under is its category, unless the item's `#tag` names another one. The
+//! HTML comment keeps the todo's ID and creation time so importing an
+//! exported file again does not duplicate todos; notes apps don't render it.
+//! Uncategorized todos come first, before any heading. Indented lines below
+//! an item are its description. Dates are wall-clock times in the caller's
+//! timezone.
+
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
+use uuid::Uuid;
+
+use crate::dates::{self, ParsedDate};
+use crate::models::{Category, Priority, Todo};
+use crate::notes::parse_checkbox_line;
+use crate::org::tag_name;
+
+/// A todo read from a markdown checklist, with its category name
+#[derive(Debug, Clone)]
+pub struct MarkdownTodo {
+    pub todo: Todo,
+    pub category: Option<String>,
+}
+
+/// Render todos as a markdown checklist, sectioned by category
+pub fn write<Tz: TimeZone>(todos: &[Todo], categories: &[Category], tz: &Tz) -> String {
+    let mut sections: Vec<(Option<&Category>, Vec<&Todo>)> = vec![(None, Vec::new())];
+    for todo in todos {
+        let category = todo
+            .category_id
+            .and_then(|id| categories.iter().find(|c| c.id == id));
+        match sections
+            .iter_mut()
+            .find(|(c, _)| c.map(|c| c.id) == category.map(|c| c.id))
+        {
+            Some((_, items)) => items.push(todo),
+            None => sections.push((category, vec![todo])),
+        }
+    }
+
+    let mut out = String::new();
+    for (category, items) in sections.iter().filter(|(_, items)| !items.is_empty()) {
+        if let Some(category) = category {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("## {}\n\n", category.name));
+        }
+        for todo in items {
+            write_item(&mut out, todo, *category, tz);
+        }
+    }
+    out
+}
+
+fn write_item<Tz: TimeZone>(out: &mut String, todo: &Todo, category: Option<&Category>, tz: &Tz) {
+    let mark = if todo.is_completed { 'x' } else { ' ' };
+    out.push_str(&format!("- [{}] {}", mark, todo.display_title()));
+    if let Some(due) = todo.due_date {
+        let stamp = if dates::is_date_only(&due) {
+            due.date_naive().format("%Y-%m-%d").to_string()
+        } else {
+            timestamp(due, tz)
+        };
+        out.push_str(&format!(" (due: {})", stamp));
+    }
+    if let Some(reminder) = todo.reminder_at {
+        out.push_str(&format!(" (remind: {})", timestamp(reminder, tz)));
+    }
+    if let Some(category) = category {
+        out.push_str(&format!(" #{}", tag_name(&category.name)));
+    }
+    if todo.priority != Priority::Medium {
+        out.push_str(&format!(" !p{}", todo.priority as i32));
+    }
+    out.push_str(&format!(
+        " <!-- todoee:{} {} -->\n",
+        todo.id,
+        todo.created_at.format("%Y-%m-%dT%H:%M:%SZ")
+    ));
+
+    if let Some(description) = todo.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        for line in description.trim_matches('\n').lines() {
+            if line.trim().is_empty() {
+                out.push('\n');
+            } else {
+                out.push_str(&format!("  {}\n", line));
+            }
+        }
+    }
+}
+
+/// Parse the checklist items of a markdown document
+pub fn parse<Tz: TimeZone>(content: &str, tz: &Tz) -> Vec<MarkdownTodo> {
+    let mut todos = Vec::new();
+    let mut section: Option<String> = None;
+    let mut current: Option<(MarkdownTodo, Vec<&str>)> = None;
+    let mut in_code_block = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+        } else if !in_code_block {
+            if let Some(heading) = heading(line) {
+                if let Some((entry, body)) = current.take() {
+                    todos.push(finish(entry, &body));
+                }
+                section = Some(heading.to_string());
+                continue;
+            }
+            if let Some((checked, text)) = parse_checkbox_line(line) {
+                if let Some((entry, body)) = current.take() {
+                    todos.push(finish(entry, &body));
+                }
+                current = parse_item(text, checked, section.as_deref(), tz)
+                    .map(|entry| (entry, Vec::new()));
+                continue;
+            }
+        }
+
+        // Indented lines and blank lines between them belong to the item
+        if let Some((entry, body)) = current.as_mut() {
+            if line.trim().is_empty() || line.starts_with([' ', '\t']) || in_code_block {
+                body.push(line);
+            } else {
+                todos.push(finish(entry.clone(), body));
+                current = None;
+            }
+        }
+    }
+
+    if let Some((entry, body)) = current {
+        todos.push(finish(entry, &body));
+    }
+    todos
+}
+
+/// "## Home" -> "Home"
+fn heading(line: &str) -> Option<&str> {
+    let rest = line.trim_start_matches('#');
+    if rest.len() == line.len() || line.len() - rest.len() > 6 {
+        return None;
+    }
+    let text = rest.strip_prefix(' ')?.trim().trim_end_matches('#').trim();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Parse an item's text after the checkbox, peeling metadata off its end
+fn parse_item<Tz: TimeZone>(
+    text: &str,
+    checked: bool,
+    section: Option<&str>,
+    tz: &Tz,
+) -> Option<MarkdownTodo> {
+    let mut rest = text.trim();
+    let mut id = None;
+    let mut created = None;
+    let mut tag = None;
+    let mut priority = Priority::Medium;
+    let mut due = None;
+    let mut reminder = None;
+
+    if let Some(start) = rest.rfind("<!-- todoee:")
+        && let Some(comment) = rest[start..].strip_suffix("-->")
+    {
+        let mut words = comment["<!-- todoee:".len()..].split_whitespace();
+        id = words.next().and_then(|w| Uuid::parse_str(w).ok());
+        created = words
+            .next()
+            .and_then(|w| DateTime::parse_from_rfc3339(w).ok())
+            .map(|c| c.with_timezone(&Utc));
+        rest = rest[..start].trim_end();
+    }
+
+    loop {
+        if let Some(open) = rest.rfind('(')
+            && rest.ends_with(')')
+            && let Some((key, value)) = rest[open + 1..rest.len() - 1].split_once(':')
+            && matches!(key.trim(), "due" | "remind")
+        {
+            let Some(stamp) = parse_timestamp(value) else {
+                break;
+            };
+            if key.trim() == "due" {
+                due = Some(stamp.due_date(tz));
+            } else {
+                reminder = Some(stamp.reminder_at(tz));
+            }
+            rest = rest[..open].trim_end();
+            continue;
+        }
+
+        let (before, word) = rest.rsplit_once(' ').unwrap_or(("", rest));
+        if let Some(level) = word.strip_prefix("!p")
+            && let Ok(level) = level.parse::<i32>()
+            && (1..=4).contains(&level)
+        {
+            priority = Priority::from_i32(level);
+        } else if let Some(name) = word.strip_prefix('#')
+            && !name.is_empty()
+            && !name.chars().all(|c| c.is_ascii_digit())
+            && !name.contains('#')
+        {
+            tag = Some(name.to_string());
+        } else {
+            break;
+        }
+        rest = before.trim_end();
+    }
+
+    if rest.is_empty() {
+        return None;
+    }
+
+    let mut todo = Todo::new(rest.to_string(), None);
+    todo.detect_icon();
+    todo.priority = priority;
+    todo.due_date = due;
+    todo.reminder_at = reminder;
+    if let Some(id) = id {
+        todo.id = id;
+    }
+    if let Some(created) = created {
+        todo.created_at = created;
+        todo.updated_at = created;
+    }
+    if checked {
+        todo.mark_complete();
+    }
+
+    // A tag that isn't the section's own names the category
+    let category = match (tag, section) {
+        (Some(tag), Some(section)) if tag_name(section) == tag => Some(section.to_string()),
+        (Some(tag), _) => Some(tag),
+        (None, section) => section.map(str::to_string),
+    };
+    Some(MarkdownTodo { todo, category })
+}
+
+fn finish(mut entry: MarkdownTodo, body: &[&str]) -> MarkdownTodo {
+    let indent = body
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let text = body
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let text = text.trim_matches('\n');
+    if !text.is_empty() {
+        entry.todo.description = Some(text.to_string());
+    }
+    entry
+}
+
+fn timestamp<Tz: TimeZone>(at: DateTime<Utc>, tz: &Tz) -> String {
+    at.with_timezone(tz)
+        .naive_local()
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+/// Parse "2026-03-01" or "2026-03-01 14:30"
+fn parse_timestamp(value: &str) -> Option<ParsedDate> {
+    let mut words = value.split_whitespace();
+    let date = NaiveDate::parse_from_str(words.next()?, "%Y-%m-%d").ok()?;
+    let time = match words.next() {
+        Some(word) => Some(NaiveTime::parse_from_str(word, "%H:%M").ok()?),
+        None => None,
+    };
+    words.next().is_none().then_some(ParsedDate { date, time })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, NaiveDateTime};
+
+    fn tz() -> FixedOffset {
+        FixedOffset::east_opt(2 * 3600).unwrap()
+    }
+
+    fn at(tz: &FixedOffset, s: &str) -> DateTime<Utc> {
+        let naive = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        tz.from_local_datetime(&naive).unwrap().with_timezone(&Utc)
+    }
+
+    const DOC: &str = "# Tasks
+
+- [ ] Loose end
+## Side project
+- [ ] Ship v2 (due: 2026-03-01 14:30) !p3
+  Needs the release notes
+
+  - and a changelog
+- [x] Fix issue #12
+* [ ] Plan trip #travel !p1
+Not part of the item
+
+```
+- [ ] Not a task
+```
+";
+
+    #[test]
+    fn test_parse_markdown_checklist() {
+        let tz = tz();
+        let todos = parse(DOC, &tz);
+        let titles: Vec<&str> = todos.iter().map(|t| t.todo.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec!["Loose end", "Ship v2", "Fix issue #12", "Plan trip"]
+        );
+
+        assert_eq!(todos[0].category.as_deref(), Some("Tasks"));
+
+        let ship = &todos[1];
+        assert_eq!(ship.category.as_deref(), Some("Side project"));
+        assert_eq!(ship.todo.priority, Priority::High);
+        assert_eq!(ship.todo.due_date, Some(at(&tz, "2026-03-01 14:30")));
+        assert_eq!(
+            ship.todo.description.as_deref(),
+            Some("Needs the release notes\n\n- and a changelog")
+        );
+
+        assert!(todos[2].todo.is_completed);
+        assert_eq!(todos[3].category.as_deref(), Some("travel"));
+        assert_eq!(todos[3].todo.priority, Priority::Low);
+        assert_eq!(todos[3].todo.description, None);
+    }
+
+    #[test]
+    fn test_markdown_round_trip() {
+        let tz = tz();
+        let home = Category::new(Uuid::new_v4(), "Side project".to_string());
+
+        let mut rent = Todo::new("Pay rent".to_string(), None);
+        rent.category_id = Some(home.id);
+        rent.priority = Priority::High;
+        rent.due_date = Some(
+            NaiveDate::from_ymd_opt(2026, 3, 1)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+                .and_utc(),
+        );
+        rent.reminder_at = Some(at(&tz, "2026-02-28 09:00"));
+        rent.description = Some("Transfer\n\nfrom joint account".to_string());
+        let mut call = Todo::new("Call bank".to_string(), None);
+        call.priority = Priority::Low;
+        call.mark_complete();
+
+        let text = write(&[rent.clone(), call.clone()], &[home], &tz);
+        assert!(text.starts_with("- [x] Call bank !p1 <!-- todoee:"));
+        assert!(text.contains("\n## Side project\n\n- [ ] Pay rent (due: 2026-03-01) (remind: 2026-02-28 09:00) #Side_project !p3 <!-- todoee:"));
+
+        let parsed = parse(&text, &tz);
+        assert_eq!(parsed.len(), 2);
+        let back = &parsed[1];
+        assert_eq!(back.category.as_deref(), Some("Side project"));
+        assert!(back.todo.is_same_todo(&rent));
+        assert_eq!(back.todo.title, rent.title);
+        assert_eq!(back.todo.priority, rent.priority);
+        assert_eq!(back.todo.due_date, rent.due_date);
+        assert_eq!(back.todo.reminder_at, rent.reminder_at);
+        assert_eq!(back.todo.description, rent.description);
+
+        assert_eq!(parsed[0].category, None);
+        assert!(parsed[0].todo.is_same_todo(&call));
+        assert!(parsed[0].todo.is_completed);
+    }
+}
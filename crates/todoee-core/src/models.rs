@@ -1,6 +1,13 @@
+use std::collections::BTreeMap;
 use std::fmt;
+use std::str::FromStr;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use unicode_width::UnicodeWidthStr;
+
+use crate::TodoeeError;
+use crate::ai::ParsedTask;
+use crate::recurrence::RecurrenceRule;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -11,15 +18,26 @@ pub enum Priority {
     #[default]
     Medium = 2,
     High = 3,
+    Urgent = 4,
 }
 
 impl Priority {
+    /// Every level, lowest first
+    pub const ALL: [Priority; 4] = [
+        Priority::Low,
+        Priority::Medium,
+        Priority::High,
+        Priority::Urgent,
+    ];
+
     /// Convert an i32 value to a Priority.
-    /// Returns Medium for any value that doesn't match Low (1) or High (3).
+    /// Returns Medium for any value that doesn't match Low (1), High (3) or
+    /// Urgent (4).
     pub fn from_i32(value: i32) -> Self {
         match value {
             1 => Priority::Low,
             3 => Priority::High,
+            4 => Priority::Urgent,
             _ => Priority::Medium,
         }
     }
@@ -34,12 +52,11 @@ pub enum SyncStatus {
     Conflict,
 }
 
+/// A user on the remote database; devices sync as one.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     pub id: Uuid,
     pub email: String,
-    #[serde(skip_serializing)]
-    pub password_hash: String,
     pub created_at: DateTime<Utc>,
 }
 
@@ -66,15 +83,141 @@ impl Category {
     }
 }
 
+/// A grouping level above categories, e.g. "Website relaunch".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Project {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub color: Option<String>,
+    /// Archived projects are hidden from lists but keep their todos
+    pub archived_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub sync_status: SyncStatus,
+}
+
+impl Project {
+    pub fn new(user_id: Uuid, name: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            name,
+            color: None,
+            archived_at: None,
+            created_at: now,
+            updated_at: now,
+            sync_status: SyncStatus::Pending,
+        }
+    }
+
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+
+    pub fn archive(&mut self) {
+        self.archived_at = Some(Utc::now());
+        self.updated_at = Utc::now();
+        self.sync_status = SyncStatus::Pending;
+    }
+}
+
+/// Completed and total todo counts for one project.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectProgress {
+    pub project: Project,
+    pub completed: u64,
+    pub total: u64,
+}
+
+impl ProjectProgress {
+    /// Fraction of the project's todos that are done, 0.0 when it has none.
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.completed as f64 / self.total as f64
+        }
+    }
+}
+
+/// Todo counts for insights, aggregated in the database.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TodoStats {
+    /// Todos created since the start of the range
+    pub created: u64,
+    /// Todos completed since the start of the range
+    pub completed: u64,
+    pub pending: u64,
+    pub overdue: u64,
+    /// Open todos of high or urgent priority
+    pub pending_high: u64,
+    pub pending_medium: u64,
+    pub pending_low: u64,
+}
+
+/// Todos due in a range, such as one local day, and how many are done.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct DueProgress {
+    pub due: u64,
+    pub completed: u64,
+}
+
+impl DueProgress {
+    /// Something was due and all of it is done
+    pub fn is_cleared(&self) -> bool {
+        self.due > 0 && self.completed == self.due
+    }
+}
+
+/// Todos created and completed in one UTC hour, from the operation log.
+///
+/// Hours rather than days, so callers can bucket them by local day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ActivityBucket {
+    /// Start of the hour
+    pub hour: DateTime<Utc>,
+    pub created: u64,
+    pub completed: u64,
+}
+
+/// Todos completed in a range, for one category.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CategoryCount {
+    /// Category name, `None` for uncategorized todos
+    pub category: Option<String>,
+    pub completed: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Todo {
     pub id: Uuid,
     pub user_id: Option<Uuid>,
     pub category_id: Option<Uuid>,
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
     pub title: String,
+    /// Emoji or symbol shown in front of the title
+    #[serde(default)]
+    pub icon: Option<String>,
     pub description: Option<String>,
     pub due_date: Option<DateTime<Utc>>,
     pub reminder_at: Option<DateTime<Utc>>,
+    /// Minutes before a timed due date to notify, instead of the defaults
+    /// for the todo's priority
+    #[serde(default)]
+    pub remind_before_minutes: Option<i64>,
+    /// Never notify again once overdue, whatever `notifications.nag_minutes` says
+    #[serde(default)]
+    pub no_nag: bool,
+    /// When this device last notified about the todo; kept locally, never
+    /// synced or recorded in history
+    #[serde(skip)]
+    pub notified_at: Option<DateTime<Utc>>,
+    /// Where the todo came from elsewhere, e.g. `github:owner/repo#12`
+    #[serde(default)]
+    pub external_ref: Option<String>,
     pub priority: Priority,
     pub is_completed: bool,
     pub completed_at: Option<DateTime<Utc>>,
@@ -91,10 +234,16 @@ impl Todo {
             id: Uuid::new_v4(),
             user_id,
             category_id: None,
+            project_id: None,
             title,
+            icon: None,
             description: None,
             due_date: None,
             reminder_at: None,
+            remind_before_minutes: None,
+            no_nag: false,
+            notified_at: None,
+            external_ref: None,
             priority: Priority::default(),
             is_completed: false,
             completed_at: None,
@@ -105,6 +254,15 @@ impl Todo {
         }
     }
 
+    /// Whether `other` is the same todo: same id and same creation time.
+    ///
+    /// Creation times are compared to the second because remote storage keeps
+    /// only microseconds. A matching id with a different creation time means two
+    /// distinct todos collided on the id.
+    pub fn is_same_todo(&self, other: &Todo) -> bool {
+        self.id == other.id && self.created_at.timestamp() == other.created_at.timestamp()
+    }
+
     pub fn mark_complete(&mut self) {
         self.is_completed = true;
         self.completed_at = Some(Utc::now());
@@ -118,6 +276,391 @@ impl Todo {
         self.updated_at = Utc::now();
         self.sync_status = SyncStatus::Pending;
     }
+
+    /// Push the reminder to `now + duration`, returning the new reminder time.
+    pub fn snooze(&mut self, duration: Duration, now: DateTime<Utc>) -> DateTime<Utc> {
+        let until = now + duration;
+        self.reminder_at = Some(until);
+        self.updated_at = Utc::now();
+        self.sync_status = SyncStatus::Pending;
+        until
+    }
+
+    /// Move a leading or trailing emoji in the title into `icon`
+    /// ("Deploy 🚀" becomes icon 🚀, title "Deploy"). Returns `true` if one was found.
+    pub fn detect_icon(&mut self) -> bool {
+        match split_icon(&self.title) {
+            Some((title, icon)) => {
+                self.title = title;
+                self.icon = Some(icon);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Title with the icon in front, for notifications and plain output
+    pub fn display_title(&self) -> String {
+        match &self.icon {
+            Some(icon) => format!("{} {}", icon, self.title),
+            None => self.title.clone(),
+        }
+    }
+
+    /// The icon padded to `ICON_WIDTH` terminal columns plus a separating space.
+    ///
+    /// With `reserve` set, todos without an icon get the same amount of blank
+    /// space so titles stay aligned in lists where some todos have icons.
+    pub fn icon_prefix(&self, reserve: bool) -> String {
+        match &self.icon {
+            Some(icon) => {
+                let pad = ICON_WIDTH.saturating_sub(icon.width());
+                format!("{}{} ", icon, " ".repeat(pad))
+            }
+            None if reserve => " ".repeat(ICON_WIDTH + 1),
+            None => String::new(),
+        }
+    }
+
+    /// Apply an AI parse of `original_input`, recording provenance in `ai_metadata`.
+    ///
+    /// Every field the model set is listed under `ai_fields` together with the value
+    /// it produced (`ai`) and the human-entered value it replaced (`human`).
+    pub fn apply_ai_parse(&mut self, parsed: &ParsedTask, original_input: &str) {
+        let before = serde_json::to_value(&*self).expect("Todo serializes to JSON");
+
+        let mut fields = vec!["title"];
+        self.title = parsed.title.clone();
+        if let Some(ref description) = parsed.description {
+            self.description = Some(description.clone());
+            fields.push("description");
+        }
+        if let Some(due) = parsed.due_date {
+            self.due_date = Some(due);
+            fields.push("due_date");
+        }
+        if let Some(reminder) = parsed.reminder_at {
+            self.reminder_at = Some(reminder);
+            fields.push("reminder_at");
+        }
+        if let Some(p) = parsed.priority {
+            // The model uses 1-4 with 1 as the highest priority
+            self.priority = match p {
+                1 => Priority::Urgent,
+                2 => Priority::High,
+                3 => Priority::Medium,
+                _ => Priority::Low,
+            };
+            fields.push("priority");
+        }
+
+        let after = serde_json::to_value(&*self).expect("Todo serializes to JSON");
+        let ai_fields: serde_json::Map<String, serde_json::Value> = fields
+            .into_iter()
+            .map(|f| {
+                let entry = serde_json::json!({ "ai": after[f], "human": before[f] });
+                (f.to_string(), entry)
+            })
+            .collect();
+
+        self.ai_metadata = Some(serde_json::json!({
+            "original_input": original_input,
+            "parsed_category": parsed.category,
+            "ai_fields": ai_fields,
+        }));
+        self.updated_at = Utc::now();
+        self.sync_status = SyncStatus::Pending;
+    }
+
+    /// Names of the fields that were set by AI, per `ai_metadata`.
+    pub fn ai_fields(&self) -> Vec<String> {
+        self.ai_metadata
+            .as_ref()
+            .and_then(|m| m.get("ai_fields"))
+            .and_then(|f| f.as_object())
+            .map(|f| f.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Restore the human-entered values of AI-set fields.
+    ///
+    /// Fields edited since the AI set them are left alone. Todos created before
+    /// provenance was recorded only get their title restored from `original_input`.
+    /// Returns the reverted and the kept (edited since) field names.
+    pub fn revert_ai_fields(&mut self) -> (Vec<String>, Vec<String>) {
+        let Some(mut metadata) = self.ai_metadata.clone() else {
+            return (Vec::new(), Vec::new());
+        };
+
+        let mut reverted = Vec::new();
+        let mut kept = Vec::new();
+
+        match metadata.get("ai_fields").and_then(|f| f.as_object()) {
+            Some(ai_fields) => {
+                let mut current = serde_json::to_value(&*self).expect("Todo serializes to JSON");
+                for (field, entry) in ai_fields {
+                    if current[field] == entry["ai"] {
+                        current[field] = entry["human"].clone();
+                        reverted.push(field.clone());
+                    } else {
+                        kept.push(field.clone());
+                    }
+                }
+                match serde_json::from_value::<Todo>(current) {
+                    Ok(todo) => *self = todo,
+                    Err(_) => return (Vec::new(), Vec::new()),
+                }
+            }
+            None => {
+                if let Some(input) = metadata.get("original_input").and_then(|i| i.as_str())
+                    && input != self.title
+                {
+                    self.title = input.to_string();
+                    reverted.push("title".to_string());
+                }
+            }
+        }
+
+        // Nothing is AI-derived any more
+        metadata["ai_fields"] = serde_json::json!({});
+        self.ai_metadata = Some(metadata);
+        if !reverted.is_empty() {
+            self.updated_at = Utc::now();
+            self.sync_status = SyncStatus::Pending;
+        }
+
+        (reverted, kept)
+    }
+
+    /// A todo is urgent when it is due (or overdue) within `URGENT_WITHIN_DAYS`.
+    pub fn is_urgent(&self, now: DateTime<Utc>) -> bool {
+        self.due_date
+            .is_some_and(|due| due <= now + Duration::days(URGENT_WITHIN_DAYS))
+    }
+
+    /// A todo is important when it has high or urgent priority.
+    pub fn is_important(&self) -> bool {
+        self.priority >= Priority::High
+    }
+
+    /// Eisenhower quadrant this todo currently falls into.
+    pub fn quadrant(&self, now: DateTime<Utc>) -> Quadrant {
+        Quadrant::from_flags(self.is_urgent(now), self.is_important())
+    }
+
+    /// Adjust priority and due date so the todo lands in `target`.
+    ///
+    /// Becoming important raises priority to High, losing importance drops it to
+    /// Medium. Becoming urgent makes it due in a day, losing urgency pushes the due
+    /// date out a week. Returns `true` if anything changed.
+    pub fn move_to_quadrant(&mut self, target: Quadrant, now: DateTime<Utc>) -> bool {
+        let mut changed = false;
+
+        if target.is_important() != self.is_important() {
+            self.priority = if target.is_important() {
+                Priority::High
+            } else {
+                Priority::Medium
+            };
+            changed = true;
+        }
+
+        if target.is_urgent() != self.is_urgent(now) {
+            self.due_date = Some(if target.is_urgent() {
+                now + Duration::days(1)
+            } else {
+                now + Duration::weeks(1)
+            });
+            changed = true;
+        }
+
+        if changed {
+            self.updated_at = Utc::now();
+            self.sync_status = SyncStatus::Pending;
+        }
+        changed
+    }
+}
+
+/// Terminal columns reserved for a todo icon (emoji are two columns wide)
+pub const ICON_WIDTH: usize = 2;
+
+/// Longest accepted icon, in chars. Allows ZWJ sequences like 👩‍💻 and flags.
+const MAX_ICON_CHARS: usize = 8;
+
+/// Whether `s` is usable as a todo icon: a single emoji or symbol, not text.
+pub fn is_icon(s: &str) -> bool {
+    let count = s.chars().count();
+    if count == 0 || count > MAX_ICON_CHARS || s.width() > ICON_WIDTH * 2 {
+        return false;
+    }
+    if count == 1 {
+        return s
+            .chars()
+            .all(|c| !c.is_ascii() && !c.is_alphanumeric() && !c.is_whitespace());
+    }
+    s.chars().all(is_emoji_part) && s.chars().any(is_emoji_base)
+}
+
+/// Split a leading or trailing emoji off a title. Returns `None` if there is
+/// none or if the title is nothing but the emoji.
+pub fn split_icon(title: &str) -> Option<(String, String)> {
+    let words: Vec<&str> = title.split_whitespace().collect();
+    if words.len() < 2 {
+        return None;
+    }
+    let is_emoji =
+        |w: &str| w.chars().all(is_emoji_part) && w.chars().any(is_emoji_base) && is_icon(w);
+
+    let (icon, rest) = if is_emoji(words[words.len() - 1]) {
+        (words[words.len() - 1], &words[..words.len() - 1])
+    } else if is_emoji(words[0]) {
+        (words[0], &words[1..])
+    } else {
+        return None;
+    };
+    Some((rest.join(" "), icon.to_string()))
+}
+
+/// Pictographic code points: symbols, dingbats and the emoji planes
+fn is_emoji_base(c: char) -> bool {
+    matches!(c as u32,
+        0x2300..=0x23FF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0x1F000..=0x1FAFF)
+}
+
+/// Anything that can appear in an emoji sequence: bases plus joiners,
+/// variation selectors, skin tones, keycaps and tag characters
+fn is_emoji_part(c: char) -> bool {
+    is_emoji_base(c) || matches!(c as u32, 0x200D | 0xFE0F | 0x20E3 | 0xE0020..=0xE007F)
+}
+
+/// Shortest short ID shown, even when fewer characters would be unique
+pub const MIN_ID_LENGTH: usize = 4;
+
+/// Characters in a full hyphenated UUID
+pub const FULL_ID_LENGTH: usize = 36;
+
+/// Longest per-todo advance notice for a due date: one week
+pub const MAX_REMIND_BEFORE_MINUTES: i64 = 7 * 24 * 60;
+
+/// Like git's abbreviated hashes: the shortest prefix length, at least
+/// [`MIN_ID_LENGTH`], that tells all `ids` apart.
+pub fn unique_prefix_len(ids: &[Uuid]) -> usize {
+    let mut ids: Vec<String> = ids.iter().map(Uuid::to_string).collect();
+    ids.sort_unstable();
+    let shared = ids
+        .windows(2)
+        .map(|pair| {
+            pair[0]
+                .chars()
+                .zip(pair[1].chars())
+                .take_while(|(a, b)| a == b)
+                .count()
+        })
+        .max()
+        .unwrap_or(0);
+
+    (shared + 1).clamp(MIN_ID_LENGTH, FULL_ID_LENGTH)
+}
+
+/// The first `len` characters of `id`
+pub fn short_id(id: &Uuid, len: usize) -> String {
+    let mut id = id.to_string();
+    id.truncate(len.clamp(1, FULL_ID_LENGTH));
+    id
+}
+
+/// Todos due within this many days count as urgent in the Eisenhower matrix.
+pub const URGENT_WITHIN_DAYS: i64 = 2;
+
+/// Quadrant of the Eisenhower (urgent/important) matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Quadrant {
+    /// Urgent and important
+    DoFirst,
+    /// Important, not urgent
+    Schedule,
+    /// Urgent, not important
+    Delegate,
+    /// Neither urgent nor important
+    Eliminate,
+}
+
+impl Quadrant {
+    /// All quadrants in reading order (top-left, top-right, bottom-left, bottom-right).
+    pub const ALL: [Quadrant; 4] = [
+        Quadrant::DoFirst,
+        Quadrant::Schedule,
+        Quadrant::Delegate,
+        Quadrant::Eliminate,
+    ];
+
+    pub fn from_flags(urgent: bool, important: bool) -> Self {
+        match (urgent, important) {
+            (true, true) => Quadrant::DoFirst,
+            (false, true) => Quadrant::Schedule,
+            (true, false) => Quadrant::Delegate,
+            (false, false) => Quadrant::Eliminate,
+        }
+    }
+
+    pub fn is_urgent(self) -> bool {
+        matches!(self, Quadrant::DoFirst | Quadrant::Delegate)
+    }
+
+    pub fn is_important(self) -> bool {
+        matches!(self, Quadrant::DoFirst | Quadrant::Schedule)
+    }
+
+    /// Position in `ALL`, 0-3.
+    pub fn index(self) -> usize {
+        match self {
+            Quadrant::DoFirst => 0,
+            Quadrant::Schedule => 1,
+            Quadrant::Delegate => 2,
+            Quadrant::Eliminate => 3,
+        }
+    }
+
+    /// Human-readable title, e.g. "Do first".
+    pub fn title(self) -> &'static str {
+        match self {
+            Quadrant::DoFirst => "Do first",
+            Quadrant::Schedule => "Schedule",
+            Quadrant::Delegate => "Delegate",
+            Quadrant::Eliminate => "Eliminate",
+        }
+    }
+}
+
+impl fmt::Display for Quadrant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Quadrant::DoFirst => "do",
+            Quadrant::Schedule => "schedule",
+            Quadrant::Delegate => "delegate",
+            Quadrant::Eliminate => "eliminate",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for Quadrant {
+    type Err = TodoeeError;
+
+    /// Parse a quadrant name ("do", "schedule", "delegate", "eliminate") or number (1-4).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "1" | "do" | "do-first" | "dofirst" => Ok(Quadrant::DoFirst),
+            "2" | "schedule" => Ok(Quadrant::Schedule),
+            "3" | "delegate" => Ok(Quadrant::Delegate),
+            "4" | "eliminate" => Ok(Quadrant::Eliminate),
+            other => Err(TodoeeError::InvalidInput(format!(
+                "Unknown quadrant '{}'. Use do, schedule, delegate, eliminate, or 1-4",
+                other
+            ))),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -134,6 +677,82 @@ pub struct Event {
     pub sync_status: SyncStatus,
 }
 
+impl Event {
+    pub fn new(title: String, start_time: DateTime<Utc>, end_time: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id: Uuid::nil(),
+            title,
+            description: None,
+            start_time,
+            end_time,
+            reminder_at: None,
+            recurrence_rule: None,
+            created_at: Utc::now(),
+            sync_status: SyncStatus::Pending,
+        }
+    }
+
+    /// Length of a single occurrence.
+    pub fn duration(&self) -> Duration {
+        self.end_time - self.start_time
+    }
+
+    /// Start times of all occurrences that overlap `[from, to)`.
+    ///
+    /// Recurring events are expanded on local wall-clock time, so a 9:30
+    /// meeting stays at 9:30 across DST changes. An unparseable rule is
+    /// treated as a single occurrence.
+    pub fn occurrences_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Vec<DateTime<Utc>> {
+        let duration = self.duration();
+
+        let rule = self
+            .recurrence_rule
+            .as_deref()
+            .and_then(|r| RecurrenceRule::parse(r).ok());
+        let Some(rule) = rule else {
+            return if self.start_time < to && self.start_time + duration > from {
+                vec![self.start_time]
+            } else {
+                Vec::new()
+            };
+        };
+
+        rule.between(&self.start_time.with_timezone(&Local), from - duration, to)
+            .into_iter()
+            .filter(|start| *start + duration > from)
+            .collect()
+    }
+
+    /// Start of the first occurrence at or after `after`, if any.
+    pub fn next_occurrence(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let rule = self
+            .recurrence_rule
+            .as_deref()
+            .and_then(|r| RecurrenceRule::parse(r).ok());
+        match rule {
+            Some(rule) => rule.next_after(
+                &self.start_time.with_timezone(&Local),
+                after - Duration::nanoseconds(1),
+            ),
+            None => (self.start_time >= after).then_some(self.start_time),
+        }
+    }
+}
+
+/// Recurrence rule stored for `--recur daily`.
+pub const RECUR_DAILY: &str = "FREQ=DAILY";
+/// Recurrence rule stored for `--recur weekdays`.
+pub const RECUR_WEEKDAYS: &str = "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR";
+/// Recurrence rule stored for `--recur weekly`.
+pub const RECUR_WEEKLY: &str = "FREQ=WEEKLY";
+/// Recurrence rule stored for `--recur monthly`.
+pub const RECUR_MONTHLY: &str = "FREQ=MONTHLY";
+
 /// Type of operation performed on an entity.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -145,6 +764,13 @@ pub enum OperationType {
     Uncomplete,
     Stash,
     Unstash,
+    /// Several todos created at once; `new_state` holds them all
+    #[serde(rename = "batch_create")]
+    BatchCreate,
+    /// Old changes to several todos compacted into one; `previous_state` and
+    /// `new_state` map each todo's ID to its state before and after, null
+    /// where it did not exist
+    Summary,
 }
 
 impl fmt::Display for OperationType {
@@ -157,6 +783,8 @@ impl fmt::Display for OperationType {
             OperationType::Uncomplete => "uncomplete",
             OperationType::Stash => "stash",
             OperationType::Unstash => "unstash",
+            OperationType::BatchCreate => "batch_create",
+            OperationType::Summary => "summary",
         };
         write!(f, "{}", s)
     }
@@ -191,6 +819,9 @@ pub struct Operation {
     pub new_state: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
     pub undone: bool,
+    /// Operations sharing a group are undone and redone together
+    #[serde(default)]
+    pub group_id: Option<Uuid>,
 }
 
 impl Operation {
@@ -210,8 +841,403 @@ impl Operation {
             new_state,
             created_at: Utc::now(),
             undone: false,
+            group_id: None,
+        }
+    }
+
+    /// Put this operation in an undo group.
+    pub fn in_group(mut self, group_id: Uuid) -> Self {
+        self.group_id = Some(group_id);
+        self
+    }
+
+    /// Record the creation of several todos as one undoable operation.
+    pub fn batch_create(todos: &[Todo]) -> Self {
+        Self::new(
+            OperationType::BatchCreate,
+            EntityType::Todo,
+            Uuid::new_v4(),
+            None,
+            serde_json::to_value(todos).ok(),
+        )
+    }
+
+    /// The todos created by a `BatchCreate` operation; empty for other types.
+    pub fn batch_todos(&self) -> Vec<Todo> {
+        if self.operation_type != OperationType::BatchCreate {
+            return Vec::new();
+        }
+        self.new_state
+            .clone()
+            .and_then(|state| serde_json::from_value(state).ok())
+            .unwrap_or_default()
+    }
+
+    /// Compact `ops`, oldest first, into one `Summary` taking each todo they
+    /// touched from its state before the first of them to its state after
+    /// the last. It keeps the group of the first, so undo still treats them
+    /// as one change.
+    pub fn summarize(ops: &[Operation]) -> Self {
+        let mut states: BTreeMap<Uuid, (Option<serde_json::Value>, Option<serde_json::Value>)> =
+            BTreeMap::new();
+        for (id, before, after) in ops.iter().flat_map(Operation::todo_states) {
+            states.entry(id).or_insert((before, None)).1 = after;
+        }
+        let (before, after): (BTreeMap<_, _>, BTreeMap<_, _>) = states
+            .into_iter()
+            .map(|(id, (before, after))| ((id, before), (id, after)))
+            .unzip();
+
+        let mut summary = Self::new(
+            OperationType::Summary,
+            EntityType::Todo,
+            Uuid::new_v4(),
+            serde_json::to_value(before).ok(),
+            serde_json::to_value(after).ok(),
+        );
+        if let Some(last) = ops.last() {
+            summary.created_at = last.created_at;
+        }
+        summary.group_id = ops.first().and_then(|op| op.group_id);
+        summary
+    }
+
+    /// Each todo a `Summary` operation changed, with its state before and
+    /// after; empty for other types.
+    pub fn summary_states(&self) -> Vec<TodoChange> {
+        if self.operation_type != OperationType::Summary {
+            return Vec::new();
+        }
+        let states =
+            |state: &Option<serde_json::Value>| -> BTreeMap<Uuid, Option<serde_json::Value>> {
+                state
+                    .clone()
+                    .and_then(|state| serde_json::from_value(state).ok())
+                    .unwrap_or_default()
+            };
+        let mut after = states(&self.new_state);
+        states(&self.previous_state)
+            .into_iter()
+            .map(|(id, before)| (id, before, after.remove(&id).flatten()))
+            .collect()
+    }
+
+    /// Each todo this operation changed, with its state before and after
+    fn todo_states(&self) -> Vec<TodoChange> {
+        match self.operation_type {
+            OperationType::BatchCreate => self
+                .batch_todos()
+                .into_iter()
+                .map(|todo| (todo.id, None, serde_json::to_value(&todo).ok()))
+                .collect(),
+            OperationType::Summary => self.summary_states(),
+            _ => vec![(
+                self.entity_id,
+                self.previous_state.clone(),
+                self.new_state.clone(),
+            )],
+        }
+    }
+}
+
+/// A todo's ID with its state before and after a change, `None` where it
+/// did not exist
+pub type TodoChange = (Uuid, Option<serde_json::Value>, Option<serde_json::Value>);
+
+/// How much undo history is kept, for `todoee log --stats`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct OperationStats {
+    pub operations: u64,
+    /// Undone operations, which redo can bring back
+    pub undone: u64,
+    /// Operations in undo groups, summaries included
+    pub grouped: u64,
+    /// Summaries left by compaction
+    pub summaries: u64,
+    /// Bytes of todo states kept for undo
+    pub bytes: u64,
+    pub oldest: Option<DateTime<Utc>>,
+    pub newest: Option<DateTime<Utc>>,
+}
+
+/// A device registered for cloud sync.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Device {
+    pub id: Uuid,
+    pub name: String,
+    pub registered_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    /// Set when the device was revoked; it can no longer sync
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl Device {
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+}
+
+/// Outcome of one sync attempt, kept locally for the sync health view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncRun {
+    pub finished_at: DateTime<Utc>,
+    pub uploaded: usize,
+    pub downloaded: usize,
+    /// Todos where a newer local change won over the remote copy
+    pub conflict_ids: Vec<Uuid>,
+    /// Set when the sync failed
+    pub error: Option<String>,
+}
+
+impl SyncRun {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// An AI parse that couldn't run while offline, queued for the daemon to retry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingAiParse {
+    pub todo_id: Uuid,
+    /// The text the user typed
+    pub input: String,
+    pub queued_at: DateTime<Utc>,
+    /// Failed retries so far
+    pub attempts: u32,
+}
+
+/// A timestamped progress note on a todo (`todoee note add`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TodoNote {
+    pub id: i64,
+    pub todo_id: Uuid,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A file or URL linked to a todo (`todoee attach`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub todo_id: Uuid,
+    /// A URL, or an absolute file path
+    pub target: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Attachment {
+    pub fn new(todo_id: Uuid, target: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            todo_id,
+            target,
+            created_at: Utc::now(),
         }
     }
+
+    /// Whether the target is a URL rather than a file path
+    pub fn is_url(&self) -> bool {
+        is_url(&self.target)
+    }
+}
+
+/// A focus timer run on a todo, saved when it ends.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FocusSession {
+    pub id: Uuid,
+    pub todo_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    /// Length the timer was set to; `None` for sessions saved before it was recorded
+    pub planned_minutes: Option<u32>,
+    /// How the session ended; `None` for sessions saved before it was recorded
+    pub outcome: Option<FocusOutcome>,
+    /// Logged during the session, oldest first
+    pub interruptions: Vec<FocusInterruption>,
+}
+
+/// How a focus session ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FocusOutcome {
+    /// The timer ran out, or was finished early on purpose
+    Completed,
+    /// The todo was marked done from the timer
+    Done,
+    /// Quit, skipped or cancelled before the end
+    Stopped,
+}
+
+impl fmt::Display for FocusOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            FocusOutcome::Completed => "completed",
+            FocusOutcome::Done => "done",
+            FocusOutcome::Stopped => "stopped",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for FocusOutcome {
+    type Err = TodoeeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "completed" => Ok(FocusOutcome::Completed),
+            "done" => Ok(FocusOutcome::Done),
+            "stopped" => Ok(FocusOutcome::Stopped),
+            other => Err(TodoeeError::InvalidInput(format!(
+                "Unknown focus outcome '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Time spent focusing on one todo.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FocusTime {
+    pub todo_id: Uuid,
+    /// `None` once the todo is deleted
+    pub title: Option<String>,
+    pub sessions: u64,
+    pub minutes: u64,
+}
+
+/// Something that broke a focus session (`i` while the timer runs).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FocusInterruption {
+    pub at: DateTime<Utc>,
+    /// One-line reason, e.g. "Slack"
+    pub reason: Option<String>,
+}
+
+impl FocusSession {
+    pub fn new(todo_id: Uuid, started_at: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            todo_id,
+            started_at,
+            ended_at: started_at,
+            planned_minutes: None,
+            outcome: None,
+            interruptions: Vec::new(),
+        }
+    }
+
+    /// Time between start and end
+    pub fn duration(&self) -> chrono::Duration {
+        self.ended_at - self.started_at
+    }
+
+    /// Log an interruption; a blank reason is stored as none
+    pub fn interrupt(&mut self, reason: &str, at: DateTime<Utc>) {
+        let reason = reason.trim();
+        self.interruptions.push(FocusInterruption {
+            at,
+            reason: (!reason.is_empty()).then(|| reason.to_string()),
+        });
+    }
+}
+
+/// Focus sessions ended in a range, and what interrupted them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FocusStats {
+    pub sessions: u64,
+    pub interruptions: u64,
+    /// Reasons with how often they were given, most common first
+    pub reasons: Vec<(String, u64)>,
+}
+
+impl FocusStats {
+    pub fn interruptions_per_session(&self) -> f64 {
+        if self.sessions == 0 {
+            0.0
+        } else {
+            self.interruptions as f64 / self.sessions as f64
+        }
+    }
+}
+
+/// Whether `target` looks like a URL (`scheme://...` or `mailto:`)
+pub fn is_url(target: &str) -> bool {
+    match target.split_once("://") {
+        Some((scheme, rest)) => {
+            !scheme.is_empty()
+                && !rest.is_empty()
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => target.starts_with("mailto:"),
+    }
+}
+
+/// A CLI invocation, kept for `todoee history` and `todoee repeat`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommandHistoryEntry {
+    pub id: i64,
+    /// Arguments after the program name, as typed
+    pub args: Vec<String>,
+    pub invoked_at: DateTime<Utc>,
+}
+
+/// Records found for an ID prefix typed on the command line: todos, unless
+/// another type is given.
+#[derive(Debug, Clone)]
+pub enum IdMatch<T = Todo> {
+    Unique(Box<T>),
+    /// Every record the prefix fits, in the order they were looked up
+    Ambiguous(Vec<T>),
+    NotFound,
+}
+
+impl<T> IdMatch<T> {
+    /// Sort the records a prefix fits into none, one or several
+    pub fn from_matches(mut matches: Vec<T>) -> Self {
+        match matches.len() {
+            0 => IdMatch::NotFound,
+            1 => IdMatch::Unique(Box::new(matches.remove(0))),
+            _ => IdMatch::Ambiguous(matches),
+        }
+    }
+
+    /// The `items` whose ID starts with `prefix`, for records that are not
+    /// looked up in the database
+    pub fn by_prefix(
+        items: impl IntoIterator<Item = T>,
+        prefix: &str,
+        id: impl Fn(&T) -> Uuid,
+    ) -> Self {
+        let Some(prefix) = id_prefix(prefix) else {
+            return IdMatch::NotFound;
+        };
+        Self::from_matches(
+            items
+                .into_iter()
+                .filter(|item| id(item).to_string().starts_with(&prefix))
+                .collect(),
+        )
+    }
+}
+
+/// `input` as a lowercase UUID prefix, or `None` if it can't start one.
+/// This also keeps LIKE wildcards out of prefix queries.
+pub fn id_prefix(input: &str) -> Option<String> {
+    let prefix = input.trim().to_lowercase();
+    (!prefix.is_empty() && prefix.chars().all(|c| c.is_ascii_hexdigit() || c == '-'))
+        .then_some(prefix)
+}
+
+/// The reflection written for one day in `todoee journal`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JournalEntry {
+    /// Local calendar day
+    pub date: NaiveDate,
+    /// Appended notes, one per line
+    pub note: String,
+    pub updated_at: DateTime<Utc>,
 }
 
 #[cfg(test)]
@@ -240,12 +1266,345 @@ mod tests {
         assert!(todo.completed_at.is_some());
     }
 
+    #[test]
+    fn test_todo_is_same_todo() {
+        let todo = Todo::new("Original".to_string(), None);
+
+        let mut copy = todo.clone();
+        copy.title = "Edited elsewhere".to_string();
+        copy.created_at += Duration::nanoseconds(500);
+        assert!(todo.is_same_todo(&copy));
+
+        let mut collision = Todo::new("Different".to_string(), None);
+        collision.id = todo.id;
+        collision.created_at = todo.created_at - Duration::days(3);
+        assert!(!todo.is_same_todo(&collision));
+    }
+
+    #[test]
+    fn test_todo_snooze_moves_reminder() {
+        let now = Utc::now();
+        let mut todo = Todo::new("Call mom".to_string(), None);
+        todo.reminder_at = Some(now - Duration::minutes(1));
+        todo.sync_status = SyncStatus::Synced;
+
+        let until = todo.snooze(Duration::minutes(10), now);
+
+        assert_eq!(until, now + Duration::minutes(10));
+        assert_eq!(todo.reminder_at, Some(until));
+        assert_eq!(todo.sync_status, SyncStatus::Pending);
+    }
+
+    #[test]
+    fn test_todo_detect_icon() {
+        let mut todo = Todo::new("Deploy 🚀".to_string(), None);
+        assert!(todo.detect_icon());
+        assert_eq!(todo.title, "Deploy");
+        assert_eq!(todo.icon.as_deref(), Some("🚀"));
+        assert_eq!(todo.display_title(), "🚀 Deploy");
+
+        let mut leading = Todo::new("👩‍💻 Pair on parser".to_string(), None);
+        assert!(leading.detect_icon());
+        assert_eq!(leading.title, "Pair on parser");
+
+        for title in ["🚀", "Fix bug #12", "Ship it!", "Add → arrow"] {
+            let mut todo = Todo::new(title.to_string(), None);
+            assert!(!todo.detect_icon(), "detected icon in '{}'", title);
+            assert_eq!(todo.title, title);
+        }
+    }
+
+    #[test]
+    fn test_is_url() {
+        for url in [
+            "https://example.com/a?b=c",
+            "file:///tmp/x",
+            "obsidian://open?vault=notes",
+            "mailto:me@example.com",
+        ] {
+            assert!(is_url(url), "{}", url);
+        }
+        for path in [
+            "/home/me/report.pdf",
+            "C:\\docs\\a.txt",
+            "notes.md",
+            "://x",
+            "http://",
+        ] {
+            assert!(!is_url(path), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_unique_prefix_len() {
+        let id = |s: &str| Uuid::parse_str(s).unwrap();
+        assert_eq!(unique_prefix_len(&[]), MIN_ID_LENGTH);
+        assert_eq!(
+            unique_prefix_len(&[id("0cf2b3ff-cbfe-4567-af06-5cc366540e74")]),
+            MIN_ID_LENGTH
+        );
+
+        let ids = [
+            id("0cf2b3ff-cbfe-4567-af06-5cc366540e74"),
+            id("0cf2b3a0-cbfe-4567-af06-5cc366540e74"),
+            id("9a000000-0000-4000-8000-000000000000"),
+        ];
+        assert_eq!(unique_prefix_len(&ids), 7);
+        assert_eq!(short_id(&ids[0], 7), "0cf2b3f");
+
+        // Past the first block the hyphen is part of the prefix
+        let ids = [
+            id("0cf2b3ff-cbfe-4567-af06-5cc366540e74"),
+            id("0cf2b3ff-dbfe-4567-af06-5cc366540e74"),
+        ];
+        assert_eq!(unique_prefix_len(&ids), 10);
+    }
+
+    #[test]
+    fn test_is_icon() {
+        for icon in ["🚀", "★", "❤️", "👍🏽", "🇳🇱"] {
+            assert!(is_icon(icon), "rejected '{}'", icon);
+        }
+        for icon in ["", "a", "ok", "!", "🚀🚀🚀🚀🚀", "🚀 x"] {
+            assert!(!is_icon(icon), "accepted '{}'", icon);
+        }
+    }
+
+    #[test]
+    fn test_icon_prefix_pads_to_icon_width() {
+        let mut todo = Todo::new("Task".to_string(), None);
+        assert_eq!(todo.icon_prefix(false), "");
+        assert_eq!(todo.icon_prefix(true), "   ");
+
+        todo.icon = Some("🚀".to_string());
+        assert_eq!(todo.icon_prefix(true), "🚀 ");
+
+        todo.icon = Some("★".to_string());
+        assert_eq!(todo.icon_prefix(false), "★  ");
+    }
+
     #[test]
     fn test_priority_ordering() {
         assert!(Priority::High > Priority::Medium);
         assert!(Priority::Medium > Priority::Low);
     }
 
+    #[test]
+    fn test_event_single_occurrence() {
+        let start = Utc::now();
+        let event = Event::new("Standup".to_string(), start, start + Duration::minutes(15));
+
+        let hits =
+            event.occurrences_between(start - Duration::hours(1), start + Duration::hours(1));
+        assert_eq!(hits, vec![start]);
+
+        let misses =
+            event.occurrences_between(start + Duration::hours(1), start + Duration::hours(2));
+        assert!(misses.is_empty());
+    }
+
+    #[test]
+    fn test_event_weekly_occurrences() {
+        let start = Utc::now();
+        let mut event = Event::new("Review".to_string(), start, start + Duration::hours(1));
+        event.recurrence_rule = Some(RECUR_WEEKLY.to_string());
+
+        let hits = event.occurrences_between(start, start + Duration::weeks(3));
+        assert_eq!(
+            hits,
+            vec![
+                start,
+                start + Duration::weeks(1),
+                start + Duration::weeks(2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_event_next_occurrence() {
+        let start = Utc::now();
+        let mut event = Event::new("Review".to_string(), start, start + Duration::hours(1));
+        assert_eq!(event.next_occurrence(start), Some(start));
+        assert_eq!(event.next_occurrence(start + Duration::seconds(1)), None);
+
+        event.recurrence_rule = Some(RECUR_WEEKLY.to_string());
+        assert_eq!(
+            event.next_occurrence(start + Duration::seconds(1)),
+            Some(start + Duration::weeks(1))
+        );
+    }
+
+    #[test]
+    fn test_event_weekday_occurrences_skip_weekends() {
+        use chrono::Datelike;
+
+        let start = Utc::now();
+        let mut event = Event::new("Standup".to_string(), start, start + Duration::minutes(15));
+        event.recurrence_rule = Some(RECUR_WEEKDAYS.to_string());
+
+        let hits = event.occurrences_between(start, start + Duration::weeks(1));
+        assert_eq!(hits.len(), 5);
+        assert!(
+            hits.iter()
+                .all(|d| { d.with_timezone(&Local).weekday().number_from_monday() <= 5 })
+        );
+    }
+
+    #[test]
+    fn test_todo_quadrant_from_due_date_and_priority() {
+        let now = Utc::now();
+        let mut todo = Todo::new("Report".to_string(), None);
+        assert_eq!(todo.quadrant(now), Quadrant::Eliminate);
+
+        todo.priority = Priority::High;
+        assert_eq!(todo.quadrant(now), Quadrant::Schedule);
+
+        todo.due_date = Some(now + Duration::days(1));
+        assert_eq!(todo.quadrant(now), Quadrant::DoFirst);
+
+        todo.priority = Priority::Low;
+        assert_eq!(todo.quadrant(now), Quadrant::Delegate);
+
+        // Overdue counts as urgent, far-off due dates do not
+        todo.due_date = Some(now - Duration::days(3));
+        assert!(todo.is_urgent(now));
+        todo.due_date = Some(now + Duration::days(URGENT_WITHIN_DAYS + 1));
+        assert!(!todo.is_urgent(now));
+    }
+
+    #[test]
+    fn test_todo_move_to_quadrant() {
+        let now = Utc::now();
+        let mut todo = Todo::new("Plan".to_string(), None);
+
+        for target in Quadrant::ALL {
+            todo.move_to_quadrant(target, now);
+            assert_eq!(todo.quadrant(now), target);
+        }
+
+        // Already there: nothing changes
+        let before = todo.clone();
+        assert!(!todo.move_to_quadrant(Quadrant::Eliminate, now));
+        assert_eq!(todo.priority, before.priority);
+        assert_eq!(todo.due_date, before.due_date);
+    }
+
+    #[test]
+    fn test_move_to_quadrant_keeps_low_priority_when_not_important() {
+        let now = Utc::now();
+        let mut todo = Todo::new("Chore".to_string(), None);
+        todo.priority = Priority::Low;
+
+        assert!(todo.move_to_quadrant(Quadrant::Delegate, now));
+        assert_eq!(todo.priority, Priority::Low);
+        assert!(todo.is_urgent(now));
+        assert_eq!(todo.sync_status, SyncStatus::Pending);
+    }
+
+    #[test]
+    fn test_quadrant_parse_and_display() {
+        for q in Quadrant::ALL {
+            assert_eq!(q.to_string().parse::<Quadrant>().unwrap(), q);
+            assert_eq!((q.index() + 1).to_string().parse::<Quadrant>().unwrap(), q);
+            assert_eq!(Quadrant::ALL[q.index()], q);
+        }
+        assert!("urgent".parse::<Quadrant>().is_err());
+    }
+
+    fn parsed(title: &str) -> ParsedTask {
+        ParsedTask {
+            title: title.to_string(),
+            description: None,
+            due_date: None,
+            category: Some("work".to_string()),
+            priority: None,
+            reminder_at: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_ai_parse_records_provenance() {
+        let input = "call bob friday urgent";
+        let mut todo = Todo::new(input.to_string(), None);
+        let mut task = parsed("Call Bob");
+        task.due_date = Some(Utc::now() + Duration::days(2));
+        task.priority = Some(1);
+
+        todo.apply_ai_parse(&task, input);
+
+        assert_eq!(todo.title, "Call Bob");
+        assert_eq!(todo.priority, Priority::Urgent);
+        let mut fields = todo.ai_fields();
+        fields.sort();
+        assert_eq!(fields, vec!["due_date", "priority", "title"]);
+
+        let meta = todo.ai_metadata.as_ref().unwrap();
+        assert_eq!(meta["original_input"], input);
+        assert_eq!(meta["parsed_category"], "work");
+        assert_eq!(meta["ai_fields"]["title"]["human"], input);
+        assert_eq!(meta["ai_fields"]["priority"]["human"], "medium");
+        assert!(meta["ai_fields"]["due_date"]["human"].is_null());
+    }
+
+    #[test]
+    fn test_revert_ai_fields_restores_human_values() {
+        let input = "call bob friday urgent";
+        let mut todo = Todo::new(input.to_string(), None);
+        let mut task = parsed("Call Bob");
+        task.due_date = Some(Utc::now() + Duration::days(2));
+        task.priority = Some(1);
+        todo.apply_ai_parse(&task, input);
+
+        let (mut reverted, kept) = todo.revert_ai_fields();
+        reverted.sort();
+        assert_eq!(reverted, vec!["due_date", "priority", "title"]);
+        assert!(kept.is_empty());
+
+        assert_eq!(todo.title, input);
+        assert!(todo.due_date.is_none());
+        assert_eq!(todo.priority, Priority::Medium);
+        assert!(todo.ai_fields().is_empty());
+
+        // A second revert has nothing left to do
+        let (reverted, _) = todo.revert_ai_fields();
+        assert!(reverted.is_empty());
+    }
+
+    #[test]
+    fn test_revert_ai_fields_keeps_later_edits() {
+        let input = "write report";
+        let mut todo = Todo::new(input.to_string(), None);
+        let mut task = parsed("Write report");
+        task.priority = Some(4);
+        todo.apply_ai_parse(&task, input);
+
+        // The user changed the priority afterwards
+        todo.priority = Priority::High;
+
+        let (reverted, kept) = todo.revert_ai_fields();
+        assert_eq!(reverted, vec!["title"]);
+        assert_eq!(kept, vec!["priority"]);
+        assert_eq!(todo.title, input);
+        assert_eq!(todo.priority, Priority::High);
+    }
+
+    #[test]
+    fn test_revert_ai_fields_legacy_metadata() {
+        let mut todo = Todo::new("Buy milk".to_string(), None);
+        todo.ai_metadata = Some(serde_json::json!({
+            "original_input": "buy milk tmrw",
+            "parsed_category": null,
+        }));
+
+        let (reverted, kept) = todo.revert_ai_fields();
+        assert_eq!(reverted, vec!["title"]);
+        assert!(kept.is_empty());
+        assert_eq!(todo.title, "buy milk tmrw");
+
+        let mut manual = Todo::new("Manual".to_string(), None);
+        assert_eq!(manual.revert_ai_fields(), (Vec::new(), Vec::new()));
+    }
+
     #[test]
     fn test_category_new() {
         let user_id = Uuid::new_v4();
@@ -0,0 +1,300 @@
+//! Markdown note checkboxes
+//!
+//! Finds `- [ ] task` items in a directory of markdown notes so they can be
+//! imported as todos, and flips a checkbox back in its file when the linked
+//! todo is completed. Items inside fenced code blocks are ignored.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// File extensions treated as markdown notes
+const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown"];
+
+/// A checkbox item found in a note
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteTask {
+    pub path: PathBuf,
+    /// 1-based line number
+    pub line: u32,
+    pub text: String,
+    pub checked: bool,
+}
+
+/// Backlink from a todo to the checkbox it was imported from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NoteLink {
+    pub todo_id: Uuid,
+    pub path: String,
+    /// 1-based line number, updated when the item moves
+    pub line: u32,
+    pub text: String,
+    /// Completing the todo also checks the box in the file
+    pub two_way: bool,
+}
+
+/// Expand a leading `~/` to the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/").zip(dirs::home_dir()) {
+        Some((rest, home)) => home.join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
+/// Scan `dir` recursively for checkbox items in markdown files.
+///
+/// Hidden files and directories (`.git`, `.obsidian`) are skipped. Results
+/// are ordered by path, then line.
+pub fn scan_dir(dir: &Path) -> Result<Vec<NoteTask>> {
+    if !dir.is_dir() {
+        anyhow::bail!("Not a directory: {}", dir.display());
+    }
+
+    let mut files = Vec::new();
+    collect_markdown_files(dir, &mut files)?;
+    files.sort();
+
+    let mut tasks = Vec::new();
+    for path in files {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read note: {}", path.display()))?;
+        tasks.extend(
+            parse_checkboxes(&content)
+                .into_iter()
+                .map(|(line, text, checked)| NoteTask {
+                    path: path.clone(),
+                    line,
+                    text,
+                    checked,
+                }),
+        );
+    }
+    Ok(tasks)
+}
+
+fn collect_markdown_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        let hidden = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with('.'));
+        if hidden {
+            continue;
+        }
+
+        // Not followed through symlinks, which could loop back up the tree
+        if entry.file_type()?.is_dir() {
+            collect_markdown_files(&path, files)?;
+        } else if path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| MARKDOWN_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Checkbox items in a markdown document as (line, text, checked)
+pub fn parse_checkboxes(content: &str) -> Vec<(u32, String, bool)> {
+    let mut in_code_block = false;
+    let mut items = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        if let Some((checked, text)) = parse_checkbox_line(line) {
+            items.push((idx as u32 + 1, text.to_string(), checked));
+        }
+    }
+    items
+}
+
+/// Parse "- [ ] text", "* [x] text" or "1. [ ] text". Returns the checked
+/// state and the trimmed item text, or `None` for other lines and empty items.
+pub(crate) fn parse_checkbox_line(line: &str) -> Option<(bool, &str)> {
+    let (marker, rest) = checkbox_bounds(line)?;
+    let checked = match marker {
+        ' ' => false,
+        'x' | 'X' => true,
+        _ => return None,
+    };
+    let text = rest.trim();
+    (!text.is_empty()).then_some((checked, text))
+}
+
+/// The checkbox marker and the text after "]", if `line` is a list checkbox
+fn checkbox_bounds(line: &str) -> Option<(char, &str)> {
+    let trimmed = line.trim_start();
+    let after_bullet = if let Some(rest) = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+        .or_else(|| trimmed.strip_prefix("+ "))
+    {
+        rest
+    } else {
+        let digits = trimmed.len()
+            - trimmed
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .len();
+        if digits == 0 {
+            return None;
+        }
+        trimmed[digits..]
+            .strip_prefix(". ")
+            .or_else(|| trimmed[digits..].strip_prefix(") "))?
+    };
+
+    let inner = after_bullet.strip_prefix('[')?;
+    let mut chars = inner.chars();
+    let marker = chars.next()?;
+    let rest = chars.as_str().strip_prefix(']')?;
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some((marker, rest))
+}
+
+/// Set the checkbox for `text` in `path` to `checked`.
+///
+/// Looks at `line` first and falls back to the first item with the same text,
+/// so edits above the item don't break the link. Returns the line the item was
+/// found on, or `None` if it is gone. The file is only written when it changes.
+pub fn set_checkbox(path: &Path, line: u32, text: &str, checked: bool) -> Result<Option<u32>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read note: {}", path.display()))?;
+    let items = parse_checkboxes(&content);
+
+    let found = items
+        .iter()
+        .find(|(l, t, _)| *l == line && t == text)
+        .or_else(|| items.iter().find(|(_, t, _)| t == text));
+    let Some(&(found_line, _, is_checked)) = found else {
+        return Ok(None);
+    };
+    if is_checked == checked {
+        return Ok(Some(found_line));
+    }
+
+    let marker = if checked { "[x]" } else { "[ ]" };
+    let updated: String = content
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(idx, l)| {
+            if idx as u32 + 1 != found_line {
+                return l.to_string();
+            }
+            // Replace only the first "[?]" so the rest of the line is untouched
+            let start = l.find('[').expect("checkbox line has a bracket");
+            format!("{}{}{}", &l[..start], marker, &l[start + 3..])
+        })
+        .collect();
+
+    fs::write(path, updated)
+        .with_context(|| format!("Failed to update note: {}", path.display()))?;
+    Ok(Some(found_line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const NOTE: &str = "# Groceries\n\
+        - [ ] Buy milk\n\
+        * [x] Buy eggs\n\
+        1. [ ] Call the bakery\n\
+        - [] not a checkbox\n\
+        - [ ]\n\
+        ```\n\
+        - [ ] example in code\n\
+        ```\n\
+        \x20 - [ ] Nested item\n";
+
+    #[test]
+    fn test_parse_checkboxes() {
+        let items = parse_checkboxes(NOTE);
+        assert_eq!(
+            items,
+            vec![
+                (2, "Buy milk".to_string(), false),
+                (3, "Buy eggs".to_string(), true),
+                (4, "Call the bakery".to_string(), false),
+                (10, "Nested item".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_dir_skips_hidden_and_non_markdown() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.md"), "- [ ] One\n").unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("sub/b.markdown"), "- [ ] Two\n").unwrap();
+        fs::write(dir.path().join("c.txt"), "- [ ] Not a note\n").unwrap();
+        fs::create_dir(dir.path().join(".obsidian")).unwrap();
+        fs::write(dir.path().join(".obsidian/d.md"), "- [ ] Hidden\n").unwrap();
+
+        let texts: Vec<String> = scan_dir(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|t| t.text)
+            .collect();
+        assert_eq!(texts, vec!["One", "Two"]);
+
+        assert!(scan_dir(&dir.path().join("missing")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_dir_does_not_follow_symlink_loops() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("sub/a.md"), "- [ ] One\n").unwrap();
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("sub/loop")).unwrap();
+
+        let texts: Vec<String> = scan_dir(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|t| t.text)
+            .collect();
+        assert_eq!(texts, vec!["One"]);
+    }
+
+    #[test]
+    fn test_set_checkbox_follows_moved_item() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("note.md");
+        fs::write(&path, "- [ ] Buy milk\r\n- [ ] Call [mom]\r\n").unwrap();
+
+        assert_eq!(set_checkbox(&path, 2, "Call [mom]", true).unwrap(), Some(2));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "- [ ] Buy milk\r\n- [x] Call [mom]\r\n"
+        );
+
+        // Item moved down a line since it was imported
+        fs::write(&path, "Intro\n- [ ] Buy milk\n").unwrap();
+        assert_eq!(set_checkbox(&path, 1, "Buy milk", true).unwrap(), Some(2));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "Intro\n- [x] Buy milk\n"
+        );
+
+        assert_eq!(set_checkbox(&path, 2, "Gone", true).unwrap(), None);
+    }
+}
@@ -0,0 +1,488 @@
+//! Emacs org-mode interop
+//!
+//! Writes todos as org headings and reads them back:
+//!
+//! ```org
+//! * TODO [#A] Pay rent :home:
+//!   DEADLINE: <2026-03-01 Sun> SCHEDULED: <2026-02-28 Sat 09:00>
+//!   :PROPERTIES:
+//!   :ID: 4f1c...
+//!   :END:
+//!   Transfer from the joint account
+//! ```
+//!
+//! TODO/DONE keywords map to completion (custom `#+TODO:` sequences are
+//! honoured), `#A`/`#B`/`#C` to high/medium/low priority, DEADLINE to the due
+//! date, SCHEDULED to the reminder and the first tag to the category.
+//! Headings without a keyword are ignored. Timestamps are wall-clock times in
+//! the caller's timezone.
+
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+use uuid::Uuid;
+
+use crate::dates::{self, ParsedDate};
+use crate::models::{Category, Priority, Todo};
+
+/// A todo read from an org file, with the heading's tags
+#[derive(Debug, Clone)]
+pub struct OrgTodo {
+    pub todo: Todo,
+    /// Heading tags in order; the first is used as the category
+    pub tags: Vec<String>,
+}
+
+/// Render todos as an org document
+pub fn write<Tz: TimeZone>(todos: &[Todo], categories: &[Category], tz: &Tz) -> String {
+    let mut out = String::from("#+TITLE: todoee\n#+TODO: TODO | DONE\n\n");
+
+    for todo in todos {
+        let keyword = if todo.is_completed { "DONE" } else { "TODO" };
+        out.push_str("* ");
+        out.push_str(keyword);
+        out.push(' ');
+        if let Some(cookie) = priority_cookie(todo.priority) {
+            out.push_str(cookie);
+            out.push(' ');
+        }
+        out.push_str(&todo.display_title());
+        if let Some(category) = todo
+            .category_id
+            .and_then(|id| categories.iter().find(|c| c.id == id))
+        {
+            out.push_str(&format!(" :{}:", tag_name(&category.name)));
+        }
+        out.push('\n');
+
+        let mut planning = Vec::new();
+        if let Some(closed) = todo.completed_at.filter(|_| todo.is_completed) {
+            planning.push(format!("CLOSED: [{}]", timestamp(closed, tz)));
+        }
+        if let Some(due) = todo.due_date {
+            let stamp = if dates::is_date_only(&due) {
+                format_naive(due.date_naive(), None)
+            } else {
+                timestamp(due, tz)
+            };
+            planning.push(format!("DEADLINE: <{}>", stamp));
+        }
+        if let Some(reminder) = todo.reminder_at {
+            planning.push(format!("SCHEDULED: <{}>", timestamp(reminder, tz)));
+        }
+        if !planning.is_empty() {
+            out.push_str(&format!("  {}\n", planning.join(" ")));
+        }
+
+        out.push_str("  :PROPERTIES:\n");
+        out.push_str(&format!("  :ID: {}\n", todo.id));
+        // Seconds keep the creation time exact enough to recognise the todo
+        let created = todo.created_at.with_timezone(tz).naive_local();
+        out.push_str(&format!(
+            "  :CREATED: [{}]\n",
+            created.format("%Y-%m-%d %a %H:%M:%S")
+        ));
+        out.push_str("  :END:\n");
+
+        if let Some(description) = todo.description.as_deref().filter(|d| !d.trim().is_empty()) {
+            for line in description.lines() {
+                if line.trim().is_empty() {
+                    out.push('\n');
+                } else {
+                    out.push_str(&format!("  {}\n", line));
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// Parse the TODO headings of an org document
+pub fn parse<Tz: TimeZone>(content: &str, tz: &Tz) -> Vec<OrgTodo> {
+    let (active, done) = todo_keywords(content);
+    let mut todos = Vec::new();
+    let mut current: Option<(OrgTodo, Vec<String>)> = None;
+    let mut in_drawer = false;
+
+    for line in content.lines() {
+        if let Some(heading) = line.strip_prefix('*')
+            && let Some(heading) = heading.trim_start_matches('*').strip_prefix(' ')
+        {
+            in_drawer = false;
+            if let Some((entry, body)) = current.take() {
+                todos.push(finish(entry, &body));
+            }
+            current = parse_heading(heading, &active, &done).map(|entry| (entry, Vec::new()));
+            continue;
+        }
+
+        let Some((entry, body)) = current.as_mut() else {
+            continue;
+        };
+        let trimmed = line.trim();
+
+        if in_drawer {
+            if trimmed.eq_ignore_ascii_case(":END:") {
+                in_drawer = false;
+            } else if let Some((name, value)) = property(trimmed) {
+                apply_property(&mut entry.todo, name, value, tz);
+            }
+            continue;
+        }
+        if is_drawer_start(trimmed) {
+            in_drawer = true;
+            continue;
+        }
+        if is_planning_line(trimmed) {
+            apply_planning(&mut entry.todo, trimmed, tz);
+            continue;
+        }
+        body.push(line.to_string());
+    }
+
+    if let Some((entry, body)) = current {
+        todos.push(finish(entry, &body));
+    }
+    todos
+}
+
+/// Org tag for a category name: tags may only contain letters, digits, `_@#%`
+pub fn tag_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || "_@#%".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn priority_cookie(priority: Priority) -> Option<&'static str> {
+    match priority {
+        Priority::Urgent | Priority::High => Some("[#A]"),
+        // B is org's default priority, so it needs no cookie
+        Priority::Medium => None,
+        Priority::Low => Some("[#C]"),
+    }
+}
+
+fn timestamp<Tz: TimeZone>(at: DateTime<Utc>, tz: &Tz) -> String {
+    let local = at.with_timezone(tz).naive_local();
+    format_naive(local.date(), Some(local.time()))
+}
+
+/// "2026-03-01 Sun" or "2026-03-01 Sun 14:00"
+fn format_naive(date: NaiveDate, time: Option<NaiveTime>) -> String {
+    match time {
+        Some(time) => format!(
+            "{} {:02}:{:02}",
+            date.format("%Y-%m-%d %a"),
+            time.hour(),
+            time.minute()
+        ),
+        None => date.format("%Y-%m-%d %a").to_string(),
+    }
+}
+
+/// Keywords from `#+TODO:` lines as (active, done); defaults to TODO | DONE
+fn todo_keywords(content: &str) -> (Vec<String>, Vec<String>) {
+    let mut active = Vec::new();
+    let mut done = Vec::new();
+
+    for line in content.lines() {
+        let lower = line.trim_start().to_lowercase();
+        let Some(prefix) = ["#+todo:", "#+seq_todo:", "#+typ_todo:"]
+            .iter()
+            .find(|p| lower.starts_with(*p))
+        else {
+            continue;
+        };
+
+        let spec = &line.trim_start()[prefix.len()..];
+        let (open, closed) = match spec.split_once('|') {
+            Some((open, closed)) => (open, closed),
+            None => {
+                // Without a bar the last keyword is the done state
+                let words: Vec<&str> = spec.split_whitespace().collect();
+                if let Some((last, rest)) = words.split_last() {
+                    active.extend(rest.iter().map(|w| keyword_name(w)));
+                    done.push(keyword_name(last));
+                }
+                continue;
+            }
+        };
+        active.extend(open.split_whitespace().map(keyword_name));
+        done.extend(closed.split_whitespace().map(keyword_name));
+    }
+
+    if active.is_empty() && done.is_empty() {
+        (vec!["TODO".to_string()], vec!["DONE".to_string()])
+    } else {
+        (active, done)
+    }
+}
+
+/// "WAIT(w@/!)" -> "WAIT"
+fn keyword_name(word: &str) -> String {
+    word.split('(').next().unwrap_or(word).to_string()
+}
+
+/// Parse the heading text after the stars, if it starts with a TODO keyword
+fn parse_heading(heading: &str, active: &[String], done: &[String]) -> Option<OrgTodo> {
+    let (keyword, rest) = heading.split_once(' ').unwrap_or((heading, ""));
+    let is_completed = if active.iter().any(|k| k == keyword) {
+        false
+    } else if done.iter().any(|k| k == keyword) {
+        true
+    } else {
+        return None;
+    };
+
+    let mut rest = rest.trim();
+    let mut priority = Priority::Medium;
+    if let Some(cookie) = rest.strip_prefix("[#")
+        && let Some((letter, after)) = cookie.split_once(']')
+    {
+        priority = match letter {
+            "A" => Priority::High,
+            "C" => Priority::Low,
+            _ => Priority::Medium,
+        };
+        rest = after.trim_start();
+    }
+
+    let mut tags = Vec::new();
+    if let Some((title, last)) = rest.rsplit_once(char::is_whitespace)
+        && last.len() > 2
+        && last.starts_with(':')
+        && last.ends_with(':')
+    {
+        tags = last
+            .trim_matches(':')
+            .split(':')
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect();
+        rest = title.trim_end();
+    }
+
+    if rest.is_empty() {
+        return None;
+    }
+
+    let mut todo = Todo::new(rest.to_string(), None);
+    todo.detect_icon();
+    todo.priority = priority;
+    if is_completed {
+        todo.mark_complete();
+    }
+    Some(OrgTodo { todo, tags })
+}
+
+fn finish(mut entry: OrgTodo, body: &[String]) -> OrgTodo {
+    // Drop the common indentation and surrounding blank lines
+    let indent = body
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let text = body
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let text = text.trim_matches('\n');
+    if !text.is_empty() {
+        entry.todo.description = Some(text.to_string());
+    }
+    entry
+}
+
+fn is_drawer_start(line: &str) -> bool {
+    line.len() > 2
+        && line.starts_with(':')
+        && line.ends_with(':')
+        && line[1..line.len() - 1]
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// ":ID: value" -> ("ID", "value")
+fn property(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix(':')?;
+    let (name, value) = rest.split_once(':')?;
+    Some((name, value.trim()))
+}
+
+fn apply_property<Tz: TimeZone>(todo: &mut Todo, name: &str, value: &str, tz: &Tz) {
+    match name.to_uppercase().as_str() {
+        "ID" => {
+            if let Ok(id) = Uuid::parse_str(value) {
+                todo.id = id;
+            }
+        }
+        "CREATED" => {
+            if let Some(created) = parse_timestamp(value) {
+                todo.created_at = created.reminder_at(tz);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_planning_line(line: &str) -> bool {
+    ["SCHEDULED:", "DEADLINE:", "CLOSED:"]
+        .iter()
+        .any(|k| line.starts_with(k))
+}
+
+/// Apply "DEADLINE: <...> SCHEDULED: <...> CLOSED: [...]" to `todo`
+fn apply_planning<Tz: TimeZone>(todo: &mut Todo, line: &str, tz: &Tz) {
+    let mut rest = line;
+    while let Some(colon) = rest.find(':') {
+        let keyword = rest[..colon].trim();
+        let after = rest[colon + 1..].trim_start();
+        let close = match after.chars().next() {
+            Some('<') => '>',
+            Some('[') => ']',
+            _ => break,
+        };
+        let Some(end) = after.find(close) else {
+            break;
+        };
+        let stamp = parse_timestamp(&after[..=end]);
+        rest = &after[end + 1..];
+
+        let Some(stamp) = stamp else {
+            continue;
+        };
+        match keyword {
+            "DEADLINE" => todo.due_date = Some(stamp.due_date(tz)),
+            "SCHEDULED" => todo.reminder_at = Some(stamp.reminder_at(tz)),
+            "CLOSED" if todo.is_completed => todo.completed_at = Some(stamp.reminder_at(tz)),
+            _ => {}
+        }
+    }
+}
+
+/// Parse "<2026-03-01 Sun 14:00 +1w>" or "[2026-03-01]". Repeaters, warning
+/// periods and the end of a time range are ignored.
+fn parse_timestamp(stamp: &str) -> Option<ParsedDate> {
+    let inner = stamp
+        .trim()
+        .trim_start_matches(['<', '['])
+        .trim_end_matches(['>', ']']);
+    let mut words = inner.split_whitespace();
+    let date = NaiveDate::parse_from_str(words.next()?, "%Y-%m-%d").ok()?;
+    let time = words.find_map(|w| {
+        let start = w.split('-').next().unwrap_or(w);
+        NaiveTime::parse_from_str(start, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(start, "%H:%M"))
+            .ok()
+    });
+    Some(ParsedDate { date, time })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, NaiveDateTime};
+
+    fn tz() -> FixedOffset {
+        FixedOffset::east_opt(2 * 3600).unwrap()
+    }
+
+    fn at(tz: &FixedOffset, s: &str) -> DateTime<Utc> {
+        let naive = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        tz.from_local_datetime(&naive).unwrap().with_timezone(&Utc)
+    }
+
+    const DOC: &str = "#+TITLE: Tasks
+#+TODO: TODO NEXT(n) | DONE CANCELLED
+
+* Projects
+** TODO [#A] Pay rent :home:bills:
+   DEADLINE: <2026-03-01 Sun> SCHEDULED: <2026-02-28 Sat 09:30 +1m>
+   :PROPERTIES:
+   :ID: 6f9619ff-8b86-d011-b42d-00cf4fc964ff
+   :CREATED: [2026-02-01 Sun 10:00]
+   :END:
+   Transfer from the joint account
+     - check balance first
+** NEXT Call the bakery
+** DONE [#C] Buy eggs
+   CLOSED: [2026-02-02 Mon 18:15]
+* Just a heading
+";
+
+    #[test]
+    fn test_parse_org_headings() {
+        let todos = parse(DOC, &tz());
+        let titles: Vec<&str> = todos.iter().map(|t| t.todo.title.as_str()).collect();
+        assert_eq!(titles, vec!["Pay rent", "Call the bakery", "Buy eggs"]);
+
+        let rent = &todos[0];
+        assert_eq!(rent.tags, vec!["home", "bills"]);
+        assert_eq!(rent.todo.priority, Priority::High);
+        assert!(!rent.todo.is_completed);
+        assert_eq!(
+            rent.todo.id.to_string(),
+            "6f9619ff-8b86-d011-b42d-00cf4fc964ff"
+        );
+        assert_eq!(rent.todo.created_at, at(&tz(), "2026-02-01 10:00"));
+        assert_eq!(
+            rent.todo.due_date.unwrap().to_rfc3339(),
+            "2026-03-01T12:00:00+00:00"
+        );
+        assert_eq!(rent.todo.reminder_at, Some(at(&tz(), "2026-02-28 09:30")));
+        assert_eq!(
+            rent.todo.description.as_deref(),
+            Some("Transfer from the joint account\n  - check balance first")
+        );
+
+        assert_eq!(todos[1].todo.priority, Priority::Medium);
+        assert!(todos[1].tags.is_empty());
+
+        let eggs = &todos[2].todo;
+        assert!(eggs.is_completed);
+        assert_eq!(eggs.priority, Priority::Low);
+        assert_eq!(eggs.completed_at, Some(at(&tz(), "2026-02-02 18:15")));
+    }
+
+    #[test]
+    fn test_org_round_trip() {
+        let category = Category::new(Uuid::new_v4(), "Side project".to_string());
+        let mut todo = Todo::new("🛒 Buy milk".to_string(), None);
+        todo.detect_icon();
+        todo.category_id = Some(category.id);
+        todo.priority = Priority::High;
+        todo.due_date = Some(at(&tz(), "2026-03-01 15:00"));
+        todo.reminder_at = Some(at(&tz(), "2026-03-01 09:00"));
+        todo.description = Some("Semi-skimmed\n\n* not a heading".to_string());
+        let mut done = Todo::new("Done already".to_string(), None);
+        done.priority = Priority::Low;
+        done.mark_complete();
+
+        let doc = write(&[todo.clone(), done.clone()], &[category], &tz());
+        assert!(doc.contains("* TODO [#A] 🛒 Buy milk :Side_project:\n"));
+        assert!(doc.contains("DEADLINE: <2026-03-01 Sun 15:00> SCHEDULED: <2026-03-01 Sun 09:00>"));
+
+        let parsed = parse(&doc, &tz());
+        assert_eq!(parsed.len(), 2);
+        let milk = &parsed[0];
+        assert_eq!(milk.tags, vec!["Side_project"]);
+        assert!(milk.todo.is_same_todo(&todo));
+        assert_eq!(milk.todo.title, "Buy milk");
+        assert_eq!(milk.todo.icon.as_deref(), Some("🛒"));
+        assert_eq!(milk.todo.priority, Priority::High);
+        assert_eq!(milk.todo.due_date, todo.due_date);
+        assert_eq!(milk.todo.reminder_at, todo.reminder_at);
+        assert_eq!(milk.todo.description, todo.description);
+
+        assert!(parsed[1].todo.is_same_todo(&done));
+        assert!(parsed[1].todo.is_completed);
+        assert_eq!(parsed[1].todo.priority, Priority::Low);
+    }
+}
@@ -0,0 +1,880 @@
+//! Recurrence rule engine
+//!
+//! Implements the subset of RFC 5545 RRULEs used by todoee:
+//! `FREQ` (DAILY, WEEKLY, MONTHLY, YEARLY), `INTERVAL`, `BYDAY`, `UNTIL`
+//! and `COUNT`. Rules are expanded on wall-clock (naive) date-times so that
+//! a "9:30 every weekday" rule stays at 9:30 across DST changes; callers
+//! convert to and from their timezone.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{
+    DateTime, Datelike, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    Weekday,
+};
+
+use crate::error::{Result, TodoeeError};
+
+/// Number of consecutive periods without a match before expansion gives up.
+/// Guards against rules that can never produce another occurrence.
+const MAX_EMPTY_PERIODS: u32 = 1000;
+
+// ============================================================================
+// Rule Types
+// ============================================================================
+
+/// How often a rule repeats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A weekday in a `BYDAY` list, optionally with an ordinal (`2TU`, `-1FR`).
+///
+/// Ordinals are only meaningful for monthly rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByDay {
+    pub ordinal: Option<i32>,
+    pub weekday: Weekday,
+}
+
+/// A parsed recurrence rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<ByDay>,
+    pub until: Option<NaiveDateTime>,
+    pub count: Option<u32>,
+}
+
+impl RecurrenceRule {
+    /// Create a rule with the given frequency, repeating forever.
+    pub fn new(freq: Frequency) -> Self {
+        Self {
+            freq,
+            interval: 1,
+            by_day: Vec::new(),
+            until: None,
+            count: None,
+        }
+    }
+
+    /// Parse an RRULE string such as `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE`.
+    ///
+    /// A leading `RRULE:` prefix is accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TodoeeError::InvalidInput` for unknown or unsupported parts,
+    /// malformed values, or rules combining `COUNT` and `UNTIL`.
+    pub fn parse(input: &str) -> Result<Self> {
+        let body = input.trim();
+        let body = body
+            .strip_prefix("RRULE:")
+            .or_else(|| body.strip_prefix("rrule:"))
+            .unwrap_or(body);
+
+        let mut freq = None;
+        let mut rule = Self::new(Frequency::Daily);
+
+        for part in body.split(';').filter(|p| !p.trim().is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("expected KEY=VALUE, got '{}'", part)))?;
+            let value = value.trim();
+
+            match key.trim().to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(invalid(format!("unsupported FREQ '{}'", value))),
+                    })
+                }
+                "INTERVAL" => {
+                    rule.interval = value
+                        .parse()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| invalid(format!("invalid INTERVAL '{}'", value)))?
+                }
+                "COUNT" => {
+                    rule.count = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|n| *n > 0)
+                            .ok_or_else(|| invalid(format!("invalid COUNT '{}'", value)))?,
+                    )
+                }
+                "UNTIL" => rule.until = Some(parse_until(value)?),
+                "BYDAY" => {
+                    rule.by_day = value
+                        .split(',')
+                        .map(parse_by_day)
+                        .collect::<Result<Vec<_>>>()?
+                }
+                other => return Err(invalid(format!("unsupported rule part '{}'", other))),
+            }
+        }
+
+        rule.freq = freq.ok_or_else(|| invalid("missing FREQ".to_string()))?;
+
+        if rule.count.is_some() && rule.until.is_some() {
+            return Err(invalid("COUNT and UNTIL cannot be combined".to_string()));
+        }
+        let has_ordinal = rule.by_day.iter().any(|d| d.ordinal.is_some());
+        if has_ordinal && rule.freq != Frequency::Monthly {
+            return Err(invalid(
+                "BYDAY ordinals (e.g. 2TU) are only supported with FREQ=MONTHLY".to_string(),
+            ));
+        }
+        if !rule.by_day.is_empty() && rule.freq == Frequency::Yearly {
+            return Err(invalid(
+                "BYDAY is not supported with FREQ=YEARLY".to_string(),
+            ));
+        }
+
+        Ok(rule)
+    }
+
+    /// Iterate over occurrences starting at `dtstart` (wall-clock time).
+    ///
+    /// `dtstart` itself is yielded only if it matches the rule.
+    pub fn iter(&self, dtstart: NaiveDateTime) -> Occurrences<'_> {
+        Occurrences {
+            rule: self,
+            dtstart,
+            period: 0,
+            pending: Vec::new(),
+            emitted: 0,
+            done: false,
+        }
+    }
+
+    /// Occurrences in `[from, to)` for a rule anchored at `dtstart` in `tz`.
+    ///
+    /// Expansion happens on wall-clock time in `tz`; local times skipped by a
+    /// DST transition are dropped.
+    pub fn between<Tz: TimeZone>(
+        &self,
+        dtstart: &DateTime<Tz>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Vec<DateTime<Utc>> {
+        let tz = dtstart.timezone();
+        self.iter(dtstart.naive_local())
+            .filter_map(|naive| {
+                tz.from_local_datetime(&naive)
+                    .earliest()
+                    .map(|dt| dt.with_timezone(&Utc))
+            })
+            .take_while(|dt| *dt < to)
+            .filter(|dt| *dt >= from)
+            .collect()
+    }
+
+    /// First occurrence strictly after `after` for a rule anchored at `dtstart` in `tz`.
+    pub fn next_after<Tz: TimeZone>(
+        &self,
+        dtstart: &DateTime<Tz>,
+        after: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let tz = dtstart.timezone();
+        self.iter(dtstart.naive_local())
+            .filter_map(|naive| {
+                tz.from_local_datetime(&naive)
+                    .earliest()
+                    .map(|dt| dt.with_timezone(&Utc))
+            })
+            .find(|dt| *dt > after)
+    }
+
+    /// Candidate dates for the `period`-th period after `dtstart`, sorted.
+    fn period_dates(&self, dtstart: NaiveDate, period: u32) -> Option<Vec<NaiveDate>> {
+        let step = period.checked_mul(self.interval)?;
+        let mut dates = match self.freq {
+            Frequency::Daily => {
+                let date = dtstart.checked_add_signed(Duration::days(step as i64))?;
+                if self.by_day.is_empty() || self.matches_weekday(date) {
+                    vec![date]
+                } else {
+                    Vec::new()
+                }
+            }
+            Frequency::Weekly => {
+                let week_start =
+                    dtstart - Duration::days(dtstart.weekday().num_days_from_monday() as i64);
+                let week_start = week_start.checked_add_signed(Duration::weeks(step as i64))?;
+                if self.by_day.is_empty() {
+                    vec![
+                        week_start
+                            + Duration::days(dtstart.weekday().num_days_from_monday() as i64),
+                    ]
+                } else {
+                    self.by_day
+                        .iter()
+                        .map(|d| {
+                            week_start + Duration::days(d.weekday.num_days_from_monday() as i64)
+                        })
+                        .collect()
+                }
+            }
+            Frequency::Monthly => {
+                let month_start = dtstart.with_day(1)?.checked_add_months(Months::new(step))?;
+                if self.by_day.is_empty() {
+                    month_start.with_day(dtstart.day()).into_iter().collect()
+                } else {
+                    self.by_day
+                        .iter()
+                        .flat_map(|d| monthly_weekdays(month_start, *d))
+                        .collect()
+                }
+            }
+            Frequency::Yearly => {
+                let year = dtstart.year().checked_add(step as i32)?;
+                NaiveDate::from_ymd_opt(year, dtstart.month(), dtstart.day())
+                    .into_iter()
+                    .collect()
+            }
+        };
+        dates.sort();
+        dates.dedup();
+        Some(dates)
+    }
+
+    fn matches_weekday(&self, date: NaiveDate) -> bool {
+        self.by_day.iter().any(|d| d.weekday == date.weekday())
+    }
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = TodoeeError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    /// Formats the rule in canonical RRULE form (without the `RRULE:` prefix).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let freq = match self.freq {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+        write!(f, "FREQ={}", freq)?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<String> = self
+                .by_day
+                .iter()
+                .map(|d| {
+                    let code = weekday_code(d.weekday);
+                    match d.ordinal {
+                        Some(n) => format!("{}{}", n, code),
+                        None => code.to_string(),
+                    }
+                })
+                .collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%dT%H%M%SZ"))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Expansion
+// ============================================================================
+
+/// Iterator over the occurrences of a rule, in chronological order
+pub struct Occurrences<'a> {
+    rule: &'a RecurrenceRule,
+    dtstart: NaiveDateTime,
+    period: u32,
+    /// Occurrences of the current period not yet yielded, latest first
+    pending: Vec<NaiveDateTime>,
+    emitted: u32,
+    done: bool,
+}
+
+impl Iterator for Occurrences<'_> {
+    type Item = NaiveDateTime;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if let Some(count) = self.rule.count
+            && self.emitted >= count
+        {
+            self.done = true;
+            return None;
+        }
+
+        let mut empty_periods = 0;
+        while self.pending.is_empty() {
+            let time: NaiveTime = self.dtstart.time();
+            let Some(dates) = self.rule.period_dates(self.dtstart.date(), self.period) else {
+                self.done = true;
+                return None;
+            };
+            self.period += 1;
+
+            self.pending = dates
+                .into_iter()
+                .map(|d| d.and_time(time))
+                .filter(|dt| *dt >= self.dtstart)
+                .rev()
+                .collect();
+
+            if self.pending.is_empty() {
+                empty_periods += 1;
+                if empty_periods >= MAX_EMPTY_PERIODS {
+                    self.done = true;
+                    return None;
+                }
+            }
+        }
+
+        let next = self.pending.pop()?;
+        if let Some(until) = self.rule.until
+            && next > until
+        {
+            self.done = true;
+            return None;
+        }
+
+        self.emitted += 1;
+        Some(next)
+    }
+}
+
+/// Dates in the month starting at `month_start` matching a BYDAY entry.
+fn monthly_weekdays(month_start: NaiveDate, by_day: ByDay) -> Vec<NaiveDate> {
+    let next_month = month_start + Months::new(1);
+    let offset = (7 + by_day.weekday.num_days_from_monday() as i64
+        - month_start.weekday().num_days_from_monday() as i64)
+        % 7;
+    let all: Vec<NaiveDate> = (0..5)
+        .map(|week| month_start + Duration::days(offset + week * 7))
+        .filter(|d| *d < next_month)
+        .collect();
+
+    match by_day.ordinal {
+        None => all,
+        Some(n) if n > 0 => all.get(n as usize - 1).copied().into_iter().collect(),
+        Some(n) => all
+            .len()
+            .checked_sub(n.unsigned_abs() as usize)
+            .and_then(|i| all.get(i).copied())
+            .into_iter()
+            .collect(),
+    }
+}
+
+// ============================================================================
+// Parsing Helpers
+// ============================================================================
+
+fn invalid(message: String) -> TodoeeError {
+    TodoeeError::InvalidInput(format!("Invalid recurrence rule: {}", message))
+}
+
+fn parse_by_day(input: &str) -> Result<ByDay> {
+    let input = input.trim().to_ascii_uppercase();
+    if input.len() < 2 {
+        return Err(invalid(format!("invalid BYDAY '{}'", input)));
+    }
+    let (ordinal, code) = input.split_at(input.len() - 2);
+    let weekday = match code {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return Err(invalid(format!("invalid weekday '{}'", code))),
+    };
+    let ordinal = if ordinal.is_empty() {
+        None
+    } else {
+        let n: i32 = ordinal
+            .trim_start_matches('+')
+            .parse()
+            .map_err(|_| invalid(format!("invalid BYDAY ordinal '{}'", ordinal)))?;
+        if n == 0 || n.abs() > 5 {
+            return Err(invalid(format!("BYDAY ordinal out of range '{}'", ordinal)));
+        }
+        Some(n)
+    };
+    Ok(ByDay { ordinal, weekday })
+}
+
+/// Parse `UNTIL` as `YYYYMMDDTHHMMSS[Z]` or `YYYYMMDD` (inclusive of the whole day).
+fn parse_until(input: &str) -> Result<NaiveDateTime> {
+    let trimmed = input.trim_end_matches('Z');
+    if let Ok(dt) = NaiveDateTime::parse_from_str(trimmed, "%Y%m%dT%H%M%S") {
+        return Ok(dt);
+    }
+    NaiveDate::parse_from_str(trimmed, "%Y%m%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(23, 59, 59))
+        .ok_or_else(|| invalid(format!("invalid UNTIL '{}'", input)))
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dt(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, 0)
+            .unwrap()
+    }
+
+    fn take(rule: &str, start: NaiveDateTime, n: usize) -> Vec<NaiveDateTime> {
+        RecurrenceRule::parse(rule)
+            .unwrap()
+            .iter(start)
+            .take(n)
+            .collect()
+    }
+
+    // ---------------------------------------------------------------- parsing
+
+    #[test]
+    fn test_parse_minimal() {
+        let rule = RecurrenceRule::parse("FREQ=DAILY").unwrap();
+        assert_eq!(rule, RecurrenceRule::new(Frequency::Daily));
+    }
+
+    #[test]
+    fn test_parse_all_parts() {
+        let rule =
+            RecurrenceRule::parse("RRULE:FREQ=MONTHLY;INTERVAL=2;BYDAY=2TU,-1FR;COUNT=5").unwrap();
+        assert_eq!(rule.freq, Frequency::Monthly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(
+            rule.by_day,
+            vec![
+                ByDay {
+                    ordinal: Some(2),
+                    weekday: Weekday::Tue
+                },
+                ByDay {
+                    ordinal: Some(-1),
+                    weekday: Weekday::Fri
+                },
+            ]
+        );
+        assert_eq!(rule.count, Some(5));
+        assert!(rule.until.is_none());
+    }
+
+    #[test]
+    fn test_parse_is_case_insensitive() {
+        let rule = RecurrenceRule::parse("freq=weekly;byday=mo,we").unwrap();
+        assert_eq!(rule.freq, Frequency::Weekly);
+        assert_eq!(rule.by_day.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_until_formats() {
+        let rule = RecurrenceRule::parse("FREQ=DAILY;UNTIL=20250110T093000Z").unwrap();
+        assert_eq!(rule.until, Some(dt(2025, 1, 10, 9, 30)));
+
+        let rule = RecurrenceRule::parse("FREQ=DAILY;UNTIL=20250110").unwrap();
+        assert_eq!(
+            rule.until,
+            Some(
+                NaiveDate::from_ymd_opt(2025, 1, 10)
+                    .unwrap()
+                    .and_hms_opt(23, 59, 59)
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let bad = [
+            "",
+            "INTERVAL=2",
+            "FREQ=HOURLY",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;INTERVAL=x",
+            "FREQ=DAILY;COUNT=0",
+            "FREQ=DAILY;COUNT=3;UNTIL=20250101",
+            "FREQ=DAILY;UNTIL=tomorrow",
+            "FREQ=WEEKLY;BYDAY=XX",
+            "FREQ=WEEKLY;BYDAY=2MO",
+            "FREQ=MONTHLY;BYDAY=0MO",
+            "FREQ=MONTHLY;BYDAY=6MO",
+            "FREQ=YEARLY;BYDAY=MO",
+            "FREQ=DAILY;BYMONTH=1",
+            "FREQ",
+        ];
+        for input in bad {
+            assert!(
+                matches!(
+                    RecurrenceRule::parse(input),
+                    Err(TodoeeError::InvalidInput(_))
+                ),
+                "expected '{}' to be rejected",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let inputs = [
+            "FREQ=DAILY",
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE,FR",
+            "FREQ=MONTHLY;BYDAY=-1FR;COUNT=3",
+            "FREQ=YEARLY;UNTIL=20300101T000000Z",
+        ];
+        for input in inputs {
+            let rule = RecurrenceRule::parse(input).unwrap();
+            assert_eq!(rule.to_string(), input);
+            assert_eq!(rule.to_string().parse::<RecurrenceRule>().unwrap(), rule);
+        }
+    }
+
+    // ------------------------------------------------------------------ daily
+
+    #[test]
+    fn test_daily() {
+        let start = dt(2025, 1, 30, 9, 0);
+        assert_eq!(
+            take("FREQ=DAILY", start, 3),
+            vec![start, dt(2025, 1, 31, 9, 0), dt(2025, 2, 1, 9, 0)]
+        );
+    }
+
+    #[test]
+    fn test_daily_interval() {
+        let start = dt(2025, 1, 1, 9, 0);
+        assert_eq!(
+            take("FREQ=DAILY;INTERVAL=3", start, 3),
+            vec![start, dt(2025, 1, 4, 9, 0), dt(2025, 1, 7, 9, 0)]
+        );
+    }
+
+    #[test]
+    fn test_daily_byday_filters() {
+        // 2025-01-03 is a Friday
+        let start = dt(2025, 1, 3, 9, 30);
+        assert_eq!(
+            take("FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR", start, 3),
+            vec![start, dt(2025, 1, 6, 9, 30), dt(2025, 1, 7, 9, 30)]
+        );
+    }
+
+    // ----------------------------------------------------------------- weekly
+
+    #[test]
+    fn test_weekly_default_day() {
+        let start = dt(2025, 1, 1, 10, 0); // Wednesday
+        assert_eq!(
+            take("FREQ=WEEKLY", start, 3),
+            vec![start, dt(2025, 1, 8, 10, 0), dt(2025, 1, 15, 10, 0)]
+        );
+    }
+
+    #[test]
+    fn test_weekly_byday_skips_days_before_start() {
+        let start = dt(2025, 1, 1, 10, 0); // Wednesday
+        assert_eq!(
+            take("FREQ=WEEKLY;BYDAY=MO,WE,FR", start, 4),
+            vec![
+                start,
+                dt(2025, 1, 3, 10, 0),
+                dt(2025, 1, 6, 10, 0),
+                dt(2025, 1, 8, 10, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_weekly_interval_with_byday() {
+        let start = dt(2025, 1, 6, 8, 0); // Monday
+        assert_eq!(
+            take("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH", start, 4),
+            vec![
+                start,
+                dt(2025, 1, 9, 8, 0),
+                dt(2025, 1, 20, 8, 0),
+                dt(2025, 1, 23, 8, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_weekly_start_not_matching_byday() {
+        let start = dt(2025, 1, 4, 8, 0); // Saturday
+        assert_eq!(
+            take("FREQ=WEEKLY;BYDAY=MO", start, 2),
+            vec![dt(2025, 1, 6, 8, 0), dt(2025, 1, 13, 8, 0)]
+        );
+    }
+
+    #[test]
+    fn test_weekly_across_year_boundary() {
+        let start = dt(2024, 12, 30, 8, 0); // Monday
+        assert_eq!(
+            take("FREQ=WEEKLY;BYDAY=MO,SU", start, 3),
+            vec![start, dt(2025, 1, 5, 8, 0), dt(2025, 1, 6, 8, 0)]
+        );
+    }
+
+    // ---------------------------------------------------------------- monthly
+
+    #[test]
+    fn test_monthly_by_month_day() {
+        let start = dt(2025, 1, 15, 12, 0);
+        assert_eq!(
+            take("FREQ=MONTHLY", start, 3),
+            vec![start, dt(2025, 2, 15, 12, 0), dt(2025, 3, 15, 12, 0)]
+        );
+    }
+
+    #[test]
+    fn test_monthly_skips_short_months() {
+        let start = dt(2025, 1, 31, 12, 0);
+        assert_eq!(
+            take("FREQ=MONTHLY", start, 3),
+            vec![start, dt(2025, 3, 31, 12, 0), dt(2025, 5, 31, 12, 0)]
+        );
+    }
+
+    #[test]
+    fn test_monthly_interval() {
+        let start = dt(2025, 11, 5, 12, 0);
+        assert_eq!(
+            take("FREQ=MONTHLY;INTERVAL=3", start, 3),
+            vec![start, dt(2026, 2, 5, 12, 0), dt(2026, 5, 5, 12, 0)]
+        );
+    }
+
+    #[test]
+    fn test_monthly_nth_weekday() {
+        let start = dt(2025, 1, 1, 9, 0);
+        assert_eq!(
+            take("FREQ=MONTHLY;BYDAY=2TU", start, 3),
+            vec![
+                dt(2025, 1, 14, 9, 0),
+                dt(2025, 2, 11, 9, 0),
+                dt(2025, 3, 11, 9, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monthly_last_weekday() {
+        let start = dt(2025, 1, 1, 17, 0);
+        assert_eq!(
+            take("FREQ=MONTHLY;BYDAY=-1FR", start, 3),
+            vec![
+                dt(2025, 1, 31, 17, 0),
+                dt(2025, 2, 28, 17, 0),
+                dt(2025, 3, 28, 17, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monthly_fifth_weekday_skips_months_without_one() {
+        let start = dt(2025, 1, 1, 9, 0);
+        // Fifth Wednesdays in 2025: Jan 29, Apr 30, Jul 30
+        assert_eq!(
+            take("FREQ=MONTHLY;BYDAY=5WE", start, 3),
+            vec![
+                dt(2025, 1, 29, 9, 0),
+                dt(2025, 4, 30, 9, 0),
+                dt(2025, 7, 30, 9, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monthly_every_weekday_of_month() {
+        let start = dt(2025, 2, 1, 9, 0);
+        let mondays = take("FREQ=MONTHLY;BYDAY=MO", start, 5);
+        assert_eq!(
+            mondays,
+            vec![
+                dt(2025, 2, 3, 9, 0),
+                dt(2025, 2, 10, 9, 0),
+                dt(2025, 2, 17, 9, 0),
+                dt(2025, 2, 24, 9, 0),
+                dt(2025, 3, 3, 9, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monthly_multiple_ordinals_sorted() {
+        let start = dt(2025, 1, 1, 9, 0);
+        assert_eq!(
+            take("FREQ=MONTHLY;BYDAY=-1MO,1MO", start, 2),
+            vec![dt(2025, 1, 6, 9, 0), dt(2025, 1, 27, 9, 0)]
+        );
+    }
+
+    // ----------------------------------------------------------------- yearly
+
+    #[test]
+    fn test_yearly() {
+        let start = dt(2025, 3, 2, 8, 0);
+        assert_eq!(
+            take("FREQ=YEARLY;INTERVAL=2", start, 3),
+            vec![start, dt(2027, 3, 2, 8, 0), dt(2029, 3, 2, 8, 0)]
+        );
+    }
+
+    #[test]
+    fn test_yearly_leap_day() {
+        let start = dt(2024, 2, 29, 8, 0);
+        assert_eq!(
+            take("FREQ=YEARLY", start, 3),
+            vec![start, dt(2028, 2, 29, 8, 0), dt(2032, 2, 29, 8, 0)]
+        );
+    }
+
+    // ------------------------------------------------------- count and until
+
+    #[test]
+    fn test_count_limits_occurrences() {
+        let start = dt(2025, 1, 1, 9, 0);
+        let rule = RecurrenceRule::parse("FREQ=DAILY;COUNT=3").unwrap();
+        assert_eq!(rule.iter(start).count(), 3);
+    }
+
+    #[test]
+    fn test_count_with_byday_counts_matches_only() {
+        let start = dt(2025, 1, 3, 9, 0); // Friday
+        let all: Vec<_> = RecurrenceRule::parse("FREQ=WEEKLY;BYDAY=MO,FR;COUNT=3")
+            .unwrap()
+            .iter(start)
+            .collect();
+        assert_eq!(
+            all,
+            vec![start, dt(2025, 1, 6, 9, 0), dt(2025, 1, 10, 9, 0)]
+        );
+    }
+
+    #[test]
+    fn test_until_is_inclusive() {
+        let start = dt(2025, 1, 1, 9, 0);
+        let all: Vec<_> = RecurrenceRule::parse("FREQ=DAILY;UNTIL=20250103T090000Z")
+            .unwrap()
+            .iter(start)
+            .collect();
+        assert_eq!(all, vec![start, dt(2025, 1, 2, 9, 0), dt(2025, 1, 3, 9, 0)]);
+    }
+
+    #[test]
+    fn test_until_date_only_covers_whole_day() {
+        let start = dt(2025, 1, 1, 22, 0);
+        let rule = RecurrenceRule::parse("FREQ=DAILY;UNTIL=20250102").unwrap();
+        assert_eq!(rule.iter(start).count(), 2);
+    }
+
+    #[test]
+    fn test_until_before_start_yields_nothing() {
+        let start = dt(2025, 1, 5, 9, 0);
+        let rule = RecurrenceRule::parse("FREQ=DAILY;UNTIL=20250101").unwrap();
+        assert_eq!(rule.iter(start).count(), 0);
+    }
+
+    #[test]
+    fn test_rare_rule_searches_ahead() {
+        // February only has five Mondays in leap years starting on a Monday
+        let start = dt(2025, 2, 1, 9, 0);
+        let rule = RecurrenceRule::parse("FREQ=MONTHLY;INTERVAL=12;BYDAY=5MO").unwrap();
+        assert_eq!(rule.iter(start).next(), Some(dt(2044, 2, 29, 9, 0)));
+    }
+
+    // ------------------------------------------------------------ tz helpers
+
+    #[test]
+    fn test_between_window() {
+        let start = Utc.from_utc_datetime(&dt(2025, 1, 1, 9, 0));
+        let rule = RecurrenceRule::parse("FREQ=DAILY").unwrap();
+        let from = Utc.from_utc_datetime(&dt(2025, 1, 3, 0, 0));
+        let to = Utc.from_utc_datetime(&dt(2025, 1, 5, 9, 0));
+        assert_eq!(
+            rule.between(&start, from, to),
+            vec![
+                Utc.from_utc_datetime(&dt(2025, 1, 3, 9, 0)),
+                Utc.from_utc_datetime(&dt(2025, 1, 4, 9, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_between_keeps_wall_clock_in_offset_timezone() {
+        let tz = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+        let start = tz.from_local_datetime(&dt(2025, 1, 6, 0, 30)).unwrap(); // Monday local
+        let rule = RecurrenceRule::parse("FREQ=WEEKLY;BYDAY=MO").unwrap();
+        let hits = rule.between(
+            &start,
+            start.with_timezone(&Utc),
+            start.with_timezone(&Utc) + Duration::weeks(2),
+        );
+        // Sunday 22:30 UTC, but Monday 00:30 local
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().all(|d| d.weekday() == Weekday::Sun));
+    }
+
+    #[test]
+    fn test_next_after() {
+        let start = Utc.from_utc_datetime(&dt(2025, 1, 1, 9, 0));
+        let rule = RecurrenceRule::parse("FREQ=WEEKLY;COUNT=2").unwrap();
+        assert_eq!(
+            rule.next_after(&start, start),
+            Some(Utc.from_utc_datetime(&dt(2025, 1, 8, 9, 0)))
+        );
+        assert_eq!(
+            rule.next_after(&start, Utc.from_utc_datetime(&dt(2025, 1, 8, 9, 0))),
+            None
+        );
+    }
+}
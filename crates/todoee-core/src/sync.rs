@@ -1,15 +1,21 @@
 //! Sync service for bi-directional synchronization between local and remote databases.
 //!
 //! This module provides `SyncService` which orchestrates sync between the local SQLite
-//! database and the remote PostgreSQL database (Neon).
+//! database and a [`SyncBackend`], by default the remote PostgreSQL database (Neon).
 
 use crate::{
     Result as TodoeeResult, TodoeeError,
-    config::Config,
-    db::{LocalDb, RemoteDb},
-    models::SyncStatus,
+    auth::{self, Auth},
+    config::{Config, DatabaseConfig},
+    conflict::Conflict,
+    db::{FileBackend, LocalDb, RemoteDb, SyncBackend, Upserted},
+    models::{Device, Project, SyncRun, SyncStatus, Todo, User},
+    net,
 };
 use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::time::Duration;
+use uuid::Uuid;
 
 /// Result of a sync operation, containing counts of items processed.
 #[derive(Debug, Default)]
@@ -18,21 +24,93 @@ pub struct SyncResult {
     pub uploaded: usize,
     /// Number of todos downloaded from remote.
     pub downloaded: usize,
-    /// Number of conflicts encountered (local wins when local is newer).
+    /// Number of todos changed both here and remotely since the last sync,
+    /// kept for review with both versions instead of uploaded.
     pub conflicts: usize,
+    /// IDs of the todos counted in `conflicts`.
+    pub conflict_ids: Vec<Uuid>,
+    /// Number of local deletions pushed to remote.
+    pub deletions_uploaded: usize,
+    /// Number of todos deleted locally because they were deleted remotely.
+    pub deletions_downloaded: usize,
+    /// Number of todos skipped because their ID belongs to a different todo on
+    /// the other side. Local ones stay pending.
+    pub id_collisions: usize,
+    /// Number of local categories adopted into an existing remote category of the same name.
+    pub categories_merged: usize,
+    /// Number of local projects adopted into an existing remote project of the same name.
+    pub projects_merged: usize,
+}
+
+/// A successful [`SyncService::login`].
+#[derive(Debug, Clone)]
+pub struct Login {
+    pub user: User,
+    /// Token to keep in `auth.json`; the remote only stores its hash.
+    pub session_token: String,
+    /// Whether this login set the user's password.
+    pub new_account: bool,
+    /// Todos without an owner that the new account took over.
+    pub claimed: u64,
+}
+
+/// The signed-in user a [`SyncService`] syncs as.
+#[derive(Debug, Clone)]
+struct Session {
+    user_id: Uuid,
+    token_hash: String,
+}
+
+/// Local view of sync state, for the TUI sync health panel.
+#[derive(Debug, Clone, Default)]
+pub struct SyncHealth {
+    /// When the last successful sync finished.
+    pub last_success: Option<DateTime<Utc>>,
+    /// The most recent sync attempt, successful or not.
+    pub last_run: Option<SyncRun>,
+    /// Local changes waiting to be uploaded.
+    pub pending_uploads: u64,
+}
+
+impl SyncHealth {
+    /// Read the sync health from the local database. Works offline.
+    pub async fn load(local: &LocalDb) -> TodoeeResult<Self> {
+        let local_err = |e: anyhow::Error| {
+            TodoeeError::Database(sqlx::Error::Protocol(format!(
+                "Failed to read sync health: {}",
+                e
+            )))
+        };
+        Ok(Self {
+            last_success: local.last_successful_sync().await.map_err(local_err)?,
+            last_run: local.last_sync_run().await.map_err(local_err)?,
+            pending_uploads: local.count_pending_uploads().await.map_err(local_err)?,
+        })
+    }
+
+    /// Error from the most recent sync, if it failed.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_run.as_ref().and_then(|run| run.error.as_deref())
+    }
 }
 
 /// Service for bi-directional sync between local and remote databases.
 pub struct SyncService {
     local: LocalDb,
-    remote: Option<RemoteDb>,
+    remote: Option<Box<dyn SyncBackend>>,
+    /// Set after `todoee login`: sync only exchanges this user's data.
+    session: Option<Session>,
+    /// Remote todos fetched and applied per batch while downloading.
+    batch_size: u32,
 }
 
 impl SyncService {
     /// Create a new SyncService from the given configuration.
     ///
-    /// This initializes the local database and optionally connects to the remote
-    /// database if `NEON_DATABASE_URL` is configured.
+    /// This initializes the local database and optionally opens the sync
+    /// directory if `database.sync_dir` is set, or else connects to the remote
+    /// database if `NEON_DATABASE_URL` is configured. The session from
+    /// `todoee login`, if any, is read from `auth.json`.
     pub async fn new(config: &Config) -> TodoeeResult<Self> {
         let local = LocalDb::new(
             &config
@@ -46,13 +124,34 @@ impl SyncService {
             .await
             .map_err(|e| TodoeeError::Config(format!("Failed to run migrations: {}", e)))?;
 
-        let remote = if let Some(url) = config.get_database_url() {
-            Some(RemoteDb::new(&url).await?)
+        let remote: Option<Box<dyn SyncBackend>> = if let Some(dir) = config.sync_dir() {
+            let device_id = local
+                .device_id()
+                .await
+                .map_err(|e| TodoeeError::Config(format!("Failed to read device ID: {}", e)))?;
+            Some(Box::new(FileBackend::open(dir, device_id)?))
+        } else if let Some(url) = config.get_database_url() {
+            // Fail immediately when offline rather than waiting for the pool to time out
+            net::check_database_reachable(&config.network, &url).await?;
+            Some(Box::new(RemoteDb::connect(&url, &config.network).await?))
         } else {
             None
         };
+        let session = Auth::load()
+            .map_err(|e| TodoeeError::Config(format!("Failed to read auth.json: {:#}", e)))?
+            .and_then(|auth| {
+                Some(Session {
+                    user_id: auth.user_id,
+                    token_hash: auth::token_hash(&auth.session_token?),
+                })
+            });
 
-        Ok(Self { local, remote })
+        Ok(Self {
+            local,
+            remote,
+            session,
+            batch_size: config.database.sync_batch_size.max(1),
+        })
     }
 
     /// Create a SyncService with a pre-existing LocalDb (useful for testing).
@@ -60,6 +159,19 @@ impl SyncService {
         Self {
             local,
             remote: None,
+            session: None,
+            batch_size: DatabaseConfig::default().sync_batch_size,
+        }
+    }
+
+    /// Create a SyncService that syncs `local` with `backend` instead of the
+    /// configured remote database.
+    pub fn with_backend(local: LocalDb, backend: Box<dyn SyncBackend>) -> Self {
+        Self {
+            local,
+            remote: Some(backend),
+            session: None,
+            batch_size: DatabaseConfig::default().sync_batch_size,
         }
     }
 
@@ -77,20 +189,65 @@ impl SyncService {
     ///
     /// This:
     /// 1. Uploads all local changes (pending sync items) to remote
-    /// 2. Downloads all remote changes since last sync
-    /// 3. Resolves conflicts using last-write-wins strategy
+    /// 2. Pushes local deletions and applies remote deletions since last sync
+    /// 3. Downloads all remote changes since last sync
+    /// 4. Resolves conflicts using last-write-wins strategy, except for todos
+    ///    changed on both sides since the last sync: those are kept for
+    ///    review (see [`crate::conflict`])
+    ///
+    /// Every attempt is recorded locally for [`SyncService::health`].
     ///
     /// # Errors
     ///
     /// Returns an error if cloud sync is not configured or if any database
     /// operations fail.
     pub async fn sync(&self) -> TodoeeResult<SyncResult> {
-        let remote = self.remote.as_ref().ok_or_else(|| {
-            TodoeeError::Config(
-                "Cloud sync not configured. Set NEON_DATABASE_URL environment variable."
-                    .to_string(),
-            )
-        })?;
+        self.sync_with_progress(|_| {}).await
+    }
+
+    /// Like [`SyncService::sync`], calling `on_download` with the number of
+    /// remote todos processed so far after each downloaded batch.
+    ///
+    /// Remote changes are fetched in batches of `database.sync_batch_size`
+    /// and applied as they arrive, so memory use stays flat on a large remote.
+    pub async fn sync_with_progress(
+        &self,
+        on_download: impl FnMut(usize),
+    ) -> TodoeeResult<SyncResult> {
+        let remote = self.remote()?;
+        let outcome = self.sync_with(remote, on_download).await;
+
+        let run = match &outcome {
+            Ok(result) => SyncRun {
+                finished_at: Utc::now(),
+                uploaded: result.uploaded,
+                downloaded: result.downloaded,
+                conflict_ids: result.conflict_ids.clone(),
+                error: None,
+            },
+            Err(e) => SyncRun {
+                finished_at: Utc::now(),
+                uploaded: 0,
+                downloaded: 0,
+                conflict_ids: Vec::new(),
+                error: Some(e.to_string()),
+            },
+        };
+        if let Err(e) = self.local.record_sync_run(&run).await {
+            tracing::warn!(error = %e, "Failed to record sync run");
+        }
+
+        outcome
+    }
+
+    async fn sync_with(
+        &self,
+        remote: &dyn SyncBackend,
+        mut on_download: impl FnMut(usize),
+    ) -> TodoeeResult<SyncResult> {
+        // 0. Refuse to sync from a revoked device or an ended session
+        self.check_device(remote).await?;
+        let owner = self.check_session(remote).await?;
 
         let mut result = SyncResult::default();
 
@@ -102,10 +259,29 @@ impl SyncService {
             )))
         })?;
 
-        for category in pending_categories {
-            remote
-                .upsert_category(&category, chrono::Utc::now())
-                .await?;
+        for mut category in pending_categories {
+            if let Some(owner) = owner {
+                category.user_id = owner;
+            }
+            match remote.upsert_category(&category).await? {
+                Upserted::Stored => {}
+                Upserted::NameTaken(remote_id) => {
+                    // The name already exists remotely under another ID: adopt that ID
+                    if let Some(remote_id) = remote_id {
+                        self.local
+                            .rekey_category(category.id, remote_id)
+                            .await
+                            .map_err(|e| {
+                                TodoeeError::Database(sqlx::Error::Protocol(format!(
+                                    "Failed to merge category: {}",
+                                    e
+                                )))
+                            })?;
+                        result.categories_merged += 1;
+                    }
+                    continue;
+                }
+            }
             self.local
                 .mark_category_synced(category.id)
                 .await
@@ -117,6 +293,48 @@ impl SyncService {
                 })?;
         }
 
+        // 1.5. Upload pending projects, which todos reference too
+        let pending_projects = self.local.list_pending_projects().await.map_err(|e| {
+            TodoeeError::Database(sqlx::Error::Protocol(format!(
+                "Failed to list pending projects: {}",
+                e
+            )))
+        })?;
+
+        for mut project in pending_projects {
+            if let Some(owner) = owner {
+                project.user_id = owner;
+            }
+            match remote.upsert_project(&project).await? {
+                Upserted::Stored => {}
+                Upserted::NameTaken(remote_id) => {
+                    // The name already exists remotely under another ID: adopt that ID
+                    if let Some(remote_id) = remote_id {
+                        self.local
+                            .rekey_project(project.id, remote_id)
+                            .await
+                            .map_err(|e| {
+                                TodoeeError::Database(sqlx::Error::Protocol(format!(
+                                    "Failed to merge project: {}",
+                                    e
+                                )))
+                            })?;
+                        result.projects_merged += 1;
+                    }
+                    continue;
+                }
+            }
+            self.local
+                .mark_project_synced(project.id)
+                .await
+                .map_err(|e| {
+                    TodoeeError::Database(sqlx::Error::Protocol(format!(
+                        "Failed to mark project synced: {}",
+                        e
+                    )))
+                })?;
+        }
+
         // 2. Upload local todo changes (existing code continues...)
         let pending = self.local.list_pending_sync().await.map_err(|e| {
             TodoeeError::Database(sqlx::Error::Protocol(format!(
@@ -125,8 +343,29 @@ impl SyncService {
             )))
         })?;
 
-        for todo in pending {
-            remote.upsert_todo(&todo).await?;
+        let conflicted = self
+            .save_conflicts(remote, owner, &pending, &mut result)
+            .await?;
+        // Todos whose ID is taken by a different remote todo stay pending
+        let mut collided = HashSet::new();
+        for mut todo in pending {
+            if conflicted.contains(&todo.id) {
+                continue;
+            }
+            if owner.is_some() {
+                todo.user_id = owner;
+            }
+            if !remote.upsert_todo(&todo).await? {
+                // Either the remote copy is newer, which the download brings
+                // in, or the ID belongs to a different todo
+                let stored = remote.get_todos(owner, &[todo.id]).await?;
+                if !stored.iter().any(|theirs| theirs.is_same_todo(&todo)) {
+                    tracing::warn!(todo_id = %todo.id, "Local todo ID collides with a different remote todo");
+                    result.id_collisions += 1;
+                    collided.insert(todo.id);
+                    continue;
+                }
+            }
             self.local.mark_synced(todo.id).await.map_err(|e| {
                 TodoeeError::Database(sqlx::Error::Protocol(format!(
                     "Failed to mark synced: {}",
@@ -146,7 +385,7 @@ impl SyncService {
 
         for id in deleted_ids {
             // Soft-delete in remote (ignore errors if already deleted or doesn't exist)
-            if let Err(_e) = remote.soft_delete_todo(id).await {
+            if let Err(_e) = remote.soft_delete_todo(owner, id).await {
                 tracing::warn!(todo_id = %id, "Failed to sync deletion to remote");
             }
             self.local.mark_deletion_synced(id).await.map_err(|e| {
@@ -155,63 +394,411 @@ impl SyncService {
                     e
                 )))
             })?;
+            result.deletions_uploaded += 1;
         }
 
-        // 3. Download remote changes
+        // 2.75. Apply remote deletions locally
         let last_sync = self.get_last_sync_time().await;
-        let remote_changes = remote.get_todos_since(last_sync).await?;
-
-        for remote_todo in remote_changes {
-            match self.local.get_todo(remote_todo.id).await {
-                Ok(Some(local_todo)) => {
-                    // Conflict resolution: last-write-wins
-                    if remote_todo.updated_at > local_todo.updated_at {
-                        let mut updated = remote_todo.clone();
-                        updated.sync_status = SyncStatus::Synced;
-                        self.local.update_todo(&updated).await.map_err(|e| {
-                            TodoeeError::Database(sqlx::Error::Protocol(format!(
-                                "Failed to update todo: {}",
-                                e
-                            )))
-                        })?;
-                        result.downloaded += 1;
-                    } else if local_todo.sync_status == SyncStatus::Pending {
-                        // Local is newer and has pending changes - count as conflict
-                        result.conflicts += 1;
-                    }
+        let remote_deleted = remote.get_deleted_todo_ids_since(owner, last_sync).await?;
+        result.deletions_downloaded = self.apply_remote_deletions(&remote_deleted).await?;
+
+        // 2.8. Download categories the local database doesn't have, so the
+        // todos below that reference them can be stored
+        let local_categories = self.local.list_categories().await.map_err(|e| {
+            TodoeeError::Database(sqlx::Error::Protocol(format!(
+                "Failed to list categories: {}",
+                e
+            )))
+        })?;
+        for category in remote.get_categories_since(owner, last_sync).await? {
+            if local_categories
+                .iter()
+                .any(|c| c.id == category.id || c.name == category.name)
+            {
+                continue;
+            }
+            self.local.create_category(&category).await.map_err(|e| {
+                TodoeeError::Database(sqlx::Error::Protocol(format!(
+                    "Failed to create category: {}",
+                    e
+                )))
+            })?;
+        }
+
+        // 2.9. Download projects before the todos that reference them
+        for remote_project in remote.get_projects_since(owner, last_sync).await? {
+            self.apply_remote_project(remote_project).await?;
+        }
+
+        // 3. Download remote changes in batches, applying each before fetching the next
+        let mut after = None;
+        let mut processed = 0;
+        loop {
+            let page = remote
+                .get_todos_page(owner, last_sync, after, self.batch_size)
+                .await?;
+            let is_last = page.len() < self.batch_size as usize;
+            after = page.last().map(|t| (t.updated_at, t.id));
+            processed += page.len();
+
+            for remote_todo in page {
+                // Already counted when the local todo failed to upload
+                if collided.contains(&remote_todo.id) {
+                    continue;
                 }
-                Ok(None) => {
-                    // Check if this was locally deleted - if so, skip re-downloading
-                    let is_deleted = self
-                        .local
-                        .is_locally_deleted(remote_todo.id)
-                        .await
-                        .unwrap_or(false);
-
-                    if is_deleted {
-                        // Skip - this was intentionally deleted locally
-                        continue;
-                    }
+                self.apply_remote_todo(remote_todo, &mut result).await?;
+            }
+            on_download(processed);
+
+            if is_last {
+                break;
+            }
+        }
 
-                    // New remote todo - download it
-                    let mut new_todo = remote_todo.clone();
-                    new_todo.sync_status = SyncStatus::Synced;
-                    self.local.create_todo(&new_todo).await.map_err(|e| {
+        Ok(result)
+    }
+
+    /// Store a conflict for each pending todo that was also changed remotely
+    /// since the last successful sync, so neither edit overwrites the other.
+    /// Returns their IDs.
+    ///
+    /// Before a first sync there is nothing to compare with and the newer
+    /// edit wins as usual.
+    async fn save_conflicts(
+        &self,
+        remote: &dyn SyncBackend,
+        owner: Option<Uuid>,
+        pending: &[Todo],
+        result: &mut SyncResult,
+    ) -> TodoeeResult<HashSet<Uuid>> {
+        let local_err = |e: anyhow::Error| {
+            TodoeeError::Database(sqlx::Error::Protocol(format!(
+                "Failed to save sync conflict: {}",
+                e
+            )))
+        };
+        let mut conflicted = HashSet::new();
+        let Some(last_success) = self.local.last_successful_sync().await.map_err(local_err)? else {
+            return Ok(conflicted);
+        };
+
+        for batch in pending.chunks(self.batch_size as usize) {
+            let ids: Vec<Uuid> = batch.iter().map(|t| t.id).collect();
+            for theirs in remote.get_todos(owner, &ids).await? {
+                let Some(ours) = batch.iter().find(|t| t.is_same_todo(&theirs)) else {
+                    continue;
+                };
+                if theirs.updated_at <= last_success {
+                    continue;
+                }
+                let conflict = Conflict {
+                    local: ours.clone(),
+                    remote: theirs,
+                    detected_at: Utc::now(),
+                };
+                if conflict.fields().is_empty() {
+                    continue;
+                }
+                self.local
+                    .save_conflict(&conflict.remote)
+                    .await
+                    .map_err(local_err)?;
+                result.conflicts += 1;
+                result.conflict_ids.push(ours.id);
+                conflicted.insert(ours.id);
+            }
+        }
+        Ok(conflicted)
+    }
+
+    /// Apply one downloaded project locally using last-write-wins.
+    async fn apply_remote_project(&self, remote_project: Project) -> TodoeeResult<()> {
+        let local_err = |e: anyhow::Error| {
+            TodoeeError::Database(sqlx::Error::Protocol(format!(
+                "Failed to apply remote project: {}",
+                e
+            )))
+        };
+        match self
+            .local
+            .get_project(remote_project.id)
+            .await
+            .map_err(local_err)?
+        {
+            Some(local_project) if remote_project.updated_at > local_project.updated_at => {
+                self.local
+                    .update_project(&remote_project)
+                    .await
+                    .map_err(local_err)?;
+            }
+            Some(_) => {}
+            None => {
+                self.local
+                    .create_project(&remote_project)
+                    .await
+                    .map_err(local_err)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply one downloaded todo locally using last-write-wins.
+    async fn apply_remote_todo(
+        &self,
+        remote_todo: Todo,
+        result: &mut SyncResult,
+    ) -> TodoeeResult<()> {
+        match self.local.get_todo(remote_todo.id).await {
+            Ok(Some(local_todo)) if !local_todo.is_same_todo(&remote_todo) => {
+                // Same ID but a different todo: keep both sides untouched
+                tracing::warn!(todo_id = %remote_todo.id, "Remote todo ID collides with a different local todo");
+                result.id_collisions += 1;
+            }
+            Ok(Some(local_todo)) if local_todo.sync_status == SyncStatus::Conflict => {
+                // Awaiting review: only refresh the remote side it is shown against
+                let stored = self.local.get_conflict(local_todo.id).await.map_err(|e| {
+                    TodoeeError::Database(sqlx::Error::Protocol(format!(
+                        "Failed to read conflict: {}",
+                        e
+                    )))
+                })?;
+                if stored.is_none_or(|c| remote_todo.updated_at > c.remote.updated_at) {
+                    self.local.save_conflict(&remote_todo).await.map_err(|e| {
                         TodoeeError::Database(sqlx::Error::Protocol(format!(
-                            "Failed to create todo: {}",
+                            "Failed to save sync conflict: {}",
+                            e
+                        )))
+                    })?;
+                }
+            }
+            Ok(Some(local_todo)) => {
+                // Conflict resolution: last-write-wins
+                if remote_todo.updated_at > local_todo.updated_at {
+                    let mut updated = remote_todo.clone();
+                    updated.sync_status = SyncStatus::Synced;
+                    self.local.update_synced_todo(&updated).await.map_err(|e| {
+                        TodoeeError::Database(sqlx::Error::Protocol(format!(
+                            "Failed to update todo: {}",
                             e
                         )))
                     })?;
                     result.downloaded += 1;
                 }
-                Err(_e) => {
-                    // Log error but continue syncing other todos
-                    tracing::warn!(todo_id = %remote_todo.id, "Failed to fetch local todo during sync");
+            }
+            Ok(None) => {
+                // Check if this was locally deleted - if so, skip re-downloading
+                let is_deleted = self
+                    .local
+                    .is_locally_deleted(remote_todo.id)
+                    .await
+                    .unwrap_or(false);
+
+                if is_deleted {
+                    // Skip - this was intentionally deleted locally
+                    return Ok(());
                 }
+
+                // New remote todo - download it
+                let mut new_todo = remote_todo.clone();
+                new_todo.sync_status = SyncStatus::Synced;
+                self.local
+                    .create_synced_todo(&new_todo)
+                    .await
+                    .map_err(|e| {
+                        TodoeeError::Database(sqlx::Error::Protocol(format!(
+                            "Failed to create todo: {}",
+                            e
+                        )))
+                    })?;
+                result.downloaded += 1;
+            }
+            Err(_e) => {
+                // Log error but continue syncing other todos
+                tracing::warn!(todo_id = %remote_todo.id, "Failed to fetch local todo during sync");
             }
         }
+        Ok(())
+    }
 
-        Ok(result)
+    /// Delete todos locally that were deleted on remote.
+    ///
+    /// Deletions are recorded as already synced so they are not pushed back.
+    /// IDs already deleted locally are skipped. Returns the number of todos removed.
+    async fn apply_remote_deletions(&self, ids: &[Uuid]) -> TodoeeResult<usize> {
+        let mut removed = 0;
+
+        for &id in ids {
+            let already_deleted = self.local.is_locally_deleted(id).await.unwrap_or(false);
+            if already_deleted {
+                continue;
+            }
+
+            let was_present = self.local.apply_remote_deletion(id).await.map_err(|e| {
+                TodoeeError::Database(sqlx::Error::Protocol(format!(
+                    "Failed to apply remote deletion: {}",
+                    e
+                )))
+            })?;
+            if was_present {
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Last sync time, pending upload count and the outcome of the last run.
+    pub async fn health(&self) -> TodoeeResult<SyncHealth> {
+        SyncHealth::load(&self.local).await
+    }
+
+    /// Round-trip time of a trivial query against the remote database.
+    pub async fn ping(&self) -> TodoeeResult<Duration> {
+        self.remote()?.ping().await
+    }
+
+    /// The ID this device syncs as.
+    pub async fn device_id(&self) -> TodoeeResult<Uuid> {
+        self.local.device_id().await.map_err(|e| {
+            TodoeeError::Database(sqlx::Error::Protocol(format!(
+                "Failed to read device ID: {}",
+                e
+            )))
+        })
+    }
+
+    /// List the devices registered for sync; once logged in, only the
+    /// user's own.
+    pub async fn list_devices(&self) -> TodoeeResult<Vec<Device>> {
+        let remote = self.remote()?;
+        let owner = self.check_session(remote).await?;
+        remote.list_devices(owner).await
+    }
+
+    /// Revoke a device so it can no longer sync. Returns false if it was
+    /// unknown or already revoked.
+    ///
+    /// Revocation is enforced by todoee itself: anyone holding the database URL
+    /// still has access until its password is rotated.
+    pub async fn revoke_device(&self, id: Uuid) -> TodoeeResult<bool> {
+        let remote = self.remote()?;
+        let owner = self.check_session(remote).await?;
+        remote.revoke_device(owner, id).await
+    }
+
+    /// The schema version the remote was migrated to when connecting.
+    pub async fn remote_schema_version(&self) -> TodoeeResult<i32> {
+        self.remote()?.schema_version().await
+    }
+
+    /// Register this device and associate it with the remote user `email`,
+    /// creating the user if needed.
+    pub async fn bootstrap_user(&self, email: &str) -> TodoeeResult<User> {
+        let remote = self.remote()?;
+        self.check_device(remote).await?;
+        let user = remote.ensure_user(email).await?;
+        remote.link_device(self.device_id().await?, user.id).await?;
+        Ok(user)
+    }
+
+    /// Whether `email` has an account, i.e. has set a password.
+    pub async fn has_account(&self, email: &str) -> TodoeeResult<bool> {
+        let remote = self.remote()?;
+        match remote.find_user(email).await? {
+            Some(user) => Ok(remote.password_hash(user.id).await?.is_some()),
+            None => Ok(false),
+        }
+    }
+
+    /// Sign this device in as `email` and start a session.
+    ///
+    /// A user without a password gets `password` as theirs, creating the
+    /// account. The first account also takes over the todos, categories and
+    /// projects synced before accounts existed.
+    pub async fn login(&self, email: &str, password: &str) -> TodoeeResult<Login> {
+        let remote = self.remote()?;
+        self.check_device(remote).await?;
+        let device_id = self.device_id().await?;
+        let auth_err = |e: anyhow::Error| TodoeeError::Auth(format!("{:#}", e));
+
+        let user = match remote.find_user(email).await? {
+            Some(user) => user,
+            None => remote.ensure_user(email).await?,
+        };
+        let (new_account, claimed) = match remote.password_hash(user.id).await? {
+            Some(hash) if auth::verify_password(password, &hash) => (false, 0),
+            Some(_) => {
+                return Err(TodoeeError::Auth("Wrong email or password".to_string()));
+            }
+            None => {
+                if password.chars().count() < auth::MIN_PASSWORD_LEN {
+                    return Err(TodoeeError::InvalidInput(format!(
+                        "Passwords need at least {} characters",
+                        auth::MIN_PASSWORD_LEN
+                    )));
+                }
+                let hash = auth::hash_password(password).map_err(auth_err)?;
+                remote.set_password_hash(user.id, &hash).await?;
+                (true, remote.claim_unowned(user.id).await?)
+            }
+        };
+
+        let session_token = auth::new_session_token().map_err(auth_err)?;
+        remote
+            .start_session(&auth::token_hash(&session_token), user.id, device_id)
+            .await?;
+        remote.link_device(device_id, user.id).await?;
+        Ok(Login {
+            user,
+            session_token,
+            new_account,
+            claimed,
+        })
+    }
+
+    /// End the session this device is signed in with.
+    pub async fn logout(&self) -> TodoeeResult<()> {
+        if let Some(session) = &self.session {
+            self.remote()?.end_session(&session.token_hash).await?;
+        }
+        Ok(())
+    }
+
+    fn remote(&self) -> TodoeeResult<&dyn SyncBackend> {
+        self.remote.as_deref().ok_or_else(|| {
+            TodoeeError::Config(
+                "Cloud sync not configured. Set NEON_DATABASE_URL environment variable, or database.sync_dir."
+                    .to_string(),
+            )
+        })
+    }
+
+    /// Register this device with the remote and fail if it has been revoked.
+    async fn check_device(&self, remote: &dyn SyncBackend) -> TodoeeResult<()> {
+        let device = remote
+            .register_device(self.device_id().await?, &device_name())
+            .await?;
+        if let Some(revoked_at) = device.revoked_at {
+            return Err(TodoeeError::Auth(format!(
+                "This device was revoked on {} and can no longer sync.\n\nRun `todoee devices wipe` to delete its local data.",
+                revoked_at.format("%Y-%m-%d %H:%M UTC")
+            )));
+        }
+        Ok(())
+    }
+
+    /// The user to sync as, after checking that the session is still live.
+    /// `None` when this device never logged in.
+    async fn check_session(&self, remote: &dyn SyncBackend) -> TodoeeResult<Option<Uuid>> {
+        let Some(session) = &self.session else {
+            return Ok(None);
+        };
+        match remote.session_user(&session.token_hash).await? {
+            Some(user_id) if user_id == session.user_id => Ok(Some(user_id)),
+            _ => Err(TodoeeError::Auth(
+                "Your session has ended. Run `todoee login` to sign in again.".to_string(),
+            )),
+        }
     }
 
     /// Get the timestamp of the last sync.
@@ -225,10 +812,25 @@ impl SyncService {
     }
 }
 
+/// Name shown for this device in `todoee devices list`
+fn device_name() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown device".to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::Todo;
+    use crate::db::remote::TodoCursor;
+    use crate::models::{Category, EntityType, Operation, OperationType, Todo};
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
     use tempfile::TempDir;
 
     #[tokio::test]
@@ -265,6 +867,31 @@ mod tests {
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(matches!(err, TodoeeError::Config(_)));
+
+        // Nothing was attempted, so nothing is recorded
+        let health = service.health().await.unwrap();
+        assert!(health.last_run.is_none());
+        assert!(health.last_success.is_none());
+        assert!(matches!(service.ping().await, Err(TodoeeError::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_devices_need_remote() {
+        let local_db = LocalDb::new_in_memory().await.unwrap();
+        local_db.run_migrations().await.unwrap();
+        let service = SyncService::with_local(local_db);
+
+        // The device ID is local and stable; device management needs the remote
+        let id = service.device_id().await.unwrap();
+        assert_eq!(service.device_id().await.unwrap(), id);
+        assert!(matches!(
+            service.list_devices().await,
+            Err(TodoeeError::Config(_))
+        ));
+        assert!(matches!(
+            service.revoke_device(id).await,
+            Err(TodoeeError::Config(_))
+        ));
     }
 
     #[tokio::test]
@@ -293,4 +920,525 @@ mod tests {
         let retrieved = local_db.get_todo(todo.id).await.unwrap().unwrap();
         assert_eq!(retrieved.sync_status, SyncStatus::Synced);
     }
+
+    #[tokio::test]
+    async fn test_local_deletion_queued_for_upload() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let local_db = LocalDb::new(&db_path).await.unwrap();
+        local_db.run_migrations().await.unwrap();
+
+        let mut todo = Todo::new("Delete me here".to_string(), None);
+        todo.sync_status = SyncStatus::Synced;
+        local_db.create_todo(&todo).await.unwrap();
+        local_db.delete_todo(todo.id).await.unwrap();
+
+        // The deletion is waiting to be pushed to remote
+        assert_eq!(
+            local_db.list_unsynced_deletions().await.unwrap(),
+            vec![todo.id]
+        );
+
+        // Once pushed, it is no longer pending
+        local_db.mark_deletion_synced(todo.id).await.unwrap();
+        assert!(local_db.list_unsynced_deletions().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_apply_remote_deletions() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let local_db = LocalDb::new(&db_path).await.unwrap();
+        local_db.run_migrations().await.unwrap();
+
+        let kept = Todo::new("Still here".to_string(), None);
+        let deleted = Todo::new("Deleted remotely".to_string(), None);
+        local_db.create_todo(&kept).await.unwrap();
+        local_db.create_todo(&deleted).await.unwrap();
+
+        let service = SyncService::with_local(local_db);
+        let unknown = Uuid::new_v4();
+        let removed = service
+            .apply_remote_deletions(&[deleted.id, unknown])
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+
+        let local = service.local();
+        assert!(local.get_todo(deleted.id).await.unwrap().is_none());
+        assert!(local.get_todo(kept.id).await.unwrap().is_some());
+
+        // Remote tombstones are not echoed back as local deletions
+        assert!(local.list_unsynced_deletions().await.unwrap().is_empty());
+        assert!(local.is_locally_deleted(deleted.id).await.unwrap());
+        assert!(local.is_locally_deleted(unknown).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_apply_remote_deletions_keeps_pending_local_deletion() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let local_db = LocalDb::new(&db_path).await.unwrap();
+        local_db.run_migrations().await.unwrap();
+
+        let todo = Todo::new("Deleted on both sides".to_string(), None);
+        local_db.create_todo(&todo).await.unwrap();
+        local_db.delete_todo(todo.id).await.unwrap();
+
+        let service = SyncService::with_local(local_db);
+        let removed = service.apply_remote_deletions(&[todo.id]).await.unwrap();
+        assert_eq!(removed, 0);
+
+        // The local deletion is still queued for upload
+        assert_eq!(
+            service.local().list_unsynced_deletions().await.unwrap(),
+            vec![todo.id]
+        );
+    }
+
+    /// A backend kept in memory, for syncing without Postgres
+    #[derive(Default)]
+    struct MemoryBackend {
+        todos: Mutex<Vec<Todo>>,
+        deleted: Mutex<Vec<Uuid>>,
+        categories: Mutex<Vec<Category>>,
+        /// Users and their password hashes
+        users: Mutex<Vec<(User, Option<String>)>>,
+        sessions: Mutex<HashMap<String, Uuid>>,
+        revoked: bool,
+    }
+
+    #[async_trait]
+    impl SyncBackend for MemoryBackend {
+        async fn ping(&self) -> TodoeeResult<Duration> {
+            Ok(Duration::ZERO)
+        }
+
+        async fn schema_version(&self) -> TodoeeResult<i32> {
+            Ok(crate::db::remote::SCHEMA_VERSION)
+        }
+
+        async fn upsert_todo(&self, todo: &Todo) -> TodoeeResult<bool> {
+            let mut todos = self.todos.lock().unwrap();
+            if let Some(stored) = todos.iter().find(|t| t.id == todo.id)
+                && (!stored.is_same_todo(todo) || stored.updated_at >= todo.updated_at)
+            {
+                return Ok(false);
+            }
+            todos.retain(|t| t.id != todo.id);
+            todos.push(todo.clone());
+            Ok(true)
+        }
+
+        async fn get_todos_page(
+            &self,
+            owner: Option<Uuid>,
+            since: DateTime<Utc>,
+            after: Option<TodoCursor>,
+            limit: u32,
+        ) -> TodoeeResult<Vec<Todo>> {
+            let mut todos: Vec<Todo> = self
+                .todos
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|t| t.updated_at > since)
+                .filter(|t| owner.is_none() || t.user_id == owner)
+                .filter(|t| after.is_none_or(|cursor| (t.updated_at, t.id) > cursor))
+                .cloned()
+                .collect();
+            todos.sort_by_key(|t| (t.updated_at, t.id));
+            todos.truncate(limit as usize);
+            Ok(todos)
+        }
+
+        async fn get_todos(&self, owner: Option<Uuid>, ids: &[Uuid]) -> TodoeeResult<Vec<Todo>> {
+            Ok(self
+                .todos
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|t| ids.contains(&t.id))
+                .filter(|t| owner.is_none() || t.user_id == owner)
+                .cloned()
+                .collect())
+        }
+
+        async fn soft_delete_todo(&self, _owner: Option<Uuid>, id: Uuid) -> TodoeeResult<()> {
+            self.todos.lock().unwrap().retain(|t| t.id != id);
+            self.deleted.lock().unwrap().push(id);
+            Ok(())
+        }
+
+        async fn get_deleted_todo_ids_since(
+            &self,
+            _owner: Option<Uuid>,
+            _since: DateTime<Utc>,
+        ) -> TodoeeResult<Vec<Uuid>> {
+            Ok(self.deleted.lock().unwrap().clone())
+        }
+
+        async fn upsert_category(&self, category: &Category) -> TodoeeResult<Upserted> {
+            let mut categories = self.categories.lock().unwrap();
+            if let Some(existing) = categories
+                .iter()
+                .find(|c| c.name == category.name && c.id != category.id)
+            {
+                return Ok(Upserted::NameTaken(Some(existing.id)));
+            }
+            categories.retain(|c| c.id != category.id);
+            categories.push(category.clone());
+            Ok(Upserted::Stored)
+        }
+
+        async fn get_categories_since(
+            &self,
+            _owner: Option<Uuid>,
+            _since: DateTime<Utc>,
+        ) -> TodoeeResult<Vec<Category>> {
+            Ok(self.categories.lock().unwrap().clone())
+        }
+
+        async fn upsert_project(&self, _project: &Project) -> TodoeeResult<Upserted> {
+            Ok(Upserted::Stored)
+        }
+
+        async fn get_projects_since(
+            &self,
+            _owner: Option<Uuid>,
+            _since: DateTime<Utc>,
+        ) -> TodoeeResult<Vec<Project>> {
+            Ok(Vec::new())
+        }
+
+        async fn register_device(&self, id: Uuid, name: &str) -> TodoeeResult<Device> {
+            let now = Utc::now();
+            Ok(Device {
+                id,
+                name: name.to_string(),
+                registered_at: now,
+                last_seen_at: now,
+                revoked_at: self.revoked.then_some(now),
+            })
+        }
+
+        async fn list_devices(&self, _owner: Option<Uuid>) -> TodoeeResult<Vec<Device>> {
+            Ok(Vec::new())
+        }
+
+        async fn revoke_device(&self, _owner: Option<Uuid>, _id: Uuid) -> TodoeeResult<bool> {
+            Ok(false)
+        }
+
+        async fn ensure_user(&self, email: &str) -> TodoeeResult<User> {
+            if let Some(user) = self.find_user(email).await? {
+                return Ok(user);
+            }
+            let user = User {
+                id: Uuid::new_v4(),
+                email: email.to_string(),
+                created_at: Utc::now(),
+            };
+            self.users.lock().unwrap().push((user.clone(), None));
+            Ok(user)
+        }
+
+        async fn link_device(&self, _device_id: Uuid, _user_id: Uuid) -> TodoeeResult<()> {
+            Ok(())
+        }
+
+        async fn find_user(&self, email: &str) -> TodoeeResult<Option<User>> {
+            let users = self.users.lock().unwrap();
+            Ok(users
+                .iter()
+                .find(|(u, _)| u.email == email)
+                .map(|(u, _)| u.clone()))
+        }
+
+        async fn password_hash(&self, user_id: Uuid) -> TodoeeResult<Option<String>> {
+            let users = self.users.lock().unwrap();
+            Ok(users
+                .iter()
+                .find(|(u, _)| u.id == user_id)
+                .and_then(|(_, hash)| hash.clone()))
+        }
+
+        async fn set_password_hash(&self, user_id: Uuid, hash: &str) -> TodoeeResult<()> {
+            for (user, stored) in self.users.lock().unwrap().iter_mut() {
+                if user.id == user_id {
+                    *stored = Some(hash.to_string());
+                }
+            }
+            Ok(())
+        }
+
+        async fn claim_unowned(&self, user_id: Uuid) -> TodoeeResult<u64> {
+            let users = self.users.lock().unwrap();
+            if users
+                .iter()
+                .any(|(u, hash)| u.id != user_id && hash.is_some())
+            {
+                return Ok(0);
+            }
+            let mut claimed = 0;
+            for todo in self.todos.lock().unwrap().iter_mut() {
+                if todo.user_id.is_none() {
+                    todo.user_id = Some(user_id);
+                    claimed += 1;
+                }
+            }
+            Ok(claimed)
+        }
+
+        async fn start_session(
+            &self,
+            token_hash: &str,
+            user_id: Uuid,
+            _device_id: Uuid,
+        ) -> TodoeeResult<()> {
+            self.sessions
+                .lock()
+                .unwrap()
+                .insert(token_hash.to_string(), user_id);
+            Ok(())
+        }
+
+        async fn session_user(&self, token_hash: &str) -> TodoeeResult<Option<Uuid>> {
+            Ok(self.sessions.lock().unwrap().get(token_hash).copied())
+        }
+
+        async fn end_session(&self, token_hash: &str) -> TodoeeResult<()> {
+            self.sessions.lock().unwrap().remove(token_hash);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_with_memory_backend() {
+        let local_db = LocalDb::new_in_memory().await.unwrap();
+        local_db.run_migrations().await.unwrap();
+
+        let user_id = Uuid::new_v4();
+        let work = Category::new(user_id, "Work".to_string());
+        local_db.create_category(&work).await.unwrap();
+        let mut mine = Todo::new("Written here".to_string(), None);
+        mine.category_id = Some(work.id);
+        local_db.create_todo(&mine).await.unwrap();
+        let mut gone = Todo::new("Deleted elsewhere".to_string(), None);
+        gone.sync_status = SyncStatus::Synced;
+        local_db.create_todo(&gone).await.unwrap();
+
+        let remote_work = Category::new(user_id, "Work".to_string());
+        let theirs = Todo::new("Written elsewhere".to_string(), None);
+        let backend = MemoryBackend {
+            todos: Mutex::new(vec![theirs.clone()]),
+            deleted: Mutex::new(vec![gone.id]),
+            categories: Mutex::new(vec![remote_work.clone()]),
+            ..Default::default()
+        };
+
+        let service = SyncService::with_backend(local_db, Box::new(backend));
+        assert!(service.is_configured());
+        let result = service.sync().await.unwrap();
+        assert_eq!(result.uploaded, 1);
+        assert_eq!(result.downloaded, 1);
+        assert_eq!(result.deletions_downloaded, 1);
+        assert_eq!(result.categories_merged, 1);
+
+        let local = service.local();
+        assert!(local.list_pending_sync().await.unwrap().is_empty());
+        assert!(local.get_todo(theirs.id).await.unwrap().is_some());
+        assert!(local.get_todo(gone.id).await.unwrap().is_none());
+        let mine = local.get_todo(mine.id).await.unwrap().unwrap();
+        assert_eq!(mine.category_id, Some(remote_work.id));
+        assert!(service.health().await.unwrap().last_success.is_some());
+
+        // A revoked device is refused before anything is exchanged
+        let local_db = LocalDb::new_in_memory().await.unwrap();
+        local_db.run_migrations().await.unwrap();
+        let revoked = MemoryBackend {
+            revoked: true,
+            ..Default::default()
+        };
+        let service = SyncService::with_backend(local_db, Box::new(revoked));
+        assert!(matches!(service.sync().await, Err(TodoeeError::Auth(_))));
+    }
+
+    #[tokio::test]
+    async fn test_colliding_id_is_not_uploaded() {
+        let local_db = LocalDb::new_in_memory().await.unwrap();
+        local_db.run_migrations().await.unwrap();
+
+        let theirs = Todo::new("Written elsewhere".to_string(), None);
+        let mut mine = Todo::new("Written here".to_string(), None);
+        mine.id = theirs.id;
+        mine.created_at = theirs.created_at - chrono::Duration::days(1);
+        mine.updated_at = theirs.updated_at + chrono::Duration::minutes(1);
+        local_db.create_todo(&mine).await.unwrap();
+
+        let backend = MemoryBackend {
+            todos: Mutex::new(vec![theirs.clone()]),
+            ..Default::default()
+        };
+        let service = SyncService::with_backend(local_db, Box::new(backend));
+        let result = service.sync().await.unwrap();
+        assert_eq!(result.uploaded, 0);
+        assert_eq!(result.downloaded, 0);
+        assert_eq!(result.id_collisions, 1);
+
+        // Neither side is overwritten and the local todo stays pending
+        let local = service.local();
+        let pending = local.list_pending_sync().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].title, "Written here");
+        let remote = service.remote().unwrap();
+        let stored = remote.get_todos(None, &[theirs.id]).await.unwrap();
+        assert_eq!(stored[0].title, "Written elsewhere");
+    }
+
+    #[tokio::test]
+    async fn test_login_scopes_sync_to_the_user() {
+        let local_db = LocalDb::new_in_memory().await.unwrap();
+        local_db.run_migrations().await.unwrap();
+        let mine = Todo::new("Written here".to_string(), None);
+        local_db.create_todo(&mine).await.unwrap();
+
+        let bob = User {
+            id: Uuid::new_v4(),
+            email: "bob@example.com".to_string(),
+            created_at: Utc::now(),
+        };
+        let before_accounts = Todo::new("Synced before accounts".to_string(), None);
+        let bobs = Todo::new("Bob's".to_string(), Some(bob.id));
+        let backend = MemoryBackend {
+            todos: Mutex::new(vec![before_accounts.clone(), bobs.clone()]),
+            users: Mutex::new(vec![(bob, None)]),
+            ..Default::default()
+        };
+
+        let mut service = SyncService::with_backend(local_db, Box::new(backend));
+        assert!(!service.has_account("ada@example.com").await.unwrap());
+        assert!(matches!(
+            service.login("ada@example.com", "short").await,
+            Err(TodoeeError::InvalidInput(_))
+        ));
+        let login = service
+            .login("ada@example.com", "correct horse")
+            .await
+            .unwrap();
+        assert!(login.new_account);
+        assert_eq!(login.claimed, 1);
+        assert!(service.has_account("ada@example.com").await.unwrap());
+        assert!(matches!(
+            service.login("ada@example.com", "wrong horse").await,
+            Err(TodoeeError::Auth(_))
+        ));
+        let again = service
+            .login("ada@example.com", "correct horse")
+            .await
+            .unwrap();
+        assert!(!again.new_account);
+        // Bob's account is created second, so he claims nothing
+        assert_eq!(
+            service
+                .login("bob@example.com", "battery staple")
+                .await
+                .unwrap()
+                .claimed,
+            0
+        );
+
+        service.session = Some(Session {
+            user_id: login.user.id,
+            token_hash: auth::token_hash(&login.session_token),
+        });
+        let result = service.sync().await.unwrap();
+        assert_eq!(result.uploaded, 1);
+        assert_eq!(result.downloaded, 1);
+        let local = service.local();
+        assert!(local.get_todo(before_accounts.id).await.unwrap().is_some());
+        assert!(local.get_todo(bobs.id).await.unwrap().is_none());
+        let uploaded = service
+            .remote()
+            .unwrap()
+            .get_todos_page(Some(login.user.id), DateTime::UNIX_EPOCH, None, 10)
+            .await
+            .unwrap();
+        assert!(uploaded.iter().any(|t| t.id == mine.id));
+
+        service.logout().await.unwrap();
+        assert!(matches!(service.sync().await, Err(TodoeeError::Auth(_))));
+    }
+
+    #[tokio::test]
+    async fn test_edits_on_both_sides_are_kept_for_review() {
+        let local_db = LocalDb::new_in_memory().await.unwrap();
+        local_db.run_migrations().await.unwrap();
+        let last_sync = Utc::now() - chrono::Duration::hours(1);
+        local_db
+            .record_sync_run(&SyncRun {
+                finished_at: last_sync,
+                uploaded: 0,
+                downloaded: 0,
+                conflict_ids: Vec::new(),
+                error: None,
+            })
+            .await
+            .unwrap();
+
+        let mut synced = Todo::new("Buy milk".to_string(), None);
+        synced.created_at = last_sync - chrono::Duration::hours(1);
+        synced.updated_at = synced.created_at;
+        let mut ours = synced.clone();
+        ours.title = "Buy oat milk".to_string();
+        ours.updated_at = Utc::now();
+        local_db.create_todo(&ours).await.unwrap();
+        let mut theirs = synced.clone();
+        theirs.priority = crate::models::Priority::Urgent;
+        theirs.updated_at = Utc::now() - chrono::Duration::minutes(5);
+        let backend = MemoryBackend {
+            todos: Mutex::new(vec![theirs.clone()]),
+            ..Default::default()
+        };
+
+        let service = SyncService::with_backend(local_db, Box::new(backend));
+        let result = service.sync().await.unwrap();
+        assert_eq!(result.conflicts, 1);
+        assert_eq!(result.conflict_ids, [ours.id]);
+        assert_eq!(result.uploaded, 0);
+        let local = service.local();
+        let conflicts = local.list_conflicts().await.unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].local.title, "Buy oat milk");
+        assert_eq!(conflicts[0].local.sync_status, SyncStatus::Conflict);
+        assert_eq!(
+            conflicts[0].fields(),
+            [
+                crate::conflict::Field::Title,
+                crate::conflict::Field::Priority
+            ]
+        );
+
+        // Another sync leaves both sides alone until the conflict is resolved
+        let result = service.sync().await.unwrap();
+        assert_eq!((result.uploaded, result.downloaded), (0, 0));
+
+        let conflict = &conflicts[0];
+        let merged = conflict.resolve(|_| crate::conflict::Side::Local);
+        let op = Operation::new(
+            OperationType::Update,
+            EntityType::Todo,
+            merged.id,
+            serde_json::to_value(&conflict.local).ok(),
+            serde_json::to_value(&merged).ok(),
+        );
+        local.resolve_conflict(&merged, &op).await.unwrap();
+        assert!(local.list_conflicts().await.unwrap().is_empty());
+        assert!(local.get_conflict(ours.id).await.unwrap().is_none());
+
+        let result = service.sync().await.unwrap();
+        assert_eq!(result.uploaded, 1);
+        let remote = service.remote().unwrap().get_todos(None, &[ours.id]).await;
+        assert_eq!(remote.unwrap()[0].title, "Buy oat milk");
+    }
 }
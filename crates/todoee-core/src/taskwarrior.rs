@@ -0,0 +1,315 @@
+//! Taskwarrior interop
+//!
+//! Writes todos in the JSON format of `task export` and reads it back, so
+//! `task import` can take a todoee export and the other way round:
+//!
+//! ```json
+//! {"uuid":"4f1c...","description":"Pay rent","status":"pending",
+//!  "entry":"20260201T100000Z","due":"20260228T230000Z","priority":"H",
+//!  "project":"Flat","tags":["Home"],
+//!  "annotations":[{"entry":"20260201T100000Z","description":"Transfer"}]}
+//! ```
+//!
+//! UUIDs, entry/modified/end times and completion carry over, so a migrated
+//! todo keeps its history. H/M/L map to high/medium/low priority, the
+//! project to the todo's project and the first tag to its category.
+//! Annotations become description lines and description lines become
+//! annotations. Deleted tasks and recurring templates are skipped. A due
+//! date at midnight in the caller's timezone, as Taskwarrior stores
+//! `due:2026-03-01`, is read as a date-only due date.
+
+use chrono::{DateTime, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::dates;
+use crate::error::{Result, TodoeeError};
+use crate::models::{Category, Priority, Project, Todo};
+
+/// Taskwarrior's date format: 20260301T120000Z
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// A todo read from a Taskwarrior export, with its project and tags
+#[derive(Debug, Clone)]
+pub struct TaskwarriorTodo {
+    pub todo: Todo,
+    /// Project name, if the task had one
+    pub project: Option<String>,
+    /// Tags in order; the first is used as the category
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Task {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    uuid: Option<Uuid>,
+    description: String,
+    status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    modified: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<Annotation>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Annotation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+    description: String,
+}
+
+/// Render todos as a Taskwarrior JSON array
+pub fn write<Tz: TimeZone>(
+    todos: &[Todo],
+    categories: &[Category],
+    projects: &[Project],
+    tz: &Tz,
+) -> String {
+    let tasks: Vec<Task> = todos
+        .iter()
+        .map(|todo| Task {
+            uuid: Some(todo.id),
+            description: todo.display_title(),
+            status: if todo.is_completed {
+                "completed"
+            } else {
+                "pending"
+            }
+            .to_string(),
+            entry: Some(timestamp(todo.created_at)),
+            modified: Some(timestamp(todo.updated_at)),
+            end: todo
+                .completed_at
+                .filter(|_| todo.is_completed)
+                .map(timestamp),
+            due: todo.due_date.map(|due| timestamp(due_out(due, tz))),
+            priority: Some(priority_letter(todo.priority).to_string()),
+            project: todo
+                .project_id
+                .and_then(|id| projects.iter().find(|p| p.id == id))
+                .map(|p| p.name.clone()),
+            tags: todo
+                .category_id
+                .and_then(|id| categories.iter().find(|c| c.id == id))
+                .map(|c| vec![crate::org::tag_name(&c.name)])
+                .unwrap_or_default(),
+            annotations: todo
+                .description
+                .as_deref()
+                .unwrap_or_default()
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| Annotation {
+                    entry: Some(timestamp(todo.updated_at)),
+                    description: line.to_string(),
+                })
+                .collect(),
+        })
+        .collect();
+
+    let mut out = serde_json::to_string_pretty(&tasks).unwrap_or_else(|_| "[]".to_string());
+    out.push('\n');
+    out
+}
+
+/// Parse a Taskwarrior export: a JSON array, or one object per line as
+/// written by Taskwarrior before 2.6
+pub fn parse<Tz: TimeZone>(content: &str, tz: &Tz) -> Result<Vec<TaskwarriorTodo>> {
+    let invalid =
+        |e: serde_json::Error| TodoeeError::InvalidInput(format!("Taskwarrior JSON: {}", e));
+    let tasks: Vec<Task> = if content.trim_start().starts_with('[') {
+        serde_json::from_str(content).map_err(invalid)?
+    } else {
+        content
+            .lines()
+            .map(|line| line.trim().trim_end_matches(','))
+            .filter(|line| !line.is_empty())
+            .map(serde_json::from_str)
+            .collect::<std::result::Result<_, _>>()
+            .map_err(invalid)?
+    };
+
+    Ok(tasks
+        .into_iter()
+        .filter(|task| !matches!(task.status.as_str(), "deleted" | "recurring"))
+        .map(|task| to_todo(task, tz))
+        .collect())
+}
+
+fn to_todo<Tz: TimeZone>(task: Task, tz: &Tz) -> TaskwarriorTodo {
+    let mut todo = Todo::new(task.description, None);
+    if let Some(id) = task.uuid {
+        todo.id = id;
+    }
+    if let Some(entry) = task.entry.as_deref().and_then(parse_timestamp) {
+        todo.created_at = entry;
+        todo.updated_at = entry;
+    }
+    if let Some(modified) = task.modified.as_deref().and_then(parse_timestamp) {
+        todo.updated_at = modified;
+    }
+    todo.due_date = task
+        .due
+        .as_deref()
+        .and_then(parse_timestamp)
+        .map(|due| due_in(due, tz));
+    todo.priority = match task.priority.as_deref() {
+        Some("H") => Priority::High,
+        Some("L") => Priority::Low,
+        _ => Priority::Medium,
+    };
+    if task.status == "completed" {
+        todo.is_completed = true;
+        todo.completed_at = task
+            .end
+            .as_deref()
+            .and_then(parse_timestamp)
+            .or(Some(todo.updated_at));
+    }
+    let description: Vec<&str> = task
+        .annotations
+        .iter()
+        .map(|a| a.description.as_str())
+        .collect();
+    if !description.is_empty() {
+        todo.description = Some(description.join("\n"));
+    }
+    todo.detect_icon();
+
+    TaskwarriorTodo {
+        todo,
+        project: task.project.filter(|p| !p.trim().is_empty()),
+        tags: task.tags,
+    }
+}
+
+/// Taskwarrior priority letter
+fn priority_letter(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Urgent | Priority::High => "H",
+        Priority::Medium => "M",
+        Priority::Low => "L",
+    }
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.format(DATE_FORMAT).to_string()
+}
+
+fn parse_timestamp(stamp: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(stamp, DATE_FORMAT)
+        .ok()
+        .map(|at| at.and_utc())
+}
+
+/// A date-only due date is written as midnight in `tz`, as Taskwarrior does
+fn due_out<Tz: TimeZone>(due: DateTime<Utc>, tz: &Tz) -> DateTime<Utc> {
+    if !dates::is_date_only(&due) {
+        return due;
+    }
+    tz.from_local_datetime(&due.date_naive().and_time(NaiveTime::MIN))
+        .earliest()
+        .map_or(due, |midnight| midnight.with_timezone(&Utc))
+}
+
+/// Midnight in `tz` is read back as a date-only due date
+fn due_in<Tz: TimeZone>(due: DateTime<Utc>, tz: &Tz) -> DateTime<Utc> {
+    if dates::due_time(&due, tz) == Some(NaiveTime::MIN) {
+        dates::with_due_time(&due, None, tz)
+    } else {
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    #[test]
+    fn test_round_trip() {
+        let berlin = FixedOffset::east_opt(3600).unwrap();
+        let category = Category::new(Uuid::nil(), "Side project".to_string());
+        let project = Project::new(Uuid::nil(), "Launch".to_string());
+
+        let mut todo = Todo::new("Pay rent".to_string(), None);
+        todo.category_id = Some(category.id);
+        todo.project_id = Some(project.id);
+        todo.priority = Priority::High;
+        todo.description = Some("Joint account\nby transfer".to_string());
+        todo.due_date = Some(Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap());
+
+        let mut done = Todo::new("Call bank".to_string(), None);
+        done.due_date = Some(Utc.with_ymd_and_hms(2026, 1, 10, 14, 30, 0).unwrap());
+        done.mark_complete();
+
+        let json = write(
+            &[todo.clone(), done.clone()],
+            &[category],
+            &[project],
+            &berlin,
+        );
+        assert!(json.contains("\"due\": \"20260228T230000Z\""));
+        assert!(json.contains("\"tags\": [\n      \"Side_project\"\n    ]"));
+
+        let parsed = parse(&json, &berlin).unwrap();
+        assert_eq!(parsed.len(), 2);
+        let first = &parsed[0];
+        assert_eq!(first.todo.id, todo.id);
+        assert_eq!(first.todo.due_date, todo.due_date);
+        assert_eq!(first.todo.priority, Priority::High);
+        assert_eq!(first.todo.description, todo.description);
+        assert_eq!(first.project.as_deref(), Some("Launch"));
+        assert_eq!(first.tags, vec!["Side_project"]);
+        assert!(first.todo.is_same_todo(&todo));
+
+        let second = &parsed[1];
+        assert!(second.todo.is_completed);
+        assert_eq!(second.todo.due_date, done.due_date);
+        assert_eq!(
+            second.todo.completed_at.map(timestamp),
+            done.completed_at.map(timestamp)
+        );
+    }
+
+    #[test]
+    fn test_parse_task_export() {
+        // Pre-2.6 exports put one object per line, without an array
+        let content = r#"{"id":1,"description":"Write report","status":"pending","entry":"20260105T081500Z","priority":"L","tags":["work","urgent"],"urgency":4.2}
+{"id":0,"description":"Old","status":"deleted","entry":"20260101T000000Z"}
+{"id":0,"description":"Weekly review","status":"recurring","recur":"weekly"}
+{"id":2,"uuid":"b6a2c2a4-1b8f-4a5e-9a53-1d2b0f6a3c11","description":"Buy milk","status":"waiting","due":"20260110T170000Z","annotations":[{"entry":"20260105T090000Z","description":"oat"},{"entry":"20260106T090000Z","description":"2 litres"}]}"#;
+
+        let parsed = parse(content, &Utc).unwrap();
+        let titles: Vec<_> = parsed.iter().map(|t| t.todo.title.as_str()).collect();
+        assert_eq!(titles, vec!["Write report", "Buy milk"]);
+
+        assert_eq!(parsed[0].todo.priority, Priority::Low);
+        assert_eq!(parsed[0].tags, vec!["work", "urgent"]);
+        assert_eq!(
+            parsed[0].todo.created_at,
+            Utc.with_ymd_and_hms(2026, 1, 5, 8, 15, 0).unwrap()
+        );
+        assert_eq!(
+            parsed[1].todo.id.to_string(),
+            "b6a2c2a4-1b8f-4a5e-9a53-1d2b0f6a3c11"
+        );
+        assert!(!parsed[1].todo.is_completed);
+        assert_eq!(parsed[1].todo.description.as_deref(), Some("oat\n2 litres"));
+        assert_eq!(parsed[1].todo.priority, Priority::Medium);
+
+        assert!(parse("[{\"status\":\"pending\"}]", &Utc).is_err());
+    }
+}
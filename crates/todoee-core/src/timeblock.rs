@@ -0,0 +1,117 @@
+//! Time blocking
+//!
+//! Lays a day's todos out on a timeline, one block each, around time that
+//! is already taken (calendar events). Blocks start on the quarter hour so
+//! the plan reads like a calendar.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::Todo;
+
+/// Grid the blocks snap to, in minutes
+pub const SLOT_MINUTES: i64 = 15;
+
+/// A todo placed on the timeline
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TimeBlock {
+    pub todo_id: Uuid,
+    pub title: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl TimeBlock {
+    /// Whether the block shares any time with `[start, end)`
+    pub fn overlaps(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        self.start < end && self.end > start
+    }
+}
+
+/// `time` rounded up to the next slot boundary
+pub fn round_up(time: DateTime<Utc>) -> DateTime<Utc> {
+    let slot = SLOT_MINUTES * 60;
+    let rest = time.timestamp().rem_euclid(slot);
+    let time = time - Duration::nanoseconds(time.timestamp_subsec_nanos() as i64);
+    if rest == 0 {
+        time
+    } else {
+        time + Duration::seconds(slot - rest)
+    }
+}
+
+/// Give each of `todos`, in order, the first free `length` between `from`
+/// and `until` that doesn't overlap `busy` or an earlier block. Todos that
+/// no longer fit are left out.
+pub fn schedule(
+    todos: &[Todo],
+    busy: &[(DateTime<Utc>, DateTime<Utc>)],
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+    length: Duration,
+) -> Vec<TimeBlock> {
+    let mut taken = busy.to_vec();
+    let mut blocks = Vec::new();
+    let from = round_up(from);
+
+    for todo in todos {
+        let mut start = from;
+        while start + length <= until {
+            let end = start + length;
+            match taken
+                .iter()
+                .filter(|(s, e)| *s < end && *e > start)
+                .map(|(_, e)| *e)
+                .max()
+            {
+                Some(busy_until) => start = round_up(busy_until),
+                None => {
+                    taken.push((start, end));
+                    blocks.push(TimeBlock {
+                        todo_id: todo.id,
+                        title: todo.title.clone(),
+                        start,
+                        end,
+                    });
+                    break;
+                }
+            }
+        }
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_schedule_around_busy_time() {
+        let at = |h: u32, m: u32| Utc.with_ymd_and_hms(2026, 10, 16, h, m, 0).unwrap();
+        let todos: Vec<Todo> = ["Report", "Email", "Review", "Taxes"]
+            .iter()
+            .map(|title| Todo::new(title.to_string(), None))
+            .collect();
+        // Standup 9:30-10:00 and lunch 11:00-12:10
+        let busy = [(at(9, 30), at(10, 0)), (at(11, 0), at(12, 10))];
+
+        let blocks = schedule(&todos, &busy, at(9, 5), at(13, 0), Duration::minutes(45));
+        let starts: Vec<_> = blocks.iter().map(|b| (b.title.as_str(), b.start)).collect();
+        assert_eq!(
+            starts,
+            [
+                // 9:15 would run into the standup
+                ("Report", at(10, 0)),
+                ("Email", at(12, 15)),
+            ]
+        );
+        assert_eq!(blocks[0].end, at(10, 45));
+        assert!(blocks[0].overlaps(at(10, 30), at(11, 0)));
+        assert!(!blocks[0].overlaps(at(10, 45), at(11, 0)));
+
+        assert_eq!(round_up(at(9, 0)), at(9, 0));
+        assert_eq!(round_up(at(9, 0) + Duration::seconds(1)), at(9, 15));
+    }
+}
@@ -0,0 +1,244 @@
+//! Time tracking export
+//!
+//! Turns focus sessions into entries for external time-tracking and
+//! commitment tools:
+//!
+//! - Toggl Track's CSV import, one row per session with the todo as the
+//!   description, its project as the project and its category as a tag:
+//!
+//!   ```text
+//!   Email,Project,Description,Start date,Start time,Duration,Tags
+//!   me@example.com,Launch,Write report,2026-03-02,09:00:00,00:25:00,Work
+//!   ```
+//!
+//! - Beeminder's `datapoints/create_all` JSON, one datapoint per session.
+//!   The session id is the `requestid`, so pushing the same export twice
+//!   does not add duplicates:
+//!
+//!   ```json
+//!   [{"timestamp":1772442000,"value":0.42,"comment":"Write report","requestid":"4f1c..."}]
+//!   ```
+//!
+//! - A generic JSON array of [`TimeEntry`].
+//!
+//! Toggl times are written in the caller's timezone, which is how Toggl
+//! reads them back.
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Serialize;
+
+use crate::models::{Category, FocusInterruption, FocusSession, Project, Todo};
+
+/// What a Beeminder datapoint counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BeeminderUnit {
+    /// One per session, e.g. for a pomodoros-per-day goal
+    Sessions,
+    Minutes,
+    Hours,
+}
+
+impl std::str::FromStr for BeeminderUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sessions" | "pomodoros" => Ok(Self::Sessions),
+            "minutes" | "min" => Ok(Self::Minutes),
+            "hours" | "h" => Ok(Self::Hours),
+            _ => Err(format!(
+                "Unknown unit '{}' (expected sessions, minutes or hours)",
+                s
+            )),
+        }
+    }
+}
+
+/// A focus session with the names external tools show
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeEntry {
+    pub id: uuid::Uuid,
+    pub todo_id: uuid::Uuid,
+    /// Title of the todo, or "(deleted todo)"
+    pub description: String,
+    pub project: Option<String>,
+    pub category: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub duration_seconds: i64,
+    pub interruptions: Vec<FocusInterruption>,
+}
+
+impl TimeEntry {
+    /// Name the session's todo, project and category from the given lists
+    pub fn new(
+        session: FocusSession,
+        todos: &[Todo],
+        categories: &[Category],
+        projects: &[Project],
+    ) -> Self {
+        let todo = todos.iter().find(|t| t.id == session.todo_id);
+        let project = todo
+            .and_then(|t| t.project_id)
+            .and_then(|id| projects.iter().find(|p| p.id == id))
+            .map(|p| p.name.clone());
+        let category = todo
+            .and_then(|t| t.category_id)
+            .and_then(|id| categories.iter().find(|c| c.id == id))
+            .map(|c| c.name.clone());
+
+        Self {
+            id: session.id,
+            todo_id: session.todo_id,
+            description: todo
+                .map(|t| t.title.clone())
+                .unwrap_or_else(|| "(deleted todo)".to_string()),
+            project,
+            category,
+            started_at: session.started_at,
+            ended_at: session.ended_at,
+            duration_seconds: (session.ended_at - session.started_at).num_seconds().max(0),
+            interruptions: session.interruptions,
+        }
+    }
+}
+
+/// Render entries as a Toggl Track CSV import. `email` fills the Email
+/// column, which Toggl needs for workspace imports; it may be empty.
+pub fn toggl_csv<Tz: TimeZone>(entries: &[TimeEntry], email: &str, tz: &Tz) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let mut out = String::from("Email,Project,Description,Start date,Start time,Duration,Tags\n");
+    for entry in entries {
+        let start = entry.started_at.with_timezone(tz);
+        let seconds = entry.duration_seconds;
+        let fields = [
+            email.to_string(),
+            entry.project.clone().unwrap_or_default(),
+            entry.description.clone(),
+            start.format("%Y-%m-%d").to_string(),
+            start.format("%H:%M:%S").to_string(),
+            format!(
+                "{:02}:{:02}:{:02}",
+                seconds / 3600,
+                seconds % 3600 / 60,
+                seconds % 60
+            ),
+            entry.category.clone().unwrap_or_default(),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// A datapoint for Beeminder's `datapoints/create_all`
+#[derive(Debug, Clone, Serialize)]
+pub struct BeeminderDatapoint {
+    /// Unix time the session started
+    pub timestamp: i64,
+    pub value: f64,
+    pub comment: String,
+    /// Beeminder ignores a datapoint whose requestid it has already seen
+    pub requestid: String,
+}
+
+/// One datapoint per entry, valued in `unit`
+pub fn beeminder(entries: &[TimeEntry], unit: BeeminderUnit) -> Vec<BeeminderDatapoint> {
+    entries
+        .iter()
+        .map(|entry| {
+            let value = match unit {
+                BeeminderUnit::Sessions => 1.0,
+                BeeminderUnit::Minutes => round2(entry.duration_seconds as f64 / 60.0),
+                BeeminderUnit::Hours => round2(entry.duration_seconds as f64 / 3600.0),
+            };
+            let comment = match entry.interruptions.len() {
+                0 => entry.description.clone(),
+                1 => format!("{} (1 interruption)", entry.description),
+                n => format!("{} ({} interruptions)", entry.description, n),
+            };
+            BeeminderDatapoint {
+                timestamp: entry.started_at.timestamp(),
+                value,
+                comment,
+                requestid: entry.id.to_string(),
+            }
+        })
+        .collect()
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Quote a CSV field if it holds a comma, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, FixedOffset};
+    use uuid::Uuid;
+
+    fn entry() -> (TimeEntry, Uuid) {
+        let category = Category::new(Uuid::nil(), "Work".to_string());
+        let project = Project::new(Uuid::nil(), "Launch".to_string());
+        let mut todo = Todo::new("Write report, draft 2".to_string(), None);
+        todo.category_id = Some(category.id);
+        todo.project_id = Some(project.id);
+
+        let start = Utc.with_ymd_and_hms(2026, 3, 2, 8, 0, 0).unwrap();
+        let mut session = FocusSession::new(todo.id, start);
+        session.interrupt("Slack", start + Duration::minutes(10));
+        session.ended_at = start + Duration::minutes(25) + Duration::seconds(30);
+        let id = session.id;
+        (
+            TimeEntry::new(session, &[todo], &[category], &[project]),
+            id,
+        )
+    }
+
+    #[test]
+    fn test_toggl_csv() {
+        let (entry, _) = entry();
+        let tz = FixedOffset::east_opt(3600).unwrap();
+        assert_eq!(
+            toggl_csv(&[entry], "me@example.com", &tz),
+            "Email,Project,Description,Start date,Start time,Duration,Tags\n\
+             me@example.com,Launch,\"Write report, draft 2\",2026-03-02,09:00:00,00:25:30,Work\n"
+        );
+    }
+
+    #[test]
+    fn test_beeminder_datapoints() {
+        let (entry, id) = entry();
+        let hours = beeminder(std::slice::from_ref(&entry), BeeminderUnit::Hours);
+        assert_eq!(hours[0].value, 0.43);
+        assert_eq!(hours[0].timestamp, entry.started_at.timestamp());
+        assert_eq!(hours[0].comment, "Write report, draft 2 (1 interruption)");
+        assert_eq!(hours[0].requestid, id.to_string());
+        assert_eq!(
+            beeminder(std::slice::from_ref(&entry), BeeminderUnit::Minutes)[0].value,
+            25.5
+        );
+        assert_eq!(beeminder(&[entry], BeeminderUnit::Sessions)[0].value, 1.0);
+        assert!("fortnights".parse::<BeeminderUnit>().is_err());
+    }
+
+    #[test]
+    fn test_entry_for_deleted_todo() {
+        let session = FocusSession::new(Uuid::new_v4(), Utc::now());
+        let entry = TimeEntry::new(session, &[], &[], &[]);
+        assert_eq!(entry.description, "(deleted todo)");
+        assert_eq!((entry.project, entry.category), (None, None));
+    }
+}
@@ -0,0 +1,279 @@
+//! Release checks and self-update
+//!
+//! Releases are published on GitHub with one binary per platform, named
+//! `todoee-<arch>-<os>` (`.exe` on Windows), and a `.sig` file next to each
+//! holding an Ed25519 signature of the binary, raw or base64. A download is
+//! only installed if the signature matches [`RELEASE_PUBLIC_KEY`], so a
+//! compromised download host can't push a binary. The key is supplied at
+//! build time; builds without one can check for new versions but not
+//! install them.
+//!
+//! The once-a-week version check stores its result in `update-check.json` in
+//! the config directory, so commands can mention a new version without going
+//! online every time.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use ring::signature::{ED25519, UnparsedPublicKey};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, TodoeeError};
+use crate::net;
+
+/// Version of this build
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// GitHub API endpoint for the newest release
+pub const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/AbenOG/Todoee-Dev-Preview/releases/latest";
+
+/// Ed25519 public key (base64) that release binaries are signed with, taken
+/// from `TODOEE_RELEASE_PUBLIC_KEY` when todoee is built
+pub const RELEASE_PUBLIC_KEY: Option<&str> = option_env!("TODOEE_RELEASE_PUBLIC_KEY");
+
+/// How long a version check result is trusted before asking again
+pub const CHECK_INTERVAL_DAYS: i64 = 7;
+
+/// File in the config directory holding the last check
+const CHECK_FILE: &str = "update-check.json";
+
+/// A published release
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    /// Release page, for the changelog
+    pub html_url: String,
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+}
+
+/// A file attached to a release
+#[derive(Debug, Clone, Deserialize)]
+pub struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    /// Version without the leading `v` of the tag
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    pub fn asset(&self, name: &str) -> Option<&Asset> {
+        self.assets.iter().find(|a| a.name == name)
+    }
+}
+
+/// Name of the release binary for this platform, e.g. `todoee-x86_64-linux`
+pub fn asset_name() -> String {
+    format!(
+        "todoee-{}-{}{}",
+        std::env::consts::ARCH,
+        std::env::consts::OS,
+        std::env::consts::EXE_SUFFIX
+    )
+}
+
+/// Whether `candidate` is a later version than `current`. A release without
+/// a pre-release suffix is later than the same version with one
+/// (`1.2.0` > `1.2.0-beta.1`). Unparseable versions are never newer.
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    match (parse_version(candidate), parse_version(current)) {
+        (Some(candidate), Some(current)) => candidate > current,
+        _ => false,
+    }
+}
+
+/// `(major, minor, patch, is_final)`, so that tuples order like versions
+fn parse_version(version: &str) -> Option<(u64, u64, u64, bool)> {
+    let version = version.trim().trim_start_matches('v');
+    let (numbers, pre) = match version.split_once('-') {
+        Some((numbers, pre)) => (numbers, Some(pre)),
+        None => (version, None),
+    };
+    let mut parts = numbers.split('.').map(|n| n.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch, pre.is_none()))
+}
+
+/// Fetch the newest release
+pub async fn latest_release(client: &reqwest::Client) -> Result<Release> {
+    let body = get(client, LATEST_RELEASE_URL).await?;
+    serde_json::from_slice(&body)
+        .map_err(|e| TodoeeError::Update(format!("Unexpected release information: {}", e)))
+}
+
+/// Download a release asset
+pub async fn download(client: &reqwest::Client, asset: &Asset) -> Result<Vec<u8>> {
+    get(client, &asset.browser_download_url).await
+}
+
+async fn get(client: &reqwest::Client, url: &str) -> Result<Vec<u8>> {
+    let response = client
+        .get(url)
+        .header(
+            reqwest::header::USER_AGENT,
+            format!("todoee/{}", CURRENT_VERSION),
+        )
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .send()
+        .await
+        .map_err(|e| {
+            net::tls_error(&e).unwrap_or_else(|| {
+                TodoeeError::Update(format!("Couldn't reach {}: {}", url, net::error_chain(&e)))
+            })
+        })?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(TodoeeError::Update(format!("{} returned {}", url, status)));
+    }
+    let body = response.bytes().await.map_err(|e| {
+        TodoeeError::Update(format!("Couldn't reach {}: {}", url, net::error_chain(&e)))
+    })?;
+    Ok(body.to_vec())
+}
+
+/// Whether this build can install releases, i.e. was built with a
+/// [`RELEASE_PUBLIC_KEY`]
+pub fn can_self_update() -> bool {
+    RELEASE_PUBLIC_KEY.is_some_and(|key| !key.trim().is_empty())
+}
+
+/// Check `binary` against its `.sig` file with [`RELEASE_PUBLIC_KEY`]
+pub fn verify(binary: &[u8], signature: &[u8]) -> Result<()> {
+    let key = RELEASE_PUBLIC_KEY
+        .filter(|_| can_self_update())
+        .ok_or_else(|| {
+            TodoeeError::Update(
+                "This build has no release signing key, so downloads can't be verified".to_string(),
+            )
+        })?;
+    let key = base64::engine::general_purpose::STANDARD
+        .decode(key.trim())
+        .map_err(|e| TodoeeError::Update(format!("Invalid release key: {}", e)))?;
+    verify_with(&key, binary, signature)
+}
+
+fn verify_with(public_key: &[u8], binary: &[u8], signature: &[u8]) -> Result<()> {
+    // Signatures are 64 bytes; anything else is read as base64 text
+    let signature = if signature.len() == 64 {
+        signature.to_vec()
+    } else {
+        let text = String::from_utf8_lossy(signature);
+        base64::engine::general_purpose::STANDARD
+            .decode(text.trim())
+            .map_err(|_| TodoeeError::Update("Release signature is malformed".to_string()))?
+    };
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(binary, &signature)
+        .map_err(|_| {
+            TodoeeError::Update(
+                "Release signature does not match; the download was not installed".to_string(),
+            )
+        })
+}
+
+/// Result of the last version check
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateCheck {
+    pub checked_at: Option<DateTime<Utc>>,
+    /// Newest released version at that time
+    pub latest: Option<String>,
+}
+
+impl UpdateCheck {
+    /// Path of the check file in `config_dir`
+    pub fn path(config_dir: &Path) -> PathBuf {
+        config_dir.join(CHECK_FILE)
+    }
+
+    /// Load the last check; a missing or unreadable file counts as never checked
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    /// Whether the last check is more than [`CHECK_INTERVAL_DAYS`] old
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.checked_at
+            .is_none_or(|at| now - at >= Duration::days(CHECK_INTERVAL_DAYS) || at > now)
+    }
+
+    /// The version found by the last check, if it is newer than this build
+    pub fn newer_version(&self) -> Option<&str> {
+        self.latest
+            .as_deref()
+            .filter(|latest| is_newer(latest, CURRENT_VERSION))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    #[test]
+    fn test_version_ordering() {
+        assert!(is_newer("0.2.0", "0.1.0"));
+        assert!(is_newer("v1.0", "0.9.9"));
+        assert!(is_newer("0.1.10", "0.1.9"));
+        assert!(is_newer("1.2.0", "1.2.0-beta.1"));
+        assert!(!is_newer("1.2.0-beta.1", "1.2.0"));
+        assert!(!is_newer("0.1.0", "0.1.0"));
+        assert!(!is_newer("nightly", "0.1.0"));
+        assert!(!is_newer("1.2.3.4", "0.1.0"));
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let rng = SystemRandom::new();
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let key = pair.public_key().as_ref();
+        let binary = b"\x7fELF pretend binary";
+        let signature = pair.sign(binary);
+
+        assert!(verify_with(key, binary, signature.as_ref()).is_ok());
+        let encoded = base64::engine::general_purpose::STANDARD.encode(signature.as_ref());
+        assert!(verify_with(key, binary, format!("{}\n", encoded).as_bytes()).is_ok());
+
+        assert!(verify_with(key, b"tampered", signature.as_ref()).is_err());
+        assert!(verify_with(key, binary, b"not a signature").is_err());
+        // Signed with a different key than the one built in, if any
+        assert!(verify(binary, signature.as_ref()).is_err());
+    }
+
+    #[test]
+    fn test_check_is_due_weekly() {
+        let now = Utc::now();
+        assert!(UpdateCheck::default().is_due(now));
+        let recent = UpdateCheck {
+            checked_at: Some(now - Duration::days(2)),
+            latest: Some("999.0.0".to_string()),
+        };
+        assert!(!recent.is_due(now));
+        assert_eq!(recent.newer_version(), Some("999.0.0"));
+        let old = UpdateCheck {
+            checked_at: Some(now - Duration::days(8)),
+            latest: Some(CURRENT_VERSION.to_string()),
+        };
+        assert!(old.is_due(now));
+        assert_eq!(old.newer_version(), None);
+    }
+}
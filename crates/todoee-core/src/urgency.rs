@@ -0,0 +1,130 @@
+//! Urgency score
+//!
+//! One number for how much a todo needs attention, worked out the way
+//! taskwarrior does it: a sum of weighted terms, each between zero and its
+//! coefficient.
+//!
+//! - due: 12 times a factor from 0.2, for two weeks or more ahead, rising
+//!   to 1.0 a week overdue; nothing without a due date
+//! - priority: the `[ranking]` weight divided by 25, by default 6 urgent,
+//!   4 high, 2 medium and 0.4 low
+//! - age: up to 2, reached a year after the todo was created
+//! - category: 1 with a category (a tag in filter queries)
+//! - project: 1 in a project
+//!
+//! Completed todos score 0.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::config::RankingConfig;
+use crate::models::Todo;
+
+pub const DUE_COEFFICIENT: f64 = 12.0;
+pub const AGE_COEFFICIENT: f64 = 2.0;
+pub const CATEGORY_COEFFICIENT: f64 = 1.0;
+pub const PROJECT_COEFFICIENT: f64 = 1.0;
+
+/// Ranking weight that makes one point of urgency
+const WEIGHT_PER_POINT: f64 = 25.0;
+
+/// Age at which the age term is full
+const MAX_AGE_DAYS: f64 = 365.0;
+
+/// The terms of a todo's urgency
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+pub struct Urgency {
+    pub due: f64,
+    pub priority: f64,
+    pub age: f64,
+    pub category: f64,
+    pub project: f64,
+}
+
+impl Urgency {
+    pub fn of(todo: &Todo, ranking: &RankingConfig, now: DateTime<Utc>) -> Self {
+        if todo.is_completed {
+            return Self::default();
+        }
+        let age_days = (now - todo.created_at).num_seconds() as f64 / 86_400.0;
+        Self {
+            due: todo
+                .due_date
+                .map_or(0.0, |due| DUE_COEFFICIENT * due_factor(due, now)),
+            priority: ranking.weight(todo.priority) as f64 / WEIGHT_PER_POINT,
+            age: AGE_COEFFICIENT * (age_days / MAX_AGE_DAYS).clamp(0.0, 1.0),
+            category: if todo.category_id.is_some() {
+                CATEGORY_COEFFICIENT
+            } else {
+                0.0
+            },
+            project: if todo.project_id.is_some() {
+                PROJECT_COEFFICIENT
+            } else {
+                0.0
+            },
+        }
+    }
+
+    pub fn total(&self) -> f64 {
+        self.due + self.priority + self.age + self.category + self.project
+    }
+}
+
+/// Urgency of `todo` at `now`
+pub fn urgency(todo: &Todo, ranking: &RankingConfig, now: DateTime<Utc>) -> f64 {
+    Urgency::of(todo, ranking, now).total()
+}
+
+/// 0.2 for a due date two weeks or more away, rising linearly to 1.0 once it
+/// is a week overdue
+fn due_factor(due: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+    let days_overdue = (now - due).num_seconds() as f64 / 86_400.0;
+    if days_overdue >= 7.0 {
+        1.0
+    } else if days_overdue >= -14.0 {
+        (days_overdue + 14.0) * 0.8 / 21.0 + 0.2
+    } else {
+        0.2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Priority;
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_urgency_terms() {
+        let now = Utc::now();
+        let ranking = RankingConfig::default();
+        let mut todo = Todo::new("Report".to_string(), None);
+        todo.created_at = now;
+        todo.priority = Priority::High;
+        assert_eq!(urgency(&todo, &ranking, now), 4.0);
+
+        // Due now is two thirds of the way from 0.2 to 1.0
+        todo.due_date = Some(now);
+        let due = Urgency::of(&todo, &ranking, now).due;
+        assert!((due - 12.0 * (14.0 * 0.8 / 21.0 + 0.2)).abs() < 1e-9);
+        todo.due_date = Some(now - Duration::days(30));
+        assert_eq!(Urgency::of(&todo, &ranking, now).due, 12.0);
+        todo.due_date = Some(now + Duration::days(60));
+        assert!((Urgency::of(&todo, &ranking, now).due - 2.4).abs() < 1e-9);
+
+        todo.created_at = now - Duration::days(730);
+        todo.category_id = Some(Uuid::new_v4());
+        todo.project_id = Some(Uuid::new_v4());
+        let terms = Urgency::of(&todo, &ranking, now);
+        assert_eq!((terms.age, terms.category, terms.project), (2.0, 1.0, 1.0));
+
+        let mut urgent = todo.clone();
+        urgent.priority = Priority::Urgent;
+        assert!(urgency(&urgent, &ranking, now) > urgency(&todo, &ranking, now));
+
+        todo.mark_complete();
+        assert_eq!(urgency(&todo, &ranking, now), 0.0);
+    }
+}
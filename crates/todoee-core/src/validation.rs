@@ -0,0 +1,141 @@
+//! Rules a todo must follow to be saved, enforced by [`LocalDb`](crate::LocalDb)
+//! when todos are created or updated.
+//!
+//! Only what changed is judged, so a todo saved before a rule existed can
+//! still be completed or edited in other ways.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::error::TodoeeError;
+use crate::models::{Priority, Todo};
+
+/// Longest title, in characters
+pub const MAX_TITLE_CHARS: usize = 500;
+
+/// How many years back a due date may be; older ones are taken for typos
+/// such as year 0226
+pub const MAX_PAST_DUE_YEARS: i64 = 10;
+
+fn invalid(field: &'static str, message: impl Into<String>) -> TodoeeError {
+    TodoeeError::Validation {
+        field,
+        message: message.into(),
+    }
+}
+
+/// Check `todo` before it is saved over `previous`, which is `None` for a
+/// new todo.
+pub fn validate_todo(
+    todo: &Todo,
+    previous: Option<&Todo>,
+    now: DateTime<Utc>,
+) -> Result<(), TodoeeError> {
+    if previous.is_none_or(|p| p.title != todo.title) {
+        if todo.title.trim().is_empty() {
+            return Err(invalid("title", "cannot be empty"));
+        }
+        let chars = todo.title.chars().count();
+        if chars > MAX_TITLE_CHARS {
+            return Err(invalid(
+                "title",
+                format!(
+                    "is {} characters long; the limit is {}",
+                    chars, MAX_TITLE_CHARS
+                ),
+            ));
+        }
+    }
+
+    let due_changed = previous.is_none_or(|p| p.due_date != todo.due_date);
+    if due_changed
+        && let Some(due) = todo.due_date
+        && due < now - Duration::days(365 * MAX_PAST_DUE_YEARS)
+    {
+        return Err(invalid(
+            "due date",
+            format!(
+                "{} is more than {} years ago",
+                due.format("%Y-%m-%d"),
+                MAX_PAST_DUE_YEARS
+            ),
+        ));
+    }
+
+    // Only when the due date is set or moved: snoozing may rightly carry a
+    // reminder past it
+    if due_changed
+        && let (Some(reminder), Some(due)) = (todo.reminder_at, todo.due_date)
+        && reminder > due
+    {
+        return Err(invalid(
+            "reminder",
+            format!(
+                "{} is after the due date {}",
+                reminder.format("%Y-%m-%d %H:%M"),
+                due.format("%Y-%m-%d %H:%M")
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Priority from its number: 1 low, 2 medium, 3 high
+pub fn priority(level: i64) -> Result<Priority, TodoeeError> {
+    match level {
+        1 => Ok(Priority::Low),
+        2 => Ok(Priority::Medium),
+        3 => Ok(Priority::High),
+        4 => Ok(Priority::Urgent),
+        _ => Err(invalid(
+            "priority",
+            format!(
+                "{} is not 1 (low), 2 (medium), 3 (high) or 4 (urgent)",
+                level
+            ),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(result: Result<(), TodoeeError>) -> Option<&'static str> {
+        match result {
+            Err(TodoeeError::Validation { field, .. }) => Some(field),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_validate_todo() {
+        let now = Utc::now();
+        let todo = Todo::new("Pay rent".to_string(), None);
+        assert!(validate_todo(&todo, None, now).is_ok());
+
+        let mut bad = todo.clone();
+        bad.title = "  ".to_string();
+        assert_eq!(field(validate_todo(&bad, None, now)), Some("title"));
+        bad.title = "x".repeat(MAX_TITLE_CHARS + 1);
+        assert_eq!(field(validate_todo(&bad, None, now)), Some("title"));
+
+        let mut bad = todo.clone();
+        bad.due_date = Some(now - Duration::days(365 * 11));
+        assert_eq!(field(validate_todo(&bad, None, now)), Some("due date"));
+        // Already stored that way: only changes are judged
+        assert!(validate_todo(&bad, Some(&bad), now).is_ok());
+
+        let mut snoozed = todo.clone();
+        snoozed.due_date = Some(now + Duration::hours(1));
+        snoozed.reminder_at = Some(now + Duration::hours(2));
+        assert_eq!(field(validate_todo(&snoozed, None, now)), Some("reminder"));
+        let mut before = snoozed.clone();
+        before.reminder_at = Some(now);
+        assert!(validate_todo(&snoozed, Some(&before), now).is_ok());
+
+        assert_eq!(priority(3).unwrap(), Priority::High);
+        assert_eq!(priority(4).unwrap(), Priority::Urgent);
+        assert!(priority(5).is_err());
+    }
+}
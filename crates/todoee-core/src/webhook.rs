@@ -0,0 +1,357 @@
+//! Webhooks: a JSON POST to configured URLs when a todo is created,
+//! completed, deleted or becomes overdue.
+//!
+//! The daemon reads events from the operation log and from due dates, and
+//! queues one delivery per subscribed URL in an [`Outbox`]. A delivery that
+//! fails is retried with exponential backoff, [`MAX_ATTEMPTS`] times in all.
+//!
+//! Payloads carry `text` and `content` summaries besides the todo itself, so
+//! Slack and Discord incoming webhooks can take them as they are.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::error::TodoeeError;
+use crate::models::{EntityType, Operation, OperationType, Todo};
+
+/// Attempts at a delivery before it is dropped
+pub const MAX_ATTEMPTS: u32 = 5;
+
+/// Wait before the first retry; doubled for each one after
+const FIRST_RETRY_SECS: i64 = 30;
+
+/// Longest wait between attempts
+const MAX_RETRY_SECS: i64 = 3600;
+
+/// Something that happened to a todo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookEvent {
+    Created,
+    Completed,
+    Overdue,
+    Deleted,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 4] = [
+        WebhookEvent::Created,
+        WebhookEvent::Completed,
+        WebhookEvent::Overdue,
+        WebhookEvent::Deleted,
+    ];
+
+    /// What the summary line says happened, e.g. "Completed"
+    fn verb(self) -> &'static str {
+        match self {
+            WebhookEvent::Created => "Created",
+            WebhookEvent::Completed => "Completed",
+            WebhookEvent::Overdue => "Overdue",
+            WebhookEvent::Deleted => "Deleted",
+        }
+    }
+}
+
+impl fmt::Display for WebhookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.verb().to_lowercase())
+    }
+}
+
+impl FromStr for WebhookEvent {
+    type Err = TodoeeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "created" => Ok(WebhookEvent::Created),
+            "completed" => Ok(WebhookEvent::Completed),
+            "overdue" => Ok(WebhookEvent::Overdue),
+            "deleted" => Ok(WebhookEvent::Deleted),
+            other => Err(TodoeeError::InvalidInput(format!(
+                "Unknown webhook event '{}'. Use created, completed, overdue or deleted",
+                other
+            ))),
+        }
+    }
+}
+
+/// A URL and the events posted to it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Webhook {
+    pub url: String,
+    #[serde(default = "all_events")]
+    pub events: Vec<WebhookEvent>,
+}
+
+impl Webhook {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            events: all_events(),
+        }
+    }
+
+    pub fn subscribes(&self, event: WebhookEvent) -> bool {
+        self.events.contains(&event)
+    }
+}
+
+fn all_events() -> Vec<WebhookEvent> {
+    WebhookEvent::ALL.to_vec()
+}
+
+/// Parse a comma-separated event list; empty or "all" is every event
+pub fn parse_events(value: &str) -> Result<Vec<WebhookEvent>, TodoeeError> {
+    let value = value.trim();
+    if value.is_empty() || value.eq_ignore_ascii_case("all") {
+        return Ok(all_events());
+    }
+    let mut events = Vec::new();
+    for name in value.split(',') {
+        let event: WebhookEvent = name.parse()?;
+        if !events.contains(&event) {
+            events.push(event);
+        }
+    }
+    Ok(events)
+}
+
+/// The body posted for `event` on `todo`
+pub fn payload(event: WebhookEvent, todo: &Todo, at: DateTime<Utc>) -> Value {
+    let text = format!("{}: {}", event.verb(), todo.display_title());
+    json!({
+        "event": event,
+        "at": at,
+        "text": text,
+        "content": text,
+        "todo": todo,
+    })
+}
+
+/// The events recorded in `operations`, in the order given
+pub fn events_from_operations(operations: &[Operation]) -> Vec<(WebhookEvent, Todo)> {
+    let todo = |state: &Option<Value>| {
+        state
+            .clone()
+            .and_then(|state| serde_json::from_value::<Todo>(state).ok())
+    };
+    let mut events = Vec::new();
+    for op in operations {
+        if op.entity_type != EntityType::Todo {
+            continue;
+        }
+        match op.operation_type {
+            OperationType::Create => {
+                events.extend(todo(&op.new_state).map(|t| (WebhookEvent::Created, t)))
+            }
+            OperationType::BatchCreate => events.extend(
+                op.batch_todos()
+                    .into_iter()
+                    .map(|t| (WebhookEvent::Created, t)),
+            ),
+            OperationType::Complete => {
+                events.extend(todo(&op.new_state).map(|t| (WebhookEvent::Completed, t)))
+            }
+            OperationType::Delete => {
+                events.extend(todo(&op.previous_state).map(|t| (WebhookEvent::Deleted, t)))
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+/// Open todos whose due date passed after `since`, up to `now`
+pub fn newly_overdue(todos: &[Todo], since: DateTime<Utc>, now: DateTime<Utc>) -> Vec<&Todo> {
+    todos
+        .iter()
+        .filter(|t| !t.is_completed && t.due_date.is_some_and(|due| due > since && due <= now))
+        .collect()
+}
+
+/// One payload on its way to one URL
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delivery {
+    pub url: String,
+    pub body: Value,
+    pub attempts: u32,
+    /// When to try next
+    pub next_at: DateTime<Utc>,
+}
+
+impl Delivery {
+    /// Count a failed attempt at `now` and schedule the next one. Returns
+    /// false once the delivery has used all its attempts.
+    pub fn failed(&mut self, now: DateTime<Utc>) -> bool {
+        self.attempts += 1;
+        if self.attempts >= MAX_ATTEMPTS {
+            return false;
+        }
+        let wait = (FIRST_RETRY_SECS << (self.attempts - 1)).min(MAX_RETRY_SECS);
+        self.next_at = now + Duration::seconds(wait);
+        true
+    }
+}
+
+/// Deliveries not yet accepted by their URL
+#[derive(Debug, Default)]
+pub struct Outbox {
+    pending: Vec<Delivery>,
+}
+
+impl Outbox {
+    /// Queue `event` for every hook subscribed to it
+    pub fn queue(
+        &mut self,
+        hooks: &BTreeMap<String, Webhook>,
+        event: WebhookEvent,
+        todo: &Todo,
+        now: DateTime<Utc>,
+    ) {
+        let body = payload(event, todo, now);
+        for hook in hooks.values().filter(|h| h.subscribes(event)) {
+            self.pending.push(Delivery {
+                url: hook.url.clone(),
+                body: body.clone(),
+                attempts: 0,
+                next_at: now,
+            });
+        }
+    }
+
+    pub fn pending(&self) -> &[Delivery] {
+        &self.pending
+    }
+
+    /// Post every delivery that is due. Returns how many were accepted, and
+    /// a message for each one that failed.
+    pub async fn flush(
+        &mut self,
+        client: &reqwest::Client,
+        now: DateTime<Utc>,
+    ) -> (u64, Vec<String>) {
+        let mut sent = 0;
+        let mut errors = Vec::new();
+        let mut kept = Vec::new();
+        for mut delivery in std::mem::take(&mut self.pending) {
+            if delivery.next_at > now {
+                kept.push(delivery);
+                continue;
+            }
+            let result = client
+                .post(&delivery.url)
+                .json(&delivery.body)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            match result {
+                Ok(_) => sent += 1,
+                Err(e) if delivery.failed(now) => {
+                    errors.push(format!(
+                        "Webhook {} failed (attempt {} of {}), retrying at {}: {}",
+                        delivery.url,
+                        delivery.attempts,
+                        MAX_ATTEMPTS,
+                        delivery.next_at.format("%H:%M:%S"),
+                        e
+                    ));
+                    kept.push(delivery);
+                }
+                Err(e) => errors.push(format!(
+                    "Webhook {} failed {} times, giving up: {}",
+                    delivery.url, MAX_ATTEMPTS, e
+                )),
+            }
+        }
+        self.pending = kept;
+        (sent, errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_events_and_retries() {
+        assert_eq!(parse_events("").unwrap(), WebhookEvent::ALL);
+        assert_eq!(
+            parse_events("Completed, overdue,completed").unwrap(),
+            [WebhookEvent::Completed, WebhookEvent::Overdue]
+        );
+        assert!(parse_events("completed,edited").is_err());
+
+        let now = Utc::now();
+        let mut todo = Todo::new("Buy milk".to_string(), None);
+        let created = Operation::new(
+            OperationType::Create,
+            EntityType::Todo,
+            todo.id,
+            None,
+            serde_json::to_value(&todo).ok(),
+        );
+        let prev = serde_json::to_value(&todo).ok();
+        todo.mark_complete();
+        let completed = Operation::new(
+            OperationType::Complete,
+            EntityType::Todo,
+            todo.id,
+            prev,
+            serde_json::to_value(&todo).ok(),
+        );
+        let events: Vec<_> = events_from_operations(&[created, completed])
+            .into_iter()
+            .map(|(event, t)| (event, t.is_completed))
+            .collect();
+        assert_eq!(
+            events,
+            [
+                (WebhookEvent::Created, false),
+                (WebhookEvent::Completed, true)
+            ]
+        );
+
+        let mut late = Todo::new("Report".to_string(), None);
+        late.due_date = Some(now - Duration::seconds(30));
+        let todos = [late, todo];
+        assert_eq!(
+            newly_overdue(&todos, now - Duration::minutes(1), now).len(),
+            1
+        );
+        assert!(newly_overdue(&todos, now - Duration::seconds(10), now).is_empty());
+
+        let hooks = BTreeMap::from([
+            (
+                "slack".to_string(),
+                Webhook {
+                    url: "https://hooks.example.com/a".to_string(),
+                    events: vec![WebhookEvent::Overdue],
+                },
+            ),
+            (
+                "log".to_string(),
+                Webhook::new("https://hooks.example.com/b".to_string()),
+            ),
+        ]);
+        let mut outbox = Outbox::default();
+        outbox.queue(&hooks, WebhookEvent::Completed, &todos[1], now);
+        assert_eq!(outbox.pending().len(), 1);
+        let mut delivery = outbox.pending()[0].clone();
+        assert_eq!(delivery.body["text"], "Completed: Buy milk");
+        assert_eq!(delivery.body["event"], "completed");
+
+        let waits: Vec<i64> = (1..MAX_ATTEMPTS)
+            .map(|_| {
+                assert!(delivery.failed(now));
+                (delivery.next_at - now).num_seconds()
+            })
+            .collect();
+        assert_eq!(waits, [30, 60, 120, 240]);
+        assert!(!delivery.failed(now), "gives up after the last attempt");
+    }
+}
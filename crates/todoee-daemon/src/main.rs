@@ -1,13 +1,34 @@
 use std::collections::HashSet;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
 use notify_rust::Notification;
-use todoee_core::{config::Config, db::LocalDb};
+use todoee_core::daemon_status::CHECK_INTERVAL_SECS;
+use todoee_core::{
+    AiClient, DaemonStatus, DailySummary, DueUrgency, EntityType, Operation, OperationType, Todo,
+    TodoeeError,
+    config::Config,
+    db::LocalDb,
+    gc, net,
+    webhook::{self, Outbox, WebhookEvent},
+};
+use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
 use tokio::time::interval;
 use uuid::Uuid;
 
-const CHECK_INTERVAL_SECS: u64 = 60; // Check every minute
+const SNOOZE_MINUTES: i64 = 10;
+const AI_RETRY_LIMIT: u32 = 5; // Failed parses before a queued one is dropped
+const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+const COMPACT_INTERVAL_SECS: u64 = 3600; // Undo history compaction
+const MISSED_REMINDER_HOURS: i64 = 24; // Reminders missed while stopped are still sent
+
+/// Button clicked on a reminder notification
+#[derive(Debug, Clone, Copy)]
+enum ReminderAction {
+    Snooze(Uuid),
+    Done(Uuid),
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -15,13 +36,20 @@ async fn main() -> Result<()> {
 
     let config = Config::load()?;
 
-    if !config.notifications.enabled {
-        println!("Notifications are disabled in config. Exiting.");
+    if !config.notifications.enabled
+        && !config.ai.retry_offline
+        && !config.gc.auto
+        && !config.history.auto_compact
+        && config.webhooks.is_empty()
+    {
+        println!(
+            "Notifications, offline AI retries, automatic cleanup, history compaction and webhooks are disabled in config. Exiting."
+        );
         return Ok(());
     }
 
     let db_path = config.local_db_path()?;
-    let db = LocalDb::new(&db_path).await?;
+    let db = LocalDb::with_pool_size(&db_path, config.database.pool_size).await?;
     db.run_migrations().await?;
 
     println!(
@@ -30,47 +58,527 @@ async fn main() -> Result<()> {
     );
 
     let mut ticker = interval(Duration::from_secs(CHECK_INTERVAL_SECS));
-    let mut sent_reminders: HashSet<Uuid> = HashSet::new();
+    let mut sent_event_reminders: HashSet<(Uuid, DateTime<Utc>)> = HashSet::new();
+    let (action_tx, mut action_rx) = unbounded_channel();
+    let mut status = DaemonStatus::new(std::process::id(), Utc::now());
+    // Events from before the daemon started are not posted
+    let mut webhooks_checked = Utc::now();
+    let mut outbox = Outbox::default();
+    let mut last_compaction: Option<Instant> = None;
+    let webhook_client = if config.webhooks.is_empty() {
+        None
+    } else {
+        Some(net::http_client(
+            &config.network,
+            Duration::from_secs(WEBHOOK_TIMEOUT_SECS),
+        )?)
+    };
 
     loop {
-        ticker.tick().await;
+        tokio::select! {
+            _ = ticker.tick() => {
+                let mut errors = Vec::new();
+                if config.notifications.enabled {
+                    match check_and_notify(&db, &config, &action_tx).await {
+                        Ok(sent) => status.reminders_sent += sent,
+                        Err(e) => errors.push(format!("Error checking reminders: {}", e)),
+                    }
+
+                    match check_events_and_notify(&db, &config, &mut sent_event_reminders).await {
+                        Ok(sent) => status.reminders_sent += sent,
+                        Err(e) => errors.push(format!("Error checking event reminders: {}", e)),
+                    }
+
+                    match check_due_and_notify(&db, &config).await {
+                        Ok(sent) => status.reminders_sent += sent,
+                        Err(e) => errors.push(format!("Error checking due dates: {}", e)),
+                    }
+
+                    match nag_overdue(&db, &config, &action_tx).await {
+                        Ok(sent) => status.reminders_sent += sent,
+                        Err(e) => errors.push(format!("Error checking overdue todos: {}", e)),
+                    }
+
+                    if let Err(e) = send_daily_summary_if_due(&db, &config).await {
+                        errors.push(format!("Error sending the daily agenda: {}", e));
+                    }
+                }
+
+                if config.ai.retry_offline
+                    && let Err(e) = retry_ai_parses(&db, &config).await
+                {
+                    errors.push(format!("Error retrying AI parses: {}", e));
+                }
+
+                if config.gc.auto
+                    && let Err(e) = run_gc_if_due(&db, &config).await
+                {
+                    errors.push(format!("Error cleaning up old data: {}", e));
+                }
 
-        if let Err(e) = check_and_notify(&db, &config, &mut sent_reminders).await {
-            eprintln!("Error checking reminders: {}", e);
+                if config.history.auto_compact
+                    && let Err(e) = compact_history_if_due(&db, &config, &mut last_compaction).await
+                {
+                    errors.push(format!("Error compacting undo history: {}", e));
+                }
+
+                if let Some(client) = &webhook_client {
+                    if let Err(e) =
+                        queue_webhooks(&db, &config, &mut outbox, &mut webhooks_checked).await
+                    {
+                        errors.push(format!("Error checking webhook events: {}", e));
+                    }
+                    let (sent, failures) = outbox.flush(client, Utc::now()).await;
+                    if sent > 0 {
+                        println!("Posted {} webhook{}", sent, if sent == 1 { "" } else { "s" });
+                    }
+                    errors.extend(failures);
+                }
+
+                let now = Utc::now();
+                for error in errors {
+                    eprintln!("{}", error);
+                    status.record_error(now, error);
+                }
+                match db.last_sync_run().await {
+                    Ok(run) => status.last_sync = run.map(|r| r.finished_at),
+                    Err(e) => eprintln!("Error reading last sync: {}", e),
+                }
+                status.last_check = Some(now);
+                if let Err(e) = status.save() {
+                    eprintln!("Error writing daemon status: {}", e);
+                }
+            }
+            Some(action) = action_rx.recv() => {
+                if let Err(e) = handle_action(&db, action).await {
+                    eprintln!("Error handling reminder action: {}", e);
+                }
+            }
         }
     }
 }
 
-async fn check_and_notify(
+/// Clean up old data once per `gc.interval`, shared with the CLI's runs
+async fn run_gc_if_due(db: &LocalDb, config: &Config) -> Result<()> {
+    let config_dir = Config::config_dir()?;
+    if let Some(summary) = gc::auto_collect(db, &config.gc, &config_dir, Utc::now()).await? {
+        println!("Automatic cleanup removed {}", summary);
+    }
+    Ok(())
+}
+
+/// Compact and cap undo history once per hour, starting at startup
+async fn compact_history_if_due(
     db: &LocalDb,
     config: &Config,
-    sent_reminders: &mut HashSet<Uuid>,
+    last_run: &mut Option<Instant>,
 ) -> Result<()> {
+    if last_run.is_some_and(|at| at.elapsed() < Duration::from_secs(COMPACT_INTERVAL_SECS)) {
+        return Ok(());
+    }
+    *last_run = Some(Instant::now());
+    let summary = gc::compact_history(db, &config.history).await?;
+    if !summary.is_empty() {
+        println!("Compacted undo history: {}", summary);
+    }
+    Ok(())
+}
+
+/// Queue webhook deliveries for todos created, completed or deleted, and due
+/// dates passed, since `since`, which moves up to now.
+async fn queue_webhooks(
+    db: &LocalDb,
+    config: &Config,
+    outbox: &mut Outbox,
+    since: &mut DateTime<Utc>,
+) -> Result<()> {
+    let now = Utc::now();
+    let mut operations: Vec<Operation> = db
+        .list_operations_since(*since)
+        .await?
+        .into_iter()
+        .filter(|op| op.created_at > *since && op.created_at <= now)
+        .collect();
+    operations.sort_by_key(|op| op.created_at);
+    for (event, todo) in webhook::events_from_operations(&operations) {
+        outbox.queue(&config.webhooks, event, &todo, now);
+    }
+
+    let todos = db.list_todos(true).await?;
+    for todo in webhook::newly_overdue(&todos, *since, now) {
+        outbox.queue(&config.webhooks, WebhookEvent::Overdue, todo, now);
+    }
+
+    *since = now;
+    Ok(())
+}
+
+/// Notify once per reminder time. Todo reminders fire at `reminder_at` itself
+/// (the advance window only applies to events), so a short snooze is honoured.
+/// With `notifications.derive_reminders`, todos without one are reminded
+/// ahead of their due time instead.
+///
+/// Sent reminders are recorded in the todos' `notified_at`, so a restart
+/// neither repeats them nor skips those that came round while stopped.
+async fn check_and_notify(
+    db: &LocalDb,
+    config: &Config,
+    actions: &UnboundedSender<ReminderAction>,
+) -> Result<u64> {
+    let now = Utc::now();
+    let catch_up = chrono::Duration::hours(MISSED_REMINDER_HOURS);
+    let mut todos = db.list_reminders_to_send(now, catch_up).await?;
+
+    if config.notifications.derive_reminders {
+        let due = db
+            .list_todos_due_within(config.notifications.longest_advance())
+            .await?;
+        todos.extend(due.into_iter().filter(|todo| {
+            // The latest derived reminder that has come round, if not already sent
+            config
+                .notifications
+                .derived_reminders(todo)
+                .into_iter()
+                .filter(|at| *at <= now && *at > now - catch_up)
+                .max()
+                .is_some_and(|at| todo.notified_at.is_none_or(|n| n < at))
+        }));
+    }
+
+    let titles: Vec<String> = todos.iter().map(|t| t.display_title()).collect();
+    match config.notifications.batch_body("reminders", &titles) {
+        // One summary without buttons; each todo is still snoozed or done by hand
+        Some(body) => send_notification("Todoee Reminders", &body, config)?,
+        None => {
+            for todo in &todos {
+                send_reminder("Todoee Reminder", todo, config, actions)?;
+            }
+        }
+    }
+
+    let ids: Vec<Uuid> = todos.iter().map(|t| t.id).collect();
+    db.mark_notified(&ids, now).await?;
+    Ok(titles.len() as u64)
+}
+
+/// Notify again about overdue high and urgent todos every
+/// `notifications.nag_minutes` until they are done or snoozed. Todos added
+/// with `--no-nag` are left alone.
+async fn nag_overdue(
+    db: &LocalDb,
+    config: &Config,
+    actions: &UnboundedSender<ReminderAction>,
+) -> Result<u64> {
+    if config.notifications.nag_minutes == 0 {
+        return Ok(0);
+    }
+    let now = Utc::now();
+    let todos: Vec<Todo> = db
+        .list_todos_overdue()
+        .await?
+        .into_iter()
+        .filter(|t| config.notifications.nags(t, now))
+        .collect();
+
+    let titles: Vec<String> = todos.iter().map(|t| t.display_title()).collect();
+    match config.notifications.batch_body("tasks overdue", &titles) {
+        Some(body) => send_notification("Todoee Overdue", &body, config)?,
+        None => {
+            for todo in &todos {
+                send_reminder("Todoee Overdue", todo, config, actions)?;
+            }
+        }
+    }
+
+    let ids: Vec<Uuid> = todos.iter().map(|t| t.id).collect();
+    db.mark_notified(&ids, now).await?;
+    Ok(titles.len() as u64)
+}
+
+/// Send the day's agenda once a day, at `notifications.daily_summary_time`
+/// or, if the daemon wasn't running then, as soon as it is later that day.
+async fn send_daily_summary_if_due(db: &LocalDb, config: &Config) -> Result<()> {
+    let Some(at) = config.notifications.daily_summary_time() else {
+        return Ok(());
+    };
+    let now = Local::now();
+    let today = now.date_naive();
+    if now.time() < at || db.daily_summary_sent_on().await? == Some(today) {
+        return Ok(());
+    }
+
+    let todos = db.list_todos(true).await?;
+    let summary = DailySummary::new(&todos, &config.ranking, now.to_utc(), &Local);
+    send_notification("Todoee Agenda", &summary.lines().join("\n"), config)?;
+    db.record_daily_summary(today).await?;
+    Ok(())
+}
+
+/// Apply a notification button click and record it for undo.
+async fn handle_action(db: &LocalDb, action: ReminderAction) -> Result<()> {
+    let id = match action {
+        ReminderAction::Snooze(id) | ReminderAction::Done(id) => id,
+    };
+    let Some(mut todo) = db.get_todo(id).await? else {
+        return Ok(());
+    };
+    if todo.is_completed {
+        return Ok(());
+    }
+
+    let prev_state = serde_json::to_value(&todo)?;
+    let operation_type = match action {
+        ReminderAction::Snooze(_) => {
+            let until = todo.snooze(chrono::Duration::minutes(SNOOZE_MINUTES), Utc::now());
+            println!(
+                "Snoozed until {}: {}",
+                until.with_timezone(&Local).format("%H:%M"),
+                todo.title
+            );
+            OperationType::Update
+        }
+        ReminderAction::Done(_) => {
+            todo.mark_complete();
+            println!("Completed: {}", todo.title);
+            OperationType::Complete
+        }
+    };
+
+    db.update_todo(&todo).await?;
+
+    let op = Operation::new(
+        operation_type,
+        EntityType::Todo,
+        todo.id,
+        Some(prev_state),
+        Some(serde_json::to_value(&todo)?),
+    );
+    db.record_operation(&op).await?;
+
+    Ok(())
+}
+
+/// Re-run AI parses queued by `todoee add --ai` while offline.
+///
+/// Stops at the first network failure; the rest wait for the next tick. A
+/// todo edited, completed or deleted since it was queued is left as it is.
+async fn retry_ai_parses(db: &LocalDb, config: &Config) -> Result<()> {
+    let pending = db.list_pending_ai_parses().await?;
+    if pending.is_empty() {
+        return Ok(());
+    }
+    let client = AiClient::new(config)?;
+
+    for item in pending {
+        let todo = db.get_todo(item.todo_id).await?;
+        let Some(mut todo) = todo.filter(|t| !t.is_completed && t.updated_at <= item.queued_at)
+        else {
+            db.remove_pending_ai_parse(item.todo_id).await?;
+            continue;
+        };
+
+        match client.parse_task_cached(db, &item.input).await {
+            Ok(parsed) => {
+                let prev_state = serde_json::to_value(&todo)?;
+                todo.apply_ai_parse(&parsed, &item.input);
+                db.update_todo(&todo).await?;
+
+                let op = Operation::new(
+                    OperationType::Update,
+                    EntityType::Todo,
+                    todo.id,
+                    Some(prev_state),
+                    Some(serde_json::to_value(&todo)?),
+                );
+                db.record_operation(&op).await?;
+                db.remove_pending_ai_parse(todo.id).await?;
+                println!("AI parsed: {}", todo.title);
+            }
+            Err(TodoeeError::Network(_)) => break,
+            Err(e) => {
+                eprintln!("AI parse failed for '{}': {}", item.input, e);
+                if item.attempts + 1 >= AI_RETRY_LIMIT {
+                    db.remove_pending_ai_parse(todo.id).await?;
+                } else {
+                    db.record_ai_parse_attempt(todo.id).await?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Notify once per event occurrence starting within the advance window.
+async fn check_events_and_notify(
+    db: &LocalDb,
+    config: &Config,
+    sent_reminders: &mut HashSet<(Uuid, DateTime<Utc>)>,
+) -> Result<u64> {
     let window = chrono::Duration::minutes(config.notifications.advance_minutes as i64);
+    let occurrences = db.list_events_starting_within(window).await?;
+
+    let mut bodies = Vec::new();
+    for (event, start) in &occurrences {
+        if !sent_reminders.insert((event.id, *start)) {
+            continue;
+        }
+        let local = start.with_timezone(&Local);
+        bodies.push(format!("{} at {}", event.title, local.format("%H:%M")));
+    }
+    match config.notifications.batch_body("events starting", &bodies) {
+        Some(body) => send_notification("Todoee Events", &body, config)?,
+        None => {
+            for body in &bodies {
+                send_notification("Todoee Event", body, config)?;
+            }
+        }
+    }
+
+    // Forget occurrences that have left the window
+    sent_reminders.retain(|key| occurrences.iter().any(|(e, s)| (e.id, *s) == *key));
+
+    Ok(bodies.len() as u64)
+}
 
-    // Use the optimized query instead of filtering in Rust
-    let todos = db.list_todos_with_reminders_due(window).await?;
+/// Notify once per due-date alert that has come round in the last 5 minutes
+/// and since the todo was last notified about.
+/// Alerts are set per priority in config, or per todo with `--remind-before`.
+/// Those of todos given derived reminders are sent by [`check_and_notify`].
+async fn check_due_and_notify(db: &LocalDb, config: &Config) -> Result<u64> {
+    let now = Utc::now();
+    let recent = now - chrono::Duration::minutes(5);
 
+    // Look as far ahead as the longest advance anyone could have asked for
+    let todos = db
+        .list_todos_due_within(config.notifications.longest_advance())
+        .await?;
+
+    // (summary, body, title) of each alert to send this time
+    let mut alerts: Vec<(&str, String, String)> = Vec::new();
+    let mut notified = Vec::new();
     for todo in &todos {
-        if sent_reminders.contains(&todo.id) {
+        let Some(due) = todo.due_date else {
+            continue;
+        };
+        if !config.notifications.derived_reminders(todo).is_empty() {
             continue;
         }
+        // Only the latest alert that has come round, if not already sent
+        let Some(at) = config
+            .notifications
+            .due_alerts(todo)
+            .into_iter()
+            .filter(|at| *at <= now && *at > recent)
+            .max()
+        else {
+            continue;
+        };
+        if todo.notified_at.is_some_and(|n| n >= at) {
+            continue;
+        }
+        notified.push(todo.id);
+        let local = due.with_timezone(&Local);
+        let days = (local.date_naive() - now.with_timezone(&Local).date_naive()).num_days();
+        let body = match days {
+            _ if at == due => format!("{} is due now", todo.display_title()),
+            0 => format!(
+                "{} is due at {}",
+                todo.display_title(),
+                local.format("%H:%M")
+            ),
+            1 => format!(
+                "{} is due tomorrow at {}",
+                todo.display_title(),
+                local.format("%H:%M")
+            ),
+            d => format!(
+                "{} is due in {} days ({})",
+                todo.display_title(),
+                d,
+                local.format("%a %H:%M")
+            ),
+        };
+        // Far-off alerts, from a long advance notice, say so
+        let summary = match config.display.due_urgency(&due, now) {
+            DueUrgency::Overdue | DueUrgency::Urgent => "Todoee Due",
+            DueUrgency::Soon => "Todoee Due Soon",
+            DueUrgency::Later => "Todoee Upcoming",
+        };
+        alerts.push((summary, body, todo.display_title()));
+    }
 
-        send_notification(&todo.title, config)?;
-        sent_reminders.insert(todo.id);
+    let titles: Vec<String> = alerts.iter().map(|(_, _, title)| title.clone()).collect();
+    match config.notifications.batch_body("tasks due", &titles) {
+        Some(body) => send_notification("Todoee Due", &body, config)?,
+        None => {
+            for (summary, body, _) in &alerts {
+                send_notification(summary, body, config)?;
+            }
+        }
     }
 
-    // Cleanup sent_reminders for todos no longer in the result
-    sent_reminders.retain(|id| todos.iter().any(|t| &t.id == id));
+    db.mark_notified(&notified, now).await?;
+    Ok(alerts.len() as u64)
+}
+
+/// Send a todo reminder with "Snooze" and "Mark done" buttons.
+///
+/// Clicks are delivered on `actions` from a blocking task that waits on the
+/// notification, so the check loop is never held up.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn send_reminder(
+    summary: &str,
+    todo: &Todo,
+    config: &Config,
+    actions: &UnboundedSender<ReminderAction>,
+) -> Result<()> {
+    let mut notification = build_notification(summary, &todo.display_title(), config);
+    notification
+        .action("snooze", &format!("Snooze {}m", SNOOZE_MINUTES))
+        .action("done", "Mark done");
+
+    let handle = notification.show()?;
+    let id = todo.id;
+    let actions = actions.clone();
+    tokio::task::spawn_blocking(move || {
+        handle.wait_for_action(|action| {
+            let action = match action {
+                "snooze" => ReminderAction::Snooze(id),
+                "done" => ReminderAction::Done(id),
+                _ => return,
+            };
+            let _ = actions.send(action);
+        });
+    });
 
+    println!("Sent reminder: {}", todo.title);
     Ok(())
 }
 
-fn send_notification(title: &str, config: &Config) -> Result<()> {
+/// Notification actions are only supported by the XDG backend.
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn send_reminder(
+    summary: &str,
+    todo: &Todo,
+    config: &Config,
+    _actions: &UnboundedSender<ReminderAction>,
+) -> Result<()> {
+    send_notification(summary, &todo.display_title(), config)
+}
+
+fn send_notification(summary: &str, title: &str, config: &Config) -> Result<()> {
+    build_notification(summary, title, config).show()?;
+
+    println!("Sent reminder: {}", title);
+    Ok(())
+}
+
+fn build_notification(summary: &str, title: &str, config: &Config) -> Notification {
     let mut notification = Notification::new();
 
     notification
-        .summary("Todoee Reminder")
+        .summary(summary)
         .body(title)
         .appname("todoee")
         .timeout(notify_rust::Timeout::Milliseconds(10000));
@@ -79,8 +587,5 @@ fn send_notification(title: &str, config: &Config) -> Result<()> {
         notification.sound_name("message-new-instant");
     }
 
-    notification.show()?;
-
-    println!("Sent reminder: {}", title);
-    Ok(())
+    notification
 }